@@ -17,10 +17,12 @@ use std::fmt;
 use std::mem;
 use std::os::raw::c_char;
 use std::os::raw::c_double;
+use std::os::raw::c_int;
 use std::os::raw::c_ulong;
 use std::os::raw::c_void;
 
 pub type Flags = u32;
+pub type Flags64 = u64;
 pub type Bool32 = u32;
 pub type DeviceSize = u64;
 pub type SampleMask = u32;
@@ -105,6 +107,7 @@ pub const ERROR_VALIDATION_FAILED_EXT: u32 = -1000011001i32 as u32;
 pub const ERROR_INVALID_SHADER_NV: u32 = -1000012000i32 as u32;
 pub const ERROR_OUT_OF_POOL_MEMORY_KHR: u32 = -1000069000i32 as u32;
 pub const ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT: u32 = -1000255000i32 as u32;
+pub const ERROR_INVALID_EXTERNAL_HANDLE: u32 = -1000072003i32 as u32;
 
 pub type StructureType = u32;
 pub const STRUCTURE_TYPE_APPLICATION_INFO: u32 = 0;
@@ -190,6 +193,7 @@ pub const STRUCTURE_TYPE_PRESENT_REGIONS_KHR: u32 = 1000084000;
 pub const STRUCTURE_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_CREATE_INFO_KHR: u32 = 1000085000;
 pub const STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR: u32 = 1000127000;
 pub const STRUCTURE_TYPE_MEMORY_DEDICATED_ALLOCATE_INFO_KHR: u32 = 1000127001;
+pub const STRUCTURE_TYPE_IMAGE_VIEW_USAGE_CREATE_INFO_KHR: u32 = 1000117002;
 pub const STRUCTURE_TYPE_BUFFER_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146000;
 pub const STRUCTURE_TYPE_IMAGE_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146001;
 pub const STRUCTURE_TYPE_IMAGE_SPARSE_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146002;
@@ -203,6 +207,62 @@ pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_ATOMIC_INT64_FEATURES: u32 = 100
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_8BIT_STORAGE_FEATURES: u32 = 1000177000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_16BIT_STORAGE_FEATURES: u32 = 1000083000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SHADER_FLOAT16_INT8_FEATURES: u32 = 1000082000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_FEATURES: u32 = 1000207000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_PROPERTIES: u32 = 1000207001;
+pub const STRUCTURE_TYPE_SEMAPHORE_TYPE_CREATE_INFO: u32 = 1000207002;
+pub const STRUCTURE_TYPE_TIMELINE_SEMAPHORE_SUBMIT_INFO: u32 = 1000207003;
+pub const STRUCTURE_TYPE_SEMAPHORE_WAIT_INFO: u32 = 1000207004;
+pub const STRUCTURE_TYPE_SEMAPHORE_SIGNAL_INFO: u32 = 1000207005;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SEPARATE_DEPTH_STENCIL_LAYOUTS_FEATURES: u32 = 1000241000;
+pub const STRUCTURE_TYPE_ATTACHMENT_REFERENCE_STENCIL_LAYOUT: u32 = 1000241001;
+pub const STRUCTURE_TYPE_ATTACHMENT_DESCRIPTION_STENCIL_LAYOUT: u32 = 1000241002;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_BINDING_FLAGS_CREATE_INFO_EXT: u32 = 1000161000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_FEATURES_EXT: u32 = 1000161001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_PROPERTIES_EXT: u32 = 1000161002;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_VARIABLE_DESCRIPTOR_COUNT_ALLOCATE_INFO_EXT: u32 =
+    1000161003;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_VARIABLE_DESCRIPTOR_COUNT_LAYOUT_SUPPORT_EXT: u32 =
+    1000161004;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SAMPLER_FILTER_MINMAX_PROPERTIES_EXT: u32 = 1000130000;
+pub const STRUCTURE_TYPE_SAMPLER_REDUCTION_MODE_CREATE_INFO_EXT: u32 = 1000130001;
+pub const STRUCTURE_TYPE_MEMORY_ALLOCATE_FLAGS_INFO_KHR: u32 = 1000060000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_GROUP_PROPERTIES_KHR: u32 = 1000070000;
+pub const STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO_KHR: u32 = 1000070001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PIPELINE_EXECUTABLE_PROPERTIES_FEATURES_KHR: u32 =
+    1000269000;
+pub const STRUCTURE_TYPE_PIPELINE_INFO_KHR: u32 = 1000269001;
+pub const STRUCTURE_TYPE_PIPELINE_EXECUTABLE_PROPERTIES_KHR: u32 = 1000269002;
+pub const STRUCTURE_TYPE_PIPELINE_EXECUTABLE_INFO_KHR: u32 = 1000269003;
+pub const STRUCTURE_TYPE_PIPELINE_EXECUTABLE_STATISTIC_KHR: u32 = 1000269004;
+pub const STRUCTURE_TYPE_PIPELINE_EXECUTABLE_INTERNAL_REPRESENTATION_KHR: u32 = 1000269005;
+pub const STRUCTURE_TYPE_PROTECTED_SUBMIT_INFO: u32 = 1000145000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES: u32 = 1000145001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_PROPERTIES: u32 = 1000145002;
+pub const STRUCTURE_TYPE_DEVICE_QUEUE_INFO_2: u32 = 1000145003;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MAINTENANCE_4_FEATURES_KHR: u32 = 1000413000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MAINTENANCE_4_PROPERTIES_KHR: u32 = 1000413001;
+pub const STRUCTURE_TYPE_EXPORT_SEMAPHORE_CREATE_INFO: u32 = 1000077000;
+pub const STRUCTURE_TYPE_IMPORT_SEMAPHORE_WIN32_HANDLE_INFO_KHR: u32 = 1000078000;
+pub const STRUCTURE_TYPE_SEMAPHORE_GET_WIN32_HANDLE_INFO_KHR: u32 = 1000078003;
+pub const STRUCTURE_TYPE_IMPORT_SEMAPHORE_FD_INFO_KHR: u32 = 1000079000;
+pub const STRUCTURE_TYPE_SEMAPHORE_GET_FD_INFO_KHR: u32 = 1000079001;
+pub const STRUCTURE_TYPE_PIPELINE_CREATION_FEEDBACK_CREATE_INFO: u32 = 1000192000;
+pub const STRUCTURE_TYPE_MEMORY_BARRIER_2: u32 = 1000314000;
+pub const STRUCTURE_TYPE_BUFFER_MEMORY_BARRIER_2: u32 = 1000314001;
+pub const STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER_2: u32 = 1000314002;
+pub const STRUCTURE_TYPE_DEPENDENCY_INFO: u32 = 1000314003;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SYNCHRONIZATION_2_FEATURES: u32 = 1000314007;
+pub const STRUCTURE_TYPE_DEVICE_BUFFER_MEMORY_REQUIREMENTS_KHR: u32 = 1000413002;
+pub const STRUCTURE_TYPE_DEVICE_IMAGE_MEMORY_REQUIREMENTS_KHR: u32 = 1000413003;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_FEATURES_EXT: u32 = 1000267000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MUTABLE_DESCRIPTOR_TYPE_FEATURES_EXT: u32 = 1000351000;
+pub const STRUCTURE_TYPE_MUTABLE_DESCRIPTOR_TYPE_CREATE_INFO_EXT: u32 = 1000351002;
+pub const STRUCTURE_TYPE_SAMPLER_CUSTOM_BORDER_COLOR_CREATE_INFO_EXT: u32 = 1000287000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_CUSTOM_BORDER_COLOR_PROPERTIES_EXT: u32 = 1000287001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_CUSTOM_BORDER_COLOR_FEATURES_EXT: u32 = 1000287002;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_INDEX_TYPE_UINT8_FEATURES_EXT: u32 = 1000265000;
+
+pub const MAX_DEVICE_GROUP_SIZE_KHR: usize = 32;
 
 pub type SystemAllocationScope = u32;
 pub const SYSTEM_ALLOCATION_SCOPE_COMMAND: u32 = 0;
@@ -437,6 +497,10 @@ pub const IMAGE_LAYOUT_TRANSFER_SRC_OPTIMAL: u32 = 6;
 pub const IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL: u32 = 7;
 pub const IMAGE_LAYOUT_PREINITIALIZED: u32 = 8;
 pub const IMAGE_LAYOUT_PRESENT_SRC_KHR: u32 = 1000001002;
+pub const IMAGE_LAYOUT_DEPTH_ATTACHMENT_OPTIMAL: u32 = 1000241000;
+pub const IMAGE_LAYOUT_DEPTH_READ_ONLY_OPTIMAL: u32 = 1000241001;
+pub const IMAGE_LAYOUT_STENCIL_ATTACHMENT_OPTIMAL: u32 = 1000241002;
+pub const IMAGE_LAYOUT_STENCIL_READ_ONLY_OPTIMAL: u32 = 1000241003;
 
 pub type ImageViewType = u32;
 pub const IMAGE_VIEW_TYPE_1D: u32 = 0;
@@ -558,6 +622,18 @@ pub const DYNAMIC_STATE_DEPTH_BOUNDS: u32 = 5;
 pub const DYNAMIC_STATE_STENCIL_COMPARE_MASK: u32 = 6;
 pub const DYNAMIC_STATE_STENCIL_WRITE_MASK: u32 = 7;
 pub const DYNAMIC_STATE_STENCIL_REFERENCE: u32 = 8;
+pub const DYNAMIC_STATE_CULL_MODE_EXT: u32 = 1000267000;
+pub const DYNAMIC_STATE_FRONT_FACE_EXT: u32 = 1000267001;
+pub const DYNAMIC_STATE_PRIMITIVE_TOPOLOGY_EXT: u32 = 1000267002;
+pub const DYNAMIC_STATE_VIEWPORT_WITH_COUNT_EXT: u32 = 1000267003;
+pub const DYNAMIC_STATE_SCISSOR_WITH_COUNT_EXT: u32 = 1000267004;
+pub const DYNAMIC_STATE_VERTEX_INPUT_BINDING_STRIDE_EXT: u32 = 1000267005;
+pub const DYNAMIC_STATE_DEPTH_TEST_ENABLE_EXT: u32 = 1000267006;
+pub const DYNAMIC_STATE_DEPTH_WRITE_ENABLE_EXT: u32 = 1000267007;
+pub const DYNAMIC_STATE_DEPTH_COMPARE_OP_EXT: u32 = 1000267008;
+pub const DYNAMIC_STATE_DEPTH_BOUNDS_TEST_ENABLE_EXT: u32 = 1000267009;
+pub const DYNAMIC_STATE_STENCIL_TEST_ENABLE_EXT: u32 = 1000267010;
+pub const DYNAMIC_STATE_STENCIL_OP_EXT: u32 = 1000267011;
 
 pub type Filter = u32;
 pub const FILTER_NEAREST: u32 = 0;
@@ -574,6 +650,11 @@ pub const SAMPLER_ADDRESS_MODE_CLAMP_TO_EDGE: u32 = 2;
 pub const SAMPLER_ADDRESS_MODE_CLAMP_TO_BORDER: u32 = 3;
 pub const SAMPLER_ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE: u32 = 4;
 
+pub type SamplerReductionModeEXT = u32;
+pub const SAMPLER_REDUCTION_MODE_WEIGHTED_AVERAGE_EXT: u32 = 0;
+pub const SAMPLER_REDUCTION_MODE_MIN_EXT: u32 = 1;
+pub const SAMPLER_REDUCTION_MODE_MAX_EXT: u32 = 2;
+
 pub type BorderColor = u32;
 pub const BORDER_COLOR_FLOAT_TRANSPARENT_BLACK: u32 = 0;
 pub const BORDER_COLOR_INT_TRANSPARENT_BLACK: u32 = 1;
@@ -581,6 +662,8 @@ pub const BORDER_COLOR_FLOAT_OPAQUE_BLACK: u32 = 2;
 pub const BORDER_COLOR_INT_OPAQUE_BLACK: u32 = 3;
 pub const BORDER_COLOR_FLOAT_OPAQUE_WHITE: u32 = 4;
 pub const BORDER_COLOR_INT_OPAQUE_WHITE: u32 = 5;
+pub const BORDER_COLOR_FLOAT_CUSTOM_EXT: u32 = 1000287003;
+pub const BORDER_COLOR_INT_CUSTOM_EXT: u32 = 1000287004;
 
 pub type DescriptorType = u32;
 pub const DESCRIPTOR_TYPE_SAMPLER: u32 = 0;
@@ -594,6 +677,7 @@ pub const DESCRIPTOR_TYPE_STORAGE_BUFFER: u32 = 7;
 pub const DESCRIPTOR_TYPE_UNIFORM_BUFFER_DYNAMIC: u32 = 8;
 pub const DESCRIPTOR_TYPE_STORAGE_BUFFER_DYNAMIC: u32 = 9;
 pub const DESCRIPTOR_TYPE_INPUT_ATTACHMENT: u32 = 10;
+pub const DESCRIPTOR_TYPE_MUTABLE_EXT: u32 = 1000351000;
 
 pub type AttachmentLoadOp = u32;
 pub const ATTACHMENT_LOAD_OP_LOAD: u32 = 0;
@@ -615,6 +699,7 @@ pub const COMMAND_BUFFER_LEVEL_SECONDARY: u32 = 1;
 pub type IndexType = u32;
 pub const INDEX_TYPE_UINT16: u32 = 0;
 pub const INDEX_TYPE_UINT32: u32 = 1;
+pub const INDEX_TYPE_UINT8_EXT: u32 = 1000265000;
 
 pub type SubpassContents = u32;
 pub const SUBPASS_CONTENTS_INLINE: u32 = 0;
@@ -690,6 +775,7 @@ pub const IMAGE_CREATE_SPARSE_ALIASED_BIT: u32 = 0x00000004;
 pub const IMAGE_CREATE_MUTABLE_FORMAT_BIT: u32 = 0x00000008;
 pub const IMAGE_CREATE_CUBE_COMPATIBLE_BIT: u32 = 0x00000010;
 pub const IMAGE_CREATE_2D_ARRAY_COMPATIBLE_BIT_KHR: u32 = 0x00000020;
+pub const IMAGE_CREATE_PROTECTED_BIT: u32 = 0x00000800;
 pub type ImageCreateFlags = Flags;
 
 pub type SampleCountFlagBits = u32;
@@ -707,6 +793,7 @@ pub const QUEUE_GRAPHICS_BIT: u32 = 0x00000001;
 pub const QUEUE_COMPUTE_BIT: u32 = 0x00000002;
 pub const QUEUE_TRANSFER_BIT: u32 = 0x00000004;
 pub const QUEUE_SPARSE_BINDING_BIT: u32 = 0x00000008;
+pub const QUEUE_PROTECTED_BIT: u32 = 0x00000010;
 pub type QueueFlags = Flags;
 
 pub type MemoryPropertyFlagBits = u32;
@@ -715,12 +802,15 @@ pub const MEMORY_PROPERTY_HOST_VISIBLE_BIT: u32 = 0x00000002;
 pub const MEMORY_PROPERTY_HOST_COHERENT_BIT: u32 = 0x00000004;
 pub const MEMORY_PROPERTY_HOST_CACHED_BIT: u32 = 0x00000008;
 pub const MEMORY_PROPERTY_LAZILY_ALLOCATED_BIT: u32 = 0x00000010;
+pub const MEMORY_PROPERTY_PROTECTED_BIT: u32 = 0x00000020;
 pub type MemoryPropertyFlags = Flags;
 
 pub type MemoryHeapFlagBits = u32;
 pub const MEMORY_HEAP_DEVICE_LOCAL_BIT: u32 = 0x00000001;
 pub type MemoryHeapFlags = Flags;
 pub type DeviceCreateFlags = Flags;
+pub type DeviceQueueCreateFlagBits = u32;
+pub const DEVICE_QUEUE_CREATE_PROTECTED_BIT: u32 = 0x00000001;
 pub type DeviceQueueCreateFlags = Flags;
 
 pub type PipelineStageFlagBits = u32;
@@ -765,6 +855,26 @@ pub type FenceCreateFlagBits = u32;
 pub const FENCE_CREATE_SIGNALED_BIT: u32 = 0x00000001;
 pub type FenceCreateFlags = Flags;
 pub type SemaphoreCreateFlags = Flags;
+
+pub type SemaphoreType = u32;
+pub const SEMAPHORE_TYPE_BINARY: u32 = 0;
+pub const SEMAPHORE_TYPE_TIMELINE: u32 = 1;
+
+pub type SemaphoreWaitFlagBits = u32;
+pub const SEMAPHORE_WAIT_ANY_BIT: u32 = 0x00000001;
+pub type SemaphoreWaitFlags = Flags;
+
+pub type ExternalSemaphoreHandleTypeFlagBits = u32;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT: u32 = 0x00000001;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT: u32 = 0x00000002;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT: u32 = 0x00000004;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT: u32 = 0x00000008;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT: u32 = 0x00000010;
+pub type ExternalSemaphoreHandleTypeFlags = Flags;
+
+pub type SemaphoreImportFlagBits = u32;
+pub const SEMAPHORE_IMPORT_TEMPORARY_BIT: u32 = 0x00000001;
+pub type SemaphoreImportFlags = Flags;
 pub type EventCreateFlags = Flags;
 pub type QueryPoolCreateFlags = Flags;
 
@@ -793,6 +903,7 @@ pub type BufferCreateFlagBits = u32;
 pub const BUFFER_CREATE_SPARSE_BINDING_BIT: u32 = 0x00000001;
 pub const BUFFER_CREATE_SPARSE_RESIDENCY_BIT: u32 = 0x00000002;
 pub const BUFFER_CREATE_SPARSE_ALIASED_BIT: u32 = 0x00000004;
+pub const BUFFER_CREATE_PROTECTED_BIT: u32 = 0x00000008;
 pub type BufferCreateFlags = Flags;
 
 pub type BufferUsageFlagBits = u32;
@@ -816,7 +927,15 @@ pub type PipelineCreateFlagBits = u32;
 pub const PIPELINE_CREATE_DISABLE_OPTIMIZATION_BIT: u32 = 0x00000001;
 pub const PIPELINE_CREATE_ALLOW_DERIVATIVES_BIT: u32 = 0x00000002;
 pub const PIPELINE_CREATE_DERIVATIVE_BIT: u32 = 0x00000004;
+pub const PIPELINE_CREATE_CAPTURE_STATISTICS_BIT_KHR: u32 = 0x00000040;
+pub const PIPELINE_CREATE_CAPTURE_INTERNAL_REPRESENTATIONS_BIT_KHR: u32 = 0x00000080;
 pub type PipelineCreateFlags = Flags;
+
+pub type PipelineCreationFeedbackFlagBits = u32;
+pub const PIPELINE_CREATION_FEEDBACK_VALID_BIT: u32 = 0x00000001;
+pub const PIPELINE_CREATION_FEEDBACK_APPLICATION_PIPELINE_CACHE_HIT_BIT: u32 = 0x00000002;
+pub const PIPELINE_CREATION_FEEDBACK_BASE_PIPELINE_ACCELERATION_BIT: u32 = 0x00000004;
+pub type PipelineCreationFeedbackFlags = Flags;
 pub type PipelineShaderStageCreateFlags = Flags;
 
 pub type ShaderStageFlagBits = u32;
@@ -857,8 +976,17 @@ pub type SubgroupFeatureFlags = Flags;
 pub type SamplerCreateFlags = Flags;
 pub type DescriptorSetLayoutCreateFlags = Flags;
 
+pub type DescriptorBindingFlagBitsEXT = u32;
+pub const DESCRIPTOR_BINDING_UPDATE_AFTER_BIND_BIT_EXT: u32 = 0x00000001;
+pub const DESCRIPTOR_BINDING_UPDATE_UNUSED_WHILE_PENDING_BIT_EXT: u32 = 0x00000002;
+pub const DESCRIPTOR_BINDING_PARTIALLY_BOUND_BIT_EXT: u32 = 0x00000004;
+pub const DESCRIPTOR_BINDING_VARIABLE_DESCRIPTOR_COUNT_BIT_EXT: u32 = 0x00000008;
+pub type DescriptorBindingFlagsEXT = Flags;
+
 pub type DescriptorPoolCreateFlagBits = u32;
 pub const DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT: u32 = 0x00000001;
+pub const DESCRIPTOR_POOL_CREATE_UPDATE_AFTER_BIND_BIT_EXT: u32 = 0x00000002;
+pub const DESCRIPTOR_POOL_CREATE_HOST_ONLY_BIT_EXT: u32 = 0x00000004;
 pub type DescriptorPoolCreateFlags = Flags;
 pub type DescriptorPoolResetFlags = Flags;
 pub type FramebufferCreateFlags = Flags;
@@ -889,6 +1017,22 @@ pub const ACCESS_MEMORY_READ_BIT: u32 = 0x00008000;
 pub const ACCESS_MEMORY_WRITE_BIT: u32 = 0x00010000;
 pub type AccessFlags = Flags;
 
+pub type PipelineStageFlags2 = Flags64;
+pub const PIPELINE_STAGE_2_NONE: u64 = 0;
+pub const PIPELINE_STAGE_2_COPY_BIT: u64 = 0x100000000;
+pub const PIPELINE_STAGE_2_RESOLVE_BIT: u64 = 0x200000000;
+pub const PIPELINE_STAGE_2_BLIT_BIT: u64 = 0x400000000;
+pub const PIPELINE_STAGE_2_CLEAR_BIT: u64 = 0x800000000;
+pub const PIPELINE_STAGE_2_INDEX_INPUT_BIT: u64 = 0x1000000000;
+pub const PIPELINE_STAGE_2_VERTEX_ATTRIBUTE_INPUT_BIT: u64 = 0x2000000000;
+pub const PIPELINE_STAGE_2_PRE_RASTERIZATION_SHADERS_BIT: u64 = 0x4000000000;
+
+pub type AccessFlags2 = Flags64;
+pub const ACCESS_2_NONE: u64 = 0;
+pub const ACCESS_2_SHADER_SAMPLED_READ_BIT: u64 = 0x100000000;
+pub const ACCESS_2_SHADER_STORAGE_READ_BIT: u64 = 0x200000000;
+pub const ACCESS_2_SHADER_STORAGE_WRITE_BIT: u64 = 0x400000000;
+
 pub type DependencyFlagBits = u32;
 pub const DEPENDENCY_BY_REGION_BIT: u32 = 0x00000001;
 pub type DependencyFlags = Flags;
@@ -896,6 +1040,7 @@ pub type DependencyFlags = Flags;
 pub type CommandPoolCreateFlagBits = u32;
 pub const COMMAND_POOL_CREATE_TRANSIENT_BIT: u32 = 0x00000001;
 pub const COMMAND_POOL_CREATE_RESET_COMMAND_BUFFER_BIT: u32 = 0x00000002;
+pub const COMMAND_POOL_CREATE_PROTECTED_BIT: u32 = 0x00000004;
 pub type CommandPoolCreateFlags = Flags;
 
 pub type CommandPoolResetFlagBits = u32;
@@ -1043,6 +1188,8 @@ pub type IOSSurfaceCreateFlagsMVK = u32;
 
 pub type DescriptorSetLayoutCreateFlagBits = u32;
 pub const DESCRIPTOR_SET_LAYOUT_CREATE_PUSH_DESCRIPTOR_BIT_KHR: u32 = 0x00000001;
+pub const DESCRIPTOR_SET_LAYOUT_CREATE_UPDATE_AFTER_BIND_POOL_BIT_EXT: u32 = 0x00000002;
+pub const DESCRIPTOR_SET_LAYOUT_CREATE_HOST_ONLY_POOL_BIT_EXT: u32 = 0x00000004;
 
 pub type DescriptorUpdateTemplateTypeKHR = u32;
 pub const DESCRIPTOR_UPDATE_TEMPLATE_TYPE_DESCRIPTOR_SET_KHR: u32 = 0;
@@ -2251,6 +2398,60 @@ pub struct ImageMemoryBarrier {
     pub subresourceRange: ImageSubresourceRange,
 }
 
+#[repr(C)]
+pub struct MemoryBarrier2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub srcStageMask: PipelineStageFlags2,
+    pub srcAccessMask: AccessFlags2,
+    pub dstStageMask: PipelineStageFlags2,
+    pub dstAccessMask: AccessFlags2,
+}
+
+#[repr(C)]
+pub struct BufferMemoryBarrier2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub srcStageMask: PipelineStageFlags2,
+    pub srcAccessMask: AccessFlags2,
+    pub dstStageMask: PipelineStageFlags2,
+    pub dstAccessMask: AccessFlags2,
+    pub srcQueueFamilyIndex: u32,
+    pub dstQueueFamilyIndex: u32,
+    pub buffer: Buffer,
+    pub offset: DeviceSize,
+    pub size: DeviceSize,
+}
+
+#[repr(C)]
+pub struct ImageMemoryBarrier2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub srcStageMask: PipelineStageFlags2,
+    pub srcAccessMask: AccessFlags2,
+    pub dstStageMask: PipelineStageFlags2,
+    pub dstAccessMask: AccessFlags2,
+    pub oldLayout: ImageLayout,
+    pub newLayout: ImageLayout,
+    pub srcQueueFamilyIndex: u32,
+    pub dstQueueFamilyIndex: u32,
+    pub image: Image,
+    pub subresourceRange: ImageSubresourceRange,
+}
+
+#[repr(C)]
+pub struct DependencyInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub dependencyFlags: DependencyFlags,
+    pub memoryBarrierCount: u32,
+    pub pMemoryBarriers: *const MemoryBarrier2,
+    pub bufferMemoryBarrierCount: u32,
+    pub pBufferMemoryBarriers: *const BufferMemoryBarrier2,
+    pub imageMemoryBarrierCount: u32,
+    pub pImageMemoryBarriers: *const ImageMemoryBarrier2,
+}
+
 #[repr(C)]
 pub struct RenderPassBeginInfo {
     pub sType: StructureType,
@@ -2538,6 +2739,134 @@ pub struct PhysicalDeviceSubgroupProperties {
     pub quadOperationsInAllStages: Bool32,
 }
 
+#[repr(C)]
+pub struct PhysicalDeviceSamplerFilterMinmaxPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub filterMinmaxSingleComponentFormats: Bool32,
+    pub filterMinmaxImageComponentMapping: Bool32,
+}
+
+pub type MemoryAllocateFlagBitsKHR = u32;
+pub const MEMORY_ALLOCATE_DEVICE_MASK_BIT_KHR: u32 = 0x00000001;
+pub type MemoryAllocateFlagsKHR = Flags;
+
+#[repr(C)]
+pub struct MemoryAllocateFlagsInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: MemoryAllocateFlagsKHR,
+    pub deviceMask: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceGroupPropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub physicalDeviceCount: u32,
+    pub physicalDevices: [PhysicalDevice; MAX_DEVICE_GROUP_SIZE_KHR],
+    pub subsetAllocation: Bool32,
+}
+
+#[repr(C)]
+pub struct DeviceGroupDeviceCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub physicalDeviceCount: u32,
+    pub pPhysicalDevices: *const PhysicalDevice,
+}
+
+pub type PipelineExecutableStatisticFormatKHR = u32;
+pub const PIPELINE_EXECUTABLE_STATISTIC_FORMAT_BOOL32_KHR: u32 = 0;
+pub const PIPELINE_EXECUTABLE_STATISTIC_FORMAT_INT64_KHR: u32 = 1;
+pub const PIPELINE_EXECUTABLE_STATISTIC_FORMAT_UINT64_KHR: u32 = 2;
+pub const PIPELINE_EXECUTABLE_STATISTIC_FORMAT_FLOAT64_KHR: u32 = 3;
+
+#[repr(C)]
+pub struct PhysicalDevicePipelineExecutablePropertiesFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub pipelineExecutableInfo: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pipeline: Pipeline,
+}
+
+#[repr(C)]
+pub struct PipelineExecutablePropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub stages: ShaderStageFlags,
+    pub name: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub subgroupSize: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PipelineCreationFeedback {
+    pub flags: PipelineCreationFeedbackFlags,
+    pub duration: u64,
+}
+
+#[repr(C)]
+pub struct PipelineCreationFeedbackCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pPipelineCreationFeedback: *mut PipelineCreationFeedback,
+    pub pipelineStageCreationFeedbackCount: u32,
+    pub pPipelineStageCreationFeedbacks: *mut PipelineCreationFeedback,
+}
+
+#[repr(C)]
+pub struct PipelineExecutableInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pipeline: Pipeline,
+    pub executableIndex: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union PipelineExecutableStatisticValueKHR {
+    pub b32: Bool32,
+    pub i64: i64,
+    pub u64: u64,
+    pub f64: f64,
+}
+
+#[repr(C)]
+pub struct PipelineExecutableStatisticKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub name: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub format: PipelineExecutableStatisticFormatKHR,
+    pub value: PipelineExecutableStatisticValueKHR,
+}
+
+#[repr(C)]
+pub struct PipelineExecutableInternalRepresentationKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub name: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub description: [c_char; MAX_DESCRIPTION_SIZE as usize],
+    pub isText: Bool32,
+    pub dataSize: usize,
+    pub pData: *mut c_void,
+}
+
+#[repr(C)]
+pub struct SamplerReductionModeCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub reductionMode: SamplerReductionModeEXT,
+}
+
 #[repr(C)]
 pub struct FormatProperties2KHR {
     pub sType: StructureType,
@@ -2647,6 +2976,294 @@ pub struct PhysicalDeviceShaderFloat16Int8Features {
     pub shaderInt8: Bool32,
 }
 
+#[repr(C)]
+pub struct PhysicalDeviceSeparateDepthStencilLayoutsFeatures {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub separateDepthStencilLayouts: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceSynchronization2Features {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub synchronization2: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDescriptorIndexingFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub shaderInputAttachmentArrayDynamicIndexing: Bool32,
+    pub shaderUniformTexelBufferArrayDynamicIndexing: Bool32,
+    pub shaderStorageTexelBufferArrayDynamicIndexing: Bool32,
+    pub shaderUniformBufferArrayNonUniformIndexing: Bool32,
+    pub shaderSampledImageArrayNonUniformIndexing: Bool32,
+    pub shaderStorageBufferArrayNonUniformIndexing: Bool32,
+    pub shaderStorageImageArrayNonUniformIndexing: Bool32,
+    pub shaderInputAttachmentArrayNonUniformIndexing: Bool32,
+    pub shaderUniformTexelBufferArrayNonUniformIndexing: Bool32,
+    pub shaderStorageTexelBufferArrayNonUniformIndexing: Bool32,
+    pub descriptorBindingUniformBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingSampledImageUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageImageUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingUniformTexelBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageTexelBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingUpdateUnusedWhilePending: Bool32,
+    pub descriptorBindingPartiallyBound: Bool32,
+    pub descriptorBindingVariableDescriptorCount: Bool32,
+    pub runtimeDescriptorArray: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDescriptorIndexingPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub maxUpdateAfterBindDescriptorsInAllPools: u32,
+    pub shaderUniformBufferArrayNonUniformIndexingNative: Bool32,
+    pub shaderSampledImageArrayNonUniformIndexingNative: Bool32,
+    pub shaderStorageBufferArrayNonUniformIndexingNative: Bool32,
+    pub shaderStorageImageArrayNonUniformIndexingNative: Bool32,
+    pub shaderInputAttachmentArrayNonUniformIndexingNative: Bool32,
+    pub robustBufferAccessUpdateAfterBind: Bool32,
+    pub quadDivergentImplicitLod: Bool32,
+    pub maxPerStageDescriptorUpdateAfterBindSamplers: u32,
+    pub maxPerStageDescriptorUpdateAfterBindUniformBuffers: u32,
+    pub maxPerStageDescriptorUpdateAfterBindStorageBuffers: u32,
+    pub maxPerStageDescriptorUpdateAfterBindSampledImages: u32,
+    pub maxPerStageDescriptorUpdateAfterBindStorageImages: u32,
+    pub maxPerStageDescriptorUpdateAfterBindInputAttachments: u32,
+    pub maxPerStageUpdateAfterBindResources: u32,
+    pub maxDescriptorSetUpdateAfterBindSamplers: u32,
+    pub maxDescriptorSetUpdateAfterBindUniformBuffers: u32,
+    pub maxDescriptorSetUpdateAfterBindUniformBuffersDynamic: u32,
+    pub maxDescriptorSetUpdateAfterBindStorageBuffers: u32,
+    pub maxDescriptorSetUpdateAfterBindStorageBuffersDynamic: u32,
+    pub maxDescriptorSetUpdateAfterBindSampledImages: u32,
+    pub maxDescriptorSetUpdateAfterBindStorageImages: u32,
+    pub maxDescriptorSetUpdateAfterBindInputAttachments: u32,
+}
+
+#[repr(C)]
+pub struct DescriptorSetLayoutBindingFlagsCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub bindingCount: u32,
+    pub pBindingFlags: *const DescriptorBindingFlagsEXT,
+}
+
+#[repr(C)]
+pub struct DescriptorSetVariableDescriptorCountAllocateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub descriptorSetCount: u32,
+    pub pDescriptorCounts: *const u32,
+}
+
+#[repr(C)]
+pub struct DescriptorSetVariableDescriptorCountLayoutSupportEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub maxVariableDescriptorCount: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTimelineSemaphoreFeatures {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub timelineSemaphore: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTimelineSemaphoreProperties {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub maxTimelineSemaphoreValueDifference: u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreTypeCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphoreType: SemaphoreType,
+    pub initialValue: u64,
+}
+
+#[repr(C)]
+pub struct ExportSemaphoreCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub handleTypes: ExternalSemaphoreHandleTypeFlags,
+}
+
+#[repr(C)]
+pub struct ImportSemaphoreFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub flags: SemaphoreImportFlags,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBits,
+    pub fd: c_int,
+}
+
+#[repr(C)]
+pub struct SemaphoreGetFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBits,
+}
+
+#[repr(C)]
+pub struct ImportSemaphoreWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub flags: SemaphoreImportFlags,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBits,
+    pub handle: *mut c_void,
+    pub name: *const u16,
+}
+
+#[repr(C)]
+pub struct SemaphoreGetWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBits,
+}
+
+#[repr(C)]
+pub struct TimelineSemaphoreSubmitInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub waitSemaphoreValueCount: u32,
+    pub pWaitSemaphoreValues: *const u64,
+    pub signalSemaphoreValueCount: u32,
+    pub pSignalSemaphoreValues: *const u64,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceProtectedMemoryFeatures {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub protectedMemory: Bool32,
+}
+
+#[repr(C)]
+pub struct ProtectedSubmitInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub protectedSubmit: Bool32,
+}
+
+#[repr(C)]
+pub struct DeviceQueueInfo2 {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: DeviceQueueCreateFlags,
+    pub queueFamilyIndex: u32,
+    pub queueIndex: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMaintenance4FeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maintenance4: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMaintenance4PropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxBufferSize: DeviceSize,
+}
+
+#[repr(C)]
+pub struct DeviceBufferMemoryRequirementsKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pCreateInfo: *const BufferCreateInfo,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceExtendedDynamicStateFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub extendedDynamicState: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMutableDescriptorTypeFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub mutableDescriptorType: Bool32,
+}
+
+#[repr(C)]
+pub struct MutableDescriptorTypeListEXT {
+    pub descriptorTypeCount: u32,
+    pub pDescriptorTypes: *const DescriptorType,
+}
+
+#[repr(C)]
+pub struct MutableDescriptorTypeCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub mutableDescriptorTypeListCount: u32,
+    pub pMutableDescriptorTypeLists: *const MutableDescriptorTypeListEXT,
+}
+
+#[repr(C)]
+pub struct SamplerCustomBorderColorCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub customBorderColor: ClearColorValue,
+    pub format: Format,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceCustomBorderColorPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxCustomBorderColorSamplers: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceCustomBorderColorFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub customBorderColors: Bool32,
+    pub customBorderColorWithoutFormat: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceIndexTypeUint8FeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub indexTypeUint8: Bool32,
+}
+
+#[repr(C)]
+pub struct SemaphoreWaitInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: SemaphoreWaitFlags,
+    pub semaphoreCount: u32,
+    pub pSemaphores: *const Semaphore,
+    pub pValues: *const u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreSignalInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub value: u64,
+}
+
 pub type ViSurfaceCreateFlagsNN = Flags;
 
 #[repr(C)]
@@ -2704,6 +3321,13 @@ pub struct MemoryDedicatedAllocateInfoKHR {
     pub buffer: Buffer,
 }
 
+#[repr(C)]
+pub struct ImageViewUsageCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub usage: ImageUsageFlags,
+}
+
 #[repr(C)]
 pub struct BufferMemoryRequirementsInfo2KHR {
     pub sType: StructureType,
@@ -2915,6 +3539,7 @@ ptrs!(InstancePointers, {
     GetPhysicalDeviceQueueFamilyProperties2KHR => (physicalDevice: PhysicalDevice, pQueueFamilyPropertiesCount: *mut u32, pQueueFamilyProperties: *mut QueueFamilyProperties2KHR) -> (),
     GetPhysicalDeviceMemoryProperties2KHR => (physicalDevice: PhysicalDevice, pMemoryProperties: *mut PhysicalDeviceMemoryProperties2KHR) -> (),
     GetPhysicalDeviceSparseImageFormatProperties2KHR => (physicalDevice: PhysicalDevice, pFormatInfo: *const PhysicalDeviceSparseImageFormatInfo2KHR, pPropertyCount: *mut u32, pProperties: *mut SparseImageFormatProperties2KHR) -> (),
+    EnumeratePhysicalDeviceGroupsKHR => (instance: Instance, pPhysicalDeviceGroupCount: *mut u32, pPhysicalDeviceGroupProperties: *mut PhysicalDeviceGroupPropertiesKHR) -> Result,
 });
 
 ptrs!(DevicePointers, {
@@ -3059,4 +3684,29 @@ ptrs!(DevicePointers, {
     AcquireFullScreenExclusiveModeEXT => (device: Device, swapchain: SwapchainKHR) -> Result,
     ReleaseFullScreenExclusiveModeEXT => (device: Device, swapchain: SwapchainKHR) -> Result,
     GetBufferDeviceAddressEXT => (device: Device, pInfo: *const BufferDeviceAddressInfo) -> DeviceAddress,
+    GetSemaphoreCounterValueKHR => (device: Device, semaphore: Semaphore, pValue: *mut u64) -> Result,
+    WaitSemaphoresKHR => (device: Device, pWaitInfo: *const SemaphoreWaitInfo, timeout: u64) -> Result,
+    SignalSemaphoreKHR => (device: Device, pSignalInfo: *const SemaphoreSignalInfo) -> Result,
+    GetSemaphoreCounterValue => (device: Device, semaphore: Semaphore, pValue: *mut u64) -> Result,
+    WaitSemaphores => (device: Device, pWaitInfo: *const SemaphoreWaitInfo, timeout: u64) -> Result,
+    SignalSemaphore => (device: Device, pSignalInfo: *const SemaphoreSignalInfo) -> Result,
+    CmdSetDeviceMaskKHR => (commandBuffer: CommandBuffer, deviceMask: u32) -> (),
+    CmdDispatchBaseKHR => (commandBuffer: CommandBuffer, baseGroupX: u32, baseGroupY: u32, baseGroupZ: u32, groupCountX: u32, groupCountY: u32, groupCountZ: u32) -> (),
+    GetPipelineExecutablePropertiesKHR => (device: Device, pPipelineInfo: *const PipelineInfoKHR, pExecutableCount: *mut u32, pProperties: *mut PipelineExecutablePropertiesKHR) -> Result,
+    GetPipelineExecutableStatisticsKHR => (device: Device, pExecutableInfo: *const PipelineExecutableInfoKHR, pStatisticCount: *mut u32, pStatistics: *mut PipelineExecutableStatisticKHR) -> Result,
+    GetPipelineExecutableInternalRepresentationsKHR => (device: Device, pExecutableInfo: *const PipelineExecutableInfoKHR, pInternalRepresentationCount: *mut u32, pInternalRepresentations: *mut PipelineExecutableInternalRepresentationKHR) -> Result,
+    GetDeviceQueue2 => (device: Device, pQueueInfo: *const DeviceQueueInfo2, pQueue: *mut Queue) -> (),
+    GetDeviceBufferMemoryRequirementsKHR => (device: Device, pInfo: *const DeviceBufferMemoryRequirementsKHR, pMemoryRequirements: *mut MemoryRequirements2KHR) -> (),
+    CmdBindVertexBuffers2EXT => (commandBuffer: CommandBuffer, firstBinding: u32, bindingCount: u32, pBuffers: *const Buffer, pOffsets: *const DeviceSize, pSizes: *const DeviceSize, pStrides: *const DeviceSize) -> (),
+    CmdPipelineBarrier2 => (commandBuffer: CommandBuffer, pDependencyInfo: *const DependencyInfo) -> (),
+    CmdPipelineBarrier2KHR => (commandBuffer: CommandBuffer, pDependencyInfo: *const DependencyInfo) -> (),
+    CmdSetCullModeEXT => (commandBuffer: CommandBuffer, cullMode: CullModeFlags) -> (),
+    CmdSetFrontFaceEXT => (commandBuffer: CommandBuffer, frontFace: FrontFace) -> (),
+    CmdSetPrimitiveTopologyEXT => (commandBuffer: CommandBuffer, primitiveTopology: PrimitiveTopology) -> (),
+    CmdSetDepthTestEnableEXT => (commandBuffer: CommandBuffer, depthTestEnable: Bool32) -> (),
+    CmdSetDepthWriteEnableEXT => (commandBuffer: CommandBuffer, depthWriteEnable: Bool32) -> (),
+    GetSemaphoreFdKHR => (device: Device, pGetFdInfo: *const SemaphoreGetFdInfoKHR, pFd: *mut c_int) -> Result,
+    ImportSemaphoreFdKHR => (device: Device, pImportSemaphoreFdInfo: *const ImportSemaphoreFdInfoKHR) -> Result,
+    GetSemaphoreWin32HandleKHR => (device: Device, pGetWin32HandleInfo: *const SemaphoreGetWin32HandleInfoKHR, pHandle: *mut *mut c_void) -> Result,
+    ImportSemaphoreWin32HandleKHR => (device: Device, pImportSemaphoreWin32HandleInfo: *const ImportSemaphoreWin32HandleInfoKHR) -> Result,
 });
@@ -713,4 +713,55 @@ mod tests {
         );
         compile_defines.expect("Setting shader macros did not work");
     }
+
+    #[test]
+    fn test_local_size() {
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let local_size = |source: &str| {
+            let comp = compile(
+                None,
+                &Path::new(""),
+                source,
+                ShaderKind::Compute,
+                &includes,
+                &defines,
+            )
+            .unwrap();
+            let doc = parse::parse_spirv(comp.as_binary()).unwrap();
+            let id = doc
+                .instructions
+                .iter()
+                .filter_map(|i| match *i {
+                    Instruction::EntryPoint { id, .. } => Some(id),
+                    _ => None,
+                })
+                .next()
+                .unwrap();
+            entry_point::compute_local_size(&doc, id)
+        };
+
+        assert_eq!(
+            local_size(
+                "
+        #version 450
+        layout(local_size_x = 8, local_size_y = 4) in;
+        void main() {}
+        "
+            ),
+            Some([8, 4, 1])
+        );
+
+        // The local size may be specialized, so it isn't known at compile time.
+        assert_eq!(
+            local_size(
+                "
+        #version 450
+        layout(local_size_x_id = 0) in;
+        void main() {}
+        "
+            ),
+            None
+        );
+    }
 }
@@ -10,7 +10,7 @@
 use proc_macro2::{Span, TokenStream};
 use syn::Ident;
 
-use crate::enums::{Decoration, ExecutionMode, ExecutionModel, StorageClass};
+use crate::enums::{BuiltIn, Decoration, ExecutionMode, ExecutionModel, StorageClass};
 use crate::parse::{Instruction, Spirv};
 use crate::spirv_search;
 
@@ -60,12 +60,17 @@ pub fn write_entry_point(doc: &Spirv, instruction: &Instruction) -> (TokenStream
 
     let (ty, f_call) = {
         if let ExecutionModel::ExecutionModelGLCompute = *execution {
+            let with_local_size = match compute_local_size(doc, id) {
+                Some([x, y, z]) => quote! { .with_local_size([#x, #y, #z]) },
+                None => quote! {},
+            };
+
             (
                 quote! { ::vulkano::pipeline::shader::ComputeEntryPoint<#spec_consts_struct, Layout> },
                 quote! { compute_entry_point(
                     ::std::ffi::CStr::from_ptr(NAME.as_ptr() as *const _),
                     Layout(ShaderStages { compute: true, .. ShaderStages::none() })
-                )},
+                )#with_local_size},
             )
         } else {
             let entry_ty = match *execution {
@@ -210,6 +215,47 @@ pub fn write_entry_point(doc: &Spirv, instruction: &Instruction) -> (TokenStream
     (interface_structs, entry_point)
 }
 
+/// Returns the size of the local workgroups declared by the `LocalSize` execution mode of the
+/// entry point.
+///
+/// Returns `None` if there is no such execution mode, or if it is overridden by a constant
+/// decorated with the `WorkgroupSize` builtin, as that constant may be specialized.
+pub(crate) fn compute_local_size(doc: &Spirv, id: u32) -> Option<[u32; 3]> {
+    for instruction in doc.instructions.iter() {
+        if let &Instruction::Decorate {
+            decoration: Decoration::DecorationBuiltIn,
+            ref params,
+            ..
+        } = instruction
+        {
+            if let Some(Ok(BuiltIn::BuiltInWorkgroupSize)) =
+                params.first().map(|&p| BuiltIn::from_num(p))
+            {
+                return None;
+            }
+        }
+    }
+
+    for instruction in doc.instructions.iter() {
+        if let &Instruction::ExecutionMode {
+            target_id,
+            mode: ExecutionMode::ExecutionModeLocalSize,
+            ref optional_literals,
+        } = instruction
+        {
+            if target_id == id && optional_literals.len() == 3 {
+                return Some([
+                    optional_literals[0],
+                    optional_literals[1],
+                    optional_literals[2],
+                ]);
+            }
+        }
+    }
+
+    None
+}
+
 struct Element {
     location: u32,
     name: String,
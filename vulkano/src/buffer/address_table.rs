@@ -0,0 +1,209 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use buffer::BufferUsage;
+use buffer::ImmutableBuffer;
+use command_buffer::AutoCommandBuffer;
+use command_buffer::CommandBufferExecFuture;
+use device::DeviceOwned;
+use device::Queue;
+use memory::DeviceMemoryAllocError;
+use sync::NowFuture;
+use VulkanObject;
+
+/// Buffer that contains the device addresses of a list of buffers.
+///
+/// In a GPU-driven renderer, shaders can access buffers through their device address instead of
+/// through descriptors. A `DeviceAddressTable` gathers the addresses of all the buffers in a
+/// single buffer of `u64`s, the *table*, which only needs to be bound once, for example as a
+/// storage buffer or by passing its own device address in a push constant. Shaders then read the
+/// address of the buffer at a given index of the table, and access the buffer through it.
+///
+/// The table keeps the buffers alive. However buffers accessed through their device address are
+/// invisible to the synchronization performed by command buffers, therefore it is your
+/// responsibility to ensure that they are not written while the GPU reads them, and vice versa.
+///
+/// All the buffers must have been created with the `device_address` usage, which requires the
+/// `buffer_device_address` feature.
+pub struct DeviceAddressTable<B> {
+    buffers: Vec<B>,
+    addresses: Vec<u64>,
+    table: Arc<ImmutableBuffer<[u64]>>,
+}
+
+impl<B> DeviceAddressTable<B>
+where
+    B: BufferAccess,
+{
+    /// Builds a table that contains the device addresses of `buffers`, in order.
+    ///
+    /// The table is uploaded with a transfer on `queue`. Returns the table and a future that
+    /// must be waited upon before the table is read by the GPU.
+    ///
+    /// The table is created with the `storage_buffer` and `device_address` usages.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `buffers` is empty.
+    /// - Panics if a buffer doesn't belong to the same device as `queue`.
+    ///
+    pub fn new<I>(
+        buffers: I,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            DeviceAddressTable<B>,
+            CommandBufferExecFuture<NowFuture, AutoCommandBuffer>,
+        ),
+        DeviceAddressTableError,
+    >
+    where
+        I: IntoIterator<Item = B>,
+    {
+        let buffers: Vec<B> = buffers.into_iter().collect();
+        assert!(!buffers.is_empty());
+
+        let addresses = buffers
+            .iter()
+            .enumerate()
+            .map(|(index, buffer)| {
+                assert_eq!(
+                    buffer.device().internal_object(),
+                    queue.device().internal_object()
+                );
+
+                buffer
+                    .raw_device_address()
+                    .map(|address| address.get())
+                    .map_err(|_| DeviceAddressTableError::DeviceAddressUsageNotEnabled { index })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let usage = BufferUsage {
+            storage_buffer: true,
+            device_address: true,
+            ..BufferUsage::none()
+        };
+        let (table, future) = ImmutableBuffer::from_iter(addresses.iter().cloned(), usage, queue)?;
+
+        Ok((
+            DeviceAddressTable {
+                buffers,
+                addresses,
+                table,
+            },
+            future,
+        ))
+    }
+
+    /// Returns the buffer that contains the addresses.
+    #[inline]
+    pub fn table(&self) -> &Arc<ImmutableBuffer<[u64]>> {
+        &self.table
+    }
+
+    /// Returns the buffers whose addresses are in the table, in order.
+    #[inline]
+    pub fn buffers(&self) -> &[B] {
+        &self.buffers
+    }
+
+    /// Returns the device address stored at `index` in the table, or `None` if out of range.
+    #[inline]
+    pub fn address(&self, index: usize) -> Option<u64> {
+        self.addresses.get(index).cloned()
+    }
+
+    /// Returns the number of entries in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+}
+
+impl<B> fmt::Debug for DeviceAddressTable<B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("DeviceAddressTable")
+            .field("addresses", &self.addresses)
+            .finish()
+    }
+}
+
+/// Error that can happen when creating a `DeviceAddressTable`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceAddressTableError {
+    /// Allocating the table failed.
+    DeviceMemoryAllocError(DeviceMemoryAllocError),
+    /// A buffer wasn't created with the `device_address` usage.
+    DeviceAddressUsageNotEnabled {
+        /// Index of the buffer in the list.
+        index: usize,
+    },
+}
+
+impl error::Error for DeviceAddressTableError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            DeviceAddressTableError::DeviceMemoryAllocError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DeviceAddressTableError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DeviceAddressTableError::DeviceMemoryAllocError(_) => {
+                    "allocating the table failed"
+                }
+                DeviceAddressTableError::DeviceAddressUsageNotEnabled { .. } => {
+                    "a buffer wasn't created with the `device_address` usage"
+                }
+            }
+        )
+    }
+}
+
+impl From<DeviceMemoryAllocError> for DeviceAddressTableError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> DeviceAddressTableError {
+        DeviceAddressTableError::DeviceMemoryAllocError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use buffer::DeviceAddressTable;
+    use buffer::DeviceAddressTableError;
+
+    #[test]
+    fn missing_usage() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device, BufferUsage::all(), false, 0u32).unwrap();
+
+        match DeviceAddressTable::new(vec![buffer], queue) {
+            Err(DeviceAddressTableError::DeviceAddressUsageNotEnabled { index: 0 }) => (),
+            _ => panic!(),
+        }
+    }
+}
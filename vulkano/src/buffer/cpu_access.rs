@@ -147,6 +147,32 @@ impl<T> CpuAccessibleBuffer<T> {
             iter::empty(),
         )
     }
+
+    /// Builds a new buffer whose content is filled with zeroes. Only allowed for sized data.
+    ///
+    /// The mapped memory is cleared from the CPU, which costs a write of the whole buffer.
+    ///
+    /// # Safety
+    ///
+    /// A value of `T` whose bytes are all zero must be valid.
+    ///
+    pub unsafe fn zeroed(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        host_cached: bool,
+    ) -> Result<Arc<CpuAccessibleBuffer<T>>, DeviceMemoryAllocError>
+    where
+        T: 'static,
+    {
+        let uninitialized = CpuAccessibleBuffer::uninitialized(device, usage, host_cached)?;
+
+        {
+            let mut mapping = uninitialized.write().unwrap();
+            ptr::write_bytes::<T>(&mut *mapping, 0, 1);
+        }
+
+        Ok(uninitialized)
+    }
 }
 
 impl<T> CpuAccessibleBuffer<[T]> {
@@ -198,6 +224,34 @@ impl<T> CpuAccessibleBuffer<[T]> {
             iter::empty(),
         )
     }
+
+    /// Builds a new array buffer whose content is filled with zeroes.
+    ///
+    /// See the documentation of `zeroed`.
+    ///
+    /// # Safety
+    ///
+    /// A value of `T` whose bytes are all zero must be valid.
+    ///
+    pub unsafe fn zeroed_array(
+        device: Arc<Device>,
+        len: usize,
+        usage: BufferUsage,
+        host_cached: bool,
+    ) -> Result<Arc<CpuAccessibleBuffer<[T]>>, DeviceMemoryAllocError>
+    where
+        T: 'static,
+    {
+        let uninitialized =
+            CpuAccessibleBuffer::uninitialized_array(device, len, usage, host_cached)?;
+
+        {
+            let mut mapping = uninitialized.write().unwrap();
+            ptr::write_bytes(mapping.as_mut_ptr(), 0, len);
+        }
+
+        Ok(uninitialized)
+    }
 }
 
 impl<T: ?Sized> CpuAccessibleBuffer<T> {
@@ -663,4 +717,15 @@ mod tests {
 
         let _ = CpuAccessibleBuffer::from_data(device, BufferUsage::all(), false, EMPTY.iter());
     }
+
+    #[test]
+    fn zeroed_array() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = unsafe {
+            CpuAccessibleBuffer::<[u32]>::zeroed_array(device, 16, BufferUsage::all(), false)
+        }
+        .unwrap();
+        assert!(buffer.read().unwrap().iter().all(|&v| v == 0));
+    }
 }
@@ -14,6 +14,8 @@
 //! write simultaneously, or write and write simultaneously will block with a semaphore.
 
 use smallvec::SmallVec;
+use std::error;
+use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::marker::PhantomData;
@@ -28,6 +30,13 @@ use buffer::traits::BufferAccess;
 use buffer::traits::BufferInner;
 use buffer::traits::TypedBufferAccess;
 use buffer::BufferUsage;
+use command_buffer::AutoCommandBuffer;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::BuildError;
+use command_buffer::CommandBuffer;
+use command_buffer::CommandBufferExecError;
+use command_buffer::CommandBufferExecFuture;
+use command_buffer::FillBufferError;
 use device::Device;
 use device::DeviceOwned;
 use device::Queue;
@@ -43,6 +52,7 @@ use memory::pool::StdMemoryPoolAlloc;
 use memory::DedicatedAlloc;
 use memory::DeviceMemoryAllocError;
 use sync::AccessError;
+use sync::NowFuture;
 use sync::Sharing;
 
 /// Buffer whose content is in device-local memory.
@@ -58,6 +68,10 @@ pub struct DeviceLocalBuffer<T: ?Sized, A = PotentialDedicatedAllocation<StdMemo
     // Inner content.
     inner: UnsafeBuffer,
 
+    // Size of the buffer as requested by the user. The inner buffer can be slightly larger if it
+    // was padded to a multiple of 4 bytes in order to be cleared.
+    size: usize,
+
     // The memory held by the buffer.
     memory: A,
 
@@ -71,6 +85,8 @@ pub struct DeviceLocalBuffer<T: ?Sized, A = PotentialDedicatedAllocation<StdMemo
     marker: PhantomData<Box<T>>,
 }
 
+type DeviceLocalBufferZeroedFuture = CommandBufferExecFuture<NowFuture, AutoCommandBuffer>;
+
 #[derive(Debug, Copy, Clone)]
 enum GpuAccess {
     None,
@@ -92,6 +108,29 @@ impl<T> DeviceLocalBuffer<T> {
     {
         unsafe { DeviceLocalBuffer::raw(device, mem::size_of::<T>(), usage, queue_families) }
     }
+
+    /// Builds a new buffer whose content is filled with zeroes. Only allowed for sized data.
+    ///
+    /// The buffer is cleared with a `fill_buffer` command submitted to `queue`. This function
+    /// returns two objects: the newly-created buffer, and a future representing the clear
+    /// operation. You must either submit your operations after this future, or execute this
+    /// future and wait for it to be finished before using the buffer.
+    ///
+    /// > **Note**: Clearing the buffer costs a submission and a GPU-side write of the whole
+    /// > buffer. Only use this function if your code actually reads the buffer before writing it.
+    ///
+    pub fn zeroed(
+        usage: BufferUsage,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (Arc<DeviceLocalBuffer<T>>, DeviceLocalBufferZeroedFuture),
+        DeviceLocalBufferZeroedError,
+    >
+    where
+        T: Send + Sync + 'static,
+    {
+        unsafe { DeviceLocalBuffer::raw_zeroed(mem::size_of::<T>(), usage, queue) }
+    }
 }
 
 impl<T> DeviceLocalBuffer<[T]> {
@@ -109,6 +148,23 @@ impl<T> DeviceLocalBuffer<[T]> {
     {
         unsafe { DeviceLocalBuffer::raw(device, len * mem::size_of::<T>(), usage, queue_families) }
     }
+
+    /// Builds a new array buffer whose content is filled with zeroes.
+    ///
+    /// See the documentation of `zeroed`.
+    pub fn zeroed_array(
+        len: usize,
+        usage: BufferUsage,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (Arc<DeviceLocalBuffer<[T]>>, DeviceLocalBufferZeroedFuture),
+        DeviceLocalBufferZeroedError,
+    >
+    where
+        T: Send + Sync + 'static,
+    {
+        unsafe { DeviceLocalBuffer::raw_zeroed(len * mem::size_of::<T>(), usage, queue) }
+    }
 }
 
 impl<T: ?Sized> DeviceLocalBuffer<T> {
@@ -127,6 +183,23 @@ impl<T: ?Sized> DeviceLocalBuffer<T> {
     where
         I: IntoIterator<Item = QueueFamily<'a>>,
     {
+        DeviceLocalBuffer::raw_padded(device, size, size, usage, queue_families)
+    }
+
+    // Same as `raw`, except that the inner buffer is created with a size of `buffer_size`, which
+    // must be greater than or equal to `size`.
+    unsafe fn raw_padded<'a, I>(
+        device: Arc<Device>,
+        size: usize,
+        buffer_size: usize,
+        usage: BufferUsage,
+        queue_families: I,
+    ) -> Result<Arc<DeviceLocalBuffer<T>>, DeviceMemoryAllocError>
+    where
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        debug_assert!(buffer_size >= size);
+
         let queue_families = queue_families
             .into_iter()
             .map(|f| f.id())
@@ -139,7 +212,13 @@ impl<T: ?Sized> DeviceLocalBuffer<T> {
                 Sharing::Exclusive
             };
 
-            match UnsafeBuffer::new(device.clone(), size, usage, sharing, SparseLevel::none()) {
+            match UnsafeBuffer::new(
+                device.clone(),
+                buffer_size,
+                usage,
+                sharing,
+                SparseLevel::none(),
+            ) {
                 Ok(b) => b,
                 Err(BufferCreationError::AllocError(err)) => return Err(err),
                 Err(_) => unreachable!(), // We don't use sparse binding, therefore the other
@@ -166,12 +245,53 @@ impl<T: ?Sized> DeviceLocalBuffer<T> {
 
         Ok(Arc::new(DeviceLocalBuffer {
             inner: buffer,
+            size: size,
             memory: mem,
             queue_families: queue_families,
             gpu_lock: Mutex::new(GpuAccess::None),
             marker: PhantomData,
         }))
     }
+
+    // Builds a buffer usable on the family of `queue` and submits a command that fills it with
+    // zeroes.
+    unsafe fn raw_zeroed(
+        size: usize,
+        usage: BufferUsage,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (Arc<DeviceLocalBuffer<T>>, DeviceLocalBufferZeroedFuture),
+        DeviceLocalBufferZeroedError,
+    >
+    where
+        T: Send + Sync + 'static,
+    {
+        // We automatically set `transfer_destination` to true in order to avoid annoying errors.
+        let actual_usage = BufferUsage {
+            transfer_destination: true,
+            ..usage
+        };
+
+        // `vkCmdFillBuffer` can only write multiples of 4 bytes, so the inner buffer is padded
+        // in order for the fill to cover the whole content.
+        let buffer = DeviceLocalBuffer::raw_padded(
+            queue.device().clone(),
+            size,
+            (size + 3) & !3,
+            actual_usage,
+            Some(queue.family()),
+        )?;
+
+        let mut cbb = AutoCommandBufferBuilder::primary_one_time_submit(
+            queue.device().clone(),
+            queue.family(),
+        )
+        .map_err(DeviceMemoryAllocError::from)?;
+        cbb.fill_buffer(buffer.clone(), 0)?;
+        let future = cbb.build()?.execute(queue)?;
+
+        Ok((buffer, future))
+    }
 }
 
 impl<T: ?Sized, A> DeviceLocalBuffer<T, A> {
@@ -212,7 +332,7 @@ where
 
     #[inline]
     fn size(&self) -> usize {
-        self.inner.size()
+        self.size
     }
 
     #[inline]
@@ -326,3 +446,116 @@ where
         self.size().hash(state);
     }
 }
+
+/// Error that can happen when creating a `DeviceLocalBuffer` filled with zeroes.
+#[derive(Debug, Clone)]
+pub enum DeviceLocalBufferZeroedError {
+    /// Allocating the buffer or the command buffer failed.
+    DeviceMemoryAllocError(DeviceMemoryAllocError),
+    /// The `fill_buffer` command couldn't be recorded.
+    FillBufferError(FillBufferError),
+    /// Building the command buffer failed.
+    BuildError(BuildError),
+    /// Submitting the command buffer failed.
+    CommandBufferExecError(CommandBufferExecError),
+}
+
+impl error::Error for DeviceLocalBufferZeroedError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            DeviceLocalBufferZeroedError::DeviceMemoryAllocError(ref err) => Some(err),
+            DeviceLocalBufferZeroedError::FillBufferError(ref err) => Some(err),
+            DeviceLocalBufferZeroedError::BuildError(ref err) => Some(err),
+            DeviceLocalBufferZeroedError::CommandBufferExecError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for DeviceLocalBufferZeroedError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DeviceLocalBufferZeroedError::DeviceMemoryAllocError(_) => {
+                    "allocating the buffer or the command buffer failed"
+                }
+                DeviceLocalBufferZeroedError::FillBufferError(_) => {
+                    "the `fill_buffer` command couldn't be recorded"
+                }
+                DeviceLocalBufferZeroedError::BuildError(_) => {
+                    "building the command buffer failed"
+                }
+                DeviceLocalBufferZeroedError::CommandBufferExecError(_) => {
+                    "submitting the command buffer failed"
+                }
+            }
+        )
+    }
+}
+
+impl From<DeviceMemoryAllocError> for DeviceLocalBufferZeroedError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> DeviceLocalBufferZeroedError {
+        DeviceLocalBufferZeroedError::DeviceMemoryAllocError(err)
+    }
+}
+
+impl From<FillBufferError> for DeviceLocalBufferZeroedError {
+    #[inline]
+    fn from(err: FillBufferError) -> DeviceLocalBufferZeroedError {
+        DeviceLocalBufferZeroedError::FillBufferError(err)
+    }
+}
+
+impl From<BuildError> for DeviceLocalBufferZeroedError {
+    #[inline]
+    fn from(err: BuildError) -> DeviceLocalBufferZeroedError {
+        DeviceLocalBufferZeroedError::BuildError(err)
+    }
+}
+
+impl From<CommandBufferExecError> for DeviceLocalBufferZeroedError {
+    #[inline]
+    fn from(err: CommandBufferExecError) -> DeviceLocalBufferZeroedError {
+        DeviceLocalBufferZeroedError::CommandBufferExecError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::DeviceLocalBuffer;
+    use buffer::TypedBufferAccess;
+    use sync::GpuFuture;
+
+    #[test]
+    fn zeroed_array() {
+        let (_, queue) = gfx_dev_and_queue!();
+
+        let (buffer, future) =
+            DeviceLocalBuffer::<[u32]>::zeroed_array(16, BufferUsage::all(), queue).unwrap();
+        assert_eq!(buffer.len(), 16);
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn zeroed_unaligned_size() {
+        let (_, queue) = gfx_dev_and_queue!();
+
+        let (buffer, future) =
+            DeviceLocalBuffer::<[u8]>::zeroed_array(3, BufferUsage::all(), queue).unwrap();
+        assert_eq!(buffer.len(), 3);
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+}
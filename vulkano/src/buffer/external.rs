@@ -0,0 +1,193 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Buffer that was created outside of vulkano.
+//!
+//! This is useful when integrating with other libraries that use Vulkan, for example a video
+//! decoder that outputs its frames in a buffer. The buffer is not destroyed when the
+//! `ExternalBuffer` is dropped, and the memory it is bound to isn't managed by vulkano.
+
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use buffer::sys::UnsafeBuffer;
+use buffer::traits::BufferAccess;
+use buffer::traits::BufferInner;
+use buffer::BufferUsage;
+use device::Device;
+use device::DeviceOwned;
+use device::Queue;
+use image::ImageAccess;
+use sync::AccessError;
+use vk;
+
+/// Buffer whose handle was created outside of vulkano.
+///
+/// Vulkano doesn't know how the buffer is used by the code that created it. Only the accesses
+/// that are made through vulkano are synchronized, and it is your responsibility to synchronize
+/// them with the other users of the buffer.
+#[derive(Debug)]
+pub struct ExternalBuffer {
+    // Inner content. Doesn't destroy the handle when dropped.
+    inner: UnsafeBuffer,
+
+    // Number of times this buffer is locked on the GPU side.
+    gpu_lock: AtomicUsize,
+}
+
+impl ExternalBuffer {
+    /// Wraps around an existing buffer handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid buffer created from `device` and bound to memory.
+    /// - `size` and `usage` must match the parameters the buffer was created with.
+    /// - The buffer must stay alive for as long as the returned object is alive.
+    /// - The buffer must use the exclusive sharing mode, or concurrent sharing between all the
+    ///   queue families it is going to be used with.
+    ///
+    pub unsafe fn from_raw(
+        device: Arc<Device>,
+        handle: vk::Buffer,
+        size: usize,
+        usage: BufferUsage,
+    ) -> Arc<ExternalBuffer> {
+        Arc::new(ExternalBuffer {
+            inner: UnsafeBuffer::from_raw(device, handle, size, usage),
+            gpu_lock: AtomicUsize::new(0),
+        })
+    }
+}
+
+unsafe impl DeviceOwned for ExternalBuffer {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+unsafe impl BufferAccess for ExternalBuffer {
+    #[inline]
+    fn inner(&self) -> BufferInner<'_> {
+        BufferInner {
+            buffer: &self.inner,
+            offset: 0,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+
+    #[inline]
+    fn conflicts_buffer(&self, other: &dyn BufferAccess) -> bool {
+        self.conflict_key() == other.conflict_key() // TODO:
+    }
+
+    #[inline]
+    fn conflicts_image(&self, other: &dyn ImageAccess) -> bool {
+        false
+    }
+
+    #[inline]
+    fn conflict_key(&self) -> (u64, usize) {
+        (self.inner.key(), 0)
+    }
+
+    #[inline]
+    fn try_gpu_lock(&self, _: bool, _: &Queue) -> Result<(), AccessError> {
+        match self
+            .gpu_lock
+            .compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(_) => Ok(()),
+            Err(_) => Err(AccessError::AlreadyInUse),
+        }
+    }
+
+    #[inline]
+    unsafe fn increase_gpu_lock(&self) {
+        let val = self.gpu_lock.fetch_add(1, Ordering::SeqCst);
+        debug_assert!(val >= 1);
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        let val = self.gpu_lock.fetch_sub(1, Ordering::SeqCst);
+        debug_assert!(val >= 1);
+    }
+}
+
+impl PartialEq for ExternalBuffer {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner() && self.size() == other.size()
+    }
+}
+
+impl Eq for ExternalBuffer {}
+
+impl Hash for ExternalBuffer {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+        self.size().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::external::ExternalBuffer;
+    use buffer::sys::SparseLevel;
+    use buffer::sys::UnsafeBuffer;
+    use buffer::BufferAccess;
+    use buffer::BufferUsage;
+    use std::iter::Empty;
+    use sync::Sharing;
+    use VulkanObject;
+
+    #[test]
+    fn wrap_without_destroying() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let (buffer, _) = unsafe {
+            UnsafeBuffer::new(
+                device.clone(),
+                128,
+                BufferUsage::transfer_source(),
+                Sharing::Exclusive::<Empty<_>>,
+                SparseLevel::none(),
+            )
+            .unwrap()
+        };
+
+        let external = unsafe {
+            ExternalBuffer::from_raw(
+                device.clone(),
+                buffer.internal_object(),
+                128,
+                BufferUsage::transfer_source(),
+            )
+        };
+        assert_eq!(external.size(), 128);
+        assert_eq!(
+            external.inner().buffer.internal_object(),
+            buffer.internal_object()
+        );
+
+        // Dropping the wrapper must not destroy the handle, otherwise destroying `buffer` is a
+        // double free.
+        drop(external);
+        drop(buffer);
+    }
+}
@@ -77,11 +77,17 @@
 //! for how to create a buffer view.
 //!
 
+pub use self::address_table::DeviceAddressTable;
+pub use self::address_table::DeviceAddressTableError;
 pub use self::cpu_access::CpuAccessibleBuffer;
 pub use self::cpu_pool::CpuBufferPool;
 pub use self::device_local::DeviceLocalBuffer;
+pub use self::device_local::DeviceLocalBufferZeroedError;
+pub use self::external::ExternalBuffer;
 pub use self::immutable::ImmutableBuffer;
 pub use self::slice::BufferSlice;
+pub use self::staging_ring::StagingRing;
+pub use self::staging_ring::StagingUploadError;
 pub use self::sys::BufferCreationError;
 pub use self::traits::BufferAccess;
 pub use self::traits::BufferInner;
@@ -89,14 +95,18 @@ pub use self::traits::TypedBufferAccess;
 pub use self::usage::BufferUsage;
 pub use self::view::BufferView;
 pub use self::view::BufferViewRef;
+pub use self::view::TexelBufferView;
 
 pub mod cpu_access;
 pub mod cpu_pool;
 pub mod device_local;
+pub mod external;
 pub mod immutable;
+pub mod staging_ring;
 pub mod sys;
 pub mod view;
 
+mod address_table;
 mod slice;
 mod traits;
 mod usage;
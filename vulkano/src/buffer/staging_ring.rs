@@ -0,0 +1,726 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::cmp;
+use std::collections::VecDeque;
+use std::error;
+use std::fmt;
+use std::iter;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use buffer::sys::BufferCreationError;
+use buffer::sys::SparseLevel;
+use buffer::sys::UnsafeBuffer;
+use buffer::traits::BufferAccess;
+use buffer::traits::BufferInner;
+use buffer::traits::TypedBufferAccess;
+use buffer::BufferUsage;
+use buffer::CpuAccessibleBuffer;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::CopyBufferError;
+use device::Device;
+use device::DeviceOwned;
+use device::Queue;
+use image::ImageAccess;
+use memory::pool::AllocFromRequirementsFilter;
+use memory::pool::AllocLayout;
+use memory::pool::MappingRequirement;
+use memory::pool::MemoryPool;
+use memory::pool::MemoryPoolAlloc;
+use memory::pool::PotentialDedicatedAllocation;
+use memory::pool::StdMemoryPoolAlloc;
+use memory::DedicatedAlloc;
+use memory::DeviceMemoryAllocError;
+use sync::AccessError;
+use sync::Fence;
+use sync::FenceWaitError;
+use sync::Sharing;
+
+use OomError;
+
+/// Host-visible ring buffer that is reused to stage uploads to other buffers.
+///
+/// Each call to `upload` or `upload_iter` writes the data in a free region of the ring and adds
+/// a copy command from that region to the destination. Once the command buffer has been
+/// submitted, call `submitted` with the fence that the submission signals. The regions that were
+/// used since the previous call to `submitted` become available again once this fence is
+/// signaled, and a command buffer that uses them can't be submitted again afterwards.
+///
+/// Uploads that are larger than the ring use a one-off staging buffer instead.
+///
+/// # Arc-like
+///
+/// The `StagingRing` struct internally contains an `Arc`. You can clone the `StagingRing` for
+/// a cheap cost, and all the clones will share the same underlying buffer.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use vulkano::buffer::StagingRing;
+/// use vulkano::sync::Fence;
+/// # use vulkano::buffer::DeviceLocalBuffer;
+/// # use vulkano::command_buffer::AutoCommandBufferBuilder;
+/// # let device: Arc<vulkano::device::Device> = return;
+/// # let destination: Arc<DeviceLocalBuffer<[u32]>> = return;
+/// # let mut builder: AutoCommandBufferBuilder = return;
+///
+/// let ring = StagingRing::new(device.clone(), 1024 * 1024).unwrap();
+/// ring.upload_iter(&mut builder, 0..256u32, destination.clone()).unwrap();
+///
+/// let fence = Arc::new(Fence::alloc(device.clone()).unwrap());
+/// // ... submit the command buffer so that it signals `fence` ...
+/// unsafe {
+///     ring.submitted(fence);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct StagingRing {
+    inner: Arc<RingBuffer>,
+}
+
+// The buffer shared between a `StagingRing` and the regions allocated from it.
+struct RingBuffer {
+    device: Arc<Device>,
+
+    // Inner content.
+    buffer: UnsafeBuffer,
+
+    // The memory held by the buffer. Always mapped.
+    memory: PotentialDedicatedAllocation<StdMemoryPoolAlloc>,
+
+    // Size of the buffer in bytes.
+    capacity: usize,
+
+    state: Mutex<RingState>,
+}
+
+struct RingState {
+    // Offset in bytes where the next region should be allocated.
+    head: usize,
+
+    // Regions that are in use, in the order in which they were allocated.
+    regions: VecDeque<RingRegion>,
+
+    // Identifier of the next region to be allocated.
+    next_id: u64,
+}
+
+struct RingRegion {
+    // Identifies the region, since offsets are reused once regions are released.
+    id: u64,
+
+    offset: usize,
+
+    // True if a submission that reads from the region is being built.
+    gpu_locked: bool,
+
+    // Fence that is signaled when the GPU has finished reading from the region. `None` if the
+    // upload hasn't been submitted yet.
+    fence: Option<Arc<Fence>>,
+}
+
+impl StagingRing {
+    /// Builds a `StagingRing` whose ring buffer has a size of `capacity` bytes.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `capacity` is 0.
+    ///
+    pub fn new(
+        device: Arc<Device>,
+        capacity: usize,
+    ) -> Result<StagingRing, DeviceMemoryAllocError> {
+        assert!(capacity != 0);
+
+        unsafe {
+            let (buffer, mem_reqs) = match UnsafeBuffer::new(
+                device.clone(),
+                capacity,
+                BufferUsage::transfer_source(),
+                Sharing::Exclusive::<iter::Empty<_>>,
+                SparseLevel::none(),
+            ) {
+                Ok(b) => b,
+                Err(BufferCreationError::AllocError(err)) => return Err(err),
+                Err(_) => unreachable!(), // We don't use sparse binding, therefore the other
+                                          // errors can't happen
+            };
+
+            let mem = MemoryPool::alloc_from_requirements(
+                &Device::standard_pool(&device),
+                &mem_reqs,
+                AllocLayout::Linear,
+                MappingRequirement::Map,
+                DedicatedAlloc::Buffer(&buffer),
+                |t| {
+                    if t.is_host_coherent() {
+                        AllocFromRequirementsFilter::Preferred
+                    } else {
+                        AllocFromRequirementsFilter::Allowed
+                    }
+                },
+            )?;
+            debug_assert!((mem.offset() % mem_reqs.alignment) == 0);
+            debug_assert!(mem.mapped_memory().is_some());
+            buffer.bind_memory(mem.memory(), mem.offset())?;
+
+            Ok(StagingRing {
+                inner: Arc::new(RingBuffer {
+                    device,
+                    buffer,
+                    memory: mem,
+                    capacity,
+                    state: Mutex::new(RingState {
+                        head: 0,
+                        regions: VecDeque::new(),
+                        next_id: 0,
+                    }),
+                }),
+            })
+        }
+    }
+
+    /// Returns the size of the ring buffer in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Returns the number of regions of the ring that are still in use.
+    #[inline]
+    pub fn regions_in_use(&self) -> usize {
+        self.inner.state.lock().unwrap().regions.len()
+    }
+
+    /// Writes `data` in the ring and adds to `builder` a command that copies it to `destination`.
+    pub fn upload<T, D, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<P>,
+        data: T,
+        destination: D,
+    ) -> Result<(), StagingUploadError>
+    where
+        T: Send + Sync + 'static,
+        D: TypedBufferAccess<Content = T> + Send + Sync + 'static,
+    {
+        let size = mem::size_of::<T>();
+        if size == 0 {
+            return Ok(());
+        }
+
+        let region = match self.allocate::<T>(size, mem::align_of::<T>())? {
+            Some(region) => region,
+            None => {
+                let staging = CpuAccessibleBuffer::from_data(
+                    self.inner.device.clone(),
+                    BufferUsage::transfer_source(),
+                    false,
+                    data,
+                )?;
+                builder.copy_buffer(staging, destination)?;
+                return Ok(());
+            }
+        };
+
+        unsafe {
+            let start = region.offset + self.inner.memory.offset();
+            let mut mapping = self
+                .inner
+                .memory
+                .mapped_memory()
+                .unwrap()
+                .read_write::<T>(start..start + size);
+            ptr::write(&mut *mapping, data);
+        }
+
+        builder.copy_buffer(region, destination)?;
+        Ok(())
+    }
+
+    /// Writes the elements of `data` in the ring and adds to `builder` a command that copies them
+    /// to `destination`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the length of the iterator didn't match the actual number of elements.
+    ///
+    pub fn upload_iter<T, I, D, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<P>,
+        data: I,
+        destination: D,
+    ) -> Result<(), StagingUploadError>
+    where
+        T: Send + Sync + 'static,
+        I: ExactSizeIterator<Item = T>,
+        D: TypedBufferAccess<Content = [T]> + Send + Sync + 'static,
+    {
+        let len = data.len();
+        let size = len * mem::size_of::<T>();
+        if size == 0 {
+            return Ok(());
+        }
+
+        let region = match self.allocate::<[T]>(size, mem::align_of::<T>())? {
+            Some(region) => region,
+            None => {
+                let staging = CpuAccessibleBuffer::from_iter(
+                    self.inner.device.clone(),
+                    BufferUsage::transfer_source(),
+                    false,
+                    data,
+                )?;
+                builder.copy_buffer(staging, destination)?;
+                return Ok(());
+            }
+        };
+
+        unsafe {
+            let start = region.offset + self.inner.memory.offset();
+            let mut mapping = self
+                .inner
+                .memory
+                .mapped_memory()
+                .unwrap()
+                .read_write::<[T]>(start..start + size);
+
+            let mut written = 0;
+            for (o, i) in mapping.iter_mut().zip(data) {
+                ptr::write(o, i);
+                written += 1;
+            }
+            assert_eq!(
+                written, len,
+                "Iterator passed to StagingRing::upload_iter has a mismatch between reported \
+                 length and actual number of elements"
+            );
+        }
+
+        builder.copy_buffer(region, destination)?;
+        Ok(())
+    }
+
+    /// Marks all the regions used since the previous call to `submitted` as being read by the
+    /// submission that signals `fence`.
+    ///
+    /// The regions become available again once `fence` is signaled. Regions that are never
+    /// associated with a fence are never reused. Command buffers that use these regions can't be
+    /// submitted anymore afterwards.
+    ///
+    /// # Safety
+    ///
+    /// `fence` must be signaled by a submission that happens after all the submissions of the
+    /// command buffers that use these regions, on the same queue. Otherwise the regions could be
+    /// overwritten while the GPU still reads from them.
+    pub unsafe fn submitted(&self, fence: Arc<Fence>) {
+        let mut state = self.inner.state.lock().unwrap();
+        for region in state.regions.iter_mut().filter(|r| r.fence.is_none()) {
+            region.fence = Some(fence.clone());
+        }
+    }
+
+    /// Makes available the regions whose fence has been signaled.
+    ///
+    /// This is automatically called when a region is allocated.
+    pub fn cleanup_finished(&self) -> Result<(), OomError> {
+        let mut state = self.inner.state.lock().unwrap();
+        Self::cleanup_finished_impl(&mut state)
+    }
+
+    fn cleanup_finished_impl(state: &mut RingState) -> Result<(), OomError> {
+        // Regions are freed in the order in which they were allocated, so that the free space
+        // of the ring always stays contiguous.
+        loop {
+            let finished = match state.regions.front() {
+                Some(&RingRegion {
+                    fence: Some(ref fence),
+                    ..
+                }) => fence.ready()?,
+                _ => false,
+            };
+
+            if !finished {
+                break;
+            }
+
+            state.regions.pop_front();
+        }
+
+        if state.regions.is_empty() {
+            state.head = 0;
+        }
+
+        Ok(())
+    }
+
+    // Reserves a region of `size` bytes whose offset is a multiple of `alignment`. Returns `None`
+    // if the upload is larger than the ring, in which case a one-off staging buffer should be used
+    // instead.
+    //
+    // Waits for the oldest regions to be released if the ring is full.
+    fn allocate<T: ?Sized>(
+        &self,
+        size: usize,
+        alignment: usize,
+    ) -> Result<Option<StagingRegion<T>>, StagingUploadError> {
+        let alignment = cmp::max(
+            self.inner
+                .device
+                .physical_device()
+                .limits()
+                .optimal_buffer_copy_offset_alignment() as usize,
+            alignment,
+        );
+
+        if size > self.inner.capacity {
+            return Ok(None);
+        }
+
+        let mut state = self.inner.state.lock().unwrap();
+        Self::cleanup_finished_impl(&mut state)?;
+
+        loop {
+            if let Some(offset) = Self::find_space(&state, self.inner.capacity, size, alignment) {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.head = offset + size;
+                state.regions.push_back(RingRegion {
+                    id,
+                    offset,
+                    gpu_locked: false,
+                    fence: None,
+                });
+
+                return Ok(Some(StagingRegion {
+                    ring: self.inner.clone(),
+                    id,
+                    offset,
+                    size,
+                    marker: PhantomData,
+                }));
+            }
+
+            // The ring is full. Wait for the oldest region to be released. The lock is released
+            // during the wait so that other threads can keep using the ring, so the state must be
+            // checked again afterwards.
+            let fence = match state.regions.front() {
+                Some(&RingRegion {
+                    fence: Some(ref fence),
+                    ..
+                }) => fence.clone(),
+                _ => return Err(StagingUploadError::RingFull),
+            };
+            drop(state);
+            fence.wait(None)?;
+            state = self.inner.state.lock().unwrap();
+            Self::cleanup_finished_impl(&mut state)?;
+        }
+    }
+
+    // Returns the offset of a free range of `size` bytes, if any.
+    fn find_space(
+        state: &RingState,
+        capacity: usize,
+        size: usize,
+        alignment: usize,
+    ) -> Option<usize> {
+        let tail = match state.regions.front() {
+            Some(region) => region.offset,
+            None => return Some(0),
+        };
+
+        let start = (state.head + alignment - 1) / alignment * alignment;
+
+        if state.head > tail {
+            // The used part of the ring is `tail .. head`. Try after it, then wrap around.
+            if start + size <= capacity {
+                Some(start)
+            } else if size <= tail {
+                Some(0)
+            } else {
+                None
+            }
+        } else {
+            // The ring has wrapped around and the used part is `tail .. capacity` and `0 .. head`.
+            if start + size <= tail {
+                Some(start)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+unsafe impl DeviceOwned for StagingRing {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.inner.device
+    }
+}
+
+impl fmt::Debug for StagingRing {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("StagingRing")
+            .field("capacity", &self.inner.capacity)
+            .field("regions_in_use", &self.regions_in_use())
+            .finish()
+    }
+}
+
+// A region of the ring that is the source of a copy command.
+//
+// The region is released by the fence passed to `StagingRing::submitted`, not when this object is
+// dropped.
+struct StagingRegion<T: ?Sized> {
+    ring: Arc<RingBuffer>,
+    id: u64,
+    offset: usize,
+    size: usize,
+    marker: PhantomData<Box<T>>,
+}
+
+unsafe impl<T: ?Sized> BufferAccess for StagingRegion<T> {
+    #[inline]
+    fn inner(&self) -> BufferInner<'_> {
+        BufferInner {
+            buffer: &self.ring.buffer,
+            offset: self.offset,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    fn conflicts_buffer(&self, other: &dyn BufferAccess) -> bool {
+        self.conflict_key() == other.conflict_key()
+    }
+
+    #[inline]
+    fn conflicts_image(&self, _: &dyn ImageAccess) -> bool {
+        false
+    }
+
+    #[inline]
+    fn conflict_key(&self) -> (u64, usize) {
+        (self.ring.buffer.key(), self.offset)
+    }
+
+    #[inline]
+    fn try_gpu_lock(&self, _: bool, _: &Queue) -> Result<(), AccessError> {
+        // Each region is only read by the copy command that it was allocated for, and only by the
+        // first submission of this command. Once the region has been passed to `submitted`, it
+        // may be released and overwritten at any time.
+        let mut state = self.ring.state.lock().unwrap();
+        match state.regions.iter_mut().find(|r| r.id == self.id) {
+            Some(region) if region.fence.is_none() && !region.gpu_locked => {
+                region.gpu_locked = true;
+                Ok(())
+            }
+            _ => Err(AccessError::AlreadyInUse),
+        }
+    }
+
+    #[inline]
+    unsafe fn increase_gpu_lock(&self) {
+        let mut state = self.ring.state.lock().unwrap();
+        if let Some(region) = state.regions.iter_mut().find(|r| r.id == self.id) {
+            debug_assert!(region.fence.is_none());
+            region.gpu_locked = true;
+        }
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        let mut state = self.ring.state.lock().unwrap();
+        if let Some(region) = state.regions.iter_mut().find(|r| r.id == self.id) {
+            region.gpu_locked = false;
+        }
+    }
+}
+
+unsafe impl<T: ?Sized> TypedBufferAccess for StagingRegion<T> {
+    type Content = T;
+}
+
+unsafe impl<T: ?Sized> DeviceOwned for StagingRegion<T> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.ring.device
+    }
+}
+
+/// Error that can happen when uploading data through a `StagingRing`.
+#[derive(Debug, Clone)]
+pub enum StagingUploadError {
+    /// Not enough memory to allocate a one-off staging buffer.
+    AllocError(DeviceMemoryAllocError),
+    /// Error while checking the status of a fence.
+    OomError(OomError),
+    /// Error while waiting for a region of the ring to be released.
+    FenceWaitError(FenceWaitError),
+    /// Error while recording the copy command.
+    CopyBufferError(CopyBufferError),
+    /// The ring is full and none of its regions has been passed to `submitted`.
+    RingFull,
+}
+
+impl error::Error for StagingUploadError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            StagingUploadError::AllocError(ref err) => Some(err),
+            StagingUploadError::OomError(ref err) => Some(err),
+            StagingUploadError::FenceWaitError(ref err) => Some(err),
+            StagingUploadError::CopyBufferError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for StagingUploadError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                StagingUploadError::AllocError(_) => "failed to allocate a staging buffer",
+                StagingUploadError::OomError(_) => "not enough memory",
+                StagingUploadError::FenceWaitError(_) => "failed to wait for a fence",
+                StagingUploadError::CopyBufferError(_) => "failed to record the copy command",
+                StagingUploadError::RingFull => {
+                    "the ring is full and none of its regions has been submitted"
+                }
+            }
+        )
+    }
+}
+
+impl From<DeviceMemoryAllocError> for StagingUploadError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> StagingUploadError {
+        StagingUploadError::AllocError(err)
+    }
+}
+
+impl From<OomError> for StagingUploadError {
+    #[inline]
+    fn from(err: OomError) -> StagingUploadError {
+        StagingUploadError::OomError(err)
+    }
+}
+
+impl From<FenceWaitError> for StagingUploadError {
+    #[inline]
+    fn from(err: FenceWaitError) -> StagingUploadError {
+        StagingUploadError::FenceWaitError(err)
+    }
+}
+
+impl From<CopyBufferError> for StagingUploadError {
+    #[inline]
+    fn from(err: CopyBufferError) -> StagingUploadError {
+        StagingUploadError::CopyBufferError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferAccess;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use buffer::StagingRing;
+    use buffer::StagingUploadError;
+    use command_buffer::AutoCommandBufferBuilder;
+    use std::sync::Arc;
+    use sync::AccessError;
+    use sync::Fence;
+
+    #[test]
+    fn reuse_after_fence() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let ring = StagingRing::new(device.clone(), 1024).unwrap();
+        let destination = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_destination(),
+            false,
+            (0..128).map(|_| 0u32),
+        )
+        .unwrap();
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+                .unwrap();
+        ring.upload_iter(&mut builder, 0..128u32, destination.clone())
+            .unwrap();
+        assert_eq!(ring.regions_in_use(), 1);
+
+        // Fills the rest of the ring, then fails because nothing was submitted.
+        ring.upload_iter(&mut builder, 0..128u32, destination.clone())
+            .unwrap();
+        match ring.upload_iter(&mut builder, 0..128u32, destination.clone()) {
+            Err(StagingUploadError::RingFull) => (),
+            _ => panic!(),
+        }
+
+        // Larger than the ring, so uses a one-off buffer.
+        let large = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_destination(),
+            false,
+            (0..1024).map(|_| 0u32),
+        )
+        .unwrap();
+        ring.upload_iter(&mut builder, 0..1024u32, large).unwrap();
+        assert_eq!(ring.regions_in_use(), 2);
+
+        let fence = Arc::new(Fence::alloc_signaled(device.clone()).unwrap());
+        unsafe {
+            ring.submitted(fence);
+        }
+        ring.cleanup_finished().unwrap();
+        assert_eq!(ring.regions_in_use(), 0);
+    }
+
+    #[test]
+    fn submitted_region_cant_be_locked() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let ring = StagingRing::new(device.clone(), 1024).unwrap();
+        let region = ring.allocate::<[u32]>(16, 4).unwrap().unwrap();
+
+        region.try_gpu_lock(false, &queue).unwrap();
+        match region.try_gpu_lock(false, &queue) {
+            Err(AccessError::AlreadyInUse) => (),
+            _ => panic!(),
+        }
+        unsafe {
+            region.unlock();
+        }
+
+        // The region may be released once submitted, so a later submission can't read it.
+        let fence = Arc::new(Fence::alloc(device.clone()).unwrap());
+        unsafe {
+            ring.submitted(fence);
+        }
+        match region.try_gpu_lock(false, &queue) {
+            Err(AccessError::AlreadyInUse) => (),
+            _ => panic!(),
+        }
+    }
+}
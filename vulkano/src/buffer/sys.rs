@@ -54,6 +54,9 @@ pub struct UnsafeBuffer {
     device: Arc<Device>,
     size: usize,
     usage: vk::BufferUsageFlags,
+    // `vkDestroyBuffer` is called only if `needs_destruction` is true.
+    needs_destruction: bool,
+    protected: bool,
 }
 
 impl UnsafeBuffer {
@@ -67,59 +70,61 @@ impl UnsafeBuffer {
     /// - Panics if `usage` is empty.
     ///
     pub unsafe fn new<'a, I>(
+        device: Arc<Device>,
+        size: usize,
+        usage: BufferUsage,
+        sharing: Sharing<I>,
+        sparse: SparseLevel,
+    ) -> Result<(UnsafeBuffer, MemoryRequirements), BufferCreationError>
+    where
+        I: Iterator<Item = u32>,
+    {
+        UnsafeBuffer::new_impl(device, size, usage, sharing, sparse, false)
+    }
+
+    /// Creates a new protected buffer of the given size.
+    ///
+    /// A protected buffer must be bound to protected memory, and can only be accessed by
+    /// protected command buffers. Protected buffers can't be sparse.
+    ///
+    /// See the module's documentation for information about safety.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `usage` is empty.
+    ///
+    pub unsafe fn new_protected<'a, I>(
+        device: Arc<Device>,
+        size: usize,
+        usage: BufferUsage,
+        sharing: Sharing<I>,
+    ) -> Result<(UnsafeBuffer, MemoryRequirements), BufferCreationError>
+    where
+        I: Iterator<Item = u32>,
+    {
+        if !device.enabled_features().protected_memory {
+            return Err(BufferCreationError::ProtectedMemoryFeatureNotEnabled);
+        }
+
+        UnsafeBuffer::new_impl(device, size, usage, sharing, SparseLevel::none(), true)
+    }
+
+    unsafe fn new_impl<'a, I>(
         device: Arc<Device>,
         size: usize,
         mut usage: BufferUsage,
         sharing: Sharing<I>,
         sparse: SparseLevel,
+        protected: bool,
     ) -> Result<(UnsafeBuffer, MemoryRequirements), BufferCreationError>
     where
         I: Iterator<Item = u32>,
     {
         let vk = device.pointers();
 
-        // Ensure we're not trying to create an empty buffer.
-        let size = if size == 0 {
-            // To avoid panicking when allocating 0 bytes, use a 1-byte buffer.
-            1
-        } else {
-            size
-        };
-
-        // Checking sparse features.
-        assert!(
-            sparse.sparse || !sparse.sparse_residency,
-            "Can't enable sparse residency without enabling sparse binding as well"
-        );
-        assert!(
-            sparse.sparse || !sparse.sparse_aliased,
-            "Can't enable sparse aliasing without enabling sparse binding as well"
-        );
-        if sparse.sparse && !device.enabled_features().sparse_binding {
-            return Err(BufferCreationError::SparseBindingFeatureNotEnabled);
-        }
-        if sparse.sparse_residency && !device.enabled_features().sparse_residency_buffer {
-            return Err(BufferCreationError::SparseResidencyBufferFeatureNotEnabled);
-        }
-        if sparse.sparse_aliased && !device.enabled_features().sparse_residency_aliased {
-            return Err(BufferCreationError::SparseResidencyAliasedFeatureNotEnabled);
-        }
-        if usage.device_address && !device.enabled_features().buffer_device_address {
-            usage.device_address = false;
-            if usage.to_vulkan_bits() == 0 {
-                // return an error iff device_address was the only requested usage and the
-                // feature isn't enabled. Otherwise we'll hit that assert below.
-                return Err(BufferCreationError::DeviceAddressFeatureNotEnabled);
-            }
-        }
+        let size = UnsafeBuffer::check_parameters(&device, size, &mut usage, sparse)?;
         let usage_bits = usage.to_vulkan_bits();
 
-        // Checking for empty BufferUsage.
-        assert!(
-            usage_bits != 0,
-            "Can't create buffer with empty BufferUsage"
-        );
-
         let buffer = {
             let (sh_mode, sh_indices) = match sharing {
                 Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
@@ -129,7 +134,12 @@ impl UnsafeBuffer {
             let infos = vk::BufferCreateInfo {
                 sType: vk::STRUCTURE_TYPE_BUFFER_CREATE_INFO,
                 pNext: ptr::null(),
-                flags: sparse.to_flags(),
+                flags: sparse.to_flags()
+                    | if protected {
+                        vk::BUFFER_CREATE_PROTECTED_BIT
+                    } else {
+                        0
+                    },
                 size: size as u64,
                 usage: usage_bits,
                 sharingMode: sh_mode,
@@ -148,11 +158,6 @@ impl UnsafeBuffer {
         };
 
         let mem_reqs = {
-            #[inline]
-            fn align(val: usize, al: usize) -> usize {
-                al * (1 + (val - 1) / al)
-            }
-
             let mut output = if device.loaded_extensions().khr_get_memory_requirements2 {
                 let infos = vk::BufferMemoryRequirementsInfo2KHR {
                     sType: vk::STRUCTURE_TYPE_BUFFER_MEMORY_REQUIREMENTS_INFO_2_KHR,
@@ -203,28 +208,7 @@ impl UnsafeBuffer {
                 MemoryRequirements::from_vulkan_reqs(output)
             };
 
-            // We have to manually enforce some additional requirements for some buffer types.
-            let limits = device.physical_device().limits();
-            if usage.uniform_texel_buffer || usage.storage_texel_buffer {
-                output.alignment = align(
-                    output.alignment,
-                    limits.min_texel_buffer_offset_alignment() as usize,
-                );
-            }
-
-            if usage.storage_buffer {
-                output.alignment = align(
-                    output.alignment,
-                    limits.min_storage_buffer_offset_alignment() as usize,
-                );
-            }
-
-            if usage.uniform_buffer {
-                output.alignment = align(
-                    output.alignment,
-                    limits.min_uniform_buffer_offset_alignment() as usize,
-                );
-            }
+            UnsafeBuffer::enforce_alignment(&device, usage, &mut output);
 
             output
         };
@@ -234,11 +218,217 @@ impl UnsafeBuffer {
             device: device.clone(),
             size: size as usize,
             usage: usage_bits,
+            needs_destruction: true,
+            protected,
         };
 
         Ok((obj, mem_reqs))
     }
 
+    // Checks the parameters of a buffer creation, and returns the actual size of the buffer.
+    //
+    // `usage.device_address` is removed if the feature isn't enabled.
+    fn check_parameters(
+        device: &Device,
+        size: usize,
+        usage: &mut BufferUsage,
+        sparse: SparseLevel,
+    ) -> Result<usize, BufferCreationError> {
+        // Ensure we're not trying to create an empty buffer.
+        let size = if size == 0 {
+            // To avoid panicking when allocating 0 bytes, use a 1-byte buffer.
+            1
+        } else {
+            size
+        };
+
+        // Checking sparse features.
+        assert!(
+            sparse.sparse || !sparse.sparse_residency,
+            "Can't enable sparse residency without enabling sparse binding as well"
+        );
+        assert!(
+            sparse.sparse || !sparse.sparse_aliased,
+            "Can't enable sparse aliasing without enabling sparse binding as well"
+        );
+        if sparse.sparse && !device.enabled_features().sparse_binding {
+            return Err(BufferCreationError::SparseBindingFeatureNotEnabled);
+        }
+        if sparse.sparse_residency && !device.enabled_features().sparse_residency_buffer {
+            return Err(BufferCreationError::SparseResidencyBufferFeatureNotEnabled);
+        }
+        if sparse.sparse_aliased && !device.enabled_features().sparse_residency_aliased {
+            return Err(BufferCreationError::SparseResidencyAliasedFeatureNotEnabled);
+        }
+        if usage.device_address && !device.enabled_features().buffer_device_address {
+            usage.device_address = false;
+            if usage.to_vulkan_bits() == 0 {
+                // return an error iff device_address was the only requested usage and the
+                // feature isn't enabled. Otherwise we'll hit that assert below.
+                return Err(BufferCreationError::DeviceAddressFeatureNotEnabled);
+            }
+        }
+
+        // Checking for empty BufferUsage.
+        assert!(
+            usage.to_vulkan_bits() != 0,
+            "Can't create buffer with empty BufferUsage"
+        );
+
+        Ok(size)
+    }
+
+    // Adjusts the alignment of `reqs` to the limits of the device that apply to `usage`.
+    fn enforce_alignment(device: &Device, usage: BufferUsage, reqs: &mut MemoryRequirements) {
+        #[inline]
+        fn align(val: usize, al: usize) -> usize {
+            al * (1 + (val - 1) / al)
+        }
+
+        // We have to manually enforce some additional requirements for some buffer types.
+        let limits = device.physical_device().limits();
+        if usage.uniform_texel_buffer || usage.storage_texel_buffer {
+            reqs.alignment = align(
+                reqs.alignment,
+                limits.min_texel_buffer_offset_alignment() as usize,
+            );
+        }
+
+        if usage.storage_buffer {
+            reqs.alignment = align(
+                reqs.alignment,
+                limits.min_storage_buffer_offset_alignment() as usize,
+            );
+        }
+
+        if usage.uniform_buffer {
+            reqs.alignment = align(
+                reqs.alignment,
+                limits.min_uniform_buffer_offset_alignment() as usize,
+            );
+        }
+    }
+
+    /// Returns the memory requirements of a buffer with the given parameters, without keeping
+    /// the buffer around.
+    ///
+    /// If the `maintenance4` feature is enabled on the device, the requirements are queried from
+    /// the parameters directly with `vkGetDeviceBufferMemoryRequirementsKHR`. Otherwise a
+    /// temporary buffer is created, queried and destroyed.
+    ///
+    /// The parameters are checked the same way as in `new`, and the returned requirements are
+    /// the same as the ones that `new` would return.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `sparse.sparse` is false and `sparse.sparse_residency` or `sparse.sparse_aliased` is true.
+    /// - Panics if `usage` is empty.
+    ///
+    pub fn memory_requirements<I>(
+        device: Arc<Device>,
+        size: usize,
+        mut usage: BufferUsage,
+        sharing: Sharing<I>,
+        sparse: SparseLevel,
+    ) -> Result<MemoryRequirements, BufferCreationError>
+    where
+        I: Iterator<Item = u32>,
+    {
+        if !device.enabled_features().maintenance4 {
+            let (_, reqs) = unsafe { UnsafeBuffer::new(device, size, usage, sharing, sparse)? };
+            return Ok(reqs);
+        }
+
+        let size = UnsafeBuffer::check_parameters(&device, size, &mut usage, sparse)?;
+
+        let (sh_mode, sh_indices) = match sharing {
+            Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
+            Sharing::Concurrent(ids) => (vk::SHARING_MODE_CONCURRENT, ids.collect()),
+        };
+
+        let create_info = vk::BufferCreateInfo {
+            sType: vk::STRUCTURE_TYPE_BUFFER_CREATE_INFO,
+            pNext: ptr::null(),
+            flags: sparse.to_flags(),
+            size: size as u64,
+            usage: usage.to_vulkan_bits(),
+            sharingMode: sh_mode,
+            queueFamilyIndexCount: sh_indices.len() as u32,
+            pQueueFamilyIndices: sh_indices.as_ptr(),
+        };
+
+        let infos = vk::DeviceBufferMemoryRequirementsKHR {
+            sType: vk::STRUCTURE_TYPE_DEVICE_BUFFER_MEMORY_REQUIREMENTS_KHR,
+            pNext: ptr::null(),
+            pCreateInfo: &create_info,
+        };
+
+        let mut reqs = unsafe {
+            let mut output2 = if device.loaded_extensions().khr_dedicated_allocation {
+                Some(vk::MemoryDedicatedRequirementsKHR {
+                    sType: vk::STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR,
+                    pNext: ptr::null(),
+                    prefersDedicatedAllocation: mem::zeroed(),
+                    requiresDedicatedAllocation: mem::zeroed(),
+                })
+            } else {
+                None
+            };
+
+            let mut output = vk::MemoryRequirements2KHR {
+                sType: vk::STRUCTURE_TYPE_MEMORY_REQUIREMENTS_2_KHR,
+                pNext: output2
+                    .as_mut()
+                    .map(|o| o as *mut vk::MemoryDedicatedRequirementsKHR)
+                    .unwrap_or(ptr::null_mut()) as *mut _,
+                memoryRequirements: mem::zeroed(),
+            };
+
+            let vk = device.pointers();
+            vk.GetDeviceBufferMemoryRequirementsKHR(device.internal_object(), &infos, &mut output);
+            debug_assert!(output.memoryRequirements.size >= size as u64);
+            debug_assert!(output.memoryRequirements.memoryTypeBits != 0);
+
+            let mut out = MemoryRequirements::from_vulkan_reqs(output.memoryRequirements);
+            if let Some(output2) = output2 {
+                debug_assert_eq!(output2.requiresDedicatedAllocation, 0);
+                out.prefer_dedicated = output2.prefersDedicatedAllocation != 0;
+            }
+            out
+        };
+
+        UnsafeBuffer::enforce_alignment(&device, usage, &mut reqs);
+        Ok(reqs)
+    }
+
+    /// Creates a buffer from a raw handle. The buffer won't be destroyed.
+    ///
+    /// This is meant to be used with buffers that were created outside of vulkano, for example
+    /// by another library that uses the same device.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid buffer created from `device`.
+    /// - `size` and `usage` must match the parameters the buffer was created with.
+    /// - The buffer must stay alive and bound to memory for as long as the returned object is
+    ///   alive.
+    ///
+    pub unsafe fn from_raw(
+        device: Arc<Device>,
+        handle: vk::Buffer,
+        size: usize,
+        usage: BufferUsage,
+    ) -> UnsafeBuffer {
+        UnsafeBuffer {
+            buffer: handle,
+            device,
+            size,
+            usage: usage.to_vulkan_bits(),
+            needs_destruction: false,
+            protected: false,
+        }
+    }
+
     pub unsafe fn bind_memory(&self, memory: &DeviceMemory, offset: usize) -> Result<(), OomError> {
         let vk = self.device.pointers();
 
@@ -286,6 +476,12 @@ impl UnsafeBuffer {
         self.size
     }
 
+    /// Returns true if the buffer was created with `new_protected`.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
     #[inline]
     pub fn usage_transfer_source(&self) -> bool {
         (self.usage & vk::BUFFER_USAGE_TRANSFER_SRC_BIT) != 0
@@ -371,6 +567,10 @@ impl fmt::Debug for UnsafeBuffer {
 impl Drop for UnsafeBuffer {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyBuffer(self.device.internal_object(), self.buffer, ptr::null());
@@ -452,6 +652,8 @@ pub enum BufferCreationError {
     SparseResidencyAliasedFeatureNotEnabled,
     /// Device address was requested but the corresponding feature wasn't enabled.
     DeviceAddressFeatureNotEnabled,
+    /// A protected buffer was requested but the `protected_memory` feature wasn't enabled.
+    ProtectedMemoryFeatureNotEnabled,
 }
 
 impl error::Error for BufferCreationError {
@@ -484,6 +686,10 @@ impl fmt::Display for BufferCreationError {
                 BufferCreationError::DeviceAddressFeatureNotEnabled => {
                     "device address was requested but the corresponding feature wasn't enabled"
                 }
+                BufferCreationError::ProtectedMemoryFeatureNotEnabled => {
+                    "a protected buffer was requested but the `protected_memory` feature wasn't \
+                     enabled"
+                }
             }
         )
     }
@@ -543,6 +749,52 @@ mod tests {
         assert_eq!(&**buf.device() as *const Device, &*device as *const Device);
     }
 
+    #[test]
+    fn memory_requirements_without_buffer() {
+        let (device, _) = gfx_dev_and_queue!();
+        let (_, reqs) = unsafe {
+            UnsafeBuffer::new(
+                device.clone(),
+                128,
+                BufferUsage::all(),
+                Sharing::Exclusive::<Empty<_>>,
+                SparseLevel::none(),
+            )
+        }
+        .unwrap();
+
+        let queried = UnsafeBuffer::memory_requirements(
+            device,
+            128,
+            BufferUsage::all(),
+            Sharing::Exclusive::<Empty<_>>,
+            SparseLevel::none(),
+        )
+        .unwrap();
+
+        assert_eq!(queried.size, reqs.size);
+        assert_eq!(queried.alignment, reqs.alignment);
+        assert_eq!(queried.memory_type_bits, reqs.memory_type_bits);
+    }
+
+    #[test]
+    fn protected_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let result = unsafe {
+            UnsafeBuffer::new_protected(
+                device,
+                128,
+                BufferUsage::all(),
+                Sharing::Exclusive::<Empty<_>>,
+            )
+        };
+
+        match result {
+            Err(BufferCreationError::ProtectedMemoryFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn panic_wrong_sparse_residency() {
         let (device, _) = gfx_dev_and_queue!();
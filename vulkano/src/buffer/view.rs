@@ -48,8 +48,10 @@ use buffer::BufferInner;
 use buffer::TypedBufferAccess;
 use device::Device;
 use device::DeviceOwned;
+use format::Format;
 use format::FormatDesc;
 use format::StrongStorage;
+use format::TexelFormat;
 
 use check_errors;
 use vk;
@@ -128,27 +130,14 @@ where
                 }
             }
 
-            let format_props = {
-                let vk_i = device.instance().pointers();
-                let mut output = MaybeUninit::uninit();
-                vk_i.GetPhysicalDeviceFormatProperties(
-                    device.physical_device().internal_object(),
-                    format as u32,
-                    output.as_mut_ptr(),
-                );
-                output.assume_init().bufferFeatures
-            };
+            let format_props = format.properties(device.physical_device()).buffer_features;
 
-            if buffer.usage_uniform_texel_buffer() {
-                if (format_props & vk::FORMAT_FEATURE_UNIFORM_TEXEL_BUFFER_BIT) == 0 {
-                    return Err(BufferViewCreationError::UnsupportedFormat);
-                }
+            if buffer.usage_uniform_texel_buffer() && !format_props.uniform_texel_buffer {
+                return Err(BufferViewCreationError::UnsupportedFormat);
             }
 
-            if buffer.usage_storage_texel_buffer() {
-                if (format_props & vk::FORMAT_FEATURE_STORAGE_TEXEL_BUFFER_BIT) == 0 {
-                    return Err(BufferViewCreationError::UnsupportedFormat);
-                }
+            if buffer.usage_storage_texel_buffer() && !format_props.storage_texel_buffer {
+                return Err(BufferViewCreationError::UnsupportedFormat);
             }
 
             let infos = vk::BufferViewCreateInfo {
@@ -176,8 +165,7 @@ where
             view: view,
             buffer: org_buffer,
             marker: PhantomData,
-            atomic_accesses: (format_props & vk::FORMAT_FEATURE_STORAGE_TEXEL_BUFFER_ATOMIC_BIT)
-                != 0,
+            atomic_accesses: format_props.storage_texel_buffer_atomic,
         })
     }
 
@@ -259,6 +247,117 @@ where
     }
 }
 
+/// A buffer view whose format is deduced from the type of the texels of the buffer.
+///
+/// The format is given by the `TexelFormat` implementation of `T`, for example a buffer of
+/// `[f32; 4]` is viewed as `R32G32B32A32Sfloat`. Whether the device supports that format for the
+/// usages of the buffer is checked when the view is created.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// use vulkano::buffer::immutable::ImmutableBuffer;
+/// use vulkano::buffer::BufferUsage;
+/// use vulkano::buffer::TexelBufferView;
+///
+/// # let queue: Arc<vulkano::device::Queue> = return;
+/// let usage = BufferUsage {
+///     uniform_texel_buffer: true,
+///     .. BufferUsage::none()
+/// };
+///
+/// let (buffer, _future) = ImmutableBuffer::from_iter((0..128).map(|n| [n as f32; 4]), usage,
+///                                                    queue.clone()).unwrap();
+/// let _view = TexelBufferView::new(buffer).unwrap();
+/// ```
+pub struct TexelBufferView<T, B>
+where
+    T: TexelFormat,
+    B: BufferAccess,
+{
+    view: BufferView<T::Format, B>,
+}
+
+impl<T, B> TexelBufferView<T, B>
+where
+    T: TexelFormat,
+    B: TypedBufferAccess<Content = [T]>,
+{
+    /// Builds a new buffer view with the format that corresponds to `T`.
+    ///
+    /// Returns `UnsupportedFormat` if the format doesn't support the `uniform_texel_buffer` or
+    /// `storage_texel_buffer` buffer feature that the usage of the buffer requires.
+    #[inline]
+    pub fn new(buffer: B) -> Result<TexelBufferView<T, B>, BufferViewCreationError> {
+        Ok(TexelBufferView {
+            view: BufferView::new(buffer, T::Format::default())?,
+        })
+    }
+}
+
+impl<T, B> TexelBufferView<T, B>
+where
+    T: TexelFormat,
+    B: BufferAccess,
+{
+    /// Returns the format of the view.
+    #[inline]
+    pub fn format(&self) -> Format {
+        T::Format::default().format()
+    }
+
+    /// Returns the underlying untyped view.
+    #[inline]
+    pub fn view(&self) -> &BufferView<T::Format, B> {
+        &self.view
+    }
+
+    /// Returns the buffer associated to this view.
+    #[inline]
+    pub fn buffer(&self) -> &B {
+        self.view.buffer()
+    }
+}
+
+unsafe impl<T, B> BufferViewRef for TexelBufferView<T, B>
+where
+    T: TexelFormat,
+    B: BufferAccess,
+{
+    type BufferAccess = B;
+    type Format = T::Format;
+
+    #[inline]
+    fn view(&self) -> &BufferView<T::Format, B> {
+        &self.view
+    }
+}
+
+unsafe impl<T, B> DeviceOwned for TexelBufferView<T, B>
+where
+    T: TexelFormat,
+    B: BufferAccess,
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.view.device()
+    }
+}
+
+impl<T, B> fmt::Debug for TexelBufferView<T, B>
+where
+    T: TexelFormat,
+    B: BufferAccess + fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("TexelBufferView")
+            .field("format", &self.format())
+            .field("view", &self.view)
+            .finish()
+    }
+}
+
 pub unsafe trait BufferViewRef {
     type BufferAccess: BufferAccess;
     type Format;
@@ -279,17 +378,17 @@ where
     }
 }
 
-unsafe impl<T, F, B> BufferViewRef for T
+unsafe impl<T> BufferViewRef for T
 where
-    T: SafeDeref<Target = BufferView<F, B>>,
-    B: BufferAccess,
+    T: SafeDeref,
+    T::Target: BufferViewRef,
 {
-    type BufferAccess = B;
-    type Format = F;
+    type BufferAccess = <T::Target as BufferViewRef>::BufferAccess;
+    type Format = <T::Target as BufferViewRef>::Format;
 
     #[inline]
-    fn view(&self) -> &BufferView<F, B> {
-        &**self
+    fn view(&self) -> &BufferView<Self::Format, Self::BufferAccess> {
+        (**self).view()
     }
 }
 
@@ -368,6 +467,7 @@ impl From<Error> for BufferViewCreationError {
 mod tests {
     use buffer::immutable::ImmutableBuffer;
     use buffer::view::BufferViewCreationError;
+    use buffer::view::TexelBufferView;
     use buffer::BufferUsage;
     use buffer::BufferView;
     use format;
@@ -467,4 +567,26 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn texel_format_deduced() {
+        // `VK_FORMAT_R32G32B32A32_SFLOAT` guaranteed to be a supported format
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let usage = BufferUsage {
+            uniform_texel_buffer: true,
+            ..BufferUsage::none()
+        };
+
+        let (buffer, _) = ImmutableBuffer::<[[f32; 4]]>::from_iter(
+            (0..128).map(|_| [0.0; 4]),
+            usage,
+            queue.clone(),
+        )
+        .unwrap();
+        let view = TexelBufferView::new(buffer).unwrap();
+
+        assert_eq!(view.format(), format::Format::R32G32B32A32Sfloat);
+        assert!(view.view().uniform_texel_buffer());
+    }
 }
@@ -7,10 +7,13 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use smallvec::SmallVec;
+use std::collections::HashMap;
 use std::error;
 use std::fmt;
 use std::iter;
 use std::mem;
+use std::panic;
 use std::slice;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
@@ -35,13 +38,18 @@ use command_buffer::sys::UnsafeCommandBufferBuilderImageAspect;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
 use command_buffer::validity::*;
+use command_buffer::BufferImageCopyLayout;
 use command_buffer::CommandBuffer;
 use command_buffer::CommandBufferExecError;
+use command_buffer::DispatchIndirectCommand;
 use command_buffer::DrawIndexedIndirectCommand;
 use command_buffer::DrawIndirectCommand;
 use command_buffer::DynamicState;
+use command_buffer::ImageBlit;
+use command_buffer::ImageCopy;
 use command_buffer::StateCacher;
 use command_buffer::StateCacherOutcome;
+use descriptor::descriptor::ShaderStages;
 use descriptor::descriptor_set::DescriptorSetsCollection;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use device::Device;
@@ -51,6 +59,7 @@ use format::AcceptsPixels;
 use format::ClearValue;
 use format::Format;
 use format::FormatTy;
+use framebuffer::AttachmentDescription;
 use framebuffer::EmptySinglePassRenderPassDesc;
 use framebuffer::Framebuffer;
 use framebuffer::FramebufferAbstract;
@@ -65,16 +74,24 @@ use image::ImageAccess;
 use image::ImageLayout;
 use instance::QueueFamily;
 use pipeline::input_assembly::Index;
+use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::raster::CullMode;
+use pipeline::raster::FrontFace;
 use pipeline::vertex::VertexSource;
 use pipeline::ComputePipelineAbstract;
 use pipeline::GraphicsPipelineAbstract;
 use query::QueryPipelineStatisticFlags;
+use query::QueryResultFlags;
+use query::UnsafeQueryPool;
 use sampler::Filter;
 use sync::AccessCheckError;
 use sync::AccessFlagBits;
+use sync::Event;
 use sync::GpuFuture;
 use sync::PipelineStages;
+use vk;
 use OomError;
+use VulkanObject;
 
 /// Note that command buffers allocated from the default command pool (`Arc<StandardCommandPool>`)
 /// don't implement the `Send` and `Sync` traits. If you use this pool, then the
@@ -102,6 +119,80 @@ pub struct AutoCommandBufferBuilder<P = StandardCommandPoolBuilder> {
 
     // Flags passed when creating the command buffer.
     flags: Flags,
+
+    // Queue family the command buffer is allocated from.
+    queue_family_id: u32,
+
+    // Events that were set or reset in this command buffer, with the stages passed to the last
+    // `set_event`, or `None` if the last command was `reset_event`.
+    events: HashMap<vk::Event, (Arc<Event>, Option<PipelineStages>)>,
+
+    // Events that are waited on before being set in this command buffer, with the stages they
+    // were set with when the wait was recorded.
+    waited_events: Vec<(Arc<Event>, PipelineStages)>,
+
+    // Vertex buffers bound by the last `bind_vertex_buffers_with_stride` and their strides, or
+    // `None` if vertex buffers were bound without strides since then.
+    vertex_strides: Option<VertexStrides>,
+
+    // Extended dynamic states set since the last time a pipeline with these states as static was
+    // bound.
+    extended_dynamic_state: ExtendedDynamicState,
+}
+
+// Vertex buffers bound with strides, identified by their buffer, offset and size.
+struct VertexStrides {
+    buffers: SmallVec<[(vk::Buffer, usize, usize); 4]>,
+    strides: SmallVec<[u32; 4]>,
+}
+
+// Returns the key that identifies a vertex buffer in `VertexStrides`.
+#[inline]
+fn vertex_buffer_key(buffer: &dyn BufferAccess) -> (vk::Buffer, usize, usize) {
+    let inner = buffer.inner();
+    (inner.buffer.internal_object(), inner.offset, buffer.size())
+}
+
+// Binding a pipeline overwrites the extended dynamic states that it has as static, so they must
+// be set again before drawing with a pipeline that has them as dynamic.
+fn clear_static_extended_states<Gp>(state: &mut ExtendedDynamicState, pipeline: &Gp)
+where
+    Gp: GraphicsPipelineAbstract,
+{
+    if !pipeline.has_dynamic_cull_mode() {
+        state.cull_mode = None;
+    }
+    if !pipeline.has_dynamic_front_face() {
+        state.front_face = None;
+    }
+    if !pipeline.has_dynamic_primitive_topology() {
+        state.primitive_topology = None;
+    }
+    if !pipeline.has_dynamic_depth_test_enable() {
+        state.depth_test_enable = None;
+    }
+    if !pipeline.has_dynamic_depth_write_enable() {
+        state.depth_write_enable = None;
+    }
+}
+
+// Returns the strides of `bound` if `vertex_buffers` are the buffers that were bound with them.
+fn bound_vertex_strides<'a>(
+    bound: &'a Option<VertexStrides>,
+    vertex_buffers: &[Box<dyn BufferAccess + Send + Sync>],
+) -> Option<&'a [u32]> {
+    let bound = bound.as_ref()?;
+    if bound.buffers.len() != vertex_buffers.len()
+        || bound
+            .buffers
+            .iter()
+            .zip(vertex_buffers.iter())
+            .any(|(&key, buffer)| key != vertex_buffer_key(&**buffer))
+    {
+        return None;
+    }
+
+    Some(&bound.strides)
 }
 
 impl AutoCommandBufferBuilder<StandardCommandPoolBuilder> {
@@ -393,6 +484,115 @@ impl AutoCommandBufferBuilder<StandardCommandPoolBuilder> {
         AutoCommandBufferBuilder::with_flags(device, queue_family, kind, Flags::SimultaneousUse)
     }
 
+    /// Records `count` secondary graphics command buffers in parallel, one per thread.
+    ///
+    /// For each index between 0 and `count`, a new thread starts building a one-time-submit
+    /// secondary command buffer that inherits `subpass` and `framebuffer`, then calls `record`
+    /// with the index and the builder. The closure is expected to record its commands and return
+    /// the result of `build()`. Each thread allocates from its own command pool.
+    ///
+    /// The returned command buffers are in the order of the indices, and can be executed inside
+    /// the subpass of a primary command buffer with `execute_commands_from_vec` after calling
+    /// `begin_render_pass` with `secondary` set to `true`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the calls to `record` panics.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use vulkano::command_buffer::AutoCommandBufferBuilder;
+    /// # use vulkano::device::Device;
+    /// # use vulkano::framebuffer::FramebufferAbstract;
+    /// # use vulkano::framebuffer::RenderPassAbstract;
+    /// # use vulkano::framebuffer::Subpass;
+    /// # use vulkano::instance::QueueFamily;
+    /// # fn example(device: Arc<Device>, queue_family: QueueFamily,
+    /// #            subpass: Subpass<Arc<dyn RenderPassAbstract + Send + Sync>>,
+    /// #            framebuffer: Arc<dyn FramebufferAbstract + Send + Sync>)
+    /// #            -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let command_buffers = AutoCommandBufferBuilder::secondary_graphics_parallel(
+    ///     device,
+    ///     queue_family,
+    ///     subpass,
+    ///     framebuffer,
+    ///     4,
+    ///     |index, builder| -> Result<_, Box<dyn std::error::Error + Send + Sync>> {
+    ///         // Record the draw commands of the `index`-th chunk of the scene here.
+    ///         Ok(builder.build()?)
+    ///     },
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn secondary_graphics_parallel<R, F, C, E>(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        subpass: Subpass<R>,
+        framebuffer: F,
+        count: usize,
+        record: C,
+    ) -> Result<Vec<AutoCommandBuffer>, E>
+    where
+        R: RenderPassAbstract + Clone + Send + Sync + 'static,
+        F: FramebufferAbstract + Clone + Send + Sync + 'static,
+        C: Fn(
+                usize,
+                AutoCommandBufferBuilder<StandardCommandPoolBuilder>,
+            ) -> Result<AutoCommandBuffer, E>
+            + Sync,
+        E: From<OomError> + Send,
+    {
+        let queue_family_id = queue_family.id();
+        let physical_device = queue_family.physical_device();
+
+        let results = crossbeam::scope(|scope| {
+            let threads = (0..count)
+                .map(|index| {
+                    let device = device.clone();
+                    let subpass = subpass.clone();
+                    let framebuffer = framebuffer.clone();
+                    let record = &record;
+
+                    scope.spawn(move |_| {
+                        let queue_family =
+                            physical_device.queue_family_by_id(queue_family_id).unwrap();
+                        let kind = Kind::Secondary {
+                            render_pass: Some(KindSecondaryRenderPass {
+                                subpass,
+                                framebuffer: Some(framebuffer),
+                            }),
+                            occlusion_query: KindOcclusionQuery::Forbidden,
+                            query_statistics_flags: QueryPipelineStatisticFlags::none(),
+                        };
+                        let builder = AutoCommandBufferBuilder::with_flags(
+                            device,
+                            queue_family,
+                            kind,
+                            Flags::OneTimeSubmit,
+                        )?;
+                        record(index, builder)
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            threads
+                .into_iter()
+                .map(|thread| match thread.join() {
+                    Ok(result) => result,
+                    Err(panic) => panic::resume_unwind(panic),
+                })
+                .collect::<Vec<_>>()
+        });
+
+        match results {
+            Ok(results) => results.into_iter().collect(),
+            Err(panic) => panic::resume_unwind(panic),
+        }
+    }
+
     // Actual constructor. Private.
     fn with_flags<R, F>(
         device: Arc<Device>,
@@ -436,6 +636,11 @@ impl AutoCommandBufferBuilder<StandardCommandPoolBuilder> {
                 secondary_cb,
                 subpass_secondary: false,
                 flags,
+                queue_family_id: queue_family.id(),
+                events: HashMap::new(),
+                waited_events: Vec::new(),
+                vertex_strides: None,
+                extended_dynamic_state: ExtendedDynamicState::default(),
             })
         }
     }
@@ -515,9 +720,23 @@ impl<P> AutoCommandBufferBuilder<P> {
             },
         };
 
+        let inheritance_render_pass = if self.secondary_cb {
+            self.render_pass.as_ref().map(|&(ref rp, index)| {
+                let attachments = (0..rp.num_attachments())
+                    .map(|num| rp.attachment_desc(num).unwrap())
+                    .collect();
+                (attachments, index)
+            })
+        } else {
+            None
+        };
+
         Ok(AutoCommandBuffer {
             inner: self.inner.build()?,
+            inheritance_render_pass,
             submit_state,
+            events: self.events.into_iter().map(|(_, event)| event).collect(),
+            waited_events: self.waited_events,
         })
     }
 
@@ -654,56 +873,158 @@ impl<P> AutoCommandBufferBuilder<P> {
         S: ImageAccess + Send + Sync + 'static,
         D: ImageAccess + Send + Sync + 'static,
     {
-        unsafe {
-            self.ensure_outside_render_pass()?;
-
-            check_copy_image(
-                self.device(),
-                &source,
+        self.copy_image_regions(
+            source,
+            ImageLayout::TransferSrcOptimal,
+            destination,
+            ImageLayout::TransferDstOptimal,
+            iter::once(ImageCopy {
                 source_offset,
                 source_base_array_layer,
                 source_mip_level,
-                &destination,
                 destination_offset,
                 destination_base_array_layer,
                 destination_mip_level,
                 extent,
                 layer_count,
-            )?;
+            }),
+        )
+    }
 
-            let copy = UnsafeCommandBufferBuilderImageCopy {
-                // TODO: Allowing choosing a subset of the image aspects, but note that if color
-                // is included, neither depth nor stencil may.
-                aspect: UnsafeCommandBufferBuilderImageAspect {
-                    color: source.has_color(),
-                    depth: !source.has_color() && source.has_depth() && destination.has_depth(),
-                    stencil: !source.has_color()
-                        && source.has_stencil()
-                        && destination.has_stencil(),
-                },
-                source_mip_level,
-                destination_mip_level,
-                source_base_array_layer,
-                destination_base_array_layer,
-                layer_count,
-                source_offset,
-                destination_offset,
-                extent,
+    /// Adds a command that copies multiple regions of an image to another.
+    ///
+    /// Same as `copy_image`, except that you can pass multiple regions and choose the layouts
+    /// the images are in during the copy. The layout of the source must be `TransferSrcOptimal`
+    /// or `General`, and the layout of the destination must be `TransferDstOptimal` or
+    /// `General`. Each region is checked against the same restrictions as `copy_image`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the source or the destination was not created with `device`.
+    ///
+    pub fn copy_image_regions<S, D, R>(
+        &mut self,
+        source: S,
+        source_layout: ImageLayout,
+        destination: D,
+        destination_layout: ImageLayout,
+        regions: R,
+    ) -> Result<&mut Self, CopyImageError>
+    where
+        S: ImageAccess + Send + Sync + 'static,
+        D: ImageAccess + Send + Sync + 'static,
+        R: IntoIterator<Item = ImageCopy>,
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+
+            check_copy_image_layouts(source_layout, destination_layout)?;
+
+            // TODO: Allowing choosing a subset of the image aspects, but note that if color
+            // is included, neither depth nor stencil may.
+            let aspect = UnsafeCommandBufferBuilderImageAspect {
+                color: source.has_color(),
+                depth: !source.has_color() && source.has_depth() && destination.has_depth(),
+                stencil: !source.has_color() && source.has_stencil() && destination.has_stencil(),
             };
 
-            // TODO: Allow choosing layouts, but note that only Transfer*Optimal and General are
-            // valid.
+            let mut copies = Vec::new();
+            for region in regions {
+                check_copy_image(
+                    self.device(),
+                    &source,
+                    region.source_offset,
+                    region.source_base_array_layer,
+                    region.source_mip_level,
+                    &destination,
+                    region.destination_offset,
+                    region.destination_base_array_layer,
+                    region.destination_mip_level,
+                    region.extent,
+                    region.layer_count,
+                )?;
+
+                copies.push(UnsafeCommandBufferBuilderImageCopy {
+                    aspect,
+                    source_mip_level: region.source_mip_level,
+                    destination_mip_level: region.destination_mip_level,
+                    source_base_array_layer: region.source_base_array_layer,
+                    destination_base_array_layer: region.destination_base_array_layer,
+                    layer_count: region.layer_count,
+                    source_offset: region.source_offset,
+                    destination_offset: region.destination_offset,
+                    extent: region.extent,
+                });
+            }
+
+            if copies.is_empty() {
+                return Ok(self);
+            }
+
             self.inner.copy_image(
                 source,
-                ImageLayout::TransferSrcOptimal,
+                source_layout,
                 destination,
-                ImageLayout::TransferDstOptimal,
-                iter::once(copy),
+                destination_layout,
+                copies.into_iter(),
             )?;
             Ok(self)
         }
     }
 
+    /// Adds a command that copies the first mipmap level of a color attachment into a storage
+    /// image, so that the result of a render pass can be processed by a compute shader.
+    ///
+    /// The source is used in the `TransferSrcOptimal` layout and the destination in the `General`
+    /// layout, which is the layout storage images are accessed in. Like for every other command,
+    /// the transitions from the layouts the images are in before the copy, and back to them
+    /// afterwards, are added automatically.
+    ///
+    /// In addition to the restrictions of `copy_image`, the source must have been created with
+    /// the color attachment usage, the destination with the storage usage, and both images must
+    /// have the same dimensions, the same number of array layers and size-compatible formats.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the source or the destination was not created with `device`.
+    ///
+    pub fn copy_attachment_to_storage_image<S, D>(
+        &mut self,
+        source: S,
+        destination: D,
+    ) -> Result<&mut Self, CopyAttachmentToStorageImageError>
+    where
+        S: ImageAccess + Send + Sync + 'static,
+        D: ImageAccess + Send + Sync + 'static,
+    {
+        check_copy_attachment_to_storage(self.device(), &source, &destination)?;
+
+        let dimensions = source.dimensions();
+        let region = ImageCopy {
+            source_offset: [0, 0, 0],
+            source_base_array_layer: 0,
+            source_mip_level: 0,
+            destination_offset: [0, 0, 0],
+            destination_base_array_layer: 0,
+            destination_mip_level: 0,
+            extent: dimensions.width_height_depth(),
+            layer_count: dimensions.array_layers(),
+        };
+
+        match self.copy_image_regions(
+            source,
+            ImageLayout::TransferSrcOptimal,
+            destination,
+            ImageLayout::General,
+            iter::once(region),
+        ) {
+            Ok(_) => Ok(self),
+            Err(CopyImageError::AutoCommandBufferBuilderContextError(err)) => Err(err.into()),
+            Err(CopyImageError::CheckCopyImageError(err)) => Err(err.into()),
+            Err(CopyImageError::SyncCommandBufferBuilderError(err)) => Err(err.into()),
+        }
+    }
+
     /// Adds a command that blits an image to another.
     ///
     /// A *blit* is similar to an image copy operation, except that the portion of the image that
@@ -754,57 +1075,113 @@ impl<P> AutoCommandBufferBuilder<P> {
         S: ImageAccess + Send + Sync + 'static,
         D: ImageAccess + Send + Sync + 'static,
     {
-        unsafe {
-            if !self.graphics_allowed {
-                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
-            }
-
-            self.ensure_outside_render_pass()?;
-
-            check_blit_image(
-                self.device(),
-                &source,
+        self.blit_image_regions(
+            source,
+            ImageLayout::TransferSrcOptimal,
+            destination,
+            ImageLayout::TransferDstOptimal,
+            iter::once(ImageBlit {
                 source_top_left,
                 source_bottom_right,
                 source_base_array_layer,
                 source_mip_level,
-                &destination,
                 destination_top_left,
                 destination_bottom_right,
                 destination_base_array_layer,
                 destination_mip_level,
                 layer_count,
-                filter,
-            )?;
+            }),
+            filter,
+        )
+    }
 
-            let blit = UnsafeCommandBufferBuilderImageBlit {
-                // TODO:
-                aspect: if source.has_color() {
-                    UnsafeCommandBufferBuilderImageAspect {
-                        color: true,
-                        depth: false,
-                        stencil: false,
-                    }
-                } else {
-                    unimplemented!()
-                },
-                source_mip_level,
-                destination_mip_level,
-                source_base_array_layer,
-                destination_base_array_layer,
-                layer_count,
-                source_top_left,
-                source_bottom_right,
-                destination_top_left,
-                destination_bottom_right,
+    /// Adds a command that blits multiple regions of an image to another.
+    ///
+    /// Same as `blit_image`, except that you can pass multiple regions and choose the layouts
+    /// the images are in during the blit. The layout of the source must be `TransferSrcOptimal`
+    /// or `General`, and the layout of the destination must be `TransferDstOptimal` or
+    /// `General`. Each region is checked against the same restrictions as `blit_image`.
+    ///
+    /// If `filter` is `Linear`, the format of the source must support linear filtering.
+    ///
+    /// To flip an image along an axis, swap the top-left and bottom-right coordinates of the
+    /// source or of the destination on that axis.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the source or the destination was not created with `device`.
+    ///
+    pub fn blit_image_regions<S, D, R>(
+        &mut self,
+        source: S,
+        source_layout: ImageLayout,
+        destination: D,
+        destination_layout: ImageLayout,
+        regions: R,
+        filter: Filter,
+    ) -> Result<&mut Self, BlitImageError>
+    where
+        S: ImageAccess + Send + Sync + 'static,
+        D: ImageAccess + Send + Sync + 'static,
+        R: IntoIterator<Item = ImageBlit>,
+    {
+        unsafe {
+            if !self.graphics_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            self.ensure_outside_render_pass()?;
+
+            check_blit_image_layouts(source_layout, destination_layout)?;
+
+            let aspect = UnsafeCommandBufferBuilderImageAspect {
+                color: source.has_color(),
+                depth: !source.has_color() && source.has_depth() && destination.has_depth(),
+                stencil: !source.has_color() && source.has_stencil() && destination.has_stencil(),
             };
 
+            let mut blits = Vec::new();
+            for region in regions {
+                check_blit_image(
+                    self.device(),
+                    &source,
+                    region.source_top_left,
+                    region.source_bottom_right,
+                    region.source_base_array_layer,
+                    region.source_mip_level,
+                    &destination,
+                    region.destination_top_left,
+                    region.destination_bottom_right,
+                    region.destination_base_array_layer,
+                    region.destination_mip_level,
+                    region.layer_count,
+                    filter,
+                )?;
+
+                blits.push(UnsafeCommandBufferBuilderImageBlit {
+                    aspect,
+                    source_mip_level: region.source_mip_level,
+                    destination_mip_level: region.destination_mip_level,
+                    source_base_array_layer: region.source_base_array_layer,
+                    destination_base_array_layer: region.destination_base_array_layer,
+                    layer_count: region.layer_count,
+                    source_top_left: region.source_top_left,
+                    source_bottom_right: region.source_bottom_right,
+                    destination_top_left: region.destination_top_left,
+                    destination_bottom_right: region.destination_bottom_right,
+                });
+            }
+
+            if blits.is_empty() {
+                return Ok(self);
+            }
+
             self.inner.blit_image(
                 source,
-                ImageLayout::TransferSrcOptimal,
-                destination, // TODO: let choose layout
-                ImageLayout::TransferDstOptimal,
-                iter::once(blit),
+                source_layout,
+                destination,
+                destination_layout,
+                blits.into_iter(),
                 filter,
             )?;
             Ok(self)
@@ -940,6 +1317,39 @@ impl<P> AutoCommandBufferBuilder<P> {
         num_layers: u32,
         mipmap: u32,
     ) -> Result<&mut Self, CopyBufferImageError>
+    where
+        S: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
+        D: ImageAccess + Send + Sync + 'static,
+        Format: AcceptsPixels<Px>,
+    {
+        self.copy_buffer_to_image_with_layout(
+            source,
+            destination,
+            BufferImageCopyLayout::packed(),
+            offset,
+            size,
+            first_layer,
+            num_layers,
+            mipmap,
+        )
+    }
+
+    /// Same as `copy_buffer_to_image_dimensions`, but the texels in the buffer follow `layout`
+    /// instead of being tightly packed.
+    ///
+    /// Use `BufferImageCopyLayout::optimal` to lay out staging buffers so that the copy is as
+    /// fast as possible.
+    pub fn copy_buffer_to_image_with_layout<S, D, Px>(
+        &mut self,
+        source: S,
+        destination: D,
+        layout: BufferImageCopyLayout,
+        offset: [u32; 3],
+        size: [u32; 3],
+        first_layer: u32,
+        num_layers: u32,
+        mipmap: u32,
+    ) -> Result<&mut Self, CopyBufferImageError>
     where
         S: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
         D: ImageAccess + Send + Sync + 'static,
@@ -948,11 +1358,12 @@ impl<P> AutoCommandBufferBuilder<P> {
         unsafe {
             self.ensure_outside_render_pass()?;
 
-            check_copy_buffer_image(
+            check_copy_buffer_image_layout(
                 self.device(),
                 &source,
                 &destination,
                 CheckCopyBufferImageTy::BufferToImage,
+                layout,
                 offset,
                 size,
                 first_layer,
@@ -961,9 +1372,9 @@ impl<P> AutoCommandBufferBuilder<P> {
             )?;
 
             let copy = UnsafeCommandBufferBuilderBufferImageCopy {
-                buffer_offset: 0,
-                buffer_row_length: 0,
-                buffer_image_height: 0,
+                buffer_offset: layout.buffer_offset * mem::size_of::<Px>(),
+                buffer_row_length: layout.row_length,
+                buffer_image_height: layout.image_height,
                 image_aspect: if destination.has_color() {
                     UnsafeCommandBufferBuilderImageAspect {
                         color: true,
@@ -1022,6 +1433,39 @@ impl<P> AutoCommandBufferBuilder<P> {
         num_layers: u32,
         mipmap: u32,
     ) -> Result<&mut Self, CopyBufferImageError>
+    where
+        S: ImageAccess + Send + Sync + 'static,
+        D: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
+        Format: AcceptsPixels<Px>,
+    {
+        self.copy_image_to_buffer_with_layout(
+            source,
+            destination,
+            BufferImageCopyLayout::packed(),
+            offset,
+            size,
+            first_layer,
+            num_layers,
+            mipmap,
+        )
+    }
+
+    /// Same as `copy_image_to_buffer_dimensions`, but the texels are written to the buffer
+    /// following `layout` instead of being tightly packed.
+    ///
+    /// When reading back the buffer, the elements of the padding at the end of the rows and
+    /// slices must be skipped. See the documentation of `BufferImageCopyLayout`.
+    pub fn copy_image_to_buffer_with_layout<S, D, Px>(
+        &mut self,
+        source: S,
+        destination: D,
+        layout: BufferImageCopyLayout,
+        offset: [u32; 3],
+        size: [u32; 3],
+        first_layer: u32,
+        num_layers: u32,
+        mipmap: u32,
+    ) -> Result<&mut Self, CopyBufferImageError>
     where
         S: ImageAccess + Send + Sync + 'static,
         D: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
@@ -1030,11 +1474,12 @@ impl<P> AutoCommandBufferBuilder<P> {
         unsafe {
             self.ensure_outside_render_pass()?;
 
-            check_copy_buffer_image(
+            check_copy_buffer_image_layout(
                 self.device(),
                 &destination,
                 &source,
                 CheckCopyBufferImageTy::ImageToBuffer,
+                layout,
                 offset,
                 size,
                 first_layer,
@@ -1043,13 +1488,16 @@ impl<P> AutoCommandBufferBuilder<P> {
             )?;
 
             let copy = UnsafeCommandBufferBuilderBufferImageCopy {
-                buffer_offset: 0,
-                buffer_row_length: 0,
-                buffer_image_height: 0,
+                buffer_offset: layout.buffer_offset * mem::size_of::<Px>(),
+                buffer_row_length: layout.row_length,
+                buffer_image_height: layout.image_height,
+                // Buffer-image copies must access a single aspect. For depth-stencil images, only
+                // the depth aspect is copied.
+                // TODO: allow choosing the aspect
                 image_aspect: UnsafeCommandBufferBuilderImageAspect {
                     color: source.has_color(),
-                    depth: source.has_depth(),
-                    stencil: source.has_stencil(),
+                    depth: !source.has_color() && source.has_depth(),
+                    stencil: !source.has_color() && !source.has_depth() && source.has_stencil(),
                 },
                 image_mip_level: mipmap,
                 image_base_array_layer: first_layer,
@@ -1068,6 +1516,55 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Adds a command that copies the results of a range of queries to a buffer.
+    ///
+    /// The results of each query are written as 64-bit values, followed by the availability of
+    /// the results if `with_availability` is set in `flags`. Without `wait`, this doesn't wait
+    /// for the queries to complete and the queries whose results aren't available yet are either
+    /// skipped or, with `with_availability`, reported as not available. With `wait`, all the
+    /// queries must have been issued before this command is executed, otherwise the device can
+    /// be lost.
+    ///
+    /// See also `QueryResultsRing`, which copies the results to a ring of buffers in order to read
+    /// them from the CPU without blocking.
+    #[inline]
+    pub fn copy_query_pool_results<D>(
+        &mut self,
+        pool: Arc<UnsafeQueryPool>,
+        first_index: u32,
+        count: u32,
+        destination: D,
+        flags: QueryResultFlags,
+    ) -> Result<&mut Self, CopyQueryPoolResultsError>
+    where
+        D: TypedBufferAccess<Content = [u64]> + Send + Sync + 'static,
+    {
+        unsafe {
+            if !self.graphics_allowed && !self.compute_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            self.ensure_outside_render_pass()?;
+            let stride = check_copy_query_pool_results(
+                self.device(),
+                &pool,
+                first_index,
+                count,
+                &destination,
+                flags,
+            )?;
+            self.inner.copy_query_pool_results(
+                pool,
+                first_index,
+                count,
+                destination,
+                stride,
+                flags,
+            );
+            Ok(self)
+        }
+    }
+
     #[inline]
     pub fn dispatch<Cp, S, Pc>(
         &mut self,
@@ -1110,36 +1607,188 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
-    /// Draw once, using the `vertex_buffer`.
+    /// Same as `dispatch`, but takes the total number of invocations in each dimension instead
+    /// of the number of workgroups.
     ///
-    /// To use only some data in the buffer, wrap it in a `vulkano::buffer::BufferSlice`.
+    /// The number of workgroups is computed by dividing `total` by the local size of the
+    /// pipeline's compute shader, rounding up. The shader should therefore discard the
+    /// invocations that are out of bounds.
+    ///
+    /// Returns an error if the local size of the pipeline isn't known. This is the case if the
+    /// shader entry point wasn't generated by `vulkano-shaders` or doesn't use
+    /// `with_local_size`.
     #[inline]
-    pub fn draw<V, Gp, S, Pc>(
+    pub fn dispatch_for<Cp, S, Pc>(
         &mut self,
-        pipeline: Gp,
-        dynamic: &DynamicState,
-        vertex_buffer: V,
+        total: [u32; 3],
+        pipeline: Cp,
         sets: S,
         constants: Pc,
-    ) -> Result<&mut Self, DrawError>
+    ) -> Result<&mut Self, DispatchError>
     where
-        Gp: GraphicsPipelineAbstract + VertexSource<V> + Send + Sync + 'static + Clone, // TODO: meh for Clone
+        Cp: ComputePipelineAbstract + Send + Sync + 'static + Clone, // TODO: meh for Clone
         S: DescriptorSetsCollection,
     {
-        unsafe {
-            // TODO: must check that pipeline is compatible with render pass
+        let dimensions = dispatch_dimensions_for(pipeline.local_size(), total)?;
+        self.dispatch(dimensions, pipeline, sets, constants)
+    }
 
-            self.ensure_inside_render_pass_inline(&pipeline)?;
-            check_dynamic_state_validity(&pipeline, dynamic)?;
-            check_push_constants_validity(&pipeline, &constants)?;
-            check_descriptor_sets_validity(&pipeline, &sets)?;
+    /// Same as `dispatch`, but reads the number of workgroups from a buffer when the command is
+    /// executed.
+    ///
+    /// This is typically used to dispatch work whose size was computed by a previous dispatch.
+    /// In that case the previous dispatch writes the `DispatchIndirectCommand` to
+    /// `indirect_buffer` through a storage buffer descriptor, so the buffer must have both the
+    /// `indirect_buffer` and the `storage_buffer` usages. The pipeline barrier between the shader
+    /// write and the read of the parameters is inserted automatically, like for any other access.
+    ///
+    /// Contrary to `dispatch`, the number of workgroups isn't checked against the
+    /// `max_compute_work_group_count` limit since it isn't known when recording.
+    #[inline]
+    pub fn dispatch_indirect<Inb, Cp, S, Pc>(
+        &mut self,
+        indirect_buffer: Inb,
+        pipeline: Cp,
+        sets: S,
+        constants: Pc,
+    ) -> Result<&mut Self, DispatchIndirectError>
+    where
+        Inb: BufferAccess
+            + TypedBufferAccess<Content = DispatchIndirectCommand>
+            + Send
+            + Sync
+            + 'static,
+        Cp: ComputePipelineAbstract + Send + Sync + 'static + Clone, // TODO: meh for Clone
+        S: DescriptorSetsCollection,
+    {
+        unsafe {
+            if !self.compute_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            self.ensure_outside_render_pass()?;
+            check_indirect_buffer(self.device(), &indirect_buffer)?;
+            if !indirect_buffer.inner().buffer.usage_storage_buffer() {
+                return Err(CheckIndirectBufferError::BufferMissingStorageUsage.into());
+            }
+            check_push_constants_validity(&pipeline, &constants)?;
+            check_descriptor_sets_validity(&pipeline, &sets)?;
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_compute_pipeline(&pipeline)
+            {
+                self.inner.bind_pipeline_compute(pipeline.clone());
+            }
+
+            push_constants(&mut self.inner, pipeline.clone(), constants);
+            descriptor_sets(
+                &mut self.inner,
+                &mut self.state_cacher,
+                false,
+                pipeline.clone(),
+                sets,
+            )?;
+
+            self.inner.dispatch_indirect(indirect_buffer)?;
+            Ok(self)
+        }
+    }
+
+    /// Binds vertex buffers with the given strides, one per buffer, instead of the strides of
+    /// the vertex definition of `pipeline`.
+    ///
+    /// The buffers are decoded by `pipeline` the same way as in `draw`. Pass the same vertex
+    /// buffers to the following draw commands, in which case they aren't bound again and the
+    /// strides stay in effect. Draw commands that use a pipeline with dynamic strides return an
+    /// error if their vertex buffers weren't bound by this method, or if a stride is smaller than
+    /// the vertex attributes of its binding.
+    ///
+    /// This allows drawing vertex buffers of different layouts with the same pipeline. The
+    /// pipeline must have been created with `vertex_input_binding_stride_dynamic`, which requires
+    /// the `extended_dynamic_state` feature, and the strides can't exceed the
+    /// `max_vertex_input_binding_stride` limit.
+    pub fn bind_vertex_buffers_with_stride<V, Gp>(
+        &mut self,
+        pipeline: &Gp,
+        vertex_buffer: V,
+        strides: &[u32],
+    ) -> Result<&mut Self, BindVertexBuffersWithStrideError>
+    where
+        Gp: GraphicsPipelineAbstract + VertexSource<V>,
+    {
+        unsafe {
+            if !self.graphics_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            let vb_infos = check_vertex_buffers(pipeline, vertex_buffer)?;
+            check_vertex_buffer_strides(pipeline, vb_infos.vertex_buffers.len(), strides)?;
+
+            // The strides must be set even if the same buffers are already bound, but the state
+            // cacher is updated so that the next draw commands don't rebind the buffers without
+            // strides.
+            {
+                let mut compare = self.state_cacher.bind_vertex_buffers();
+                for vb in vb_infos.vertex_buffers.iter() {
+                    compare.add(vb);
+                }
+                compare.compare();
+            }
+
+            let buffers = vb_infos
+                .vertex_buffers
+                .iter()
+                .map(|vb| vertex_buffer_key(&**vb))
+                .collect();
+            let mut binder = self.inner.bind_vertex_buffers();
+            for vb in vb_infos.vertex_buffers {
+                binder.add(vb);
+            }
+            binder.submit_with_strides(0, strides.iter().cloned().collect())?;
+            self.vertex_strides = Some(VertexStrides {
+                buffers,
+                strides: strides.iter().cloned().collect(),
+            });
+            Ok(self)
+        }
+    }
+
+    /// Draw once, using the `vertex_buffer`.
+    ///
+    /// To use only some data in the buffer, wrap it in a `vulkano::buffer::BufferSlice`.
+    #[inline]
+    pub fn draw<V, Gp, S, Pc>(
+        &mut self,
+        pipeline: Gp,
+        dynamic: &DynamicState,
+        vertex_buffer: V,
+        sets: S,
+        constants: Pc,
+    ) -> Result<&mut Self, DrawError>
+    where
+        Gp: GraphicsPipelineAbstract + VertexSource<V> + Send + Sync + 'static + Clone, // TODO: meh for Clone
+        S: DescriptorSetsCollection,
+    {
+        unsafe {
+            // TODO: must check that pipeline is compatible with render pass
+
+            self.ensure_inside_render_pass_inline(&pipeline)?;
+            check_dynamic_state_validity(&pipeline, dynamic)?;
+            check_extended_dynamic_state_validity(&pipeline, &self.extended_dynamic_state)?;
+            check_push_constants_validity(&pipeline, &constants)?;
+            check_descriptor_sets_validity(&pipeline, &sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertex_buffer)?;
+            check_draw_vertex_buffer_strides(
+                &pipeline,
+                bound_vertex_strides(&self.vertex_strides, &vb_infos.vertex_buffers),
+            )?;
 
             if let StateCacherOutcome::NeedChange =
                 self.state_cacher.bind_graphics_pipeline(&pipeline)
             {
                 self.inner.bind_pipeline_graphics(pipeline.clone());
             }
+            clear_static_extended_states(&mut self.extended_dynamic_state, &pipeline);
 
             let dynamic = self.state_cacher.dynamic_state(dynamic);
 
@@ -1155,6 +1804,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             vertex_buffers(
                 &mut self.inner,
                 &mut self.state_cacher,
+                &mut self.vertex_strides,
                 vb_infos.vertex_buffers,
             )?;
 
@@ -1195,15 +1845,21 @@ impl<P> AutoCommandBufferBuilder<P> {
             self.ensure_inside_render_pass_inline(&pipeline)?;
             let ib_infos = check_index_buffer(self.device(), &index_buffer)?;
             check_dynamic_state_validity(&pipeline, dynamic)?;
+            check_extended_dynamic_state_validity(&pipeline, &self.extended_dynamic_state)?;
             check_push_constants_validity(&pipeline, &constants)?;
             check_descriptor_sets_validity(&pipeline, &sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertex_buffer)?;
+            check_draw_vertex_buffer_strides(
+                &pipeline,
+                bound_vertex_strides(&self.vertex_strides, &vb_infos.vertex_buffers),
+            )?;
 
             if let StateCacherOutcome::NeedChange =
                 self.state_cacher.bind_graphics_pipeline(&pipeline)
             {
                 self.inner.bind_pipeline_graphics(pipeline.clone());
             }
+            clear_static_extended_states(&mut self.extended_dynamic_state, &pipeline);
 
             if let StateCacherOutcome::NeedChange =
                 self.state_cacher.bind_index_buffer(&index_buffer, I::ty())
@@ -1225,6 +1881,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             vertex_buffers(
                 &mut self.inner,
                 &mut self.state_cacher,
+                &mut self.vertex_strides,
                 vb_infos.vertex_buffers,
             )?;
             // TODO: how to handle an index out of range of the vertex buffers?
@@ -1270,9 +1927,14 @@ impl<P> AutoCommandBufferBuilder<P> {
 
             self.ensure_inside_render_pass_inline(&pipeline)?;
             check_dynamic_state_validity(&pipeline, dynamic)?;
+            check_extended_dynamic_state_validity(&pipeline, &self.extended_dynamic_state)?;
             check_push_constants_validity(&pipeline, &constants)?;
             check_descriptor_sets_validity(&pipeline, &sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertex_buffer)?;
+            check_draw_vertex_buffer_strides(
+                &pipeline,
+                bound_vertex_strides(&self.vertex_strides, &vb_infos.vertex_buffers),
+            )?;
 
             let draw_count = indirect_buffer.len() as u32;
 
@@ -1281,6 +1943,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             {
                 self.inner.bind_pipeline_graphics(pipeline.clone());
             }
+            clear_static_extended_states(&mut self.extended_dynamic_state, &pipeline);
 
             let dynamic = self.state_cacher.dynamic_state(dynamic);
 
@@ -1296,6 +1959,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             vertex_buffers(
                 &mut self.inner,
                 &mut self.state_cacher,
+                &mut self.vertex_strides,
                 vb_infos.vertex_buffers,
             )?;
 
@@ -1342,9 +2006,14 @@ impl<P> AutoCommandBufferBuilder<P> {
             self.ensure_inside_render_pass_inline(&pipeline)?;
             let ib_infos = check_index_buffer(self.device(), &index_buffer)?;
             check_dynamic_state_validity(&pipeline, dynamic)?;
+            check_extended_dynamic_state_validity(&pipeline, &self.extended_dynamic_state)?;
             check_push_constants_validity(&pipeline, &constants)?;
             check_descriptor_sets_validity(&pipeline, &sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertex_buffer)?;
+            check_draw_vertex_buffer_strides(
+                &pipeline,
+                bound_vertex_strides(&self.vertex_strides, &vb_infos.vertex_buffers),
+            )?;
 
             let draw_count = indirect_buffer.len() as u32;
 
@@ -1353,6 +2022,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             {
                 self.inner.bind_pipeline_graphics(pipeline.clone());
             }
+            clear_static_extended_states(&mut self.extended_dynamic_state, &pipeline);
 
             if let StateCacherOutcome::NeedChange =
                 self.state_cacher.bind_index_buffer(&index_buffer, I::ty())
@@ -1374,6 +2044,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             vertex_buffers(
                 &mut self.inner,
                 &mut self.state_cacher,
+                &mut self.vertex_strides,
                 vb_infos.vertex_buffers,
             )?;
 
@@ -1422,6 +2093,10 @@ impl<P> AutoCommandBufferBuilder<P> {
 
     /// Adds a command that executes a secondary command buffer.
     ///
+    /// If the secondary command buffer wasn't created with the simultaneous use flag, it can't be
+    /// executed by another command buffer until this one is destroyed, and this command buffer
+    /// must not have been created with the simultaneous use flag either.
+    ///
     /// **This function is unsafe for now because safety checks and synchronization are not
     /// implemented.**
     // TODO: implement correctly
@@ -1432,9 +2107,12 @@ impl<P> AutoCommandBufferBuilder<P> {
     where
         C: CommandBuffer + Send + Sync + 'static,
     {
+        self.check_secondary_render_pass(&command_buffer)?;
+        self.check_secondary_usage(&command_buffer)?;
+
         {
             let mut builder = self.inner.execute_commands();
-            builder.add(command_buffer);
+            builder.add(command_buffer)?;
             builder.submit()?;
         }
 
@@ -1455,10 +2133,15 @@ impl<P> AutoCommandBufferBuilder<P> {
     where
         C: CommandBuffer + Send + Sync + 'static,
     {
+        for cmd_buffer in command_buffers.iter() {
+            self.check_secondary_render_pass(cmd_buffer)?;
+            self.check_secondary_usage(cmd_buffer)?;
+        }
+
         {
             let mut builder = self.inner.execute_commands();
             for cmd_buffer in command_buffers {
-                builder.add(cmd_buffer);
+                builder.add(cmd_buffer)?;
             }
             builder.submit()?;
         }
@@ -1468,6 +2151,46 @@ impl<P> AutoCommandBufferBuilder<P> {
         Ok(self)
     }
 
+    // Checks that a secondary command buffer can be executed in a command buffer created with
+    // `Flags::SimultaneousUse`, if this is the case of this one.
+    fn check_secondary_usage<C>(
+        &self,
+        command_buffer: &C,
+    ) -> Result<(), AutoCommandBufferBuilderContextError>
+    where
+        C: CommandBuffer,
+    {
+        if self.flags == Flags::SimultaneousUse && command_buffer.usage() != Flags::SimultaneousUse
+        {
+            return Err(AutoCommandBufferBuilderContextError::SecondaryNotSimultaneousUse);
+        }
+
+        Ok(())
+    }
+
+    // Checks that a secondary command buffer is compatible with the current render pass, if any.
+    fn check_secondary_render_pass<C>(
+        &self,
+        command_buffer: &C,
+    ) -> Result<(), CheckSecondaryRenderPassError>
+    where
+        C: CommandBuffer,
+    {
+        if let Some((ref render_pass, subpass)) = self.render_pass {
+            if let Some((attachments, secondary_subpass)) = command_buffer.inheritance_render_pass()
+            {
+                check_secondary_render_pass(
+                    &**render_pass,
+                    subpass,
+                    attachments,
+                    secondary_subpass,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Adds a command that writes the content of a buffer.
     ///
     /// This function is similar to the `memset` function in C. The `data` parameter is a number
@@ -1477,6 +2200,9 @@ impl<P> AutoCommandBufferBuilder<P> {
     /// > floating point numbers, which are always valid whatever their memory representation is.
     /// > But unless your buffer actually contains only 32-bits integers, you are encouraged to use
     /// > this function only for zeroing the content of a buffer by passing `0` for the data.
+    ///
+    /// On a queue family that supports neither graphics nor compute operations, the
+    /// `khr_maintenance1` extension must be enabled.
     // TODO: not safe because of signalling NaNs
     #[inline]
     pub fn fill_buffer<B>(&mut self, buffer: B, data: u32) -> Result<&mut Self, FillBufferError>
@@ -1484,6 +2210,13 @@ impl<P> AutoCommandBufferBuilder<P> {
         B: BufferAccess + Send + Sync + 'static,
     {
         unsafe {
+            if !self.graphics_allowed
+                && !self.compute_allowed
+                && !self.device().loaded_extensions().khr_maintenance1
+            {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
             self.ensure_outside_render_pass()?;
             check_fill_buffer(self.device(), &buffer)?;
             self.inner.fill_buffer(buffer, data);
@@ -1532,6 +2265,197 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Adds a command that updates a part of the push constants.
+    ///
+    /// `data` is written at `offset` bytes from the start of the push constants, for the given
+    /// `stages`. This makes it possible, for example, to update only the part of the push
+    /// constants used by the fragment shader without rewriting the part used by the vertex
+    /// shader. The range must match the push constants ranges declared by `pipeline_layout`, and
+    /// must not exceed the `max_push_constants_size` limit.
+    ///
+    /// > **Note**: The draw and dispatch commands also set the push constants that are passed to
+    /// > them, which overwrites the values written with this method.
+    #[inline]
+    pub fn push_constants<Pl, D>(
+        &mut self,
+        pipeline_layout: Pl,
+        stages: ShaderStages,
+        offset: u32,
+        data: D,
+    ) -> Result<&mut Self, PushConstantsError>
+    where
+        Pl: PipelineLayoutAbstract + Send + Sync + 'static,
+        D: Send + Sync + 'static,
+    {
+        unsafe {
+            if !self.graphics_allowed && !self.compute_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            let size = mem::size_of_val(&data) as u32;
+            check_push_constants_range(&pipeline_layout, stages, offset, size)?;
+            self.inner
+                .push_constants(pipeline_layout, stages, offset, size, &data);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that resets an event to the unsignaled state once the given stages of the
+    /// previous commands have completed.
+    ///
+    /// The event can't be waited on afterwards, until it is set again.
+    #[inline]
+    pub fn reset_event(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, EventCommandError> {
+        unsafe {
+            self.ensure_event_command()?;
+            check_set_event(self.device(), &event, stages)?;
+            self.events
+                .insert(event.internal_object(), (event.clone(), None));
+            self.inner.reset_event(event, stages);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that sets the cull mode of the following draw commands.
+    ///
+    /// Only affects draws that use a pipeline built with `cull_mode_dynamic`. The cull mode must
+    /// be set before drawing with such a pipeline. Requires the `extended_dynamic_state`
+    /// feature.
+    #[inline]
+    pub fn set_cull_mode(
+        &mut self,
+        cull_mode: CullMode,
+    ) -> Result<&mut Self, SetExtendedDynamicStateError> {
+        unsafe {
+            self.ensure_extended_dynamic_state()?;
+            self.inner.set_cull_mode(cull_mode);
+            self.extended_dynamic_state.cull_mode = Some(cull_mode);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that enables or disables the depth test of the following draw commands.
+    ///
+    /// Only affects draws that use a pipeline built with `depth_test_enable_dynamic`. The value
+    /// must be set before drawing with such a pipeline. Requires the `extended_dynamic_state`
+    /// feature.
+    #[inline]
+    pub fn set_depth_test_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, SetExtendedDynamicStateError> {
+        unsafe {
+            self.ensure_extended_dynamic_state()?;
+            self.inner.set_depth_test_enable(enable);
+            self.extended_dynamic_state.depth_test_enable = Some(enable);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that enables or disables the depth writes of the following draw commands.
+    ///
+    /// Only affects draws that use a pipeline built with `depth_write_enable_dynamic`. The value
+    /// must be set before drawing with such a pipeline. Requires the `extended_dynamic_state`
+    /// feature.
+    #[inline]
+    pub fn set_depth_write_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, SetExtendedDynamicStateError> {
+        unsafe {
+            self.ensure_extended_dynamic_state()?;
+            self.inner.set_depth_write_enable(enable);
+            self.extended_dynamic_state.depth_write_enable = Some(enable);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that sets which side of the primitives is the front face for the
+    /// following draw commands.
+    ///
+    /// Only affects draws that use a pipeline built with `front_face_dynamic`. The front face
+    /// must be set before drawing with such a pipeline. Requires the `extended_dynamic_state`
+    /// feature.
+    #[inline]
+    pub fn set_front_face(
+        &mut self,
+        front_face: FrontFace,
+    ) -> Result<&mut Self, SetExtendedDynamicStateError> {
+        unsafe {
+            self.ensure_extended_dynamic_state()?;
+            self.inner.set_front_face(front_face);
+            self.extended_dynamic_state.front_face = Some(front_face);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that signals an event once the given stages of the previous commands have
+    /// completed.
+    ///
+    /// The event can then be waited on with `wait_events`, either later in this command buffer
+    /// or in a command buffer of the same queue that is recorded after this command buffer is
+    /// submitted. If this is a secondary command buffer, it can only be waited on later in this
+    /// command buffer.
+    #[inline]
+    pub fn set_event(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, EventCommandError> {
+        unsafe {
+            self.ensure_event_command()?;
+            check_set_event(self.device(), &event, stages)?;
+            self.events
+                .insert(event.internal_object(), (event.clone(), Some(stages)));
+            self.inner.set_event(event, stages);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that sets the primitive topology of the following draw commands.
+    ///
+    /// Only affects draws that use a pipeline built with `primitive_topology_dynamic`. The
+    /// topology must be set before drawing with such a pipeline, and must belong to the same
+    /// topology class (points, lines, triangles or patches) as the topology the pipeline was
+    /// built with. Requires the `extended_dynamic_state` feature.
+    #[inline]
+    pub fn set_primitive_topology(
+        &mut self,
+        topology: PrimitiveTopology,
+    ) -> Result<&mut Self, SetExtendedDynamicStateError> {
+        unsafe {
+            self.ensure_extended_dynamic_state()?;
+            self.inner.set_primitive_topology(topology);
+            self.extended_dynamic_state.primitive_topology = Some(topology);
+            Ok(self)
+        }
+    }
+
+    // Checks that the extended dynamic states can be set in this command buffer.
+    #[inline]
+    fn ensure_extended_dynamic_state(&self) -> Result<(), SetExtendedDynamicStateError> {
+        if !self.graphics_allowed {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
+
+        check_extended_dynamic_state(self.device())?;
+        Ok(())
+    }
+
+    // Checks that event commands can be recorded at this point of the command buffer.
+    #[inline]
+    fn ensure_event_command(&self) -> Result<(), AutoCommandBufferBuilderContextError> {
+        if !self.graphics_allowed && !self.compute_allowed {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily);
+        }
+
+        self.ensure_outside_render_pass()
+    }
+
     /// Adds a command that writes data to a buffer.
     ///
     /// If `data` is larger than the buffer, only the part of `data` that fits is written. If the
@@ -1562,6 +2486,92 @@ impl<P> AutoCommandBufferBuilder<P> {
             Ok(self)
         }
     }
+
+    /// Adds a command that waits until all the given events are signaled, then makes the
+    /// `source_access` of the commands that signaled them available to the `destination_access`
+    /// of `destination_stages` of the following commands.
+    ///
+    /// The source stages of the dependency are the stages passed to the last `set_event` of each
+    /// event, and the host stage for events that are set from the host. Events that weren't set
+    /// in this command buffer must have been set by the host or by a command buffer that was
+    /// submitted before this method is called, otherwise an error is returned. Submitting this
+    /// command buffer then returns an error if such an event was last set on another queue, or
+    /// if it was set again or reset in the meantime. Secondary command buffers can only wait on
+    /// events that they set themselves.
+    ///
+    /// Contrary to a pipeline barrier, the commands recorded between `set_event` and
+    /// `wait_events` can keep running while waiting. Resources used by these commands are not
+    /// synchronized by the wait.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the events was not created with the same device as this command buffer.
+    ///
+    #[inline]
+    pub fn wait_events<I>(
+        &mut self,
+        events: I,
+        source_access: AccessFlagBits,
+        destination_stages: PipelineStages,
+        destination_access: AccessFlagBits,
+    ) -> Result<&mut Self, EventCommandError>
+    where
+        I: IntoIterator<Item = Arc<Event>>,
+    {
+        unsafe {
+            self.ensure_event_command()?;
+
+            let events: SmallVec<[Arc<Event>; 4]> = events.into_iter().collect();
+
+            let mut source_stages = PipelineStages::none();
+            let mut waited_events = SmallVec::<[_; 4]>::new();
+            for event in events.iter() {
+                let stages = match self.events.get(&event.internal_object()) {
+                    Some(&(_, Some(stages))) => stages,
+                    Some(&(_, None)) => return Err(CheckEventError::EventNotSet.into()),
+                    None if self.secondary_cb => {
+                        return Err(CheckEventError::EventNotSet.into());
+                    }
+                    None => {
+                        match event.queue_family_id() {
+                            Some(id) if id != self.queue_family_id => {
+                                return Err(CheckEventError::WrongQueueFamily.into());
+                            }
+                            _ => (),
+                        }
+
+                        let stages = match event.set_stages() {
+                            Some(stages) => stages,
+                            None => return Err(CheckEventError::EventNotSet.into()),
+                        };
+                        waited_events.push((event.clone(), stages));
+                        stages
+                    }
+                };
+                source_stages = source_stages | stages;
+            }
+
+            check_wait_events(
+                self.device(),
+                events.iter().map(|e| &**e),
+                source_stages,
+                source_access,
+                destination_stages,
+                destination_access,
+            )?;
+
+            self.waited_events.extend(waited_events);
+
+            self.inner.wait_events(
+                events,
+                source_stages,
+                source_access,
+                destination_stages,
+                destination_access,
+            );
+            Ok(self)
+        }
+    }
 }
 
 unsafe impl<P> DeviceOwned for AutoCommandBufferBuilder<P> {
@@ -1636,6 +2646,7 @@ unsafe fn set_state<P>(destination: &mut SyncCommandBufferBuilder<P>, dynamic: &
 unsafe fn vertex_buffers<P>(
     destination: &mut SyncCommandBufferBuilder<P>,
     state_cacher: &mut StateCacher,
+    vertex_strides: &mut Option<VertexStrides>,
     vertex_buffers: Vec<Box<dyn BufferAccess + Send + Sync>>,
 ) -> Result<(), SyncCommandBufferBuilderError> {
     let binding_range = {
@@ -1649,6 +2660,9 @@ unsafe fn vertex_buffers<P>(
         }
     };
 
+    // The strides of the buffers that are rebound are no longer known.
+    *vertex_strides = None;
+
     let first_binding = binding_range.start;
     let num_bindings = binding_range.end - binding_range.start;
 
@@ -1677,6 +2691,8 @@ where
 {
     let sets = sets.into_vec();
 
+    state_cacher.bind_pipeline_layout(gfx, &pipeline);
+
     let first_binding = {
         let mut compare = state_cacher.bind_descriptor_sets(gfx);
         for set in sets.iter() {
@@ -1690,19 +2706,36 @@ where
         Some(fb) => fb,
     };
 
+    // The automatic builder has no way to specify dynamic offsets yet, so every dynamic buffer
+    // descriptor is bound with an offset of 0.
+    let mut num_dynamic_offsets = 0;
     let mut sets_binder = destination.bind_descriptor_sets();
     for set in sets.into_iter().skip(first_binding as usize) {
+        num_dynamic_offsets += set.dynamic_descriptor_count() as usize;
         sets_binder.add(set);
     }
-    sets_binder.submit(gfx, pipeline.clone(), first_binding, iter::empty())?;
+    let dynamic_offsets = iter::repeat(0).take(num_dynamic_offsets);
+    sets_binder.submit(gfx, pipeline.clone(), first_binding, dynamic_offsets)?;
     Ok(())
 }
 
 pub struct AutoCommandBuffer<P = StandardCommandPoolAlloc> {
     inner: SyncCommandBuffer<P>,
 
+    // For secondary command buffers executed inside a render pass, the attachments of the
+    // render pass and the index of the subpass.
+    inheritance_render_pass: Option<(Vec<AttachmentDescription>, u32)>,
+
     // Tracks usage of the command buffer on the GPU.
     submit_state: SubmitState,
+
+    // Events that are set or reset by the command buffer, with the stages of the last
+    // `set_event`, or `None` if the last command was `reset_event`.
+    events: Vec<(Arc<Event>, Option<PipelineStages>)>,
+
+    // Events that are waited on before being set in the command buffer, with the stages they
+    // were set with when the wait was recorded.
+    waited_events: Vec<(Arc<Event>, PipelineStages)>,
 }
 
 // Whether the command buffer can be submitted.
@@ -1726,6 +2759,20 @@ enum SubmitState {
     },
 }
 
+impl<P> AutoCommandBuffer<P> {
+    // Checks that the events waited on by the command buffer are still set with the same stages,
+    // and that they were set on `queue` if they were set by a command buffer.
+    fn check_waited_events(&self, queue: &Queue) -> Result<(), CommandBufferExecError> {
+        for &(ref event, stages) in self.waited_events.iter() {
+            if event.set_stages_on_queue(queue) != Some(stages) {
+                return Err(CommandBufferExecError::EventNotSet);
+            }
+        }
+
+        Ok(())
+    }
+}
+
 unsafe impl<P> CommandBuffer for AutoCommandBuffer<P> {
     type PoolAlloc = P;
 
@@ -1734,6 +2781,61 @@ unsafe impl<P> CommandBuffer for AutoCommandBuffer<P> {
         self.inner.as_ref()
     }
 
+    #[inline]
+    fn inheritance_render_pass(&self) -> Option<(&[AttachmentDescription], u32)> {
+        self.inheritance_render_pass
+            .as_ref()
+            .map(|&(ref attachments, index)| (&attachments[..], index))
+    }
+
+    #[inline]
+    fn usage(&self) -> Flags {
+        match self.submit_state {
+            SubmitState::Concurrent => Flags::SimultaneousUse,
+            SubmitState::ExclusiveUse { .. } => Flags::None,
+            SubmitState::OneTime { .. } => Flags::OneTimeSubmit,
+        }
+    }
+
+    #[inline]
+    fn lock_record(&self) -> Result<(), CommandBufferExecError> {
+        match self.submit_state {
+            SubmitState::OneTime {
+                ref already_submitted,
+            } => {
+                let was_already_submitted = already_submitted.swap(true, Ordering::SeqCst);
+                if was_already_submitted {
+                    return Err(CommandBufferExecError::OneTimeSubmitAlreadySubmitted);
+                }
+            }
+            SubmitState::ExclusiveUse { ref in_use } => {
+                let already_in_use = in_use.swap(true, Ordering::SeqCst);
+                if already_in_use {
+                    return Err(CommandBufferExecError::ExclusiveAlreadyInUse);
+                }
+            }
+            SubmitState::Concurrent => (),
+        };
+
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn unlock_record(&self) {
+        match self.submit_state {
+            SubmitState::OneTime {
+                ref already_submitted,
+            } => {
+                debug_assert!(already_submitted.load(Ordering::SeqCst));
+            }
+            SubmitState::ExclusiveUse { ref in_use } => {
+                let old_val = in_use.swap(false, Ordering::SeqCst);
+                debug_assert!(old_val);
+            }
+            SubmitState::Concurrent => (),
+        };
+    }
+
     #[inline]
     fn lock_submit(
         &self,
@@ -1758,8 +2860,16 @@ unsafe impl<P> CommandBuffer for AutoCommandBuffer<P> {
             SubmitState::Concurrent => (),
         };
 
-        let err = match self.inner.lock_submit(future, queue) {
-            Ok(()) => return Ok(()),
+        let err = match self.check_waited_events(queue) {
+            Ok(()) => match self.inner.lock_submit(future, queue) {
+                Ok(()) => {
+                    for &(ref event, stages) in self.events.iter() {
+                        event.submit_set(queue, stages);
+                    }
+                    return Ok(());
+                }
+                Err(err) => err,
+            },
             Err(err) => err,
         };
 
@@ -1888,6 +2998,13 @@ err_gen!(CopyImageError {
     SyncCommandBufferBuilderError,
 });
 
+err_gen!(CopyAttachmentToStorageImageError {
+    AutoCommandBufferBuilderContextError,
+    CheckCopyAttachmentToStorageError,
+    CheckCopyImageError,
+    SyncCommandBufferBuilderError,
+});
+
 err_gen!(BlitImageError {
     AutoCommandBufferBuilderContextError,
     CheckBlitImageError,
@@ -1912,6 +3029,11 @@ err_gen!(CopyBufferImageError {
     SyncCommandBufferBuilderError,
 });
 
+err_gen!(CopyQueryPoolResultsError {
+    AutoCommandBufferBuilderContextError,
+    CheckCopyQueryPoolResultsError,
+});
+
 err_gen!(FillBufferError {
     AutoCommandBufferBuilderContextError,
     CheckFillBufferError,
@@ -1925,12 +3047,28 @@ err_gen!(DispatchError {
     SyncCommandBufferBuilderError,
 });
 
+err_gen!(DispatchIndirectError {
+    AutoCommandBufferBuilderContextError,
+    CheckIndirectBufferError,
+    CheckPushConstantsValidityError,
+    CheckDescriptorSetsValidityError,
+    SyncCommandBufferBuilderError,
+});
+
+err_gen!(BindVertexBuffersWithStrideError {
+    AutoCommandBufferBuilderContextError,
+    CheckVertexBufferError,
+    CheckVertexBufferStridesError,
+    SyncCommandBufferBuilderError,
+});
+
 err_gen!(DrawError {
     AutoCommandBufferBuilderContextError,
     CheckDynamicStateValidityError,
     CheckPushConstantsValidityError,
     CheckDescriptorSetsValidityError,
     CheckVertexBufferError,
+    CheckVertexBufferStridesError,
     SyncCommandBufferBuilderError,
 });
 
@@ -1940,6 +3078,7 @@ err_gen!(DrawIndexedError {
     CheckPushConstantsValidityError,
     CheckDescriptorSetsValidityError,
     CheckVertexBufferError,
+    CheckVertexBufferStridesError,
     CheckIndexBufferError,
     SyncCommandBufferBuilderError,
 });
@@ -1950,6 +3089,7 @@ err_gen!(DrawIndirectError {
     CheckPushConstantsValidityError,
     CheckDescriptorSetsValidityError,
     CheckVertexBufferError,
+    CheckVertexBufferStridesError,
     SyncCommandBufferBuilderError,
 });
 
@@ -1959,15 +3099,33 @@ err_gen!(DrawIndexedIndirectError {
     CheckPushConstantsValidityError,
     CheckDescriptorSetsValidityError,
     CheckVertexBufferError,
+    CheckVertexBufferStridesError,
     CheckIndexBufferError,
     SyncCommandBufferBuilderError,
 });
 
 err_gen!(ExecuteCommandsError {
     AutoCommandBufferBuilderContextError,
+    CheckSecondaryRenderPassError,
+    CommandBufferExecError,
     SyncCommandBufferBuilderError,
 });
 
+err_gen!(PushConstantsError {
+    AutoCommandBufferBuilderContextError,
+    CheckPushConstantsRangeError,
+});
+
+err_gen!(SetExtendedDynamicStateError {
+    AutoCommandBufferBuilderContextError,
+    CheckExtendedDynamicStateError,
+});
+
+err_gen!(EventCommandError {
+    AutoCommandBufferBuilderContextError,
+    CheckEventError,
+});
+
 err_gen!(UpdateBufferError {
     AutoCommandBufferBuilderContextError,
     CheckUpdateBufferError,
@@ -2000,6 +3158,9 @@ pub enum AutoCommandBufferBuilderContextError {
     /// Tried to use a graphics pipeline whose render pass is incompatible with the current render
     /// pass.
     IncompatibleRenderPass,
+    /// Tried to execute a secondary command buffer that wasn't created with
+    /// `Flags::SimultaneousUse` in a command buffer that was.
+    SecondaryNotSimultaneousUse,
 }
 
 impl error::Error for AutoCommandBufferBuilderContextError {}
@@ -2040,6 +3201,10 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
                     "tried to use a graphics pipeline whose render pass is incompatible with the \
                  current render pass"
                 }
+                AutoCommandBufferBuilderContextError::SecondaryNotSimultaneousUse => {
+                    "tried to execute a secondary command buffer that wasn't created with the \
+                 simultaneous use flag in a command buffer that was"
+                }
             }
         )
     }
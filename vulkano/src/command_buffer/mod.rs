@@ -77,26 +77,40 @@ pub use self::auto::AutoCommandBuffer;
 pub use self::auto::AutoCommandBufferBuilder;
 pub use self::auto::AutoCommandBufferBuilderContextError;
 pub use self::auto::BeginRenderPassError;
+pub use self::auto::BindVertexBuffersWithStrideError;
 pub use self::auto::BlitImageError;
 pub use self::auto::BuildError;
 pub use self::auto::ClearColorImageError;
+pub use self::auto::CopyAttachmentToStorageImageError;
 pub use self::auto::CopyBufferError;
 pub use self::auto::CopyBufferImageError;
 pub use self::auto::CopyImageError;
+pub use self::auto::CopyQueryPoolResultsError;
 pub use self::auto::DispatchError;
+pub use self::auto::DispatchIndirectError;
 pub use self::auto::DrawError;
 pub use self::auto::DrawIndexedError;
 pub use self::auto::DrawIndexedIndirectError;
 pub use self::auto::DrawIndirectError;
+pub use self::auto::EventCommandError;
 pub use self::auto::ExecuteCommandsError;
 pub use self::auto::FillBufferError;
+pub use self::auto::PushConstantsError;
+pub use self::auto::SetExtendedDynamicStateError;
 pub use self::auto::UpdateBufferError;
+pub use self::reusable::ReusableCommandBuffer;
 pub use self::state_cacher::StateCacher;
 pub use self::state_cacher::StateCacherOutcome;
 pub use self::traits::CommandBuffer;
 pub use self::traits::CommandBufferExecError;
 pub use self::traits::CommandBufferExecFuture;
+pub use self::transfer::TransferCommandBufferBuilder;
 
+use std::mem;
+
+use device::Device;
+use format::AcceptsPixels;
+use format::Format;
 use pipeline::depth_stencil::DynamicStencilValue;
 use pipeline::viewport::Scissor;
 use pipeline::viewport::Viewport;
@@ -108,8 +122,10 @@ pub mod sys;
 pub mod validity;
 
 mod auto;
+mod reusable;
 mod state_cacher;
 mod traits;
+mod transfer;
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -138,6 +154,172 @@ pub struct DispatchIndirectCommand {
     pub z: u32,
 }
 
+/// Region of an image to copy to another image with `copy_image_regions`.
+///
+/// If `layer_count` is greater than 1, the copy happens between each individual layer as if they
+/// were separate images.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ImageCopy {
+    pub source_offset: [i32; 3],
+    pub source_base_array_layer: u32,
+    pub source_mip_level: u32,
+    pub destination_offset: [i32; 3],
+    pub destination_base_array_layer: u32,
+    pub destination_mip_level: u32,
+    pub extent: [u32; 3],
+    pub layer_count: u32,
+}
+
+/// Region of an image to blit to another image with `blit_image_regions`.
+///
+/// The area of the source between `source_top_left` and `source_bottom_right` is resized to fit
+/// the area of the destination between `destination_top_left` and `destination_bottom_right`.
+/// The image is flipped along an axis if the top-left coordinate is greater than the bottom-right
+/// coordinate on that axis for only one of the source and the destination.
+///
+/// If `layer_count` is greater than 1, the blit happens between each individual layer as if they
+/// were separate images.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ImageBlit {
+    pub source_top_left: [i32; 3],
+    pub source_bottom_right: [i32; 3],
+    pub source_base_array_layer: u32,
+    pub source_mip_level: u32,
+    pub destination_top_left: [i32; 3],
+    pub destination_bottom_right: [i32; 3],
+    pub destination_base_array_layer: u32,
+    pub destination_mip_level: u32,
+    pub layer_count: u32,
+}
+
+/// Layout of the texels of an image in a buffer, for copies between buffers and images.
+///
+/// By default the texels are tightly packed, and each row of the image directly follows the
+/// previous one. A non-zero `row_length` or `image_height` adds padding at the end of each row or
+/// of each slice. The texel `(x, y)` of the slice `z` (the array layer or the depth coordinate)
+/// is then stored at the element `buffer_offset + ((z * image_height + y) * row_length + x) *
+/// rate` of the buffer, where `rate` is the number of buffer elements per texel. For compressed
+/// formats, coordinates are in blocks instead of texels. Padding elements are neither read nor
+/// written by copies.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct BufferImageCopyLayout {
+    /// Index of the element of the buffer where the first texel is stored.
+    pub buffer_offset: usize,
+    /// Number of texels between the starts of two consecutive rows, or 0 if the rows are
+    /// tightly packed. Must otherwise be at least the width of the copied region, and a multiple
+    /// of the block width for compressed formats.
+    pub row_length: u32,
+    /// Number of rows between the starts of two consecutive slices, or 0 if the slices are
+    /// tightly packed. Must otherwise be at least the height of the copied region, and a
+    /// multiple of the block height for compressed formats.
+    pub image_height: u32,
+}
+
+impl BufferImageCopyLayout {
+    /// Returns a layout where texels are tightly packed.
+    #[inline]
+    pub fn packed() -> BufferImageCopyLayout {
+        BufferImageCopyLayout::default()
+    }
+
+    /// Returns the layout that gives the best copy performance on `device` for an image region
+    /// that is `width` texels wide, in a buffer of elements of type `P` that starts
+    /// `buffer_start` bytes after the start of its `UnsafeBuffer`. This is the `offset` of the
+    /// `BufferInner` of the buffer, which is 0 unless the buffer is a slice.
+    ///
+    /// The first texel is skipped forward to the first element whose offset in the
+    /// `UnsafeBuffer` is a multiple of the `optimal_buffer_copy_offset_alignment` limit and of
+    /// the texel block size. Rows are padded so that the distance in bytes between two rows is a
+    /// multiple of the `optimal_buffer_copy_row_pitch_alignment` limit.
+    pub fn optimal<P>(
+        device: &Device,
+        format: Format,
+        width: u32,
+        buffer_start: usize,
+    ) -> BufferImageCopyLayout
+    where
+        Format: AcceptsPixels<P>,
+    {
+        let block_size = match format.size() {
+            Some(size) if size != 0 => size,
+            _ => return BufferImageCopyLayout::packed(),
+        };
+        let limits = device.physical_device().limits();
+
+        // Offsets must stay a multiple of the block size as well.
+        let offset_alignment = lcm(
+            (limits.optimal_buffer_copy_offset_alignment() as usize).max(1),
+            block_size,
+        );
+        let element_size = mem::size_of::<P>().max(1);
+        let buffer_offset = (0..offset_alignment)
+            .find(|n| (buffer_start + n * element_size) % offset_alignment == 0)
+            .unwrap_or(0);
+
+        let alignment = limits.optimal_buffer_copy_row_pitch_alignment() as usize;
+        let (block_width, _) = format.block_dimensions();
+        let blocks = ((width + block_width - 1) / block_width) as usize;
+
+        // The row pitch must stay a multiple of the block size.
+        let multiple = lcm(alignment.max(1), block_size);
+        let row_pitch = (blocks * block_size + multiple - 1) / multiple * multiple;
+        let padded_blocks = row_pitch / block_size;
+
+        BufferImageCopyLayout {
+            buffer_offset,
+            row_length: if padded_blocks == blocks {
+                0
+            } else {
+                padded_blocks as u32 * block_width
+            },
+            image_height: 0,
+        }
+    }
+
+    /// Returns the minimum number of elements that a buffer must contain in order to hold a
+    /// region of `image_size` texels and `num_layers` array layers of an image of the given
+    /// format with this layout.
+    pub fn required_len<P>(&self, format: Format, image_size: [u32; 3], num_layers: u32) -> usize
+    where
+        Format: AcceptsPixels<P>,
+    {
+        let (block_width, block_height) = format.block_dimensions();
+        let blocks_x = ((image_size[0] + block_width - 1) / block_width) as usize;
+        let blocks_y = ((image_size[1] + block_height - 1) / block_height) as usize;
+        let slices = image_size[2] as usize * num_layers as usize;
+
+        if blocks_x == 0 || blocks_y == 0 || slices == 0 {
+            return self.buffer_offset;
+        }
+
+        let row_blocks = if self.row_length == 0 {
+            blocks_x
+        } else {
+            ((self.row_length + block_width - 1) / block_width) as usize
+        };
+        let slice_rows = if self.image_height == 0 {
+            blocks_y
+        } else {
+            ((self.image_height + block_height - 1) / block_height) as usize
+        };
+
+        let num_blocks =
+            (slices - 1) * slice_rows * row_blocks + (blocks_y - 1) * row_blocks + blocks_x;
+        self.buffer_offset + num_blocks * format.rate() as usize
+    }
+}
+
+// Least common multiple of two non-zero numbers.
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        let r = x % y;
+        x = y;
+        y = r;
+    }
+    a / x * b
+}
+
 /// The dynamic state to use for a draw command.
 // TODO: probably not the right location
 #[derive(Debug, Clone)]
@@ -0,0 +1,221 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use command_buffer::pool::UnsafeCommandPool;
+use device::Device;
+use instance::QueueFamily;
+use sync::Fence;
+use sync::FenceWaitError;
+use OomError;
+
+/// One command pool per frame in flight.
+///
+/// Resetting a command pool while the GPU is still executing one of its command buffers isn't
+/// allowed. The usual solution is to record the commands of each frame in a different pool, and
+/// to reset a pool only once the frame that used it has finished executing.
+///
+/// Call `next_frame` at the start of each frame to get the pool to record into, then `submitted`
+/// with the fence signaled by each submission of the frame. The next time that this pool is
+/// returned by `next_frame`, all these fences are waited upon before resetting the pool.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use vulkano::command_buffer::pool::FrameCommandPools;
+/// use vulkano::sync::Fence;
+/// # let device: Arc<vulkano::device::Device> = return;
+/// # let queue: Arc<vulkano::device::Queue> = return;
+///
+/// let mut pools = FrameCommandPools::new(device.clone(), queue.family(), 2).unwrap();
+///
+/// loop {
+///     let pool = unsafe { pools.next_frame().unwrap() };
+///     let command_buffers = pool.alloc_command_buffers(false, 1).unwrap();
+///     // ... record and submit the command buffers so that they signal `fence` ...
+///     # let fence: Arc<Fence> = return;
+///     pools.submitted(fence);
+/// }
+/// ```
+pub struct FrameCommandPools {
+    pools: Vec<UnsafeCommandPool>,
+
+    // For each pool, the fences signaled by the submissions of the frame that used it.
+    fences: Vec<SmallVec<[Arc<Fence>; 2]>>,
+
+    // Index of the current frame within `pools`.
+    current: usize,
+}
+
+impl FrameCommandPools {
+    /// Creates `frames_in_flight` transient command pools for the given queue family.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `frames_in_flight` is 0.
+    /// - Panics if the queue family doesn't belong to the same physical device as `device`.
+    ///
+    pub fn new(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        frames_in_flight: usize,
+    ) -> Result<FrameCommandPools, OomError> {
+        assert!(frames_in_flight != 0);
+
+        let pools = (0..frames_in_flight)
+            .map(|_| UnsafeCommandPool::new(device.clone(), queue_family, true, false))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(FrameCommandPools {
+            pools,
+            fences: (0..frames_in_flight).map(|_| SmallVec::new()).collect(),
+            current: frames_in_flight - 1,
+        })
+    }
+
+    /// Returns the number of frames in flight, which is the number of pools.
+    #[inline]
+    pub fn frames_in_flight(&self) -> usize {
+        self.pools.len()
+    }
+
+    /// Returns the index of the current frame, between 0 and `frames_in_flight() - 1`.
+    #[inline]
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the pool of the current frame.
+    #[inline]
+    pub fn current_pool(&self) -> &UnsafeCommandPool {
+        &self.pools[self.current]
+    }
+
+    /// Moves to the next frame and returns its pool.
+    ///
+    /// Waits for all the fences that were passed to `submitted` the last time that this pool
+    /// was used to be signaled. The pool is then reset.
+    ///
+    /// # Safety
+    ///
+    /// The command buffers that were allocated from the pool the last time that it was used jump
+    /// to the initial state, and must not be submitted again.
+    ///
+    pub unsafe fn next_frame(&mut self) -> Result<&UnsafeCommandPool, FrameCommandPoolsError> {
+        self.current = (self.current + 1) % self.pools.len();
+
+        for fence in self.fences[self.current].iter() {
+            fence.wait(None)?;
+        }
+        self.fences[self.current].clear();
+
+        self.pools[self.current].reset(false)?;
+        Ok(&self.pools[self.current])
+    }
+
+    /// Adds a fence that is signaled once some of the command buffers of the current frame have
+    /// finished executing.
+    ///
+    /// Call this once for each submission of the frame. The pool of the frame is only reset once
+    /// all these fences are signaled.
+    #[inline]
+    pub fn submitted(&mut self, fence: Arc<Fence>) {
+        self.fences[self.current].push(fence);
+    }
+}
+
+impl fmt::Debug for FrameCommandPools {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("FrameCommandPools")
+            .field("frames_in_flight", &self.pools.len())
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+/// Error that can happen when moving to the next frame of a `FrameCommandPools`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameCommandPoolsError {
+    /// Error while waiting for the fences of the previous use of the pool.
+    FenceWaitError(FenceWaitError),
+    /// Not enough memory to reset the pool.
+    OomError(OomError),
+}
+
+impl error::Error for FrameCommandPoolsError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            FrameCommandPoolsError::FenceWaitError(ref err) => Some(err),
+            FrameCommandPoolsError::OomError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FrameCommandPoolsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                FrameCommandPoolsError::FenceWaitError(_) => "failed to wait for a fence",
+                FrameCommandPoolsError::OomError(_) => "not enough memory to reset the pool",
+            }
+        )
+    }
+}
+
+impl From<FenceWaitError> for FrameCommandPoolsError {
+    #[inline]
+    fn from(err: FenceWaitError) -> FrameCommandPoolsError {
+        FrameCommandPoolsError::FenceWaitError(err)
+    }
+}
+
+impl From<OomError> for FrameCommandPoolsError {
+    #[inline]
+    fn from(err: OomError) -> FrameCommandPoolsError {
+        FrameCommandPoolsError::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_buffer::pool::FrameCommandPools;
+    use std::sync::Arc;
+    use sync::Fence;
+
+    #[test]
+    fn cycles_through_frames() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let mut pools = FrameCommandPools::new(device.clone(), queue.family(), 2).unwrap();
+        assert_eq!(pools.frames_in_flight(), 2);
+
+        unsafe {
+            pools.next_frame().unwrap();
+            assert_eq!(pools.current_frame(), 0);
+            pools.submitted(Arc::new(Fence::alloc_signaled(device.clone()).unwrap()));
+            pools.submitted(Arc::new(Fence::alloc_signaled(device.clone()).unwrap()));
+            assert_eq!(pools.fences[0].len(), 2);
+
+            pools.next_frame().unwrap();
+            assert_eq!(pools.current_frame(), 1);
+
+            pools.next_frame().unwrap();
+            assert_eq!(pools.current_frame(), 0);
+            assert!(pools.fences[0].is_empty());
+        }
+    }
+}
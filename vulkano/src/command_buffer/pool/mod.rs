@@ -21,12 +21,15 @@ use instance::QueueFamily;
 use device::DeviceOwned;
 use OomError;
 
+pub use self::frame::FrameCommandPools;
+pub use self::frame::FrameCommandPoolsError;
 pub use self::standard::StandardCommandPool;
 pub use self::sys::CommandPoolTrimError;
 pub use self::sys::UnsafeCommandPool;
 pub use self::sys::UnsafeCommandPoolAlloc;
 pub use self::sys::UnsafeCommandPoolAllocIter;
 
+mod frame;
 pub mod standard;
 mod sys;
 
@@ -41,6 +41,9 @@ pub struct UnsafeCommandPool {
     // Index of the associated queue family in the physical device.
     queue_family_index: u32,
 
+    // True if the command buffers allocated from this pool are protected.
+    protected: bool,
+
     // We don't want `UnsafeCommandPool` to implement Sync.
     // This marker unimplements both Send and Sync, but we reimplement Send manually right under.
     dummy_avoid_sync: PhantomData<*const u8>,
@@ -67,6 +70,46 @@ impl UnsafeCommandPool {
         queue_family: QueueFamily,
         transient: bool,
         reset_cb: bool,
+    ) -> Result<UnsafeCommandPool, OomError> {
+        UnsafeCommandPool::new_impl(device, queue_family, transient, reset_cb, false)
+    }
+
+    /// Creates a new pool whose command buffers are protected.
+    ///
+    /// Protected command buffers can access protected resources, but can't write to unprotected
+    /// resources. They must be submitted to a protected-capable queue with a protected
+    /// submission.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue family doesn't belong to the same physical device as `device`.
+    /// - Panics if the `protected_memory` feature isn't enabled.
+    /// - Panics if the queue family doesn't support protected queues.
+    ///
+    pub fn new_protected(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        transient: bool,
+        reset_cb: bool,
+    ) -> Result<UnsafeCommandPool, OomError> {
+        assert!(
+            device.enabled_features().protected_memory,
+            "The `protected_memory` feature must be enabled to create a protected command pool"
+        );
+        assert!(
+            queue_family.supports_protected(),
+            "The queue family doesn't support protected queues"
+        );
+
+        UnsafeCommandPool::new_impl(device, queue_family, transient, reset_cb, true)
+    }
+
+    fn new_impl(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        transient: bool,
+        reset_cb: bool,
+        protected: bool,
     ) -> Result<UnsafeCommandPool, OomError> {
         assert_eq!(
             device.physical_device().internal_object(),
@@ -87,7 +130,12 @@ impl UnsafeCommandPool {
             } else {
                 0
             };
-            flag1 | flag2
+            let flag3 = if protected {
+                vk::COMMAND_POOL_CREATE_PROTECTED_BIT
+            } else {
+                0
+            };
+            flag1 | flag2 | flag3
         };
 
         let pool = unsafe {
@@ -112,6 +160,7 @@ impl UnsafeCommandPool {
             pool: pool,
             device: device.clone(),
             queue_family_index: queue_family.id(),
+            protected,
             dummy_avoid_sync: PhantomData,
         })
     }
@@ -174,6 +223,7 @@ impl UnsafeCommandPool {
         if count == 0 {
             return Ok(UnsafeCommandPoolAllocIter {
                 list: vec![].into_iter(),
+                protected: self.protected,
             });
         }
 
@@ -202,6 +252,7 @@ impl UnsafeCommandPool {
 
             Ok(UnsafeCommandPoolAllocIter {
                 list: out.into_iter(),
+                protected: self.protected,
             })
         }
     }
@@ -234,6 +285,12 @@ impl UnsafeCommandPool {
             .queue_family_by_id(self.queue_family_index)
             .unwrap()
     }
+
+    /// Returns true if the command buffers allocated from this pool are protected.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandPool {
@@ -265,7 +322,15 @@ impl Drop for UnsafeCommandPool {
 }
 
 /// Opaque type that represents a command buffer allocated from a pool.
-pub struct UnsafeCommandPoolAlloc(vk::CommandBuffer);
+pub struct UnsafeCommandPoolAlloc(vk::CommandBuffer, bool);
+
+impl UnsafeCommandPoolAlloc {
+    /// Returns true if the command buffer was allocated from a protected pool.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.1
+    }
+}
 
 unsafe impl VulkanObject for UnsafeCommandPoolAlloc {
     type Object = vk::CommandBuffer;
@@ -282,6 +347,7 @@ unsafe impl VulkanObject for UnsafeCommandPoolAlloc {
 #[derive(Debug)]
 pub struct UnsafeCommandPoolAllocIter {
     list: VecIntoIter<vk::CommandBuffer>,
+    protected: bool,
 }
 
 impl Iterator for UnsafeCommandPoolAllocIter {
@@ -289,7 +355,10 @@ impl Iterator for UnsafeCommandPoolAllocIter {
 
     #[inline]
     fn next(&mut self) -> Option<UnsafeCommandPoolAlloc> {
-        self.list.next().map(|cb| UnsafeCommandPoolAlloc(cb))
+        let protected = self.protected;
+        self.list
+            .next()
+            .map(|cb| UnsafeCommandPoolAlloc(cb, protected))
     }
 
     #[inline]
@@ -0,0 +1,181 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use command_buffer::AutoCommandBuffer;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::BuildError;
+use device::Device;
+use instance::QueueFamily;
+use OomError;
+
+/// Primary command buffer that is recorded once and submitted as many times as needed.
+///
+/// Re-recording a command buffer every frame is a waste of CPU time if its content rarely
+/// changes. A `ReusableCommandBuffer` holds a closure that records the commands, and records them
+/// in a command buffer created with the "simultaneous use" flag. This command buffer can then be
+/// submitted repeatedly, including while a previous submission is still in flight.
+///
+/// The command buffer keeps alive all the resources that it uses, like any other
+/// `AutoCommandBuffer`. Descriptor sets can't be modified once built, therefore the only way for
+/// the content of the command buffer to change is to re-record it. When something captured by
+/// the closure changes, for example a descriptor set was rebuilt with a new image, call
+/// `invalidate`. The next call to `command_buffer` then records a new command buffer. Previous
+/// command buffers that are still in flight stay alive until the GPU is done with them.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::command_buffer::BuildError;
+/// use vulkano::command_buffer::CommandBuffer;
+/// use vulkano::command_buffer::ReusableCommandBuffer;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+///
+/// let reusable = ReusableCommandBuffer::<BuildError>::new(
+///     device.clone(),
+///     queue.family(),
+///     |builder| {
+///         // builder.draw(...);
+///         Ok(())
+///     },
+/// )
+/// .unwrap();
+///
+/// // Every frame.
+/// let future = reusable.command_buffer().unwrap().execute(queue.clone()).unwrap();
+/// ```
+pub struct ReusableCommandBuffer<E> {
+    device: Arc<Device>,
+    queue_family: u32,
+    record: Box<dyn Fn(&mut AutoCommandBufferBuilder) -> Result<(), E> + Send + Sync>,
+    // `None` if the command buffer has been invalidated.
+    command_buffer: Mutex<Option<Arc<AutoCommandBuffer>>>,
+}
+
+impl<E> ReusableCommandBuffer<E>
+where
+    E: From<OomError> + From<BuildError>,
+{
+    /// Records the command buffer for the first time by calling `record`.
+    ///
+    /// `record` is called again every time the command buffer needs to be re-recorded after a
+    /// call to `invalidate`.
+    pub fn new<F>(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        record: F,
+    ) -> Result<ReusableCommandBuffer<E>, E>
+    where
+        F: Fn(&mut AutoCommandBufferBuilder) -> Result<(), E> + Send + Sync + 'static,
+    {
+        let reusable = ReusableCommandBuffer {
+            device,
+            queue_family: queue_family.id(),
+            record: Box::new(record),
+            command_buffer: Mutex::new(None),
+        };
+
+        reusable.command_buffer()?;
+        Ok(reusable)
+    }
+
+    /// Returns the command buffer, recording it first if it has been invalidated.
+    ///
+    /// You should call this function every time you submit the command buffer instead of keeping
+    /// the returned command buffer around, otherwise invalidations won't be taken into account.
+    pub fn command_buffer(&self) -> Result<Arc<AutoCommandBuffer>, E> {
+        let mut command_buffer = self.command_buffer.lock().unwrap();
+
+        if let Some(ref command_buffer) = *command_buffer {
+            return Ok(command_buffer.clone());
+        }
+
+        let queue_family = self
+            .device
+            .physical_device()
+            .queue_family_by_id(self.queue_family)
+            .unwrap();
+        let mut builder =
+            AutoCommandBufferBuilder::primary_simultaneous_use(self.device.clone(), queue_family)?;
+        (self.record)(&mut builder)?;
+        let recorded = Arc::new(builder.build()?);
+
+        *command_buffer = Some(recorded.clone());
+        Ok(recorded)
+    }
+
+    /// Marks the command buffer as outdated. It will be recorded again the next time
+    /// `command_buffer` is called.
+    ///
+    /// Call this function whenever a resource or a value captured by the recording closure
+    /// changes.
+    #[inline]
+    pub fn invalidate(&self) {
+        *self.command_buffer.lock().unwrap() = None;
+    }
+
+    /// Returns true if the command buffer is up to date and doesn't need to be re-recorded.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.command_buffer.lock().unwrap().is_some()
+    }
+}
+
+impl<E> fmt::Debug for ReusableCommandBuffer<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("ReusableCommandBuffer")
+            .field("device", &self.device)
+            .field("queue_family", &self.queue_family)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use command_buffer::BuildError;
+    use command_buffer::CommandBuffer;
+    use command_buffer::ReusableCommandBuffer;
+    use sync::GpuFuture;
+
+    #[test]
+    fn submit_twice_and_invalidate() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let reusable =
+            ReusableCommandBuffer::<BuildError>::new(device, queue.family(), |_| Ok(())).unwrap();
+        let first = reusable.command_buffer().unwrap();
+        assert!(Arc::ptr_eq(&first, &reusable.command_buffer().unwrap()));
+
+        let future1 = first
+            .clone()
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        let future2 = first
+            .clone()
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+
+        reusable.invalidate();
+        assert!(!reusable.is_valid());
+        assert!(!Arc::ptr_eq(&first, &reusable.command_buffer().unwrap()));
+
+        future1.wait(None).unwrap();
+        future2.wait(None).unwrap();
+    }
+}
@@ -9,6 +9,9 @@
 
 use buffer::BufferAccess;
 use command_buffer::DynamicState;
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::ShaderStages;
+use descriptor::pipeline_layout::PipelineLayoutDesc;
 use descriptor::DescriptorSet;
 use pipeline::input_assembly::IndexType;
 use pipeline::ComputePipelineAbstract;
@@ -34,6 +37,10 @@ pub struct StateCacher {
     compute_descriptor_sets: SmallVec<[vk::DescriptorSet; 12]>,
     // The descriptor sets for the graphics pipeline.
     graphics_descriptor_sets: SmallVec<[vk::DescriptorSet; 12]>,
+    // The pipeline layout the compute descriptor sets were bound with.
+    compute_layout: Option<BoundLayout>,
+    // The pipeline layout the graphics descriptor sets were bound with.
+    graphics_layout: Option<BoundLayout>,
     // If the user starts comparing descriptor sets, but drops the helper struct in the middle of
     // the processing then we will end up in a weird state. This bool is true when we start
     // comparing sets, and is set to false when we end up comparing. If it was true when we start
@@ -47,6 +54,48 @@ pub struct StateCacher {
     index_buffer: Option<(vk::Buffer, usize, IndexType)>,
 }
 
+// Description of a pipeline layout, used to determine which bound descriptor sets are disturbed
+// when binding descriptor sets with a different layout.
+#[derive(Debug, Clone, PartialEq)]
+struct BoundLayout {
+    sets: SmallVec<[SmallVec<[Option<DescriptorDesc>; 8]>; 4]>,
+    push_constants: SmallVec<[(usize, usize, ShaderStages); 4]>,
+}
+
+impl BoundLayout {
+    fn new<Pl>(layout: &Pl) -> BoundLayout
+    where
+        Pl: ?Sized + PipelineLayoutDesc,
+    {
+        BoundLayout {
+            sets: (0..layout.num_sets())
+                .map(|set| {
+                    (0..layout.num_bindings_in_set(set).unwrap_or(0))
+                        .map(|binding| layout.descriptor(set, binding))
+                        .collect()
+                })
+                .collect(),
+            push_constants: (0..layout.num_push_constants_ranges())
+                .filter_map(|num| layout.push_constants_range(num))
+                .map(|range| (range.offset, range.size, range.stages))
+                .collect(),
+        }
+    }
+
+    // Returns the number of leading sets for which the two layouts are compatible.
+    fn compatible_sets(&self, other: &BoundLayout) -> usize {
+        if self.push_constants != other.push_constants {
+            return 0;
+        }
+
+        self.sets
+            .iter()
+            .zip(other.sets.iter())
+            .take_while(|&(a, b)| a == b)
+            .count()
+    }
+}
+
 /// Outcome of an operation.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum StateCacherOutcome {
@@ -66,6 +115,8 @@ impl StateCacher {
             graphics_pipeline: 0,
             compute_descriptor_sets: SmallVec::new(),
             graphics_descriptor_sets: SmallVec::new(),
+            compute_layout: None,
+            graphics_layout: None,
             poisoned_descriptor_sets: false,
             vertex_buffers: SmallVec::new(),
             poisoned_vertex_buffers: false,
@@ -82,6 +133,8 @@ impl StateCacher {
         self.graphics_pipeline = 0;
         self.compute_descriptor_sets = SmallVec::new();
         self.graphics_descriptor_sets = SmallVec::new();
+        self.compute_layout = None;
+        self.graphics_layout = None;
         self.vertex_buffers = SmallVec::new();
         self.index_buffer = None;
     }
@@ -115,6 +168,41 @@ impl StateCacher {
         changed
     }
 
+    /// Informs the state cacher of the pipeline layout that the next descriptor sets are going to
+    /// be bound with, and returns the number of leading sets that stay valid.
+    ///
+    /// Binding descriptor sets with a pipeline layout disturbs the sets that were bound with a
+    /// previous layout, starting from the first set whose layout differs. If the push constant
+    /// ranges differ, all the sets are disturbed. The disturbed sets are removed from the cache,
+    /// while the leading sets whose layouts are identical are kept. This makes it possible to
+    /// bind a set shared by multiple pipelines (for example camera and lights) only once.
+    ///
+    /// Call this function before `bind_descriptor_sets` whenever the pipeline may have changed.
+    pub fn bind_pipeline_layout<Pl>(&mut self, graphics: bool, layout: &Pl) -> usize
+    where
+        Pl: ?Sized + PipelineLayoutDesc,
+    {
+        let incoming = BoundLayout::new(layout);
+
+        let (bound_layout, bound_sets) = if graphics {
+            (
+                &mut self.graphics_layout,
+                &mut self.graphics_descriptor_sets,
+            )
+        } else {
+            (&mut self.compute_layout, &mut self.compute_descriptor_sets)
+        };
+
+        let compatible = match *bound_layout {
+            Some(ref bound_layout) => bound_layout.compatible_sets(&incoming),
+            None => 0,
+        };
+
+        bound_sets.truncate(compatible);
+        *bound_layout = Some(incoming);
+        compatible
+    }
+
     /// Starts the process of comparing a list of descriptor sets to the descriptor sets currently
     /// in cache.
     ///
@@ -362,6 +450,12 @@ mod tests {
     use buffer::BufferUsage;
     use buffer::CpuAccessibleBuffer;
     use command_buffer::state_cacher::StateCacher;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::iter;
 
     #[test]
     fn vb_caching_single() {
@@ -491,4 +585,41 @@ mod tests {
             assert_eq!(bind_vb.compare(), Some(0..1));
         }
     }
+
+    #[test]
+    fn compatible_layouts() {
+        let uniform = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(false),
+                storage: false,
+            }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let sampler = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            ..uniform.clone()
+        };
+
+        let layout = |sets: Vec<Vec<Option<DescriptorDesc>>>| {
+            RuntimePipelineDesc::new(sets, iter::empty()).unwrap()
+        };
+        let a = layout(vec![
+            vec![Some(uniform.clone())],
+            vec![Some(sampler.clone())],
+        ]);
+        let b = layout(vec![
+            vec![Some(uniform.clone())],
+            vec![Some(uniform.clone())],
+        ]);
+        let c = layout(vec![vec![Some(sampler.clone())]]);
+
+        let mut cacher = StateCacher::new();
+        assert_eq!(cacher.bind_pipeline_layout(true, &a), 0);
+        assert_eq!(cacher.bind_pipeline_layout(true, &a), 2);
+        assert_eq!(cacher.bind_pipeline_layout(true, &b), 1);
+        assert_eq!(cacher.bind_pipeline_layout(true, &c), 0);
+        assert_eq!(cacher.bind_pipeline_layout(false, &c), 0);
+    }
 }
@@ -16,6 +16,7 @@ use std::ptr;
 use buffer::sys::UnsafeBuffer;
 use device::Queue;
 use image::sys::UnsafeImage;
+use image::ImageAspects;
 use memory::DeviceMemory;
 use sync::Fence;
 use sync::Semaphore;
@@ -455,7 +456,7 @@ impl<'a> SubmitBindSparseImageBindBuilder<'a> {
     ///
     /// # Safety
     ///
-    /// - `image` must be an image with sparse binding enabled.
+    /// - `image` must be an image with sparse residency enabled.
     pub unsafe fn new(image: &'a UnsafeImage) -> SubmitBindSparseImageBindBuilder {
         SubmitBindSparseImageBindBuilder {
             image: image.internal_object(),
@@ -464,7 +465,86 @@ impl<'a> SubmitBindSparseImageBindBuilder<'a> {
         }
     }
 
-    // TODO: finish
+    /// Binds memory to a block of texels of one mipmap level and array layer of the image.
+    ///
+    /// # Safety
+    ///
+    /// - `aspects` must be one of the groups of aspects returned by
+    ///   `UnsafeImage::sparse_memory_requirements`.
+    /// - `offset` must be a multiple of the image granularity, and `extent` must be a multiple of
+    ///   the image granularity or reach the edge of the mipmap level.
+    /// - `mip_level` must not be part of the mip tail. The mip tail must be bound with a
+    ///   `SubmitBindSparseImageOpaqueBindBuilder` instead.
+    /// - `memory` must have a memory type allowed by the memory requirements of the image, and
+    ///   `memory_offset` must be aligned to the alignment of the memory requirements.
+    ///
+    pub unsafe fn add_bind(
+        &mut self,
+        aspects: ImageAspects,
+        mip_level: u32,
+        array_layer: u32,
+        offset: [i32; 3],
+        extent: [u32; 3],
+        memory: &DeviceMemory,
+        memory_offset: usize,
+    ) {
+        self.binds.push(vk::SparseImageMemoryBind {
+            subresource: vk::ImageSubresource {
+                aspectMask: aspects.into_vulkan_bits(),
+                mipLevel: mip_level,
+                arrayLayer: array_layer,
+            },
+            offset: vk::Offset3D {
+                x: offset[0],
+                y: offset[1],
+                z: offset[2],
+            },
+            extent: vk::Extent3D {
+                width: extent[0],
+                height: extent[1],
+                depth: extent[2],
+            },
+            memory: memory.internal_object(),
+            memoryOffset: memory_offset as vk::DeviceSize,
+            flags: 0,
+        });
+    }
+
+    /// Unbinds the memory of a block of texels of one mipmap level and array layer of the image.
+    ///
+    /// # Safety
+    ///
+    /// Same as `add_bind`.
+    ///
+    pub unsafe fn add_unbind(
+        &mut self,
+        aspects: ImageAspects,
+        mip_level: u32,
+        array_layer: u32,
+        offset: [i32; 3],
+        extent: [u32; 3],
+    ) {
+        self.binds.push(vk::SparseImageMemoryBind {
+            subresource: vk::ImageSubresource {
+                aspectMask: aspects.into_vulkan_bits(),
+                mipLevel: mip_level,
+                arrayLayer: array_layer,
+            },
+            offset: vk::Offset3D {
+                x: offset[0],
+                y: offset[1],
+                z: offset[2],
+            },
+            extent: vk::Extent3D {
+                width: extent[0],
+                height: extent[1],
+                depth: extent[2],
+            },
+            memory: 0,
+            memoryOffset: 0,
+            flags: 0,
+        });
+    }
 }
 
 /// Error that can happen when submitting the present prototype.
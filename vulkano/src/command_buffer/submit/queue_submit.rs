@@ -18,6 +18,7 @@ use device::Queue;
 use sync::Fence;
 use sync::PipelineStages;
 use sync::Semaphore;
+use sync::TimelineSemaphore;
 
 use check_errors;
 use vk;
@@ -31,9 +32,18 @@ use VulkanObject;
 #[derive(Debug)]
 pub struct SubmitCommandBufferBuilder<'a> {
     wait_semaphores: SmallVec<[vk::Semaphore; 16]>,
+    wait_values: SmallVec<[u64; 16]>,
     destination_stages: SmallVec<[vk::PipelineStageFlags; 8]>,
     signal_semaphores: SmallVec<[vk::Semaphore; 16]>,
+    signal_values: SmallVec<[u64; 16]>,
+    // True if at least one timeline semaphore has been added, in which case a
+    // `VkTimelineSemaphoreSubmitInfo` must be chained to the submission.
+    has_timeline_semaphores: bool,
     command_buffers: SmallVec<[vk::CommandBuffer; 4]>,
+    // Number of command buffers in `command_buffers` that are protected.
+    num_protected_command_buffers: usize,
+    // True if this is a protected submission.
+    protected: bool,
     fence: vk::Fence,
     marker: PhantomData<&'a ()>,
 }
@@ -44,9 +54,14 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     pub fn new() -> SubmitCommandBufferBuilder<'a> {
         SubmitCommandBufferBuilder {
             wait_semaphores: SmallVec::new(),
+            wait_values: SmallVec::new(),
             destination_stages: SmallVec::new(),
             signal_semaphores: SmallVec::new(),
+            signal_values: SmallVec::new(),
+            has_timeline_semaphores: false,
             command_buffers: SmallVec::new(),
+            num_protected_command_buffers: 0,
+            protected: false,
             fence: 0,
             marker: PhantomData,
         }
@@ -144,9 +159,43 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         debug_assert!(stages.into_vulkan_bits() != 0);
         // TODO: debug assert that the device supports the stages
         self.wait_semaphores.push(semaphore.internal_object());
+        // The value is ignored for binary semaphores.
+        self.wait_values.push(0);
         self.destination_stages.push(stages.into_vulkan_bits());
     }
 
+    /// Adds a timeline semaphore to be waited upon before the command buffers are executed. The
+    /// wait is over once the counter of the semaphore reaches at least `value`.
+    ///
+    /// Only the given `stages` of the command buffers added afterwards will wait upon
+    /// the semaphore. Other stages not included in `stages` can execute before waiting.
+    ///
+    /// # Safety
+    ///
+    /// - The stages must be supported by the device.
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has at least started executing the command buffers.
+    ///
+    /// - If you submit this builder, there must be one or more submissions or host operations that
+    ///   are going to signal the semaphore with a value greater or equal to `value`.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_wait_timeline_semaphore(
+        &mut self,
+        semaphore: &'a TimelineSemaphore,
+        value: u64,
+        stages: PipelineStages,
+    ) {
+        debug_assert!(stages.into_vulkan_bits() != 0);
+        self.wait_semaphores.push(semaphore.internal_object());
+        self.wait_values.push(value);
+        self.destination_stages.push(stages.into_vulkan_bits());
+        self.has_timeline_semaphores = true;
+    }
+
     /// Adds a command buffer that is executed as part of this command.
     ///
     /// The command buffers are submitted in the order in which they are added.
@@ -168,6 +217,25 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     #[inline]
     pub unsafe fn add_command_buffer<P>(&mut self, command_buffer: &'a UnsafeCommandBuffer<P>) {
         self.command_buffers.push(command_buffer.internal_object());
+        if command_buffer.is_protected() {
+            self.num_protected_command_buffers += 1;
+        }
+    }
+
+    /// Sets whether this is a protected submission. The default value is false.
+    ///
+    /// A protected submission can only contain protected command buffers, and an unprotected
+    /// submission can only contain unprotected command buffers. This is checked when calling
+    /// `submit`, along with the fact that the queue is protected-capable.
+    #[inline]
+    pub fn set_protected(&mut self, protected: bool) {
+        self.protected = protected;
+    }
+
+    /// Returns true if this is a protected submission.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
     }
 
     /// Returns the number of semaphores to signal.
@@ -193,6 +261,32 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     #[inline]
     pub unsafe fn add_signal_semaphore(&mut self, semaphore: &'a Semaphore) {
         self.signal_semaphores.push(semaphore.internal_object());
+        // The value is ignored for binary semaphores.
+        self.signal_values.push(0);
+    }
+
+    /// Adds a timeline semaphore whose counter is going to be set to `value` at the end of the
+    /// submission.
+    ///
+    /// # Safety
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has finished executing this submission.
+    ///
+    /// - `value` must be greater than the current value of the semaphore and than the value of
+    ///   any pending signal operation on it when the submission is executed.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_signal_timeline_semaphore(
+        &mut self,
+        semaphore: &'a TimelineSemaphore,
+        value: u64,
+    ) {
+        self.signal_semaphores.push(semaphore.internal_object());
+        self.signal_values.push(value);
+        self.has_timeline_semaphores = true;
     }
 
     /// Submits the command buffer to the given queue.
@@ -201,15 +295,51 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     /// > possible together and avoid submitting them one by one.
     ///
     pub fn submit(self, queue: &Queue) -> Result<(), SubmitCommandBufferError> {
+        if self.protected {
+            if !queue.is_protected() {
+                return Err(SubmitCommandBufferError::QueueNotProtected);
+            }
+            if self.num_protected_command_buffers != self.command_buffers.len() {
+                return Err(SubmitCommandBufferError::ProtectedCommandBufferMismatch);
+            }
+        } else if self.num_protected_command_buffers != 0 {
+            return Err(SubmitCommandBufferError::ProtectedCommandBufferMismatch);
+        }
+
         unsafe {
             let vk = queue.device().pointers();
             let queue = queue.internal_object_guard();
 
             debug_assert_eq!(self.wait_semaphores.len(), self.destination_stages.len());
+            debug_assert_eq!(self.wait_semaphores.len(), self.wait_values.len());
+            debug_assert_eq!(self.signal_semaphores.len(), self.signal_values.len());
+
+            let timeline_infos = vk::TimelineSemaphoreSubmitInfo {
+                sType: vk::STRUCTURE_TYPE_TIMELINE_SEMAPHORE_SUBMIT_INFO,
+                pNext: ptr::null(),
+                waitSemaphoreValueCount: self.wait_values.len() as u32,
+                pWaitSemaphoreValues: self.wait_values.as_ptr(),
+                signalSemaphoreValueCount: self.signal_values.len() as u32,
+                pSignalSemaphoreValues: self.signal_values.as_ptr(),
+            };
+
+            let protected_infos = vk::ProtectedSubmitInfo {
+                sType: vk::STRUCTURE_TYPE_PROTECTED_SUBMIT_INFO,
+                pNext: if self.has_timeline_semaphores {
+                    &timeline_infos as *const _ as *const _
+                } else {
+                    ptr::null()
+                },
+                protectedSubmit: vk::TRUE,
+            };
 
             let batch = vk::SubmitInfo {
                 sType: vk::STRUCTURE_TYPE_SUBMIT_INFO,
-                pNext: ptr::null(),
+                pNext: if self.protected {
+                    &protected_infos as *const _ as *const _
+                } else {
+                    protected_infos.pNext
+                },
                 waitSemaphoreCount: self.wait_semaphores.len() as u32,
                 pWaitSemaphores: self.wait_semaphores.as_ptr(),
                 pWaitDstStageMask: self.destination_stages.as_ptr(),
@@ -229,17 +359,26 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     /// # Panic
     ///
     /// Panics if both builders have a fence already set.
+    /// Panics if one builder is a protected submission and the other isn't.
     // TODO: create multiple batches instead
     pub fn merge(mut self, other: Self) -> Self {
         assert!(
             self.fence == 0 || other.fence == 0,
             "Can't merge two queue submits that both have a fence"
         );
+        assert_eq!(
+            self.protected, other.protected,
+            "Can't merge a protected queue submit with an unprotected one"
+        );
 
         self.wait_semaphores.extend(other.wait_semaphores);
+        self.wait_values.extend(other.wait_values);
         self.destination_stages.extend(other.destination_stages); // TODO: meh? will be solved if we submit multiple batches
         self.signal_semaphores.extend(other.signal_semaphores);
+        self.signal_values.extend(other.signal_values);
+        self.has_timeline_semaphores |= other.has_timeline_semaphores;
         self.command_buffers.extend(other.command_buffers);
+        self.num_protected_command_buffers += other.num_protected_command_buffers;
 
         if self.fence == 0 {
             self.fence = other.fence;
@@ -258,6 +397,13 @@ pub enum SubmitCommandBufferError {
 
     /// The connection to the device has been lost.
     DeviceLost,
+
+    /// The submission is protected but the queue isn't protected-capable.
+    QueueNotProtected,
+
+    /// A protected command buffer was added to an unprotected submission, or an unprotected
+    /// command buffer was added to a protected submission.
+    ProtectedCommandBufferMismatch,
 }
 
 impl error::Error for SubmitCommandBufferError {
@@ -280,6 +426,12 @@ impl fmt::Display for SubmitCommandBufferError {
                 SubmitCommandBufferError::OomError(_) => "not enough memory",
                 SubmitCommandBufferError::DeviceLost =>
                     "the connection to the device has been lost",
+                SubmitCommandBufferError::QueueNotProtected => {
+                    "the submission is protected but the queue isn't protected-capable"
+                }
+                SubmitCommandBufferError::ProtectedCommandBufferMismatch => {
+                    "protected and unprotected command buffers can't be mixed in a submission"
+                }
             }
         )
     }
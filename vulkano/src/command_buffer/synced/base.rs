@@ -153,6 +153,23 @@ pub enum SyncCommandBufferBuilderError {
         command2_param: Cow<'static, str>,
         command2_offset: usize,
     },
+
+    /// The number of dynamic offsets doesn't match the number of dynamic descriptors in the
+    /// descriptor sets being bound.
+    DynamicOffsetsCountMismatch {
+        /// Number of dynamic descriptors in the sets.
+        expected: usize,
+        /// Number of dynamic offsets that were provided.
+        obtained: usize,
+    },
+
+    /// A protected resource was used in an unprotected command buffer, or an unprotected
+    /// resource was written by a protected command buffer.
+    ProtectedAccessMismatch {
+        command_name: &'static str,
+        command_param: Cow<'static, str>,
+        command_offset: usize,
+    },
 }
 
 impl error::Error for SyncCommandBufferBuilderError {}
@@ -165,6 +182,14 @@ impl fmt::Display for SyncCommandBufferBuilderError {
             "{}",
             match *self {
                 SyncCommandBufferBuilderError::Conflict { .. } => "unsolvable conflict",
+                SyncCommandBufferBuilderError::DynamicOffsetsCountMismatch { .. } => {
+                    "the number of dynamic offsets doesn't match the number of dynamic \
+                     descriptors in the descriptor sets"
+                }
+                SyncCommandBufferBuilderError::ProtectedAccessMismatch { .. } => {
+                    "a protected resource was used in an unprotected command buffer, or an \
+                     unprotected resource was written in a protected command buffer"
+                }
             }
         )
     }
@@ -494,6 +519,36 @@ impl<P> SyncCommandBufferBuilder<P> {
         let (first_unflushed_cmd_id, latest_command_id) = {
             let commands_lock = self.commands.lock().unwrap();
             debug_assert!(commands_lock.commands.len() >= 1);
+
+            // Protected and unprotected resources can't be mixed. An unprotected command buffer
+            // can't access protected resources at all, and a protected command buffer can only
+            // read from unprotected resources.
+            let latest_command = commands_lock.commands.last().unwrap();
+            let resource_protected = match resource_ty {
+                KeyTy::Buffer => latest_command
+                    .buffer(resource_index)
+                    .inner()
+                    .buffer
+                    .is_protected(),
+                KeyTy::Image => latest_command
+                    .image(resource_index)
+                    .inner()
+                    .image
+                    .is_protected(),
+            };
+            let cb_protected = self.inner.is_protected();
+            if (resource_protected && !cb_protected)
+                || (!resource_protected && cb_protected && exclusive)
+            {
+                return Err(SyncCommandBufferBuilderError::ProtectedAccessMismatch {
+                    command_name: latest_command.name(),
+                    command_param: match resource_ty {
+                        KeyTy::Buffer => latest_command.buffer_name(resource_index),
+                        KeyTy::Image => latest_command.image_name(resource_index),
+                    },
+                    command_offset: commands_lock.commands.len() - 1,
+                });
+            }
             (
                 commands_lock.first_unflushed,
                 commands_lock.commands.len() - 1,
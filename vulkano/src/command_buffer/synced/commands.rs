@@ -27,7 +27,9 @@ use command_buffer::sys::UnsafeCommandBufferBuilderColorImageClear;
 use command_buffer::sys::UnsafeCommandBufferBuilderExecuteCommands;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
+use command_buffer::sys::UnsafeCommandBufferBuilderPipelineBarrier;
 use command_buffer::CommandBuffer;
+use command_buffer::CommandBufferExecError;
 use descriptor::descriptor::DescriptorDescTy;
 use descriptor::descriptor::ShaderStages;
 use descriptor::descriptor_set::DescriptorSet;
@@ -40,10 +42,15 @@ use image::ImageLayout;
 use pipeline::depth_stencil::DynamicStencilValue;
 use pipeline::depth_stencil::StencilFaceFlags;
 use pipeline::input_assembly::IndexType;
+use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::raster::CullMode;
+use pipeline::raster::FrontFace;
 use pipeline::viewport::Scissor;
 use pipeline::viewport::Viewport;
 use pipeline::ComputePipelineAbstract;
 use pipeline::GraphicsPipelineAbstract;
+use query::QueryResultFlags;
+use query::UnsafeQueryPool;
 use sampler::Filter;
 use sync::AccessFlagBits;
 use sync::Event;
@@ -1127,6 +1134,110 @@ impl<P> SyncCommandBufferBuilder<P> {
         Ok(())
     }
 
+    /// Calls `vkCmdCopyQueryPoolResults` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the range of queries is out of the pool.
+    ///
+    #[inline]
+    pub unsafe fn copy_query_pool_results<D>(
+        &mut self,
+        pool: Arc<UnsafeQueryPool>,
+        first_index: u32,
+        count: u32,
+        destination: D,
+        stride: usize,
+        flags: QueryResultFlags,
+    ) where
+        D: BufferAccess + Send + Sync + 'static,
+    {
+        struct Cmd<D> {
+            pool: Arc<UnsafeQueryPool>,
+            first_index: u32,
+            count: u32,
+            destination: D,
+            stride: usize,
+            flags: QueryResultFlags,
+        }
+
+        impl<P, D> Command<P> for Cmd<D>
+        where
+            D: BufferAccess + Send + Sync + 'static,
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdCopyQueryPoolResults"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.copy_query_pool_results(
+                    self.pool
+                        .queries_range(self.first_index, self.count)
+                        .unwrap(),
+                    &self.destination,
+                    self.stride,
+                    self.flags,
+                );
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                struct Fin<D>(Arc<UnsafeQueryPool>, D);
+                impl<D> FinalCommand for Fin<D>
+                where
+                    D: BufferAccess + Send + Sync + 'static,
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdCopyQueryPoolResults"
+                    }
+                    fn buffer(&self, num: usize) -> &dyn BufferAccess {
+                        assert_eq!(num, 0);
+                        &self.1
+                    }
+                    fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                        "destination".into()
+                    }
+                }
+                Box::new(Fin(self.pool, self.destination))
+            }
+
+            fn buffer(&self, num: usize) -> &dyn BufferAccess {
+                assert_eq!(num, 0);
+                &self.destination
+            }
+
+            fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                "destination".into()
+            }
+        }
+
+        assert!(pool.queries_range(first_index, count).is_some());
+
+        self.append_command(Cmd {
+            pool,
+            first_index,
+            count,
+            destination,
+            stride,
+            flags,
+        });
+        self.prev_cmd_resource(
+            KeyTy::Buffer,
+            0,
+            true,
+            PipelineStages {
+                transfer: true,
+                ..PipelineStages::none()
+            },
+            AccessFlagBits {
+                transfer_write: true,
+                ..AccessFlagBits::none()
+            },
+            ImageLayout::Undefined,
+            ImageLayout::Undefined,
+        )
+        .unwrap();
+    }
+
     /// Calls `vkCmdDispatch` on the builder.
     #[inline]
     pub unsafe fn dispatch(&mut self, dimensions: [u32; 3]) {
@@ -1742,6 +1853,30 @@ impl<P> SyncCommandBufferBuilder<P> {
         self.append_command(Cmd { constants });
     }
 
+    /// Calls `vkCmdSetCullModeEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        struct Cmd {
+            cull_mode: CullMode,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetCullModeEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.set_cull_mode(self.cull_mode);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetCullModeEXT")
+            }
+        }
+
+        self.append_command(Cmd { cull_mode });
+    }
+
     /// Calls `vkCmdSetDepthBias` on the builder.
     #[inline]
     pub unsafe fn set_depth_bias(&mut self, constant_factor: f32, clamp: f32, slope_factor: f32) {
@@ -1797,6 +1932,54 @@ impl<P> SyncCommandBufferBuilder<P> {
         self.append_command(Cmd { min, max });
     }
 
+    /// Calls `vkCmdSetDepthTestEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_test_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDepthTestEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.set_depth_test_enable(self.enable);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDepthTestEnableEXT")
+            }
+        }
+
+        self.append_command(Cmd { enable });
+    }
+
+    /// Calls `vkCmdSetDepthWriteEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_write_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDepthWriteEnableEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.set_depth_write_enable(self.enable);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDepthWriteEnableEXT")
+            }
+        }
+
+        self.append_command(Cmd { enable });
+    }
+
     /// Calls `vkCmdSetEvent` on the builder.
     #[inline]
     pub unsafe fn set_event(&mut self, event: Arc<Event>, stages: PipelineStages) {
@@ -1828,6 +2011,30 @@ impl<P> SyncCommandBufferBuilder<P> {
         self.append_command(Cmd { event, stages });
     }
 
+    /// Calls `vkCmdSetFrontFaceEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_front_face(&mut self, front_face: FrontFace) {
+        struct Cmd {
+            front_face: FrontFace,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetFrontFaceEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.set_front_face(self.front_face);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetFrontFaceEXT")
+            }
+        }
+
+        self.append_command(Cmd { front_face });
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
@@ -1852,6 +2059,30 @@ impl<P> SyncCommandBufferBuilder<P> {
         self.append_command(Cmd { line_width });
     }
 
+    /// Calls `vkCmdSetPrimitiveTopologyEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_primitive_topology(&mut self, topology: PrimitiveTopology) {
+        struct Cmd {
+            topology: PrimitiveTopology,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetPrimitiveTopologyEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.set_primitive_topology(self.topology);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetPrimitiveTopologyEXT")
+            }
+        }
+
+        self.append_command(Cmd { topology });
+    }
+
     /// Calls `vkCmdSetStencilCompareMask` on the builder.
     #[inline]
     pub unsafe fn set_stencil_compare_mask(&mut self, compare_mask: DynamicStencilValue) {
@@ -2081,6 +2312,63 @@ impl<P> SyncCommandBufferBuilder<P> {
         )
         .unwrap();
     }
+
+    /// Calls `vkCmdWaitEvents` on the builder, with a global memory barrier that makes the
+    /// `source_access` of `source_stages` available to the `destination_access` of
+    /// `destination_stages`.
+    #[inline]
+    pub unsafe fn wait_events(
+        &mut self,
+        events: SmallVec<[Arc<Event>; 4]>,
+        source_stages: PipelineStages,
+        source_access: AccessFlagBits,
+        destination_stages: PipelineStages,
+        destination_access: AccessFlagBits,
+    ) {
+        struct Cmd {
+            events: SmallVec<[Arc<Event>; 4]>,
+            source_stages: PipelineStages,
+            source_access: AccessFlagBits,
+            destination_stages: PipelineStages,
+            destination_access: AccessFlagBits,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdWaitEvents"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_memory_barrier(
+                    self.source_stages,
+                    self.source_access,
+                    self.destination_stages,
+                    self.destination_access,
+                    false,
+                );
+                out.wait_events(self.events.iter().map(|e| &**e), &barrier);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
+                struct Fin(SmallVec<[Arc<Event>; 4]>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdWaitEvents"
+                    }
+                }
+                Box::new(Fin(self.events))
+            }
+        }
+
+        self.append_command(Cmd {
+            events,
+            source_stages,
+            source_access,
+            destination_stages,
+            destination_access,
+        });
+    }
 }
 
 pub struct SyncCommandBufferBuilderBindDescriptorSets<'b, P: 'b> {
@@ -2098,6 +2386,11 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
         self.inner.push(Box::new(set));
     }
 
+    /// Binds the descriptor sets that were added.
+    ///
+    /// `dynamic_offsets` must contain one offset per element of each dynamic buffer descriptor,
+    /// ordered by set, then by binding number, then by array element. Returns an error if the
+    /// number of offsets doesn't match.
     #[inline]
     pub unsafe fn submit<Pl, I>(
         self,
@@ -2114,6 +2407,19 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
             return Ok(());
         }
 
+        let dynamic_offsets: SmallVec<[u32; 32]> = dynamic_offsets.collect();
+        let num_dynamic_descriptors = self
+            .inner
+            .iter()
+            .map(|set| set.dynamic_descriptor_count() as usize)
+            .sum();
+        if dynamic_offsets.len() != num_dynamic_descriptors {
+            return Err(SyncCommandBufferBuilderError::DynamicOffsetsCountMismatch {
+                expected: num_dynamic_descriptors,
+                obtained: dynamic_offsets.len(),
+            });
+        }
+
         struct Cmd<Pl, I> {
             inner: SmallVec<[Box<dyn DescriptorSet + Send + Sync>; 12]>,
             graphics: bool,
@@ -2295,7 +2601,7 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
             graphics,
             pipeline_layout,
             first_binding,
-            dynamic_offsets: Some(dynamic_offsets),
+            dynamic_offsets: Some(dynamic_offsets.into_iter()),
         });
 
         for (n, (write, stages, access)) in all_buffers.into_iter().enumerate() {
@@ -2351,9 +2657,30 @@ impl<'a, P> SyncCommandBufferBuilderBindVertexBuffer<'a, P> {
 
     #[inline]
     pub unsafe fn submit(self, first_binding: u32) -> Result<(), SyncCommandBufferBuilderError> {
+        self.submit_impl(first_binding, None)
+    }
+
+    /// Same as `submit`, but also sets the stride of each buffer with
+    /// `vkCmdBindVertexBuffers2EXT`. There must be as many strides as buffers.
+    #[inline]
+    pub unsafe fn submit_with_strides(
+        self,
+        first_binding: u32,
+        strides: SmallVec<[u32; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        debug_assert_eq!(strides.len(), self.buffers.len());
+        self.submit_impl(first_binding, Some(strides))
+    }
+
+    unsafe fn submit_impl(
+        self,
+        first_binding: u32,
+        strides: Option<SmallVec<[u32; 4]>>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
             first_binding: u32,
             inner: Option<UnsafeCommandBufferBuilderBindVertexBuffer>,
+            strides: Option<SmallVec<[u32; 4]>>,
             buffers: Vec<Box<dyn BufferAccess + Send + Sync>>,
         }
 
@@ -2363,7 +2690,14 @@ impl<'a, P> SyncCommandBufferBuilderBindVertexBuffer<'a, P> {
             }
 
             unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
-                out.bind_vertex_buffers(self.first_binding, self.inner.take().unwrap());
+                match self.strides {
+                    Some(ref strides) => out.bind_vertex_buffers_with_stride(
+                        self.first_binding,
+                        self.inner.take().unwrap(),
+                        strides,
+                    ),
+                    None => out.bind_vertex_buffers(self.first_binding, self.inner.take().unwrap()),
+                }
             }
 
             fn into_final_command(self: Box<Self>) -> Box<dyn FinalCommand + Send + Sync> {
@@ -2396,6 +2730,7 @@ impl<'a, P> SyncCommandBufferBuilderBindVertexBuffer<'a, P> {
         self.builder.append_command(Cmd {
             first_binding,
             inner: Some(self.inner),
+            strides,
             buffers: self.buffers,
         });
 
@@ -2429,16 +2764,39 @@ pub struct SyncCommandBufferBuilderExecuteCommands<'a, P: 'a> {
     command_buffers: Vec<Box<dyn Any + Send + Sync>>,
 }
 
+// Secondary command buffer that was locked with `lock_record`, and that is unlocked when dropped
+// along with the command that executes it.
+struct RecordedCommandBuffer<C>(C)
+where
+    C: CommandBuffer;
+
+impl<C> Drop for RecordedCommandBuffer<C>
+where
+    C: CommandBuffer,
+{
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            self.0.unlock_record();
+        }
+    }
+}
+
 impl<'a, P> SyncCommandBufferBuilderExecuteCommands<'a, P> {
     /// Adds a command buffer to the list.
+    ///
+    /// The command buffer is locked with `lock_record` until the command buffer being built is
+    /// destroyed.
     #[inline]
-    pub fn add<C>(&mut self, command_buffer: C)
+    pub fn add<C>(&mut self, command_buffer: C) -> Result<(), CommandBufferExecError>
     where
         C: CommandBuffer + Send + Sync + 'static,
     {
+        command_buffer.lock_record()?;
         self.inner.add(&command_buffer);
         self.command_buffers
-            .push(Box::new(command_buffer) as Box<_>);
+            .push(Box::new(RecordedCommandBuffer(command_buffer)) as Box<_>);
+        Ok(())
     }
 
     #[inline]
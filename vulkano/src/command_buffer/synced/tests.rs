@@ -8,6 +8,7 @@
 // according to those terms.
 
 use std::iter;
+use std::sync::Arc;
 
 use buffer::BufferUsage;
 use buffer::CpuAccessibleBuffer;
@@ -15,6 +16,10 @@ use command_buffer::synced::base::SyncCommandBufferBuilder;
 use command_buffer::synced::base::SyncCommandBufferBuilderError;
 use command_buffer::sys::Flags;
 use command_buffer::sys::Kind;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::AutoCommandBufferBuilderContextError;
+use command_buffer::CommandBufferExecError;
+use command_buffer::ExecuteCommandsError;
 use device::Device;
 
 #[test]
@@ -42,3 +47,56 @@ fn basic_conflict() {
         };
     }
 }
+
+#[test]
+fn secondary_record_lock() {
+    unsafe {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let exclusive = Arc::new(
+            AutoCommandBufferBuilder::secondary_compute(device.clone(), queue.family())
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+        let simultaneous = Arc::new(
+            AutoCommandBufferBuilder::secondary_compute_simultaneous_use(
+                device.clone(),
+                queue.family(),
+            )
+            .unwrap()
+            .build()
+            .unwrap(),
+        );
+
+        // A secondary command buffer without the simultaneous use flag can only be recorded in
+        // one primary command buffer at a time.
+        let mut primary1 = AutoCommandBufferBuilder::new(device.clone(), queue.family()).unwrap();
+        primary1.execute_commands(exclusive.clone()).unwrap();
+        primary1.execute_commands(simultaneous.clone()).unwrap();
+
+        let mut primary2 = AutoCommandBufferBuilder::new(device.clone(), queue.family()).unwrap();
+        match primary2.execute_commands(exclusive.clone()) {
+            Err(ExecuteCommandsError::CommandBufferExecError(
+                CommandBufferExecError::ExclusiveAlreadyInUse,
+            )) => (),
+            _ => panic!(),
+        };
+        primary2.execute_commands(simultaneous.clone()).unwrap();
+
+        drop(primary1);
+        primary2.execute_commands(exclusive.clone()).unwrap();
+
+        // It can't be executed by a primary command buffer with the simultaneous use flag.
+        let mut primary3 =
+            AutoCommandBufferBuilder::primary_simultaneous_use(device.clone(), queue.family())
+                .unwrap();
+        match primary3.execute_commands(exclusive.clone()) {
+            Err(ExecuteCommandsError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::SecondaryNotSimultaneousUse,
+            )) => (),
+            _ => panic!(),
+        };
+        primary3.execute_commands(simultaneous).unwrap();
+    }
+}
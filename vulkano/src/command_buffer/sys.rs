@@ -36,20 +36,28 @@ use framebuffer::RenderPass;
 use framebuffer::RenderPassAbstract;
 use framebuffer::Subpass;
 use framebuffer::SubpassContents;
+use image::resolve_remaining;
 use image::ImageAccess;
+use image::ImageAspects;
 use image::ImageLayout;
 use instance::QueueFamily;
+use instance::Version;
 use pipeline::depth_stencil::StencilFaceFlags;
 use pipeline::input_assembly::IndexType;
+use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::raster::CullMode;
+use pipeline::raster::FrontFace;
 use pipeline::viewport::Scissor;
 use pipeline::viewport::Viewport;
 use pipeline::ComputePipelineAbstract;
 use pipeline::GraphicsPipelineAbstract;
 use query::QueryPipelineStatisticFlags;
+use query::QueryResultFlags;
 use query::UnsafeQueriesRange;
 use query::UnsafeQuery;
 use sampler::Filter;
 use std::ffi::CStr;
+use sync::access2_to_access1;
 use sync::AccessFlagBits;
 use sync::Event;
 use sync::PipelineStages;
@@ -184,6 +192,9 @@ pub struct UnsafeCommandBufferBuilder<P> {
     // Device that owns the command buffer.
     // TODO: necessary?
     device: Arc<Device>,
+
+    // True if the command buffer was allocated from a protected pool.
+    protected: bool,
 }
 
 impl<P> fmt::Debug for UnsafeCommandBufferBuilder<P> {
@@ -252,6 +263,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         let device = alloc.device().clone();
         let vk = device.pointers();
         let cmd = alloc.inner().internal_object();
+        let protected = alloc.inner().is_protected();
 
         let vk_flags = {
             let a = match flags {
@@ -345,9 +357,16 @@ impl<P> UnsafeCommandBufferBuilder<P> {
             cmd: Some(alloc),
             cmd_raw: cmd,
             device: device.clone(),
+            protected,
         })
     }
 
+    /// Returns true if the command buffer was allocated from a protected pool.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
     /// Returns the queue family of the builder.
     #[inline]
     pub fn queue_family(&self) -> QueueFamily
@@ -373,6 +392,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                 cmd: cmd.into_alloc(),
                 cmd_raw: cmd_raw,
                 device: self.device.clone(),
+                protected: self.protected,
             })
         }
     }
@@ -475,6 +495,9 @@ impl<P> UnsafeCommandBufferBuilder<P> {
     ///
     /// Does nothing if the list of descriptor sets is empty, as it would be a no-op and isn't a
     /// valid usage of the command anyway.
+    ///
+    /// `dynamic_offsets` must contain one offset per element of each dynamic buffer descriptor,
+    /// ordered by set, then by binding number, then by array element.
     #[inline]
     pub unsafe fn bind_descriptor_sets<'s, Pl, S, I>(
         &mut self,
@@ -605,6 +628,54 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         );
     }
 
+    /// Calls `vkCmdBindVertexBuffers2EXT` on the builder, with one stride per buffer.
+    ///
+    /// Does nothing if the list of buffers is empty, as it would be a no-op and isn't a valid
+    /// usage of the command anyway.
+    #[inline]
+    pub unsafe fn bind_vertex_buffers_with_stride(
+        &mut self,
+        first_binding: u32,
+        params: UnsafeCommandBufferBuilderBindVertexBuffer,
+        strides: &[u32],
+    ) {
+        debug_assert_eq!(params.raw_buffers.len(), params.offsets.len());
+        debug_assert_eq!(params.raw_buffers.len(), strides.len());
+        debug_assert!(self.device().enabled_features().extended_dynamic_state);
+
+        if params.raw_buffers.is_empty() {
+            return;
+        }
+
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        let num_bindings = params.raw_buffers.len() as u32;
+        let strides = strides
+            .iter()
+            .map(|&stride| stride as vk::DeviceSize)
+            .collect::<SmallVec<[_; 4]>>();
+
+        debug_assert!({
+            let max_bindings = self
+                .device()
+                .physical_device()
+                .limits()
+                .max_vertex_input_bindings();
+            first_binding + num_bindings <= max_bindings
+        });
+
+        vk.CmdBindVertexBuffers2EXT(
+            cmd,
+            first_binding,
+            num_bindings,
+            params.raw_buffers.as_ptr(),
+            params.offsets.as_ptr(),
+            params.sizes.as_ptr(),
+            strides.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdCopyImage` on the builder.
     ///
     /// Does nothing if the list of regions is empty, as it would be a no-op and isn't a valid
@@ -637,6 +708,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         );
 
         debug_assert_eq!(source.samples(), destination.samples());
+        let source_format = source.format();
         let source = source.inner();
         debug_assert!(source.image.usage_transfer_source());
         debug_assert!(
@@ -644,6 +716,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                 || source_layout == ImageLayout::TransferSrcOptimal
         );
 
+        let destination_format = destination.format();
         let destination = destination.inner();
         debug_assert!(destination.image.usage_transfer_destination());
         debug_assert!(
@@ -654,6 +727,8 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         let regions: SmallVec<[_; 8]> = regions
             .filter_map(|copy| {
                 // TODO: not everything is checked here
+                debug_assert!(copy.aspect.is_valid_for_format(source_format, false));
+                debug_assert!(copy.aspect.is_valid_for_format(destination_format, false));
                 debug_assert!(
                     copy.source_base_array_layer + copy.layer_count <= source.num_layers as u32
                 );
@@ -670,7 +745,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
 
                 Some(vk::ImageCopy {
                     srcSubresource: vk::ImageSubresourceLayers {
-                        aspectMask: copy.aspect.to_vk_bits(),
+                        aspectMask: copy.aspect.into_vulkan_bits(),
                         mipLevel: copy.source_mip_level,
                         baseArrayLayer: copy.source_base_array_layer + source.first_layer as u32,
                         layerCount: copy.layer_count,
@@ -681,7 +756,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                         z: copy.source_offset[2],
                     },
                     dstSubresource: vk::ImageSubresourceLayers {
-                        aspectMask: copy.aspect.to_vk_bits(),
+                        aspectMask: copy.aspect.into_vulkan_bits(),
                         mipLevel: copy.destination_mip_level,
                         baseArrayLayer: copy.destination_base_array_layer
                             + destination.first_layer as u32,
@@ -751,6 +826,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         );
 
         debug_assert_eq!(source.samples(), 1);
+        let source_format = source.format();
         let source = source.inner();
         debug_assert!(source.image.supports_blit_source());
         debug_assert!(source.image.usage_transfer_source());
@@ -760,6 +836,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         );
 
         debug_assert_eq!(destination.samples(), 1);
+        let destination_format = destination.format();
         let destination = destination.inner();
         debug_assert!(destination.image.supports_blit_destination());
         debug_assert!(destination.image.usage_transfer_destination());
@@ -771,6 +848,8 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         let regions: SmallVec<[_; 8]> = regions
             .filter_map(|blit| {
                 // TODO: not everything is checked here
+                debug_assert!(blit.aspect.is_valid_for_format(source_format, false));
+                debug_assert!(blit.aspect.is_valid_for_format(destination_format, false));
                 debug_assert!(
                     blit.source_base_array_layer + blit.layer_count <= source.num_layers as u32
                 );
@@ -787,7 +866,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
 
                 Some(vk::ImageBlit {
                     srcSubresource: vk::ImageSubresourceLayers {
-                        aspectMask: blit.aspect.to_vk_bits(),
+                        aspectMask: blit.aspect.into_vulkan_bits(),
                         mipLevel: blit.source_mip_level,
                         baseArrayLayer: blit.source_base_array_layer + source.first_layer as u32,
                         layerCount: blit.layer_count,
@@ -805,7 +884,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                         },
                     ],
                     dstSubresource: vk::ImageSubresourceLayers {
-                        aspectMask: blit.aspect.to_vk_bits(),
+                        aspectMask: blit.aspect.into_vulkan_bits(),
                         mipLevel: blit.destination_mip_level,
                         baseArrayLayer: blit.destination_base_array_layer
                             + destination.first_layer as u32,
@@ -1004,6 +1083,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         debug_assert!(source.buffer.usage_transfer_source());
 
         debug_assert_eq!(destination.samples(), 1);
+        let destination_format = destination.format();
         let destination = destination.inner();
         debug_assert!(destination.image.usage_transfer_destination());
         debug_assert!(
@@ -1013,6 +1093,9 @@ impl<P> UnsafeCommandBufferBuilder<P> {
 
         let regions: SmallVec<[_; 8]> = regions
             .map(|copy| {
+                debug_assert!(copy
+                    .image_aspect
+                    .is_valid_for_format(destination_format, true));
                 debug_assert!(copy.image_layer_count <= destination.num_layers as u32);
                 debug_assert!(copy.image_mip_level < destination.num_mipmap_levels as u32);
 
@@ -1021,7 +1104,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                     bufferRowLength: copy.buffer_row_length,
                     bufferImageHeight: copy.buffer_image_height,
                     imageSubresource: vk::ImageSubresourceLayers {
-                        aspectMask: copy.image_aspect.to_vk_bits(),
+                        aspectMask: copy.image_aspect.into_vulkan_bits(),
                         mipLevel: copy.image_mip_level + destination.first_mipmap_level as u32,
                         baseArrayLayer: copy.image_base_array_layer
                             + destination.first_layer as u32,
@@ -1074,6 +1157,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         R: Iterator<Item = UnsafeCommandBufferBuilderBufferImageCopy>,
     {
         debug_assert_eq!(source.samples(), 1);
+        let source_format = source.format();
         let source = source.inner();
         debug_assert!(source.image.usage_transfer_source());
         debug_assert!(
@@ -1087,6 +1171,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
 
         let regions: SmallVec<[_; 8]> = regions
             .map(|copy| {
+                debug_assert!(copy.image_aspect.is_valid_for_format(source_format, true));
                 debug_assert!(copy.image_layer_count <= source.num_layers as u32);
                 debug_assert!(copy.image_mip_level < source.num_mipmap_levels as u32);
 
@@ -1095,7 +1180,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                     bufferRowLength: copy.buffer_row_length,
                     bufferImageHeight: copy.buffer_image_height,
                     imageSubresource: vk::ImageSubresourceLayers {
-                        aspectMask: copy.image_aspect.to_vk_bits(),
+                        aspectMask: copy.image_aspect.into_vulkan_bits(),
                         mipLevel: copy.image_mip_level + source.first_mipmap_level as u32,
                         baseArrayLayer: copy.image_base_array_layer + source.first_layer as u32,
                         layerCount: copy.image_layer_count,
@@ -1131,18 +1216,23 @@ impl<P> UnsafeCommandBufferBuilder<P> {
     }
 
     /// Calls `vkCmdCopyQueryPoolResults` on the builder.
+    ///
+    /// The results are written as 64-bit values.
     #[inline]
     pub unsafe fn copy_query_pool_results(
         &mut self,
         queries: UnsafeQueriesRange,
         destination: &dyn BufferAccess,
         stride: usize,
+        flags: QueryResultFlags,
     ) {
         let destination = destination.inner();
         debug_assert!(destination.offset < destination.buffer.size());
         debug_assert!(destination.buffer.usage_transfer_destination());
+        debug_assert!(destination.offset % 8 == 0);
+        debug_assert!(stride % 8 == 0);
 
-        let flags = 0; // FIXME:
+        let flags = flags.into_vulkan_bits();
 
         let vk = self.device().pointers();
         let cmd = self.internal_object();
@@ -1177,6 +1267,38 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdDispatch(cmd, dimensions[0], dimensions[1], dimensions[2]);
     }
 
+    /// Calls `vkCmdDispatchBaseKHR` on the builder.
+    ///
+    /// Same as `dispatch`, except that the workgroup IDs start at `base_group` instead of 0.
+    ///
+    /// The `khr_device_group` extension must be enabled on the device.
+    #[inline]
+    pub unsafe fn dispatch_base(&mut self, base_group: [u32; 3], group_counts: [u32; 3]) {
+        debug_assert!(self.device().loaded_extensions().khr_device_group);
+        debug_assert!({
+            let max_dims = self
+                .device()
+                .physical_device()
+                .limits()
+                .max_compute_work_group_count();
+            group_counts[0] <= max_dims[0]
+                && group_counts[1] <= max_dims[1]
+                && group_counts[2] <= max_dims[2]
+        });
+
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdDispatchBaseKHR(
+            cmd,
+            base_group[0],
+            base_group[1],
+            base_group[2],
+            group_counts[0],
+            group_counts[1],
+            group_counts[2],
+        );
+    }
+
     /// Calls `vkCmdDispatchIndirect` on the builder.
     #[inline]
     pub unsafe fn dispatch_indirect<B>(&mut self, buffer: &B)
@@ -1330,25 +1452,19 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         let vk = self.device().pointers();
         let cmd = self.internal_object();
 
-        let size = buffer.size();
-
-        let (buffer_handle, offset) = {
+        let (buffer_handle, offset, size) = {
             let BufferInner {
                 buffer: buffer_inner,
                 offset,
             } = buffer.inner();
             debug_assert!(buffer_inner.usage_transfer_destination());
             debug_assert_eq!(offset % 4, 0);
-            (buffer_inner.internal_object(), offset)
+
+            let size = fill_buffer_size(offset, buffer.size(), buffer_inner.size());
+            (buffer_inner.internal_object(), offset, size)
         };
 
-        vk.CmdFillBuffer(
-            cmd,
-            buffer_handle,
-            offset as vk::DeviceSize,
-            size as vk::DeviceSize,
-            data,
-        );
+        vk.CmdFillBuffer(cmd, buffer_handle, offset as vk::DeviceSize, size, data);
     }
 
     /// Calls `vkCmdNextSubpass` on the builder.
@@ -1359,6 +1475,13 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdNextSubpass(cmd, subpass_contents as u32);
     }
 
+    /// Records a pipeline barrier.
+    ///
+    /// If the `synchronization2` feature is enabled on the device, and either the
+    /// `khr_synchronization2` extension is loaded or the device supports Vulkan 1.3, this calls
+    /// `pipeline_barrier2` and each barrier keeps its own source and destination stages.
+    /// Otherwise this calls `vkCmdPipelineBarrier`, whose stages are the union of the stages of
+    /// all the barriers.
     #[inline]
     pub unsafe fn pipeline_barrier(&mut self, command: &UnsafeCommandBufferBuilderPipelineBarrier) {
         // If barrier is empty, don't do anything.
@@ -1370,26 +1493,92 @@ impl<P> UnsafeCommandBufferBuilder<P> {
             return;
         }
 
+        if self.synchronization2_supported() {
+            self.pipeline_barrier2(command);
+            return;
+        }
+
         let vk = self.device().pointers();
         let cmd = self.internal_object();
 
         debug_assert_ne!(command.src_stage_mask, 0);
         debug_assert_ne!(command.dst_stage_mask, 0);
 
+        let (memory_barriers, buffer_barriers, image_barriers) = command.to_sync1();
+
         vk.CmdPipelineBarrier(
             cmd,
             command.src_stage_mask,
             command.dst_stage_mask,
             command.dependency_flags,
-            command.memory_barriers.len() as u32,
-            command.memory_barriers.as_ptr(),
-            command.buffer_barriers.len() as u32,
-            command.buffer_barriers.as_ptr(),
-            command.image_barriers.len() as u32,
-            command.image_barriers.as_ptr(),
+            memory_barriers.len() as u32,
+            memory_barriers.as_ptr(),
+            buffer_barriers.len() as u32,
+            buffer_barriers.as_ptr(),
+            image_barriers.len() as u32,
+            image_barriers.as_ptr(),
         );
     }
 
+    /// Calls `vkCmdPipelineBarrier2` on the builder, or `vkCmdPipelineBarrier2KHR` if the
+    /// `khr_synchronization2` extension is loaded.
+    ///
+    /// Contrary to `pipeline_barrier`, each memory, buffer and image barrier carries its own
+    /// source and destination stages.
+    ///
+    /// # Safety
+    ///
+    /// - The `synchronization2` feature must be enabled on the device.
+    /// - The `khr_synchronization2` extension must be loaded, or the device must support
+    ///   Vulkan 1.3.
+    ///
+    #[inline]
+    pub unsafe fn pipeline_barrier2(
+        &mut self,
+        command: &UnsafeCommandBufferBuilderPipelineBarrier,
+    ) {
+        if command.is_empty() {
+            return;
+        }
+
+        debug_assert!(self.synchronization2_supported());
+
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        let infos = vk::DependencyInfo {
+            sType: vk::STRUCTURE_TYPE_DEPENDENCY_INFO,
+            pNext: ptr::null(),
+            dependencyFlags: command.dependency_flags,
+            memoryBarrierCount: command.memory_barriers.len() as u32,
+            pMemoryBarriers: command.memory_barriers.as_ptr(),
+            bufferMemoryBarrierCount: command.buffer_barriers.len() as u32,
+            pBufferMemoryBarriers: command.buffer_barriers.as_ptr(),
+            imageMemoryBarrierCount: command.image_barriers.len() as u32,
+            pImageMemoryBarriers: command.image_barriers.as_ptr(),
+        };
+
+        if self.device().loaded_extensions().khr_synchronization2 {
+            vk.CmdPipelineBarrier2KHR(cmd, &infos);
+        } else {
+            vk.CmdPipelineBarrier2(cmd, &infos);
+        }
+    }
+
+    // Returns true if `vkCmdPipelineBarrier2` or `vkCmdPipelineBarrier2KHR` can be called.
+    #[inline]
+    fn synchronization2_supported(&self) -> bool {
+        let version_1_3 = Version {
+            major: 1,
+            minor: 3,
+            patch: 0,
+        };
+        let device = self.device();
+        device.enabled_features().synchronization2
+            && (device.loaded_extensions().khr_synchronization2
+                || device.api_version() >= version_1_3)
+    }
+
     /// Calls `vkCmdPushConstants` on the builder.
     #[inline]
     pub unsafe fn push_constants<Pl, D>(
@@ -1455,6 +1644,15 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetBlendConstants(cmd, constants); // TODO: correct to pass array?
     }
 
+    /// Calls `vkCmdSetCullModeEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(self.device().enabled_features().extended_dynamic_state);
+        vk.CmdSetCullModeEXT(cmd, cull_mode as u32);
+    }
+
     /// Calls `vkCmdSetDepthBias` on the builder.
     #[inline]
     pub unsafe fn set_depth_bias(&mut self, constant_factor: f32, clamp: f32, slope_factor: f32) {
@@ -1474,6 +1672,41 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetDepthBounds(cmd, min, max);
     }
 
+    /// Calls `vkCmdSetDepthTestEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_test_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(self.device().enabled_features().extended_dynamic_state);
+        vk.CmdSetDepthTestEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
+    /// Calls `vkCmdSetDepthWriteEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_write_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(self.device().enabled_features().extended_dynamic_state);
+        vk.CmdSetDepthWriteEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
+    /// Calls `vkCmdSetDeviceMaskKHR` on the builder.
+    ///
+    /// The following commands are only executed by the physical devices whose bit is set in
+    /// `device_mask`. The `khr_device_group` extension must be enabled on the device.
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, device_mask: u32) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(self.device().loaded_extensions().khr_device_group);
+        debug_assert_ne!(device_mask, 0);
+        debug_assert_eq!(
+            (device_mask as u64) >> self.device().physical_device_count(),
+            0
+        );
+        vk.CmdSetDeviceMaskKHR(cmd, device_mask);
+    }
+
     /// Calls `vkCmdSetEvent` on the builder.
     #[inline]
     pub unsafe fn set_event(&mut self, event: &Event, stages: PipelineStages) {
@@ -1486,6 +1719,15 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetEvent(cmd, event.internal_object(), stages.into_vulkan_bits());
     }
 
+    /// Calls `vkCmdSetFrontFaceEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_front_face(&mut self, front_face: FrontFace) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(self.device().enabled_features().extended_dynamic_state);
+        vk.CmdSetFrontFaceEXT(cmd, front_face as u32);
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
@@ -1495,6 +1737,15 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetLineWidth(cmd, line_width);
     }
 
+    /// Calls `vkCmdSetPrimitiveTopologyEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_primitive_topology(&mut self, topology: PrimitiveTopology) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(self.device().enabled_features().extended_dynamic_state);
+        vk.CmdSetPrimitiveTopologyEXT(cmd, topology.into());
+    }
+
     /// Calls `vkCmdSetStencilCompareMask` on the builder.
     #[inline]
     pub unsafe fn set_stencil_compare_mask(
@@ -1627,6 +1878,44 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         );
     }
 
+    /// Calls `vkCmdWaitEvents` on the builder.
+    ///
+    /// The source and destination stages and the memory barriers of `command` are used as the
+    /// dependency of the wait. Its dependency flags are ignored.
+    #[inline]
+    pub unsafe fn wait_events<'a, I>(
+        &mut self,
+        events: I,
+        command: &UnsafeCommandBufferBuilderPipelineBarrier,
+    ) where
+        I: IntoIterator<Item = &'a Event>,
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        let events: SmallVec<[_; 8]> = events.into_iter().map(|e| e.internal_object()).collect();
+
+        debug_assert!(!events.is_empty());
+        debug_assert_ne!(command.src_stage_mask, 0);
+        debug_assert_ne!(command.dst_stage_mask, 0);
+
+        let (memory_barriers, buffer_barriers, image_barriers) = command.to_sync1();
+
+        vk.CmdWaitEvents(
+            cmd,
+            events.len() as u32,
+            events.as_ptr(),
+            command.src_stage_mask,
+            command.dst_stage_mask,
+            memory_barriers.len() as u32,
+            memory_barriers.as_ptr(),
+            buffer_barriers.len() as u32,
+            buffer_barriers.as_ptr(),
+            image_barriers.len() as u32,
+            image_barriers.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdWriteTimestamp` on the builder.
     #[inline]
     pub unsafe fn write_timestamp(&mut self, query: UnsafeQuery, stages: PipelineStages) {
@@ -1714,6 +2003,8 @@ pub struct UnsafeCommandBufferBuilderBindVertexBuffer {
     raw_buffers: SmallVec<[vk::Buffer; 4]>,
     // Raw offsets of the buffers to bind.
     offsets: SmallVec<[vk::DeviceSize; 4]>,
+    // Sizes of the buffers to bind. Only used by `bind_vertex_buffers_with_stride`.
+    sizes: SmallVec<[vk::DeviceSize; 4]>,
 }
 
 impl UnsafeCommandBufferBuilderBindVertexBuffer {
@@ -1723,6 +2014,7 @@ impl UnsafeCommandBufferBuilderBindVertexBuffer {
         UnsafeCommandBufferBuilderBindVertexBuffer {
             raw_buffers: SmallVec::new(),
             offsets: SmallVec::new(),
+            sizes: SmallVec::new(),
         }
     }
 
@@ -1736,6 +2028,7 @@ impl UnsafeCommandBufferBuilderBindVertexBuffer {
         debug_assert!(inner.buffer.usage_vertex_buffer());
         self.raw_buffers.push(inner.buffer.internal_object());
         self.offsets.push(inner.offset as vk::DeviceSize);
+        self.sizes.push(buffer.size() as vk::DeviceSize);
     }
 }
 
@@ -1771,29 +2064,10 @@ impl UnsafeCommandBufferBuilderExecuteCommands {
     }
 }
 
-// TODO: move somewhere else?
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct UnsafeCommandBufferBuilderImageAspect {
-    pub color: bool,
-    pub depth: bool,
-    pub stencil: bool,
-}
-
-impl UnsafeCommandBufferBuilderImageAspect {
-    pub(crate) fn to_vk_bits(&self) -> vk::ImageAspectFlagBits {
-        let mut out = 0;
-        if self.color {
-            out |= vk::IMAGE_ASPECT_COLOR_BIT
-        };
-        if self.depth {
-            out |= vk::IMAGE_ASPECT_DEPTH_BIT
-        };
-        if self.stencil {
-            out |= vk::IMAGE_ASPECT_STENCIL_BIT
-        };
-        out
-    }
-}
+/// Aspects of an image accessed by a copy, blit or clear command.
+///
+/// This is the same type as `ImageAspects`, which is used everywhere else in the library.
+pub type UnsafeCommandBufferBuilderImageAspect = ImageAspects;
 
 // TODO: move somewhere else?
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -1857,16 +2131,22 @@ pub struct UnsafeCommandBufferBuilderImageBlit {
 /// Please take a look at the Vulkan specifications for more information. Pipeline barriers are a
 /// complex topic and explaining them in this documentation would be redundant.
 ///
+/// Each barrier remembers its own source and destination stages. They are passed as they are to
+/// the implementation if the `synchronization2` feature is enabled, and merged into a single
+/// execution dependency otherwise.
+///
 /// > **Note**: We use a builder-like API here so that users can pass multiple buffers or images of
 /// > multiple different types. Doing so with a single function would be very tedious in terms of
 /// > API.
 pub struct UnsafeCommandBufferBuilderPipelineBarrier {
+    // Union of the stages of all the barriers, used with `vkCmdPipelineBarrier`.
     src_stage_mask: vk::PipelineStageFlags,
     dst_stage_mask: vk::PipelineStageFlags,
     dependency_flags: vk::DependencyFlags,
-    memory_barriers: SmallVec<[vk::MemoryBarrier; 2]>,
-    buffer_barriers: SmallVec<[vk::BufferMemoryBarrier; 8]>,
-    image_barriers: SmallVec<[vk::ImageMemoryBarrier; 8]>,
+    // Execution dependencies are stored as memory barriers without any access.
+    memory_barriers: SmallVec<[vk::MemoryBarrier2; 2]>,
+    buffer_barriers: SmallVec<[vk::BufferMemoryBarrier2; 8]>,
+    image_barriers: SmallVec<[vk::ImageMemoryBarrier2; 8]>,
 }
 
 impl UnsafeCommandBufferBuilderPipelineBarrier {
@@ -1918,6 +2198,25 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         source: PipelineStages,
         destination: PipelineStages,
         by_region: bool,
+    ) {
+        self.add_stages(source, destination, by_region);
+
+        self.memory_barriers.push(vk::MemoryBarrier2 {
+            sType: vk::STRUCTURE_TYPE_MEMORY_BARRIER_2,
+            pNext: ptr::null(),
+            srcStageMask: source.into_vulkan_bits() as vk::PipelineStageFlags2,
+            srcAccessMask: vk::ACCESS_2_NONE,
+            dstStageMask: destination.into_vulkan_bits() as vk::PipelineStageFlags2,
+            dstAccessMask: vk::ACCESS_2_NONE,
+        });
+    }
+
+    // Adds the stages of a barrier to the execution dependency used with `vkCmdPipelineBarrier`.
+    unsafe fn add_stages(
+        &mut self,
+        source: PipelineStages,
+        destination: PipelineStages,
+        by_region: bool,
     ) {
         if !by_region {
             self.dependency_flags = 0;
@@ -1951,13 +2250,15 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         debug_assert!(source_access.is_compatible_with(&source_stage));
         debug_assert!(destination_access.is_compatible_with(&destination_stage));
 
-        self.add_execution_dependency(source_stage, destination_stage, by_region);
+        self.add_stages(source_stage, destination_stage, by_region);
 
-        self.memory_barriers.push(vk::MemoryBarrier {
-            sType: vk::STRUCTURE_TYPE_MEMORY_BARRIER,
+        self.memory_barriers.push(vk::MemoryBarrier2 {
+            sType: vk::STRUCTURE_TYPE_MEMORY_BARRIER_2,
             pNext: ptr::null(),
-            srcAccessMask: source_access.into_vulkan_bits(),
-            dstAccessMask: destination_access.into_vulkan_bits(),
+            srcStageMask: source_stage.into_vulkan_bits() as vk::PipelineStageFlags2,
+            srcAccessMask: source_access.into_vulkan_bits2(),
+            dstStageMask: destination_stage.into_vulkan_bits() as vk::PipelineStageFlags2,
+            dstAccessMask: destination_access.into_vulkan_bits2(),
         });
     }
 
@@ -1993,7 +2294,7 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         debug_assert!(source_access.is_compatible_with(&source_stage));
         debug_assert!(destination_access.is_compatible_with(&destination_stage));
 
-        self.add_execution_dependency(source_stage, destination_stage, by_region);
+        self.add_stages(source_stage, destination_stage, by_region);
 
         debug_assert!(size <= buffer.size());
         let BufferInner {
@@ -2008,11 +2309,13 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
             (vk::QUEUE_FAMILY_IGNORED, vk::QUEUE_FAMILY_IGNORED)
         };
 
-        self.buffer_barriers.push(vk::BufferMemoryBarrier {
-            sType: vk::STRUCTURE_TYPE_BUFFER_MEMORY_BARRIER,
+        self.buffer_barriers.push(vk::BufferMemoryBarrier2 {
+            sType: vk::STRUCTURE_TYPE_BUFFER_MEMORY_BARRIER_2,
             pNext: ptr::null(),
-            srcAccessMask: source_access.into_vulkan_bits(),
-            dstAccessMask: destination_access.into_vulkan_bits(),
+            srcStageMask: source_stage.into_vulkan_bits() as vk::PipelineStageFlags2,
+            srcAccessMask: source_access.into_vulkan_bits2(),
+            dstStageMask: destination_stage.into_vulkan_bits() as vk::PipelineStageFlags2,
+            dstAccessMask: destination_access.into_vulkan_bits2(),
             srcQueueFamilyIndex: src_queue,
             dstQueueFamilyIndex: dest_queue,
             buffer: buffer.internal_object(),
@@ -2038,6 +2341,10 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
     /// - Image layouts transfers must be correct.
     /// - Access flags must be compatible with the image usage flags passed at image creation.
     ///
+    /// The end of `mipmaps` and `layers` can be `REMAINING_MIP_LEVELS` and
+    /// `REMAINING_ARRAY_LAYERS` respectively, in which case the barrier applies to all the
+    /// mipmap levels or array layers of the image starting from the start of the range.
+    ///
     pub unsafe fn add_image_memory_barrier<I>(
         &mut self,
         image: &I,
@@ -2057,48 +2364,156 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
         debug_assert!(source_access.is_compatible_with(&source_stage));
         debug_assert!(destination_access.is_compatible_with(&destination_stage));
 
-        self.add_execution_dependency(source_stage, destination_stage, by_region);
+        self.add_stages(source_stage, destination_stage, by_region);
 
         debug_assert_ne!(new_layout, ImageLayout::Undefined);
         debug_assert_ne!(new_layout, ImageLayout::Preinitialized);
 
+        let mipmaps = resolve_remaining(mipmaps, image.mipmap_levels());
+        let layers = resolve_remaining(layers, image.dimensions().array_layers());
+
         debug_assert!(mipmaps.start < mipmaps.end);
         debug_assert!(mipmaps.end <= image.mipmap_levels());
         debug_assert!(layers.start < layers.end);
         debug_assert!(layers.end <= image.dimensions().array_layers());
 
-        let (src_queue, dest_queue) = if let Some((src_queue, dest_queue)) = queue_transfer {
-            (src_queue, dest_queue)
-        } else {
-            (vk::QUEUE_FAMILY_IGNORED, vk::QUEUE_FAMILY_IGNORED)
-        };
-
-        let aspect_mask = if image.has_color() {
-            vk::IMAGE_ASPECT_COLOR_BIT
+        let aspects = if image.has_color() {
+            ImageAspects {
+                color: true,
+                ..ImageAspects::none()
+            }
         } else if image.has_depth() && image.has_stencil() {
-            vk::IMAGE_ASPECT_DEPTH_BIT | vk::IMAGE_ASPECT_STENCIL_BIT
+            ImageAspects {
+                depth: true,
+                stencil: true,
+                ..ImageAspects::none()
+            }
         } else if image.has_depth() {
-            vk::IMAGE_ASPECT_DEPTH_BIT
+            ImageAspects::depth()
         } else if image.has_stencil() {
-            vk::IMAGE_ASPECT_STENCIL_BIT
+            ImageAspects::stencil()
         } else {
             unreachable!()
         };
 
+        self.add_image_memory_barrier_aspects(
+            image,
+            aspects,
+            mipmaps,
+            layers,
+            source_stage,
+            source_access,
+            destination_stage,
+            destination_access,
+            queue_transfer,
+            current_layout,
+            new_layout,
+        );
+    }
+
+    /// Same as `add_image_memory_barrier`, except that the barrier only applies to the given
+    /// aspects of the image.
+    ///
+    /// With the `separate_depth_stencil_layouts` feature, this can be used to transition the
+    /// depth and the stencil aspects of a depth-stencil image to different layouts, for example
+    /// `DepthReadOnlyOptimal` and `StencilAttachmentOptimal`.
+    ///
+    /// # Safety
+    ///
+    /// - Same as `add_image_memory_barrier`.
+    /// - `aspects` must only contain aspects of the format of the image.
+    /// - If the depth and stencil aspects of the image are in different layouts, the
+    ///   `separate_depth_stencil_layouts` feature must be enabled.
+    ///
+    pub unsafe fn add_image_memory_barrier_with_aspects<I>(
+        &mut self,
+        image: &I,
+        aspects: ImageAspects,
+        mipmaps: Range<u32>,
+        layers: Range<u32>,
+        source_stage: PipelineStages,
+        source_access: AccessFlagBits,
+        destination_stage: PipelineStages,
+        destination_access: AccessFlagBits,
+        by_region: bool,
+        queue_transfer: Option<(u32, u32)>,
+        current_layout: ImageLayout,
+        new_layout: ImageLayout,
+    ) where
+        I: ?Sized + ImageAccess,
+    {
+        debug_assert!(source_access.is_compatible_with(&source_stage));
+        debug_assert!(destination_access.is_compatible_with(&destination_stage));
+
+        self.add_stages(source_stage, destination_stage, by_region);
+
+        debug_assert_ne!(new_layout, ImageLayout::Undefined);
+        debug_assert_ne!(new_layout, ImageLayout::Preinitialized);
+
+        let mipmaps = resolve_remaining(mipmaps, image.mipmap_levels());
+        let layers = resolve_remaining(layers, image.dimensions().array_layers());
+
+        debug_assert!(mipmaps.start < mipmaps.end);
+        debug_assert!(mipmaps.end <= image.mipmap_levels());
+        debug_assert!(layers.start < layers.end);
+        debug_assert!(layers.end <= image.dimensions().array_layers());
+
+        self.add_image_memory_barrier_aspects(
+            image,
+            aspects,
+            mipmaps,
+            layers,
+            source_stage,
+            source_access,
+            destination_stage,
+            destination_access,
+            queue_transfer,
+            current_layout,
+            new_layout,
+        );
+    }
+
+    // Pushes the `vk::ImageMemoryBarrier2`. The execution dependency must have been added.
+    unsafe fn add_image_memory_barrier_aspects<I>(
+        &mut self,
+        image: &I,
+        aspects: ImageAspects,
+        mipmaps: Range<u32>,
+        layers: Range<u32>,
+        source_stage: PipelineStages,
+        source_access: AccessFlagBits,
+        destination_stage: PipelineStages,
+        destination_access: AccessFlagBits,
+        queue_transfer: Option<(u32, u32)>,
+        current_layout: ImageLayout,
+        new_layout: ImageLayout,
+    ) where
+        I: ?Sized + ImageAccess,
+    {
+        debug_assert!(aspects.is_valid_for_format(image.format(), false));
+
+        let (src_queue, dest_queue) = if let Some((src_queue, dest_queue)) = queue_transfer {
+            (src_queue, dest_queue)
+        } else {
+            (vk::QUEUE_FAMILY_IGNORED, vk::QUEUE_FAMILY_IGNORED)
+        };
+
         let image = image.inner();
 
-        self.image_barriers.push(vk::ImageMemoryBarrier {
-            sType: vk::STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER,
+        self.image_barriers.push(vk::ImageMemoryBarrier2 {
+            sType: vk::STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER_2,
             pNext: ptr::null(),
-            srcAccessMask: source_access.into_vulkan_bits(),
-            dstAccessMask: destination_access.into_vulkan_bits(),
+            srcStageMask: source_stage.into_vulkan_bits() as vk::PipelineStageFlags2,
+            srcAccessMask: source_access.into_vulkan_bits2(),
+            dstStageMask: destination_stage.into_vulkan_bits() as vk::PipelineStageFlags2,
+            dstAccessMask: destination_access.into_vulkan_bits2(),
             oldLayout: current_layout as u32,
             newLayout: new_layout as u32,
             srcQueueFamilyIndex: src_queue,
             dstQueueFamilyIndex: dest_queue,
             image: image.image.internal_object(),
             subresourceRange: vk::ImageSubresourceRange {
-                aspectMask: aspect_mask,
+                aspectMask: aspects.into_vulkan_bits(),
                 baseMipLevel: mipmaps.start + image.first_mipmap_level as u32,
                 levelCount: mipmaps.end - mipmaps.start,
                 baseArrayLayer: layers.start + image.first_layer as u32,
@@ -2106,6 +2521,268 @@ impl UnsafeCommandBufferBuilderPipelineBarrier {
             },
         });
     }
+
+    // Turns the barriers into the structs of `vkCmdPipelineBarrier` and `vkCmdWaitEvents`, whose
+    // stages are `src_stage_mask` and `dst_stage_mask` instead of being part of each barrier.
+    //
+    // Memory barriers without any access are dropped, as they are fully described by the
+    // execution dependency.
+    fn to_sync1(
+        &self,
+    ) -> (
+        SmallVec<[vk::MemoryBarrier; 2]>,
+        SmallVec<[vk::BufferMemoryBarrier; 8]>,
+        SmallVec<[vk::ImageMemoryBarrier; 8]>,
+    ) {
+        // The access flags that only exist with synchronization2 are replaced with the flags that
+        // include them.
+        let memory_barriers = self
+            .memory_barriers
+            .iter()
+            .filter(|b| b.srcAccessMask != 0 || b.dstAccessMask != 0)
+            .map(|b| vk::MemoryBarrier {
+                sType: vk::STRUCTURE_TYPE_MEMORY_BARRIER,
+                pNext: ptr::null(),
+                srcAccessMask: access2_to_access1(b.srcAccessMask),
+                dstAccessMask: access2_to_access1(b.dstAccessMask),
+            })
+            .collect();
+
+        let buffer_barriers = self
+            .buffer_barriers
+            .iter()
+            .map(|b| vk::BufferMemoryBarrier {
+                sType: vk::STRUCTURE_TYPE_BUFFER_MEMORY_BARRIER,
+                pNext: ptr::null(),
+                srcAccessMask: access2_to_access1(b.srcAccessMask),
+                dstAccessMask: access2_to_access1(b.dstAccessMask),
+                srcQueueFamilyIndex: b.srcQueueFamilyIndex,
+                dstQueueFamilyIndex: b.dstQueueFamilyIndex,
+                buffer: b.buffer,
+                offset: b.offset,
+                size: b.size,
+            })
+            .collect();
+
+        let image_barriers = self
+            .image_barriers
+            .iter()
+            .map(|b| vk::ImageMemoryBarrier {
+                sType: vk::STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER,
+                pNext: ptr::null(),
+                srcAccessMask: access2_to_access1(b.srcAccessMask),
+                dstAccessMask: access2_to_access1(b.dstAccessMask),
+                oldLayout: b.oldLayout,
+                newLayout: b.newLayout,
+                srcQueueFamilyIndex: b.srcQueueFamilyIndex,
+                dstQueueFamilyIndex: b.dstQueueFamilyIndex,
+                image: b.image,
+                subresourceRange: vk::ImageSubresourceRange {
+                    aspectMask: b.subresourceRange.aspectMask,
+                    baseMipLevel: b.subresourceRange.baseMipLevel,
+                    levelCount: b.subresourceRange.levelCount,
+                    baseArrayLayer: b.subresourceRange.baseArrayLayer,
+                    layerCount: b.subresourceRange.layerCount,
+                },
+            })
+            .collect();
+
+        (memory_barriers, buffer_barriers, image_barriers)
+    }
+
+    // Merges the barriers that can be expressed as a single barrier.
+    //
+    // All the global memory barriers are merged into one. Buffer barriers are merged if they
+    // apply to overlapping or adjacent ranges of the same buffer. Image barriers are merged if
+    // they perform the same layout transition on overlapping or adjacent subresource ranges of
+    // the same image. The access masks of the merged barriers are combined.
+    fn coalesce(&mut self) {
+        while self.memory_barriers.len() >= 2 {
+            let other = self.memory_barriers.pop().unwrap();
+            let first = &mut self.memory_barriers[0];
+            first.srcStageMask |= other.srcStageMask;
+            first.srcAccessMask |= other.srcAccessMask;
+            first.dstStageMask |= other.dstStageMask;
+            first.dstAccessMask |= other.dstAccessMask;
+        }
+
+        coalesce_barriers(&mut self.buffer_barriers, |a, b| {
+            if a.buffer != b.buffer
+                || a.srcQueueFamilyIndex != b.srcQueueFamilyIndex
+                || a.dstQueueFamilyIndex != b.dstQueueFamilyIndex
+            {
+                return false;
+            }
+
+            let (start, end) = match merge_ranges(a.offset, a.size, b.offset, b.size) {
+                Some(r) => r,
+                None => return false,
+            };
+            a.offset = start;
+            a.size = end - start;
+            a.srcStageMask |= b.srcStageMask;
+            a.srcAccessMask |= b.srcAccessMask;
+            a.dstStageMask |= b.dstStageMask;
+            a.dstAccessMask |= b.dstAccessMask;
+            true
+        });
+
+        coalesce_barriers(&mut self.image_barriers, |a, b| {
+            if a.image != b.image
+                || a.oldLayout != b.oldLayout
+                || a.newLayout != b.newLayout
+                || a.srcQueueFamilyIndex != b.srcQueueFamilyIndex
+                || a.dstQueueFamilyIndex != b.dstQueueFamilyIndex
+                || a.subresourceRange.aspectMask != b.subresourceRange.aspectMask
+            {
+                return false;
+            }
+
+            let (ra, rb) = (&mut a.subresourceRange, &b.subresourceRange);
+            if ra.baseMipLevel == rb.baseMipLevel && ra.levelCount == rb.levelCount {
+                let (start, end) = match merge_ranges(
+                    ra.baseArrayLayer,
+                    ra.layerCount,
+                    rb.baseArrayLayer,
+                    rb.layerCount,
+                ) {
+                    Some(r) => r,
+                    None => return false,
+                };
+                ra.baseArrayLayer = start;
+                ra.layerCount = end - start;
+            } else if ra.baseArrayLayer == rb.baseArrayLayer && ra.layerCount == rb.layerCount {
+                let (start, end) = match merge_ranges(
+                    ra.baseMipLevel,
+                    ra.levelCount,
+                    rb.baseMipLevel,
+                    rb.levelCount,
+                ) {
+                    Some(r) => r,
+                    None => return false,
+                };
+                ra.baseMipLevel = start;
+                ra.levelCount = end - start;
+            } else {
+                return false;
+            }
+
+            a.srcStageMask |= b.srcStageMask;
+            a.srcAccessMask |= b.srcAccessMask;
+            a.dstStageMask |= b.dstStageMask;
+            a.dstAccessMask |= b.dstAccessMask;
+            true
+        });
+    }
+}
+
+// Repeatedly tries to merge pairs of barriers with `merge`, which must merge its second parameter
+// into its first parameter and return true, or return false if they can't be merged.
+fn coalesce_barriers<A, F>(barriers: &mut SmallVec<A>, mut merge: F)
+where
+    A: ::smallvec::Array,
+    F: FnMut(&mut A::Item, &A::Item) -> bool,
+{
+    'outer: loop {
+        for i in 0..barriers.len() {
+            for j in i + 1..barriers.len() {
+                let (first, second) = barriers.split_at_mut(j);
+                if merge(&mut first[i], &second[0]) {
+                    barriers.remove(j);
+                    continue 'outer;
+                }
+            }
+        }
+
+        break;
+    }
+}
+
+// If the ranges `a_start .. a_start + a_len` and `b_start .. b_start + b_len` overlap or are
+// adjacent, returns the start and end of their union.
+fn merge_ranges<T>(a_start: T, a_len: T, b_start: T, b_len: T) -> Option<(T, T)>
+where
+    T: Copy + Ord + ::std::ops::Add<Output = T>,
+{
+    let (a_end, b_end) = (a_start + a_len, b_start + b_len);
+    if a_start > b_end || b_start > a_end {
+        return None;
+    }
+
+    Some((a_start.min(b_start), a_end.max(b_end)))
+}
+
+/// Accumulates pipeline barriers in order to record them with a single `vkCmdPipelineBarrier`.
+///
+/// Recording many small pipeline barriers is inefficient. A `BarrierBatch` collects the
+/// barriers that are added to it, merges the ones that overlap, and records everything as one
+/// pipeline barrier whose stage masks are the union of the stage masks of all the barriers when
+/// `flush` is called.
+///
+/// > **Note**: Merging barriers can only make the synchronization more conservative. However
+/// > the barriers must be valid to record at the same point of the command buffer, as all of
+/// > them are delayed until `flush` is called.
+pub struct BarrierBatch {
+    barrier: UnsafeCommandBufferBuilderPipelineBarrier,
+}
+
+impl BarrierBatch {
+    /// Creates a new empty batch.
+    #[inline]
+    pub fn new() -> BarrierBatch {
+        BarrierBatch {
+            barrier: UnsafeCommandBufferBuilderPipelineBarrier::new(),
+        }
+    }
+
+    /// Returns true if no barrier has been added since the last flush.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.barrier.is_empty()
+    }
+
+    /// Adds the barriers and execution dependencies of `barrier` to the batch.
+    #[inline]
+    pub fn add(&mut self, barrier: UnsafeCommandBufferBuilderPipelineBarrier) {
+        self.barrier.merge(barrier);
+        self.barrier.coalesce();
+    }
+
+    /// Records all the accumulated barriers as a single pipeline barrier on `builder`, and
+    /// empties the batch.
+    ///
+    /// Does nothing if the batch is empty.
+    ///
+    /// # Safety
+    ///
+    /// - The barriers must be valid to record at this point of the command buffer, see the
+    ///   safety requirements of the methods of `UnsafeCommandBufferBuilderPipelineBarrier`.
+    ///
+    #[inline]
+    pub unsafe fn flush<P>(&mut self, builder: &mut UnsafeCommandBufferBuilder<P>) {
+        let barrier = self.take();
+        builder.pipeline_barrier(&barrier);
+    }
+
+    /// Returns the accumulated barriers as a single pipeline barrier, and empties the batch.
+    #[inline]
+    pub fn take(&mut self) -> UnsafeCommandBufferBuilderPipelineBarrier {
+        mem::replace(
+            &mut self.barrier,
+            UnsafeCommandBufferBuilderPipelineBarrier::new(),
+        )
+    }
+}
+
+impl fmt::Debug for BarrierBatch {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("BarrierBatch")
+            .field("memory_barriers", &self.barrier.memory_barriers.len())
+            .field("buffer_barriers", &self.barrier.buffer_barriers.len())
+            .field("image_barriers", &self.barrier.image_barriers.len())
+            .finish()
+    }
 }
 
 /// Command buffer that has been built.
@@ -2121,6 +2798,18 @@ pub struct UnsafeCommandBuffer<P> {
     // Device that owns the command buffer.
     // TODO: necessary?
     device: Arc<Device>,
+
+    // True if the command buffer was allocated from a protected pool.
+    protected: bool,
+}
+
+impl<P> UnsafeCommandBuffer<P> {
+    /// Returns true if the command buffer was allocated from a protected pool, in which case it
+    /// must be submitted with a protected submission.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl<P> DeviceOwned for UnsafeCommandBuffer<P> {
@@ -2140,3 +2829,170 @@ unsafe impl<P> VulkanObject for UnsafeCommandBuffer<P> {
         self.cmd_raw
     }
 }
+
+// Returns the size to pass to `vkCmdFillBuffer` in order to fill `size` bytes at `offset` of a
+// buffer of `buffer_size` bytes. The size passed to Vulkan must be a multiple of 4 or
+// `VK_WHOLE_SIZE`, so if the range rounded up to 4 bytes goes up to the end of the buffer,
+// `VK_WHOLE_SIZE` is used in order to also cover the padding of a buffer whose content isn't a
+// multiple of 4 bytes.
+fn fill_buffer_size(offset: usize, size: usize, buffer_size: usize) -> vk::DeviceSize {
+    let padded_size = (size + 3) & !3;
+    if offset + padded_size == buffer_size || offset + size == buffer_size {
+        vk::WHOLE_SIZE
+    } else {
+        debug_assert_eq!(size % 4, 0);
+        size as vk::DeviceSize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fill_buffer_size;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::sys::BarrierBatch;
+    use command_buffer::sys::UnsafeCommandBufferBuilderPipelineBarrier;
+    use sync::AccessFlagBits;
+    use sync::PipelineStages;
+    use vk;
+
+    #[test]
+    fn barrier_batch_merges_buffer_barriers() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage::all(),
+            false,
+            (0..16).map(|_| 0u32),
+        )
+        .unwrap();
+
+        let transfer = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+        let compute = PipelineStages {
+            compute_shader: true,
+            ..PipelineStages::none()
+        };
+        let write = AccessFlagBits {
+            transfer_write: true,
+            ..AccessFlagBits::none()
+        };
+        let read = AccessFlagBits {
+            shader_read: true,
+            ..AccessFlagBits::none()
+        };
+
+        let mut batch = BarrierBatch::new();
+        assert!(batch.is_empty());
+
+        for &(offset, size) in &[(0, 16), (16, 16), (8, 16), (48, 4)] {
+            let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+            unsafe {
+                barrier.add_buffer_memory_barrier(
+                    &buffer, transfer, write, compute, read, false, None, offset, size,
+                );
+                barrier.add_memory_barrier(transfer, write, compute, read, false);
+            }
+            batch.add(barrier);
+        }
+
+        assert!(!batch.is_empty());
+        let barrier = batch.take();
+        assert!(batch.is_empty());
+
+        assert_eq!(barrier.memory_barriers.len(), 1);
+        assert_eq!(barrier.buffer_barriers.len(), 2);
+        assert_eq!(barrier.buffer_barriers[0].offset, 0);
+        assert_eq!(barrier.buffer_barriers[0].size, 32);
+        assert_eq!(barrier.buffer_barriers[1].offset, 48);
+        assert_eq!(barrier.buffer_barriers[1].size, 4);
+    }
+
+    #[test]
+    fn barrier_keeps_stages_per_barrier() {
+        let transfer = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+        let compute = PipelineStages {
+            compute_shader: true,
+            ..PipelineStages::none()
+        };
+        let vertex = PipelineStages {
+            vertex_shader: true,
+            ..PipelineStages::none()
+        };
+        let fragment = PipelineStages {
+            fragment_shader: true,
+            ..PipelineStages::none()
+        };
+        let write = AccessFlagBits {
+            transfer_write: true,
+            ..AccessFlagBits::none()
+        };
+        let read = AccessFlagBits {
+            shader_read: true,
+            ..AccessFlagBits::none()
+        };
+
+        let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+        unsafe {
+            barrier.add_memory_barrier(transfer, write, compute, read, false);
+            barrier.add_execution_dependency(vertex, fragment, false);
+        }
+
+        assert_eq!(
+            barrier.src_stage_mask,
+            vk::PIPELINE_STAGE_TRANSFER_BIT | vk::PIPELINE_STAGE_VERTEX_SHADER_BIT
+        );
+        assert_eq!(
+            barrier.dst_stage_mask,
+            vk::PIPELINE_STAGE_COMPUTE_SHADER_BIT | vk::PIPELINE_STAGE_FRAGMENT_SHADER_BIT
+        );
+
+        assert_eq!(barrier.memory_barriers.len(), 2);
+        let memory = &barrier.memory_barriers[0];
+        assert_eq!(memory.srcStageMask, vk::PIPELINE_STAGE_TRANSFER_BIT as u64);
+        assert_eq!(memory.srcAccessMask, vk::ACCESS_TRANSFER_WRITE_BIT as u64);
+        assert_eq!(
+            memory.dstStageMask,
+            vk::PIPELINE_STAGE_COMPUTE_SHADER_BIT as u64
+        );
+        assert_eq!(memory.dstAccessMask, vk::ACCESS_SHADER_READ_BIT as u64);
+        let execution = &barrier.memory_barriers[1];
+        assert_eq!(
+            execution.srcStageMask,
+            vk::PIPELINE_STAGE_VERTEX_SHADER_BIT as u64
+        );
+        assert_eq!(execution.srcAccessMask, vk::ACCESS_2_NONE);
+        assert_eq!(
+            execution.dstStageMask,
+            vk::PIPELINE_STAGE_FRAGMENT_SHADER_BIT as u64
+        );
+        assert_eq!(execution.dstAccessMask, vk::ACCESS_2_NONE);
+
+        let (memory_barriers, buffer_barriers, image_barriers) = barrier.to_sync1();
+        assert_eq!(memory_barriers.len(), 1);
+        assert_eq!(
+            memory_barriers[0].srcAccessMask,
+            vk::ACCESS_TRANSFER_WRITE_BIT
+        );
+        assert_eq!(memory_barriers[0].dstAccessMask, vk::ACCESS_SHADER_READ_BIT);
+        assert!(buffer_barriers.is_empty());
+        assert!(image_barriers.is_empty());
+    }
+
+    #[test]
+    fn fill_buffer_size_padded() {
+        // Content of 3 bytes in a buffer padded to 4 bytes.
+        assert_eq!(fill_buffer_size(0, 3, 4), vk::WHOLE_SIZE);
+        // Unpadded range up to the end of the buffer.
+        assert_eq!(fill_buffer_size(4, 6, 10), vk::WHOLE_SIZE);
+        // Range that doesn't go up to the end of the buffer.
+        assert_eq!(fill_buffer_size(4, 8, 16), 8);
+        assert_eq!(fill_buffer_size(0, 16, 16), vk::WHOLE_SIZE);
+    }
+}
@@ -18,10 +18,12 @@ use std::sync::Mutex;
 use buffer::BufferAccess;
 use command_buffer::submit::SubmitAnyBuilder;
 use command_buffer::submit::SubmitCommandBufferBuilder;
+use command_buffer::sys::Flags;
 use command_buffer::sys::UnsafeCommandBuffer;
 use device::Device;
 use device::DeviceOwned;
 use device::Queue;
+use framebuffer::AttachmentDescription;
 use image::ImageAccess;
 use image::ImageLayout;
 use sync::now;
@@ -50,6 +52,49 @@ pub unsafe trait CommandBuffer: DeviceOwned {
         self.inner().queue_family()
     }*/
 
+    /// If this is a secondary command buffer that is executed inside a render pass, returns the
+    /// attachments of the render pass and the index of the subpass it was created with.
+    ///
+    /// This is used to check that the secondary command buffer is compatible with the current
+    /// render pass of the primary command buffer it is executed in. The default implementation
+    /// returns `None`, which disables the check.
+    #[inline]
+    fn inheritance_render_pass(&self) -> Option<(&[AttachmentDescription], u32)> {
+        None
+    }
+
+    /// Returns the usage flags the command buffer was created with.
+    ///
+    /// This is used to check that a secondary command buffer can be executed in a primary
+    /// command buffer created with `Flags::SimultaneousUse`. The default implementation returns
+    /// `Flags::SimultaneousUse`, which disables the check.
+    #[inline]
+    fn usage(&self) -> Flags {
+        Flags::SimultaneousUse
+    }
+
+    /// Checks whether this secondary command buffer is allowed to be recorded into a primary
+    /// command buffer, and if so locks it.
+    ///
+    /// A secondary command buffer that wasn't created with `Flags::SimultaneousUse` can only be
+    /// recorded in one primary command buffer at a time, as recording it in another primary
+    /// command buffer invalidates the first one.
+    ///
+    /// If you call this function, then you should call `unlock_record` once the primary command
+    /// buffer is destroyed. The default implementation always succeeds.
+    #[inline]
+    fn lock_record(&self) -> Result<(), CommandBufferExecError> {
+        Ok(())
+    }
+
+    /// Unlocks the command buffer. Should be called once for each call to `lock_record`.
+    ///
+    /// # Safety
+    ///
+    /// Must not be called if you haven't called `lock_record` before.
+    #[inline]
+    unsafe fn unlock_record(&self) {}
+
     /// Checks whether this command buffer is allowed to be submitted after the `future` and on
     /// the given queue, and if so locks it.
     ///
@@ -179,6 +224,26 @@ where
         (**self).inner()
     }
 
+    #[inline]
+    fn inheritance_render_pass(&self) -> Option<(&[AttachmentDescription], u32)> {
+        (**self).inheritance_render_pass()
+    }
+
+    #[inline]
+    fn usage(&self) -> Flags {
+        (**self).usage()
+    }
+
+    #[inline]
+    fn lock_record(&self) -> Result<(), CommandBufferExecError> {
+        (**self).lock_record()
+    }
+
+    #[inline]
+    unsafe fn unlock_record(&self) {
+        (**self).unlock_record();
+    }
+
     #[inline]
     fn lock_submit(
         &self,
@@ -401,6 +466,10 @@ pub enum CommandBufferExecError {
     /// The command buffer or one of the secondary command buffers it executes is already in use by
     /// the GPU and was not created with the "concurrent" flag.
     ExclusiveAlreadyInUse,
+
+    /// The command buffer waits on an event that was last set by a command buffer submitted to
+    /// another queue, or that was set again or reset since the wait was recorded.
+    EventNotSet,
     // TODO: missing entries (eg. wrong queue family, secondary command buffer)
 }
 
@@ -432,6 +501,11 @@ impl fmt::Display for CommandBufferExecError {
                     "the command buffer or one of the secondary command buffers it executes is \
                  already in use by the GPU and was not created with the \"concurrent\" flag"
                 }
+                CommandBufferExecError::EventNotSet => {
+                    "the command buffer waits on an event that was last set by a command buffer \
+                 submitted to another queue, or that was set again or reset since the wait was \
+                 recorded"
+                }
             }
         )
     }
@@ -0,0 +1,367 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use buffer::TypedBufferAccess;
+use command_buffer::pool::standard::StandardCommandPoolBuilder;
+use command_buffer::pool::CommandPoolBuilderAlloc;
+use command_buffer::AutoCommandBuffer;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::BuildError;
+use command_buffer::CopyBufferError;
+use command_buffer::CopyBufferImageError;
+use command_buffer::CopyImageError;
+use command_buffer::FillBufferError;
+use command_buffer::UpdateBufferError;
+use device::Device;
+use device::DeviceOwned;
+use format::AcceptsPixels;
+use format::Format;
+use image::ImageAccess;
+use instance::QueueFamily;
+use OomError;
+
+/// Builds a command buffer that only contains transfer commands.
+///
+/// This is a restricted version of `AutoCommandBufferBuilder` that only exposes the commands
+/// that are valid on any queue family that supports transfer operations: copies between buffers
+/// and images, buffer fills and buffer updates. Since graphics and compute commands don't exist
+/// on this type, recording them into a command buffer meant for a dedicated transfer queue is a
+/// compile-time error.
+///
+/// The command buffer is allocated from the standard command pool of the queue family, which is
+/// separate from the pools of the other queue families. Like with `AutoCommandBufferBuilder`,
+/// the layout transitions and the pipeline barriers that the copies need are inserted
+/// automatically.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::command_buffer::CommandBuffer;
+/// use vulkano::command_buffer::TransferCommandBufferBuilder;
+/// # use std::sync::Arc;
+/// # use vulkano::buffer::CpuAccessibleBuffer;
+/// # use vulkano::buffer::DeviceLocalBuffer;
+/// # use vulkano::device::Device;
+/// # use vulkano::device::Queue;
+/// # let device: Arc<Device> = return;
+/// # let transfer_queue: Arc<Queue> = return;
+/// # let staging: Arc<CpuAccessibleBuffer<[u32]>> = return;
+/// # let destination: Arc<DeviceLocalBuffer<[u32]>> = return;
+///
+/// let mut builder =
+///     TransferCommandBufferBuilder::primary_one_time_submit(device, transfer_queue.family())
+///         .unwrap();
+/// builder.copy_buffer(staging, destination).unwrap();
+/// let command_buffer = builder.build().unwrap();
+///
+/// let _future = command_buffer.execute(transfer_queue).unwrap();
+/// ```
+pub struct TransferCommandBufferBuilder<P = StandardCommandPoolBuilder> {
+    inner: AutoCommandBufferBuilder<P>,
+}
+
+impl TransferCommandBufferBuilder<StandardCommandPoolBuilder> {
+    /// Starts building a primary command buffer that only contains transfer commands.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue family doesn't support transfer operations.
+    ///
+    #[inline]
+    pub fn primary(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+    ) -> Result<TransferCommandBufferBuilder<StandardCommandPoolBuilder>, OomError> {
+        assert!(supports_transfers(queue_family));
+
+        Ok(TransferCommandBufferBuilder {
+            inner: AutoCommandBufferBuilder::primary(device, queue_family)?,
+        })
+    }
+
+    /// Same as `primary`, but the final command buffer can only be submitted once.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue family doesn't support transfer operations.
+    ///
+    #[inline]
+    pub fn primary_one_time_submit(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+    ) -> Result<TransferCommandBufferBuilder<StandardCommandPoolBuilder>, OomError> {
+        assert!(supports_transfers(queue_family));
+
+        Ok(TransferCommandBufferBuilder {
+            inner: AutoCommandBufferBuilder::primary_one_time_submit(device, queue_family)?,
+        })
+    }
+}
+
+impl<P> TransferCommandBufferBuilder<P> {
+    /// Builds the command buffer.
+    #[inline]
+    pub fn build(self) -> Result<AutoCommandBuffer<P::Alloc>, BuildError>
+    where
+        P: CommandPoolBuilderAlloc,
+    {
+        self.inner.build()
+    }
+
+    /// Adds a command that copies from a buffer to another.
+    ///
+    /// See `AutoCommandBufferBuilder::copy_buffer`.
+    #[inline]
+    pub fn copy_buffer<S, D, T>(
+        &mut self,
+        source: S,
+        destination: D,
+    ) -> Result<&mut Self, CopyBufferError>
+    where
+        S: TypedBufferAccess<Content = T> + Send + Sync + 'static,
+        D: TypedBufferAccess<Content = T> + Send + Sync + 'static,
+        T: ?Sized,
+    {
+        self.inner.copy_buffer(source, destination)?;
+        Ok(self)
+    }
+
+    /// Adds a command that copies from a buffer to a whole image.
+    ///
+    /// See `AutoCommandBufferBuilder::copy_buffer_to_image`.
+    #[inline]
+    pub fn copy_buffer_to_image<S, D, Px>(
+        &mut self,
+        source: S,
+        destination: D,
+    ) -> Result<&mut Self, CopyBufferImageError>
+    where
+        S: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
+        D: ImageAccess + Send + Sync + 'static,
+        Format: AcceptsPixels<Px>,
+    {
+        self.inner.copy_buffer_to_image(source, destination)?;
+        Ok(self)
+    }
+
+    /// Adds a command that copies from a buffer to a region of an image.
+    ///
+    /// See `AutoCommandBufferBuilder::copy_buffer_to_image_dimensions`.
+    #[inline]
+    pub fn copy_buffer_to_image_dimensions<S, D, Px>(
+        &mut self,
+        source: S,
+        destination: D,
+        offset: [u32; 3],
+        size: [u32; 3],
+        first_layer: u32,
+        num_layers: u32,
+        mipmap: u32,
+    ) -> Result<&mut Self, CopyBufferImageError>
+    where
+        S: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
+        D: ImageAccess + Send + Sync + 'static,
+        Format: AcceptsPixels<Px>,
+    {
+        self.inner.copy_buffer_to_image_dimensions(
+            source,
+            destination,
+            offset,
+            size,
+            first_layer,
+            num_layers,
+            mipmap,
+        )?;
+        Ok(self)
+    }
+
+    /// Adds a command that copies a whole image to a buffer.
+    ///
+    /// See `AutoCommandBufferBuilder::copy_image_to_buffer`.
+    #[inline]
+    pub fn copy_image_to_buffer<S, D, Px>(
+        &mut self,
+        source: S,
+        destination: D,
+    ) -> Result<&mut Self, CopyBufferImageError>
+    where
+        S: ImageAccess + Send + Sync + 'static,
+        D: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
+        Format: AcceptsPixels<Px>,
+    {
+        self.inner.copy_image_to_buffer(source, destination)?;
+        Ok(self)
+    }
+
+    /// Adds a command that copies a region of an image to a buffer.
+    ///
+    /// See `AutoCommandBufferBuilder::copy_image_to_buffer_dimensions`.
+    #[inline]
+    pub fn copy_image_to_buffer_dimensions<S, D, Px>(
+        &mut self,
+        source: S,
+        destination: D,
+        offset: [u32; 3],
+        size: [u32; 3],
+        first_layer: u32,
+        num_layers: u32,
+        mipmap: u32,
+    ) -> Result<&mut Self, CopyBufferImageError>
+    where
+        S: ImageAccess + Send + Sync + 'static,
+        D: TypedBufferAccess<Content = [Px]> + Send + Sync + 'static,
+        Format: AcceptsPixels<Px>,
+    {
+        self.inner.copy_image_to_buffer_dimensions(
+            source,
+            destination,
+            offset,
+            size,
+            first_layer,
+            num_layers,
+            mipmap,
+        )?;
+        Ok(self)
+    }
+
+    /// Adds a command that copies a region of an image to another.
+    ///
+    /// See `AutoCommandBufferBuilder::copy_image`.
+    #[inline]
+    pub fn copy_image<S, D>(
+        &mut self,
+        source: S,
+        source_offset: [i32; 3],
+        source_base_array_layer: u32,
+        source_mip_level: u32,
+        destination: D,
+        destination_offset: [i32; 3],
+        destination_base_array_layer: u32,
+        destination_mip_level: u32,
+        extent: [u32; 3],
+        layer_count: u32,
+    ) -> Result<&mut Self, CopyImageError>
+    where
+        S: ImageAccess + Send + Sync + 'static,
+        D: ImageAccess + Send + Sync + 'static,
+    {
+        self.inner.copy_image(
+            source,
+            source_offset,
+            source_base_array_layer,
+            source_mip_level,
+            destination,
+            destination_offset,
+            destination_base_array_layer,
+            destination_mip_level,
+            extent,
+            layer_count,
+        )?;
+        Ok(self)
+    }
+
+    /// Adds a command that writes the content of a buffer.
+    ///
+    /// On a queue family that supports neither graphics nor compute operations, the
+    /// `khr_maintenance1` extension must be enabled. See `AutoCommandBufferBuilder::fill_buffer`.
+    #[inline]
+    pub fn fill_buffer<B>(&mut self, buffer: B, data: u32) -> Result<&mut Self, FillBufferError>
+    where
+        B: BufferAccess + Send + Sync + 'static,
+    {
+        self.inner.fill_buffer(buffer, data)?;
+        Ok(self)
+    }
+
+    /// Adds a command that writes data to a buffer.
+    ///
+    /// See `AutoCommandBufferBuilder::update_buffer`.
+    #[inline]
+    pub fn update_buffer<B, D>(
+        &mut self,
+        buffer: B,
+        data: D,
+    ) -> Result<&mut Self, UpdateBufferError>
+    where
+        B: TypedBufferAccess<Content = D> + Send + Sync + 'static,
+        D: Send + Sync + 'static,
+    {
+        self.inner.update_buffer(buffer, data)?;
+        Ok(self)
+    }
+}
+
+unsafe impl<P> DeviceOwned for TransferCommandBufferBuilder<P> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+// Graphics and compute queue families implicitly support transfer operations.
+#[inline]
+fn supports_transfers(queue_family: QueueFamily) -> bool {
+    queue_family.explicitly_supports_transfers()
+        || queue_family.supports_graphics()
+        || queue_family.supports_compute()
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::CommandBuffer;
+    use command_buffer::TransferCommandBufferBuilder;
+    use sync::GpuFuture;
+
+    #[test]
+    fn copy_and_fill() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let source = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            (0..16u32).map(|n| n),
+        )
+        .unwrap();
+        let destination = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            true,
+            (0..16u32).map(|_| 0),
+        )
+        .unwrap();
+
+        let mut builder =
+            TransferCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+                .unwrap();
+        builder
+            .copy_buffer(source.clone(), destination.clone())
+            .unwrap()
+            .fill_buffer(source.clone(), 7)
+            .unwrap();
+        let command_buffer = builder.build().unwrap();
+
+        command_buffer
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let destination = destination.read().unwrap();
+        assert_eq!(&*destination, &(0..16).collect::<Vec<u32>>()[..]);
+        assert!(source.read().unwrap().iter().all(|&n| n == 7));
+    }
+}
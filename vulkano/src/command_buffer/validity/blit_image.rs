@@ -14,6 +14,7 @@ use device::Device;
 use format::FormatTy;
 use image::ImageAccess;
 use image::ImageDimensions;
+use image::ImageLayout;
 use sampler::Filter;
 use VulkanObject;
 
@@ -76,6 +77,10 @@ where
         return Err(CheckBlitImageError::UnexpectedMultisampled);
     }
 
+    if filter == Filter::Linear && !source_inner.image.supports_linear_filtering() {
+        return Err(CheckBlitImageError::LinearFilterNotSupported);
+    }
+
     let source_format_ty = source.format().ty();
     let destination_format_ty = destination.format().ty();
 
@@ -218,6 +223,27 @@ where
     Ok(())
 }
 
+/// Checks whether the layouts passed to a blit image command are valid.
+///
+/// The source must be in the `TransferSrcOptimal` or `General` layout, and the destination in the
+/// `TransferDstOptimal` or `General` layout.
+pub fn check_blit_image_layouts(
+    source_layout: ImageLayout,
+    destination_layout: ImageLayout,
+) -> Result<(), CheckBlitImageError> {
+    match source_layout {
+        ImageLayout::TransferSrcOptimal | ImageLayout::General => (),
+        _ => return Err(CheckBlitImageError::UnsupportedSourceLayout),
+    }
+
+    match destination_layout {
+        ImageLayout::TransferDstOptimal | ImageLayout::General => (),
+        _ => return Err(CheckBlitImageError::UnsupportedDestinationLayout),
+    }
+
+    Ok(())
+}
+
 /// Error that can happen from `check_clear_color_image`.
 #[derive(Debug, Copy, Clone)]
 pub enum CheckBlitImageError {
@@ -240,12 +266,18 @@ pub enum CheckBlitImageError {
     },
     /// Blitting between multisampled images is forbidden.
     UnexpectedMultisampled,
+    /// The format of the source image doesn't support linear filtering.
+    LinearFilterNotSupported,
     /// The offsets, array layers and/or mipmap levels are out of range in the source image.
     SourceCoordinatesOutOfRange,
     /// The offsets, array layers and/or mipmap levels are out of range in the destination image.
     DestinationCoordinatesOutOfRange,
     /// The top-left and/or bottom-right coordinates are incompatible with the image type.
     IncompatibleRangeForImageType,
+    /// The source layout is not `TransferSrcOptimal` or `General`.
+    UnsupportedSourceLayout,
+    /// The destination layout is not `TransferDstOptimal` or `General`.
+    UnsupportedDestinationLayout,
 }
 
 impl error::Error for CheckBlitImageError {}
@@ -282,6 +314,9 @@ impl fmt::Display for CheckBlitImageError {
                 CheckBlitImageError::UnexpectedMultisampled => {
                     "blitting between multisampled images is forbidden"
                 }
+                CheckBlitImageError::LinearFilterNotSupported => {
+                    "the format of the source image doesn't support linear filtering"
+                }
                 CheckBlitImageError::SourceCoordinatesOutOfRange => {
                     "the offsets, array layers and/or mipmap levels are out of range in the source \
                  image"
@@ -293,6 +328,12 @@ impl fmt::Display for CheckBlitImageError {
                 CheckBlitImageError::IncompatibleRangeForImageType => {
                     "the top-left and/or bottom-right coordinates are incompatible with the image type"
                 }
+                CheckBlitImageError::UnsupportedSourceLayout => {
+                    "the source layout is not `TransferSrcOptimal` or `General`"
+                }
+                CheckBlitImageError::UnsupportedDestinationLayout => {
+                    "the destination layout is not `TransferDstOptimal` or `General`"
+                }
             }
         )
     }
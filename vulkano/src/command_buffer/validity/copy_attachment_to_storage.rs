@@ -0,0 +1,225 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use device::Device;
+use format::Format;
+use format::PossibleCompressedFormatDesc;
+use image::ImageAccess;
+use VulkanObject;
+
+/// Checks whether a color attachment can be copied into a storage image with
+/// `copy_attachment_to_storage_image`.
+///
+/// The other checks of a copy, like the transfer usages, are done by `check_copy_image`.
+///
+/// # Panic
+///
+/// - Panics if the source or the destination was not created with `device`.
+///
+pub fn check_copy_attachment_to_storage<S, D>(
+    device: &Device,
+    source: &S,
+    destination: &D,
+) -> Result<(), CheckCopyAttachmentToStorageError>
+where
+    S: ?Sized + ImageAccess,
+    D: ?Sized + ImageAccess,
+{
+    let source_inner = source.inner();
+    let destination_inner = destination.inner();
+
+    assert_eq!(
+        source_inner.image.device().internal_object(),
+        device.internal_object()
+    );
+    assert_eq!(
+        destination_inner.image.device().internal_object(),
+        device.internal_object()
+    );
+
+    if !source_inner.image.usage_color_attachment() {
+        return Err(CheckCopyAttachmentToStorageError::MissingColorAttachmentUsage);
+    }
+
+    if !destination_inner.image.usage_storage() {
+        return Err(CheckCopyAttachmentToStorageError::MissingStorageUsage);
+    }
+
+    if !source.has_color() {
+        return Err(CheckCopyAttachmentToStorageError::SourceNotColor);
+    }
+
+    if source.format().is_compressed()
+        || destination.format().is_compressed()
+        || source.format().size() != destination.format().size()
+    {
+        return Err(CheckCopyAttachmentToStorageError::IncompatibleFormats {
+            source: source.format(),
+            destination: destination.format(),
+        });
+    }
+
+    let source_dimensions = source.dimensions();
+    let destination_dimensions = destination.dimensions();
+    if source_dimensions.width_height_depth() != destination_dimensions.width_height_depth()
+        || source_dimensions.array_layers() != destination_dimensions.array_layers()
+    {
+        return Err(CheckCopyAttachmentToStorageError::DimensionsMismatch);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen from `check_copy_attachment_to_storage`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckCopyAttachmentToStorageError {
+    /// The source is missing the color attachment usage.
+    MissingColorAttachmentUsage,
+    /// The destination is missing the storage usage.
+    MissingStorageUsage,
+    /// The source doesn't have a color format.
+    SourceNotColor,
+    /// The formats of the source and the destination don't have the same texel size, or one of
+    /// them is compressed.
+    IncompatibleFormats {
+        /// Format of the source.
+        source: Format,
+        /// Format of the destination.
+        destination: Format,
+    },
+    /// The source and the destination don't have the same dimensions and number of array
+    /// layers.
+    DimensionsMismatch,
+}
+
+impl error::Error for CheckCopyAttachmentToStorageError {}
+
+impl fmt::Display for CheckCopyAttachmentToStorageError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckCopyAttachmentToStorageError::MissingColorAttachmentUsage => {
+                    "the source is missing the color attachment usage"
+                }
+                CheckCopyAttachmentToStorageError::MissingStorageUsage => {
+                    "the destination is missing the storage usage"
+                }
+                CheckCopyAttachmentToStorageError::SourceNotColor => {
+                    "the source doesn't have a color format"
+                }
+                CheckCopyAttachmentToStorageError::IncompatibleFormats { .. } => {
+                    "the formats of the source and the destination aren't size-compatible"
+                }
+                CheckCopyAttachmentToStorageError::DimensionsMismatch => {
+                    "the source and the destination don't have the same dimensions"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use format::R8G8B8A8Unorm;
+    use format::R8Unorm;
+    use image::AttachmentImage;
+    use image::Dimensions;
+    use image::ImageUsage;
+    use image::StorageImage;
+
+    #[test]
+    fn checks() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let attachment = AttachmentImage::with_usage(
+            device.clone(),
+            [32, 32],
+            R8G8B8A8Unorm,
+            ImageUsage {
+                transfer_source: true,
+                ..ImageUsage::none()
+            },
+        )
+        .unwrap();
+        let storage = StorageImage::new(
+            device.clone(),
+            Dimensions::Dim2d {
+                width: 32,
+                height: 32,
+            },
+            R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+        let small = StorageImage::new(
+            device.clone(),
+            Dimensions::Dim2d {
+                width: 16,
+                height: 16,
+            },
+            R8G8B8A8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+        let storage_only = StorageImage::with_usage(
+            device.clone(),
+            Dimensions::Dim2d {
+                width: 32,
+                height: 32,
+            },
+            R8G8B8A8Unorm,
+            ImageUsage {
+                transfer_source: true,
+                storage: true,
+                ..ImageUsage::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+        let narrow = StorageImage::new(
+            device.clone(),
+            Dimensions::Dim2d {
+                width: 32,
+                height: 32,
+            },
+            R8Unorm,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        check_copy_attachment_to_storage(&device, &attachment, &storage).unwrap();
+
+        match check_copy_attachment_to_storage(&device, &storage_only, &storage) {
+            Err(CheckCopyAttachmentToStorageError::MissingColorAttachmentUsage) => (),
+            _ => panic!(),
+        }
+
+        match check_copy_attachment_to_storage(&device, &attachment, &attachment) {
+            Err(CheckCopyAttachmentToStorageError::MissingStorageUsage) => (),
+            _ => panic!(),
+        }
+
+        match check_copy_attachment_to_storage(&device, &attachment, &small) {
+            Err(CheckCopyAttachmentToStorageError::DimensionsMismatch) => (),
+            _ => panic!(),
+        }
+
+        match check_copy_attachment_to_storage(&device, &attachment, &narrow) {
+            Err(CheckCopyAttachmentToStorageError::IncompatibleFormats { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -15,6 +15,7 @@ use format::FormatTy;
 use format::PossibleCompressedFormatDesc;
 use image::ImageAccess;
 use image::ImageDimensions;
+use image::ImageLayout;
 use VulkanObject;
 
 /// Checks whether a copy image command is valid.
@@ -176,6 +177,27 @@ where
     Ok(())
 }
 
+/// Checks whether the layouts passed to a copy image command are valid.
+///
+/// The source must be in the `TransferSrcOptimal` or `General` layout, and the destination in the
+/// `TransferDstOptimal` or `General` layout.
+pub fn check_copy_image_layouts(
+    source_layout: ImageLayout,
+    destination_layout: ImageLayout,
+) -> Result<(), CheckCopyImageError> {
+    match source_layout {
+        ImageLayout::TransferSrcOptimal | ImageLayout::General => (),
+        _ => return Err(CheckCopyImageError::UnsupportedSourceLayout),
+    }
+
+    match destination_layout {
+        ImageLayout::TransferDstOptimal | ImageLayout::General => (),
+        _ => return Err(CheckCopyImageError::UnsupportedDestinationLayout),
+    }
+
+    Ok(())
+}
+
 /// Error that can happen from `check_copy_image`.
 #[derive(Debug, Copy, Clone)]
 pub enum CheckCopyImageError {
@@ -198,6 +220,10 @@ pub enum CheckCopyImageError {
     DestinationCoordinatesOutOfRange,
     /// The offsets or extent are incompatible with the image type.
     IncompatibleRangeForImageType,
+    /// The layout of the source must be `TransferSrcOptimal` or `General`.
+    UnsupportedSourceLayout,
+    /// The layout of the destination must be `TransferDstOptimal` or `General`.
+    UnsupportedDestinationLayout,
 }
 
 impl error::Error for CheckCopyImageError {}
@@ -236,6 +262,12 @@ impl fmt::Display for CheckCopyImageError {
                 CheckCopyImageError::IncompatibleRangeForImageType => {
                     "the offsets or extent are incompatible with the image type"
                 }
+                CheckCopyImageError::UnsupportedSourceLayout => {
+                    "the layout of the source must be `TransferSrcOptimal` or `General`"
+                }
+                CheckCopyImageError::UnsupportedDestinationLayout => {
+                    "the layout of the destination must be `TransferDstOptimal` or `General`"
+                }
             }
         )
     }
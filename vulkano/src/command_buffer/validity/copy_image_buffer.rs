@@ -11,6 +11,7 @@ use std::error;
 use std::fmt;
 
 use buffer::TypedBufferAccess;
+use command_buffer::BufferImageCopyLayout;
 use device::Device;
 use device::DeviceOwned;
 use format::AcceptsPixels;
@@ -48,6 +49,44 @@ where
     I: ?Sized + ImageAccess,
     B: ?Sized + TypedBufferAccess<Content = [P]>,
     Format: AcceptsPixels<P>, // TODO: use a trait on the image itself instead
+{
+    check_copy_buffer_image_layout(
+        device,
+        buffer,
+        image,
+        ty,
+        BufferImageCopyLayout::packed(),
+        image_offset,
+        image_size,
+        image_first_layer,
+        image_num_layers,
+        image_mipmap,
+    )
+}
+
+/// Same as `check_copy_buffer_image`, but for copies where the texels in the buffer follow
+/// `layout` instead of being tightly packed.
+///
+/// # Panic
+///
+/// - Panics if the buffer and image were not created with `device`.
+///
+pub fn check_copy_buffer_image_layout<B, I, P>(
+    device: &Device,
+    buffer: &B,
+    image: &I,
+    ty: CheckCopyBufferImageTy,
+    layout: BufferImageCopyLayout,
+    image_offset: [u32; 3],
+    image_size: [u32; 3],
+    image_first_layer: u32,
+    image_num_layers: u32,
+    image_mipmap: u32,
+) -> Result<(), CheckCopyBufferImageError>
+where
+    I: ?Sized + ImageAccess,
+    B: ?Sized + TypedBufferAccess<Content = [P]>,
+    Format: AcceptsPixels<P>,
 {
     let buffer_inner = buffer.inner();
     let image_inner = image.inner();
@@ -107,8 +146,22 @@ where
 
     image.format().ensure_accepts()?;
 
+    let (block_width, block_height) = image.format().block_dimensions();
+
+    if layout.row_length != 0
+        && (layout.row_length < image_size[0] || layout.row_length % block_width != 0)
+    {
+        return Err(CheckCopyBufferImageError::InvalidRowLength);
+    }
+
+    if layout.image_height != 0
+        && (layout.image_height < image_size[1] || layout.image_height % block_height != 0)
+    {
+        return Err(CheckCopyBufferImageError::InvalidImageHeight);
+    }
+
     {
-        let required_len = required_len_for_format(image.format(), image_size, image_num_layers);
+        let required_len = layout.required_len(image.format(), image_size, image_num_layers);
         if required_len > buffer.len() {
             return Err(CheckCopyBufferImageError::BufferTooSmall {
                 required_len: required_len,
@@ -122,26 +175,17 @@ where
     Ok(())
 }
 
-/// Computes the minimum required len in elements for buffer with image data in specified
-/// format of specified size.
-fn required_len_for_format<P>(format: Format, image_size: [u32; 3], image_num_layers: u32) -> usize
-where
-    Format: AcceptsPixels<P>,
-{
-    let (block_width, block_height) = format.block_dimensions();
-    let num_blocks = (image_size[0] + block_width - 1) / block_width
-        * ((image_size[1] + block_height - 1) / block_height)
-        * image_size[2]
-        * image_num_layers;
-    let required_len = num_blocks as usize * format.rate() as usize;
-
-    return required_len;
-}
-
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
-    use command_buffer::validity::copy_image_buffer::required_len_for_format;
+    use command_buffer::BufferImageCopyLayout;
+
+    fn required_len_for_format<P>(format: Format, image_size: [u32; 3], num_layers: u32) -> usize
+    where
+        Format: ::format::AcceptsPixels<P>,
+    {
+        BufferImageCopyLayout::packed().required_len::<P>(format, image_size, num_layers)
+    }
 
     #[test]
     fn test_required_len_for_format() {
@@ -184,6 +228,50 @@ mod tests {
             29584
         );
     }
+
+    #[test]
+    fn required_len_with_padding() {
+        let layout = BufferImageCopyLayout {
+            buffer_offset: 16,
+            row_length: 64,
+            image_height: 0,
+        };
+
+        // The last row isn't padded.
+        assert_eq!(
+            layout.required_len::<u8>(Format::R8G8B8A8Unorm, [60, 2, 1], 1),
+            16 + (64 + 60) * 4
+        );
+        assert_eq!(
+            layout.required_len::<[u8; 4]>(Format::R8G8B8A8Unorm, [60, 2, 1], 2),
+            16 + 64 * 3 + 60
+        );
+    }
+
+    #[test]
+    fn optimal_layout() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let limits = device.physical_device().limits();
+        let alignment = limits.optimal_buffer_copy_row_pitch_alignment();
+        let offset_alignment = limits.optimal_buffer_copy_offset_alignment().max(4);
+
+        let layout = BufferImageCopyLayout::optimal::<u8>(&device, Format::R8G8B8A8Unorm, 3, 0);
+        let row_length = if layout.row_length == 0 {
+            3
+        } else {
+            layout.row_length
+        };
+
+        assert_eq!(layout.buffer_offset, 0);
+        assert!(row_length >= 3);
+        assert_eq!((row_length as u64 * 4) % alignment.max(1), 0);
+
+        // The first texel of a slice that starts at an unaligned offset is moved forward.
+        let layout = BufferImageCopyLayout::optimal::<u8>(&device, Format::R8G8B8A8Unorm, 3, 5);
+        assert!(layout.buffer_offset > 0);
+        assert_eq!((5 + layout.buffer_offset as u64) % offset_alignment, 0);
+    }
 }
 
 /// Error that can happen from `check_copy_buffer_image`.
@@ -208,6 +296,12 @@ pub enum CheckCopyBufferImageError {
         /// Actual number of elements in the buffer.
         actual_len: usize,
     },
+    /// The row length of the buffer layout is smaller than the width of the region, or isn't a
+    /// multiple of the block width of the format.
+    InvalidRowLength,
+    /// The image height of the buffer layout is smaller than the height of the region, or isn't
+    /// a multiple of the block height of the format.
+    InvalidImageHeight,
 }
 
 impl error::Error for CheckCopyBufferImageError {
@@ -247,6 +341,14 @@ impl fmt::Display for CheckCopyBufferImageError {
                 CheckCopyBufferImageError::BufferTooSmall { .. } => {
                     "the buffer is too small for the copy operation"
                 }
+                CheckCopyBufferImageError::InvalidRowLength => {
+                    "the row length of the buffer layout is smaller than the width of the region \
+                     or isn't a multiple of the block width"
+                }
+                CheckCopyBufferImageError::InvalidImageHeight => {
+                    "the image height of the buffer layout is smaller than the height of the \
+                     region or isn't a multiple of the block height"
+                }
             }
         )
     }
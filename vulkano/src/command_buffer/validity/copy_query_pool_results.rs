@@ -0,0 +1,206 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::mem;
+
+use buffer::TypedBufferAccess;
+use device::Device;
+use device::DeviceOwned;
+use query::QueryResultFlags;
+use query::QueryType;
+use query::UnsafeQueryPool;
+use VulkanObject;
+
+/// Checks whether a copy query pool results command is valid.
+///
+/// On success, returns the stride in bytes between the results of two queries in the
+/// destination buffer.
+///
+/// # Panic
+///
+/// - Panics if the query pool or the buffer were not created with `device`.
+///
+pub fn check_copy_query_pool_results<D>(
+    device: &Device,
+    pool: &UnsafeQueryPool,
+    first_index: u32,
+    count: u32,
+    destination: &D,
+    flags: QueryResultFlags,
+) -> Result<usize, CheckCopyQueryPoolResultsError>
+where
+    D: ?Sized + TypedBufferAccess<Content = [u64]>,
+{
+    assert_eq!(pool.device().internal_object(), device.internal_object());
+    assert_eq!(
+        destination.inner().buffer.device().internal_object(),
+        device.internal_object()
+    );
+
+    if count == 0 || pool.queries_range(first_index, count).is_none() {
+        return Err(CheckCopyQueryPoolResultsError::OutOfRange);
+    }
+
+    if !destination.inner().buffer.usage_transfer_destination() {
+        return Err(CheckCopyQueryPoolResultsError::BufferMissingUsage);
+    }
+
+    if destination.inner().offset % mem::size_of::<u64>() != 0 {
+        return Err(CheckCopyQueryPoolResultsError::WrongAlignment);
+    }
+
+    if flags.partial {
+        if let QueryType::Timestamp = pool.ty() {
+            return Err(CheckCopyQueryPoolResultsError::PartialTimestamps);
+        }
+    }
+
+    let values_per_query = pool.ty().result_size() + flags.with_availability as usize;
+    let required_len = values_per_query * count as usize;
+    if destination.len() < required_len {
+        return Err(CheckCopyQueryPoolResultsError::BufferTooSmall {
+            required_len,
+            actual_len: destination.len(),
+        });
+    }
+
+    Ok(values_per_query * mem::size_of::<u64>())
+}
+
+/// Error that can happen when attempting to add a `copy_query_pool_results` command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckCopyQueryPoolResultsError {
+    /// The range of queries is empty or out of the pool.
+    OutOfRange,
+    /// The "transfer destination" usage must be enabled on the buffer.
+    BufferMissingUsage,
+    /// The offset of the buffer must be a multiple of 8.
+    WrongAlignment,
+    /// Partial results can't be retrieved from timestamp queries.
+    PartialTimestamps,
+    /// The buffer is too small for the results.
+    BufferTooSmall {
+        /// Required number of elements in the buffer.
+        required_len: usize,
+        /// Actual number of elements in the buffer.
+        actual_len: usize,
+    },
+}
+
+impl error::Error for CheckCopyQueryPoolResultsError {}
+
+impl fmt::Display for CheckCopyQueryPoolResultsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckCopyQueryPoolResultsError::OutOfRange => {
+                    "the range of queries is empty or out of the pool"
+                }
+                CheckCopyQueryPoolResultsError::BufferMissingUsage => {
+                    "the transfer destination usage must be enabled on the buffer"
+                }
+                CheckCopyQueryPoolResultsError::WrongAlignment => {
+                    "the offset of the buffer must be a multiple of 8"
+                }
+                CheckCopyQueryPoolResultsError::PartialTimestamps => {
+                    "partial results can't be retrieved from timestamp queries"
+                }
+                CheckCopyQueryPoolResultsError::BufferTooSmall { .. } => {
+                    "the buffer is too small for the results"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use std::iter;
+
+    #[test]
+    fn stride_with_availability() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Timestamp, 4).unwrap();
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_destination(),
+            true,
+            iter::repeat(0u64).take(8),
+        )
+        .unwrap();
+
+        let flags = QueryResultFlags {
+            with_availability: true,
+            ..QueryResultFlags::default()
+        };
+        assert_eq!(
+            check_copy_query_pool_results(&device, &pool, 0, 4, &buffer, flags),
+            Ok(16)
+        );
+        assert_eq!(
+            check_copy_query_pool_results(&device, &pool, 2, 3, &buffer, flags),
+            Err(CheckCopyQueryPoolResultsError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Occlusion, 4).unwrap();
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_destination(),
+            true,
+            iter::repeat(0u64).take(4),
+        )
+        .unwrap();
+
+        let flags = QueryResultFlags {
+            with_availability: true,
+            ..QueryResultFlags::default()
+        };
+        assert_eq!(
+            check_copy_query_pool_results(&device, &pool, 0, 4, &buffer, flags),
+            Err(CheckCopyQueryPoolResultsError::BufferTooSmall {
+                required_len: 8,
+                actual_len: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn partial_timestamps() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Timestamp, 1).unwrap();
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_destination(),
+            true,
+            iter::once(0u64),
+        )
+        .unwrap();
+
+        let flags = QueryResultFlags {
+            partial: true,
+            ..QueryResultFlags::default()
+        };
+        assert_eq!(
+            check_copy_query_pool_results(&device, &pool, 0, 1, &buffer, flags),
+            Err(CheckCopyQueryPoolResultsError::PartialTimestamps)
+        );
+    }
+}
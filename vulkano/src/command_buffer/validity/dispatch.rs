@@ -29,6 +29,31 @@ pub fn check_dispatch(device: &Device, dimensions: [u32; 3]) -> Result<(), Check
     Ok(())
 }
 
+/// Computes the number of workgroups needed to cover `total` invocations in each dimension with
+/// workgroups of `local_size`, rounding up.
+///
+/// Returns an error if the local size isn't known.
+pub fn dispatch_dimensions_for(
+    local_size: Option<[u32; 3]>,
+    total: [u32; 3],
+) -> Result<[u32; 3], CheckDispatchError> {
+    let local_size = match local_size {
+        Some(s) => s,
+        None => return Err(CheckDispatchError::UnknownLocalSize),
+    };
+
+    let div = |total: u32, local: u32| {
+        debug_assert!(local != 0);
+        total / local + if total % local != 0 { 1 } else { 0 }
+    };
+
+    Ok([
+        div(total[0], local_size[0]),
+        div(total[1], local_size[1]),
+        div(total[2], local_size[2]),
+    ])
+}
+
 /// Error that can happen when checking dispatch command validity.
 #[derive(Debug, Copy, Clone)]
 pub enum CheckDispatchError {
@@ -39,6 +64,8 @@ pub enum CheckDispatchError {
         /// The actual supported dimensions.
         max_supported: [u32; 3],
     },
+    /// The size of the local workgroups of the compute pipeline isn't known.
+    UnknownLocalSize,
 }
 
 impl error::Error for CheckDispatchError {}
@@ -53,6 +80,9 @@ impl fmt::Display for CheckDispatchError {
                 CheckDispatchError::UnsupportedDimensions { .. } => {
                     "the dimensions are too large for the device's limits"
                 }
+                CheckDispatchError::UnknownLocalSize => {
+                    "the size of the local workgroups of the compute pipeline isn't known"
+                }
             }
         )
     }
@@ -85,4 +115,21 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn dimensions_rounded_up() {
+        assert_eq!(
+            validity::dispatch_dimensions_for(Some([8, 8, 1]), [64, 65, 1]).unwrap(),
+            [8, 9, 1]
+        );
+        assert_eq!(
+            validity::dispatch_dimensions_for(Some([64, 1, 1]), [0, 1, 1]).unwrap(),
+            [0, 1, 1]
+        );
+
+        match validity::dispatch_dimensions_for(None, [1, 1, 1]) {
+            Err(validity::CheckDispatchError::UnknownLocalSize) => (),
+            _ => panic!(),
+        }
+    }
 }
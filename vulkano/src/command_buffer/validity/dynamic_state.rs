@@ -11,6 +11,10 @@ use std::error;
 use std::fmt;
 
 use command_buffer::DynamicState;
+use device::Device;
+use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::raster::CullMode;
+use pipeline::raster::FrontFace;
 use pipeline::GraphicsPipelineAbstract;
 
 /// Checks whether states that are about to be set are correct.
@@ -148,6 +152,24 @@ pub enum CheckDynamicStateValidityError {
     ReferenceNotDynamic,
     /// The pipeline has dynamic reference, but no reference was passed.
     ReferenceMissing,
+    /// The pipeline has a dynamic cull mode, but no cull mode was set.
+    CullModeMissing,
+    /// The pipeline has a dynamic front face, but no front face was set.
+    FrontFaceMissing,
+    /// The pipeline has a dynamic primitive topology, but no primitive topology was set.
+    PrimitiveTopologyMissing,
+    /// The primitive topology that was set doesn't belong to the same topology class as the
+    /// primitive topology of the pipeline.
+    PrimitiveTopologyClassMismatch {
+        /// Primitive topology of the pipeline.
+        pipeline: PrimitiveTopology,
+        /// Primitive topology that was set.
+        obtained: PrimitiveTopology,
+    },
+    /// The pipeline has a dynamic depth test enable, but no value was set.
+    DepthTestEnableMissing,
+    /// The pipeline has a dynamic depth write enable, but no value was set.
+    DepthWriteEnableMissing,
 }
 
 impl error::Error for CheckDynamicStateValidityError {}
@@ -207,9 +229,205 @@ impl fmt::Display for CheckDynamicStateValidityError {
                 CheckDynamicStateValidityError::ReferenceMissing => {
                     "the pipeline has dynamic reference, but no reference was passed"
                 }
+                CheckDynamicStateValidityError::CullModeMissing => {
+                    "the pipeline has a dynamic cull mode, but no cull mode was set"
+                }
+                CheckDynamicStateValidityError::FrontFaceMissing => {
+                    "the pipeline has a dynamic front face, but no front face was set"
+                }
+                CheckDynamicStateValidityError::PrimitiveTopologyMissing => {
+                    "the pipeline has a dynamic primitive topology, but no primitive topology was \
+                     set"
+                }
+                CheckDynamicStateValidityError::PrimitiveTopologyClassMismatch { .. } => {
+                    "the primitive topology that was set doesn't belong to the same topology class \
+                     as the primitive topology of the pipeline"
+                }
+                CheckDynamicStateValidityError::DepthTestEnableMissing => {
+                    "the pipeline has a dynamic depth test enable, but no value was set"
+                }
+                CheckDynamicStateValidityError::DepthWriteEnableMissing => {
+                    "the pipeline has a dynamic depth write enable, but no value was set"
+                }
             }
         )
     }
 }
 
-// TODO: tests
+/// Extended dynamic states that were set in a command buffer, such as the cull mode or the
+/// primitive topology. A state is `None` if it hasn't been set yet.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ExtendedDynamicState {
+    pub cull_mode: Option<CullMode>,
+    pub front_face: Option<FrontFace>,
+    pub primitive_topology: Option<PrimitiveTopology>,
+    pub depth_test_enable: Option<bool>,
+    pub depth_write_enable: Option<bool>,
+}
+
+/// Checks whether the extended dynamic states that the pipeline has as dynamic were set, and
+/// that the primitive topology belongs to the topology class of the pipeline.
+pub fn check_extended_dynamic_state_validity<Pl>(
+    pipeline: &Pl,
+    state: &ExtendedDynamicState,
+) -> Result<(), CheckDynamicStateValidityError>
+where
+    Pl: GraphicsPipelineAbstract,
+{
+    let dynamic = ExtendedDynamicFlags {
+        cull_mode: pipeline.has_dynamic_cull_mode(),
+        front_face: pipeline.has_dynamic_front_face(),
+        primitive_topology: pipeline.has_dynamic_primitive_topology(),
+        depth_test_enable: pipeline.has_dynamic_depth_test_enable(),
+        depth_write_enable: pipeline.has_dynamic_depth_write_enable(),
+    };
+
+    check_extended_states(&dynamic, pipeline.primitive_topology(), state)
+}
+
+// Which extended dynamic states a pipeline has as dynamic.
+#[derive(Default)]
+struct ExtendedDynamicFlags {
+    cull_mode: bool,
+    front_face: bool,
+    primitive_topology: bool,
+    depth_test_enable: bool,
+    depth_write_enable: bool,
+}
+
+fn check_extended_states(
+    dynamic: &ExtendedDynamicFlags,
+    pipeline_topology: PrimitiveTopology,
+    state: &ExtendedDynamicState,
+) -> Result<(), CheckDynamicStateValidityError> {
+    if dynamic.cull_mode && state.cull_mode.is_none() {
+        return Err(CheckDynamicStateValidityError::CullModeMissing);
+    }
+
+    if dynamic.front_face && state.front_face.is_none() {
+        return Err(CheckDynamicStateValidityError::FrontFaceMissing);
+    }
+
+    if dynamic.primitive_topology {
+        match state.primitive_topology {
+            Some(topology) if !topology.is_same_class(&pipeline_topology) => {
+                return Err(
+                    CheckDynamicStateValidityError::PrimitiveTopologyClassMismatch {
+                        pipeline: pipeline_topology,
+                        obtained: topology,
+                    },
+                );
+            }
+            Some(_) => (),
+            None => return Err(CheckDynamicStateValidityError::PrimitiveTopologyMissing),
+        }
+    }
+
+    if dynamic.depth_test_enable && state.depth_test_enable.is_none() {
+        return Err(CheckDynamicStateValidityError::DepthTestEnableMissing);
+    }
+
+    if dynamic.depth_write_enable && state.depth_write_enable.is_none() {
+        return Err(CheckDynamicStateValidityError::DepthWriteEnableMissing);
+    }
+
+    Ok(())
+}
+
+/// Checks whether the extended dynamic states, such as the cull mode or the primitive topology,
+/// can be set on the given device.
+pub fn check_extended_dynamic_state(device: &Device) -> Result<(), CheckExtendedDynamicStateError> {
+    if !device.enabled_features().extended_dynamic_state {
+        return Err(CheckExtendedDynamicStateError::ExtendedDynamicStateFeatureNotEnabled);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when setting an extended dynamic state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckExtendedDynamicStateError {
+    /// The `extended_dynamic_state` feature must be enabled on the device.
+    ExtendedDynamicStateFeatureNotEnabled,
+}
+
+impl error::Error for CheckExtendedDynamicStateError {}
+
+impl fmt::Display for CheckExtendedDynamicStateError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckExtendedDynamicStateError::ExtendedDynamicStateFeatureNotEnabled => {
+                    "the `extended_dynamic_state` feature must be enabled on the device"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_extended_states;
+    use super::CheckDynamicStateValidityError;
+    use super::ExtendedDynamicFlags;
+    use super::ExtendedDynamicState;
+    use pipeline::input_assembly::PrimitiveTopology;
+    use pipeline::raster::CullMode;
+
+    #[test]
+    fn static_states_need_nothing() {
+        let dynamic = ExtendedDynamicFlags::default();
+        let state = ExtendedDynamicState::default();
+        assert!(check_extended_states(&dynamic, PrimitiveTopology::TriangleList, &state).is_ok());
+    }
+
+    #[test]
+    fn missing_state() {
+        let dynamic = ExtendedDynamicFlags {
+            cull_mode: true,
+            depth_write_enable: true,
+            ..ExtendedDynamicFlags::default()
+        };
+        let mut state = ExtendedDynamicState::default();
+
+        match check_extended_states(&dynamic, PrimitiveTopology::TriangleList, &state) {
+            Err(CheckDynamicStateValidityError::CullModeMissing) => (),
+            _ => panic!(),
+        }
+
+        state.cull_mode = Some(CullMode::Back);
+        match check_extended_states(&dynamic, PrimitiveTopology::TriangleList, &state) {
+            Err(CheckDynamicStateValidityError::DepthWriteEnableMissing) => (),
+            _ => panic!(),
+        }
+
+        state.depth_write_enable = Some(false);
+        assert!(check_extended_states(&dynamic, PrimitiveTopology::TriangleList, &state).is_ok());
+    }
+
+    #[test]
+    fn topology_class() {
+        let dynamic = ExtendedDynamicFlags {
+            primitive_topology: true,
+            ..ExtendedDynamicFlags::default()
+        };
+        let mut state = ExtendedDynamicState::default();
+
+        match check_extended_states(&dynamic, PrimitiveTopology::TriangleList, &state) {
+            Err(CheckDynamicStateValidityError::PrimitiveTopologyMissing) => (),
+            _ => panic!(),
+        }
+
+        state.primitive_topology = Some(PrimitiveTopology::TriangleStrip);
+        assert!(check_extended_states(&dynamic, PrimitiveTopology::TriangleList, &state).is_ok());
+
+        state.primitive_topology = Some(PrimitiveTopology::LineList);
+        match check_extended_states(&dynamic, PrimitiveTopology::TriangleList, &state) {
+            Err(CheckDynamicStateValidityError::PrimitiveTopologyClassMismatch { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,262 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use device::Device;
+use device::DeviceOwned;
+use sync::AccessFlagBits;
+use sync::Event;
+use sync::PipelineStages;
+use VulkanObject;
+
+/// Checks whether a `set_event` or `reset_event` command is valid.
+///
+/// # Panic
+///
+/// - Panics if the event was not created with `device`.
+///
+pub fn check_set_event(
+    device: &Device,
+    event: &Event,
+    stages: PipelineStages,
+) -> Result<(), CheckEventError> {
+    assert_eq!(event.device().internal_object(), device.internal_object());
+    check_stages(device, stages, false)?;
+    Ok(())
+}
+
+/// Checks whether a `wait_events` command is valid.
+///
+/// `source_stages` must be the union of the stages passed to the `set_event` commands of the
+/// events, and must contain the host stage if one of the events is set from the host. This isn't
+/// checked by this function.
+///
+/// # Panic
+///
+/// - Panics if one of the events was not created with `device`.
+///
+pub fn check_wait_events<'a, I>(
+    device: &Device,
+    events: I,
+    source_stages: PipelineStages,
+    source_access: AccessFlagBits,
+    destination_stages: PipelineStages,
+    destination_access: AccessFlagBits,
+) -> Result<(), CheckEventError>
+where
+    I: IntoIterator<Item = &'a Event>,
+{
+    let mut num_events = 0;
+    for event in events {
+        assert_eq!(event.device().internal_object(), device.internal_object());
+        num_events += 1;
+    }
+    if num_events == 0 {
+        return Err(CheckEventError::NoEvents);
+    }
+
+    check_stages(device, source_stages, true)?;
+    check_stages(device, destination_stages, false)?;
+
+    if !source_access.is_compatible_with(&source_stages) {
+        return Err(CheckEventError::SourceAccessNotSupportedByStages);
+    }
+    if !destination_access.is_compatible_with(&destination_stages) {
+        return Err(CheckEventError::DestinationAccessNotSupportedByStages);
+    }
+
+    Ok(())
+}
+
+fn check_stages(
+    device: &Device,
+    stages: PipelineStages,
+    allow_host: bool,
+) -> Result<(), CheckEventError> {
+    if stages == PipelineStages::none() {
+        return Err(CheckEventError::NoStages);
+    }
+
+    if stages.host && !allow_host {
+        return Err(CheckEventError::HostStage);
+    }
+
+    if stages.geometry_shader && !device.enabled_features().geometry_shader {
+        return Err(CheckEventError::GeometryShaderFeatureNotEnabled);
+    }
+
+    if (stages.tessellation_control_shader || stages.tessellation_evaluation_shader)
+        && !device.enabled_features().tessellation_shader
+    {
+        return Err(CheckEventError::TessellationShaderFeatureNotEnabled);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `set_event`, `reset_event` or `wait_events`
+/// command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckEventError {
+    /// No event was passed to `wait_events`.
+    NoEvents,
+    /// An event passed to `wait_events` is reset, or wasn't set by a previous `set_event` command,
+    /// by the host or by a command buffer that was submitted before.
+    EventNotSet,
+    /// An event passed to `wait_events` was last set by a command buffer of a different queue
+    /// family.
+    WrongQueueFamily,
+    /// At least one stage must be specified.
+    NoStages,
+    /// The host stage can only be used as a source stage of `wait_events`.
+    HostStage,
+    /// The geometry shader stage was specified, but the `geometry_shader` feature isn't enabled.
+    GeometryShaderFeatureNotEnabled,
+    /// A tessellation shader stage was specified, but the `tessellation_shader` feature isn't
+    /// enabled.
+    TessellationShaderFeatureNotEnabled,
+    /// The source access flags aren't supported by the source stages.
+    SourceAccessNotSupportedByStages,
+    /// The destination access flags aren't supported by the destination stages.
+    DestinationAccessNotSupportedByStages,
+}
+
+impl error::Error for CheckEventError {}
+
+impl fmt::Display for CheckEventError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckEventError::NoEvents => "no event was passed to `wait_events`",
+                CheckEventError::EventNotSet => {
+                    "an event passed to `wait_events` is reset, or wasn't set by a previous \
+                     `set_event` command, by the host or by a command buffer that was submitted \
+                     before"
+                }
+                CheckEventError::WrongQueueFamily => {
+                    "an event passed to `wait_events` was last set by a command buffer of a \
+                     different queue family"
+                }
+                CheckEventError::NoStages => "at least one stage must be specified",
+                CheckEventError::HostStage => {
+                    "the host stage can only be used as a source stage of `wait_events`"
+                }
+                CheckEventError::GeometryShaderFeatureNotEnabled => {
+                    "the geometry shader stage was specified, but the `geometry_shader` feature \
+                     isn't enabled"
+                }
+                CheckEventError::TessellationShaderFeatureNotEnabled => {
+                    "a tessellation shader stage was specified, but the `tessellation_shader` \
+                     feature isn't enabled"
+                }
+                CheckEventError::SourceAccessNotSupportedByStages => {
+                    "the source access flags aren't supported by the source stages"
+                }
+                CheckEventError::DestinationAccessNotSupportedByStages => {
+                    "the destination access flags aren't supported by the destination stages"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::iter;
+
+    fn transfer() -> PipelineStages {
+        PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        }
+    }
+
+    #[test]
+    fn stages() {
+        let (device, _) = gfx_dev_and_queue!();
+        let event = Event::alloc(device.clone()).unwrap();
+
+        assert_eq!(
+            check_set_event(&device, &event, PipelineStages::none()),
+            Err(CheckEventError::NoStages)
+        );
+
+        let host = PipelineStages {
+            host: true,
+            ..PipelineStages::none()
+        };
+        assert_eq!(
+            check_set_event(&device, &event, host),
+            Err(CheckEventError::HostStage)
+        );
+
+        // The host stage is allowed as a source stage of a wait.
+        assert_eq!(
+            check_wait_events(
+                &device,
+                iter::once(&event),
+                host,
+                AccessFlagBits {
+                    host_write: true,
+                    ..AccessFlagBits::none()
+                },
+                transfer(),
+                AccessFlagBits {
+                    transfer_read: true,
+                    ..AccessFlagBits::none()
+                },
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn no_events() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_eq!(
+            check_wait_events(
+                &device,
+                iter::empty(),
+                transfer(),
+                AccessFlagBits::none(),
+                transfer(),
+                AccessFlagBits::none(),
+            ),
+            Err(CheckEventError::NoEvents)
+        );
+    }
+
+    #[test]
+    fn access_not_supported() {
+        let (device, _) = gfx_dev_and_queue!();
+        let event = Event::alloc(device.clone()).unwrap();
+
+        assert_eq!(
+            check_wait_events(
+                &device,
+                iter::once(&event),
+                transfer(),
+                AccessFlagBits {
+                    shader_write: true,
+                    ..AccessFlagBits::none()
+                },
+                transfer(),
+                AccessFlagBits::none(),
+            ),
+            Err(CheckEventError::SourceAccessNotSupportedByStages)
+        );
+    }
+}
@@ -15,6 +15,7 @@ use buffer::TypedBufferAccess;
 use device::Device;
 use device::DeviceOwned;
 use pipeline::input_assembly::Index;
+use pipeline::input_assembly::IndexType;
 use VulkanObject;
 
 /// Checks whether an index buffer can be bound.
@@ -40,6 +41,14 @@ where
         return Err(CheckIndexBufferError::BufferMissingUsage);
     }
 
+    if I::ty() == IndexType::U8 {
+        if !device.loaded_extensions().ext_index_type_uint8
+            || !device.enabled_features().index_type_uint8
+        {
+            return Err(CheckIndexBufferError::UnsupportIndexType);
+        }
+    }
+
     // TODO: The sum of offset and the address of the range of VkDeviceMemory object that is
     //       backing buffer, must be a multiple of the type indicated by indexType
 
@@ -64,6 +73,9 @@ pub enum CheckIndexBufferError {
     /// The data or size must be 4-bytes aligned.
     WrongAlignment,
     /// The type of the indices is not supported by the device.
+    ///
+    /// Using `u8` indices requires the `VK_EXT_index_type_uint8` extension and the
+    /// `index_type_uint8` feature. Use `u16` indices instead if they aren't available.
     UnsupportIndexType,
 }
 
@@ -96,6 +108,7 @@ mod tests {
     use super::*;
     use buffer::BufferUsage;
     use buffer::CpuAccessibleBuffer;
+    use vk;
 
     #[test]
     fn num_indices() {
@@ -116,6 +129,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn index_widths() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::index_buffer(),
+            false,
+            0..200u8,
+        )
+        .unwrap();
+        match check_index_buffer(&device, &buffer) {
+            Err(CheckIndexBufferError::UnsupportIndexType) => (),
+            _ => panic!(),
+        }
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::index_buffer(),
+            false,
+            0..200u16,
+        )
+        .unwrap();
+        assert_eq!(
+            check_index_buffer(&device, &buffer).unwrap().num_indices,
+            200
+        );
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::index_buffer(),
+            false,
+            0..200u32,
+        )
+        .unwrap();
+        assert_eq!(
+            check_index_buffer(&device, &buffer).unwrap().num_indices,
+            200
+        );
+
+        assert_eq!(<u8 as Index>::ty() as u32, vk::INDEX_TYPE_UINT8_EXT);
+        assert_eq!(<u16 as Index>::ty() as u32, vk::INDEX_TYPE_UINT16);
+        assert_eq!(<u32 as Index>::ty() as u32, vk::INDEX_TYPE_UINT32);
+    }
+
     #[test]
     fn missing_usage() {
         let (device, queue) = gfx_dev_and_queue!();
@@ -0,0 +1,119 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use buffer::BufferAccess;
+use device::Device;
+use device::DeviceOwned;
+use VulkanObject;
+
+/// Checks whether a buffer can be used as the source of the parameters of an indirect command.
+///
+/// # Panic
+///
+/// - Panics if the buffer not created with `device`.
+///
+pub fn check_indirect_buffer<B>(device: &Device, buffer: &B) -> Result<(), CheckIndirectBufferError>
+where
+    B: ?Sized + BufferAccess,
+{
+    assert_eq!(
+        buffer.inner().buffer.device().internal_object(),
+        device.internal_object()
+    );
+
+    if !buffer.inner().buffer.usage_indirect_buffer() {
+        return Err(CheckIndirectBufferError::BufferMissingUsage);
+    }
+
+    if buffer.inner().offset % 4 != 0 {
+        return Err(CheckIndirectBufferError::WrongAlignment);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to use a buffer as an indirect buffer.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckIndirectBufferError {
+    /// The "indirect buffer" usage must be enabled on the buffer.
+    BufferMissingUsage,
+    /// The "storage buffer" usage must be enabled on a buffer that is written by a previous
+    /// dispatch.
+    BufferMissingStorageUsage,
+    /// The offset of the buffer must be 4-bytes aligned.
+    WrongAlignment,
+}
+
+impl error::Error for CheckIndirectBufferError {}
+
+impl fmt::Display for CheckIndirectBufferError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckIndirectBufferError::BufferMissingUsage => {
+                    "the indirect buffer usage must be enabled on the buffer"
+                }
+                CheckIndirectBufferError::BufferMissingStorageUsage => {
+                    "the storage buffer usage must be enabled on the buffer"
+                }
+                CheckIndirectBufferError::WrongAlignment => {
+                    "the offset of the buffer is not aligned to 4 bytes"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::DispatchIndirectCommand;
+
+    #[test]
+    fn missing_usage() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage::vertex_buffer(),
+            false,
+            DispatchIndirectCommand { x: 1, y: 1, z: 1 },
+        )
+        .unwrap();
+
+        match check_indirect_buffer(&device, &buffer) {
+            Err(CheckIndirectBufferError::BufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wrong_device() {
+        let (dev1, queue) = gfx_dev_and_queue!();
+        let (dev2, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_data(
+            dev1,
+            BufferUsage::all(),
+            false,
+            DispatchIndirectCommand { x: 1, y: 1, z: 1 },
+        )
+        .unwrap();
+
+        assert_should_panic!({
+            let _ = check_indirect_buffer(&dev2, &buffer);
+        });
+    }
+}
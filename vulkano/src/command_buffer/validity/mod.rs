@@ -9,32 +9,57 @@
 
 //! Functions that check the validity of commands.
 
-pub use self::blit_image::{check_blit_image, CheckBlitImageError};
+pub use self::blit_image::{check_blit_image, check_blit_image_layouts, CheckBlitImageError};
 pub use self::clear_color_image::{check_clear_color_image, CheckClearColorImageError};
+pub use self::copy_attachment_to_storage::{
+    check_copy_attachment_to_storage, CheckCopyAttachmentToStorageError,
+};
 pub use self::copy_buffer::{check_copy_buffer, CheckCopyBuffer, CheckCopyBufferError};
-pub use self::copy_image::{check_copy_image, CheckCopyImageError};
+pub use self::copy_image::{check_copy_image, check_copy_image_layouts, CheckCopyImageError};
 pub use self::copy_image_buffer::{
-    check_copy_buffer_image, CheckCopyBufferImageError, CheckCopyBufferImageTy,
+    check_copy_buffer_image, check_copy_buffer_image_layout, CheckCopyBufferImageError,
+    CheckCopyBufferImageTy,
+};
+pub use self::copy_query_pool_results::{
+    check_copy_query_pool_results, CheckCopyQueryPoolResultsError,
 };
 pub use self::descriptor_sets::{check_descriptor_sets_validity, CheckDescriptorSetsValidityError};
-pub use self::dispatch::{check_dispatch, CheckDispatchError};
-pub use self::dynamic_state::{check_dynamic_state_validity, CheckDynamicStateValidityError};
+pub use self::dispatch::{check_dispatch, dispatch_dimensions_for, CheckDispatchError};
+pub use self::dynamic_state::{
+    check_dynamic_state_validity, check_extended_dynamic_state,
+    check_extended_dynamic_state_validity, CheckDynamicStateValidityError,
+    CheckExtendedDynamicStateError, ExtendedDynamicState,
+};
+pub use self::event::{check_set_event, check_wait_events, CheckEventError};
 pub use self::fill_buffer::{check_fill_buffer, CheckFillBufferError};
 pub use self::index_buffer::{check_index_buffer, CheckIndexBuffer, CheckIndexBufferError};
-pub use self::push_constants::{check_push_constants_validity, CheckPushConstantsValidityError};
+pub use self::indirect_buffer::{check_indirect_buffer, CheckIndirectBufferError};
+pub use self::push_constants::{
+    check_push_constants_range, check_push_constants_validity, CheckPushConstantsRangeError,
+    CheckPushConstantsValidityError,
+};
+pub use self::secondary_render_pass::{check_secondary_render_pass, CheckSecondaryRenderPassError};
 pub use self::update_buffer::{check_update_buffer, CheckUpdateBufferError};
-pub use self::vertex_buffers::{check_vertex_buffers, CheckVertexBuffer, CheckVertexBufferError};
+pub use self::vertex_buffers::{
+    check_draw_vertex_buffer_strides, check_vertex_buffer_strides, check_vertex_buffers,
+    CheckVertexBuffer, CheckVertexBufferError, CheckVertexBufferStridesError,
+};
 
 mod blit_image;
 mod clear_color_image;
+mod copy_attachment_to_storage;
 mod copy_buffer;
 mod copy_image;
 mod copy_image_buffer;
+mod copy_query_pool_results;
 mod descriptor_sets;
 mod dispatch;
 mod dynamic_state;
+mod event;
 mod fill_buffer;
 mod index_buffer;
+mod indirect_buffer;
 mod push_constants;
+mod secondary_render_pass;
 mod update_buffer;
 mod vertex_buffers;
@@ -10,6 +10,7 @@
 use std::error;
 use std::fmt;
 
+use descriptor::descriptor::ShaderStages;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use descriptor::pipeline_layout::PipelineLayoutPushConstantsCompatible;
 
@@ -29,6 +30,119 @@ where
     Ok(())
 }
 
+/// Checks whether a partial update of the push constants is valid.
+///
+/// The range of bytes `offset .. offset + size` must be entirely covered, for each stage of
+/// `stages`, by a push constants range of the layout that includes this stage. Additionally,
+/// `stages` must include all the stages of the ranges of the layout that overlap this range of
+/// bytes.
+pub fn check_push_constants_range<Pl>(
+    pipeline_layout: &Pl,
+    stages: ShaderStages,
+    offset: u32,
+    size: u32,
+) -> Result<(), CheckPushConstantsRangeError>
+where
+    Pl: ?Sized + PipelineLayoutAbstract,
+{
+    if stages == ShaderStages::none() {
+        return Err(CheckPushConstantsRangeError::NoStages);
+    }
+
+    if size == 0 || offset % 4 != 0 || size % 4 != 0 {
+        return Err(CheckPushConstantsRangeError::OffsetOrSizeNotAligned);
+    }
+
+    let max = pipeline_layout
+        .device()
+        .physical_device()
+        .limits()
+        .max_push_constants_size();
+    let end = offset as u64 + size as u64;
+    if end > max as u64 {
+        return Err(CheckPushConstantsRangeError::MaxPushConstantsSizeExceeded {
+            end: end as usize,
+            max,
+        });
+    }
+
+    let (offset, end) = (offset as usize, end as usize);
+    let ranges = (0..pipeline_layout.num_push_constants_ranges())
+        .filter_map(|num| pipeline_layout.push_constants_range(num));
+
+    let mut covered = ShaderStages::none();
+    for range in ranges {
+        if range.offset >= end || range.offset + range.size <= offset {
+            continue;
+        }
+
+        if stages.is_superset_of(&range.stages).is_err() {
+            return Err(CheckPushConstantsRangeError::MissingStages);
+        }
+
+        if range.offset <= offset && range.offset + range.size >= end {
+            covered = covered | range.stages;
+        }
+    }
+
+    if covered.is_superset_of(&stages).is_err() {
+        return Err(CheckPushConstantsRangeError::RangeNotDeclared);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking a partial update of the push constants.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckPushConstantsRangeError {
+    /// No stage was specified.
+    NoStages,
+    /// The offset or the size is not a multiple of 4, or the size is 0.
+    OffsetOrSizeNotAligned,
+    /// The end of the updated range exceeds the `max_push_constants_size` limit.
+    MaxPushConstantsSizeExceeded {
+        /// End of the updated range, in bytes.
+        end: usize,
+        /// Value of the limit.
+        max: u32,
+    },
+    /// The pipeline layout doesn't declare a push constants range that covers the updated range
+    /// for each of the stages.
+    RangeNotDeclared,
+    /// A push constants range of the pipeline layout overlaps the updated range, but the stages
+    /// of the update don't include all of its stages.
+    MissingStages,
+}
+
+impl error::Error for CheckPushConstantsRangeError {}
+
+impl fmt::Display for CheckPushConstantsRangeError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckPushConstantsRangeError::NoStages => "no stage was specified",
+                CheckPushConstantsRangeError::OffsetOrSizeNotAligned => {
+                    "the offset or the size is not a multiple of 4, or the size is 0"
+                }
+                CheckPushConstantsRangeError::MaxPushConstantsSizeExceeded { .. } => {
+                    "the end of the updated range exceeds the `max_push_constants_size` limit"
+                }
+                CheckPushConstantsRangeError::RangeNotDeclared => {
+                    "the pipeline layout doesn't declare a push constants range that covers the \
+                     updated range for each of the stages"
+                }
+                CheckPushConstantsRangeError::MissingStages => {
+                    "a push constants range of the pipeline layout overlaps the updated range, \
+                     but the stages of the update don't include all of its stages"
+                }
+            }
+        )
+    }
+}
+
 /// Error that can happen when checking push constants validity.
 #[derive(Debug, Copy, Clone)]
 pub enum CheckPushConstantsValidityError {
@@ -52,3 +166,67 @@ impl fmt::Display for CheckPushConstantsValidityError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+
+    #[test]
+    fn partial_ranges() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vertex = ShaderStages {
+            vertex: true,
+            ..ShaderStages::none()
+        };
+        let fragment = ShaderStages {
+            fragment: true,
+            ..ShaderStages::none()
+        };
+
+        let layout = RuntimePipelineDesc::new(
+            Vec::<Vec<Option<DescriptorDesc>>>::new(),
+            vec![
+                PipelineLayoutDescPcRange {
+                    offset: 0,
+                    size: 16,
+                    stages: vertex,
+                },
+                PipelineLayoutDescPcRange {
+                    offset: 16,
+                    size: 16,
+                    stages: fragment,
+                },
+            ],
+        )
+        .unwrap()
+        .build(device)
+        .unwrap();
+
+        assert_eq!(
+            check_push_constants_range(&layout, fragment, 16, 16),
+            Ok(())
+        );
+        assert_eq!(check_push_constants_range(&layout, vertex, 4, 8), Ok(()));
+        assert_eq!(
+            check_push_constants_range(&layout, vertex, 16, 4),
+            Err(CheckPushConstantsRangeError::MissingStages)
+        );
+        assert_eq!(
+            check_push_constants_range(&layout, vertex | fragment, 16, 4),
+            Err(CheckPushConstantsRangeError::RangeNotDeclared)
+        );
+        assert_eq!(
+            check_push_constants_range(&layout, fragment, 18, 4),
+            Err(CheckPushConstantsRangeError::OffsetOrSizeNotAligned)
+        );
+        assert_eq!(
+            check_push_constants_range(&layout, ShaderStages::none(), 0, 4),
+            Err(CheckPushConstantsRangeError::NoStages)
+        );
+    }
+}
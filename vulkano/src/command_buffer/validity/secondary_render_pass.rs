@@ -0,0 +1,207 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use format::Format;
+use framebuffer::AttachmentDescription;
+use framebuffer::RenderPassDesc;
+
+/// Checks whether a secondary command buffer that inherits a render pass can be executed in the
+/// current subpass of a primary command buffer.
+///
+/// `render_pass` and `subpass` are the render pass and the subpass the primary command buffer is
+/// currently in. `secondary_attachments` and `secondary_subpass` are the attachments of the
+/// render pass and the subpass that the secondary command buffer was created with.
+pub fn check_secondary_render_pass<R>(
+    render_pass: &R,
+    subpass: u32,
+    secondary_attachments: &[AttachmentDescription],
+    secondary_subpass: u32,
+) -> Result<(), CheckSecondaryRenderPassError>
+where
+    R: ?Sized + RenderPassDesc,
+{
+    if subpass != secondary_subpass {
+        return Err(CheckSecondaryRenderPassError::SubpassIndexMismatch {
+            primary: subpass,
+            secondary: secondary_subpass,
+        });
+    }
+
+    if render_pass.num_attachments() != secondary_attachments.len() {
+        return Err(CheckSecondaryRenderPassError::AttachmentCountMismatch {
+            primary: render_pass.num_attachments(),
+            secondary: secondary_attachments.len(),
+        });
+    }
+
+    for (num, secondary) in secondary_attachments.iter().enumerate() {
+        let primary = render_pass.attachment_desc(num).unwrap();
+
+        if primary.format != secondary.format {
+            return Err(CheckSecondaryRenderPassError::AttachmentFormatMismatch {
+                attachment: num,
+                primary: primary.format,
+                secondary: secondary.format,
+            });
+        }
+
+        if primary.samples != secondary.samples {
+            return Err(CheckSecondaryRenderPassError::AttachmentSamplesMismatch {
+                attachment: num,
+                primary: primary.samples,
+                secondary: secondary.samples,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when executing a secondary command buffer inside a render pass.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckSecondaryRenderPassError {
+    /// The secondary command buffer was created for a different subpass than the current one.
+    SubpassIndexMismatch {
+        /// Index of the current subpass of the primary command buffer.
+        primary: u32,
+        /// Index of the subpass of the secondary command buffer.
+        secondary: u32,
+    },
+    /// The render passes don't have the same number of attachments.
+    AttachmentCountMismatch {
+        /// Number of attachments of the render pass of the primary command buffer.
+        primary: usize,
+        /// Number of attachments of the render pass of the secondary command buffer.
+        secondary: usize,
+    },
+    /// An attachment doesn't have the same format in both render passes.
+    AttachmentFormatMismatch {
+        /// Index of the attachment.
+        attachment: usize,
+        /// Format of the attachment in the render pass of the primary command buffer.
+        primary: Format,
+        /// Format of the attachment in the render pass of the secondary command buffer.
+        secondary: Format,
+    },
+    /// An attachment doesn't have the same number of samples in both render passes.
+    AttachmentSamplesMismatch {
+        /// Index of the attachment.
+        attachment: usize,
+        /// Number of samples of the attachment in the render pass of the primary command buffer.
+        primary: u32,
+        /// Number of samples of the attachment in the render pass of the secondary command
+        /// buffer.
+        secondary: u32,
+    },
+}
+
+impl error::Error for CheckSecondaryRenderPassError {}
+
+impl fmt::Display for CheckSecondaryRenderPassError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckSecondaryRenderPassError::SubpassIndexMismatch { .. } => {
+                    "the secondary command buffer was created for a different subpass than the \
+                     current one"
+                }
+                CheckSecondaryRenderPassError::AttachmentCountMismatch { .. } => {
+                    "the render passes of the primary and secondary command buffers don't have the \
+                     same number of attachments"
+                }
+                CheckSecondaryRenderPassError::AttachmentFormatMismatch { .. } => {
+                    "an attachment doesn't have the same format in the render passes of the \
+                     primary and secondary command buffers"
+                }
+                CheckSecondaryRenderPassError::AttachmentSamplesMismatch { .. } => {
+                    "an attachment doesn't have the same number of samples in the render passes \
+                     of the primary and secondary command buffers"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use framebuffer::RenderPassDesc;
+
+    #[test]
+    fn compatibility() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = ::single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        let other = ::single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: DontCare,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 4,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        let same = rp.attachment_descs().collect::<Vec<_>>();
+        check_secondary_render_pass(&rp, 0, &same, 0).unwrap();
+
+        match check_secondary_render_pass(&rp, 0, &same, 1) {
+            Err(CheckSecondaryRenderPassError::SubpassIndexMismatch {
+                primary: 0,
+                secondary: 1,
+            }) => (),
+            _ => panic!(),
+        }
+
+        let different = other.attachment_descs().collect::<Vec<_>>();
+        match check_secondary_render_pass(&rp, 0, &different, 0) {
+            Err(CheckSecondaryRenderPassError::AttachmentSamplesMismatch {
+                attachment: 0,
+                primary: 1,
+                secondary: 4,
+            }) => (),
+            _ => panic!(),
+        }
+
+        match check_secondary_render_pass(&rp, 0, &[], 0) {
+            Err(CheckSecondaryRenderPassError::AttachmentCountMismatch {
+                primary: 1,
+                secondary: 0,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -13,6 +13,7 @@ use std::fmt;
 use buffer::BufferAccess;
 use device::DeviceOwned;
 use pipeline::vertex::VertexSource;
+use pipeline::GraphicsPipelineAbstract;
 use VulkanObject;
 
 /// Checks whether vertex buffers can be bound.
@@ -48,6 +49,100 @@ where
     })
 }
 
+/// Checks whether the strides passed to `bind_vertex_buffers_with_stride` are valid.
+pub fn check_vertex_buffer_strides<P>(
+    pipeline: &P,
+    num_buffers: usize,
+    strides: &[u32],
+) -> Result<(), CheckVertexBufferStridesError>
+where
+    P: ?Sized + GraphicsPipelineAbstract,
+{
+    if !pipeline.device().enabled_features().extended_dynamic_state {
+        return Err(CheckVertexBufferStridesError::ExtendedDynamicStateFeatureNotEnabled);
+    }
+
+    if !pipeline.has_dynamic_vertex_input_binding_stride() {
+        return Err(CheckVertexBufferStridesError::StridesNotDynamic);
+    }
+
+    if strides.len() != num_buffers {
+        return Err(CheckVertexBufferStridesError::StrideCountMismatch {
+            buffers: num_buffers,
+            strides: strides.len(),
+        });
+    }
+
+    let max = pipeline
+        .device()
+        .physical_device()
+        .limits()
+        .max_vertex_input_binding_stride();
+    for (num_buffer, &stride) in strides.iter().enumerate() {
+        if stride > max {
+            return Err(CheckVertexBufferStridesError::StrideTooLarge {
+                num_buffer,
+                stride,
+                max,
+            });
+        }
+    }
+
+    check_strides_fit_attributes(strides, |binding| {
+        pipeline.min_vertex_input_binding_stride(binding)
+    })
+}
+
+/// Checks whether the vertex buffer strides that are in effect can be used by a draw command
+/// with `pipeline`.
+///
+/// `strides` must be the strides passed to the last `bind_vertex_buffers_with_stride` if the
+/// vertex buffers of the draw command are the ones that it bound, or `None` otherwise. Nothing is
+/// checked if the strides of `pipeline` aren't dynamic.
+pub fn check_draw_vertex_buffer_strides<P>(
+    pipeline: &P,
+    strides: Option<&[u32]>,
+) -> Result<(), CheckVertexBufferStridesError>
+where
+    P: ?Sized + GraphicsPipelineAbstract,
+{
+    if !pipeline.has_dynamic_vertex_input_binding_stride() {
+        return Ok(());
+    }
+
+    let strides = match strides {
+        Some(strides) => strides,
+        None => return Err(CheckVertexBufferStridesError::StridesNotSet),
+    };
+
+    check_strides_fit_attributes(strides, |binding| {
+        pipeline.min_vertex_input_binding_stride(binding)
+    })
+}
+
+// Checks that each stride is either 0 or large enough for the vertex attributes of its binding.
+// `min_stride` returns the number of bytes read by the attributes of a binding.
+fn check_strides_fit_attributes<F>(
+    strides: &[u32],
+    min_stride: F,
+) -> Result<(), CheckVertexBufferStridesError>
+where
+    F: Fn(u32) -> u32,
+{
+    for (num_buffer, &stride) in strides.iter().enumerate() {
+        let min = min_stride(num_buffer as u32);
+        if stride != 0 && stride < min {
+            return Err(CheckVertexBufferStridesError::StrideTooSmall {
+                num_buffer,
+                stride,
+                min,
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// Information returned if `check_vertex_buffer` succeeds.
 pub struct CheckVertexBuffer {
     /// The list of vertex buffers.
@@ -84,3 +179,103 @@ impl fmt::Display for CheckVertexBufferError {
         )
     }
 }
+
+/// Error that can happen when checking whether the strides of vertex buffers are valid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckVertexBufferStridesError {
+    /// The `extended_dynamic_state` feature must be enabled in order to set the strides of
+    /// vertex buffers.
+    ExtendedDynamicStateFeatureNotEnabled,
+    /// The pipeline wasn't created with dynamic vertex buffer strides.
+    StridesNotDynamic,
+    /// The number of strides doesn't match the number of vertex buffers.
+    StrideCountMismatch {
+        /// Number of vertex buffers.
+        buffers: usize,
+        /// Number of strides.
+        strides: usize,
+    },
+    /// A stride exceeds the `max_vertex_input_binding_stride` limit.
+    StrideTooLarge {
+        /// Index of the buffer whose stride is too large.
+        num_buffer: usize,
+        /// The stride.
+        stride: u32,
+        /// Value of the limit.
+        max: u32,
+    },
+    /// A stride is smaller than the number of bytes read by the vertex attributes of its
+    /// binding.
+    StrideTooSmall {
+        /// Index of the buffer whose stride is too small.
+        num_buffer: usize,
+        /// The stride.
+        stride: u32,
+        /// Number of bytes read by the vertex attributes.
+        min: u32,
+    },
+    /// The pipeline has dynamic vertex buffer strides, but the vertex buffers of the draw
+    /// command weren't bound with `bind_vertex_buffers_with_stride`.
+    StridesNotSet,
+}
+
+impl error::Error for CheckVertexBufferStridesError {}
+
+impl fmt::Display for CheckVertexBufferStridesError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                CheckVertexBufferStridesError::ExtendedDynamicStateFeatureNotEnabled => {
+                    "the `extended_dynamic_state` feature must be enabled in order to set the \
+                     strides of vertex buffers"
+                }
+                CheckVertexBufferStridesError::StridesNotDynamic => {
+                    "the pipeline wasn't created with dynamic vertex buffer strides"
+                }
+                CheckVertexBufferStridesError::StrideCountMismatch { .. } => {
+                    "the number of strides doesn't match the number of vertex buffers"
+                }
+                CheckVertexBufferStridesError::StrideTooLarge { .. } => {
+                    "a stride exceeds the `max_vertex_input_binding_stride` limit"
+                }
+                CheckVertexBufferStridesError::StrideTooSmall { .. } => {
+                    "a stride is smaller than the number of bytes read by the vertex attributes of \
+                     its binding"
+                }
+                CheckVertexBufferStridesError::StridesNotSet => {
+                    "the pipeline has dynamic vertex buffer strides, but the vertex buffers \
+                     weren't bound with strides"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_strides_fit_attributes;
+    use super::CheckVertexBufferStridesError;
+
+    #[test]
+    fn strides_fit_attributes() {
+        let min_stride = |binding| match binding {
+            0 => 12,
+            _ => 0,
+        };
+
+        assert_eq!(check_strides_fit_attributes(&[12, 4], min_stride), Ok(()));
+        assert_eq!(check_strides_fit_attributes(&[0, 0], min_stride), Ok(()));
+        assert_eq!(check_strides_fit_attributes(&[16, 4], min_stride), Ok(()));
+        assert_eq!(
+            check_strides_fit_attributes(&[8], min_stride),
+            Err(CheckVertexBufferStridesError::StrideTooSmall {
+                num_buffer: 0,
+                stride: 8,
+                min: 12,
+            })
+        );
+    }
+}
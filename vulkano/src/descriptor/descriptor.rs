@@ -222,6 +222,24 @@ impl DescriptorDesc {
                     }
                 }
             }
+            DescriptorDescTy::Mutable(ref types) => {
+                // The union of the accesses of all the types that the descriptor can have.
+                let mut access = AccessFlagBits::none();
+                for ty in types {
+                    match *ty {
+                        DescriptorType::Sampler | DescriptorType::Mutable => (),
+                        DescriptorType::UniformBuffer | DescriptorType::UniformBufferDynamic => {
+                            access.uniform_read = true;
+                        }
+                        DescriptorType::InputAttachment => access.input_attachment_read = true,
+                        _ => {
+                            access.shader_read = true;
+                            access.shader_write |= !self.readonly;
+                        }
+                    }
+                }
+                access
+            }
         };
 
         (stages, access)
@@ -250,6 +268,11 @@ pub enum DescriptorDescTy {
         array_layers: DescriptorImageDescArray,
     },
     Buffer(DescriptorBufferDesc),
+    /// A descriptor whose type is chosen when it is written, among the given list of types.
+    ///
+    /// Requires the `ext_mutable_descriptor_type` extension and the `mutable_descriptor_type`
+    /// feature.
+    Mutable(Vec<DescriptorType>),
 }
 
 impl DescriptorDescTy {
@@ -288,6 +311,7 @@ impl DescriptorDescTy {
                     DescriptorType::UniformTexelBuffer
                 }
             }
+            DescriptorDescTy::Mutable(_) => DescriptorType::Mutable,
         })
     }
 
@@ -390,6 +414,20 @@ impl DescriptorDescTy {
                 }
             }
 
+            (&DescriptorDescTy::Mutable(ref me), &DescriptorDescTy::Mutable(ref other)) => {
+                if other.iter().all(|ty| me.contains(ty)) {
+                    Ok(())
+                } else {
+                    Err(DescriptorDescSupersetError::TypeMismatch)
+                }
+            }
+
+            // A mutable descriptor can hold any of the types of its list.
+            (&DescriptorDescTy::Mutable(ref me), other) => match other.ty() {
+                Some(ty) if me.contains(&ty) => Ok(()),
+                _ => Err(DescriptorDescSupersetError::TypeMismatch),
+            },
+
             // Any other combination is invalid.
             _ => Err(DescriptorDescSupersetError::TypeMismatch),
         }
@@ -553,6 +591,7 @@ pub enum DescriptorType {
     UniformBufferDynamic = vk::DESCRIPTOR_TYPE_UNIFORM_BUFFER_DYNAMIC,
     StorageBufferDynamic = vk::DESCRIPTOR_TYPE_STORAGE_BUFFER_DYNAMIC,
     InputAttachment = vk::DESCRIPTOR_TYPE_INPUT_ATTACHMENT,
+    Mutable = vk::DESCRIPTOR_TYPE_MUTABLE_EXT,
 }
 
 /// Error when checking whether a descriptor is a superset of another one.
@@ -747,6 +786,19 @@ impl ShaderStages {
             || (self.compute && other.compute)
     }
 
+    /// Returns the stages of `self` that aren't present in `other`.
+    #[inline]
+    pub fn difference(&self, other: &ShaderStages) -> ShaderStages {
+        ShaderStages {
+            vertex: self.vertex && !other.vertex,
+            tessellation_control: self.tessellation_control && !other.tessellation_control,
+            tessellation_evaluation: self.tessellation_evaluation && !other.tessellation_evaluation,
+            geometry: self.geometry && !other.geometry,
+            fragment: self.fragment && !other.fragment,
+            compute: self.compute && !other.compute,
+        }
+    }
+
     #[inline]
     pub(crate) fn into_vulkan_bits(self) -> vk::ShaderStageFlags {
         let mut result = 0;
@@ -770,6 +822,18 @@ impl ShaderStages {
         }
         result
     }
+
+    #[inline]
+    pub(crate) fn from_vulkan_bits(bits: vk::ShaderStageFlags) -> ShaderStages {
+        ShaderStages {
+            vertex: (bits & vk::SHADER_STAGE_VERTEX_BIT) != 0,
+            tessellation_control: (bits & vk::SHADER_STAGE_TESSELLATION_CONTROL_BIT) != 0,
+            tessellation_evaluation: (bits & vk::SHADER_STAGE_TESSELLATION_EVALUATION_BIT) != 0,
+            geometry: (bits & vk::SHADER_STAGE_GEOMETRY_BIT) != 0,
+            fragment: (bits & vk::SHADER_STAGE_FRAGMENT_BIT) != 0,
+            compute: (bits & vk::SHADER_STAGE_COMPUTE_BIT) != 0,
+        }
+    }
 }
 
 impl BitOr for ShaderStages {
@@ -823,3 +887,46 @@ impl fmt::Display for ShaderStagesSupersetError {
         )
     }
 }
+
+/// Additional properties of a binding of a descriptor set layout.
+///
+/// Setting any of these flags requires the `ext_descriptor_indexing` extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DescriptorBindingFlags {
+    /// `True` means that the array elements of the descriptor don't all need to be written, as
+    /// long as the elements that are not written aren't dynamically used by the shader. Requires
+    /// the `descriptor_binding_partially_bound` feature.
+    pub partially_bound: bool,
+
+    /// `True` means that the descriptor can be updated after the descriptor set has been bound
+    /// in a command buffer, as long as the command buffer isn't submitted yet. Requires the
+    /// `descriptor_binding_*_update_after_bind` feature corresponding to the type of the
+    /// descriptor. Dynamic buffers and input attachments can't be updated after being bound.
+    ///
+    /// The descriptors of a layout with such a binding count against the limits of
+    /// `UpdateAfterBindLimits` instead of the regular limits.
+    pub update_after_bind: bool,
+}
+
+impl DescriptorBindingFlags {
+    /// Creates a `DescriptorBindingFlags` struct with all flags set to `false`.
+    #[inline]
+    pub fn none() -> DescriptorBindingFlags {
+        DescriptorBindingFlags {
+            partially_bound: false,
+            update_after_bind: false,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::DescriptorBindingFlagsEXT {
+        let mut result = 0;
+        if self.partially_bound {
+            result |= vk::DESCRIPTOR_BINDING_PARTIALLY_BOUND_BIT_EXT;
+        }
+        if self.update_after_bind {
+            result |= vk::DESCRIPTOR_BINDING_UPDATE_AFTER_BIND_BIT_EXT;
+        }
+        result
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+
+use descriptor::descriptor::DescriptorBindingFlags;
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorType;
+use descriptor::descriptor_set::unsafe_layout::update_after_bind_feature;
+use descriptor::descriptor_set::DescriptorSetLayoutError;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+use descriptor::pipeline_layout::exceeded_update_after_bind_set_limit;
+use descriptor::pipeline_layout::DescriptorCounts;
+use device::Device;
+use features::Features;
+
+/// Builds a descriptor set layout whose array bindings adapt to the capabilities of the device.
+///
+/// Each binding added with `bindless_array` is either created as a large *bindless* array, if
+/// the device supports it, or as a smaller fixed-size array otherwise. A bindless array is
+/// partially bound and update-after-bind, which requires the `ext_descriptor_indexing` extension,
+/// the `descriptor_binding_partially_bound` feature, and for the type of the descriptor the
+/// `descriptor_binding_*_update_after_bind` and `shader_*_array_non_uniform_indexing` features.
+/// If the bindless arrays together exceed the update-after-bind limits of the device, they all
+/// fall back to fixed-size arrays.
+///
+/// Since the shaders must be written for the strategy that was chosen, the strategy of each
+/// binding can be queried on the built `AdaptiveDescriptorSetLayout`.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::descriptor::descriptor::DescriptorDesc;
+/// use vulkano::descriptor::descriptor::DescriptorDescTy;
+/// use vulkano::descriptor::descriptor::DescriptorImageDesc;
+/// use vulkano::descriptor::descriptor::DescriptorImageDescArray;
+/// use vulkano::descriptor::descriptor::DescriptorImageDescDimensions;
+/// use vulkano::descriptor::descriptor::ShaderStages;
+/// use vulkano::descriptor::descriptor_set::AdaptiveDescriptorSetLayoutBuilder;
+/// use vulkano::descriptor::descriptor_set::BindingStrategy;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+///
+/// let textures = DescriptorDesc {
+///     ty: DescriptorDescTy::CombinedImageSampler(DescriptorImageDesc {
+///         sampled: true,
+///         dimensions: DescriptorImageDescDimensions::TwoDimensional,
+///         format: None,
+///         multisampled: false,
+///         array_layers: DescriptorImageDescArray::NonArrayed,
+///     }),
+///     array_count: 4096,
+///     stages: ShaderStages { fragment: true, .. ShaderStages::none() },
+///     readonly: true,
+/// };
+///
+/// let layout = AdaptiveDescriptorSetLayoutBuilder::new(device.clone())
+///     .bindless_array(textures, 16)
+///     .build()
+///     .unwrap();
+///
+/// match layout.strategy(0) {
+///     Some(BindingStrategy::Bindless { .. }) => { /* use the bindless shaders */ }
+///     _ => { /* use the shaders with a fixed-size array */ }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AdaptiveDescriptorSetLayoutBuilder {
+    device: Arc<Device>,
+    bindings: Vec<Option<AdaptiveBinding>>,
+}
+
+#[derive(Debug)]
+struct AdaptiveBinding {
+    desc: DescriptorDesc,
+    flags: DescriptorBindingFlags,
+    // If `Some`, the binding is a bindless array of `desc.array_count` descriptors that falls
+    // back to an array of this number of descriptors.
+    fallback_array_count: Option<u32>,
+}
+
+impl AdaptiveDescriptorSetLayoutBuilder {
+    /// Starts building a descriptor set layout with no bindings.
+    #[inline]
+    pub fn new(device: Arc<Device>) -> AdaptiveDescriptorSetLayoutBuilder {
+        AdaptiveDescriptorSetLayoutBuilder {
+            device,
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Adds a binding that is always created as described by `desc`, whatever the capabilities
+    /// of the device.
+    #[inline]
+    pub fn binding(self, desc: DescriptorDesc) -> AdaptiveDescriptorSetLayoutBuilder {
+        self.binding_with_flags(desc, DescriptorBindingFlags::none())
+    }
+
+    /// Same as `binding`, but with binding flags. The flags are checked when the layout is
+    /// built, as with `UnsafeDescriptorSetLayout::with_binding_flags`.
+    #[inline]
+    pub fn binding_with_flags(
+        mut self,
+        desc: DescriptorDesc,
+        flags: DescriptorBindingFlags,
+    ) -> AdaptiveDescriptorSetLayoutBuilder {
+        self.bindings.push(Some(AdaptiveBinding {
+            desc,
+            flags,
+            fallback_array_count: None,
+        }));
+        self
+    }
+
+    /// Adds an empty binding.
+    #[inline]
+    pub fn empty_binding(mut self) -> AdaptiveDescriptorSetLayoutBuilder {
+        self.bindings.push(None);
+        self
+    }
+
+    /// Adds a binding that is a bindless array of `desc.array_count` descriptors if the device
+    /// supports it, or an array of `fallback_array_count` descriptors otherwise.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `fallback_array_count` is 0 or greater than `desc.array_count`.
+    ///
+    #[inline]
+    pub fn bindless_array(
+        mut self,
+        desc: DescriptorDesc,
+        fallback_array_count: u32,
+    ) -> AdaptiveDescriptorSetLayoutBuilder {
+        assert!(fallback_array_count != 0);
+        assert!(fallback_array_count <= desc.array_count);
+
+        self.bindings.push(Some(AdaptiveBinding {
+            desc,
+            flags: DescriptorBindingFlags::none(),
+            fallback_array_count: Some(fallback_array_count),
+        }));
+        self
+    }
+
+    /// Chooses the strategy of each binding and builds the layout.
+    pub fn build(self) -> Result<AdaptiveDescriptorSetLayout, DescriptorSetLayoutError> {
+        let bindless = bindless_supported(&self.device, &self.bindings);
+
+        let mut descriptors = Vec::with_capacity(self.bindings.len());
+        let mut binding_flags = Vec::with_capacity(self.bindings.len());
+        let mut strategies = Vec::with_capacity(self.bindings.len());
+
+        for (binding, &supported) in self.bindings.into_iter().zip(bindless.iter()) {
+            let binding = match binding {
+                Some(binding) => binding,
+                None => {
+                    descriptors.push(None);
+                    binding_flags.push(DescriptorBindingFlags::none());
+                    strategies.push(None);
+                    continue;
+                }
+            };
+
+            let mut desc = binding.desc;
+            let mut flags = binding.flags;
+
+            let strategy = match binding.fallback_array_count {
+                Some(_) if supported => {
+                    flags.partially_bound = true;
+                    flags.update_after_bind = true;
+                    BindingStrategy::Bindless {
+                        array_count: desc.array_count,
+                    }
+                }
+                Some(fallback_array_count) => {
+                    desc.array_count = fallback_array_count;
+                    BindingStrategy::FixedSize {
+                        array_count: fallback_array_count,
+                    }
+                }
+                None => BindingStrategy::FixedSize {
+                    array_count: desc.array_count,
+                },
+            };
+
+            descriptors.push(Some(desc));
+            binding_flags.push(flags);
+            strategies.push(Some(strategy));
+        }
+
+        let layout =
+            UnsafeDescriptorSetLayout::with_binding_flags(self.device, descriptors, binding_flags)?;
+
+        Ok(AdaptiveDescriptorSetLayout {
+            layout: Arc::new(layout),
+            strategies,
+        })
+    }
+}
+
+/// A descriptor set layout built by an `AdaptiveDescriptorSetLayoutBuilder`, along with the
+/// strategy that was chosen for each of its bindings.
+#[derive(Debug)]
+pub struct AdaptiveDescriptorSetLayout {
+    layout: Arc<UnsafeDescriptorSetLayout>,
+    strategies: Vec<Option<BindingStrategy>>,
+}
+
+impl AdaptiveDescriptorSetLayout {
+    /// Returns the layout.
+    #[inline]
+    pub fn layout(&self) -> &Arc<UnsafeDescriptorSetLayout> {
+        &self.layout
+    }
+
+    /// Returns the strategy that was chosen for a binding, or `None` if the binding is empty or
+    /// out of range.
+    #[inline]
+    pub fn strategy(&self, binding: usize) -> Option<BindingStrategy> {
+        self.strategies.get(binding).cloned().unwrap_or(None)
+    }
+
+    /// Returns true if at least one binding is a bindless array. In that case, the descriptor
+    /// sets must be allocated from an update-after-bind pool.
+    #[inline]
+    pub fn is_bindless(&self) -> bool {
+        self.strategies.iter().any(|strategy| match *strategy {
+            Some(BindingStrategy::Bindless { .. }) => true,
+            _ => false,
+        })
+    }
+}
+
+/// Strategy chosen for a binding of an `AdaptiveDescriptorSetLayout`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BindingStrategy {
+    /// The binding is a partially bound and update-after-bind array. Its elements don't all
+    /// need to be written, and the shaders can index it with non-uniform values.
+    Bindless {
+        /// Number of elements of the array.
+        array_count: u32,
+    },
+    /// The binding is a regular array whose elements must all be written before it is used.
+    FixedSize {
+        /// Number of elements of the array.
+        array_count: u32,
+    },
+}
+
+// Returns, for each binding, whether it can be created as a bindless array. All the bindless
+// arrays fall back together if they don't fit in the update-after-bind limits.
+fn bindless_supported(device: &Device, bindings: &[Option<AdaptiveBinding>]) -> Vec<bool> {
+    let features = device.enabled_features();
+    let device_supported = device.loaded_extensions().ext_descriptor_indexing
+        && features.descriptor_binding_partially_bound;
+
+    let mut supported: Vec<bool> = bindings
+        .iter()
+        .map(|binding| match *binding {
+            Some(AdaptiveBinding {
+                ref desc,
+                fallback_array_count: Some(_),
+                ..
+            }) => {
+                device_supported
+                    && desc.ty.ty().map_or(false, |ty| {
+                        update_after_bind_feature(features, ty) == Some(true)
+                            && non_uniform_indexing_feature(features, ty)
+                    })
+            }
+            _ => false,
+        })
+        .collect();
+
+    if supported.iter().any(|&s| s) {
+        let mut counts = DescriptorCounts::default();
+        for (binding, &supported) in bindings.iter().zip(supported.iter()) {
+            if let Some(ref binding) = *binding {
+                match binding.fallback_array_count {
+                    Some(fallback_array_count) if !supported => {
+                        counts.add(&DescriptorDesc {
+                            array_count: fallback_array_count,
+                            ..binding.desc.clone()
+                        });
+                    }
+                    _ => counts.add(&binding.desc),
+                }
+            }
+        }
+
+        let fits = match *device
+            .physical_device()
+            .extended_properties()
+            .update_after_bind_limits()
+        {
+            Some(ref limits) => exceeded_update_after_bind_set_limit(&counts, limits).is_none(),
+            None => false,
+        };
+
+        if !fits {
+            for s in supported.iter_mut() {
+                *s = false;
+            }
+        }
+    }
+
+    supported
+}
+
+// Returns whether the shaders can index arrays of descriptors of type `ty` with non-uniform
+// values.
+fn non_uniform_indexing_feature(features: &Features, ty: DescriptorType) -> bool {
+    match ty {
+        DescriptorType::Sampler
+        | DescriptorType::CombinedImageSampler
+        | DescriptorType::SampledImage => features.shader_sampled_image_array_non_uniform_indexing,
+        DescriptorType::StorageImage => features.shader_storage_image_array_non_uniform_indexing,
+        DescriptorType::UniformTexelBuffer => {
+            features.shader_uniform_texel_buffer_array_non_uniform_indexing
+        }
+        DescriptorType::StorageTexelBuffer => {
+            features.shader_storage_texel_buffer_array_non_uniform_indexing
+        }
+        DescriptorType::UniformBuffer => features.shader_uniform_buffer_array_non_uniform_indexing,
+        DescriptorType::StorageBuffer => features.shader_storage_buffer_array_non_uniform_indexing,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::AdaptiveDescriptorSetLayoutBuilder;
+    use descriptor::descriptor_set::BindingStrategy;
+    use descriptor::descriptor_set::DescriptorSetDesc;
+
+    #[test]
+    fn fallback_without_descriptor_indexing() {
+        // `gfx_dev_and_queue!` doesn't enable `ext_descriptor_indexing`.
+        let (device, _) = gfx_dev_and_queue!();
+
+        let storage_buffers = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(false),
+                storage: true,
+            }),
+            array_count: 1024,
+            stages: ShaderStages::all(),
+            readonly: true,
+        };
+
+        let layout = AdaptiveDescriptorSetLayoutBuilder::new(device)
+            .empty_binding()
+            .bindless_array(storage_buffers, 8)
+            .build()
+            .unwrap();
+
+        assert_eq!(layout.strategy(0), None);
+        assert_eq!(
+            layout.strategy(1),
+            Some(BindingStrategy::FixedSize { array_count: 8 })
+        );
+        assert!(!layout.is_bindless());
+        assert_eq!(layout.layout().descriptor(1).unwrap().array_count, 8);
+        assert!(!layout.layout().is_update_after_bind_pool());
+    }
+}
@@ -261,6 +261,10 @@ unsafe impl DescriptorPool for LocalPool {
                         unreachable!()
                     }
                     Err(DescriptorPoolAllocError::OutOfPoolMemory) => unreachable!(),
+                    // The pool is created with exactly enough sets.
+                    Err(DescriptorPoolAllocError::DescriptorPoolExhausted { .. }) => {
+                        unreachable!()
+                    }
                 }
             };
 
@@ -491,6 +495,19 @@ impl<'a, R> FixedSizeDescriptorSetBuilderArray<'a, R> {
         })
     }
 
+    /// Leaves the next `count` elements of the array unwritten.
+    ///
+    /// This is only allowed if the binding was created as partially bound.
+    pub fn skip(
+        self,
+        count: u32,
+    ) -> Result<FixedSizeDescriptorSetBuilderArray<'a, R>, PersistentDescriptorSetError> {
+        Ok(FixedSizeDescriptorSetBuilderArray {
+            pool: self.pool,
+            inner: self.inner.skip(count)?,
+        })
+    }
+
     /// Binds a buffer as the next element in the array.
     ///
     /// An error is returned if the buffer isn't compatible with the descriptor.
@@ -39,17 +39,23 @@ use std::hash::Hash;
 use std::hash::Hasher;
 
 use buffer::BufferAccess;
+use descriptor::descriptor::DescriptorBufferDesc;
 use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
 use device::DeviceOwned;
 use image::ImageViewAccess;
 use SafeDeref;
 use VulkanObject;
 
+pub use self::adaptive_layout::AdaptiveDescriptorSetLayout;
+pub use self::adaptive_layout::AdaptiveDescriptorSetLayoutBuilder;
+pub use self::adaptive_layout::BindingStrategy;
 pub use self::collection::DescriptorSetsCollection;
 pub use self::fixed_size_pool::FixedSizeDescriptorSet;
 pub use self::fixed_size_pool::FixedSizeDescriptorSetBuilder;
 pub use self::fixed_size_pool::FixedSizeDescriptorSetBuilderArray;
 pub use self::fixed_size_pool::FixedSizeDescriptorSetsPool;
+pub use self::per_frame::PerFrameDescriptorSet;
 pub use self::persistent::PersistentDescriptorSet;
 pub use self::persistent::PersistentDescriptorSetBuf;
 pub use self::persistent::PersistentDescriptorSetBufView;
@@ -59,21 +65,28 @@ pub use self::persistent::PersistentDescriptorSetBuilderArray;
 pub use self::persistent::PersistentDescriptorSetError;
 pub use self::persistent::PersistentDescriptorSetImg;
 pub use self::persistent::PersistentDescriptorSetSampler;
+pub use self::persistent::PersistentDescriptorSetUpdate;
 pub use self::std_pool::StdDescriptorPool;
 pub use self::std_pool::StdDescriptorPoolAlloc;
 pub use self::sys::DescriptorPool;
 pub use self::sys::DescriptorPoolAlloc;
 pub use self::sys::DescriptorPoolAllocError;
+pub use self::sys::DescriptorPoolCreationError;
 pub use self::sys::DescriptorWrite;
 pub use self::sys::DescriptorsCount;
 pub use self::sys::UnsafeDescriptorPool;
 pub use self::sys::UnsafeDescriptorPoolAllocIter;
 pub use self::sys::UnsafeDescriptorSet;
+pub use self::sys::UnsafeDescriptorSetUpdate;
+pub use self::unsafe_layout::DescriptorSetLayoutError;
+pub use self::unsafe_layout::DescriptorWriteError;
 pub use self::unsafe_layout::UnsafeDescriptorSetLayout;
 
 pub mod collection;
 
+mod adaptive_layout;
 mod fixed_size_pool;
+mod per_frame;
 mod persistent;
 mod std_pool;
 mod sys;
@@ -161,6 +174,54 @@ pub unsafe trait DescriptorSetDesc {
 
     /// Returns a description of a descriptor, or `None` if out of range.
     fn descriptor(&self, binding: usize) -> Option<DescriptorDesc>;
+
+    /// Returns the number of dynamic uniform and storage buffer descriptors in the set, taking
+    /// arrays into account.
+    ///
+    /// This is the number of dynamic offsets that must be provided when binding the set. Each
+    /// element of an array of dynamic buffers consumes one offset, so a binding with an
+    /// `array_count` of 4 consumes 4 offsets.
+    fn dynamic_descriptor_count(&self) -> u32 {
+        (0..self.num_bindings())
+            .filter_map(|binding| self.descriptor(binding))
+            .filter(|desc| match desc.ty {
+                DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                    dynamic: Some(true),
+                    ..
+                }) => true,
+                _ => false,
+            })
+            .map(|desc| desc.array_count)
+            .sum()
+    }
+
+    /// Returns the position of the dynamic offset of an element of a dynamic buffer descriptor
+    /// within the offsets of the set.
+    ///
+    /// Vulkan requires the dynamic offsets to be ordered by binding number, then by array
+    /// element. Returns `None` if the binding isn't a dynamic buffer or if `array_element` is out
+    /// of range.
+    fn dynamic_offset_index(&self, binding: usize, array_element: u32) -> Option<u32> {
+        let is_dynamic = |desc: &DescriptorDesc| match desc.ty {
+            DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(true),
+                ..
+            }) => true,
+            _ => false,
+        };
+
+        match self.descriptor(binding) {
+            Some(ref desc) if is_dynamic(desc) && array_element < desc.array_count => (),
+            _ => return None,
+        }
+
+        let previous: u32 = (0..binding)
+            .filter_map(|binding| self.descriptor(binding))
+            .filter(|desc| is_dynamic(desc))
+            .map(|desc| desc.array_count)
+            .sum();
+        Some(previous + array_element)
+    }
 }
 
 unsafe impl<T> DescriptorSetDesc for T
@@ -177,4 +238,14 @@ where
     fn descriptor(&self, binding: usize) -> Option<DescriptorDesc> {
         (**self).descriptor(binding)
     }
+
+    #[inline]
+    fn dynamic_descriptor_count(&self) -> u32 {
+        (**self).dynamic_descriptor_count()
+    }
+
+    #[inline]
+    fn dynamic_offset_index(&self, binding: usize, array_element: u32) -> Option<u32> {
+        (**self).dynamic_offset_index(binding, array_element)
+    }
 }
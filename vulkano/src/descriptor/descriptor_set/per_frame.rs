@@ -0,0 +1,194 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::fmt;
+use std::mem;
+
+use sync::FenceSignalFuture;
+use sync::FlushError;
+use sync::GpuFuture;
+
+/// One copy of a descriptor set per frame in flight.
+///
+/// When a descriptor set references a buffer that is updated every frame, the CPU can't write
+/// the buffer while the GPU is still reading the previous frame. The usual solution is to have
+/// one buffer and one descriptor set per frame in flight, and to wait until the GPU is done with
+/// a copy before reusing it. `PerFrameDescriptorSet` implements this pattern.
+///
+/// Each frame, call `next_frame` to get the copy of the set for this frame. This function waits
+/// for the fence of the last submission that used this copy, so that its resources can then be
+/// safely written to or replaced with `replace`. After submitting the frame, pass the future to
+/// `submitted`.
+///
+/// `S` is usually an `Arc` of a descriptor set, or a tuple of a descriptor set and the buffers
+/// it references.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::descriptor::descriptor_set::PerFrameDescriptorSet;
+/// # use std::sync::Arc;
+/// # use vulkano::descriptor::descriptor_set::DescriptorSet;
+/// # use vulkano::sync::GpuFuture;
+/// # let set1: Arc<dyn DescriptorSet + Send + Sync> = return;
+/// # let set2: Arc<dyn DescriptorSet + Send + Sync> = return;
+/// # let frame_future: Box<dyn GpuFuture + Send + Sync> = return;
+///
+/// let mut sets = PerFrameDescriptorSet::new(vec![set1, set2]);
+///
+/// // Every frame.
+/// let set = sets.next_frame().unwrap().clone();
+/// // Update the buffers referenced by `set`, then record and submit the frame.
+/// sets.submitted(frame_future).unwrap();
+/// ```
+pub struct PerFrameDescriptorSet<S> {
+    frames: Vec<Frame<S>>,
+    current: usize,
+}
+
+struct Frame<S> {
+    set: S,
+    fence: Option<FenceSignalFuture<Box<dyn GpuFuture + Send + Sync>>>,
+}
+
+impl<S> PerFrameDescriptorSet<S> {
+    /// Builds a `PerFrameDescriptorSet` with one copy per element of `sets`. The number of
+    /// elements is the number of frames in flight.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `sets` is empty.
+    ///
+    pub fn new<I>(sets: I) -> PerFrameDescriptorSet<S>
+    where
+        I: IntoIterator<Item = S>,
+    {
+        let frames: Vec<_> = sets
+            .into_iter()
+            .map(|set| Frame { set, fence: None })
+            .collect();
+        assert!(!frames.is_empty());
+
+        PerFrameDescriptorSet {
+            current: frames.len() - 1,
+            frames,
+        }
+    }
+
+    /// Returns the number of frames in flight, which is the number of copies of the set.
+    #[inline]
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the index of the current frame, between 0 and `frames_in_flight() - 1`.
+    #[inline]
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the copy of the set of the current frame.
+    #[inline]
+    pub fn current(&self) -> &S {
+        &self.frames[self.current].set
+    }
+
+    /// Moves to the next frame and returns its copy of the set.
+    ///
+    /// Blocks until the GPU has finished executing the last submission that was passed to
+    /// `submitted` for this copy.
+    pub fn next_frame(&mut self) -> Result<&S, FlushError> {
+        let next = (self.current + 1) % self.frames.len();
+
+        if let Some(fence) = self.frames[next].fence.take() {
+            fence.wait(None)?;
+        }
+
+        self.current = next;
+        Ok(&self.frames[next].set)
+    }
+
+    /// Replaces the copy of the set of the current frame, and returns the previous one.
+    ///
+    /// This is safe to call after `next_frame` and before `submitted`, since the GPU is then
+    /// guaranteed not to use the copy anymore.
+    #[inline]
+    pub fn replace(&mut self, set: S) -> S {
+        debug_assert!(self.frames[self.current].fence.is_none());
+        mem::replace(&mut self.frames[self.current].set, set)
+    }
+
+    /// Signals a fence after `future` and flushes it, then associates the fence to the copy of
+    /// the set of the current frame.
+    ///
+    /// `future` must be the future of the submission that uses the current copy.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `future` isn't tied to a queue, for example if it is a `NowFuture`.
+    ///
+    pub fn submitted<F>(&mut self, future: F) -> Result<(), FlushError>
+    where
+        F: GpuFuture + Send + Sync + 'static,
+    {
+        let fence =
+            (Box::new(future) as Box<dyn GpuFuture + Send + Sync>).then_signal_fence_and_flush()?;
+        self.frames[self.current].fence = Some(fence);
+        Ok(())
+    }
+}
+
+impl<S> fmt::Debug for PerFrameDescriptorSet<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("PerFrameDescriptorSet")
+            .field(
+                "sets",
+                &self
+                    .frames
+                    .iter()
+                    .map(|frame| &frame.set)
+                    .collect::<Vec<_>>(),
+            )
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_buffer::AutoCommandBufferBuilder;
+    use command_buffer::CommandBuffer;
+    use descriptor::descriptor_set::PerFrameDescriptorSet;
+
+    #[test]
+    fn cycle() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut sets = PerFrameDescriptorSet::new(vec![0, 1]);
+        assert_eq!(sets.frames_in_flight(), 2);
+
+        for frame in 0..4 {
+            assert_eq!(*sets.next_frame().unwrap(), frame % 2);
+
+            let cb =
+                AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+                    .unwrap()
+                    .build()
+                    .unwrap();
+            sets.submitted(cb.execute(queue.clone()).unwrap()).unwrap();
+        }
+
+        sets.next_frame().unwrap();
+        assert_eq!(sets.replace(5), 0);
+        assert_eq!(*sets.current(), 5);
+    }
+}
@@ -13,8 +13,10 @@ use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::Arc;
 
+use buffer::cpu_pool::CpuBufferPoolSubbuffer;
 use buffer::BufferAccess;
 use buffer::BufferViewRef;
+use buffer::CpuBufferPool;
 use descriptor::descriptor::DescriptorDesc;
 use descriptor::descriptor::DescriptorDescTy;
 use descriptor::descriptor::DescriptorImageDesc;
@@ -26,6 +28,7 @@ use descriptor::descriptor_set::DescriptorPoolAlloc;
 use descriptor::descriptor_set::DescriptorSet;
 use descriptor::descriptor_set::DescriptorSetDesc;
 use descriptor::descriptor_set::DescriptorWrite;
+use descriptor::descriptor_set::DescriptorWriteError;
 use descriptor::descriptor_set::StdDescriptorPoolAlloc;
 use descriptor::descriptor_set::UnsafeDescriptorSet;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
@@ -33,6 +36,8 @@ use device::Device;
 use device::DeviceOwned;
 use format::Format;
 use image::ImageViewAccess;
+use memory::pool::MemoryPool;
+use memory::DeviceMemoryAllocError;
 use sampler::Sampler;
 use OomError;
 use VulkanObject;
@@ -58,9 +63,20 @@ use VulkanObject;
 pub struct PersistentDescriptorSet<R, P = StdDescriptorPoolAlloc> {
     inner: P,
     resources: R,
+    // Resources written with `begin_update`. The resources that they replaced are still kept
+    // alive in `resources`.
+    updated: UpdatedResources,
     layout: Arc<UnsafeDescriptorSetLayout>,
 }
 
+// Resources that were written to a `PersistentDescriptorSet` after it was built.
+#[derive(Default)]
+struct UpdatedResources {
+    buffers: Vec<(Box<dyn BufferAccess + Send + Sync>, u32)>,
+    images: Vec<(Box<dyn ImageViewAccess + Send + Sync>, u32)>,
+    samplers: Vec<Arc<Sampler>>,
+}
+
 impl PersistentDescriptorSet<()> {
     /// Starts the process of building a `PersistentDescriptorSet`. Returns a builder.
     ///
@@ -92,22 +108,62 @@ where
 
     #[inline]
     fn num_buffers(&self) -> usize {
-        self.resources.num_buffers()
+        self.resources.num_buffers() + self.updated.buffers.len()
     }
 
     #[inline]
     fn buffer(&self, index: usize) -> Option<(&dyn BufferAccess, u32)> {
-        self.resources.buffer(index)
+        let num_built = self.resources.num_buffers();
+        if index < num_built {
+            self.resources.buffer(index)
+        } else {
+            self.updated
+                .buffers
+                .get(index - num_built)
+                .map(|&(ref buf, binding)| (&**buf as &dyn BufferAccess, binding))
+        }
     }
 
     #[inline]
     fn num_images(&self) -> usize {
-        self.resources.num_images()
+        self.resources.num_images() + self.updated.images.len()
     }
 
     #[inline]
     fn image(&self, index: usize) -> Option<(&dyn ImageViewAccess, u32)> {
-        self.resources.image(index)
+        let num_built = self.resources.num_images();
+        if index < num_built {
+            self.resources.image(index)
+        } else {
+            self.updated
+                .images
+                .get(index - num_built)
+                .map(|&(ref img, binding)| (&**img as &dyn ImageViewAccess, binding))
+        }
+    }
+}
+
+impl<R, P> PersistentDescriptorSet<R, P>
+where
+    P: DescriptorPoolAlloc,
+{
+    /// Starts updating some descriptors of the set. Returns an object that accumulates the
+    /// writes.
+    ///
+    /// The writes are checked against the layout of the set as they are added, and are all
+    /// performed with a single call to `vkUpdateDescriptorSets` when the returned object is
+    /// committed or dropped. The set keeps the new resources alive, in addition to the ones it
+    /// was built with.
+    ///
+    /// Since this requires exclusive access to the set, the set can't be in use by a command
+    /// buffer while it is being updated.
+    #[inline]
+    pub fn begin_update<'a>(&'a mut self) -> PersistentDescriptorSetUpdate<'a, R, P> {
+        PersistentDescriptorSetUpdate {
+            set: self,
+            writes: Vec::new(),
+            resources: UpdatedResources::default(),
+        }
     }
 }
 
@@ -161,6 +217,266 @@ where
     }
 }
 
+/// Writes to a `PersistentDescriptorSet` that are accumulated and performed at once.
+///
+/// Created with `PersistentDescriptorSet::begin_update`. The writes are performed when `commit`
+/// is called or when the object is dropped.
+pub struct PersistentDescriptorSetUpdate<'a, R: 'a, P: 'a>
+where
+    P: DescriptorPoolAlloc,
+{
+    set: &'a mut PersistentDescriptorSet<R, P>,
+    // The writes to perform.
+    writes: Vec<DescriptorWrite>,
+    // Holds the resources of the writes alive until they are transferred to the set.
+    resources: UpdatedResources,
+}
+
+impl<'a, R, P> PersistentDescriptorSetUpdate<'a, R, P>
+where
+    P: DescriptorPoolAlloc,
+{
+    /// Writes a buffer to the descriptor `binding` at the array element `array_element`.
+    ///
+    /// The binding must be a uniform or storage buffer, dynamic or not. If the binding is
+    /// mutable, the descriptor becomes a uniform buffer if the binding and the usage of the
+    /// buffer allow it, and a storage buffer otherwise.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the buffer doesn't have the same device as the descriptor set.
+    ///
+    pub fn buffer<T>(
+        &mut self,
+        binding: u32,
+        array_element: u32,
+        buffer: T,
+    ) -> Result<&mut Self, DescriptorWriteError>
+    where
+        T: BufferAccess + Send + Sync + 'static,
+    {
+        assert_eq!(
+            self.set.device().internal_object(),
+            buffer.inner().buffer.device().internal_object()
+        );
+
+        let writes = unsafe {
+            match self.descriptor_ty(binding) {
+                Some(DescriptorType::StorageBuffer) => {
+                    vec![DescriptorWrite::storage_buffer(
+                        binding,
+                        array_element,
+                        &buffer,
+                    )]
+                }
+                Some(DescriptorType::UniformBufferDynamic) => {
+                    vec![DescriptorWrite::dynamic_uniform_buffer(
+                        binding,
+                        array_element,
+                        &buffer,
+                    )]
+                }
+                Some(DescriptorType::StorageBufferDynamic) => {
+                    vec![DescriptorWrite::dynamic_storage_buffer(
+                        binding,
+                        array_element,
+                        &buffer,
+                    )]
+                }
+                Some(DescriptorType::Mutable) => vec![
+                    DescriptorWrite::uniform_buffer(binding, array_element, &buffer),
+                    DescriptorWrite::storage_buffer(binding, array_element, &buffer),
+                ],
+                _ => vec![DescriptorWrite::uniform_buffer(
+                    binding,
+                    array_element,
+                    &buffer,
+                )],
+            }
+        };
+
+        let write = first_accepted_write(&self.set.layout, writes)?;
+        self.writes.push(write);
+        self.resources.buffers.push((Box::new(buffer), binding));
+        Ok(self)
+    }
+
+    /// Writes an image view to the descriptor `binding` at the array element `array_element`.
+    ///
+    /// The binding must be a sampled image, a storage image or an input attachment. If the
+    /// binding is mutable, the first of these types that the binding and the usage of the image
+    /// allow is used.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the image doesn't have the same device as the descriptor set.
+    ///
+    pub fn image<T>(
+        &mut self,
+        binding: u32,
+        array_element: u32,
+        image: T,
+    ) -> Result<&mut Self, DescriptorWriteError>
+    where
+        T: ImageViewAccess + Send + Sync + 'static,
+    {
+        assert_eq!(
+            self.set.device().internal_object(),
+            image.parent().inner().image.device().internal_object()
+        );
+
+        let writes = match self.descriptor_ty(binding) {
+            Some(DescriptorType::StorageImage) => {
+                vec![DescriptorWrite::storage_image(
+                    binding,
+                    array_element,
+                    &image,
+                )]
+            }
+            Some(DescriptorType::InputAttachment) => {
+                vec![DescriptorWrite::input_attachment(
+                    binding,
+                    array_element,
+                    &image,
+                )]
+            }
+            Some(DescriptorType::Mutable) => vec![
+                DescriptorWrite::sampled_image(binding, array_element, &image),
+                DescriptorWrite::storage_image(binding, array_element, &image),
+                DescriptorWrite::input_attachment(binding, array_element, &image),
+            ],
+            _ => vec![DescriptorWrite::sampled_image(
+                binding,
+                array_element,
+                &image,
+            )],
+        };
+
+        let write = first_accepted_write(&self.set.layout, writes)?;
+        self.writes.push(write);
+        self.resources.images.push((Box::new(image), binding));
+        Ok(self)
+    }
+
+    /// Writes an image view and a sampler to the combined image sampler descriptor `binding` at
+    /// the array element `array_element`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the image or the sampler doesn't have the same device as the descriptor set.
+    ///
+    pub fn image_sampler<T>(
+        &mut self,
+        binding: u32,
+        array_element: u32,
+        image: T,
+        sampler: Arc<Sampler>,
+    ) -> Result<&mut Self, DescriptorWriteError>
+    where
+        T: ImageViewAccess + Send + Sync + 'static,
+    {
+        assert_eq!(
+            self.set.device().internal_object(),
+            image.parent().inner().image.device().internal_object()
+        );
+        assert_eq!(
+            self.set.device().internal_object(),
+            sampler.device().internal_object()
+        );
+
+        let write =
+            DescriptorWrite::combined_image_sampler(binding, array_element, &sampler, &image);
+
+        self.set.layout.check_write(&write)?;
+        self.writes.push(write);
+        self.resources.images.push((Box::new(image), binding));
+        self.resources.samplers.push(sampler);
+        Ok(self)
+    }
+
+    /// Writes a sampler to the descriptor `binding` at the array element `array_element`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the sampler doesn't have the same device as the descriptor set.
+    ///
+    pub fn sampler(
+        &mut self,
+        binding: u32,
+        array_element: u32,
+        sampler: Arc<Sampler>,
+    ) -> Result<&mut Self, DescriptorWriteError> {
+        assert_eq!(
+            self.set.device().internal_object(),
+            sampler.device().internal_object()
+        );
+
+        let write = DescriptorWrite::sampler(binding, array_element, &sampler);
+
+        self.set.layout.check_write(&write)?;
+        self.writes.push(write);
+        self.resources.samplers.push(sampler);
+        Ok(self)
+    }
+
+    /// Returns the number of pending writes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// Returns true if there is no pending write.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Performs all the pending writes with a single `vkUpdateDescriptorSets` call.
+    ///
+    /// This is the same as dropping the object, but makes the intent explicit.
+    #[inline]
+    pub fn commit(mut self) {
+        self.flush();
+    }
+
+    // Returns the type of the descriptor `binding` in the layout of the set.
+    fn descriptor_ty(&self, binding: u32) -> Option<DescriptorType> {
+        self.set
+            .layout
+            .descriptor(binding as usize)
+            .and_then(|desc| desc.ty.ty())
+    }
+
+    fn flush(&mut self) {
+        if self.writes.is_empty() {
+            return;
+        }
+
+        unsafe {
+            let device = self.set.layout.device().clone();
+            self.set
+                .inner
+                .inner_mut()
+                .write(&device, self.writes.drain(..));
+        }
+
+        let updated = &mut self.set.updated;
+        updated.buffers.extend(self.resources.buffers.drain(..));
+        updated.images.extend(self.resources.images.drain(..));
+        updated.samplers.extend(self.resources.samplers.drain(..));
+    }
+}
+
+impl<'a, R, P> Drop for PersistentDescriptorSetUpdate<'a, R, P>
+where
+    P: DescriptorPoolAlloc,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
 /// Prototype of a `PersistentDescriptorSet`.
 ///
 /// The template parameter `R` is an unspecified type that represents the list of resources.
@@ -220,6 +536,10 @@ impl<R> PersistentDescriptorSetBuilder<R> {
 
         debug_assert_eq!(expected_desc, self.binding_id);
 
+        self.layout
+            .validate_writes(&self.writes)
+            .map_err(PersistentDescriptorSetBuildError::InvalidWrite)?;
+
         let set = unsafe {
             let mut set = pool.alloc(&self.layout)?;
             set.inner_mut()
@@ -230,6 +550,7 @@ impl<R> PersistentDescriptorSetBuilder<R> {
         Ok(PersistentDescriptorSet {
             inner: set,
             resources: self.resources,
+            updated: UpdatedResources::default(),
             layout: self.layout,
         })
     }
@@ -297,6 +618,41 @@ impl<R> PersistentDescriptorSetBuilder<R> {
         self.enter_array()?.add_buffer(buffer)?.leave_array()
     }
 
+    /// Writes `value` in a subbuffer of `pool` and binds that subbuffer as the next descriptor.
+    ///
+    /// This is a shortcut for calling `pool.next(value)` followed with `add_buffer`. The pool
+    /// must have been created with the uniform buffer or storage buffer usage, for example with
+    /// `CpuBufferPool::uniform_buffer`.
+    ///
+    /// The value is copied as-is in the buffer. It is your responsibility to ensure that the
+    /// memory layout of `T` matches the std140 or std430 layout of the block declared in the
+    /// shader, which is the case of the structs generated by `vulkano-shaders`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the pool doesn't have the same device as the descriptor set layout.
+    ///
+    #[inline]
+    pub fn add_uniform_value<T, A>(
+        self,
+        pool: &CpuBufferPool<T, A>,
+        value: T,
+    ) -> Result<
+        PersistentDescriptorSetBuilder<(
+            R,
+            PersistentDescriptorSetBuf<CpuBufferPoolSubbuffer<T, A>>,
+        )>,
+        PersistentDescriptorSetError,
+    >
+    where
+        T: Copy + Send + Sync + 'static,
+        A: MemoryPool,
+    {
+        self.enter_array()?
+            .add_uniform_value(pool, value)?
+            .leave_array()
+    }
+
     /// Binds a buffer view as the next descriptor.
     ///
     /// An error is returned if the buffer isn't compatible with the descriptor.
@@ -401,22 +757,56 @@ pub struct PersistentDescriptorSetBuilderArray<R> {
 
 impl<R> PersistentDescriptorSetBuilderArray<R> {
     /// Leaves the array. Call this once you added all the elements of the array.
+    ///
+    /// If the binding was created as partially bound, the remaining elements of the array are
+    /// left unwritten.
     pub fn leave_array(
         mut self,
     ) -> Result<PersistentDescriptorSetBuilder<R>, PersistentDescriptorSetError> {
-        if self.desc.array_count > self.array_element as u32 {
+        let partially_bound = self
+            .builder
+            .layout
+            .binding_flags(self.builder.binding_id)
+            .partially_bound;
+
+        if !partially_bound && self.desc.array_count > self.array_element as u32 {
             return Err(PersistentDescriptorSetError::MissingArrayElements {
                 expected: self.desc.array_count,
                 obtained: self.array_element as u32,
             });
         }
 
-        debug_assert_eq!(self.desc.array_count, self.array_element as u32);
+        debug_assert!(self.desc.array_count >= self.array_element as u32);
 
         self.builder.binding_id += 1;
         Ok(self.builder)
     }
 
+    /// Leaves the next `count` elements of the array unwritten.
+    ///
+    /// This is only allowed if the binding was created as partially bound. The shader must not
+    /// access the elements that are skipped.
+    pub fn skip(
+        mut self,
+        count: u32,
+    ) -> Result<PersistentDescriptorSetBuilderArray<R>, PersistentDescriptorSetError> {
+        if !self
+            .builder
+            .layout
+            .binding_flags(self.builder.binding_id)
+            .partially_bound
+        {
+            return Err(PersistentDescriptorSetError::NotPartiallyBound);
+        }
+
+        if self.array_element as u32 + count > self.desc.array_count {
+            return Err(PersistentDescriptorSetError::ArrayOutOfBounds);
+        }
+
+        self.array_element += count as usize;
+        Ok(self)
+    }
+
     /// Binds a buffer as the next element in the array.
     ///
     /// An error is returned if the buffer isn't compatible with the descriptor.
@@ -471,11 +861,19 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
                     }
 
                     unsafe {
-                        DescriptorWrite::storage_buffer(
-                            self.builder.binding_id as u32,
-                            self.array_element as u32,
-                            &buffer,
-                        )
+                        if buffer_desc.dynamic == Some(true) {
+                            DescriptorWrite::dynamic_storage_buffer(
+                                self.builder.binding_id as u32,
+                                self.array_element as u32,
+                                &buffer,
+                            )
+                        } else {
+                            DescriptorWrite::storage_buffer(
+                                self.builder.binding_id as u32,
+                                self.array_element as u32,
+                                &buffer,
+                            )
+                        }
                     }
                 } else {
                     if !buffer.inner().buffer.usage_uniform_buffer() {
@@ -485,14 +883,33 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
                     }
 
                     unsafe {
-                        DescriptorWrite::uniform_buffer(
-                            self.builder.binding_id as u32,
-                            self.array_element as u32,
-                            &buffer,
-                        )
+                        if buffer_desc.dynamic == Some(true) {
+                            DescriptorWrite::dynamic_uniform_buffer(
+                                self.builder.binding_id as u32,
+                                self.array_element as u32,
+                                &buffer,
+                            )
+                        } else {
+                            DescriptorWrite::uniform_buffer(
+                                self.builder.binding_id as u32,
+                                self.array_element as u32,
+                                &buffer,
+                            )
+                        }
                     }
                 }
             }
+            DescriptorDescTy::Mutable(_) => {
+                let (binding, array_element) =
+                    (self.builder.binding_id as u32, self.array_element as u32);
+                let writes = unsafe {
+                    vec![
+                        DescriptorWrite::uniform_buffer(binding, array_element, &buffer),
+                        DescriptorWrite::storage_buffer(binding, array_element, &buffer),
+                    ]
+                };
+                first_accepted_write(&self.builder.layout, writes)?
+            }
             ref d => {
                 return Err(PersistentDescriptorSetError::WrongDescriptorTy {
                     expected: d.ty().unwrap(),
@@ -518,6 +935,34 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
         })
     }
 
+    /// Writes `value` in a subbuffer of `pool` and binds that subbuffer as the next element in
+    /// the array.
+    ///
+    /// See the documentation of `PersistentDescriptorSetBuilder::add_uniform_value`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the pool doesn't have the same device as the descriptor set layout.
+    ///
+    pub fn add_uniform_value<T, A>(
+        self,
+        pool: &CpuBufferPool<T, A>,
+        value: T,
+    ) -> Result<
+        PersistentDescriptorSetBuilderArray<(
+            R,
+            PersistentDescriptorSetBuf<CpuBufferPoolSubbuffer<T, A>>,
+        )>,
+        PersistentDescriptorSetError,
+    >
+    where
+        T: Copy + Send + Sync + 'static,
+        A: MemoryPool,
+    {
+        let buffer = pool.next(value)?;
+        self.add_buffer(buffer)
+    }
+
     /// Binds a buffer view as the next element in the array.
     ///
     /// An error is returned if the buffer isn't compatible with the descriptor.
@@ -575,6 +1020,15 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
                     )
                 }
             }
+            DescriptorDescTy::Mutable(_) => {
+                let (binding, array_element) =
+                    (self.builder.binding_id as u32, self.array_element as u32);
+                let writes = vec![
+                    DescriptorWrite::uniform_texel_buffer(binding, array_element, view.view()),
+                    DescriptorWrite::storage_texel_buffer(binding, array_element, view.view()),
+                ];
+                first_accepted_write(&self.builder.layout, writes)?
+            }
             ref d => {
                 return Err(PersistentDescriptorSetError::WrongDescriptorTy {
                     expected: d.ty().unwrap(),
@@ -697,6 +1151,16 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
                     &image_view,
                 )
             }
+            DescriptorDescTy::Mutable(_) => {
+                let (binding, array_element) =
+                    (self.builder.binding_id as u32, self.array_element as u32);
+                let writes = vec![
+                    DescriptorWrite::sampled_image(binding, array_element, &image_view),
+                    DescriptorWrite::storage_image(binding, array_element, &image_view),
+                    DescriptorWrite::input_attachment(binding, array_element, &image_view),
+                ];
+                first_accepted_write(&self.builder.layout, writes)?
+            }
             ty => {
                 return Err(PersistentDescriptorSetError::WrongDescriptorTy {
                     expected: ty.ty().unwrap(),
@@ -776,6 +1240,15 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
                     &image_view,
                 )
             }
+            DescriptorDescTy::Mutable(_) => {
+                let write = DescriptorWrite::combined_image_sampler(
+                    self.builder.binding_id as u32,
+                    self.array_element as u32,
+                    &sampler,
+                    &image_view,
+                );
+                first_accepted_write(&self.builder.layout, vec![write])?
+            }
             ty => {
                 return Err(PersistentDescriptorSetError::WrongDescriptorTy {
                     expected: ty.ty().unwrap(),
@@ -839,6 +1312,14 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
                 self.array_element as u32,
                 &sampler,
             ),
+            DescriptorDescTy::Mutable(_) => {
+                let write = DescriptorWrite::sampler(
+                    self.builder.binding_id as u32,
+                    self.array_element as u32,
+                    &sampler,
+                );
+                first_accepted_write(&self.builder.layout, vec![write])?
+            }
             ty => {
                 return Err(PersistentDescriptorSetError::WrongDescriptorTy {
                     expected: ty.ty().unwrap(),
@@ -862,6 +1343,26 @@ impl<R> PersistentDescriptorSetBuilderArray<R> {
     }
 }
 
+// Returns the first of `writes` that the layout accepts, or the error of the last one.
+//
+// The type of a mutable descriptor is the type of the write, so a resource can be written to a
+// mutable descriptor as any of the types that the binding and the usage of the resource allow.
+fn first_accepted_write(
+    layout: &UnsafeDescriptorSetLayout,
+    writes: Vec<DescriptorWrite>,
+) -> Result<DescriptorWrite, DescriptorWriteError> {
+    let mut last_err = None;
+
+    for write in writes {
+        match layout.check_write(&write) {
+            Ok(()) => return Ok(write),
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    Err(last_err.expect("no descriptor write to check"))
+}
+
 // Checks whether an image view matches the descriptor.
 fn image_match_desc<I>(
     image_view: &I,
@@ -1189,9 +1690,41 @@ pub enum PersistentDescriptorSetError {
         /// Type of the image view that was passed.
         obtained: DescriptorImageDescDimensions,
     },
+    /// Failed to allocate a buffer to hold a uniform value.
+    AllocError(DeviceMemoryAllocError),
+
+    /// Tried to skip array elements of a binding that isn't partially bound.
+    NotPartiallyBound,
+
+    /// The resource can't be written to a mutable descriptor, because none of the types that
+    /// the resource can be written as is allowed by the binding and the usage of the resource.
+    InvalidMutableWrite(DescriptorWriteError),
 }
 
-impl error::Error for PersistentDescriptorSetError {}
+impl From<DeviceMemoryAllocError> for PersistentDescriptorSetError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> PersistentDescriptorSetError {
+        PersistentDescriptorSetError::AllocError(err)
+    }
+}
+
+impl From<DescriptorWriteError> for PersistentDescriptorSetError {
+    #[inline]
+    fn from(err: DescriptorWriteError) -> PersistentDescriptorSetError {
+        PersistentDescriptorSetError::InvalidMutableWrite(err)
+    }
+}
+
+impl error::Error for PersistentDescriptorSetError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            PersistentDescriptorSetError::AllocError(ref err) => Some(err),
+            PersistentDescriptorSetError::InvalidMutableWrite(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl fmt::Display for PersistentDescriptorSetError {
     #[inline]
@@ -1236,6 +1769,15 @@ impl fmt::Display for PersistentDescriptorSetError {
                 PersistentDescriptorSetError::ImageViewTypeMismatch { .. } => {
                     "the type of an image view doesn't match what was expected"
                 }
+                PersistentDescriptorSetError::AllocError(_) => {
+                    "failed to allocate a buffer to hold a uniform value"
+                }
+                PersistentDescriptorSetError::NotPartiallyBound => {
+                    "tried to skip array elements of a binding that isn't partially bound"
+                }
+                PersistentDescriptorSetError::InvalidMutableWrite(_) => {
+                    "the resource can't be written to the mutable descriptor"
+                }
             }
         )
     }
@@ -1254,9 +1796,21 @@ pub enum PersistentDescriptorSetBuildError {
         /// Number of descriptors that were added.
         obtained: u32,
     },
+
+    /// One of the descriptors that were added isn't compatible with the layout.
+    InvalidWrite(DescriptorWriteError),
 }
 
-impl error::Error for PersistentDescriptorSetBuildError {}
+impl error::Error for PersistentDescriptorSetBuildError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            PersistentDescriptorSetBuildError::OomError(ref err) => Some(err),
+            PersistentDescriptorSetBuildError::InvalidWrite(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl From<OomError> for PersistentDescriptorSetBuildError {
     #[inline]
@@ -1276,7 +1830,167 @@ impl fmt::Display for PersistentDescriptorSetBuildError {
                     "didn't fill all the descriptors before building"
                 }
                 PersistentDescriptorSetBuildError::OomError(_) => "not enough memory available",
+                PersistentDescriptorSetBuildError::InvalidWrite(_) => {
+                    "one of the descriptors isn't compatible with the layout"
+                }
             }
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorType;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorSet;
+    use descriptor::descriptor_set::DescriptorWriteError;
+    use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::descriptor_set::PersistentDescriptorSetError;
+    use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use device::Device;
+    use device::DeviceExtensions;
+    use features::Features;
+    use instance::PhysicalDevice;
+    use std::iter;
+    use std::sync::Arc;
+
+    #[test]
+    fn update() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(false),
+                storage: false,
+            }),
+            array_count: 1,
+            stages: ShaderStages::all(),
+            readonly: true,
+        };
+        let layout = Arc::new(
+            UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(desc))).unwrap(),
+        );
+
+        let uniform = || {
+            CpuAccessibleBuffer::from_data(
+                device.clone(),
+                BufferUsage::uniform_buffer(),
+                false,
+                0u32,
+            )
+            .unwrap()
+        };
+        let storage = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage {
+                storage_buffer: true,
+                ..BufferUsage::none()
+            },
+            false,
+            0u32,
+        )
+        .unwrap();
+
+        let mut set = PersistentDescriptorSet::start(layout)
+            .add_buffer(uniform())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        {
+            let mut update = set.begin_update();
+            match update.buffer(0, 0, storage) {
+                Err(DescriptorWriteError::MissingUsage { binding: 0 }) => (),
+                _ => panic!(),
+            }
+            match update.buffer(0, 1, uniform()) {
+                Err(DescriptorWriteError::ArrayOutOfRange { .. }) => (),
+                _ => panic!(),
+            }
+            update.buffer(0, 0, uniform()).unwrap();
+            assert_eq!(update.len(), 1);
+            update.commit();
+        }
+
+        // The buffer the set was built with is still kept alive.
+        assert_eq!(set.num_buffers(), 2);
+    }
+
+    #[test]
+    fn mutable() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+        let queue = match physical.queue_families().find(|q| q.supports_graphics()) {
+            Some(q) => q,
+            None => return,
+        };
+
+        let extensions = DeviceExtensions {
+            ext_mutable_descriptor_type: true,
+            ..DeviceExtensions::none()
+        };
+        let features = Features {
+            mutable_descriptor_type: true,
+            ..Features::none()
+        };
+        if !DeviceExtensions::supported_by_device(physical).ext_mutable_descriptor_type
+            || !physical.supported_features().superset_of(&features)
+        {
+            return;
+        }
+        let (device, _) =
+            Device::new(physical, &features, &extensions, iter::once((queue, 0.5))).unwrap();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Mutable(vec![
+                DescriptorType::UniformBuffer,
+                DescriptorType::StorageBuffer,
+            ]),
+            array_count: 1,
+            stages: ShaderStages::all(),
+            readonly: true,
+        };
+        let layout = Arc::new(
+            UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(desc))).unwrap(),
+        );
+        assert_eq!(
+            &layout.mutable_types()[..],
+            &[DescriptorType::UniformBuffer, DescriptorType::StorageBuffer]
+        );
+
+        let buffer = |usage| CpuAccessibleBuffer::from_data(device.clone(), usage, false, 0u32);
+        let storage = BufferUsage {
+            storage_buffer: true,
+            ..BufferUsage::none()
+        };
+
+        // A buffer without the uniform or storage usage can't be written to the binding.
+        match PersistentDescriptorSet::start(layout.clone())
+            .add_buffer(buffer(BufferUsage::transfer_source()).unwrap())
+        {
+            Err(PersistentDescriptorSetError::InvalidMutableWrite(
+                DescriptorWriteError::MissingUsage { binding: 0 },
+            )) => (),
+            _ => panic!(),
+        }
+
+        // The descriptor becomes a storage buffer, and the pool allows the types of the layout.
+        let mut set = PersistentDescriptorSet::start(layout)
+            .add_buffer(buffer(storage).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        set.begin_update()
+            .buffer(0, 0, buffer(BufferUsage::uniform_buffer()).unwrap())
+            .unwrap();
+    }
+}
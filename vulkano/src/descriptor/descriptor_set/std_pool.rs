@@ -7,12 +7,15 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 use descriptor::descriptor_set::DescriptorPool;
 use descriptor::descriptor_set::DescriptorPoolAlloc;
 use descriptor::descriptor_set::DescriptorPoolAllocError;
+use descriptor::descriptor_set::DescriptorPoolCreationError;
 use descriptor::descriptor_set::DescriptorsCount;
 use descriptor::descriptor_set::UnsafeDescriptorPool;
 use descriptor::descriptor_set::UnsafeDescriptorSet;
@@ -28,10 +31,18 @@ use OomError;
 ///
 /// Whenever a set is allocated, this implementation will try to find a pool that has some space
 /// for it. If there is one, allocate from it. If there is none, create a new pool whose capacity
-/// is 40 sets and 40 times the requested descriptors. This number is arbitrary.
+/// is 40 sets and 40 times the requested descriptors. This number is arbitrary, and can be
+/// changed with `with_max_sets`.
+///
+/// Allocating from a pool can fail with a `FragmentedPool` or `OutOfPoolMemory` error even if the
+/// pool seems to have enough space left. When this happens, the allocation is transparently
+/// retried in the next pools, and then in a new pool. This automatic growth can be disabled with
+/// `set_auto_grow`.
 pub struct StdDescriptorPool {
     device: Arc<Device>,
     pools: Mutex<Vec<Arc<Mutex<Pool>>>>,
+    max_sets: u32,
+    auto_grow: AtomicBool,
 }
 
 struct Pool {
@@ -43,33 +54,65 @@ struct Pool {
 impl StdDescriptorPool {
     /// Builds a new `StdDescriptorPool`.
     pub fn new(device: Arc<Device>) -> StdDescriptorPool {
+        StdDescriptorPool::with_max_sets(device, 40)
+    }
+
+    /// Builds a new `StdDescriptorPool` whose internal pools have a capacity of `max_sets` sets,
+    /// and `max_sets` times the descriptors of the first layout allocated from them.
+    ///
+    /// The pools of update-after-bind layouts always have a capacity of one set.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `max_sets` is 0.
+    ///
+    pub fn with_max_sets(device: Arc<Device>, max_sets: u32) -> StdDescriptorPool {
+        assert_ne!(max_sets, 0, "The maximum number of sets can't be 0");
+
         StdDescriptorPool {
             device: device,
             pools: Mutex::new(Vec::new()),
+            max_sets,
+            auto_grow: AtomicBool::new(true),
         }
     }
-}
 
-/// A descriptor set allocated from a `StdDescriptorPool`.
-pub struct StdDescriptorPoolAlloc {
-    pool: Arc<Mutex<Pool>>,
-    // The set. Inside an option so that we can extract it in the destructor.
-    set: Option<UnsafeDescriptorSet>,
-    // We need to keep track of this count in order to add it back to the capacity when freeing.
-    descriptors: DescriptorsCount,
-    // We keep the parent of the pool alive, otherwise it would be destroyed.
-    pool_parent: Arc<StdDescriptorPool>,
-}
+    /// Returns the maximum number of sets of each of the internal pools.
+    #[inline]
+    pub fn max_sets(&self) -> u32 {
+        self.max_sets
+    }
 
-unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
-    type Alloc = StdDescriptorPoolAlloc;
+    /// Sets whether new internal pools are created when the existing ones can't serve an
+    /// allocation. The default is true.
+    ///
+    /// If false, only the first internal pool is ever created, and allocations that don't fit in
+    /// it fail. Use `try_alloc` in order to know why an allocation failed.
+    #[inline]
+    pub fn set_auto_grow(&self, auto_grow: bool) {
+        self.auto_grow.store(auto_grow, Ordering::Relaxed);
+    }
 
+    /// Returns true if new internal pools are created when the existing ones are full or
+    /// fragmented.
+    #[inline]
+    pub fn auto_grow(&self) -> bool {
+        self.auto_grow.load(Ordering::Relaxed)
+    }
+
+    /// Allocates a descriptor set with the given layout.
+    ///
+    /// Same as `DescriptorPool::alloc`, except that the `FragmentedPool` and `OutOfPoolMemory`
+    /// errors are returned instead of being reported as `OutOfDeviceMemory` when the set can't
+    /// be allocated because automatic growth is disabled.
     // TODO: eventually use a lock-free algorithm?
-    fn alloc(
-        &mut self,
+    pub fn try_alloc(
+        self: &Arc<Self>,
         layout: &UnsafeDescriptorSetLayout,
-    ) -> Result<StdDescriptorPoolAlloc, OomError> {
+    ) -> Result<StdDescriptorPoolAlloc, DescriptorPoolAllocError> {
         let mut pools = self.pools.lock().unwrap();
+        let mut last_error = DescriptorPoolAllocError::OutOfPoolMemory;
+        let mutable_types = layout.mutable_types();
 
         // Try find an existing pool with some free space.
         for pool_arc in pools.iter_mut() {
@@ -79,6 +122,21 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
                 continue;
             }
 
+            // Update-after-bind layouts must be allocated from update-after-bind pools.
+            if pool.pool.is_update_after_bind() != layout.is_update_after_bind_pool() {
+                continue;
+            }
+
+            // Same for host-only layouts, and mutable descriptors must be allocated from a pool
+            // that allows all their types.
+            if pool.pool.is_host_only() != layout.is_host_only_pool()
+                || !mutable_types
+                    .iter()
+                    .all(|ty| pool.pool.mutable_types().contains(ty))
+            {
+                continue;
+            }
+
             if !(pool.remaining_capacity >= *layout.descriptors_count()) {
                 continue;
             }
@@ -93,9 +151,15 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
             let alloc = unsafe {
                 match pool.pool.alloc(Some(layout)) {
                     Ok(mut sets) => sets.next().unwrap(),
-                    // An error can happen if we're out of memory, or if the pool is fragmented.
-                    // We handle these errors by just ignoring this pool and trying the next ones.
-                    Err(_) => continue,
+                    // Out of memory errors would happen with a new pool as well.
+                    Err(err @ DescriptorPoolAllocError::OutOfHostMemory)
+                    | Err(err @ DescriptorPoolAllocError::OutOfDeviceMemory) => return Err(err),
+                    // The pool is fragmented or its bookkeeping was wrong. We handle these
+                    // errors by just ignoring this pool and trying the next ones.
+                    Err(err) => {
+                        last_error = err;
+                        continue;
+                    }
                 }
             };
 
@@ -107,33 +171,63 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
             });
         }
 
+        if !pools.is_empty() && !self.auto_grow() {
+            return Err(last_error);
+        }
+
         // No existing pool can be used. Create a new one.
-        // We use an arbitrary number of 40 sets and 40 times the requested descriptors.
-        let count = layout.descriptors_count().clone() * 40;
+        // We use `max_sets` sets and `max_sets` times the requested descriptors. Update-after-bind
+        // layouts usually have large arrays of descriptors, and the descriptors of all the
+        // update-after-bind pools of the device are limited, so their pools only hold one set.
+        let max_sets = if layout.is_update_after_bind_pool() {
+            1
+        } else {
+            self.max_sets
+        };
+        let count = layout.descriptors_count().clone() * max_sets;
         // Failure to allocate a new pool results in an error for the whole function because
         // there's no way we can recover from that.
-        let mut new_pool = UnsafeDescriptorPool::new(self.device.clone(), &count, 40, true)?;
+        let new_pool = UnsafeDescriptorPool::new_impl(
+            self.device.clone(),
+            &count,
+            max_sets,
+            true,
+            layout.is_host_only_pool(),
+            &mutable_types,
+            layout.is_update_after_bind_pool(),
+        );
+        let mut new_pool = match new_pool {
+            Ok(pool) => pool,
+            Err(DescriptorPoolCreationError::OomError(OomError::OutOfHostMemory)) => {
+                return Err(DescriptorPoolAllocError::OutOfHostMemory)
+            }
+            Err(DescriptorPoolCreationError::OomError(OomError::OutOfDeviceMemory)) => {
+                return Err(DescriptorPoolAllocError::OutOfDeviceMemory)
+            }
+            // The update-after-bind pools of the device have no room left for the descriptors.
+            Err(DescriptorPoolCreationError::UpdateAfterBindLimitExceeded { .. }) => {
+                return Err(DescriptorPoolAllocError::OutOfPoolMemory)
+            }
+        };
 
         let alloc = unsafe {
             match new_pool.alloc(Some(layout)) {
                 Ok(mut sets) => sets.next().unwrap(),
-                Err(DescriptorPoolAllocError::OutOfHostMemory) => {
-                    return Err(OomError::OutOfHostMemory);
-                }
-                Err(DescriptorPoolAllocError::OutOfDeviceMemory) => {
-                    return Err(OomError::OutOfDeviceMemory);
-                }
+                Err(err @ DescriptorPoolAllocError::OutOfHostMemory)
+                | Err(err @ DescriptorPoolAllocError::OutOfDeviceMemory) => return Err(err),
                 // A fragmented pool error can't happen at the first ever allocation.
                 Err(DescriptorPoolAllocError::FragmentedPool) => unreachable!(),
                 // Out of pool memory cannot happen at the first ever allocation.
                 Err(DescriptorPoolAllocError::OutOfPoolMemory) => unreachable!(),
+                // The pool has at least one set available.
+                Err(DescriptorPoolAllocError::DescriptorPoolExhausted { .. }) => unreachable!(),
             }
         };
 
         let pool_obj = Arc::new(Mutex::new(Pool {
             pool: new_pool,
             remaining_capacity: count - *layout.descriptors_count(),
-            remaining_sets_count: 40 - 1,
+            remaining_sets_count: max_sets - 1,
         }));
 
         pools.push(pool_obj.clone());
@@ -147,6 +241,34 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
     }
 }
 
+/// A descriptor set allocated from a `StdDescriptorPool`.
+pub struct StdDescriptorPoolAlloc {
+    pool: Arc<Mutex<Pool>>,
+    // The set. Inside an option so that we can extract it in the destructor.
+    set: Option<UnsafeDescriptorSet>,
+    // We need to keep track of this count in order to add it back to the capacity when freeing.
+    descriptors: DescriptorsCount,
+    // We keep the parent of the pool alive, otherwise it would be destroyed.
+    pool_parent: Arc<StdDescriptorPool>,
+}
+
+unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
+    type Alloc = StdDescriptorPoolAlloc;
+
+    #[inline]
+    fn alloc(
+        &mut self,
+        layout: &UnsafeDescriptorSetLayout,
+    ) -> Result<StdDescriptorPoolAlloc, OomError> {
+        match self.try_alloc(layout) {
+            Ok(alloc) => Ok(alloc),
+            Err(DescriptorPoolAllocError::OutOfHostMemory) => Err(OomError::OutOfHostMemory),
+            // The other errors can only happen if automatic growth is disabled.
+            Err(_) => Err(OomError::OutOfDeviceMemory),
+        }
+    }
+}
+
 unsafe impl DeviceOwned for StdDescriptorPool {
     #[inline]
     fn device(&self) -> &Arc<Device> {
@@ -185,6 +307,7 @@ mod tests {
     use descriptor::descriptor::DescriptorDescTy;
     use descriptor::descriptor::ShaderStages;
     use descriptor::descriptor_set::DescriptorPool;
+    use descriptor::descriptor_set::DescriptorPoolAllocError;
     use descriptor::descriptor_set::StdDescriptorPool;
     use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
     use std::iter;
@@ -210,4 +333,30 @@ mod tests {
         drop(pool);
         assert!(pool_weak.upgrade().is_some());
     }
+
+    #[test]
+    fn auto_grow() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 1,
+            stages: ShaderStages::all(),
+            readonly: false,
+        };
+        let layout =
+            UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(desc))).unwrap();
+
+        let pool = Arc::new(StdDescriptorPool::with_max_sets(device, 1));
+        pool.set_auto_grow(false);
+        let _first = pool.try_alloc(&layout).unwrap();
+
+        match pool.try_alloc(&layout) {
+            Err(DescriptorPoolAllocError::OutOfPoolMemory) => (),
+            _ => panic!(),
+        }
+
+        pool.set_auto_grow(true);
+        let _second = pool.try_alloc(&layout).unwrap();
+    }
 }
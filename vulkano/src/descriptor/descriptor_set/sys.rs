@@ -21,6 +21,7 @@ use buffer::BufferAccess;
 use buffer::BufferInner;
 use buffer::BufferView;
 use descriptor::descriptor::DescriptorType;
+use descriptor::descriptor_set::DescriptorWriteError;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use device::Device;
 use device::DeviceOwned;
@@ -104,8 +105,15 @@ macro_rules! descriptors_count {
                     DescriptorType::UniformBufferDynamic => self.uniform_buffer_dynamic += 1,
                     DescriptorType::StorageBufferDynamic => self.storage_buffer_dynamic += 1,
                     DescriptorType::InputAttachment => self.input_attachment += 1,
+                    DescriptorType::Mutable => self.mutable += 1,
                 };
             }
+
+            /// Returns the total number of descriptors, of all types.
+            #[inline]
+            pub fn total(&self) -> u32 {
+                0 $(+ self.$name)+
+            }
         }
 
         impl cmp::PartialEq for DescriptorsCount {
@@ -220,6 +228,7 @@ descriptors_count! {
     sampler,
     combined_image_sampler,
     input_attachment,
+    mutable,
 }
 
 /// Pool from which descriptor sets are allocated from.
@@ -229,6 +238,18 @@ descriptors_count! {
 pub struct UnsafeDescriptorPool {
     pool: vk::DescriptorPool,
     device: Arc<Device>,
+    max_sets: u32,
+    // Number of sets that are currently allocated from the pool.
+    allocated_sets: u32,
+    // True if the pool was created with `VK_DESCRIPTOR_POOL_CREATE_HOST_ONLY_BIT_EXT`.
+    host_only: bool,
+    // True if the pool was created with `VK_DESCRIPTOR_POOL_CREATE_UPDATE_AFTER_BIND_BIT_EXT`.
+    update_after_bind: bool,
+    // Number of descriptors of the pool that count against the update-after-bind limit of the
+    // device. 0 if the pool isn't an update-after-bind pool.
+    update_after_bind_descriptors: u32,
+    // Types that the mutable descriptors of the pool can have.
+    mutable_types: SmallVec<[DescriptorType; 8]>,
 }
 
 impl UnsafeDescriptorPool {
@@ -240,21 +261,163 @@ impl UnsafeDescriptorPool {
     /// If `free_descriptor_set_bit` is `true`, then individual descriptor sets can be free'd from
     /// the pool. Otherwise you must reset or destroy the whole pool at once.
     ///
+    /// Pools with mutable descriptors must be created with `with_mutable_types` instead.
+    ///
     /// # Panic
     ///
     /// - Panics if all the descriptors count are 0.
     /// - Panics if `max_sets` is 0.
+    /// - Panics if `count.mutable` is not 0.
     ///
+    #[inline]
     pub fn new(
         device: Arc<Device>,
         count: &DescriptorsCount,
         max_sets: u32,
         free_descriptor_set_bit: bool,
     ) -> Result<UnsafeDescriptorPool, OomError> {
+        UnsafeDescriptorPool::with_mutable_types(
+            device,
+            count,
+            max_sets,
+            free_descriptor_set_bit,
+            false,
+            &[],
+        )
+    }
+
+    /// Same as `new`, but allows allocating mutable descriptors and creating a host-only pool.
+    ///
+    /// `mutable_types` is the list of types that the mutable descriptors allocated from this
+    /// pool can have. The number of mutable descriptors is given by `count.mutable`. A layout
+    /// whose mutable bindings can hold types that aren't in this list, see
+    /// `UnsafeDescriptorSetLayout::mutable_types`, can't be allocated from this pool.
+    ///
+    /// If `host_only` is true, the descriptor sets allocated from the pool live in host memory.
+    /// They can be written to and copied from, but they can't be bound in a command buffer.
+    /// Layouts created with `UnsafeDescriptorSetLayout::with_host_only_pool` can only be
+    /// allocated from a host-only pool.
+    ///
+    /// # Panic
+    ///
+    /// - Panics in the same situations as `new`.
+    /// - Panics if `count.mutable` is not 0 or `host_only` is true, and the
+    ///   `ext_mutable_descriptor_type` extension isn't enabled.
+    /// - Panics if `count.mutable` is not 0 and `mutable_types` is empty.
+    ///
+    #[inline]
+    pub fn with_mutable_types(
+        device: Arc<Device>,
+        count: &DescriptorsCount,
+        max_sets: u32,
+        free_descriptor_set_bit: bool,
+        host_only: bool,
+        mutable_types: &[DescriptorType],
+    ) -> Result<UnsafeDescriptorPool, OomError> {
+        match UnsafeDescriptorPool::new_impl(
+            device,
+            count,
+            max_sets,
+            free_descriptor_set_bit,
+            host_only,
+            mutable_types,
+            false,
+        ) {
+            Ok(pool) => Ok(pool),
+            Err(DescriptorPoolCreationError::OomError(err)) => Err(err),
+            // Only update-after-bind pools have a limit on their number of descriptors.
+            Err(DescriptorPoolCreationError::UpdateAfterBindLimitExceeded { .. }) => {
+                unreachable!()
+            }
+        }
+    }
+
+    /// Same as `new`, but creates an update-after-bind pool.
+    ///
+    /// Layouts that have an update-after-bind binding, see
+    /// `UnsafeDescriptorSetLayout::is_update_after_bind_pool`, can only be allocated from an
+    /// update-after-bind pool.
+    ///
+    /// The descriptors of all the update-after-bind pools of a device count against the
+    /// `max_update_after_bind_descriptors_in_all_pools` limit of the `UpdateAfterBindLimits` of
+    /// the physical device. An error is returned if creating this pool would exceed it. The
+    /// descriptors of the pool are released when the pool is destroyed.
+    ///
+    /// # Panic
+    ///
+    /// - Panics in the same situations as `new`.
+    ///
+    #[inline]
+    pub fn with_update_after_bind(
+        device: Arc<Device>,
+        count: &DescriptorsCount,
+        max_sets: u32,
+        free_descriptor_set_bit: bool,
+    ) -> Result<UnsafeDescriptorPool, DescriptorPoolCreationError> {
+        UnsafeDescriptorPool::new_impl(
+            device,
+            count,
+            max_sets,
+            free_descriptor_set_bit,
+            false,
+            &[],
+            true,
+        )
+    }
+
+    pub(crate) fn new_impl(
+        device: Arc<Device>,
+        count: &DescriptorsCount,
+        max_sets: u32,
+        free_descriptor_set_bit: bool,
+        host_only: bool,
+        mutable_types: &[DescriptorType],
+        update_after_bind: bool,
+    ) -> Result<UnsafeDescriptorPool, DescriptorPoolCreationError> {
         let vk = device.pointers();
 
         assert_ne!(max_sets, 0, "The maximum number of sets can't be 0");
 
+        if count.mutable >= 1 || host_only {
+            assert!(
+                device.loaded_extensions().ext_mutable_descriptor_type,
+                "The `ext_mutable_descriptor_type` extension must be enabled in order to \
+                 allocate mutable descriptors or to create a host-only pool"
+            );
+        }
+
+        assert!(
+            count.mutable == 0 || !mutable_types.is_empty(),
+            "The types of the mutable descriptors of a pool can't be empty"
+        );
+
+        // The descriptors of update-after-bind pools count against a limit shared by all the
+        // pools of the device. The lock is kept until the pool is created.
+        let mut update_after_bind_descriptors = if update_after_bind {
+            let total = device
+                .update_after_bind_descriptors()
+                .lock()
+                .expect("Poisoned mutex");
+            let physical_device = device.physical_device();
+            if let Some(ref limits) = *physical_device
+                .extended_properties()
+                .update_after_bind_limits()
+            {
+                let available = limits
+                    .max_update_after_bind_descriptors_in_all_pools
+                    .saturating_sub(*total);
+                if count.total() > available {
+                    return Err(DescriptorPoolCreationError::UpdateAfterBindLimitExceeded {
+                        requested: count.total(),
+                        available,
+                    });
+                }
+            }
+            Some(total)
+        } else {
+            None
+        };
+
         let mut pool_sizes: SmallVec<[_; 10]> = SmallVec::new();
 
         macro_rules! elem {
@@ -294,21 +457,63 @@ impl UnsafeDescriptorPool {
             vk::DESCRIPTOR_TYPE_COMBINED_IMAGE_SAMPLER
         );
         elem!(input_attachment, vk::DESCRIPTOR_TYPE_INPUT_ATTACHMENT);
+        elem!(mutable, vk::DESCRIPTOR_TYPE_MUTABLE_EXT);
 
         assert!(
             !pool_sizes.is_empty(),
             "All the descriptors count of a pool are 0"
         );
 
+        // One list of types per pool size. Only the list of the mutable pool size isn't empty.
+        let vk_mutable_types: SmallVec<[vk::DescriptorType; 8]> =
+            mutable_types.iter().map(|&ty| ty as u32).collect();
+        let mutable_type_lists: SmallVec<[_; 12]> = pool_sizes
+            .iter()
+            .map(|size| {
+                if size.ty == vk::DESCRIPTOR_TYPE_MUTABLE_EXT {
+                    vk::MutableDescriptorTypeListEXT {
+                        descriptorTypeCount: vk_mutable_types.len() as u32,
+                        pDescriptorTypes: vk_mutable_types.as_ptr(),
+                    }
+                } else {
+                    vk::MutableDescriptorTypeListEXT {
+                        descriptorTypeCount: 0,
+                        pDescriptorTypes: ptr::null(),
+                    }
+                }
+            })
+            .collect();
+
         let pool = unsafe {
+            let mutable_infos = if count.mutable >= 1 {
+                Some(vk::MutableDescriptorTypeCreateInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_MUTABLE_DESCRIPTOR_TYPE_CREATE_INFO_EXT,
+                    pNext: ptr::null(),
+                    mutableDescriptorTypeListCount: mutable_type_lists.len() as u32,
+                    pMutableDescriptorTypeLists: mutable_type_lists.as_ptr(),
+                })
+            } else {
+                None
+            };
+
+            let mut flags = 0;
+            if free_descriptor_set_bit {
+                flags |= vk::DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT;
+            }
+            if host_only {
+                flags |= vk::DESCRIPTOR_POOL_CREATE_HOST_ONLY_BIT_EXT;
+            }
+            if update_after_bind {
+                flags |= vk::DESCRIPTOR_POOL_CREATE_UPDATE_AFTER_BIND_BIT_EXT;
+            }
+
             let infos = vk::DescriptorPoolCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DESCRIPTOR_POOL_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: if free_descriptor_set_bit {
-                    vk::DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT
-                } else {
-                    0
-                },
+                pNext: mutable_infos
+                    .as_ref()
+                    .map(|infos| infos as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
+                flags,
                 maxSets: max_sets,
                 poolSizeCount: pool_sizes.len() as u32,
                 pPoolSizes: pool_sizes.as_ptr(),
@@ -320,32 +525,83 @@ impl UnsafeDescriptorPool {
                 &infos,
                 ptr::null(),
                 output.as_mut_ptr(),
-            ))?;
+            ))
+            .map_err(OomError::from)?;
             output.assume_init()
         };
 
+        if let Some(ref mut total) = update_after_bind_descriptors {
+            **total += count.total();
+        }
+
         Ok(UnsafeDescriptorPool {
             pool: pool,
             device: device.clone(),
+            max_sets,
+            allocated_sets: 0,
+            host_only,
+            update_after_bind,
+            update_after_bind_descriptors: if update_after_bind { count.total() } else { 0 },
+            mutable_types: mutable_types.iter().cloned().collect(),
         })
     }
 
+    /// Returns the types that the mutable descriptors of this pool can have.
+    #[inline]
+    pub fn mutable_types(&self) -> &[DescriptorType] {
+        &self.mutable_types
+    }
+
+    /// Returns true if the pool is host-only.
+    #[inline]
+    pub fn is_host_only(&self) -> bool {
+        self.host_only
+    }
+
+    /// Returns true if the pool is an update-after-bind pool.
+    #[inline]
+    pub fn is_update_after_bind(&self) -> bool {
+        self.update_after_bind
+    }
+
+    /// Returns the maximum number of sets that can be allocated at once from this pool.
+    #[inline]
+    pub fn max_sets(&self) -> u32 {
+        self.max_sets
+    }
+
+    /// Returns the number of sets that can still be allocated from this pool.
+    ///
+    /// Sets that are free'd or a reset of the pool make their space available again. Note that
+    /// an allocation can still fail with `OutOfPoolMemory` or `FragmentedPool` even if this
+    /// value is not 0.
+    #[inline]
+    pub fn remaining_sets(&self) -> u32 {
+        self.max_sets - self.allocated_sets
+    }
+
     /// Allocates descriptor sets from the pool, one for each layout.
     /// Returns an iterator to the allocated sets, or an error.
     ///
     /// The `FragmentedPool` errors often can't be prevented. If the function returns this error,
     /// you should just create a new pool.
     ///
+    /// Returns `DescriptorPoolExhausted` without calling Vulkan if allocating the sets would
+    /// exceed the `max_sets` of the pool.
+    ///
     /// # Panic
     ///
     /// - Panics if one of the layouts wasn't created with the same device as the pool.
+    /// - Panics if one of the layouts was created with `with_host_only_pool` and the pool isn't
+    ///   host-only.
+    /// - Panics if one of the layouts has an update-after-bind binding and the pool isn't an
+    ///   update-after-bind pool.
     ///
     /// # Safety
     ///
     /// See also the `new` function.
     ///
     /// - The total descriptors of the layouts must fit in the pool.
-    /// - The total number of descriptor sets allocated from the pool must not overflow the pool.
     /// - You must ensure that the allocated descriptor sets are no longer in use when the pool
     ///   is destroyed, as destroying the pool is equivalent to freeing all the sets.
     ///
@@ -366,6 +622,16 @@ impl UnsafeDescriptorPool {
                     "Tried to allocate from a pool with a set layout of a different \
                                  device"
                 );
+                assert!(
+                    self.host_only || !l.is_host_only_pool(),
+                    "Tried to allocate a set layout of a host-only pool from a pool that isn't \
+                     host-only"
+                );
+                assert!(
+                    self.update_after_bind || !l.is_update_after_bind_pool(),
+                    "Tried to allocate a set layout with update-after-bind bindings from a pool \
+                     that isn't update-after-bind"
+                );
                 l.internal_object()
             })
             .collect();
@@ -386,6 +652,11 @@ impl UnsafeDescriptorPool {
             });
         }
 
+        let sets_remaining = self.remaining_sets();
+        if num as u64 > sets_remaining as u64 {
+            return Err(DescriptorPoolAllocError::DescriptorPoolExhausted { sets_remaining });
+        }
+
         let infos = vk::DescriptorSetAllocateInfo {
             sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_ALLOCATE_INFO,
             pNext: ptr::null(),
@@ -420,6 +691,7 @@ impl UnsafeDescriptorPool {
         };
 
         output.set_len(num);
+        self.allocated_sets += num as u32;
 
         Ok(UnsafeDescriptorPoolAllocIter {
             sets: output.into_iter(),
@@ -463,6 +735,7 @@ impl UnsafeDescriptorPool {
             sets.len() as u32,
             sets.as_ptr(),
         ))?;
+        self.allocated_sets -= sets.len() as u32;
         Ok(())
     }
 
@@ -476,6 +749,7 @@ impl UnsafeDescriptorPool {
             self.pool,
             0, /* reserved flags */
         ))?;
+        self.allocated_sets = 0;
         Ok(())
     }
 }
@@ -492,6 +766,8 @@ impl fmt::Debug for UnsafeDescriptorPool {
         fmt.debug_struct("UnsafeDescriptorPool")
             .field("raw", &self.pool)
             .field("device", &self.device)
+            .field("max_sets", &self.max_sets)
+            .field("allocated_sets", &self.allocated_sets)
             .finish()
     }
 }
@@ -502,10 +778,66 @@ impl Drop for UnsafeDescriptorPool {
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyDescriptorPool(self.device.internal_object(), self.pool, ptr::null());
+            if self.update_after_bind_descriptors != 0 {
+                let mut total = self
+                    .device
+                    .update_after_bind_descriptors()
+                    .lock()
+                    .expect("Poisoned mutex");
+                *total -= self.update_after_bind_descriptors;
+            }
         }
     }
 }
 
+/// Error that can be returned when creating a descriptor pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DescriptorPoolCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// Creating the pool would exceed the `max_update_after_bind_descriptors_in_all_pools` limit.
+    UpdateAfterBindLimitExceeded {
+        /// Number of descriptors of the pool.
+        requested: u32,
+        /// Number of descriptors that the update-after-bind pools of the device can still have.
+        available: u32,
+    },
+}
+
+impl error::Error for DescriptorPoolCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DescriptorPoolCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DescriptorPoolCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DescriptorPoolCreationError::OomError(_) => "not enough memory available",
+                DescriptorPoolCreationError::UpdateAfterBindLimitExceeded { .. } => {
+                    "creating the pool would exceed the limit of descriptors in all the \
+                     update-after-bind pools"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for DescriptorPoolCreationError {
+    #[inline]
+    fn from(err: OomError) -> DescriptorPoolCreationError {
+        DescriptorPoolCreationError::OomError(err)
+    }
+}
+
 /// Error that can be returned when creating a device.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DescriptorPoolAllocError {
@@ -517,6 +849,11 @@ pub enum DescriptorPoolAllocError {
     FragmentedPool,
     /// There is no more space available in the descriptor pool.
     OutOfPoolMemory,
+    /// Allocating the sets would exceed the maximum number of sets of the pool.
+    DescriptorPoolExhausted {
+        /// Number of sets that could still be allocated from the pool.
+        sets_remaining: u32,
+    },
 }
 
 impl error::Error for DescriptorPoolAllocError {}
@@ -538,6 +875,9 @@ impl fmt::Display for DescriptorPoolAllocError {
                 DescriptorPoolAllocError::OutOfPoolMemory => {
                     "there is no more space available in the descriptor pool"
                 }
+                DescriptorPoolAllocError::DescriptorPoolExhausted { .. } => {
+                    "allocating the sets would exceed the maximum number of sets of the pool"
+                }
             }
         )
     }
@@ -761,6 +1101,53 @@ impl UnsafeDescriptorSet {
             );
         }
     }
+
+    /// Same as `write`, but first checks the writes against the layout of the descriptor set
+    /// with `UnsafeDescriptorSetLayout::validate_writes`. Nothing is written if one of the writes
+    /// is invalid.
+    ///
+    /// # Safety
+    ///
+    /// Same as `write`, except that the writes are checked against `layout`, which must be the
+    /// layout this set was allocated with.
+    ///
+    pub unsafe fn checked_write<I>(
+        &mut self,
+        device: &Device,
+        layout: &UnsafeDescriptorSetLayout,
+        writes: I,
+    ) -> Result<(), DescriptorWriteError>
+    where
+        I: IntoIterator<Item = DescriptorWrite>,
+    {
+        let writes: SmallVec<[DescriptorWrite; 16]> = writes.into_iter().collect();
+        layout.validate_writes(&writes)?;
+        self.write(device, writes.into_iter());
+        Ok(())
+    }
+
+    /// Starts accumulating writes to the descriptor set.
+    ///
+    /// The writes added to the returned object are all performed with a single call to
+    /// `vkUpdateDescriptorSets` when it is committed or dropped. This is cheaper than calling
+    /// `write` multiple times when a set is built from many writes.
+    ///
+    /// # Safety
+    ///
+    /// Same as `write`. In particular, the resources referenced by the writes must be kept alive
+    /// by the caller, and the set must not be in use when the writes are flushed.
+    ///
+    #[inline]
+    pub unsafe fn begin_update<'a>(
+        &'a mut self,
+        device: &'a Device,
+    ) -> UnsafeDescriptorSetUpdate<'a> {
+        UnsafeDescriptorSetUpdate {
+            set: self,
+            device,
+            writes: SmallVec::new(),
+        }
+    }
 }
 
 unsafe impl VulkanObject for UnsafeDescriptorSet {
@@ -780,6 +1167,71 @@ impl fmt::Debug for UnsafeDescriptorSet {
     }
 }
 
+/// Writes to a descriptor set that are accumulated and performed at once.
+///
+/// Created with `UnsafeDescriptorSet::begin_update`. The writes are performed when `commit` is
+/// called or when the object is dropped.
+pub struct UnsafeDescriptorSetUpdate<'a> {
+    set: &'a mut UnsafeDescriptorSet,
+    device: &'a Device,
+    writes: SmallVec<[DescriptorWrite; 16]>,
+}
+
+impl<'a> UnsafeDescriptorSetUpdate<'a> {
+    /// Adds a write to the list of pending writes.
+    #[inline]
+    pub fn write(&mut self, write: DescriptorWrite) -> &mut Self {
+        self.writes.push(write);
+        self
+    }
+
+    /// Returns the number of pending writes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// Returns true if there is no pending write.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Performs all the pending writes with a single `vkUpdateDescriptorSets` call.
+    ///
+    /// This is the same as dropping the object, but makes the intent explicit.
+    #[inline]
+    pub fn commit(mut self) {
+        self.flush();
+    }
+
+    fn flush(&mut self) {
+        if self.writes.is_empty() {
+            return;
+        }
+
+        unsafe {
+            self.set.write(self.device, self.writes.drain(..));
+        }
+    }
+}
+
+impl<'a> Drop for UnsafeDescriptorSetUpdate<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl<'a> fmt::Debug for UnsafeDescriptorSetUpdate<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("UnsafeDescriptorSetUpdate")
+            .field("set", &self.set)
+            .field("writes", &self.writes.len())
+            .finish()
+    }
+}
+
 /// Represents a single write entry to a descriptor set.
 ///
 /// Use the various constructors to build a `DescriptorWrite`. While it is safe to build a
@@ -789,6 +1241,8 @@ pub struct DescriptorWrite {
     binding: u32,
     first_array_element: u32,
     inner: SmallVec<[DescriptorWriteInner; 1]>,
+    // True if the resource wasn't created with the usage that the type of descriptor requires.
+    missing_usage: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -823,6 +1277,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: !image.parent().inner().image.usage_storage(),
             inner: smallvec!({
                 let layout = image.descriptor_set_storage_image_layout() as u32;
                 DescriptorWriteInner::StorageImage(image.inner().internal_object(), layout)
@@ -835,6 +1290,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: false,
             inner: smallvec!(DescriptorWriteInner::Sampler(sampler.internal_object())),
         }
     }
@@ -847,6 +1303,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: !image.parent().inner().image.usage_sampled(),
             inner: smallvec!({
                 let layout = image.descriptor_set_sampled_image_layout() as u32;
                 DescriptorWriteInner::SampledImage(image.inner().internal_object(), layout)
@@ -867,6 +1324,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: !image.parent().inner().image.usage_sampled(),
             inner: smallvec!({
                 let layout = image.descriptor_set_combined_image_sampler_layout() as u32;
                 DescriptorWriteInner::CombinedImageSampler(
@@ -892,6 +1350,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: false,
             inner: smallvec!(DescriptorWriteInner::UniformTexelBuffer(
                 view.internal_object()
             )),
@@ -912,6 +1371,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: false,
             inner: smallvec!(DescriptorWriteInner::StorageTexelBuffer(
                 view.internal_object()
             )),
@@ -946,6 +1406,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: !buffer.usage_uniform_buffer(),
             inner: smallvec!({
                 DescriptorWriteInner::UniformBuffer(buffer.internal_object(), offset, size)
             }),
@@ -980,6 +1441,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: !buffer.usage_storage_buffer(),
             inner: smallvec!({
                 DescriptorWriteInner::StorageBuffer(buffer.internal_object(), offset, size)
             }),
@@ -1018,6 +1480,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: !buffer.usage_uniform_buffer(),
             inner: smallvec!(DescriptorWriteInner::DynamicUniformBuffer(
                 buffer.internal_object(),
                 offset,
@@ -1058,6 +1521,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: !buffer.usage_storage_buffer(),
             inner: smallvec!(DescriptorWriteInner::DynamicStorageBuffer(
                 buffer.internal_object(),
                 offset,
@@ -1074,6 +1538,7 @@ impl DescriptorWrite {
         DescriptorWrite {
             binding: binding,
             first_array_element: array_element,
+            missing_usage: !image.parent().inner().image.usage_input_attachment(),
             inner: smallvec!({
                 let layout = image.descriptor_set_input_attachment_layout() as u32;
                 DescriptorWriteInner::InputAttachment(image.inner().internal_object(), layout)
@@ -1081,6 +1546,31 @@ impl DescriptorWrite {
         }
     }
 
+    /// Returns the binding that this write updates.
+    #[inline]
+    pub fn binding(&self) -> u32 {
+        self.binding
+    }
+
+    /// Returns the first array element of the binding that this write updates.
+    #[inline]
+    pub fn first_array_element(&self) -> u32 {
+        self.first_array_element
+    }
+
+    /// Returns the number of array elements that this write updates.
+    #[inline]
+    pub fn num_descriptors(&self) -> u32 {
+        self.inner.len() as u32
+    }
+
+    // Returns true if the resource of this write is missing the usage that its type of
+    // descriptor requires.
+    #[inline]
+    pub(crate) fn missing_usage(&self) -> bool {
+        self.missing_usage
+    }
+
     /// Returns the type corresponding to this write.
     #[inline]
     pub fn ty(&self) -> DescriptorType {
@@ -1108,10 +1598,15 @@ impl DescriptorWrite {
 
 #[cfg(test)]
 mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
     use descriptor::descriptor::DescriptorBufferDesc;
     use descriptor::descriptor::DescriptorDesc;
     use descriptor::descriptor::DescriptorDescTy;
     use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorPoolAllocError;
+    use descriptor::descriptor_set::DescriptorPoolCreationError;
+    use descriptor::descriptor_set::DescriptorWrite;
     use descriptor::descriptor_set::DescriptorsCount;
     use descriptor::descriptor_set::UnsafeDescriptorPool;
     use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
@@ -1141,6 +1636,32 @@ mod tests {
         });
     }
 
+    #[test]
+    fn update_after_bind_limit() {
+        let (device, _) = gfx_dev_and_queue!();
+        let limit = match *device
+            .physical_device()
+            .extended_properties()
+            .update_after_bind_limits()
+        {
+            Some(ref limits)
+                if limits.max_update_after_bind_descriptors_in_all_pools < u32::MAX =>
+            {
+                limits.max_update_after_bind_descriptors_in_all_pools
+            }
+            _ => return,
+        };
+        let desc = DescriptorsCount {
+            uniform_buffer: limit + 1,
+            ..DescriptorsCount::zero()
+        };
+
+        match UnsafeDescriptorPool::with_update_after_bind(device, &desc, 1, false) {
+            Err(DescriptorPoolCreationError::UpdateAfterBindLimitExceeded { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn zero_descriptors() {
         let (device, _) = gfx_dev_and_queue!();
@@ -1179,6 +1700,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn batched_update() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(false),
+                storage: false,
+            }),
+            array_count: 2,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let set_layout =
+            UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(layout))).unwrap();
+
+        let desc = DescriptorsCount {
+            uniform_buffer: 2,
+            ..DescriptorsCount::zero()
+        };
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, [0u32; 4])
+                .unwrap();
+
+        let mut pool = UnsafeDescriptorPool::new(device.clone(), &desc, 1, false).unwrap();
+        unsafe {
+            let mut set = pool.alloc(iter::once(&set_layout)).unwrap().next().unwrap();
+            let mut update = set.begin_update(&device);
+            update
+                .write(DescriptorWrite::uniform_buffer(0, 0, &buffer))
+                .write(DescriptorWrite::uniform_buffer(0, 1, &buffer));
+            assert_eq!(update.len(), 2);
+            update.commit();
+        }
+    }
+
     #[test]
     fn alloc_diff_device() {
         let (device1, _) = gfx_dev_and_queue!();
@@ -1229,4 +1788,43 @@ mod tests {
             assert_eq!(sets.count(), 0);
         }
     }
+
+    #[test]
+    fn alloc_exhausted() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(false),
+                storage: false,
+            }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let set_layout =
+            UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(layout))).unwrap();
+
+        let desc = DescriptorsCount {
+            uniform_buffer: 10,
+            ..DescriptorsCount::zero()
+        };
+
+        let mut pool = UnsafeDescriptorPool::new(device, &desc, 2, false).unwrap();
+        assert_eq!(pool.remaining_sets(), 2);
+        unsafe {
+            let sets = pool.alloc(iter::once(&set_layout)).unwrap();
+            assert_eq!(sets.count(), 1);
+            assert_eq!(pool.remaining_sets(), 1);
+
+            match pool.alloc(vec![&set_layout, &set_layout]) {
+                Err(DescriptorPoolAllocError::DescriptorPoolExhausted { sets_remaining: 1 }) => (),
+                _ => panic!(),
+            }
+
+            pool.reset().unwrap();
+            assert_eq!(pool.remaining_sets(), 2);
+        }
+    }
 }
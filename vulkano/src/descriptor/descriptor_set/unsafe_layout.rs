@@ -8,21 +8,31 @@
 // according to those terms.
 
 use smallvec::SmallVec;
+use std::error;
 use std::fmt;
+use std::iter;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
 
 use check_errors;
 use vk;
+use Error;
 use OomError;
 use VulkanObject;
 
+use descriptor::descriptor::DescriptorBindingFlags;
 use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
+use descriptor::descriptor::DescriptorType;
 use descriptor::descriptor_set::DescriptorSetDesc;
+use descriptor::descriptor_set::DescriptorWrite;
 use descriptor::descriptor_set::DescriptorsCount;
+use descriptor::pipeline_layout::exceeded_update_after_bind_set_limit;
+use descriptor::pipeline_layout::DescriptorCounts;
 use device::Device;
 use device::DeviceOwned;
+use features::Features;
 
 /// Describes to the Vulkan implementation the layout of all descriptors within a descriptor set.
 ///
@@ -36,8 +46,15 @@ pub struct UnsafeDescriptorSetLayout {
     device: Arc<Device>,
     // Descriptors.
     descriptors: SmallVec<[Option<DescriptorDesc>; 32]>,
+    // Flags of each binding.
+    binding_flags: SmallVec<[DescriptorBindingFlags; 32]>,
     // Number of descriptors.
     descriptors_count: DescriptorsCount,
+    // True if the layout was created with `VK_DESCRIPTOR_SET_LAYOUT_CREATE_HOST_ONLY_POOL_BIT_EXT`.
+    host_only_pool: bool,
+    // True if the layout was created with
+    // `VK_DESCRIPTOR_SET_LAYOUT_CREATE_UPDATE_AFTER_BIND_POOL_BIT_EXT`.
+    update_after_bind_pool: bool,
 }
 
 impl UnsafeDescriptorSetLayout {
@@ -46,20 +63,145 @@ impl UnsafeDescriptorSetLayout {
     /// The descriptors must be passed in the order of the bindings. In order words, descriptor
     /// at bind point 0 first, then descriptor at bind point 1, and so on. If a binding must remain
     /// empty, you can make the iterator yield `None` for an element.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the descriptors is mutable and the `mutable_descriptor_type` feature
+    ///   isn't enabled, or if its list of types is invalid. Use `with_binding_flags` in order to
+    ///   get an error instead.
+    ///
     pub fn new<I>(
         device: Arc<Device>,
         descriptors: I,
     ) -> Result<UnsafeDescriptorSetLayout, OomError>
     where
         I: IntoIterator<Item = Option<DescriptorDesc>>,
+    {
+        match UnsafeDescriptorSetLayout::with_binding_flags(device, descriptors, iter::empty()) {
+            Ok(layout) => Ok(layout),
+            Err(DescriptorSetLayoutError::OomError(err)) => Err(err),
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Builds a new `UnsafeDescriptorSetLayout` with the given descriptors and binding flags.
+    ///
+    /// The descriptors are passed the same way as for `new`. `binding_flags` yields the flags of
+    /// each binding, in the order of the bindings. Bindings for which no flags are provided use
+    /// `DescriptorBindingFlags::none()`, and the flags of empty bindings are ignored.
+    ///
+    /// If one of the bindings is update-after-bind, the descriptors of the layout are checked
+    /// against the `UpdateAfterBindLimits` of the physical device, and the descriptor sets of
+    /// this layout must be allocated from an update-after-bind pool, see
+    /// `UnsafeDescriptorPool::with_update_after_bind`.
+    #[inline]
+    pub fn with_binding_flags<I, F>(
+        device: Arc<Device>,
+        descriptors: I,
+        binding_flags: F,
+    ) -> Result<UnsafeDescriptorSetLayout, DescriptorSetLayoutError>
+    where
+        I: IntoIterator<Item = Option<DescriptorDesc>>,
+        F: IntoIterator<Item = DescriptorBindingFlags>,
+    {
+        UnsafeDescriptorSetLayout::new_impl(device, descriptors, binding_flags, false)
+    }
+
+    /// Same as `with_binding_flags`, but the descriptor sets of this layout can only be
+    /// allocated from a host-only pool, see `UnsafeDescriptorPool::with_mutable_types`.
+    ///
+    /// Requires the `ext_mutable_descriptor_type` extension and the `mutable_descriptor_type`
+    /// feature.
+    #[inline]
+    pub fn with_host_only_pool<I, F>(
+        device: Arc<Device>,
+        descriptors: I,
+        binding_flags: F,
+    ) -> Result<UnsafeDescriptorSetLayout, DescriptorSetLayoutError>
+    where
+        I: IntoIterator<Item = Option<DescriptorDesc>>,
+        F: IntoIterator<Item = DescriptorBindingFlags>,
+    {
+        UnsafeDescriptorSetLayout::new_impl(device, descriptors, binding_flags, true)
+    }
+
+    fn new_impl<I, F>(
+        device: Arc<Device>,
+        descriptors: I,
+        binding_flags: F,
+        host_only_pool: bool,
+    ) -> Result<UnsafeDescriptorSetLayout, DescriptorSetLayoutError>
+    where
+        I: IntoIterator<Item = Option<DescriptorDesc>>,
+        F: IntoIterator<Item = DescriptorBindingFlags>,
     {
         let descriptors = descriptors.into_iter().collect::<SmallVec<[_; 32]>>();
         let mut descriptors_count = DescriptorsCount::zero();
 
-        let bindings = descriptors
+        let binding_flags = {
+            let mut flags = binding_flags.into_iter().collect::<SmallVec<[_; 32]>>();
+            flags.resize(descriptors.len(), DescriptorBindingFlags::none());
+            for (flags, desc) in flags.iter_mut().zip(descriptors.iter()) {
+                if desc.is_none() {
+                    *flags = DescriptorBindingFlags::none();
+                }
+            }
+            flags
+        };
+
+        if binding_flags.iter().any(|flags| flags.partially_bound)
+            && !device.enabled_features().descriptor_binding_partially_bound
+        {
+            return Err(DescriptorSetLayoutError::PartiallyBoundFeatureNotEnabled);
+        }
+
+        let update_after_bind_pool = binding_flags.iter().any(|flags| flags.update_after_bind);
+        if update_after_bind_pool {
+            check_update_after_bind(&device, &descriptors, &binding_flags)?;
+        }
+
+        let has_mutable = descriptors.iter().any(|desc| match desc {
+            Some(DescriptorDesc {
+                ty: DescriptorDescTy::Mutable(_),
+                ..
+            }) => true,
+            _ => false,
+        });
+        if (has_mutable || host_only_pool)
+            && (!device.loaded_extensions().ext_mutable_descriptor_type
+                || !device.enabled_features().mutable_descriptor_type)
+        {
+            return Err(DescriptorSetLayoutError::MutableDescriptorTypeNotEnabled);
+        }
+
+        for (binding, desc) in descriptors.iter().enumerate() {
+            if let Some(DescriptorDesc {
+                ty: DescriptorDescTy::Mutable(ref types),
+                ..
+            }) = *desc
+            {
+                let valid = types.iter().enumerate().all(|(num, ty)| match *ty {
+                    DescriptorType::Mutable
+                    | DescriptorType::UniformBufferDynamic
+                    | DescriptorType::StorageBufferDynamic => false,
+                    _ => !types[..num].contains(ty),
+                });
+                if types.is_empty() || !valid {
+                    return Err(DescriptorSetLayoutError::InvalidMutableTypeList {
+                        binding: binding as u32,
+                    });
+                }
+            }
+        }
+
+        // The lists of types of the mutable descriptors, indexed like `bindings`.
+        let mut mutable_types: SmallVec<[SmallVec<[vk::DescriptorType; 8]>; 32]> = SmallVec::new();
+
+        let (bindings, vk_binding_flags): (SmallVec<[_; 32]>, SmallVec<[_; 32]>) = descriptors
             .iter()
+            .zip(binding_flags.iter())
             .enumerate()
-            .filter_map(|(binding, desc)| {
+            .filter_map(|(binding, (desc, flags))| {
                 let desc = match desc {
                     Some(d) => d,
                     None => return None,
@@ -71,23 +213,83 @@ impl UnsafeDescriptorSetLayout {
                 let ty = desc.ty.ty().unwrap(); // TODO: shouldn't panic
                 descriptors_count.add_one(ty);
 
-                Some(vk::DescriptorSetLayoutBinding {
-                    binding: binding as u32,
-                    descriptorType: ty as u32,
-                    descriptorCount: desc.array_count,
-                    stageFlags: desc.stages.into_vulkan_bits(),
-                    pImmutableSamplers: ptr::null(), // FIXME: not yet implemented
-                })
+                mutable_types.push(match desc.ty {
+                    DescriptorDescTy::Mutable(ref types) => {
+                        types.iter().map(|&ty| ty as u32).collect()
+                    }
+                    _ => SmallVec::new(),
+                });
+
+                Some((
+                    vk::DescriptorSetLayoutBinding {
+                        binding: binding as u32,
+                        descriptorType: ty as u32,
+                        descriptorCount: desc.array_count,
+                        stageFlags: desc.stages.into_vulkan_bits(),
+                        pImmutableSamplers: ptr::null(), // FIXME: not yet implemented
+                    },
+                    flags.into_vulkan_bits(),
+                ))
             })
-            .collect::<SmallVec<[_; 32]>>();
+            .unzip();
 
         // Note that it seems legal to have no descriptor at all in the set.
 
         let layout = unsafe {
+            let mutable_type_lists: SmallVec<[_; 32]> = mutable_types
+                .iter()
+                .map(|types| vk::MutableDescriptorTypeListEXT {
+                    descriptorTypeCount: types.len() as u32,
+                    pDescriptorTypes: types.as_ptr(),
+                })
+                .collect();
+
+            let mutable_infos = if has_mutable {
+                Some(vk::MutableDescriptorTypeCreateInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_MUTABLE_DESCRIPTOR_TYPE_CREATE_INFO_EXT,
+                    pNext: ptr::null(),
+                    mutableDescriptorTypeListCount: mutable_type_lists.len() as u32,
+                    pMutableDescriptorTypeLists: mutable_type_lists.as_ptr(),
+                })
+            } else {
+                None
+            };
+
+            let binding_flags_infos = if vk_binding_flags.iter().any(|&flags| flags != 0) {
+                Some(vk::DescriptorSetLayoutBindingFlagsCreateInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_BINDING_FLAGS_CREATE_INFO_EXT,
+                    pNext: mutable_infos
+                        .as_ref()
+                        .map(|infos| infos as *const _ as *const _)
+                        .unwrap_or(ptr::null()),
+                    bindingCount: vk_binding_flags.len() as u32,
+                    pBindingFlags: vk_binding_flags.as_ptr(),
+                })
+            } else {
+                None
+            };
+
             let infos = vk::DescriptorSetLayoutCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0, // reserved
+                pNext: binding_flags_infos
+                    .as_ref()
+                    .map(|infos| infos as *const _ as *const _)
+                    .or_else(|| {
+                        mutable_infos
+                            .as_ref()
+                            .map(|infos| infos as *const _ as *const _)
+                    })
+                    .unwrap_or(ptr::null()),
+                flags: {
+                    let mut flags = 0;
+                    if host_only_pool {
+                        flags |= vk::DESCRIPTOR_SET_LAYOUT_CREATE_HOST_ONLY_POOL_BIT_EXT;
+                    }
+                    if update_after_bind_pool {
+                        flags |= vk::DESCRIPTOR_SET_LAYOUT_CREATE_UPDATE_AFTER_BIND_POOL_BIT_EXT;
+                    }
+                    flags
+                },
                 bindingCount: bindings.len() as u32,
                 pBindings: bindings.as_ptr(),
             };
@@ -107,10 +309,121 @@ impl UnsafeDescriptorSetLayout {
             layout: layout,
             device: device,
             descriptors: descriptors,
+            binding_flags: binding_flags,
             descriptors_count: descriptors_count,
+            host_only_pool,
+            update_after_bind_pool,
         })
     }
 
+    /// Returns true if one of the bindings of this layout is update-after-bind, in which case
+    /// its descriptor sets must be allocated from an update-after-bind pool.
+    #[inline]
+    pub fn is_update_after_bind_pool(&self) -> bool {
+        self.update_after_bind_pool
+    }
+
+    /// Returns true if the descriptor sets of this layout must be allocated from a host-only
+    /// pool.
+    #[inline]
+    pub fn is_host_only_pool(&self) -> bool {
+        self.host_only_pool
+    }
+
+    /// Returns all the types that the mutable bindings of this layout can hold, without
+    /// duplicates.
+    ///
+    /// A pool must be created with at least these types, see
+    /// `UnsafeDescriptorPool::with_mutable_types`, in order to allocate the descriptor sets of
+    /// this layout. Returns an empty list if the layout has no mutable binding.
+    pub fn mutable_types(&self) -> SmallVec<[DescriptorType; 8]> {
+        let mut types: SmallVec<[DescriptorType; 8]> = SmallVec::new();
+
+        for desc in self.descriptors.iter().filter_map(|d| d.as_ref()) {
+            if let DescriptorDescTy::Mutable(ref desc_types) = desc.ty {
+                for &ty in desc_types {
+                    if !types.contains(&ty) {
+                        types.push(ty);
+                    }
+                }
+            }
+        }
+
+        types
+    }
+
+    /// Checks whether `write` is compatible with this layout.
+    ///
+    /// The binding of the write must exist, all the array elements that it updates must be in
+    /// range, and its type must be the type of the binding. For mutable bindings, the type of the
+    /// write must be one of the types that the binding can hold. The resource of the write must
+    /// also have the usage that its type of descriptor requires.
+    pub fn check_write(&self, write: &DescriptorWrite) -> Result<(), DescriptorWriteError> {
+        let binding = write.binding();
+        let desc = match self.descriptors.get(binding as usize) {
+            Some(&Some(ref desc)) => desc,
+            _ => return Err(DescriptorWriteError::EmptyBinding { binding }),
+        };
+
+        if write.first_array_element() >= desc.array_count {
+            return Err(DescriptorWriteError::ArrayOutOfRange {
+                binding,
+                array_element: write.first_array_element(),
+            });
+        }
+
+        if desc.array_count - write.first_array_element() < write.num_descriptors() {
+            return Err(DescriptorWriteError::ArrayOutOfRange {
+                binding,
+                array_element: desc.array_count,
+            });
+        }
+
+        let ty = write.ty();
+        let allowed = match desc.ty {
+            DescriptorDescTy::Mutable(ref types) => types.contains(&ty),
+            ref desc_ty => match desc_ty.ty() {
+                Some(desc_ty) => desc_ty == ty,
+                None => true,
+            },
+        };
+        if !allowed {
+            return Err(DescriptorWriteError::WrongDescriptorType { binding, ty });
+        }
+
+        if write.missing_usage() {
+            return Err(DescriptorWriteError::MissingUsage { binding });
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether all the writes of a batch are compatible with this layout, with
+    /// `check_write`.
+    ///
+    /// This is useful to validate writes that are built manually, for example from the
+    /// reflection of a shader, before passing them to `UnsafeDescriptorSet::write`. Returns the
+    /// error of the first invalid write.
+    #[inline]
+    pub fn validate_writes(&self, writes: &[DescriptorWrite]) -> Result<(), DescriptorWriteError> {
+        for write in writes {
+            self.check_write(write)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the flags of the given binding.
+    ///
+    /// Returns `DescriptorBindingFlags::none()` if the binding is empty or out of range.
+    #[inline]
+    pub fn binding_flags(&self, binding: usize) -> DescriptorBindingFlags {
+        self.binding_flags
+            .get(binding)
+            .cloned()
+            .unwrap_or(DescriptorBindingFlags::none())
+    }
+
     /// Returns the number of descriptors of each type.
     #[inline]
     pub fn descriptors_count(&self) -> &DescriptorsCount {
@@ -166,14 +479,278 @@ impl Drop for UnsafeDescriptorSetLayout {
     }
 }
 
+// Returns whether the update-after-bind feature of `ty` is enabled, or `None` if there is none.
+pub(crate) fn update_after_bind_feature(features: &Features, ty: DescriptorType) -> Option<bool> {
+    match ty {
+        DescriptorType::Sampler
+        | DescriptorType::CombinedImageSampler
+        | DescriptorType::SampledImage => {
+            Some(features.descriptor_binding_sampled_image_update_after_bind)
+        }
+        DescriptorType::StorageImage => {
+            Some(features.descriptor_binding_storage_image_update_after_bind)
+        }
+        DescriptorType::UniformTexelBuffer => {
+            Some(features.descriptor_binding_uniform_texel_buffer_update_after_bind)
+        }
+        DescriptorType::StorageTexelBuffer => {
+            Some(features.descriptor_binding_storage_texel_buffer_update_after_bind)
+        }
+        DescriptorType::UniformBuffer => {
+            Some(features.descriptor_binding_uniform_buffer_update_after_bind)
+        }
+        DescriptorType::StorageBuffer => {
+            Some(features.descriptor_binding_storage_buffer_update_after_bind)
+        }
+        _ => None,
+    }
+}
+
+// Checks that the update-after-bind bindings are allowed by the enabled features, and that the
+// descriptors of the layout don't exceed the update-after-bind limits.
+fn check_update_after_bind(
+    device: &Device,
+    descriptors: &[Option<DescriptorDesc>],
+    binding_flags: &[DescriptorBindingFlags],
+) -> Result<(), DescriptorSetLayoutError> {
+    let features = device.enabled_features();
+    let mut counts = DescriptorCounts::default();
+
+    for (binding, (desc, flags)) in descriptors.iter().zip(binding_flags.iter()).enumerate() {
+        let desc = match *desc {
+            Some(ref desc) => desc,
+            None => continue,
+        };
+
+        counts.add(desc);
+
+        if !flags.update_after_bind {
+            continue;
+        }
+
+        let feature = match desc
+            .ty
+            .ty()
+            .and_then(|ty| update_after_bind_feature(features, ty))
+        {
+            Some(feature) => feature,
+            None => {
+                return Err(DescriptorSetLayoutError::UpdateAfterBindUnsupportedType {
+                    binding: binding as u32,
+                });
+            }
+        };
+
+        if !feature {
+            return Err(DescriptorSetLayoutError::UpdateAfterBindFeatureNotEnabled {
+                binding: binding as u32,
+            });
+        }
+    }
+
+    let physical_device = device.physical_device();
+    if let Some(ref limits) = *physical_device
+        .extended_properties()
+        .update_after_bind_limits()
+    {
+        if let Some((ty, limit, requested)) = exceeded_update_after_bind_set_limit(&counts, limits)
+        {
+            return Err(DescriptorSetLayoutError::UpdateAfterBindLimitExceeded {
+                ty,
+                limit,
+                requested,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when creating a descriptor set layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DescriptorSetLayoutError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// A binding is partially bound, but the `descriptor_binding_partially_bound` feature isn't
+    /// enabled.
+    PartiallyBoundFeatureNotEnabled,
+
+    /// A binding is mutable or the layout is for a host-only pool, but the
+    /// `ext_mutable_descriptor_type` extension or the `mutable_descriptor_type` feature isn't
+    /// enabled.
+    MutableDescriptorTypeNotEnabled,
+
+    /// The list of types of a mutable binding is empty, contains duplicates, or contains a type
+    /// that can't be mutable.
+    InvalidMutableTypeList {
+        /// The binding of the mutable descriptor.
+        binding: u32,
+    },
+
+    /// A binding is update-after-bind, but the `descriptor_binding_*_update_after_bind` feature
+    /// corresponding to its type isn't enabled.
+    UpdateAfterBindFeatureNotEnabled {
+        /// The update-after-bind binding.
+        binding: u32,
+    },
+
+    /// A binding is update-after-bind, but its type can't be updated after being bound.
+    UpdateAfterBindUnsupportedType {
+        /// The update-after-bind binding.
+        binding: u32,
+    },
+
+    /// The layout has an update-after-bind binding, and its descriptors exceed one of the
+    /// `max_descriptor_set_*` limits of `UpdateAfterBindLimits`.
+    UpdateAfterBindLimitExceeded {
+        /// The type of descriptor the limit applies to.
+        ty: DescriptorType,
+        /// The limit that must be fulfilled.
+        limit: u32,
+        /// What was requested.
+        requested: u32,
+    },
+}
+
+impl error::Error for DescriptorSetLayoutError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            DescriptorSetLayoutError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DescriptorSetLayoutError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DescriptorSetLayoutError::OomError(_) => "not enough memory available",
+                DescriptorSetLayoutError::PartiallyBoundFeatureNotEnabled => {
+                    "the `descriptor_binding_partially_bound` feature is not enabled"
+                }
+                DescriptorSetLayoutError::MutableDescriptorTypeNotEnabled => {
+                    "the `ext_mutable_descriptor_type` extension or the `mutable_descriptor_type` \
+                     feature is not enabled"
+                }
+                DescriptorSetLayoutError::InvalidMutableTypeList { .. } => {
+                    "the list of types of a mutable binding is empty, contains duplicates, or \
+                     contains a type that can't be mutable"
+                }
+                DescriptorSetLayoutError::UpdateAfterBindFeatureNotEnabled { .. } => {
+                    "the `descriptor_binding_*_update_after_bind` feature corresponding to the \
+                     type of an update-after-bind binding is not enabled"
+                }
+                DescriptorSetLayoutError::UpdateAfterBindUnsupportedType { .. } => {
+                    "the type of an update-after-bind binding can't be updated after being bound"
+                }
+                DescriptorSetLayoutError::UpdateAfterBindLimitExceeded { .. } => {
+                    "the descriptors of the layout exceed an update-after-bind limit"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for DescriptorSetLayoutError {
+    #[inline]
+    fn from(err: OomError) -> DescriptorSetLayoutError {
+        DescriptorSetLayoutError::OomError(err)
+    }
+}
+
+impl From<Error> for DescriptorSetLayoutError {
+    #[inline]
+    fn from(err: Error) -> DescriptorSetLayoutError {
+        match err {
+            e @ Error::OutOfHostMemory | e @ Error::OutOfDeviceMemory => {
+                DescriptorSetLayoutError::OomError(e.into())
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+/// Error that can happen when checking a descriptor write against a layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DescriptorWriteError {
+    /// The binding of the write is empty or out of range.
+    EmptyBinding {
+        /// The binding of the write.
+        binding: u32,
+    },
+
+    /// The array element of the write is out of range.
+    ArrayOutOfRange {
+        /// The binding of the write.
+        binding: u32,
+        /// The array element of the write.
+        array_element: u32,
+    },
+
+    /// The type of the write isn't allowed by the binding.
+    WrongDescriptorType {
+        /// The binding of the write.
+        binding: u32,
+        /// The type of the write.
+        ty: DescriptorType,
+    },
+
+    /// The resource of the write doesn't have the usage that the type of the write requires.
+    MissingUsage {
+        /// The binding of the write.
+        binding: u32,
+    },
+}
+
+impl error::Error for DescriptorWriteError {}
+
+impl fmt::Display for DescriptorWriteError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                DescriptorWriteError::EmptyBinding { .. } => {
+                    "the binding of the write is empty or out of range"
+                }
+                DescriptorWriteError::ArrayOutOfRange { .. } => {
+                    "the array element of the write is out of range"
+                }
+                DescriptorWriteError::WrongDescriptorType { .. } => {
+                    "the type of the write isn't allowed by the binding"
+                }
+                DescriptorWriteError::MissingUsage { .. } => {
+                    "the resource of the write doesn't have the usage required by its type"
+                }
+            }
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use descriptor::descriptor::DescriptorBindingFlags;
     use descriptor::descriptor::DescriptorBufferDesc;
     use descriptor::descriptor::DescriptorDesc;
     use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorType;
     use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorSetDesc;
+    use descriptor::descriptor_set::DescriptorSetLayoutError;
+    use descriptor::descriptor_set::DescriptorWrite;
+    use descriptor::descriptor_set::DescriptorWriteError;
     use descriptor::descriptor_set::DescriptorsCount;
     use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use sampler::Sampler;
     use std::iter;
 
     #[test]
@@ -206,4 +783,210 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn dynamic_descriptor_count() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer = |dynamic, storage, array_count| DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(dynamic),
+                storage,
+            }),
+            array_count,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let sl = UnsafeDescriptorSetLayout::new(
+            device.clone(),
+            vec![
+                Some(buffer(true, false, 3)),
+                Some(buffer(false, false, 1)),
+                None,
+                Some(buffer(true, true, 1)),
+            ]
+            .into_iter(),
+        )
+        .unwrap();
+
+        assert_eq!(sl.dynamic_descriptor_count(), 4);
+        assert_eq!(sl.dynamic_offset_index(0, 2), Some(2));
+        assert_eq!(sl.dynamic_offset_index(0, 3), None);
+        assert_eq!(sl.dynamic_offset_index(1, 0), None);
+        assert_eq!(sl.dynamic_offset_index(3, 0), Some(3));
+    }
+
+    #[test]
+    fn partially_bound_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(false),
+                storage: false,
+            }),
+            array_count: 8,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let flags = DescriptorBindingFlags {
+            partially_bound: true,
+            ..DescriptorBindingFlags::none()
+        };
+
+        match UnsafeDescriptorSetLayout::with_binding_flags(
+            device,
+            iter::once(Some(layout)),
+            iter::once(flags),
+        ) {
+            Err(DescriptorSetLayoutError::PartiallyBoundFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn update_after_bind_checks() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer = |dynamic| DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(dynamic),
+                storage: true,
+            }),
+            array_count: 8,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let flags = DescriptorBindingFlags {
+            update_after_bind: true,
+            ..DescriptorBindingFlags::none()
+        };
+
+        match UnsafeDescriptorSetLayout::with_binding_flags(
+            device.clone(),
+            iter::once(Some(buffer(false))),
+            iter::once(flags),
+        ) {
+            Err(DescriptorSetLayoutError::UpdateAfterBindFeatureNotEnabled { binding: 0 }) => (),
+            _ => panic!(),
+        }
+
+        // Dynamic buffers can never be updated after being bound.
+        match UnsafeDescriptorSetLayout::with_binding_flags(
+            device,
+            vec![None, Some(buffer(true))],
+            vec![DescriptorBindingFlags::none(), flags],
+        ) {
+            Err(DescriptorSetLayoutError::UpdateAfterBindUnsupportedType { binding: 1 }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn mutable_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Mutable(vec![
+                DescriptorType::SampledImage,
+                DescriptorType::StorageBuffer,
+            ]),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        match UnsafeDescriptorSetLayout::with_binding_flags(
+            device,
+            iter::once(Some(layout)),
+            iter::empty(),
+        ) {
+            Err(DescriptorSetLayoutError::MutableDescriptorTypeNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn check_write() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 2,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let sl = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(layout))).unwrap();
+        let sampler = Sampler::simple_repeat_linear(device);
+
+        assert_eq!(
+            sl.check_write(&DescriptorWrite::sampler(0, 1, &sampler)),
+            Ok(())
+        );
+        assert_eq!(
+            sl.check_write(&DescriptorWrite::sampler(0, 2, &sampler)),
+            Err(DescriptorWriteError::ArrayOutOfRange {
+                binding: 0,
+                array_element: 2,
+            })
+        );
+        assert_eq!(
+            sl.check_write(&DescriptorWrite::sampler(1, 0, &sampler)),
+            Err(DescriptorWriteError::EmptyBinding { binding: 1 })
+        );
+    }
+
+    #[test]
+    fn validate_writes() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                dynamic: Some(false),
+                storage: true,
+            }),
+            array_count: 1,
+            stages: ShaderStages::all(),
+            readonly: true,
+        };
+        let sl = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(layout))).unwrap();
+
+        let storage = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage {
+                storage_buffer: true,
+                ..BufferUsage::none()
+            },
+            false,
+            0u32,
+        )
+        .unwrap();
+        let uniform =
+            CpuAccessibleBuffer::from_data(device, BufferUsage::uniform_buffer(), false, 0u32)
+                .unwrap();
+
+        unsafe {
+            assert_eq!(
+                sl.validate_writes(&[DescriptorWrite::storage_buffer(0, 0, &storage)]),
+                Ok(())
+            );
+            assert_eq!(
+                sl.validate_writes(&[
+                    DescriptorWrite::storage_buffer(0, 0, &storage),
+                    DescriptorWrite::storage_buffer(0, 0, &uniform),
+                ]),
+                Err(DescriptorWriteError::MissingUsage { binding: 0 })
+            );
+            assert_eq!(
+                sl.validate_writes(&[DescriptorWrite::uniform_buffer(0, 0, &uniform)]),
+                Err(DescriptorWriteError::WrongDescriptorType {
+                    binding: 0,
+                    ty: DescriptorType::UniformBuffer,
+                })
+            );
+        }
+    }
 }
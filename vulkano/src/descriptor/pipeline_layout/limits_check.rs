@@ -12,29 +12,31 @@
 use std::error;
 use std::fmt;
 
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
 use descriptor::descriptor::DescriptorType;
 use descriptor::descriptor::ShaderStages;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
 use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
 use instance::Limits;
+use instance::UpdateAfterBindLimits;
 
 /// Checks whether the pipeline layout description fulfills the device limits requirements.
 pub fn check_desc_against_limits<D>(
     desc: &D,
     limits: Limits,
+    update_after_bind_limits: Option<&UpdateAfterBindLimits>,
 ) -> Result<(), PipelineLayoutLimitsError>
 where
     D: ?Sized + PipelineLayoutDesc,
 {
-    let mut num_resources = Counter::default();
-    let mut num_samplers = Counter::default();
-    let mut num_uniform_buffers = Counter::default();
-    let mut num_uniform_buffers_dynamic = 0;
-    let mut num_storage_buffers = Counter::default();
-    let mut num_storage_buffers_dynamic = 0;
-    let mut num_sampled_images = Counter::default();
-    let mut num_storage_images = Counter::default();
-    let mut num_input_attachments = Counter::default();
+    // The descriptors of the sets that aren't update-after-bind, which count against the regular
+    // limits.
+    let mut counts = DescriptorCounts::default();
+    // The descriptors of all the sets, which count against the update-after-bind limits if one
+    // of the sets is update-after-bind.
+    let mut all_counts = DescriptorCounts::default();
+    let mut has_update_after_bind = false;
 
     for set in 0..desc.num_sets() {
         let num_bindings_in_set = match desc.num_bindings_in_set(set) {
@@ -42,50 +44,37 @@ where
             Some(n) => n,
         };
 
+        let update_after_bind = desc
+            .provided_set_layout(set)
+            .map(|layout| layout.is_update_after_bind_pool())
+            .unwrap_or(false);
+        has_update_after_bind |= update_after_bind;
+
         for binding in 0..num_bindings_in_set {
             let descriptor = match desc.descriptor(set, binding) {
                 None => continue,
                 Some(n) => n,
             };
 
-            num_resources.increment(descriptor.array_count, &descriptor.stages);
-
-            match descriptor.ty.ty().expect("Not implemented yet") {
-                // TODO:
-                DescriptorType::Sampler => {
-                    num_samplers.increment(descriptor.array_count, &descriptor.stages);
-                }
-                DescriptorType::CombinedImageSampler => {
-                    num_samplers.increment(descriptor.array_count, &descriptor.stages);
-                    num_sampled_images.increment(descriptor.array_count, &descriptor.stages);
-                }
-                DescriptorType::SampledImage | DescriptorType::UniformTexelBuffer => {
-                    num_sampled_images.increment(descriptor.array_count, &descriptor.stages);
-                }
-                DescriptorType::StorageImage | DescriptorType::StorageTexelBuffer => {
-                    num_storage_images.increment(descriptor.array_count, &descriptor.stages);
-                }
-                DescriptorType::UniformBuffer => {
-                    num_uniform_buffers.increment(descriptor.array_count, &descriptor.stages);
-                }
-                DescriptorType::UniformBufferDynamic => {
-                    num_uniform_buffers.increment(descriptor.array_count, &descriptor.stages);
-                    num_uniform_buffers_dynamic += 1;
-                }
-                DescriptorType::StorageBuffer => {
-                    num_storage_buffers.increment(descriptor.array_count, &descriptor.stages);
-                }
-                DescriptorType::StorageBufferDynamic => {
-                    num_storage_buffers.increment(descriptor.array_count, &descriptor.stages);
-                    num_storage_buffers_dynamic += 1;
-                }
-                DescriptorType::InputAttachment => {
-                    num_input_attachments.increment(descriptor.array_count, &descriptor.stages);
-                }
+            all_counts.add(&descriptor);
+            if !update_after_bind {
+                counts.add(&descriptor);
             }
         }
     }
 
+    let DescriptorCounts {
+        resources: num_resources,
+        samplers: num_samplers,
+        uniform_buffers: num_uniform_buffers,
+        uniform_buffers_dynamic: num_uniform_buffers_dynamic,
+        storage_buffers: num_storage_buffers,
+        storage_buffers_dynamic: num_storage_buffers_dynamic,
+        sampled_images: num_sampled_images,
+        storage_images: num_storage_images,
+        input_attachments: num_input_attachments,
+    } = counts;
+
     if desc.num_sets() > limits.max_bound_descriptor_sets() as usize {
         return Err(PipelineLayoutLimitsError::MaxDescriptorSetsLimitExceeded {
             limit: limits.max_bound_descriptor_sets() as usize,
@@ -216,6 +205,10 @@ where
         );
     }
 
+    if let (true, Some(uab_limits)) = (has_update_after_bind, update_after_bind_limits) {
+        check_update_after_bind_limits(&all_counts, uab_limits)?;
+    }
+
     for pc_id in 0..desc.num_push_constants_ranges() {
         let PipelineLayoutDescPcRange { offset, size, .. } = {
             match desc.push_constants_range(pc_id) {
@@ -373,6 +366,35 @@ pub enum PipelineLayoutLimitsError {
         /// What was requested.
         requested: u32,
     },
+
+    /// The `max_per_stage_resources` limit of `UpdateAfterBindLimits` has been exceeded.
+    MaxPerStageUpdateAfterBindResourcesLimitExceeded {
+        /// The limit that must be fulfilled.
+        limit: u32,
+        /// What was requested.
+        requested: u32,
+    },
+
+    /// One of the `max_per_stage_descriptor_*` limits of `UpdateAfterBindLimits` has been
+    /// exceeded.
+    MaxPerStageDescriptorUpdateAfterBindLimitExceeded {
+        /// The type of descriptor the limit applies to.
+        ty: DescriptorType,
+        /// The limit that must be fulfilled.
+        limit: u32,
+        /// What was requested.
+        requested: u32,
+    },
+
+    /// One of the `max_descriptor_set_*` limits of `UpdateAfterBindLimits` has been exceeded.
+    MaxDescriptorSetUpdateAfterBindLimitExceeded {
+        /// The type of descriptor the limit applies to.
+        ty: DescriptorType,
+        /// The limit that must be fulfilled.
+        limit: u32,
+        /// What was requested.
+        requested: u32,
+    },
 }
 
 impl error::Error for PipelineLayoutLimitsError {}
@@ -447,11 +469,214 @@ impl fmt::Display for PipelineLayoutLimitsError {
                 } => {
                     "the `max_descriptor_set_input_attachments()` limit has been exceeded"
                 }
+                PipelineLayoutLimitsError::MaxPerStageUpdateAfterBindResourcesLimitExceeded {
+                    ..
+                } => "the update-after-bind `max_per_stage_resources` limit has been exceeded",
+                PipelineLayoutLimitsError::MaxPerStageDescriptorUpdateAfterBindLimitExceeded {
+                    ..
+                } => "an update-after-bind `max_per_stage_descriptor_*` limit has been exceeded",
+                PipelineLayoutLimitsError::MaxDescriptorSetUpdateAfterBindLimitExceeded {
+                    ..
+                } => "an update-after-bind `max_descriptor_set_*` limit has been exceeded",
             }
         )
     }
 }
 
+// Checks the descriptors of all the sets of a pipeline layout against the update-after-bind
+// limits.
+fn check_update_after_bind_limits(
+    counts: &DescriptorCounts,
+    limits: &UpdateAfterBindLimits,
+) -> Result<(), PipelineLayoutLimitsError> {
+    if counts.resources.max_per_stage() > limits.max_per_stage_resources {
+        return Err(
+            PipelineLayoutLimitsError::MaxPerStageUpdateAfterBindResourcesLimitExceeded {
+                limit: limits.max_per_stage_resources,
+                requested: counts.resources.max_per_stage(),
+            },
+        );
+    }
+
+    let per_stage = [
+        (
+            DescriptorType::Sampler,
+            &counts.samplers,
+            limits.max_per_stage_descriptor_samplers,
+        ),
+        (
+            DescriptorType::UniformBuffer,
+            &counts.uniform_buffers,
+            limits.max_per_stage_descriptor_uniform_buffers,
+        ),
+        (
+            DescriptorType::StorageBuffer,
+            &counts.storage_buffers,
+            limits.max_per_stage_descriptor_storage_buffers,
+        ),
+        (
+            DescriptorType::SampledImage,
+            &counts.sampled_images,
+            limits.max_per_stage_descriptor_sampled_images,
+        ),
+        (
+            DescriptorType::StorageImage,
+            &counts.storage_images,
+            limits.max_per_stage_descriptor_storage_images,
+        ),
+        (
+            DescriptorType::InputAttachment,
+            &counts.input_attachments,
+            limits.max_per_stage_descriptor_input_attachments,
+        ),
+    ];
+    for &(ty, counter, limit) in per_stage.iter() {
+        if counter.max_per_stage() > limit {
+            return Err(
+                PipelineLayoutLimitsError::MaxPerStageDescriptorUpdateAfterBindLimitExceeded {
+                    ty,
+                    limit,
+                    requested: counter.max_per_stage(),
+                },
+            );
+        }
+    }
+
+    if let Some((ty, limit, requested)) = exceeded_update_after_bind_set_limit(counts, limits) {
+        return Err(
+            PipelineLayoutLimitsError::MaxDescriptorSetUpdateAfterBindLimitExceeded {
+                ty,
+                limit,
+                requested,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+// Returns the first of the `max_descriptor_set_*` update-after-bind limits that is exceeded by
+// `counts`, as the type of descriptor it applies to, the limit and the number of descriptors.
+pub(crate) fn exceeded_update_after_bind_set_limit(
+    counts: &DescriptorCounts,
+    limits: &UpdateAfterBindLimits,
+) -> Option<(DescriptorType, u32, u32)> {
+    let per_set = [
+        (
+            DescriptorType::Sampler,
+            counts.samplers.total,
+            limits.max_descriptor_set_samplers,
+        ),
+        (
+            DescriptorType::UniformBuffer,
+            counts.uniform_buffers.total,
+            limits.max_descriptor_set_uniform_buffers,
+        ),
+        (
+            DescriptorType::UniformBufferDynamic,
+            counts.uniform_buffers_dynamic,
+            limits.max_descriptor_set_uniform_buffers_dynamic,
+        ),
+        (
+            DescriptorType::StorageBuffer,
+            counts.storage_buffers.total,
+            limits.max_descriptor_set_storage_buffers,
+        ),
+        (
+            DescriptorType::StorageBufferDynamic,
+            counts.storage_buffers_dynamic,
+            limits.max_descriptor_set_storage_buffers_dynamic,
+        ),
+        (
+            DescriptorType::SampledImage,
+            counts.sampled_images.total,
+            limits.max_descriptor_set_sampled_images,
+        ),
+        (
+            DescriptorType::StorageImage,
+            counts.storage_images.total,
+            limits.max_descriptor_set_storage_images,
+        ),
+        (
+            DescriptorType::InputAttachment,
+            counts.input_attachments.total,
+            limits.max_descriptor_set_input_attachments,
+        ),
+    ];
+
+    per_set
+        .iter()
+        .find(|&&(_, requested, limit)| requested > limit)
+        .map(|&(ty, requested, limit)| (ty, limit, requested))
+}
+
+// Number of descriptors of each category that the limits apply to.
+#[derive(Default)]
+pub(crate) struct DescriptorCounts {
+    resources: Counter,
+    samplers: Counter,
+    uniform_buffers: Counter,
+    uniform_buffers_dynamic: u32,
+    storage_buffers: Counter,
+    storage_buffers_dynamic: u32,
+    sampled_images: Counter,
+    storage_images: Counter,
+    input_attachments: Counter,
+}
+
+impl DescriptorCounts {
+    // Adds the descriptors of a binding.
+    pub(crate) fn add(&mut self, descriptor: &DescriptorDesc) {
+        let array_count = descriptor.array_count;
+        let stages = &descriptor.stages;
+
+        self.resources.increment(array_count, stages);
+
+        // A mutable descriptor counts against the limits of each type it can hold.
+        let types = match descriptor.ty {
+            DescriptorDescTy::Mutable(ref types) => types.clone(),
+            ref ty => vec![ty.ty().expect("Not implemented yet")],
+        };
+
+        for ty in types {
+            match ty {
+                // TODO:
+                DescriptorType::Sampler => {
+                    self.samplers.increment(array_count, stages);
+                }
+                DescriptorType::CombinedImageSampler => {
+                    self.samplers.increment(array_count, stages);
+                    self.sampled_images.increment(array_count, stages);
+                }
+                DescriptorType::SampledImage | DescriptorType::UniformTexelBuffer => {
+                    self.sampled_images.increment(array_count, stages);
+                }
+                DescriptorType::StorageImage | DescriptorType::StorageTexelBuffer => {
+                    self.storage_images.increment(array_count, stages);
+                }
+                DescriptorType::UniformBuffer => {
+                    self.uniform_buffers.increment(array_count, stages);
+                }
+                DescriptorType::UniformBufferDynamic => {
+                    self.uniform_buffers.increment(array_count, stages);
+                    self.uniform_buffers_dynamic += array_count;
+                }
+                DescriptorType::StorageBuffer => {
+                    self.storage_buffers.increment(array_count, stages);
+                }
+                DescriptorType::StorageBufferDynamic => {
+                    self.storage_buffers.increment(array_count, stages);
+                    self.storage_buffers_dynamic += array_count;
+                }
+                DescriptorType::InputAttachment => {
+                    self.input_attachments.increment(array_count, stages);
+                }
+                DescriptorType::Mutable => (),
+            }
+        }
+    }
+}
+
 // Helper struct for the main function.
 #[derive(Default)]
 struct Counter {
@@ -510,3 +735,151 @@ impl Counter {
         max
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::pipeline_layout::PipelineLayout;
+    use descriptor::pipeline_layout::PipelineLayoutCreationError;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::PipelineLayoutLimitsError;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::iter;
+
+    fn samplers(array_count: u32, stages: ShaderStages) -> Option<DescriptorDesc> {
+        Some(DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count,
+            stages,
+            readonly: true,
+        })
+    }
+
+    #[test]
+    fn per_stage_limit_across_sets() {
+        let (device, _) = gfx_dev_and_queue!();
+        let limit = device
+            .physical_device()
+            .limits()
+            .max_per_stage_descriptor_samplers();
+
+        // Each set is within the limit, but the fragment stage sees both of them. Depending on
+        // the device, the total can exceed the limit of the whole pipeline layout as well.
+        let half = limit / 2 + 1;
+        let desc = RuntimePipelineDesc::new(
+            vec![
+                vec![samplers(half, ShaderStages::all_graphics())],
+                vec![samplers(half, ShaderStages::all_graphics())],
+            ],
+            iter::empty(),
+        )
+        .unwrap();
+
+        match PipelineLayout::new(device.clone(), desc) {
+            Err(PipelineLayoutCreationError::LimitsError(
+                PipelineLayoutLimitsError::MaxPerStageDescriptorSamplersLimitExceeded {
+                    limit: l,
+                    requested,
+                },
+            )) => {
+                assert_eq!(l, limit);
+                assert_eq!(requested, half.saturating_mul(2));
+            }
+            Err(PipelineLayoutCreationError::LimitsError(
+                PipelineLayoutLimitsError::MaxDescriptorSetSamplersLimitExceeded {
+                    requested, ..
+                },
+            )) => {
+                assert_eq!(requested, half.saturating_mul(2));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn per_stage_limit_different_stages() {
+        let (device, _) = gfx_dev_and_queue!();
+        let limit = device
+            .physical_device()
+            .limits()
+            .max_per_stage_descriptor_samplers();
+
+        // Samplers used by different stages don't add up.
+        let desc = RuntimePipelineDesc::new(
+            vec![
+                vec![samplers(limit / 2 + 1, ShaderStages::compute())],
+                vec![samplers(
+                    limit / 2 + 1,
+                    ShaderStages {
+                        fragment: true,
+                        ..ShaderStages::none()
+                    },
+                )],
+            ],
+            iter::empty(),
+        )
+        .unwrap();
+        match desc.check_against_limits(&device) {
+            Err(PipelineLayoutLimitsError::MaxPerStageDescriptorSamplersLimitExceeded {
+                ..
+            }) => panic!(),
+            _ => (),
+        }
+    }
+
+    #[test]
+    fn dynamic_buffer_arrays() {
+        let (device, _) = gfx_dev_and_queue!();
+        let limit = device
+            .physical_device()
+            .limits()
+            .max_descriptor_set_uniform_buffers_dynamic();
+
+        let dynamic_buffers = |array_count, stages| {
+            Some(DescriptorDesc {
+                ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                    dynamic: Some(true),
+                    storage: false,
+                }),
+                array_count,
+                stages,
+                readonly: true,
+            })
+        };
+
+        // Every element of an array of dynamic buffers counts against the limit. The arrays are
+        // split between two stages so that the per-stage limits aren't reached first.
+        let vertex = ShaderStages {
+            vertex: true,
+            ..ShaderStages::none()
+        };
+        let fragment = ShaderStages {
+            fragment: true,
+            ..ShaderStages::none()
+        };
+        let desc = RuntimePipelineDesc::new(
+            vec![vec![
+                dynamic_buffers(limit / 2 + 1, vertex),
+                dynamic_buffers(limit - limit / 2, fragment),
+            ]],
+            iter::empty(),
+        )
+        .unwrap();
+
+        match desc.check_against_limits(&device) {
+            Err(
+                PipelineLayoutLimitsError::MaxDescriptorSetUniformBuffersDynamicLimitExceeded {
+                    limit: l,
+                    requested,
+                },
+            ) => {
+                assert_eq!(l, limit);
+                assert_eq!(requested, limit + 1);
+            }
+            _ => panic!(),
+        }
+    }
+}
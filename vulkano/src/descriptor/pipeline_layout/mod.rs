@@ -53,6 +53,7 @@ pub use self::empty::EmptyPipelineDesc;
 pub use self::limits_check::PipelineLayoutLimitsError;
 pub use self::runtime_desc::RuntimePipelineDesc;
 pub use self::runtime_desc::RuntimePipelineDescError;
+pub use self::stages_check::UnusedDescriptorStages;
 pub use self::sys::PipelineLayout;
 pub use self::sys::PipelineLayoutCreationError;
 pub use self::sys::PipelineLayoutSys;
@@ -65,11 +66,14 @@ pub use self::traits::PipelineLayoutSetsCompatible;
 pub use self::traits::PipelineLayoutSuperset;
 pub use self::union::PipelineLayoutDescUnion;
 
+pub(crate) use self::limits_check::exceeded_update_after_bind_set_limit;
+pub(crate) use self::limits_check::DescriptorCounts;
 pub(crate) use self::tweaks::PipelineLayoutDescTweaks;
 
 mod empty;
 mod limits_check;
 mod runtime_desc;
+mod stages_check;
 mod sys;
 mod traits;
 mod tweaks;
@@ -0,0 +1,116 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Contains the `unused_descriptor_stages` function and the `UnusedDescriptorStages` struct.
+
+use descriptor::descriptor::ShaderStages;
+use descriptor::pipeline_layout::PipelineLayoutDesc;
+
+/// Returns the descriptors of the pipeline layout description that are declared for stages that
+/// aren't in `pipeline_stages`.
+pub fn unused_descriptor_stages<D>(
+    desc: &D,
+    pipeline_stages: ShaderStages,
+) -> Vec<UnusedDescriptorStages>
+where
+    D: ?Sized + PipelineLayoutDesc,
+{
+    let mut unused = Vec::new();
+
+    for set in 0..desc.num_sets() {
+        let num_bindings = desc.num_bindings_in_set(set).unwrap_or(0);
+        for binding in 0..num_bindings {
+            let descriptor = match desc.descriptor(set, binding) {
+                Some(d) => d,
+                None => continue,
+            };
+
+            let stages = descriptor.stages.difference(&pipeline_stages);
+            if stages != ShaderStages::none() {
+                unused.push(UnusedDescriptorStages {
+                    set,
+                    binding,
+                    stages,
+                });
+            }
+        }
+    }
+
+    unused
+}
+
+/// A descriptor of a pipeline layout that is declared for stages that the pipeline doesn't have.
+///
+/// This isn't an error, but the implementation may reserve resources for these stages for
+/// nothing. Removing them from the stages of the descriptor is usually better.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnusedDescriptorStages {
+    /// Index of the set of the descriptor.
+    pub set: usize,
+    /// Index of the binding of the descriptor within its set.
+    pub binding: usize,
+    /// The stages of the descriptor that the pipeline doesn't have.
+    pub stages: ShaderStages,
+}
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use descriptor::pipeline_layout::UnusedDescriptorStages;
+    use std::iter;
+
+    fn sampler(stages: ShaderStages) -> Option<DescriptorDesc> {
+        Some(DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 1,
+            stages,
+            readonly: true,
+        })
+    }
+
+    #[test]
+    fn unused_geometry_stage() {
+        let vertex_fragment = ShaderStages {
+            vertex: true,
+            fragment: true,
+            ..ShaderStages::none()
+        };
+
+        let desc = RuntimePipelineDesc::new(
+            vec![vec![
+                sampler(vertex_fragment),
+                None,
+                sampler(ShaderStages::all_graphics()),
+            ]],
+            iter::empty(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            desc.unused_descriptor_stages(vertex_fragment),
+            vec![UnusedDescriptorStages {
+                set: 0,
+                binding: 2,
+                stages: ShaderStages {
+                    tessellation_control: true,
+                    tessellation_evaluation: true,
+                    geometry: true,
+                    ..ShaderStages::none()
+                },
+            }]
+        );
+        assert!(desc
+            .unused_descriptor_stages(ShaderStages::all_graphics())
+            .is_empty());
+    }
+}
@@ -10,6 +10,7 @@
 use smallvec::SmallVec;
 use std::error;
 use std::fmt;
+use std::iter;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
@@ -22,6 +23,7 @@ use VulkanObject;
 
 use descriptor::descriptor::DescriptorDesc;
 use descriptor::descriptor::ShaderStages;
+use descriptor::descriptor_set::DescriptorSetLayoutError;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
@@ -70,7 +72,11 @@ where
                     None => {
                         let sets_iter = 0..desc.num_bindings_in_set(num).unwrap_or(0);
                         let desc_iter = sets_iter.map(|d| desc.descriptor(num, d));
-                        Arc::new(UnsafeDescriptorSetLayout::new(device.clone(), desc_iter)?)
+                        Arc::new(UnsafeDescriptorSetLayout::with_binding_flags(
+                            device.clone(),
+                            desc_iter,
+                            iter::empty(),
+                        )?)
                     }
                 });
             }
@@ -184,8 +190,8 @@ where
     }
 
     #[inline]
-    fn descriptor_set_layout(&self, index: usize) -> Option<&Arc<UnsafeDescriptorSetLayout>> {
-        self.layouts.get(index)
+    fn set_layouts(&self) -> &[Arc<UnsafeDescriptorSetLayout>] {
+        &self.layouts
     }
 }
 
@@ -277,6 +283,8 @@ pub enum PipelineLayoutCreationError {
     /// One of the push constants range didn't obey the rules. The list of stages must not be
     /// empty, the size must not be 0, and the size must be a multiple or 4.
     InvalidPushConstant,
+    /// Creating the layout of one of the descriptor sets failed.
+    DescriptorSetLayoutError(DescriptorSetLayoutError),
 }
 
 impl error::Error for PipelineLayoutCreationError {
@@ -285,6 +293,7 @@ impl error::Error for PipelineLayoutCreationError {
         match *self {
             PipelineLayoutCreationError::OomError(ref err) => Some(err),
             PipelineLayoutCreationError::LimitsError(ref err) => Some(err),
+            PipelineLayoutCreationError::DescriptorSetLayoutError(ref err) => Some(err),
             _ => None,
         }
     }
@@ -304,6 +313,9 @@ impl fmt::Display for PipelineLayoutCreationError {
                 PipelineLayoutCreationError::InvalidPushConstant => {
                     "one of the push constants range didn't obey the rules"
                 }
+                PipelineLayoutCreationError::DescriptorSetLayoutError(_) => {
+                    "creating the layout of one of the descriptor sets failed"
+                }
             }
         )
     }
@@ -323,6 +335,16 @@ impl From<PipelineLayoutLimitsError> for PipelineLayoutCreationError {
     }
 }
 
+impl From<DescriptorSetLayoutError> for PipelineLayoutCreationError {
+    #[inline]
+    fn from(err: DescriptorSetLayoutError) -> PipelineLayoutCreationError {
+        match err {
+            DescriptorSetLayoutError::OomError(err) => PipelineLayoutCreationError::OomError(err),
+            err => PipelineLayoutCreationError::DescriptorSetLayoutError(err),
+        }
+    }
+}
+
 impl From<Error> for PipelineLayoutCreationError {
     #[inline]
     fn from(err: Error) -> PipelineLayoutCreationError {
@@ -405,3 +427,42 @@ mod tests {
         }
     }
 }*/
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorType;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorSetLayoutError;
+    use descriptor::pipeline_layout::PipelineLayout;
+    use descriptor::pipeline_layout::PipelineLayoutCreationError;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::iter;
+
+    #[test]
+    fn mutable_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = RuntimePipelineDesc::new(
+            vec![vec![Some(DescriptorDesc {
+                ty: DescriptorDescTy::Mutable(vec![
+                    DescriptorType::UniformBuffer,
+                    DescriptorType::StorageBuffer,
+                ]),
+                array_count: 1,
+                stages: ShaderStages::all(),
+                readonly: true,
+            })]],
+            iter::empty(),
+        )
+        .unwrap();
+
+        match PipelineLayout::new(device, desc) {
+            Err(PipelineLayoutCreationError::DescriptorSetLayoutError(
+                DescriptorSetLayoutError::MutableDescriptorTypeNotEnabled,
+            )) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -18,6 +18,7 @@ use descriptor::descriptor::ShaderStages;
 use descriptor::descriptor_set::DescriptorSetsCollection;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use descriptor::pipeline_layout::limits_check;
+use descriptor::pipeline_layout::stages_check;
 use descriptor::pipeline_layout::PipelineLayout;
 use descriptor::pipeline_layout::PipelineLayoutCreationError;
 use descriptor::pipeline_layout::PipelineLayoutDescUnion;
@@ -35,10 +36,18 @@ pub unsafe trait PipelineLayoutAbstract: PipelineLayoutDesc + DeviceOwned {
     /// > **Note**: This is an internal function that you normally don't need to call.
     fn sys(&self) -> PipelineLayoutSys;
 
+    /// Returns the `UnsafeDescriptorSetLayout` objects of all the sets of the layout, in order.
+    ///
+    /// Empty sets have an empty layout, so the index of each element is its set number.
+    fn set_layouts(&self) -> &[Arc<UnsafeDescriptorSetLayout>];
+
     /// Returns the `UnsafeDescriptorSetLayout` object of the specified set index.
     ///
-    /// Returns `None` if out of range or if the set is empty for this index.
-    fn descriptor_set_layout(&self, index: usize) -> Option<&Arc<UnsafeDescriptorSetLayout>>;
+    /// Returns `None` if out of range.
+    #[inline]
+    fn descriptor_set_layout(&self, index: usize) -> Option<&Arc<UnsafeDescriptorSetLayout>> {
+        self.set_layouts().get(index)
+    }
 }
 
 unsafe impl<T> PipelineLayoutAbstract for T
@@ -51,6 +60,11 @@ where
         (**self).sys()
     }
 
+    #[inline]
+    fn set_layouts(&self) -> &[Arc<UnsafeDescriptorSetLayout>] {
+        (**self).set_layouts()
+    }
+
     #[inline]
     fn descriptor_set_layout(&self, index: usize) -> Option<&Arc<UnsafeDescriptorSetLayout>> {
         (**self).descriptor_set_layout(index)
@@ -109,7 +123,31 @@ pub unsafe trait PipelineLayoutDesc {
         &self,
         device: &Device,
     ) -> Result<(), limits_check::PipelineLayoutLimitsError> {
-        limits_check::check_desc_against_limits(self, device.physical_device().limits())
+        let physical_device = device.physical_device();
+        limits_check::check_desc_against_limits(
+            self,
+            physical_device.limits(),
+            physical_device
+                .extended_properties()
+                .update_after_bind_limits()
+                .as_ref(),
+        )
+    }
+
+    /// Returns the descriptors that are declared for stages that aren't in `pipeline_stages`.
+    ///
+    /// Declaring a descriptor for a stage that the pipeline doesn't have, for example the geometry
+    /// stage for a pipeline without a geometry shader, isn't an error but is wasteful. This can be
+    /// used during development to find the stages that can be removed from the layout. Pass the
+    /// stages returned by `GraphicsPipeline::shader_stages`, or `ShaderStages::compute()` for a
+    /// compute pipeline. Building a graphics pipeline calls this method, and the result is
+    /// returned by `GraphicsPipeline::unused_descriptor_stages`.
+    #[inline]
+    fn unused_descriptor_stages(
+        &self,
+        pipeline_stages: ShaderStages,
+    ) -> Vec<stages_check::UnusedDescriptorStages> {
+        stages_check::unused_descriptor_stages(self, pipeline_stages)
     }
 
     /// Turns the layout description into a `PipelineLayout` object that can be used by Vulkan.
@@ -0,0 +1,365 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use device::Device;
+use device::DeviceCreationError;
+use device::DeviceExtensions;
+use device::Queue;
+use features::Features;
+use instance::ApplicationInfo;
+use instance::Instance;
+use instance::InstanceCreationError;
+use instance::InstanceExtensions;
+use instance::PhysicalDevice;
+use instance::PhysicalDeviceType;
+use instance::QueueFamily;
+
+/// Name of the layer enabled by `ContextBuilder::validation`.
+pub const VALIDATION_LAYER: &str = "VK_LAYER_KHRONOS_validation";
+
+/// An instance, a device and its queues, ready to be used.
+///
+/// This is a shortcut for the usual initialization steps: creating an instance, picking a
+/// physical device, and creating a device with a graphics queue and a transfer queue. Use
+/// `Context::builder` in order to enable extensions or validation, or to reuse an existing
+/// instance. Everything that is created is accessible, so that the context can be replaced with
+/// the lower-level objects once more control is needed.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::device::Context;
+/// use vulkano::device::Features;
+///
+/// let context = Context::new("my application", &Features::none()).unwrap();
+/// let device = context.device().clone();
+/// let queue = context.graphics_queue().clone();
+/// ```
+#[derive(Debug)]
+pub struct Context {
+    instance: Arc<Instance>,
+    physical_device: usize,
+    device: Arc<Device>,
+    graphics_queue: Arc<Queue>,
+    transfer_queue: Arc<Queue>,
+}
+
+impl Context {
+    /// Builds a context with the default parameters and the given features.
+    ///
+    /// Equivalent to `Context::builder(app_name).features(required_features).build()`.
+    #[inline]
+    pub fn new(
+        app_name: &str,
+        required_features: &Features,
+    ) -> Result<Context, ContextCreationError> {
+        Context::builder(app_name)
+            .features(required_features.clone())
+            .build()
+    }
+
+    /// Starts building a context.
+    #[inline]
+    pub fn builder(app_name: &str) -> ContextBuilder {
+        ContextBuilder {
+            app_name: app_name.to_owned(),
+            validation: false,
+            instance_extensions: InstanceExtensions::none(),
+            device_extensions: DeviceExtensions::none(),
+            features: Features::none(),
+            instance: None,
+            physical_device: None,
+        }
+    }
+
+    /// Returns the instance.
+    #[inline]
+    pub fn instance(&self) -> &Arc<Instance> {
+        &self.instance
+    }
+
+    /// Returns the physical device that was picked.
+    #[inline]
+    pub fn physical_device<'a>(&'a self) -> PhysicalDevice<'a> {
+        PhysicalDevice::from_index(&self.instance, self.physical_device).unwrap()
+    }
+
+    /// Returns the device.
+    #[inline]
+    pub fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+
+    /// Returns a queue of the first queue family of the physical device that supports graphics
+    /// operations.
+    #[inline]
+    pub fn graphics_queue(&self) -> &Arc<Queue> {
+        &self.graphics_queue
+    }
+
+    /// Returns a queue for transfer operations.
+    ///
+    /// This is a queue of a family dedicated to transfers if the device has one, otherwise a
+    /// second queue of the graphics family if possible. If neither is available, this is the
+    /// same queue as `graphics_queue`.
+    #[inline]
+    pub fn transfer_queue(&self) -> &Arc<Queue> {
+        &self.transfer_queue
+    }
+}
+
+/// Prototype of a `Context`.
+///
+/// Created with `Context::builder`.
+#[derive(Debug, Clone)]
+pub struct ContextBuilder {
+    app_name: String,
+    validation: bool,
+    instance_extensions: InstanceExtensions,
+    device_extensions: DeviceExtensions,
+    features: Features,
+    instance: Option<Arc<Instance>>,
+    physical_device: Option<usize>,
+}
+
+impl ContextBuilder {
+    /// Enables the `VK_LAYER_KHRONOS_validation` layer on the instance. The default is false.
+    ///
+    /// Building the context returns an error if the layer isn't installed.
+    #[inline]
+    pub fn validation(mut self, validation: bool) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Sets the extensions to enable on the instance. The default is none.
+    #[inline]
+    pub fn instance_extensions(mut self, extensions: InstanceExtensions) -> Self {
+        self.instance_extensions = extensions;
+        self
+    }
+
+    /// Sets the extensions to enable on the device. Physical devices that don't support them
+    /// are skipped. The default is none.
+    #[inline]
+    pub fn device_extensions(mut self, extensions: DeviceExtensions) -> Self {
+        self.device_extensions = extensions;
+        self
+    }
+
+    /// Sets the features to enable on the device. Physical devices that don't support them are
+    /// skipped. The default is none.
+    #[inline]
+    pub fn features(mut self, features: Features) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Uses an existing instance instead of creating one. The application name, the validation
+    /// and the instance extensions are then ignored.
+    #[inline]
+    pub fn instance(mut self, instance: Arc<Instance>) -> Self {
+        self.instance = Some(instance);
+        self
+    }
+
+    /// Uses the physical device with the given index instead of picking one automatically.
+    #[inline]
+    pub fn physical_device(mut self, index: usize) -> Self {
+        self.physical_device = Some(index);
+        self
+    }
+
+    /// Builds the context.
+    ///
+    /// Unless a physical device was specified, discrete GPUs are preferred over integrated
+    /// GPUs, which are preferred over the other device types.
+    pub fn build(self) -> Result<Context, ContextCreationError> {
+        let instance = match self.instance {
+            Some(instance) => instance,
+            None => {
+                let app_infos = ApplicationInfo {
+                    application_name: Some(self.app_name.into()),
+                    application_version: None,
+                    engine_name: Some("vulkano".into()),
+                    engine_version: None,
+                };
+                let layers = if self.validation {
+                    Some(VALIDATION_LAYER)
+                } else {
+                    None
+                };
+                Instance::new(Some(&app_infos), &self.instance_extensions, layers)?
+            }
+        };
+
+        let (physical_device, graphics_family, transfer_family) = {
+            let device_extensions = &self.device_extensions;
+            let features = &self.features;
+            let is_suitable = |physical: &PhysicalDevice| -> bool {
+                let supported = DeviceExtensions::supported_by_device(*physical);
+                supported.intersection(device_extensions) == *device_extensions
+                    && physical.supported_features().superset_of(features)
+                    && physical.queue_families().any(|q| q.supports_graphics())
+            };
+
+            let physical = match self.physical_device {
+                Some(index) => PhysicalDevice::from_index(&instance, index).filter(is_suitable),
+                None => PhysicalDevice::enumerate(&instance)
+                    .filter(is_suitable)
+                    .min_by_key(|physical| match physical.ty() {
+                        PhysicalDeviceType::DiscreteGpu => 0,
+                        PhysicalDeviceType::IntegratedGpu => 1,
+                        PhysicalDeviceType::VirtualGpu => 2,
+                        PhysicalDeviceType::Cpu => 3,
+                        PhysicalDeviceType::Other => 4,
+                    }),
+            };
+            let physical = physical.ok_or(ContextCreationError::NoSuitablePhysicalDevice)?;
+
+            let graphics = physical
+                .queue_families()
+                .find(|q| q.supports_graphics())
+                .unwrap();
+            let transfer = physical
+                .queue_families()
+                .find(|q| {
+                    q.explicitly_supports_transfers()
+                        && !q.supports_graphics()
+                        && !q.supports_compute()
+                })
+                .or_else(|| {
+                    if graphics.queues_count() >= 2 {
+                        Some(graphics)
+                    } else {
+                        None
+                    }
+                });
+
+            (physical.index(), graphics.id(), transfer.map(|q| q.id()))
+        };
+
+        let physical = PhysicalDevice::from_index(&instance, physical_device).unwrap();
+        let family = |id| -> QueueFamily { physical.queue_family_by_id(id).unwrap() };
+        let queue_families = Some((family(graphics_family), 1.0))
+            .into_iter()
+            .chain(transfer_family.map(|id| (family(id), 0.5)));
+
+        let (device, mut queues) = Device::new(
+            physical,
+            &self.features,
+            &self.device_extensions,
+            queue_families,
+        )?;
+        let graphics_queue = queues.next().unwrap();
+        let transfer_queue = queues.next().unwrap_or_else(|| graphics_queue.clone());
+
+        Ok(Context {
+            instance: instance.clone(),
+            physical_device,
+            device,
+            graphics_queue,
+            transfer_queue,
+        })
+    }
+}
+
+/// Error that can happen when creating a `Context`.
+#[derive(Debug, Clone)]
+pub enum ContextCreationError {
+    /// Failed to create the instance.
+    InstanceCreationError(InstanceCreationError),
+    /// No physical device supports the requested features and extensions and has a graphics
+    /// queue.
+    NoSuitablePhysicalDevice,
+    /// Failed to create the device.
+    DeviceCreationError(DeviceCreationError),
+}
+
+impl error::Error for ContextCreationError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            ContextCreationError::InstanceCreationError(ref err) => Some(err),
+            ContextCreationError::DeviceCreationError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ContextCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                ContextCreationError::InstanceCreationError(_) => "failed to create the instance",
+                ContextCreationError::NoSuitablePhysicalDevice => {
+                    "no physical device supports the requested features and extensions"
+                }
+                ContextCreationError::DeviceCreationError(_) => "failed to create the device",
+            }
+        )
+    }
+}
+
+impl From<InstanceCreationError> for ContextCreationError {
+    #[inline]
+    fn from(err: InstanceCreationError) -> ContextCreationError {
+        ContextCreationError::InstanceCreationError(err)
+    }
+}
+
+impl From<DeviceCreationError> for ContextCreationError {
+    #[inline]
+    fn from(err: DeviceCreationError) -> ContextCreationError {
+        ContextCreationError::DeviceCreationError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use device::Context;
+    use device::ContextCreationError;
+
+    #[test]
+    fn basic() {
+        let instance = instance!();
+
+        let context = match Context::builder("test").instance(instance).build() {
+            Ok(context) => context,
+            Err(ContextCreationError::NoSuitablePhysicalDevice) => return,
+            Err(err) => panic!("{:?}", err),
+        };
+        assert!(context.graphics_queue().family().supports_graphics());
+        assert_eq!(
+            context.device().physical_device().index(),
+            context.physical_device().index()
+        );
+    }
+
+    #[test]
+    fn unknown_physical_device() {
+        let instance = instance!();
+
+        match Context::builder("test")
+            .instance(instance)
+            .physical_device(usize::max_value())
+            .build()
+        {
+            Err(ContextCreationError::NoSuitablePhysicalDevice) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -118,6 +118,23 @@ device_extensions! {
     ext_debug_utils => b"VK_EXT_debug_utils",
     khr_multiview => b"VK_KHR_multiview",
     ext_full_screen_exclusive => b"VK_EXT_full_screen_exclusive",
+    khr_timeline_semaphore => b"VK_KHR_timeline_semaphore",
+    ext_descriptor_indexing => b"VK_EXT_descriptor_indexing",
+    khr_separate_depth_stencil_layouts => b"VK_KHR_separate_depth_stencil_layouts",
+    ext_sampler_filter_minmax => b"VK_EXT_sampler_filter_minmax",
+    khr_device_group => b"VK_KHR_device_group",
+    khr_pipeline_executable_properties => b"VK_KHR_pipeline_executable_properties",
+    khr_maintenance4 => b"VK_KHR_maintenance4",
+    ext_extended_dynamic_state => b"VK_EXT_extended_dynamic_state",
+    khr_maintenance2 => b"VK_KHR_maintenance2",
+    ext_mutable_descriptor_type => b"VK_EXT_mutable_descriptor_type",
+    ext_custom_border_color => b"VK_EXT_custom_border_color",
+    ext_index_type_uint8 => b"VK_EXT_index_type_uint8",
+    khr_external_semaphore => b"VK_KHR_external_semaphore",
+    khr_external_semaphore_fd => b"VK_KHR_external_semaphore_fd",
+    khr_external_semaphore_win32 => b"VK_KHR_external_semaphore_win32",
+    ext_pipeline_creation_feedback => b"VK_EXT_pipeline_creation_feedback",
+    khr_synchronization2 => b"VK_KHR_synchronization2",
 }
 
 /// This helper type can only be instantiated inside this module.
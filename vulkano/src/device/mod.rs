@@ -81,9 +81,35 @@
 //! each element is a tuple containing a queue family and a number between 0.0 and 1.0 indicating
 //! the priority of execution of the queue relative to the others.
 //!
-//! TODO: write better doc here
+//! The `Device::new` function returns the newly-created device, but also the list of queues, in
+//! the same order as the queues were requested.
 //!
-//! The `Device::new` function returns the newly-created device, but also the list of queues.
+//! To create several queues from the same family, for example two graphics queues in order to
+//! submit work asynchronously, pass the family several times with a priority for each queue. The
+//! number of queues requested for a family can't be larger than `QueueFamily::queues_count`.
+//!
+//! ```no_run
+//! # use vulkano::device::Device;
+//! # use vulkano::device::DeviceExtensions;
+//! # use vulkano::device::Features;
+//! # use vulkano::instance::PhysicalDevice;
+//! # let physical_device: PhysicalDevice = return;
+//! let family = physical_device.queue_families().find(|q| q.supports_graphics()).unwrap();
+//! let priorities = [1.0, 0.5];
+//!
+//! let (device, queues) = Device::new(
+//!     physical_device,
+//!     &Features::none(),
+//!     &DeviceExtensions::none(),
+//!     priorities.iter().map(|&priority| (family, priority)),
+//! ).unwrap();
+//!
+//! let queues = queues.collect::<Vec<_>>();
+//! assert_eq!(queues.len(), 2);
+//! ```
+//!
+//! Each queue is a separate object that is synchronized individually, even if it belongs to the
+//! same family as another queue. See the documentation of `Queue`.
 //!
 //! # Extended example
 //!
@@ -91,6 +117,7 @@
 
 use fnv::FnvHasher;
 use smallvec::SmallVec;
+use std::cmp;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::error;
@@ -99,6 +126,7 @@ use std::fmt;
 use std::hash::BuildHasherDefault;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::ptr;
@@ -111,7 +139,9 @@ use command_buffer::pool::StandardCommandPool;
 use descriptor::descriptor_set::StdDescriptorPool;
 use instance::Instance;
 use instance::PhysicalDevice;
+use instance::PhysicalDeviceGroup;
 use instance::QueueFamily;
+use instance::Version;
 use memory::pool::StdMemoryPool;
 
 use check_errors;
@@ -122,9 +152,14 @@ use SynchronizedVulkanObject;
 use VulkanHandle;
 use VulkanObject;
 
+pub use self::context::Context;
+pub use self::context::ContextBuilder;
+pub use self::context::ContextCreationError;
+pub use self::context::VALIDATION_LAYER;
 pub use self::extensions::DeviceExtensions;
 pub use self::extensions::RawDeviceExtensions;
 pub use features::Features;
+mod context;
 mod extensions;
 
 use format::Format;
@@ -138,6 +173,7 @@ use image::ImageUsage;
 pub struct Device {
     instance: Arc<Instance>,
     physical_device: usize,
+    physical_devices: SmallVec<[usize; 4]>,
     device: vk::Device,
     vk: vk::DevicePointers,
     standard_pool: Mutex<Weak<StdMemoryPool>>,
@@ -148,6 +184,7 @@ pub struct Device {
     extensions: DeviceExtensions,
     active_queue_families: SmallVec<[u32; 8]>,
     allocation_count: Mutex<u32>,
+    update_after_bind_descriptors: Mutex<u32>,
     fence_pool: Mutex<Vec<vk::Fence>>,
     semaphore_pool: Mutex<Vec<vk::Semaphore>>,
     event_pool: Mutex<Vec<vk::Event>>,
@@ -173,6 +210,10 @@ impl Device {
     ///   queue with a lower value. Note however that no guarantee can be made on the way the
     ///   priority value is handled by the implementation.
     ///
+    ///   Passing the same family multiple times creates multiple queues from this family, up to
+    ///   `QueueFamily::queues_count`. The queues are returned in the same order as they are
+    ///   requested.
+    ///
     /// # Panic
     ///
     /// - Panics if one of the queue families doesn't belong to the given device.
@@ -185,6 +226,65 @@ impl Device {
         extensions: Ext,
         queue_families: I,
     ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+    where
+        I: IntoIterator<Item = (QueueFamily<'a>, f32)>,
+        Ext: Into<RawDeviceExtensions>,
+    {
+        let physical_devices = iter::once(phys.index()).collect();
+        Device::new_impl(
+            phys,
+            physical_devices,
+            requested_features,
+            extensions,
+            queue_families,
+        )
+    }
+
+    /// Builds a new Vulkan device that spans all the physical devices of a group.
+    ///
+    /// This works the same way as `new`, except that the queue families must belong to the first
+    /// physical device of the group. The `khr_device_group` extension should be enabled in order
+    /// to use device masks when allocating memory and recording command buffers.
+    ///
+    /// If the group only contains one physical device, this is equivalent to calling `new`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the queue families doesn't belong to the first physical device of the
+    ///   group.
+    ///
+    pub fn with_device_group<'a, I, Ext>(
+        group: &PhysicalDeviceGroup,
+        requested_features: &Features,
+        extensions: Ext,
+        queue_families: I,
+    ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+    where
+        I: IntoIterator<Item = (QueueFamily<'a>, f32)>,
+        Ext: Into<RawDeviceExtensions>,
+    {
+        let physical_devices: SmallVec<[usize; 4]> = group
+            .physical_devices()
+            .iter()
+            .map(|physical_device| physical_device.index())
+            .collect();
+        let phys = group.physical_devices()[0];
+        Device::new_impl(
+            phys,
+            physical_devices,
+            requested_features,
+            extensions,
+            queue_families,
+        )
+    }
+
+    fn new_impl<'a, I, Ext>(
+        phys: PhysicalDevice,
+        physical_devices: SmallVec<[usize; 4]>,
+        requested_features: &Features,
+        extensions: Ext,
+        queue_families: I,
+    ) -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
     where
         I: IntoIterator<Item = (QueueFamily<'a>, f32)>,
         Ext: Into<RawDeviceExtensions>,
@@ -258,15 +358,16 @@ impl Device {
             // turning `queues` into an array of `vkDeviceQueueCreateInfo` suitable for Vulkan
             let queues = queues
                 .iter()
-                .map(|&(queue_id, ref priorities)| {
-                    vk::DeviceQueueCreateInfo {
-                        sType: vk::STRUCTURE_TYPE_DEVICE_QUEUE_CREATE_INFO,
-                        pNext: ptr::null(),
-                        flags: 0, // reserved
-                        queueFamilyIndex: queue_id,
-                        queueCount: priorities.len() as u32,
-                        pQueuePriorities: priorities.as_ptr(),
-                    }
+                .map(|&(queue_id, ref priorities)| vk::DeviceQueueCreateInfo {
+                    sType: vk::STRUCTURE_TYPE_DEVICE_QUEUE_CREATE_INFO,
+                    pNext: ptr::null(),
+                    flags: queue_create_flags(
+                        &requested_features,
+                        phys.queue_family_by_id(queue_id).unwrap(),
+                    ),
+                    queueFamilyIndex: queue_id,
+                    queueCount: priorities.len() as u32,
+                    pQueuePriorities: priorities.as_ptr(),
                 })
                 .collect::<SmallVec<[_; 16]>>();
 
@@ -289,9 +390,28 @@ impl Device {
 
             let features = requested_features.into_vulkan_features_v2();
 
+            let group_handles = physical_devices
+                .iter()
+                .map(|&index| {
+                    PhysicalDevice::from_index(phys.instance(), index)
+                        .unwrap()
+                        .internal_object()
+                })
+                .collect::<SmallVec<[_; 4]>>();
+            let group_infos = vk::DeviceGroupDeviceCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO_KHR,
+                pNext: features.base_ptr() as *const _,
+                physicalDeviceCount: group_handles.len() as u32,
+                pPhysicalDevices: group_handles.as_ptr(),
+            };
+
             let infos = vk::DeviceCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DEVICE_CREATE_INFO,
-                pNext: features.base_ptr() as *const _,
+                pNext: if group_handles.len() > 1 {
+                    &group_infos as *const _ as *const _
+                } else {
+                    features.base_ptr() as *const _
+                },
                 flags: 0, // reserved
                 queueCreateInfoCount: queues.len() as u32,
                 pQueueCreateInfos: queues.as_ptr(),
@@ -330,6 +450,7 @@ impl Device {
         let device = Arc::new(Device {
             instance: phys.instance().clone(),
             physical_device: phys.index(),
+            physical_devices,
             device: device,
             vk: vk,
             standard_pool: Mutex::new(Weak::new()),
@@ -343,6 +464,7 @@ impl Device {
             extensions: (&extensions).into(),
             active_queue_families,
             allocation_count: Mutex::new(0),
+            update_after_bind_descriptors: Mutex::new(0),
             fence_pool: Mutex::new(Vec::new()),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
@@ -392,6 +514,26 @@ impl Device {
         PhysicalDevice::from_index(&self.instance, self.physical_device).unwrap()
     }
 
+    /// Returns the version of Vulkan whose core functionalities can be used with this device.
+    ///
+    /// This is the lowest of the version supported by the physical device and the version
+    /// requested by the instance.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        cmp::min(
+            self.instance.api_version(),
+            self.physical_device().api_version(),
+        )
+    }
+
+    /// Returns the number of physical devices this device spans.
+    ///
+    /// This is always 1, unless the device was created with `with_device_group`.
+    #[inline]
+    pub fn physical_device_count(&self) -> usize {
+        self.physical_devices.len()
+    }
+
     /// Returns an iterator to the list of queues families that this device uses.
     ///
     /// > **Note**: Will return `-> impl ExactSizeIterator<Item = QueueFamily>` in the future.
@@ -487,6 +629,16 @@ impl Device {
         &self.allocation_count
     }
 
+    /// Used to track the number of descriptors in all the update-after-bind descriptor pools of
+    /// this device.
+    ///
+    /// This number can't exceed the `max_update_after_bind_descriptors_in_all_pools` limit.
+    ///
+    /// Warning: You should never modify this value, except in the `descriptor_set::sys` module
+    pub(crate) fn update_after_bind_descriptors(&self) -> &Mutex<u32> {
+        &self.update_after_bind_descriptors
+    }
+
     pub(crate) fn fence_pool(&self) -> &Mutex<Vec<vk::Fence>> {
         &self.fence_pool
     }
@@ -671,16 +823,42 @@ impl Iterator for QueuesIter {
 
             self.next_queue += 1;
 
+            let flags = queue_create_flags(
+                self.device.enabled_features(),
+                self.device
+                    .physical_device()
+                    .queue_family_by_id(family)
+                    .unwrap(),
+            );
+
+            // Queues created with flags can only be retrieved with `vkGetDeviceQueue2`.
             let mut output = MaybeUninit::uninit();
-            self.device
-                .vk
-                .GetDeviceQueue(self.device.device, family, id, output.as_mut_ptr());
+            if flags != 0 {
+                let infos = vk::DeviceQueueInfo2 {
+                    sType: vk::STRUCTURE_TYPE_DEVICE_QUEUE_INFO_2,
+                    pNext: ptr::null(),
+                    flags,
+                    queueFamilyIndex: family,
+                    queueIndex: id,
+                };
+                self.device
+                    .vk
+                    .GetDeviceQueue2(self.device.device, &infos, output.as_mut_ptr());
+            } else {
+                self.device
+                    .vk
+                    .GetDeviceQueue(self.device.device, family, id, output.as_mut_ptr());
+            }
+
+            let handle = output.assume_init();
 
             Some(Arc::new(Queue {
-                queue: Mutex::new(output.assume_init()),
+                queue: Mutex::new(handle),
+                handle,
                 device: self.device.clone(),
                 family: family,
                 id: id,
+                protected: (flags & vk::DEVICE_QUEUE_CREATE_PROTECTED_BIT) != 0,
             }))
         }
     }
@@ -694,6 +872,20 @@ impl Iterator for QueuesIter {
 
 impl ExactSizeIterator for QueuesIter {}
 
+// Returns the flags that the queues of `family` are created with.
+//
+// When the `protected_memory` feature is enabled, the queues of the families that support it
+// are created as protected-capable queues, which can execute both protected and unprotected
+// command buffers.
+#[inline]
+fn queue_create_flags(features: &Features, family: QueueFamily) -> vk::DeviceQueueCreateFlags {
+    if features.protected_memory && family.supports_protected() {
+        vk::DEVICE_QUEUE_CREATE_PROTECTED_BIT
+    } else {
+        0
+    }
+}
+
 /// Error that can be returned when creating a device.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DeviceCreationError {
@@ -772,13 +964,31 @@ impl From<Error> for DeviceCreationError {
 }
 
 /// Represents a queue where commands can be submitted.
-// TODO: should use internal synchronization?
+///
+/// Vulkan requires queues to be externally synchronized: two threads must never submit to, present
+/// on, or wait on the same queue at the same time. In order to make it possible to share an
+/// `Arc<Queue>` between threads, the handle is protected by an internal mutex which is locked for
+/// the duration of each submission, presentation, sparse binding and `wait()`.
+///
+/// > **Note**: The lock is held only for the duration of the Vulkan call itself and is usually
+/// > uncontended, but submitting from many threads at once to the same queue will serialize these
+/// > calls. If this matters, consider creating one queue per thread or batching your submissions.
+/// > Single-threaded applications that want to avoid the lock entirely can use
+/// > `internal_object_unsynchronized`, but must then never use the queue through vulkano at the
+/// > same time as the raw handle.
+///
+/// Queues that belong to the same family each have their own lock. They are separate Vulkan
+/// queues, so two threads can submit to two of them at the same time. Only concurrent uses of the
+/// same queue are serialized.
 #[derive(Debug)]
 pub struct Queue {
     queue: Mutex<vk::Queue>,
+    // Copy of the handle protected by `queue`. Only used by `internal_object_unsynchronized`.
+    handle: vk::Queue,
     device: Arc<Device>,
     family: u32,
     id: u32, // id within family
+    protected: bool,
 }
 
 impl Queue {
@@ -811,9 +1021,22 @@ impl Queue {
         self.id
     }
 
+    /// Returns true if this queue is protected-capable, in which case protected command buffers
+    /// can be submitted to it.
+    ///
+    /// Queues are protected-capable if the `protected_memory` feature was enabled when creating
+    /// the device and their family supports it.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
     /// Waits until all work on this queue has finished.
     ///
     /// Just like `Device::wait()`, you shouldn't have to call this function in a typical program.
+    ///
+    /// The internal lock of the queue is held while waiting, which means that other threads
+    /// trying to submit to this queue will block until this function returns.
     #[inline]
     pub fn wait(&self) -> Result<(), OomError> {
         unsafe {
@@ -823,6 +1046,29 @@ impl Queue {
             Ok(())
         }
     }
+
+    /// Returns the Vulkan handle of the queue without locking the internal mutex.
+    ///
+    /// This is an escape hatch for applications that submit to this queue from a single thread
+    /// and want to avoid the cost of the lock.
+    ///
+    /// Vulkano's own submissions, presentations, sparse bindings and `wait()` keep locking the
+    /// internal mutex, but uses of the returned handle don't. The mutex therefore doesn't protect
+    /// the raw handle against vulkano, and the two must never be used at the same time.
+    ///
+    /// # Safety
+    ///
+    /// - The caller must ensure that the returned handle isn't used by a Vulkan function that
+    ///   requires external synchronization (`vkQueueSubmit`, `vkQueuePresentKHR`,
+    ///   `vkQueueBindSparse`, `vkQueueWaitIdle`) while another thread uses this queue.
+    /// - In particular, the caller must not submit, present, bind sparse memory or call `wait()`
+    ///   through vulkano on this queue, from any thread, while the returned handle is in use. Use
+    ///   `internal_object_guard` instead if vulkano may use the queue concurrently.
+    ///
+    #[inline]
+    pub unsafe fn internal_object_unsynchronized(&self) -> vk::Queue {
+        self.handle
+    }
 }
 
 impl PartialEq for Queue {
@@ -850,12 +1096,15 @@ unsafe impl SynchronizedVulkanObject for Queue {
 
 #[cfg(test)]
 mod tests {
+    use check_errors;
     use device::Device;
     use device::DeviceCreationError;
     use device::DeviceExtensions;
     use features::Features;
     use instance;
     use std::sync::Arc;
+    use std::thread;
+    use SynchronizedVulkanObject;
 
     #[test]
     fn one_ref() {
@@ -863,6 +1112,76 @@ mod tests {
         assert!(Arc::get_mut(&mut device).is_some());
     }
 
+    #[test]
+    fn queue_wait_from_multiple_threads() {
+        let (_, queue) = gfx_dev_and_queue!();
+
+        let threads = (0..4)
+            .map(|_| {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    for _ in 0..16 {
+                        queue.wait().unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn queue_unsynchronized_handle() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let handle = unsafe { queue.internal_object_unsynchronized() };
+        assert_eq!(handle, *queue.internal_object_guard());
+
+        // Alternating between the raw handle and vulkano's locked paths is fine as long as the
+        // two are never used at the same time.
+        unsafe {
+            let vk = device.pointers();
+            check_errors(vk.QueueWaitIdle(handle)).unwrap();
+        }
+        queue.wait().unwrap();
+        unsafe {
+            let vk = device.pointers();
+            check_errors(vk.QueueWaitIdle(handle)).unwrap();
+        }
+    }
+
+    #[test]
+    fn multiple_queues_same_family() {
+        let instance = instance!();
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = match physical.queue_families().find(|q| q.queues_count() >= 2) {
+            Some(f) => f,
+            None => return,
+        };
+
+        let (_, queues) = Device::new(
+            physical,
+            &Features::none(),
+            &DeviceExtensions::none(),
+            vec![(family, 1.0), (family, 0.5)],
+        )
+        .unwrap();
+
+        let queues = queues.collect::<Vec<_>>();
+        assert_eq!(queues.len(), 2);
+        for (id, queue) in queues.iter().enumerate() {
+            assert_eq!(queue.family().id(), family.id());
+            assert_eq!(queue.id_within_family(), id as u32);
+        }
+        assert!(!queues[0].is_same(&queues[1]));
+    }
+
     #[test]
     fn too_many_queues() {
         let instance = instance!();
@@ -280,6 +280,48 @@ pub struct Features {
 
     pub shader_float16: bool,
     pub shader_int8: bool,
+
+    pub timeline_semaphore: bool,
+
+    pub shader_input_attachment_array_dynamic_indexing: bool,
+    pub shader_uniform_texel_buffer_array_dynamic_indexing: bool,
+    pub shader_storage_texel_buffer_array_dynamic_indexing: bool,
+    pub shader_uniform_buffer_array_non_uniform_indexing: bool,
+    pub shader_sampled_image_array_non_uniform_indexing: bool,
+    pub shader_storage_buffer_array_non_uniform_indexing: bool,
+    pub shader_storage_image_array_non_uniform_indexing: bool,
+    pub shader_input_attachment_array_non_uniform_indexing: bool,
+    pub shader_uniform_texel_buffer_array_non_uniform_indexing: bool,
+    pub shader_storage_texel_buffer_array_non_uniform_indexing: bool,
+    pub descriptor_binding_uniform_buffer_update_after_bind: bool,
+    pub descriptor_binding_sampled_image_update_after_bind: bool,
+    pub descriptor_binding_storage_image_update_after_bind: bool,
+    pub descriptor_binding_storage_buffer_update_after_bind: bool,
+    pub descriptor_binding_uniform_texel_buffer_update_after_bind: bool,
+    pub descriptor_binding_storage_texel_buffer_update_after_bind: bool,
+    pub descriptor_binding_update_unused_while_pending: bool,
+    pub descriptor_binding_partially_bound: bool,
+    pub descriptor_binding_variable_descriptor_count: bool,
+    pub runtime_descriptor_array: bool,
+
+    pub separate_depth_stencil_layouts: bool,
+
+    pub pipeline_executable_info: bool,
+
+    pub protected_memory: bool,
+
+    pub maintenance4: bool,
+
+    pub extended_dynamic_state: bool,
+
+    pub mutable_descriptor_type: bool,
+
+    pub custom_border_colors: bool,
+    pub custom_border_color_without_format: bool,
+
+    pub index_type_uint8: bool,
+
+    pub synchronization2: bool,
 }
 
 pub(crate) struct FeaturesFfi {
@@ -291,6 +333,17 @@ pub(crate) struct FeaturesFfi {
     i8_storage: vk::PhysicalDevice8BitStorageFeatures,
     i16_storage: vk::PhysicalDevice16BitStorageFeatures,
     f16_i8: vk::PhysicalDeviceShaderFloat16Int8Features,
+    timeline_semaphore: vk::PhysicalDeviceTimelineSemaphoreFeatures,
+    descriptor_indexing: vk::PhysicalDeviceDescriptorIndexingFeaturesEXT,
+    separate_depth_stencil_layouts: vk::PhysicalDeviceSeparateDepthStencilLayoutsFeatures,
+    pipeline_executable_properties: vk::PhysicalDevicePipelineExecutablePropertiesFeaturesKHR,
+    protected_memory: vk::PhysicalDeviceProtectedMemoryFeatures,
+    maintenance4: vk::PhysicalDeviceMaintenance4FeaturesKHR,
+    extended_dynamic_state: vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT,
+    mutable_descriptor_type: vk::PhysicalDeviceMutableDescriptorTypeFeaturesEXT,
+    custom_border_color: vk::PhysicalDeviceCustomBorderColorFeaturesEXT,
+    index_type_uint8: vk::PhysicalDeviceIndexTypeUint8FeaturesEXT,
+    synchronization2: vk::PhysicalDeviceSynchronization2Features,
 }
 
 macro_rules! features {
@@ -554,4 +607,112 @@ features! {
         shader_int8 => shaderInt8,
       ],
     },
+    extension {
+      ty: vk::PhysicalDeviceTimelineSemaphoreFeatures,
+      ffi_name: timeline_semaphore,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_FEATURES,
+      fields: [
+        timeline_semaphore => timelineSemaphore,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceDescriptorIndexingFeaturesEXT,
+      ffi_name: descriptor_indexing,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_FEATURES_EXT,
+      fields: [
+        shader_input_attachment_array_dynamic_indexing => shaderInputAttachmentArrayDynamicIndexing,
+        shader_uniform_texel_buffer_array_dynamic_indexing => shaderUniformTexelBufferArrayDynamicIndexing,
+        shader_storage_texel_buffer_array_dynamic_indexing => shaderStorageTexelBufferArrayDynamicIndexing,
+        shader_uniform_buffer_array_non_uniform_indexing => shaderUniformBufferArrayNonUniformIndexing,
+        shader_sampled_image_array_non_uniform_indexing => shaderSampledImageArrayNonUniformIndexing,
+        shader_storage_buffer_array_non_uniform_indexing => shaderStorageBufferArrayNonUniformIndexing,
+        shader_storage_image_array_non_uniform_indexing => shaderStorageImageArrayNonUniformIndexing,
+        shader_input_attachment_array_non_uniform_indexing => shaderInputAttachmentArrayNonUniformIndexing,
+        shader_uniform_texel_buffer_array_non_uniform_indexing => shaderUniformTexelBufferArrayNonUniformIndexing,
+        shader_storage_texel_buffer_array_non_uniform_indexing => shaderStorageTexelBufferArrayNonUniformIndexing,
+        descriptor_binding_uniform_buffer_update_after_bind => descriptorBindingUniformBufferUpdateAfterBind,
+        descriptor_binding_sampled_image_update_after_bind => descriptorBindingSampledImageUpdateAfterBind,
+        descriptor_binding_storage_image_update_after_bind => descriptorBindingStorageImageUpdateAfterBind,
+        descriptor_binding_storage_buffer_update_after_bind => descriptorBindingStorageBufferUpdateAfterBind,
+        descriptor_binding_uniform_texel_buffer_update_after_bind => descriptorBindingUniformTexelBufferUpdateAfterBind,
+        descriptor_binding_storage_texel_buffer_update_after_bind => descriptorBindingStorageTexelBufferUpdateAfterBind,
+        descriptor_binding_update_unused_while_pending => descriptorBindingUpdateUnusedWhilePending,
+        descriptor_binding_partially_bound => descriptorBindingPartiallyBound,
+        descriptor_binding_variable_descriptor_count => descriptorBindingVariableDescriptorCount,
+        runtime_descriptor_array => runtimeDescriptorArray,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceSeparateDepthStencilLayoutsFeatures,
+      ffi_name: separate_depth_stencil_layouts,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SEPARATE_DEPTH_STENCIL_LAYOUTS_FEATURES,
+      fields: [
+        separate_depth_stencil_layouts => separateDepthStencilLayouts,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDevicePipelineExecutablePropertiesFeaturesKHR,
+      ffi_name: pipeline_executable_properties,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PIPELINE_EXECUTABLE_PROPERTIES_FEATURES_KHR,
+      fields: [
+        pipeline_executable_info => pipelineExecutableInfo,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceProtectedMemoryFeatures,
+      ffi_name: protected_memory,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROTECTED_MEMORY_FEATURES,
+      fields: [
+        protected_memory => protectedMemory,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceMaintenance4FeaturesKHR,
+      ffi_name: maintenance4,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MAINTENANCE_4_FEATURES_KHR,
+      fields: [
+        maintenance4 => maintenance4,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT,
+      ffi_name: extended_dynamic_state,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_FEATURES_EXT,
+      fields: [
+        extended_dynamic_state => extendedDynamicState,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceMutableDescriptorTypeFeaturesEXT,
+      ffi_name: mutable_descriptor_type,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MUTABLE_DESCRIPTOR_TYPE_FEATURES_EXT,
+      fields: [
+        mutable_descriptor_type => mutableDescriptorType,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceCustomBorderColorFeaturesEXT,
+      ffi_name: custom_border_color,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_CUSTOM_BORDER_COLOR_FEATURES_EXT,
+      fields: [
+        custom_border_colors => customBorderColors,
+        custom_border_color_without_format => customBorderColorWithoutFormat,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceIndexTypeUint8FeaturesEXT,
+      ffi_name: index_type_uint8,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_INDEX_TYPE_UINT8_FEATURES_EXT,
+      fields: [
+        index_type_uint8 => indexTypeUint8,
+      ],
+    },
+    extension {
+      ty: vk::PhysicalDeviceSynchronization2Features,
+      ffi_name: synchronization2,
+      sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SYNCHRONIZATION_2_FEATURES,
+      fields: [
+        synchronization2 => synchronization2,
+      ],
+    },
 }
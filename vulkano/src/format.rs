@@ -107,6 +107,7 @@ use std::vec::IntoIter as VecIntoIter;
 use std::{error, fmt, mem};
 
 use half::f16;
+use image::ImageAspects;
 use instance::PhysicalDevice;
 
 use vk;
@@ -245,6 +246,16 @@ macro_rules! formats {
                 }
             }
 
+            /// Returns the aspects that an image of this format has.
+            ///
+            /// This is the aspect mask to use when accessing the whole image, for example in a
+            /// barrier. Copies between a buffer and a depth-stencil image must use only one of
+            /// the depth and the stencil aspects.
+            #[inline]
+            pub fn aspects(&self) -> ImageAspects {
+                ImageAspects::from_format(*self)
+            }
+
             /// Retrieves the properties of a format when used by a certain device.
             #[inline]
             pub fn properties(&self, device: PhysicalDevice) -> FormatProperties {
@@ -809,6 +820,56 @@ pub unsafe trait StrongStorage: FormatDesc {
     type Pixel: Copy;
 }
 
+/// Type of a texel that can be read from or written to a texel buffer.
+///
+/// Maps a Rust type to the format of a buffer view, for example `[f32; 4]` to
+/// `R32G32B32A32Sfloat`. Integer types map to the `Uint` and `Sint` formats, and floating-point
+/// types map to the `Sfloat` formats.
+pub unsafe trait TexelFormat: Copy + 'static {
+    /// The format that texel buffers of this type use.
+    type Format: StrongStorage<Pixel = Self> + Default + Send + Sync + 'static;
+}
+
+macro_rules! impl_texel_format {
+    {$($ty:ty => $format:ident;)+} => {
+        $(
+            unsafe impl TexelFormat for $ty {
+                type Format = $format;
+            }
+        )+
+    };
+}
+
+impl_texel_format! {
+    u8 => R8Uint;
+    [u8; 2] => R8G8Uint;
+    [u8; 4] => R8G8B8A8Uint;
+    i8 => R8Sint;
+    [i8; 2] => R8G8Sint;
+    [i8; 4] => R8G8B8A8Sint;
+    u16 => R16Uint;
+    [u16; 2] => R16G16Uint;
+    [u16; 4] => R16G16B16A16Uint;
+    i16 => R16Sint;
+    [i16; 2] => R16G16Sint;
+    [i16; 4] => R16G16B16A16Sint;
+    f16 => R16Sfloat;
+    [f16; 2] => R16G16Sfloat;
+    [f16; 4] => R16G16B16A16Sfloat;
+    u32 => R32Uint;
+    [u32; 2] => R32G32Uint;
+    [u32; 3] => R32G32B32Uint;
+    [u32; 4] => R32G32B32A32Uint;
+    i32 => R32Sint;
+    [i32; 2] => R32G32Sint;
+    [i32; 3] => R32G32B32Sint;
+    [i32; 4] => R32G32B32A32Sint;
+    f32 => R32Sfloat;
+    [f32; 2] => R32G32Sfloat;
+    [f32; 3] => R32G32B32Sfloat;
+    [f32; 4] => R32G32B32A32Sfloat;
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum FormatTy {
     Float,
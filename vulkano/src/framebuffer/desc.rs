@@ -457,6 +457,10 @@ pub struct AttachmentDescription {
 
     /// Equivalent of `load` for the stencil component of the attachment, if any. Irrelevant if
     /// there is no stencil component.
+    ///
+    /// This allows for example loading the depth component while clearing the stencil
+    /// component. The render pass macros use the same value as `load` unless `stencil_load` is
+    /// specified.
     pub stencil_load: LoadOp,
     /// Equivalent of `store` for the stencil component of the attachment, if any. Irrelevant if
     /// there is no stencil component.
@@ -565,6 +569,59 @@ pub struct PassDependencyDescription {
     pub by_region: bool,
 }
 
+impl PassDependencyDescription {
+    /// Builds a dependency of a subpass on itself, for reading in a fragment shader through an
+    /// input attachment what previous draw commands of the same subpass wrote to a color or
+    /// depth-stencil attachment. This is typically used for programmable blending.
+    ///
+    /// The attachment must be referenced both as an input attachment and as a color or
+    /// depth-stencil attachment of the subpass, with the `General` layout in both references.
+    /// The `ordered_passes_renderpass!` and `single_pass_renderpass!` macros automatically do
+    /// this and add this dependency when an attachment appears in both the `color` (or
+    /// `depth_stencil`) and `input` lists of a pass.
+    ///
+    /// The dependency is by region, which means that a fragment can only read the value written
+    /// at its own location.
+    ///
+    /// > **Note**: A self-dependency doesn't synchronize anything by itself. Between the draw
+    /// > command that writes the attachment and the one that reads it, a `vkCmdPipelineBarrier`
+    /// > must be recorded inside the render pass, with the `BY_REGION` flag and with stages and
+    /// > access masks that are a subset of the ones of this dependency. Such a barrier can be
+    /// > recorded with `UnsafeCommandBufferBuilder::pipeline_barrier`.
+    #[inline]
+    pub fn self_dependency(subpass: usize) -> PassDependencyDescription {
+        PassDependencyDescription {
+            source_subpass: subpass,
+            destination_subpass: subpass,
+            source_stages: PipelineStages {
+                color_attachment_output: true,
+                late_fragment_tests: true,
+                ..PipelineStages::none()
+            },
+            destination_stages: PipelineStages {
+                fragment_shader: true,
+                ..PipelineStages::none()
+            },
+            source_access: AccessFlagBits {
+                color_attachment_write: true,
+                depth_stencil_attachment_write: true,
+                ..AccessFlagBits::none()
+            },
+            destination_access: AccessFlagBits {
+                input_attachment_read: true,
+                ..AccessFlagBits::none()
+            },
+            by_region: true,
+        }
+    }
+
+    /// Returns true if this is a dependency of a subpass on itself.
+    #[inline]
+    pub fn is_self_dependency(&self) -> bool {
+        self.source_subpass == self.destination_subpass
+    }
+}
+
 /// Describes what the implementation should do with an attachment after all the subpasses have
 /// completed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
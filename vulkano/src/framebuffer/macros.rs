@@ -44,6 +44,8 @@ macro_rules! ordered_passes_renderpass {
                 $atch_name:ident: {
                     load: $load:ident,
                     store: $store:ident,
+                    $(stencil_load: $stencil_load:ident,)*
+                    $(stencil_store: $stencil_store:ident,)*
                     format: $format:expr,
                     samples: $samples:expr,
                     $(initial_layout: $init_layout:expr,)*
@@ -151,8 +153,16 @@ macro_rules! ordered_passes_renderpass {
                             samples: desc.$atch_name.1,
                             load: $crate::framebuffer::LoadOp::$load,
                             store: $crate::framebuffer::StoreOp::$store,
-                            stencil_load: $crate::framebuffer::LoadOp::$load,
-                            stencil_store: $crate::framebuffer::StoreOp::$store,
+                            stencil_load: {
+                                let mut op = $crate::framebuffer::LoadOp::$load;
+                                $(op = $crate::framebuffer::LoadOp::$stencil_load;)*
+                                op
+                            },
+                            stencil_store: {
+                                let mut op = $crate::framebuffer::StoreOp::$store;
+                                $(op = $crate::framebuffer::StoreOp::$stencil_store;)*
+                                op
+                            },
                             initial_layout: initial_layout,
                             final_layout: final_layout,
                         });
@@ -190,21 +200,38 @@ macro_rules! ordered_passes_renderpass {
 
                 $({
                     if id == cur_pass_num {
+                        // Attachments used both as an input and as an output must be in the
+                        // `General` layout.
+                        let inputs: &[usize] = &[$($input_atch),*];
+                        let outputs: &[usize] = &[$($color_atch,)* $($depth_atch)*];
+
                         let mut depth = None;
                         $(
-                            depth = Some(($depth_atch, ImageLayout::DepthStencilAttachmentOptimal));
+                            depth = Some(($depth_atch, if inputs.contains(&$depth_atch) {
+                                ImageLayout::General
+                            } else {
+                                ImageLayout::DepthStencilAttachmentOptimal
+                            }));
                         )*
 
                         let mut desc = PassDescription {
                             color_attachments: vec![
                                 $(
-                                    ($color_atch, ImageLayout::ColorAttachmentOptimal)
+                                    ($color_atch, if inputs.contains(&$color_atch) {
+                                        ImageLayout::General
+                                    } else {
+                                        ImageLayout::ColorAttachmentOptimal
+                                    })
                                 ),*
                             ],
                             depth_stencil: depth,
                             input_attachments: vec![
                                 $(
-                                    ($input_atch, ImageLayout::ShaderReadOnlyOptimal)
+                                    ($input_atch, if outputs.contains(&$input_atch) {
+                                        ImageLayout::General
+                                    } else {
+                                        ImageLayout::ShaderReadOnlyOptimal
+                                    })
                                 ),*
                             ],
                             resolve_attachments: vec![
@@ -232,9 +259,38 @@ macro_rules! ordered_passes_renderpass {
                 None
             }
 
+            /// Returns the list of subpasses that use an attachment both as an input and as an
+            /// output, and that therefore need a self-dependency.
+            fn feedback_subpasses() -> Vec<usize> {
+                #![allow(unused_assignments)]
+                #![allow(unused_mut)]
+                #![allow(unused_variables)]
+
+                let mut attachment_num = 0;
+                $(
+                    let $atch_name = attachment_num;
+                    attachment_num += 1;
+                )*
+
+                let mut out = Vec::new();
+                let mut cur_pass_num = 0;
+
+                $({
+                    let inputs: &[usize] = &[$($input_atch),*];
+                    let outputs: &[usize] = &[$($color_atch,)* $($depth_atch)*];
+                    if outputs.iter().any(|o| inputs.contains(o)) {
+                        out.push(cur_pass_num);
+                    }
+
+                    cur_pass_num += 1;
+                })*
+
+                out
+            }
+
             #[inline]
             fn num_dependencies() -> usize {
-                num_subpasses().saturating_sub(1)
+                num_subpasses().saturating_sub(1) + feedback_subpasses().len()
             }
 
             #[inline]
@@ -242,7 +298,10 @@ macro_rules! ordered_passes_renderpass {
                 let num_passes = num_subpasses();
 
                 if id + 1 >= num_passes {
-                    return None;
+                    let id = id + 1 - num_passes.max(1);
+                    return feedback_subpasses()
+                        .get(id)
+                        .map(|&pass| PassDependencyDescription::self_dependency(pass));
                 }
 
                 Some(PassDependencyDescription {
@@ -274,41 +333,51 @@ macro_rules! ordered_passes_renderpass {
                 let mut final_layout = None;
 
                 $({
-                    $(
-                        if $depth_atch == num {
-                            if initial_layout.is_none() {
-                                initial_layout = Some(ImageLayout::DepthStencilAttachmentOptimal);
-                            }
-                            final_layout = Some(ImageLayout::DepthStencilAttachmentOptimal);
+                    let inputs: &[usize] = &[$($input_atch),*];
+                    let outputs: &[usize] = &[$($color_atch,)* $($depth_atch)*];
+
+                    if inputs.contains(&num) && outputs.contains(&num) {
+                        if initial_layout.is_none() {
+                            initial_layout = Some(ImageLayout::General);
                         }
-                    )*
+                        final_layout = Some(ImageLayout::General);
+                    } else {
+                        $(
+                            if $depth_atch == num {
+                                if initial_layout.is_none() {
+                                    initial_layout = Some(ImageLayout::DepthStencilAttachmentOptimal);
+                                }
+                                final_layout = Some(ImageLayout::DepthStencilAttachmentOptimal);
+                            }
+                        )*
 
-                    $(
-                        if $color_atch == num {
-                            if initial_layout.is_none() {
-                                initial_layout = Some(ImageLayout::ColorAttachmentOptimal);
+                        $(
+                            if $color_atch == num {
+                                if initial_layout.is_none() {
+                                    initial_layout = Some(ImageLayout::ColorAttachmentOptimal);
+                                }
+                                final_layout = Some(ImageLayout::ColorAttachmentOptimal);
                             }
-                            final_layout = Some(ImageLayout::ColorAttachmentOptimal);
-                        }
-                    )*
+                        )*
 
-                    $($(
-                        if $resolve_atch == num {
-                            if initial_layout.is_none() {
-                                initial_layout = Some(ImageLayout::TransferDstOptimal);
+                        $($(
+                            if $resolve_atch == num {
+                                if initial_layout.is_none() {
+                                    initial_layout = Some(ImageLayout::TransferDstOptimal);
+                                }
+                                final_layout = Some(ImageLayout::TransferDstOptimal);
                             }
-                            final_layout = Some(ImageLayout::TransferDstOptimal);
-                        }
-                    )*)*
+                        )*)*
 
-                    $(
-                        if $input_atch == num {
-                            if initial_layout.is_none() {
-                                initial_layout = Some(ImageLayout::ShaderReadOnlyOptimal);
+                        $(
+                            if $input_atch == num {
+                                if initial_layout.is_none() {
+                                    initial_layout = Some(ImageLayout::ShaderReadOnlyOptimal);
+                                }
+                                final_layout = Some(ImageLayout::ShaderReadOnlyOptimal);
                             }
-                            final_layout = Some(ImageLayout::ShaderReadOnlyOptimal);
-                        }
-                    )*
+                        )*
+                    }
                 })*
 
                 $(if $atch_name == num {
@@ -337,6 +406,11 @@ macro_rules! ordered_passes_renderpass {
 #[cfg(test)]
 mod tests {
     use format::Format;
+    use framebuffer::LoadOp;
+    use framebuffer::RenderPassCreationError;
+    use framebuffer::RenderPassDesc;
+    use framebuffer::StoreOp;
+    use image::ImageLayout;
 
     #[test]
     fn single_pass_resolve() {
@@ -364,4 +438,82 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn feedback_attachment() {
+        let (device, _) = gfx_dev_and_queue!();
+        let render_pass = ordered_passes_renderpass!(device.clone(),
+            attachments: {
+                a: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            passes: [
+                {
+                    color: [a],
+                    depth_stencil: {},
+                    input: [a]
+                }
+            ]
+        )
+        .unwrap();
+
+        let subpass = render_pass.subpass_desc(0).unwrap();
+        assert_eq!(subpass.color_attachments, vec![(0, ImageLayout::General)]);
+        assert_eq!(subpass.input_attachments, vec![(0, ImageLayout::General)]);
+        assert_eq!(render_pass.num_dependencies(), 1);
+        assert!(render_pass.dependency_desc(0).unwrap().is_self_dependency());
+    }
+
+    #[test]
+    fn separate_stencil_ops() {
+        let (device, _) = gfx_dev_and_queue!();
+        let render_pass = single_pass_renderpass!(device.clone(),
+            attachments: {
+                depth: {
+                    load: Load,
+                    store: Store,
+                    stencil_load: Clear,
+                    stencil_store: DontCare,
+                    format: Format::D24Unorm_S8Uint,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth}
+            }
+        )
+        .unwrap();
+
+        let desc = render_pass.attachment_desc(0).unwrap();
+        assert_eq!(desc.load, LoadOp::Load);
+        assert_eq!(desc.store, StoreOp::Store);
+        assert_eq!(desc.stencil_load, LoadOp::Clear);
+        assert_eq!(desc.stencil_store, StoreOp::DontCare);
+
+        let result = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Load,
+                    store: Store,
+                    stencil_load: Clear,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        );
+
+        match result {
+            Err(RenderPassCreationError::StencilOpsWithoutStencilAspect { attachment: 0 }) => (),
+            _ => panic!(),
+        }
+    }
 }
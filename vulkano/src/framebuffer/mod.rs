@@ -107,6 +107,9 @@ pub use self::framebuffer::Framebuffer;
 pub use self::framebuffer::FramebufferBuilder;
 pub use self::framebuffer::FramebufferCreationError;
 pub use self::framebuffer::FramebufferSys;
+pub use self::msaa::MsaaRenderPassDesc;
+pub use self::render_target::RenderTarget;
+pub use self::render_target::RenderTargetCreationError;
 pub use self::sys::RenderPass;
 pub use self::sys::RenderPassCreationError;
 pub use self::sys::RenderPassSys;
@@ -126,6 +129,8 @@ mod compat_atch;
 mod desc;
 mod empty;
 mod framebuffer;
+mod msaa;
+mod render_target;
 mod sys;
 mod traits;
 
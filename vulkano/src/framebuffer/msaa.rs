@@ -0,0 +1,233 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::iter;
+
+use format::ClearValue;
+use format::Format;
+use framebuffer::AttachmentDescription;
+use framebuffer::LoadOp;
+use framebuffer::PassDependencyDescription;
+use framebuffer::PassDescription;
+use framebuffer::RenderPassDesc;
+use framebuffer::RenderPassDescClearValues;
+use framebuffer::StoreOp;
+use image::ImageLayout;
+
+/// Description of a render pass with one subpass that draws to a multisampled color attachment
+/// and resolves it into a single-sampled attachment.
+///
+/// The render pass has two attachments:
+///
+/// - Attachment 0 is the multisampled attachment. It is cleared at the start of the render pass
+///   and its content is discarded at the end, as only the resolved image is usually needed.
+/// - Attachment 1 is the single-sampled resolve attachment. Its previous content is ignored and
+///   the resolved image is stored at the end of the render pass.
+///
+/// Both attachments have the same format. Whether the device supports the number of samples for
+/// color attachments is checked when the render pass is built.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::format::Format;
+/// use vulkano::framebuffer::MsaaRenderPassDesc;
+/// use vulkano::framebuffer::RenderPassDesc;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+///
+/// let render_pass = MsaaRenderPassDesc::new(Format::B8G8R8A8Srgb, 4)
+///     .build_render_pass(device.clone())
+///     .unwrap();
+///
+/// // The framebuffer must contain the multisampled image followed by the resolve image, and
+/// // only the multisampled attachment is cleared:
+/// // builder.begin_render_pass(framebuffer, false, [0.0, 0.0, 0.0, 1.0].into())
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct MsaaRenderPassDesc {
+    format: Format,
+    samples: u32,
+    resolve_final_layout: ImageLayout,
+}
+
+impl MsaaRenderPassDesc {
+    /// Builds the description of a render pass with a multisampled attachment of `samples`
+    /// samples and its resolve attachment, both of format `format`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `samples` is not a power of two greater than 1.
+    ///
+    #[inline]
+    pub fn new(format: Format, samples: u32) -> MsaaRenderPassDesc {
+        assert!(samples > 1 && samples.is_power_of_two());
+
+        MsaaRenderPassDesc {
+            format,
+            samples,
+            resolve_final_layout: ImageLayout::ColorAttachmentOptimal,
+        }
+    }
+
+    /// Sets the layout that the resolve attachment is transitioned to at the end of the render
+    /// pass. The default is `ColorAttachmentOptimal`.
+    ///
+    /// For example, use `PresentSrc` if the resolve attachment is a swapchain image.
+    #[inline]
+    pub fn resolve_final_layout(mut self, layout: ImageLayout) -> MsaaRenderPassDesc {
+        self.resolve_final_layout = layout;
+        self
+    }
+
+    /// Returns the format of the attachments.
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the number of samples of the multisampled attachment.
+    #[inline]
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+}
+
+unsafe impl RenderPassDesc for MsaaRenderPassDesc {
+    #[inline]
+    fn num_attachments(&self) -> usize {
+        2
+    }
+
+    #[inline]
+    fn attachment_desc(&self, num: usize) -> Option<AttachmentDescription> {
+        match num {
+            0 => Some(AttachmentDescription {
+                format: self.format,
+                samples: self.samples,
+                load: LoadOp::Clear,
+                store: StoreOp::DontCare,
+                stencil_load: LoadOp::Clear,
+                stencil_store: StoreOp::DontCare,
+                initial_layout: ImageLayout::Undefined,
+                final_layout: ImageLayout::ColorAttachmentOptimal,
+            }),
+            1 => Some(AttachmentDescription {
+                format: self.format,
+                samples: 1,
+                load: LoadOp::DontCare,
+                store: StoreOp::Store,
+                stencil_load: LoadOp::DontCare,
+                stencil_store: StoreOp::Store,
+                initial_layout: ImageLayout::Undefined,
+                final_layout: self.resolve_final_layout,
+            }),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn num_subpasses(&self) -> usize {
+        1
+    }
+
+    #[inline]
+    fn subpass_desc(&self, num: usize) -> Option<PassDescription> {
+        if num == 0 {
+            Some(PassDescription {
+                color_attachments: vec![(0, ImageLayout::ColorAttachmentOptimal)],
+                depth_stencil: None,
+                input_attachments: vec![],
+                resolve_attachments: vec![(1, ImageLayout::ColorAttachmentOptimal)],
+                preserve_attachments: vec![],
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn num_dependencies(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn dependency_desc(&self, _: usize) -> Option<PassDependencyDescription> {
+        None
+    }
+}
+
+unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for MsaaRenderPassDesc {
+    #[inline]
+    fn convert_clear_values(
+        &self,
+        values: Vec<ClearValue>,
+    ) -> Box<dyn Iterator<Item = ClearValue>> {
+        // FIXME: safety checks
+        Box::new(values.into_iter())
+    }
+}
+
+unsafe impl RenderPassDescClearValues<ClearValue> for MsaaRenderPassDesc {
+    #[inline]
+    fn convert_clear_values(&self, value: ClearValue) -> Box<dyn Iterator<Item = ClearValue>> {
+        Box::new(iter::once(value).chain(iter::once(ClearValue::None)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use framebuffer::LoadOp;
+    use framebuffer::MsaaRenderPassDesc;
+    use framebuffer::RenderPassCreationError;
+    use framebuffer::RenderPassDesc;
+    use framebuffer::StoreOp;
+
+    #[test]
+    fn attachments() {
+        let desc = MsaaRenderPassDesc::new(Format::R8G8B8A8Unorm, 4);
+
+        let msaa = desc.attachment_desc(0).unwrap();
+        assert_eq!(msaa.samples, 4);
+        assert_eq!(msaa.load, LoadOp::Clear);
+        assert_eq!(msaa.store, StoreOp::DontCare);
+
+        let resolve = desc.attachment_desc(1).unwrap();
+        assert_eq!(resolve.samples, 1);
+        assert_eq!(resolve.store, StoreOp::Store);
+
+        let pass = desc.subpass_desc(0).unwrap();
+        assert_eq!(pass.resolve_attachments.len(), pass.color_attachments.len());
+    }
+
+    #[test]
+    fn build() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let supported = device
+            .physical_device()
+            .limits()
+            .framebuffer_color_sample_counts();
+
+        // The `R8G8B8A8Unorm` format supports all the color sample counts of the device.
+        for &samples in &[2, 4, 8, 16, 32, 64] {
+            let result = MsaaRenderPassDesc::new(Format::R8G8B8A8Unorm, samples)
+                .build_render_pass(device.clone());
+
+            if supported & samples != 0 {
+                result.unwrap();
+            } else {
+                match result {
+                    Err(RenderPassCreationError::SampleCountNotSupported { attachment: 0 }) => (),
+                    _ => panic!(),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::AutoCommandBufferBuilderContextError;
+use command_buffer::BeginRenderPassError;
+use descriptor::descriptor_set::PersistentDescriptorSetBuilder;
+use descriptor::descriptor_set::PersistentDescriptorSetError;
+use descriptor::descriptor_set::PersistentDescriptorSetImg;
+use descriptor::descriptor_set::PersistentDescriptorSetSampler;
+use device::Device;
+use format::ClearValue;
+use format::Format;
+use framebuffer::Framebuffer;
+use framebuffer::FramebufferAbstract;
+use framebuffer::FramebufferCreationError;
+use framebuffer::RenderPassAbstract;
+use framebuffer::RenderPassCreationError;
+use framebuffer::Subpass;
+use image::AttachmentImage;
+use image::ImageCreationError;
+use sampler::Sampler;
+
+/// Image that can be rendered to and then sampled, along with everything needed to draw to it.
+///
+/// Creating a `RenderTarget` creates a color image, an optional depth image, a render pass with a
+/// single subpass that draws to them, and a framebuffer. This is the usual setup for offscreen
+/// rendering such as shadow maps, reflections or post-processing.
+///
+/// The color attachment is cleared at the start of the render pass, and the render pass leaves it
+/// in the `ShaderReadOnlyOptimal` layout so that the following commands of the same command
+/// buffer can sample it without a layout transition. At the end of the command buffer the image
+/// is transitioned back to `ColorAttachmentOptimal`, which is the layout that `AttachmentImage`
+/// expects between command buffers. The depth attachment is cleared to 1.0 and its content is
+/// discarded at the end of the render pass.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::format::Format;
+/// use vulkano::framebuffer::RenderTarget;
+/// # use vulkano::command_buffer::AutoCommandBufferBuilder;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// # let mut builder: AutoCommandBufferBuilder = return;
+///
+/// let target = RenderTarget::new(
+///     device.clone(),
+///     [512, 512],
+///     Format::R8G8B8A8Unorm,
+///     Some(Format::D16Unorm),
+/// ).unwrap();
+///
+/// // Graphics pipelines that draw to the target must be created with `target.subpass()`.
+/// target.begin(&mut builder, [0.0, 0.0, 0.0, 1.0].into()).unwrap();
+/// // builder.draw(...);
+/// target.end(&mut builder).unwrap();
+/// ```
+pub struct RenderTarget {
+    color: Arc<AttachmentImage>,
+    depth: Option<Arc<AttachmentImage>>,
+    render_pass: Arc<dyn RenderPassAbstract + Send + Sync>,
+    framebuffer: Arc<dyn FramebufferAbstract + Send + Sync>,
+}
+
+impl RenderTarget {
+    /// Creates the images, the render pass and the framebuffer of a render target.
+    ///
+    /// The color image is created with the sampled usage in addition to the color attachment
+    /// usage. If `depth_format` is `Some`, a depth image is created as well.
+    pub fn new(
+        device: Arc<Device>,
+        dimensions: [u32; 2],
+        color_format: Format,
+        depth_format: Option<Format>,
+    ) -> Result<RenderTarget, RenderTargetCreationError> {
+        let color = AttachmentImage::sampled(device.clone(), dimensions, color_format)?;
+
+        let (render_pass, depth, framebuffer) = match depth_format {
+            Some(depth_format) => {
+                let depth = AttachmentImage::new(device.clone(), dimensions, depth_format)?;
+
+                let render_pass: Arc<dyn RenderPassAbstract + Send + Sync> =
+                    Arc::new(single_pass_renderpass!(device.clone(),
+                        attachments: {
+                            color: {
+                                load: Clear,
+                                store: Store,
+                                format: color_format,
+                                samples: 1,
+                                final_layout: ImageLayout::ShaderReadOnlyOptimal,
+                            },
+                            depth: {
+                                load: Clear,
+                                store: DontCare,
+                                format: depth_format,
+                                samples: 1,
+                            }
+                        },
+                        pass: {
+                            color: [color],
+                            depth_stencil: {depth}
+                        }
+                    )?);
+
+                let framebuffer: Arc<dyn FramebufferAbstract + Send + Sync> = Arc::new(
+                    Framebuffer::start(render_pass.clone())
+                        .add(color.clone())?
+                        .add(depth.clone())?
+                        .build()?,
+                );
+
+                (render_pass, Some(depth), framebuffer)
+            }
+            None => {
+                let render_pass: Arc<dyn RenderPassAbstract + Send + Sync> =
+                    Arc::new(single_pass_renderpass!(device.clone(),
+                        attachments: {
+                            color: {
+                                load: Clear,
+                                store: Store,
+                                format: color_format,
+                                samples: 1,
+                                final_layout: ImageLayout::ShaderReadOnlyOptimal,
+                            }
+                        },
+                        pass: {
+                            color: [color],
+                            depth_stencil: {}
+                        }
+                    )?);
+
+                let framebuffer: Arc<dyn FramebufferAbstract + Send + Sync> = Arc::new(
+                    Framebuffer::start(render_pass.clone())
+                        .add(color.clone())?
+                        .build()?,
+                );
+
+                (render_pass, None, framebuffer)
+            }
+        };
+
+        Ok(RenderTarget {
+            color,
+            depth,
+            render_pass,
+            framebuffer,
+        })
+    }
+
+    /// Returns the dimensions of the target.
+    #[inline]
+    pub fn dimensions(&self) -> [u32; 2] {
+        self.color.dimensions()
+    }
+
+    /// Returns the color image. It can be sampled once the render pass has ended.
+    #[inline]
+    pub fn color_image(&self) -> &Arc<AttachmentImage> {
+        &self.color
+    }
+
+    /// Returns the depth image, if any.
+    #[inline]
+    pub fn depth_image(&self) -> Option<&Arc<AttachmentImage>> {
+        self.depth.as_ref()
+    }
+
+    /// Returns the render pass.
+    #[inline]
+    pub fn render_pass(&self) -> &Arc<dyn RenderPassAbstract + Send + Sync> {
+        &self.render_pass
+    }
+
+    /// Returns the framebuffer.
+    #[inline]
+    pub fn framebuffer(&self) -> &Arc<dyn FramebufferAbstract + Send + Sync> {
+        &self.framebuffer
+    }
+
+    /// Returns the subpass that graphics pipelines drawing to the target must be created with.
+    #[inline]
+    pub fn subpass(&self) -> Subpass<Arc<dyn RenderPassAbstract + Send + Sync>> {
+        Subpass::from(self.render_pass.clone(), 0).unwrap()
+    }
+
+    /// Begins the render pass of the target, clearing the color attachment with `clear_color`
+    /// and the depth attachment with 1.0.
+    #[inline]
+    pub fn begin<P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<P>,
+        clear_color: ClearValue,
+    ) -> Result<(), BeginRenderPassError> {
+        let mut clear_values = vec![clear_color];
+        if self.depth.is_some() {
+            clear_values.push(ClearValue::Depth(1.0));
+        }
+
+        builder.begin_render_pass(self.framebuffer.clone(), false, clear_values)?;
+        Ok(())
+    }
+
+    /// Ends the render pass of the target. The color image is then ready to be sampled.
+    #[inline]
+    pub fn end<P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<P>,
+    ) -> Result<(), AutoCommandBufferBuilderContextError> {
+        builder.end_render_pass()?;
+        Ok(())
+    }
+
+    /// Binds the color image with `sampler` as the next descriptor of a descriptor set.
+    ///
+    /// This is a shortcut for calling `add_sampled_image` with `color_image()`.
+    #[inline]
+    pub fn add_to_descriptor_set<R>(
+        &self,
+        builder: PersistentDescriptorSetBuilder<R>,
+        sampler: Arc<Sampler>,
+    ) -> Result<
+        PersistentDescriptorSetBuilder<(
+            (R, PersistentDescriptorSetImg<Arc<AttachmentImage>>),
+            PersistentDescriptorSetSampler,
+        )>,
+        PersistentDescriptorSetError,
+    > {
+        builder.add_sampled_image(self.color.clone(), sampler)
+    }
+}
+
+impl fmt::Debug for RenderTarget {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("RenderTarget")
+            .field("color", &self.color)
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
+/// Error that can happen when creating a `RenderTarget`.
+#[derive(Debug, Clone)]
+pub enum RenderTargetCreationError {
+    /// Failed to create one of the images.
+    ImageCreationError(ImageCreationError),
+    /// Failed to create the render pass.
+    RenderPassCreationError(RenderPassCreationError),
+    /// Failed to create the framebuffer.
+    FramebufferCreationError(FramebufferCreationError),
+}
+
+impl error::Error for RenderTargetCreationError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            RenderTargetCreationError::ImageCreationError(ref err) => Some(err),
+            RenderTargetCreationError::RenderPassCreationError(ref err) => Some(err),
+            RenderTargetCreationError::FramebufferCreationError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for RenderTargetCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                RenderTargetCreationError::ImageCreationError(_) => "failed to create an image",
+                RenderTargetCreationError::RenderPassCreationError(_) => {
+                    "failed to create the render pass"
+                }
+                RenderTargetCreationError::FramebufferCreationError(_) => {
+                    "failed to create the framebuffer"
+                }
+            }
+        )
+    }
+}
+
+impl From<ImageCreationError> for RenderTargetCreationError {
+    #[inline]
+    fn from(err: ImageCreationError) -> RenderTargetCreationError {
+        RenderTargetCreationError::ImageCreationError(err)
+    }
+}
+
+impl From<RenderPassCreationError> for RenderTargetCreationError {
+    #[inline]
+    fn from(err: RenderPassCreationError) -> RenderTargetCreationError {
+        RenderTargetCreationError::RenderPassCreationError(err)
+    }
+}
+
+impl From<FramebufferCreationError> for RenderTargetCreationError {
+    #[inline]
+    fn from(err: FramebufferCreationError) -> RenderTargetCreationError {
+        RenderTargetCreationError::FramebufferCreationError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_buffer::AutoCommandBufferBuilder;
+    use format::Format;
+    use framebuffer::FramebufferAbstract;
+    use framebuffer::RenderTarget;
+
+    #[test]
+    fn color_and_depth() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let target = RenderTarget::new(
+            device.clone(),
+            [64, 32],
+            Format::R8G8B8A8Unorm,
+            Some(Format::D16Unorm),
+        )
+        .unwrap();
+        assert_eq!(target.dimensions(), [64, 32]);
+        assert!(target.depth_image().is_some());
+        assert!(target.framebuffer().attached_image_view(1).is_some());
+
+        let mut builder =
+            AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+                .unwrap();
+        target
+            .begin(&mut builder, [0.0, 0.0, 0.0, 1.0].into())
+            .unwrap();
+        target.end(&mut builder).unwrap();
+        let _ = builder.build().unwrap();
+    }
+}
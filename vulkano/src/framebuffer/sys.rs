@@ -19,6 +19,7 @@ use std::sync::Mutex;
 use device::Device;
 use device::DeviceOwned;
 use format::ClearValue;
+use format::FormatTy;
 use framebuffer::AttachmentDescription;
 use framebuffer::EmptySinglePassRenderPassDesc;
 use framebuffer::LoadOp;
@@ -27,6 +28,8 @@ use framebuffer::PassDescription;
 use framebuffer::RenderPassAbstract;
 use framebuffer::RenderPassDesc;
 use framebuffer::RenderPassDescClearValues;
+use framebuffer::StoreOp;
+use image::ImageLayout;
 
 use check_errors;
 use vk;
@@ -106,6 +109,83 @@ where
                 true
             }));
 
+        // The number of samples of each attachment must be supported by the device for the
+        // aspects of its format.
+        for (atch_num, attachment) in description.attachment_descs().enumerate() {
+            let limits = device.physical_device().limits();
+            let supported = match attachment.format.ty() {
+                FormatTy::Depth => limits.framebuffer_depth_sample_counts(),
+                FormatTy::Stencil => limits.framebuffer_stencil_sample_counts(),
+                FormatTy::DepthStencil => {
+                    limits.framebuffer_depth_sample_counts()
+                        & limits.framebuffer_stencil_sample_counts()
+                }
+                _ => limits.framebuffer_color_sample_counts(),
+            };
+
+            if !attachment.samples.is_power_of_two() || (supported & attachment.samples) == 0 {
+                return Err(RenderPassCreationError::SampleCountNotSupported {
+                    attachment: atch_num,
+                });
+            }
+        }
+
+        // The stencil operations of attachments without a stencil component are ignored by the
+        // implementation, therefore specifying different stencil operations is most likely a
+        // mistake.
+        for (atch_num, attachment) in description.attachment_descs().enumerate() {
+            let has_stencil = match attachment.format.ty() {
+                FormatTy::Stencil | FormatTy::DepthStencil => true,
+                _ => false,
+            };
+
+            if !has_stencil
+                && ((attachment.stencil_load != attachment.load
+                    && attachment.stencil_load != LoadOp::DontCare)
+                    || (attachment.stencil_store != attachment.store
+                        && attachment.stencil_store != StoreOp::DontCare))
+            {
+                return Err(RenderPassCreationError::StencilOpsWithoutStencilAspect {
+                    attachment: atch_num,
+                });
+            }
+        }
+
+        // An attachment used both as an input attachment and as a color or depth-stencil
+        // attachment of the same subpass must be in the `General` layout, and reading it requires
+        // a by-region self-dependency.
+        for (pass_num, pass) in description.subpass_descs().enumerate() {
+            for &(input, input_layout) in pass.input_attachments.iter() {
+                let output_layout = pass
+                    .color_attachments
+                    .iter()
+                    .chain(pass.depth_stencil.iter())
+                    .find(|&&(a, _)| a == input)
+                    .map(|&(_, layout)| layout);
+
+                match output_layout {
+                    Some(ImageLayout::General) if input_layout == ImageLayout::General => (),
+                    Some(_) => {
+                        return Err(
+                            RenderPassCreationError::FeedbackAttachmentLayoutNotGeneral {
+                                subpass: pass_num,
+                                attachment: input,
+                            },
+                        );
+                    }
+                    None => (),
+                }
+            }
+        }
+
+        for dependency in description.dependency_descs() {
+            if dependency.is_self_dependency() && !dependency.by_region {
+                return Err(RenderPassCreationError::SelfDependencyNotByRegion {
+                    subpass: dependency.source_subpass,
+                });
+            }
+        }
+
         let attachments = description
             .attachment_descs()
             .map(|attachment| {
@@ -542,6 +622,31 @@ pub enum RenderPassCreationError {
     OomError(OomError),
     /// The maximum number of color attachments has been exceeded.
     ColorAttachmentsLimitExceeded,
+    /// An attachment is used both as an input attachment and as a color or depth-stencil
+    /// attachment of a subpass, but the `General` layout isn't used in both references.
+    FeedbackAttachmentLayoutNotGeneral {
+        /// Index of the subpass.
+        subpass: usize,
+        /// Index of the attachment.
+        attachment: usize,
+    },
+    /// A subpass has a dependency on itself that isn't by region.
+    SelfDependencyNotByRegion {
+        /// Index of the subpass.
+        subpass: usize,
+    },
+    /// An attachment whose format doesn't have a stencil component has stencil operations that
+    /// are different from its other operations.
+    StencilOpsWithoutStencilAspect {
+        /// Index of the attachment.
+        attachment: usize,
+    },
+    /// The number of samples of an attachment isn't supported by the device for attachments of
+    /// its format. See the `framebuffer_*_sample_counts` limits.
+    SampleCountNotSupported {
+        /// Index of the attachment.
+        attachment: usize,
+    },
 }
 
 impl error::Error for RenderPassCreationError {
@@ -565,6 +670,20 @@ impl fmt::Display for RenderPassCreationError {
                 RenderPassCreationError::ColorAttachmentsLimitExceeded => {
                     "the maximum number of color attachments has been exceeded"
                 }
+                RenderPassCreationError::FeedbackAttachmentLayoutNotGeneral { .. } => {
+                    "an attachment used both as an input attachment and as an output attachment \
+                     of a subpass must use the `General` layout"
+                }
+                RenderPassCreationError::SelfDependencyNotByRegion { .. } => {
+                    "a subpass has a dependency on itself that isn't by region"
+                }
+                RenderPassCreationError::StencilOpsWithoutStencilAspect { .. } => {
+                    "an attachment without a stencil component has stencil operations that are \
+                     different from its other operations"
+                }
+                RenderPassCreationError::SampleCountNotSupported { .. } => {
+                    "the number of samples of an attachment isn't supported by the device"
+                }
             }
         )
     }
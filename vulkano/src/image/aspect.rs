@@ -0,0 +1,157 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use format::Format;
+use format::FormatTy;
+use vk;
+
+/// Describes which aspects of an image are accessed.
+///
+/// A color image only has the color aspect, while a depth-stencil image has both the depth and
+/// the stencil aspects. Some operations, such as sampling from a depth-stencil image, must only
+/// access one of the aspects of the image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ImageAspects {
+    pub color: bool,
+    pub depth: bool,
+    pub stencil: bool,
+}
+
+impl ImageAspects {
+    /// Builds an `ImageAspects` with all values set to false.
+    #[inline]
+    pub fn none() -> ImageAspects {
+        ImageAspects {
+            color: false,
+            depth: false,
+            stencil: false,
+        }
+    }
+
+    /// Builds an `ImageAspects` with only the depth aspect.
+    #[inline]
+    pub fn depth() -> ImageAspects {
+        ImageAspects {
+            depth: true,
+            ..ImageAspects::none()
+        }
+    }
+
+    /// Builds an `ImageAspects` with only the stencil aspect.
+    #[inline]
+    pub fn stencil() -> ImageAspects {
+        ImageAspects {
+            stencil: true,
+            ..ImageAspects::none()
+        }
+    }
+
+    /// Returns the aspects that an image of the given format has.
+    #[inline]
+    pub fn from_format(format: Format) -> ImageAspects {
+        match format.ty() {
+            FormatTy::Float | FormatTy::Uint | FormatTy::Sint | FormatTy::Compressed => {
+                ImageAspects {
+                    color: true,
+                    ..ImageAspects::none()
+                }
+            }
+            FormatTy::Depth => ImageAspects::depth(),
+            FormatTy::Stencil => ImageAspects::stencil(),
+            FormatTy::DepthStencil => ImageAspects {
+                depth: true,
+                stencil: true,
+                ..ImageAspects::none()
+            },
+        }
+    }
+
+    /// Returns true if all the aspects of `other` are also in `self`.
+    #[inline]
+    pub fn contains(&self, other: &ImageAspects) -> bool {
+        (self.color || !other.color)
+            && (self.depth || !other.depth)
+            && (self.stencil || !other.stencil)
+    }
+
+    /// Returns true if no aspect is set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.color && !self.depth && !self.stencil
+    }
+
+    // Returns true if the aspects are a non-empty subset of the aspects of `format`. If
+    // `single` is true, exactly one aspect must be set, as required by buffer-image copies.
+    #[inline]
+    pub(crate) fn is_valid_for_format(&self, format: Format, single: bool) -> bool {
+        let count = self.color as u32 + self.depth as u32 + self.stencil as u32;
+        format.aspects().contains(self) && count >= 1 && (!single || count == 1)
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::ImageAspectFlags {
+        let mut result = 0;
+        if self.color {
+            result |= vk::IMAGE_ASPECT_COLOR_BIT;
+        }
+        if self.depth {
+            result |= vk::IMAGE_ASPECT_DEPTH_BIT;
+        }
+        if self.stencil {
+            result |= vk::IMAGE_ASPECT_STENCIL_BIT;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use image::ImageAspects;
+
+    #[test]
+    fn from_format() {
+        let aspects = ImageAspects::from_format(Format::D24Unorm_S8Uint);
+        assert!(aspects.contains(&ImageAspects::depth()));
+        assert!(aspects.contains(&ImageAspects::stencil()));
+        assert!(!aspects.color);
+
+        let aspects = ImageAspects::from_format(Format::D32Sfloat);
+        assert!(!aspects.contains(&ImageAspects::stencil()));
+    }
+
+    #[test]
+    fn format_aspects() {
+        assert_eq!(
+            Format::R8G8B8A8Unorm.aspects(),
+            ImageAspects {
+                color: true,
+                ..ImageAspects::none()
+            }
+        );
+        assert!(!Format::D16Unorm.aspects().color);
+        assert_eq!(Format::S8Uint.aspects(), ImageAspects::stencil());
+    }
+
+    #[test]
+    fn valid_for_format() {
+        let color = ImageAspects {
+            color: true,
+            ..ImageAspects::none()
+        };
+        assert!(color.is_valid_for_format(Format::R8G8B8A8Unorm, true));
+        assert!(!color.is_valid_for_format(Format::D32Sfloat, false));
+        assert!(!ImageAspects::none().is_valid_for_format(Format::R8G8B8A8Unorm, false));
+
+        let depth_stencil = Format::D24Unorm_S8Uint.aspects();
+        assert!(depth_stencil.is_valid_for_format(Format::D24Unorm_S8Uint, false));
+        assert!(!depth_stencil.is_valid_for_format(Format::D24Unorm_S8Uint, true));
+        assert!(ImageAspects::stencil().is_valid_for_format(Format::D24Unorm_S8Uint, true));
+    }
+}
@@ -486,6 +486,15 @@ impl<F, A> AttachmentImage<F, A> {
     }
 }
 
+impl<F, A> AttachmentImage<F, PotentialDedicatedAllocation<A>> {
+    /// Returns true if the memory of the image is a dedicated allocation, and false if it is a
+    /// suballocation of the memory pool.
+    #[inline]
+    pub fn is_dedicated_allocation(&self) -> bool {
+        self.memory.is_dedicated()
+    }
+}
+
 unsafe impl<F, A> ImageAccess for AttachmentImage<F, A>
 where
     F: 'static + Send + Sync,
@@ -0,0 +1,256 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use device::Device;
+use format::ClearValue;
+use format::Format;
+use format::FormatDesc;
+use image::sys::UnsafeImage;
+use image::sys::UnsafeImageView;
+use image::traits::ImageAccess;
+use image::traits::ImageClearValue;
+use image::traits::ImageViewAccess;
+use image::Dimensions;
+use image::ImageAspects;
+use image::ImageInner;
+use image::ImageLayout;
+use image::ImageUsage;
+use sync::AccessError;
+use vk;
+
+/// Image and image view whose handles were created outside of vulkano.
+///
+/// This is useful when integrating with other libraries that use Vulkan, for example a UI
+/// library that renders into its own images. The handles are not destroyed when the
+/// `ExternalImage` is dropped.
+///
+/// The image must always be in `layout` when vulkano accesses it, and vulkano leaves it in that
+/// layout after each use. Vulkano doesn't know how the image is used by the code that created
+/// it. Only the accesses that are made through vulkano are synchronized, and it is your
+/// responsibility to synchronize them with the other users of the image.
+#[derive(Debug)]
+pub struct ExternalImage {
+    // Inner implementation. Doesn't destroy the handle when dropped.
+    image: UnsafeImage,
+
+    // View of the whole image. Doesn't destroy the handle when dropped.
+    view: UnsafeImageView,
+
+    // Dimensions of the image view.
+    dimensions: Dimensions,
+
+    // Layout the image is always in.
+    layout: ImageLayout,
+
+    // Number of times this image is locked on the GPU side.
+    gpu_lock: AtomicUsize,
+}
+
+impl ExternalImage {
+    /// Wraps around an existing image and a view of it.
+    ///
+    /// # Safety
+    ///
+    /// - `image` must be a valid image created from `device` and bound to memory, with a single
+    ///   sample and the exclusive sharing mode.
+    /// - `format`, `dimensions`, `mipmaps` and `usage` must match the parameters the image was
+    ///   created with.
+    /// - `view` must be a valid view of all the mipmaps and array layers of `image`, of the type
+    ///   that corresponds to `dimensions`, with the same format and with identity swizzling.
+    /// - The image must be in `layout` when it is first used by vulkano.
+    /// - The image and the view must stay alive for as long as the returned object is alive.
+    ///
+    pub unsafe fn from_raw(
+        device: Arc<Device>,
+        image: vk::Image,
+        view: vk::ImageView,
+        format: Format,
+        dimensions: Dimensions,
+        mipmaps: u32,
+        usage: ImageUsage,
+        layout: ImageLayout,
+    ) -> Arc<ExternalImage> {
+        let image = UnsafeImage::from_raw(
+            device,
+            image,
+            usage.to_usage_bits(),
+            format,
+            dimensions.to_image_dimensions(),
+            1,
+            mipmaps,
+        );
+        let view = UnsafeImageView::from_raw(&image, view, ImageAspects::from_format(format));
+
+        Arc::new(ExternalImage {
+            image,
+            view,
+            dimensions,
+            layout,
+            gpu_lock: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the dimensions of the image.
+    #[inline]
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// Returns the layout the image is always in.
+    #[inline]
+    pub fn layout(&self) -> ImageLayout {
+        self.layout
+    }
+}
+
+unsafe impl ImageAccess for ExternalImage {
+    #[inline]
+    fn inner(&self) -> ImageInner<'_> {
+        ImageInner {
+            image: &self.image,
+            first_layer: 0,
+            num_layers: self.image.dimensions().array_layers() as usize,
+            first_mipmap_level: 0,
+            num_mipmap_levels: self.image.mipmap_levels() as usize,
+        }
+    }
+
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn conflicts_buffer(&self, other: &dyn BufferAccess) -> bool {
+        false
+    }
+
+    #[inline]
+    fn conflicts_image(&self, other: &dyn ImageAccess) -> bool {
+        self.conflict_key() == other.conflict_key()
+    }
+
+    #[inline]
+    fn conflict_key(&self) -> u64 {
+        self.image.key()
+    }
+
+    #[inline]
+    fn try_gpu_lock(&self, _: bool, expected_layout: ImageLayout) -> Result<(), AccessError> {
+        if expected_layout != self.layout && expected_layout != ImageLayout::Undefined {
+            return Err(AccessError::UnexpectedImageLayout {
+                requested: expected_layout,
+                allowed: self.layout,
+            });
+        }
+
+        match self
+            .gpu_lock
+            .compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(_) => Ok(()),
+            Err(_) => Err(AccessError::AlreadyInUse),
+        }
+    }
+
+    #[inline]
+    unsafe fn increase_gpu_lock(&self) {
+        let val = self.gpu_lock.fetch_add(1, Ordering::SeqCst);
+        debug_assert!(val >= 1);
+    }
+
+    #[inline]
+    unsafe fn unlock(&self, new_layout: Option<ImageLayout>) {
+        debug_assert!(new_layout.is_none() || new_layout == Some(self.layout));
+        let val = self.gpu_lock.fetch_sub(1, Ordering::SeqCst);
+        debug_assert!(val >= 1);
+    }
+
+    #[inline]
+    fn is_layout_initialized(&self) -> bool {
+        true
+    }
+}
+
+unsafe impl ImageClearValue<<Format as FormatDesc>::ClearValue> for ExternalImage {
+    #[inline]
+    fn decode(&self, value: <Format as FormatDesc>::ClearValue) -> Option<ClearValue> {
+        Some(self.image.format().decode_clear_value(value))
+    }
+}
+
+unsafe impl ImageViewAccess for ExternalImage {
+    #[inline]
+    fn parent(&self) -> &dyn ImageAccess {
+        self
+    }
+
+    #[inline]
+    fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    #[inline]
+    fn inner(&self) -> &UnsafeImageView {
+        &self.view
+    }
+
+    #[inline]
+    fn descriptor_set_storage_image_layout(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn descriptor_set_combined_image_sampler_layout(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn descriptor_set_sampled_image_layout(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn descriptor_set_input_attachment_layout(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn identity_swizzle(&self) -> bool {
+        true
+    }
+}
+
+impl PartialEq for ExternalImage {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ImageAccess::inner(self) == ImageAccess::inner(other)
+    }
+}
+
+impl Eq for ExternalImage {}
+
+impl Hash for ExternalImage {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ImageAccess::inner(self).hash(state);
+    }
+}
@@ -10,6 +10,7 @@
 use smallvec::SmallVec;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -20,6 +21,7 @@ use buffer::CpuAccessibleBuffer;
 use buffer::TypedBufferAccess;
 use command_buffer::AutoCommandBuffer;
 use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::BufferImageCopyLayout;
 use command_buffer::CommandBuffer;
 use command_buffer::CommandBufferExecFuture;
 use device::Device;
@@ -218,6 +220,9 @@ impl<F> ImmutableImage<F> {
 
     /// Construct an ImmutableImage from the contents of `iter`.
     ///
+    /// The texels are copied to a staging buffer whose rows are padded according to
+    /// `BufferImageCopyLayout::optimal`, so that the upload is as fast as possible.
+    ///
     /// TODO: Support mipmaps
     #[inline]
     pub fn from_iter<P, I>(
@@ -238,13 +243,45 @@ impl<F> ImmutableImage<F> {
         I: ExactSizeIterator<Item = P>,
         Format: AcceptsPixels<P>,
     {
-        let source = CpuAccessibleBuffer::from_iter(
-            queue.device().clone(),
-            BufferUsage::transfer_source(),
-            false,
-            iter,
-        )?;
-        ImmutableImage::from_buffer(source, dimensions, format, queue)
+        // The staging buffer isn't a slice, so it starts at the start of its `UnsafeBuffer`.
+        let layout = BufferImageCopyLayout::optimal::<P>(
+            queue.device(),
+            format.format(),
+            dimensions.width(),
+            0,
+        );
+        debug_assert_eq!(layout.buffer_offset, 0);
+
+        let source = if layout.row_length == 0 {
+            CpuAccessibleBuffer::from_iter(
+                queue.device().clone(),
+                BufferUsage::transfer_source(),
+                false,
+                iter,
+            )?
+        } else {
+            // Pad each row with copies of its last texel up to the row length of the layout.
+            let rate = format.rate() as usize;
+            let (block_width, _) = format.format().block_dimensions();
+            let row_len = ((dimensions.width() + block_width - 1) / block_width) as usize * rate;
+            let padded_row_len = (layout.row_length / block_width) as usize * rate;
+
+            let pixels: Vec<P> = iter.collect();
+            let mut padded = Vec::with_capacity(pixels.len() / row_len * padded_row_len);
+            for row in pixels.chunks(row_len) {
+                padded.extend_from_slice(row);
+                let padding = row[row.len() - 1].clone();
+                padded.extend(iter::repeat(padding).take(padded_row_len - row.len()));
+            }
+
+            CpuAccessibleBuffer::from_iter(
+                queue.device().clone(),
+                BufferUsage::transfer_source(),
+                false,
+                padded.into_iter(),
+            )?
+        };
+        ImmutableImage::from_buffer_with_layout(source, layout, dimensions, format, queue)
     }
 
     /// Construct an ImmutableImage containing a copy of the data in `source`.
@@ -262,6 +299,35 @@ impl<F> ImmutableImage<F> {
         ),
         ImageCreationError,
     >
+    where
+        B: BufferAccess + TypedBufferAccess<Content = [P]> + 'static + Clone + Send + Sync,
+        P: Send + Sync + Clone + 'static,
+        F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
+        Format: AcceptsPixels<P>,
+    {
+        ImmutableImage::from_buffer_with_layout(
+            source,
+            BufferImageCopyLayout::packed(),
+            dimensions,
+            format,
+            queue,
+        )
+    }
+
+    // Same as `from_buffer`, but the texels in `source` follow `layout`.
+    fn from_buffer_with_layout<B, P>(
+        source: B,
+        copy_layout: BufferImageCopyLayout,
+        dimensions: Dimensions,
+        format: F,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, AutoCommandBuffer>,
+        ),
+        ImageCreationError,
+    >
     where
         B: BufferAccess + TypedBufferAccess<Content = [P]> + 'static + Clone + Send + Sync,
         P: Send + Sync + Clone + 'static,
@@ -286,9 +352,10 @@ impl<F> ImmutableImage<F> {
         )?;
 
         let mut cbb = AutoCommandBufferBuilder::new(source.device().clone(), queue.family())?;
-        cbb.copy_buffer_to_image_dimensions(
+        cbb.copy_buffer_to_image_with_layout(
             source,
             init,
+            copy_layout,
             [0, 0, 0],
             dimensions.width_height_depth(),
             0,
@@ -321,6 +388,15 @@ impl<F, A> ImmutableImage<F, A> {
     }
 }
 
+impl<F, A> ImmutableImage<F, PotentialDedicatedAllocation<A>> {
+    /// Returns true if the memory of the image is a dedicated allocation, and false if it is a
+    /// suballocation of the memory pool.
+    #[inline]
+    pub fn is_dedicated_allocation(&self) -> bool {
+        self.memory.is_dedicated()
+    }
+}
+
 unsafe impl<F, A> ImageAccess for ImmutableImage<F, A>
 where
     F: 'static + Send + Sync,
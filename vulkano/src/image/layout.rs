@@ -39,4 +39,16 @@ pub enum ImageLayout {
     TransferDstOptimal = vk::IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL,
     Preinitialized = vk::IMAGE_LAYOUT_PREINITIALIZED,
     PresentSrc = vk::IMAGE_LAYOUT_PRESENT_SRC_KHR,
+    /// Layout of the depth aspect of a depth-stencil image used as attachment. Requires the
+    /// `separate_depth_stencil_layouts` feature.
+    DepthAttachmentOptimal = vk::IMAGE_LAYOUT_DEPTH_ATTACHMENT_OPTIMAL,
+    /// Layout of the depth aspect of a depth-stencil image that is only read. Requires the
+    /// `separate_depth_stencil_layouts` feature.
+    DepthReadOnlyOptimal = vk::IMAGE_LAYOUT_DEPTH_READ_ONLY_OPTIMAL,
+    /// Layout of the stencil aspect of a depth-stencil image used as attachment. Requires the
+    /// `separate_depth_stencil_layouts` feature.
+    StencilAttachmentOptimal = vk::IMAGE_LAYOUT_STENCIL_ATTACHMENT_OPTIMAL,
+    /// Layout of the stencil aspect of a depth-stencil image that is only read. Requires the
+    /// `separate_depth_stencil_layouts` feature.
+    StencilReadOnlyOptimal = vk::IMAGE_LAYOUT_STENCIL_READ_ONLY_OPTIMAL,
 }
@@ -0,0 +1,323 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::ops::Range;
+
+use image::sys::UnsafeImageView;
+use image::Dimensions;
+use image::ImageAccess;
+use image::ImageLayout;
+use image::ImageViewAccess;
+use sampler::Sampler;
+use OomError;
+
+/// View of an image that only covers some of its mipmap levels.
+///
+/// Binding a `MipRangeView` as a sampled image or a combined image sampler restricts the
+/// accessible mipmap levels of the image, whatever the LOD computed by the sampler. The level 0
+/// of the view is the first level of the range.
+///
+/// This is typically used by texture streaming systems, where only the smallest mipmap levels of
+/// a texture are uploaded at first. Views are immutable, therefore when the resident levels
+/// change, create a new view and a new descriptor set that uses it.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::image::MipRangeView;
+/// # use std::sync::Arc;
+/// # use vulkano::image::ImmutableImage;
+/// # use vulkano::format::R8G8B8A8Unorm;
+/// # let image: Arc<ImmutableImage<R8G8B8A8Unorm>> = return;
+///
+/// // Only the levels 3 and above have been uploaded.
+/// let resident = 3..image.mipmap_levels();
+/// let view = MipRangeView::new(image.clone(), resident.clone()).unwrap();
+/// assert!(view.is_within_resident(&resident));
+/// ```
+pub struct MipRangeView<I> {
+    image: I,
+    view: UnsafeImageView,
+    mipmap_levels: Range<u32>,
+}
+
+impl<I> MipRangeView<I>
+where
+    I: ImageViewAccess,
+{
+    /// Builds a view of `image` that covers the mipmap levels `mipmap_levels` and all the array
+    /// layers of `image`.
+    ///
+    /// Mipmap levels are relative to the first level of `image`. Returns an error if the range
+    /// is empty or out of range of the image.
+    pub fn new(
+        image: I,
+        mipmap_levels: Range<u32>,
+    ) -> Result<MipRangeView<I>, MipRangeViewCreationError> {
+        let view = {
+            let inner = image.parent().inner();
+
+            if mipmap_levels.start >= mipmap_levels.end {
+                return Err(MipRangeViewCreationError::EmptyRange);
+            }
+
+            if mipmap_levels.end as usize > inner.num_mipmap_levels {
+                return Err(MipRangeViewCreationError::OutOfRange {
+                    end: mipmap_levels.end,
+                    available: inner.num_mipmap_levels as u32,
+                });
+            }
+
+            let first_level = inner.first_mipmap_level as u32;
+            let first_layer = inner.first_layer as u32;
+
+            unsafe {
+                UnsafeImageView::raw(
+                    inner.image,
+                    image.dimensions().to_view_type(),
+                    first_level + mipmap_levels.start..first_level + mipmap_levels.end,
+                    first_layer..first_layer + inner.num_layers as u32,
+                )?
+            }
+        };
+
+        Ok(MipRangeView {
+            image,
+            view,
+            mipmap_levels,
+        })
+    }
+
+    /// Returns the image this view was created from.
+    #[inline]
+    pub fn image(&self) -> &I {
+        &self.image
+    }
+
+    /// Returns the mipmap levels covered by the view.
+    #[inline]
+    pub fn mipmap_levels(&self) -> Range<u32> {
+        self.mipmap_levels.clone()
+    }
+
+    /// Returns true if all the mipmap levels covered by the view are within `resident`.
+    ///
+    /// Streaming systems should check this before binding the view, since sampling a level that
+    /// hasn't been uploaded yet returns undefined values.
+    #[inline]
+    pub fn is_within_resident(&self, resident: &Range<u32>) -> bool {
+        self.mipmap_levels.start >= resident.start && self.mipmap_levels.end <= resident.end
+    }
+}
+
+unsafe impl<I> ImageViewAccess for MipRangeView<I>
+where
+    I: ImageViewAccess,
+{
+    #[inline]
+    fn parent(&self) -> &dyn ImageAccess {
+        self.image.parent()
+    }
+
+    #[inline]
+    fn dimensions(&self) -> Dimensions {
+        let level = self.mipmap_levels.start;
+        let shrink = |value: u32| cmp::max(1, value >> level);
+
+        match self.image.dimensions() {
+            Dimensions::Dim1d { width } => Dimensions::Dim1d {
+                width: shrink(width),
+            },
+            Dimensions::Dim1dArray {
+                width,
+                array_layers,
+            } => Dimensions::Dim1dArray {
+                width: shrink(width),
+                array_layers,
+            },
+            Dimensions::Dim2d { width, height } => Dimensions::Dim2d {
+                width: shrink(width),
+                height: shrink(height),
+            },
+            Dimensions::Dim2dArray {
+                width,
+                height,
+                array_layers,
+            } => Dimensions::Dim2dArray {
+                width: shrink(width),
+                height: shrink(height),
+                array_layers,
+            },
+            Dimensions::Dim3d {
+                width,
+                height,
+                depth,
+            } => Dimensions::Dim3d {
+                width: shrink(width),
+                height: shrink(height),
+                depth: shrink(depth),
+            },
+            Dimensions::Cubemap { size } => Dimensions::Cubemap { size: shrink(size) },
+            Dimensions::CubemapArray { size, array_layers } => Dimensions::CubemapArray {
+                size: shrink(size),
+                array_layers,
+            },
+        }
+    }
+
+    #[inline]
+    fn inner(&self) -> &UnsafeImageView {
+        &self.view
+    }
+
+    #[inline]
+    fn descriptor_set_storage_image_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_storage_image_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_combined_image_sampler_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_combined_image_sampler_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_sampled_image_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_sampled_image_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_input_attachment_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_input_attachment_layout()
+    }
+
+    #[inline]
+    fn identity_swizzle(&self) -> bool {
+        self.image.identity_swizzle()
+    }
+
+    #[inline]
+    fn can_be_sampled(&self, sampler: &Sampler) -> bool {
+        self.image.can_be_sampled(sampler)
+    }
+}
+
+impl<I> fmt::Debug for MipRangeView<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("MipRangeView")
+            .field("view", &self.view)
+            .field("mipmap_levels", &self.mipmap_levels)
+            .finish()
+    }
+}
+
+/// Error that can happen when creating a `MipRangeView`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MipRangeViewCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The range of mipmap levels is empty.
+    EmptyRange,
+    /// The range of mipmap levels goes beyond the levels of the image.
+    OutOfRange {
+        /// End of the requested range.
+        end: u32,
+        /// Number of mipmap levels of the image.
+        available: u32,
+    },
+}
+
+impl error::Error for MipRangeViewCreationError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            MipRangeViewCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MipRangeViewCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                MipRangeViewCreationError::OomError(_) => "not enough memory available",
+                MipRangeViewCreationError::EmptyRange => "the range of mipmap levels is empty",
+                MipRangeViewCreationError::OutOfRange { .. } => {
+                    "the range of mipmap levels goes beyond the levels of the image"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for MipRangeViewCreationError {
+    #[inline]
+    fn from(err: OomError) -> MipRangeViewCreationError {
+        MipRangeViewCreationError::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::R8G8B8A8Unorm;
+    use image::Dimensions;
+    use image::ImageLayout;
+    use image::ImageUsage;
+    use image::ImageViewAccess;
+    use image::ImmutableImage;
+    use image::MipRangeView;
+    use image::MipRangeViewCreationError;
+    use image::MipmapsCount;
+
+    #[test]
+    fn mip_range() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (image, _) = ImmutableImage::uninitialized(
+            device,
+            Dimensions::Dim2d {
+                width: 64,
+                height: 64,
+            },
+            R8G8B8A8Unorm,
+            MipmapsCount::Specific(4),
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageLayout::ShaderReadOnlyOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let view = MipRangeView::new(image.clone(), 2..4).unwrap();
+        assert_eq!(view.dimensions().width(), 16);
+        assert!(view.is_within_resident(&(1..4)));
+        assert!(!view.is_within_resident(&(3..4)));
+
+        match MipRangeView::new(image.clone(), 2..2) {
+            Err(MipRangeViewCreationError::EmptyRange) => (),
+            _ => panic!(),
+        }
+
+        match MipRangeView::new(image, 2..5) {
+            Err(MipRangeViewCreationError::OutOfRange {
+                end: 5,
+                available: 4,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -48,26 +48,69 @@
 
 use std::cmp;
 use std::convert::TryFrom;
+use std::ops::Range;
 
+use vk;
+
+pub use self::aspect::ImageAspects;
 pub use self::attachment::AttachmentImage;
+pub use self::external::ExternalImage;
 pub use self::immutable::ImmutableImage;
 pub use self::layout::ImageLayout;
+pub use self::mip_range::MipRangeView;
+pub use self::mip_range::MipRangeViewCreationError;
+pub use self::sparse::SparseImage;
 pub use self::storage::StorageImage;
+pub use self::swapchain::SampledSwapchainImage;
+pub use self::swapchain::SampledSwapchainImageError;
 pub use self::swapchain::SwapchainImage;
 pub use self::sys::ImageCreationError;
+pub use self::sys::ImageViewCreationError;
 pub use self::traits::ImageAccess;
 pub use self::traits::ImageInner;
 pub use self::traits::ImageViewAccess;
 pub use self::usage::ImageUsage;
+pub use self::usage_view::UsageView;
 
+mod aspect;
 pub mod attachment; // TODO: make private
+mod external;
 pub mod immutable; // TODO: make private
 mod layout;
+mod mip_range;
+mod sparse;
 mod storage;
 pub mod swapchain; // TODO: make private
 pub mod sys;
 pub mod traits;
 mod usage;
+mod usage_view;
+
+/// Value to use as the end of a range of mipmap levels to designate all the mipmap levels of
+/// the image starting from the start of the range.
+///
+/// This is useful when the number of mipmap levels of the image isn't known, for example with a
+/// type-erased image. For instance `0..REMAINING_MIP_LEVELS` designates all the mipmap levels.
+pub const REMAINING_MIP_LEVELS: u32 = vk::REMAINING_MIP_LEVELS;
+
+/// Value to use as the end of a range of array layers to designate all the array layers of the
+/// image starting from the start of the range.
+///
+/// See also `REMAINING_MIP_LEVELS`.
+pub const REMAINING_ARRAY_LAYERS: u32 = vk::REMAINING_ARRAY_LAYERS;
+
+/// Replaces the `REMAINING_MIP_LEVELS` or `REMAINING_ARRAY_LAYERS` sentinel at the end of
+/// `range` with `count`, the actual number of mipmap levels or array layers of the image.
+#[inline]
+pub(crate) fn resolve_remaining(range: Range<u32>, count: u32) -> Range<u32> {
+    debug_assert_eq!(REMAINING_MIP_LEVELS, REMAINING_ARRAY_LAYERS);
+
+    if range.end == REMAINING_MIP_LEVELS {
+        range.start..count
+    } else {
+        range
+    }
+}
 
 /// Specifies how many mipmaps must be allocated.
 ///
@@ -403,9 +446,12 @@ pub struct ImageCreateFlags {
     pub mutable_format: bool,
     pub cube_compatible: bool,
     pub array_2d_compatible: bool,
+    pub protected: bool,
 }
 
 impl ImageCreateFlags {
+    /// Returns all the flags except `protected`, which requires protected memory and can't be
+    /// combined with the sparse flags.
     pub fn all() -> Self {
         Self {
             sparse_binding: true,
@@ -414,6 +460,7 @@ impl ImageCreateFlags {
             mutable_format: true,
             cube_compatible: true,
             array_2d_compatible: true,
+            protected: false,
         }
     }
 
@@ -443,6 +490,9 @@ impl From<ImageCreateFlags> for vk::ImageCreateFlags {
         if flags.array_2d_compatible {
             vk_flags |= vk::IMAGE_CREATE_2D_ARRAY_COMPATIBLE_BIT_KHR
         };
+        if flags.protected {
+            vk_flags |= vk::IMAGE_CREATE_PROTECTED_BIT
+        };
         vk_flags
     }
 }
@@ -684,7 +734,17 @@ impl ImageDimensions {
 
 #[cfg(test)]
 mod tests {
+    use image::resolve_remaining;
     use image::ImageDimensions;
+    use image::REMAINING_ARRAY_LAYERS;
+    use image::REMAINING_MIP_LEVELS;
+
+    #[test]
+    fn remaining_sentinels() {
+        assert_eq!(resolve_remaining(0..REMAINING_MIP_LEVELS, 10), 0..10);
+        assert_eq!(resolve_remaining(3..REMAINING_ARRAY_LAYERS, 6), 3..6);
+        assert_eq!(resolve_remaining(1..2, 6), 1..2);
+    }
 
     #[test]
     fn max_mipmaps() {
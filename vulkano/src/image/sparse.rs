@@ -0,0 +1,414 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use device::Device;
+use format::ClearValue;
+use format::FormatDesc;
+use image::sys::ImageCreationError;
+use image::sys::SparseImageMemoryRequirements;
+use image::sys::UnsafeImage;
+use image::sys::UnsafeImageView;
+use image::traits::ImageAccess;
+use image::traits::ImageClearValue;
+use image::traits::ImageContent;
+use image::traits::ImageViewAccess;
+use image::Dimensions;
+use image::ImageInner;
+use image::ImageLayout;
+use image::ImageUsage;
+use image::MipmapsCount;
+use instance::QueueFamily;
+use memory::MemoryRequirements;
+use sync::AccessError;
+use sync::Sharing;
+
+/// Image whose memory is bound in blocks, which can be bound and unbound individually while the
+/// image is in use. This is also known as a partially resident texture.
+///
+/// Creating a `SparseImage` doesn't allocate any memory. Instead, you allocate memory yourself
+/// and bind it to blocks of the image with a `SubmitBindSparseImageBindBuilder`, which is
+/// submitted to a queue that supports sparse binding. Reading a block that isn't bound to memory
+/// returns an undefined value, or zero if the implementation has the
+/// `residencyNonResidentStrict` sparse property.
+///
+/// `sparse_memory_requirements` describes the size of the blocks and how the mip tail must be
+/// bound. The mip tail is made of the smallest mipmap levels, which are smaller than a block.
+/// It can't be bound block by block, and must be bound as a whole with a
+/// `SubmitBindSparseImageOpaqueBindBuilder` before these mipmap levels are accessed. The memory
+/// bound to the blocks and to the mip tail must use one of the memory types of
+/// `memory_requirements`, and be aligned to its `alignment`, which is also the size in bytes of
+/// a block.
+///
+/// The `sparse_binding` feature must be enabled, as well as `sparse_residency_image2d` or
+/// `sparse_residency_image3d` depending on the dimensions. One-dimensional images can't be
+/// sparse.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::command_buffer::submit::SubmitBindSparseBatchBuilder;
+/// use vulkano::command_buffer::submit::SubmitBindSparseBuilder;
+/// use vulkano::command_buffer::submit::SubmitBindSparseImageBindBuilder;
+/// use vulkano::image::ImageAccess;
+/// use vulkano::image::SparseImage;
+/// # use std::sync::Arc;
+/// # use vulkano::device::Queue;
+/// # use vulkano::memory::DeviceMemory;
+/// # let image: Arc<SparseImage<vulkano::format::Format>> = return;
+/// # let queue: Arc<Queue> = return;
+/// # let memory: DeviceMemory = return;
+///
+/// // Binds `memory` to the first block of the first mipmap level.
+/// let reqs = image.sparse_memory_requirements()[0];
+/// let [width, height, depth] = reqs.image_granularity;
+///
+/// let mut bind = unsafe { SubmitBindSparseImageBindBuilder::new(image.inner().image) };
+/// unsafe {
+///     bind.add_bind(reqs.aspects, 0, 0, [0, 0, 0], [width, height, depth], &memory, 0);
+/// }
+///
+/// let mut batch = SubmitBindSparseBatchBuilder::new();
+/// batch.add_image(bind);
+/// let mut builder = SubmitBindSparseBuilder::new();
+/// builder.add(batch);
+/// builder.submit(&queue).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SparseImage<F> {
+    // Inner implementation.
+    image: UnsafeImage,
+
+    // We maintain a view of the whole image.
+    view: UnsafeImageView,
+
+    // Dimensions of the image view.
+    dimensions: Dimensions,
+
+    // Format.
+    format: F,
+
+    // Requirements of the memory bound to the blocks of the image.
+    memory_requirements: MemoryRequirements,
+
+    // How the memory of the image must be bound.
+    sparse_requirements: Vec<SparseImageMemoryRequirements>,
+
+    // Queue families allowed to access this image.
+    queue_families: SmallVec<[u32; 4]>,
+
+    // Number of times this image is locked on the GPU side.
+    gpu_lock: AtomicUsize,
+}
+
+impl<F> SparseImage<F> {
+    /// Creates a new sparse image with the given dimensions, format and usage. No memory is
+    /// bound to the image.
+    pub fn new<'a, Mi, I>(
+        device: Arc<Device>,
+        dimensions: Dimensions,
+        format: F,
+        usage: ImageUsage,
+        mipmaps: Mi,
+        queue_families: I,
+    ) -> Result<Arc<SparseImage<F>>, ImageCreationError>
+    where
+        F: FormatDesc,
+        Mi: Into<MipmapsCount>,
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        let queue_families = queue_families
+            .into_iter()
+            .map(|f| f.id())
+            .collect::<SmallVec<[u32; 4]>>();
+
+        let (image, memory_requirements) = unsafe {
+            let sharing = if queue_families.len() >= 2 {
+                Sharing::Concurrent(queue_families.iter().cloned())
+            } else {
+                Sharing::Exclusive
+            };
+
+            UnsafeImage::new_sparse(
+                device.clone(),
+                usage,
+                format.format(),
+                dimensions.to_image_dimensions(),
+                1,
+                mipmaps,
+                sharing,
+            )?
+        };
+
+        let view = unsafe {
+            UnsafeImageView::raw(
+                &image,
+                dimensions.to_view_type(),
+                0..image.mipmap_levels(),
+                0..image.dimensions().array_layers(),
+            )?
+        };
+
+        let sparse_requirements = image.sparse_memory_requirements();
+
+        Ok(Arc::new(SparseImage {
+            image,
+            view,
+            dimensions,
+            format,
+            memory_requirements,
+            sparse_requirements,
+            queue_families,
+            gpu_lock: AtomicUsize::new(0),
+        }))
+    }
+
+    /// Returns the dimensions of the image.
+    #[inline]
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// Returns the requirements of the memory bound to the image. `size` is the size that the
+    /// memory would have if the whole image was bound, and `alignment` is the size of a block.
+    #[inline]
+    pub fn memory_requirements(&self) -> &MemoryRequirements {
+        &self.memory_requirements
+    }
+
+    /// Returns how the memory of the image must be bound, one entry for each group of aspects
+    /// whose memory is bound separately.
+    #[inline]
+    pub fn sparse_memory_requirements(&self) -> &[SparseImageMemoryRequirements] {
+        &self.sparse_requirements
+    }
+}
+
+unsafe impl<F> ImageAccess for SparseImage<F>
+where
+    F: 'static + Send + Sync,
+{
+    #[inline]
+    fn inner(&self) -> ImageInner<'_> {
+        ImageInner {
+            image: &self.image,
+            first_layer: 0,
+            num_layers: self.dimensions.array_layers() as usize,
+            first_mipmap_level: 0,
+            num_mipmap_levels: self.image.mipmap_levels() as usize,
+        }
+    }
+
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn conflicts_buffer(&self, other: &dyn BufferAccess) -> bool {
+        false
+    }
+
+    #[inline]
+    fn conflicts_image(&self, other: &dyn ImageAccess) -> bool {
+        self.conflict_key() == other.conflict_key()
+    }
+
+    #[inline]
+    fn conflict_key(&self) -> u64 {
+        self.image.key()
+    }
+
+    #[inline]
+    fn try_gpu_lock(&self, _: bool, expected_layout: ImageLayout) -> Result<(), AccessError> {
+        // TODO: handle initial layout transition
+        if expected_layout != ImageLayout::General && expected_layout != ImageLayout::Undefined {
+            return Err(AccessError::UnexpectedImageLayout {
+                requested: expected_layout,
+                allowed: ImageLayout::General,
+            });
+        }
+
+        match self
+            .gpu_lock
+            .compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(_) => Ok(()),
+            Err(_) => Err(AccessError::AlreadyInUse),
+        }
+    }
+
+    #[inline]
+    unsafe fn increase_gpu_lock(&self) {
+        let val = self.gpu_lock.fetch_add(1, Ordering::SeqCst);
+        debug_assert!(val >= 1);
+    }
+
+    #[inline]
+    unsafe fn unlock(&self, new_layout: Option<ImageLayout>) {
+        assert!(new_layout.is_none() || new_layout == Some(ImageLayout::General));
+        self.gpu_lock.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+unsafe impl<F> ImageClearValue<F::ClearValue> for SparseImage<F>
+where
+    F: FormatDesc + 'static + Send + Sync,
+{
+    #[inline]
+    fn decode(&self, value: F::ClearValue) -> Option<ClearValue> {
+        Some(self.format.decode_clear_value(value))
+    }
+}
+
+unsafe impl<P, F> ImageContent<P> for SparseImage<F>
+where
+    F: 'static + Send + Sync,
+{
+    #[inline]
+    fn matches_format(&self) -> bool {
+        true // FIXME:
+    }
+}
+
+unsafe impl<F> ImageViewAccess for SparseImage<F>
+where
+    F: 'static + Send + Sync,
+{
+    #[inline]
+    fn parent(&self) -> &dyn ImageAccess {
+        self
+    }
+
+    #[inline]
+    fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    #[inline]
+    fn inner(&self) -> &UnsafeImageView {
+        &self.view
+    }
+
+    #[inline]
+    fn descriptor_set_storage_image_layout(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn descriptor_set_combined_image_sampler_layout(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn descriptor_set_sampled_image_layout(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn descriptor_set_input_attachment_layout(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn identity_swizzle(&self) -> bool {
+        true
+    }
+}
+
+impl<F> PartialEq for SparseImage<F>
+where
+    F: 'static + Send + Sync,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ImageAccess::inner(self) == ImageAccess::inner(other)
+    }
+}
+
+impl<F> Eq for SparseImage<F> where F: 'static + Send + Sync {}
+
+impl<F> Hash for SparseImage<F>
+where
+    F: 'static + Send + Sync,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ImageAccess::inner(self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseImage;
+    use format::Format;
+    use image::sys::ImageCreationError;
+    use image::Dimensions;
+    use image::ImageUsage;
+    use image::MipmapsCount;
+
+    #[test]
+    fn sparse_binding_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let res = SparseImage::new(
+            device,
+            Dimensions::Dim2d {
+                width: 1024,
+                height: 1024,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            MipmapsCount::Log2,
+            Some(queue.family()),
+        );
+
+        match res {
+            Err(ImageCreationError::SparseBindingFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn one_dimensional() {
+        let (device, queue) = gfx_dev_and_queue!(sparse_binding);
+
+        let res = SparseImage::new(
+            device,
+            Dimensions::Dim1d { width: 1024 },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            MipmapsCount::One,
+            Some(queue.family()),
+        );
+
+        match res {
+            Err(ImageCreationError::UnsupportedDimensions { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -107,6 +107,7 @@ impl<F> StorageImage<F> {
     }
 
     /// Same as `new`, but allows specifying the usage.
+    #[inline]
     pub fn with_usage<'a, I>(
         device: Arc<Device>,
         dimensions: Dimensions,
@@ -118,6 +119,40 @@ impl<F> StorageImage<F> {
         F: FormatDesc,
         I: IntoIterator<Item = QueueFamily<'a>>,
     {
+        StorageImage::with_pool(
+            Device::standard_pool(&device),
+            dimensions,
+            format,
+            usage,
+            queue_families,
+        )
+    }
+}
+
+impl<F, A> StorageImage<F, A>
+where
+    A: MemoryPool,
+{
+    /// Same as `with_usage`, but allocates the memory of the image from `pool` instead of the
+    /// standard pool of the device.
+    ///
+    /// The image is bound to a suballocation of `pool` that uses the optimal tiling layout and
+    /// respects the alignment required by the image. If the implementation recommends a
+    /// dedicated allocation for this image, which is common for large images and attachments,
+    /// and if the `khr_dedicated_allocation` extension is enabled, then a dedicated allocation
+    /// is performed instead. Use `is_dedicated_allocation` to know which one was chosen.
+    pub fn with_pool<'a, I>(
+        pool: A,
+        dimensions: Dimensions,
+        format: F,
+        usage: ImageUsage,
+        queue_families: I,
+    ) -> Result<Arc<StorageImage<F, A>>, ImageCreationError>
+    where
+        F: FormatDesc,
+        I: IntoIterator<Item = QueueFamily<'a>>,
+    {
+        let device = pool.device().clone();
         let queue_families = queue_families
             .into_iter()
             .map(|f| f.id())
@@ -144,7 +179,7 @@ impl<F> StorageImage<F> {
         };
 
         let mem = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
+            &pool,
             &mem_reqs,
             AllocLayout::Optimal,
             MappingRequirement::DoNotMap,
@@ -181,12 +216,14 @@ impl<F> StorageImage<F> {
             gpu_lock: AtomicUsize::new(0),
         }))
     }
-}
 
-impl<F, A> StorageImage<F, A>
-where
-    A: MemoryPool,
-{
+    /// Returns true if the memory of the image is a dedicated allocation, and false if it is a
+    /// suballocation of the memory pool.
+    #[inline]
+    pub fn is_dedicated_allocation(&self) -> bool {
+        self.memory.is_dedicated()
+    }
+
     /// Returns the dimensions of the image.
     #[inline]
     pub fn dimensions(&self) -> Dimensions {
@@ -366,8 +403,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::StorageImage;
+    use device::Device;
     use format::Format;
     use image::Dimensions;
+    use image::ImageUsage;
 
     #[test]
     fn create() {
@@ -383,4 +422,28 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn with_pool() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let img = StorageImage::with_pool(
+            Device::standard_pool(&device),
+            Dimensions::Dim2d {
+                width: 32,
+                height: 32,
+            },
+            Format::R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                transfer_destination: true,
+                ..ImageUsage::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        if !device.loaded_extensions().khr_dedicated_allocation {
+            assert!(!img.is_dedicated_allocation());
+        }
+    }
 }
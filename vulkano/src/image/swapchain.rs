@@ -7,6 +7,8 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::error;
+use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::Arc;
@@ -234,3 +236,105 @@ impl<W> Hash for SwapchainImage<W> {
         ImageAccess::inner(self).hash(state);
     }
 }
+
+/// View of a swapchain image that can be bound as a sampled image or as a combined image sampler.
+///
+/// This is typically used by post-processing passes that sample the image of the previous frame.
+/// The swapchain must have been created with the `sampled` usage, which is checked when creating
+/// the view. Add the `transfer_source` usage as well if the image is also copied or blitted.
+///
+/// Like the `SwapchainImage` it was created from, the view can only be used after the image has
+/// been acquired. When the view is used by a descriptor set, the command buffer transitions the
+/// image from the `PresentSrc` layout to the `ShaderReadOnlyOptimal` layout, and back to
+/// `PresentSrc` at the end of the command buffer.
+pub struct SampledSwapchainImage<W> {
+    image: Arc<SwapchainImage<W>>,
+}
+
+impl<W> SampledSwapchainImage<W> {
+    /// Builds a sampled view of a swapchain image.
+    ///
+    /// Returns an error if the swapchain wasn't created with the `sampled` usage.
+    #[inline]
+    pub fn new(
+        image: Arc<SwapchainImage<W>>,
+    ) -> Result<SampledSwapchainImage<W>, SampledSwapchainImageError> {
+        if !image.swapchain().usage().sampled {
+            return Err(SampledSwapchainImageError::MissingSampledUsage);
+        }
+
+        Ok(SampledSwapchainImage { image })
+    }
+
+    /// Returns the swapchain image this view was created from.
+    #[inline]
+    pub fn image(&self) -> &Arc<SwapchainImage<W>> {
+        &self.image
+    }
+}
+
+unsafe impl<W> ImageViewAccess for SampledSwapchainImage<W> {
+    #[inline]
+    fn parent(&self) -> &dyn ImageAccess {
+        self.image.parent()
+    }
+
+    #[inline]
+    fn dimensions(&self) -> Dimensions {
+        ImageViewAccess::dimensions(&*self.image)
+    }
+
+    #[inline]
+    fn inner(&self) -> &UnsafeImageView {
+        ImageViewAccess::inner(&*self.image)
+    }
+
+    #[inline]
+    fn descriptor_set_storage_image_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_storage_image_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_combined_image_sampler_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_combined_image_sampler_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_sampled_image_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_sampled_image_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_input_attachment_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_input_attachment_layout()
+    }
+
+    #[inline]
+    fn identity_swizzle(&self) -> bool {
+        self.image.identity_swizzle()
+    }
+}
+
+/// Error that can happen when creating a `SampledSwapchainImage`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SampledSwapchainImageError {
+    /// The swapchain wasn't created with the `sampled` usage.
+    MissingSampledUsage,
+}
+
+impl error::Error for SampledSwapchainImageError {}
+
+impl fmt::Display for SampledSwapchainImageError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                SampledSwapchainImageError::MissingSampledUsage => {
+                    "the swapchain wasn't created with the `sampled` usage"
+                }
+            }
+        )
+    }
+}
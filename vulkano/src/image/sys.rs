@@ -27,10 +27,12 @@ use std::sync::Arc;
 use device::Device;
 use format::Format;
 use format::FormatTy;
+use image::ImageAspects;
 use image::ImageDimensions;
 use image::ImageUsage;
 use image::MipmapsCount;
 use image::ViewType;
+use instance::Version;
 use memory::DeviceMemory;
 use memory::DeviceMemoryAllocError;
 use memory::MemoryRequirements;
@@ -71,6 +73,8 @@ pub struct UnsafeImage {
     // `vkDestroyImage` is called only if `needs_destruction` is true.
     needs_destruction: bool,
     preinitialized_layout: bool,
+    protected: bool,
+    sparse: bool,
 }
 
 impl UnsafeImage {
@@ -113,6 +117,160 @@ impl UnsafeImage {
             sharing,
             linear_tiling,
             preinitialized_layout,
+            false,
+            false,
+        )
+    }
+
+    /// Same as `new`, but creates a protected image.
+    ///
+    /// A protected image must be bound to protected memory, and can only be accessed by
+    /// protected command buffers. The `protected_memory` feature must be enabled.
+    ///
+    /// # Panic
+    ///
+    /// Same as `new`.
+    ///
+    #[inline]
+    pub unsafe fn new_protected<'a, Mi, I>(
+        device: Arc<Device>,
+        usage: ImageUsage,
+        format: Format,
+        dimensions: ImageDimensions,
+        num_samples: u32,
+        mipmaps: Mi,
+        sharing: Sharing<I>,
+        linear_tiling: bool,
+        preinitialized_layout: bool,
+    ) -> Result<(UnsafeImage, MemoryRequirements), ImageCreationError>
+    where
+        Mi: Into<MipmapsCount>,
+        I: Iterator<Item = u32>,
+    {
+        if !device.enabled_features().protected_memory {
+            return Err(ImageCreationError::ProtectedMemoryFeatureNotEnabled);
+        }
+
+        let sharing = match sharing {
+            Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
+            Sharing::Concurrent(ids) => (vk::SHARING_MODE_CONCURRENT, ids.collect()),
+        };
+
+        UnsafeImage::new_impl(
+            device,
+            usage,
+            format,
+            dimensions,
+            num_samples,
+            mipmaps.into(),
+            sharing,
+            linear_tiling,
+            preinitialized_layout,
+            true,
+            false,
+        )
+    }
+
+    /// Same as `new`, but creates a sparse residency image.
+    ///
+    /// A sparse residency image isn't bound to memory as a whole. Instead, individual blocks of
+    /// the image are bound and unbound with `vkQueueBindSparse`, and the parts of the image that
+    /// aren't bound to memory can still be accessed. See `sparse_memory_requirements` for the
+    /// size of the blocks.
+    ///
+    /// The `sparse_binding` feature must be enabled, as well as `sparse_residency_image2d` or
+    /// `sparse_residency_image3d` depending on the dimensions, and the `sparse_residency*_samples`
+    /// feature that corresponds to the number of samples if it's greater than 1. One-dimensional
+    /// images can't be sparse, and sparse images always use the optimal tiling.
+    ///
+    /// # Panic
+    ///
+    /// Same as `new`.
+    ///
+    pub unsafe fn new_sparse<'a, Mi, I>(
+        device: Arc<Device>,
+        usage: ImageUsage,
+        format: Format,
+        dimensions: ImageDimensions,
+        num_samples: u32,
+        mipmaps: Mi,
+        sharing: Sharing<I>,
+    ) -> Result<(UnsafeImage, MemoryRequirements), ImageCreationError>
+    where
+        Mi: Into<MipmapsCount>,
+        I: Iterator<Item = u32>,
+    {
+        let features = device.enabled_features();
+        if !features.sparse_binding {
+            return Err(ImageCreationError::SparseBindingFeatureNotEnabled);
+        }
+
+        let ty = match dimensions {
+            ImageDimensions::Dim1d { .. } => {
+                return Err(ImageCreationError::UnsupportedDimensions { dimensions });
+            }
+            ImageDimensions::Dim2d { .. } => {
+                if !features.sparse_residency_image2d {
+                    return Err(ImageCreationError::SparseResidencyImage2dFeatureNotEnabled);
+                }
+                vk::IMAGE_TYPE_2D
+            }
+            ImageDimensions::Dim3d { .. } => {
+                if !features.sparse_residency_image3d {
+                    return Err(ImageCreationError::SparseResidencyImage3dFeatureNotEnabled);
+                }
+                vk::IMAGE_TYPE_3D
+            }
+        };
+
+        let samples_feature = match num_samples {
+            2 => features.sparse_residency2_samples,
+            4 => features.sparse_residency4_samples,
+            8 => features.sparse_residency8_samples,
+            16 => features.sparse_residency16_samples,
+            _ => true,
+        };
+        if !samples_feature {
+            return Err(ImageCreationError::SparseResidencySamplesFeatureNotEnabled);
+        }
+
+        // An implementation that doesn't support sparse residency for a combination of
+        // parameters returns no properties at all.
+        if num_samples.is_power_of_two() {
+            let vk_i = device.instance().pointers();
+            let mut count = 0;
+            vk_i.GetPhysicalDeviceSparseImageFormatProperties(
+                device.physical_device().internal_object(),
+                format as u32,
+                ty,
+                num_samples,
+                usage.to_usage_bits(),
+                vk::IMAGE_TILING_OPTIMAL,
+                &mut count,
+                ptr::null_mut(),
+            );
+            if count == 0 {
+                return Err(ImageCreationError::FormatNotSupported);
+            }
+        }
+
+        let sharing = match sharing {
+            Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
+            Sharing::Concurrent(ids) => (vk::SHARING_MODE_CONCURRENT, ids.collect()),
+        };
+
+        UnsafeImage::new_impl(
+            device,
+            usage,
+            format,
+            dimensions,
+            num_samples,
+            mipmaps.into(),
+            sharing,
+            false,
+            false,
+            false,
+            true,
         )
     }
 
@@ -127,6 +285,8 @@ impl UnsafeImage {
         (sh_mode, sh_indices): (vk::SharingMode, SmallVec<[u32; 8]>),
         linear_tiling: bool,
         preinitialized_layout: bool,
+        protected: bool,
+        sparse: bool,
     ) -> Result<(UnsafeImage, MemoryRequirements), ImageCreationError> {
         // TODO: doesn't check that the proper features are enabled
 
@@ -462,6 +622,12 @@ impl UnsafeImage {
             _ => unreachable!(),
         };
 
+        let flags = if sparse {
+            flags | vk::IMAGE_CREATE_SPARSE_BINDING_BIT | vk::IMAGE_CREATE_SPARSE_RESIDENCY_BIT
+        } else {
+            flags
+        };
+
         let usage = usage.to_usage_bits();
 
         // Now that all checks have been performed, if any of the check failed we query the Vulkan
@@ -481,7 +647,7 @@ impl UnsafeImage {
                 ty,
                 tiling,
                 usage,
-                0, /* TODO */
+                flags,
                 output.as_mut_ptr(),
             );
 
@@ -511,7 +677,11 @@ impl UnsafeImage {
             let infos = vk::ImageCreateInfo {
                 sType: vk::STRUCTURE_TYPE_IMAGE_CREATE_INFO,
                 pNext: ptr::null(),
-                flags: flags,
+                flags: if protected {
+                    flags | vk::IMAGE_CREATE_PROTECTED_BIT
+                } else {
+                    flags
+                },
                 imageType: ty,
                 format: format as u32,
                 extent: extent,
@@ -599,6 +769,8 @@ impl UnsafeImage {
             format_features: format_features,
             needs_destruction: true,
             preinitialized_layout,
+            protected,
+            sparse,
         };
 
         Ok((image, mem_reqs))
@@ -635,10 +807,13 @@ impl UnsafeImage {
             format_features: output.assume_init().optimalTilingFeatures,
             needs_destruction: false,     // TODO: pass as parameter
             preinitialized_layout: false, // TODO: Maybe this should be passed in?
+            protected: false,
+            sparse: false,
         }
     }
 
     pub unsafe fn bind_memory(&self, memory: &DeviceMemory, offset: usize) -> Result<(), OomError> {
+        debug_assert!(!self.sparse);
         let vk = self.device.pointers();
 
         // We check for correctness in debug mode.
@@ -847,6 +1022,52 @@ impl UnsafeImage {
     pub fn preinitialized_layout(&self) -> bool {
         self.preinitialized_layout
     }
+
+    /// Returns true if the image was created with `new_protected`.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
+    /// Returns true if the image was created with `new_sparse`.
+    #[inline]
+    pub fn is_sparse(&self) -> bool {
+        self.sparse
+    }
+
+    /// Returns the sparse memory requirements of the image, one for each group of aspects whose
+    /// memory is bound separately. Returns an empty list if the image isn't sparse.
+    pub fn sparse_memory_requirements(&self) -> Vec<SparseImageMemoryRequirements> {
+        if !self.sparse {
+            return Vec::new();
+        }
+
+        unsafe {
+            let vk = self.device.pointers();
+
+            let mut count = 0;
+            vk.GetImageSparseMemoryRequirements(
+                self.device.internal_object(),
+                self.image,
+                &mut count,
+                ptr::null_mut(),
+            );
+
+            let mut output = Vec::with_capacity(count as usize);
+            vk.GetImageSparseMemoryRequirements(
+                self.device.internal_object(),
+                self.image,
+                &mut count,
+                output.as_mut_ptr(),
+            );
+            output.set_len(count as usize);
+
+            output
+                .iter()
+                .map(SparseImageMemoryRequirements::from_vulkan)
+                .collect()
+        }
+    }
 }
 
 unsafe impl VulkanObject for UnsafeImage {
@@ -898,6 +1119,79 @@ impl Hash for UnsafeImage {
     }
 }
 
+/// Describes how the memory of a sparse image must be bound.
+///
+/// The memory of the image is bound in blocks of `image_granularity` texels, except for the
+/// mip tail. The mip tail is made of the mipmap levels starting at `mip_tail_first_lod`, which
+/// are too small to be split into blocks. It can't be bound with the image block functions and
+/// must instead be bound as a whole with an opaque bind of `mip_tail_size` bytes at
+/// `mip_tail_offset`. Unless `single_mip_tail` is true, each array layer has its own mip tail,
+/// which starts `mip_tail_stride` bytes after the previous one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SparseImageMemoryRequirements {
+    /// The aspects of the image that these requirements apply to.
+    pub aspects: ImageAspects,
+    /// True if these requirements apply to the metadata of the image, which must always be bound
+    /// with an opaque bind.
+    pub metadata: bool,
+    /// Width, height and depth in texels of the blocks of the image.
+    pub image_granularity: [u32; 3],
+    /// True if all the array layers share a single mip tail.
+    pub single_mip_tail: bool,
+    /// True if the first mipmap level whose dimensions aren't a multiple of the block size is
+    /// already part of the mip tail.
+    pub aligned_mip_size: bool,
+    /// True if `image_granularity` doesn't match the standard block size of the format.
+    pub nonstandard_block_size: bool,
+    /// First mipmap level that is part of the mip tail. If it's greater than or equal to the
+    /// number of mipmap levels of the image, the image doesn't have a mip tail.
+    pub mip_tail_first_lod: u32,
+    /// Size in bytes of the mip tail of one array layer, or of the whole image if
+    /// `single_mip_tail` is true.
+    pub mip_tail_size: usize,
+    /// Offset in bytes of the mip tail, to use with an opaque bind.
+    pub mip_tail_offset: usize,
+    /// Distance in bytes between the mip tails of two consecutive array layers.
+    pub mip_tail_stride: usize,
+}
+
+impl SparseImageMemoryRequirements {
+    fn from_vulkan(reqs: &vk::SparseImageMemoryRequirements) -> SparseImageMemoryRequirements {
+        let aspect_mask = reqs.formatProperties.aspectMask;
+        let granularity = &reqs.formatProperties.imageGranularity;
+        let flags = reqs.formatProperties.flags;
+
+        SparseImageMemoryRequirements {
+            aspects: ImageAspects {
+                color: (aspect_mask & vk::IMAGE_ASPECT_COLOR_BIT) != 0,
+                depth: (aspect_mask & vk::IMAGE_ASPECT_DEPTH_BIT) != 0,
+                stencil: (aspect_mask & vk::IMAGE_ASPECT_STENCIL_BIT) != 0,
+            },
+            metadata: (aspect_mask & vk::IMAGE_ASPECT_METADATA_BIT) != 0,
+            image_granularity: [granularity.width, granularity.height, granularity.depth],
+            single_mip_tail: (flags & vk::SPARSE_IMAGE_FORMAT_SINGLE_MIPTAIL_BIT) != 0,
+            aligned_mip_size: (flags & vk::SPARSE_IMAGE_FORMAT_ALIGNED_MIP_SIZE_BIT) != 0,
+            nonstandard_block_size: (flags & vk::SPARSE_IMAGE_FORMAT_NONSTANDARD_BLOCK_SIZE_BIT)
+                != 0,
+            mip_tail_first_lod: reqs.imageMipTailFirstLod,
+            mip_tail_size: reqs.imageMipTailSize as usize,
+            mip_tail_offset: reqs.imageMipTailOffset as usize,
+            mip_tail_stride: reqs.imageMipTailStride as usize,
+        }
+    }
+
+    /// Returns the offset in bytes of the mip tail of the given array layer, to use with an
+    /// opaque bind.
+    #[inline]
+    pub fn mip_tail_offset_for_layer(&self, array_layer: u32) -> usize {
+        if self.single_mip_tail {
+            self.mip_tail_offset
+        } else {
+            self.mip_tail_offset + array_layer as usize * self.mip_tail_stride
+        }
+    }
+}
+
 /// Error that can happen when creating an instance.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ImageCreationError {
@@ -918,6 +1212,19 @@ pub enum ImageCreationError {
     UnsupportedUsage,
     /// The `shader_storage_image_multisample` feature must be enabled to create such an image.
     ShaderStorageImageMultisampleFeatureNotEnabled,
+    /// The `protected_memory` feature must be enabled to create a protected image.
+    ProtectedMemoryFeatureNotEnabled,
+    /// The `sparse_binding` feature must be enabled to create a sparse image.
+    SparseBindingFeatureNotEnabled,
+    /// The `sparse_residency_image2d` feature must be enabled to create a two-dimensional sparse
+    /// image.
+    SparseResidencyImage2dFeatureNotEnabled,
+    /// The `sparse_residency_image3d` feature must be enabled to create a three-dimensional
+    /// sparse image.
+    SparseResidencyImage3dFeatureNotEnabled,
+    /// The `sparse_residency*_samples` feature that corresponds to the number of samples must be
+    /// enabled to create a multisampled sparse image.
+    SparseResidencySamplesFeatureNotEnabled,
 }
 
 impl error::Error for ImageCreationError {
@@ -958,6 +1265,24 @@ impl fmt::Display for ImageCreationError {
                     "the `shader_storage_image_multisample` feature must be enabled to create such \
                  an image"
                 }
+                ImageCreationError::ProtectedMemoryFeatureNotEnabled => {
+                    "the `protected_memory` feature must be enabled to create a protected image"
+                }
+                ImageCreationError::SparseBindingFeatureNotEnabled => {
+                    "the `sparse_binding` feature must be enabled to create a sparse image"
+                }
+                ImageCreationError::SparseResidencyImage2dFeatureNotEnabled => {
+                    "the `sparse_residency_image2d` feature must be enabled to create a \
+                 two-dimensional sparse image"
+                }
+                ImageCreationError::SparseResidencyImage3dFeatureNotEnabled => {
+                    "the `sparse_residency_image3d` feature must be enabled to create a \
+                 three-dimensional sparse image"
+                }
+                ImageCreationError::SparseResidencySamplesFeatureNotEnabled => {
+                    "the `sparse_residency*_samples` feature that corresponds to the number of \
+                 samples must be enabled to create a multisampled sparse image"
+                }
             }
         )
     }
@@ -988,6 +1313,63 @@ impl From<Error> for ImageCreationError {
     }
 }
 
+/// Error that can happen when creating an image view.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImageViewCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The requested aspects are empty or not all present in the format of the image.
+    UnsupportedAspects {
+        format: Format,
+        aspects: ImageAspects,
+    },
+    /// The `khr_maintenance2` extension must be enabled, or the device must support Vulkan 1.1,
+    /// in order to specify the usage of a view.
+    Maintenance2ExtensionNotEnabled,
+    /// The requested usage is empty or isn't a subset of the usage of the image.
+    UsageNotSubsetOfImage,
+}
+
+impl error::Error for ImageViewCreationError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            ImageViewCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ImageViewCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                ImageViewCreationError::OomError(_) => "not enough memory available",
+                ImageViewCreationError::UnsupportedAspects { .. } => {
+                    "the requested aspects are empty or not present in the format of the image"
+                }
+                ImageViewCreationError::Maintenance2ExtensionNotEnabled => {
+                    "the `khr_maintenance2` extension must be enabled in order to specify the \
+                     usage of a view"
+                }
+                ImageViewCreationError::UsageNotSubsetOfImage => {
+                    "the requested usage is empty or isn't a subset of the usage of the image"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for ImageViewCreationError {
+    #[inline]
+    fn from(err: OomError) -> ImageViewCreationError {
+        ImageViewCreationError::OomError(err)
+    }
+}
+
 /// Describes the memory layout of an image with linear tiling.
 ///
 /// Obtained by calling `*_linear_layout` on the image.
@@ -1018,6 +1400,9 @@ pub struct UnsafeImageView {
     usage: vk::ImageUsageFlagBits,
     identity_swizzle: bool,
     format: Format,
+    aspects: ImageAspects,
+    // `vkDestroyImageView` is called only if `needs_destruction` is true.
+    needs_destruction: bool,
 }
 
 impl UnsafeImageView {
@@ -1027,6 +1412,99 @@ impl UnsafeImageView {
         ty: ViewType,
         mipmap_levels: Range<u32>,
         array_layers: Range<u32>,
+    ) -> Result<UnsafeImageView, OomError> {
+        let aspects = ImageAspects::from_format(image.format);
+        UnsafeImageView::raw_impl(image, ty, mipmap_levels, array_layers, aspects, None)
+    }
+
+    /// Same as `raw`, but the view only has the given subset of the usage of the image.
+    ///
+    /// For example, this can be used to create a storage-only view of an image that is also a
+    /// color attachment, when the format of the image supports the attachment usage but not the
+    /// storage usage. The `usage_*` methods of the view then return the usage of the view.
+    ///
+    /// Requires the `khr_maintenance2` extension or Vulkan 1.1. Returns an error if `usage` is
+    /// empty or isn't a subset of the usage of the image.
+    ///
+    /// # Panic
+    ///
+    /// Panics in the same situations as `new`.
+    ///
+    pub unsafe fn raw_with_usage(
+        image: &UnsafeImage,
+        ty: ViewType,
+        mipmap_levels: Range<u32>,
+        array_layers: Range<u32>,
+        usage: ImageUsage,
+    ) -> Result<UnsafeImageView, ImageViewCreationError> {
+        let usage_bits = usage.to_usage_bits();
+        if usage_bits == 0 || (usage_bits & image.usage) != usage_bits {
+            return Err(ImageViewCreationError::UsageNotSubsetOfImage);
+        }
+
+        let version_1_1 = Version {
+            major: 1,
+            minor: 1,
+            patch: 0,
+        };
+        if !image.device.loaded_extensions().khr_maintenance2
+            && image.device.api_version() < version_1_1
+        {
+            return Err(ImageViewCreationError::Maintenance2ExtensionNotEnabled);
+        }
+
+        let aspects = ImageAspects::from_format(image.format);
+        Ok(UnsafeImageView::raw_impl(
+            image,
+            ty,
+            mipmap_levels,
+            array_layers,
+            aspects,
+            Some(usage_bits),
+        )?)
+    }
+
+    /// Same as `raw`, but only the given aspects of the image are part of the view.
+    ///
+    /// This can be used to create a depth-only or a stencil-only view of a depth-stencil image,
+    /// which is required for example to sample from it. Returns an error if the format of the
+    /// image doesn't have all the requested aspects, or if `aspects` is empty.
+    ///
+    /// # Panic
+    ///
+    /// Panics in the same situations as `new`.
+    ///
+    pub unsafe fn raw_with_aspects(
+        image: &UnsafeImage,
+        ty: ViewType,
+        mipmap_levels: Range<u32>,
+        array_layers: Range<u32>,
+        aspects: ImageAspects,
+    ) -> Result<UnsafeImageView, ImageViewCreationError> {
+        if aspects.is_empty() || !ImageAspects::from_format(image.format).contains(&aspects) {
+            return Err(ImageViewCreationError::UnsupportedAspects {
+                format: image.format,
+                aspects,
+            });
+        }
+
+        Ok(UnsafeImageView::raw_impl(
+            image,
+            ty,
+            mipmap_levels,
+            array_layers,
+            aspects,
+            None,
+        )?)
+    }
+
+    unsafe fn raw_impl(
+        image: &UnsafeImage,
+        ty: ViewType,
+        mipmap_levels: Range<u32>,
+        array_layers: Range<u32>,
+        aspects: ImageAspects,
+        view_usage: Option<vk::ImageUsageFlagBits>,
     ) -> Result<UnsafeImageView, OomError> {
         let vk = image.device.pointers();
 
@@ -1035,15 +1513,6 @@ impl UnsafeImageView {
         assert!(array_layers.end > array_layers.start);
         assert!(array_layers.end <= image.dimensions.array_layers());
 
-        let aspect_mask = match image.format.ty() {
-            FormatTy::Float | FormatTy::Uint | FormatTy::Sint | FormatTy::Compressed => {
-                vk::IMAGE_ASPECT_COLOR_BIT
-            }
-            FormatTy::Depth => vk::IMAGE_ASPECT_DEPTH_BIT,
-            FormatTy::Stencil => vk::IMAGE_ASPECT_STENCIL_BIT,
-            FormatTy::DepthStencil => vk::IMAGE_ASPECT_DEPTH_BIT | vk::IMAGE_ASPECT_STENCIL_BIT,
-        };
-
         let view_type = match (
             image.dimensions(),
             ty,
@@ -1082,9 +1551,18 @@ impl UnsafeImageView {
         };
 
         let view = {
+            let usage_infos = view_usage.map(|usage| vk::ImageViewUsageCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_IMAGE_VIEW_USAGE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                usage,
+            });
+
             let infos = vk::ImageViewCreateInfo {
                 sType: vk::STRUCTURE_TYPE_IMAGE_VIEW_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: usage_infos
+                    .as_ref()
+                    .map(|infos| infos as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
                 flags: 0, // reserved
                 image: image.internal_object(),
                 viewType: view_type,
@@ -1096,7 +1574,7 @@ impl UnsafeImageView {
                     a: 0,
                 }, // FIXME:
                 subresourceRange: vk::ImageSubresourceRange {
-                    aspectMask: aspect_mask,
+                    aspectMask: aspects.into_vulkan_bits(),
                     baseMipLevel: mipmap_levels.start,
                     levelCount: mipmap_levels.end - mipmap_levels.start,
                     baseArrayLayer: array_layers.start,
@@ -1117,12 +1595,42 @@ impl UnsafeImageView {
         Ok(UnsafeImageView {
             view: view,
             device: image.device.clone(),
-            usage: image.usage,
+            usage: view_usage.unwrap_or(image.usage),
             identity_swizzle: true, // FIXME:
             format: image.format,
+            aspects,
+            needs_destruction: true,
         })
     }
 
+    /// Creates an image view from a raw handle. The view won't be destroyed.
+    ///
+    /// This is meant to be used with views that were created outside of vulkano, for example by
+    /// another library that uses the same device.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid view of `image`, with the same format as `image` and with
+    ///   identity swizzling.
+    /// - `aspects` must match the aspects of the subresource range of the view.
+    /// - The view must stay alive for as long as the returned object is alive.
+    ///
+    pub unsafe fn from_raw(
+        image: &UnsafeImage,
+        handle: vk::ImageView,
+        aspects: ImageAspects,
+    ) -> UnsafeImageView {
+        UnsafeImageView {
+            view: handle,
+            device: image.device.clone(),
+            usage: image.usage,
+            identity_swizzle: true,
+            format: image.format,
+            aspects,
+            needs_destruction: false,
+        }
+    }
+
     /// Creates a new view from an image.
     ///
     /// Note that you must create the view with identity swizzling if you want to use this view
@@ -1153,6 +1661,12 @@ impl UnsafeImageView {
         self.format
     }
 
+    /// Returns the aspects of the image that are part of the view.
+    #[inline]
+    pub fn aspects(&self) -> ImageAspects {
+        self.aspects
+    }
+
     #[inline]
     pub fn usage_transfer_source(&self) -> bool {
         (self.usage & vk::IMAGE_USAGE_TRANSFER_SRC_BIT) != 0
@@ -1215,6 +1729,10 @@ impl fmt::Debug for UnsafeImageView {
 impl Drop for UnsafeImageView {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyImageView(self.device.internal_object(), self.view, ptr::null());
@@ -1246,10 +1764,13 @@ mod tests {
 
     use super::ImageCreationError;
     use super::ImageUsage;
+    use super::ImageViewCreationError;
     use super::UnsafeImage;
+    use super::UnsafeImageView;
 
     use format::Format;
     use image::ImageDimensions;
+    use image::ViewType;
     use sync::Sharing;
 
     #[test]
@@ -1594,4 +2115,55 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn view_usage_not_subset() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        let (image, _) = unsafe {
+            UnsafeImage::new(
+                device,
+                usage,
+                Format::R8G8B8A8Unorm,
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                    cubemap_compatible: false,
+                },
+                1,
+                1,
+                Sharing::Exclusive::<Empty<_>>,
+                false,
+                false,
+            )
+        }
+        .unwrap();
+
+        let view_usage = ImageUsage {
+            storage: true,
+            ..ImageUsage::none()
+        };
+
+        let res = unsafe {
+            UnsafeImageView::raw_with_usage(&image, ViewType::Dim2d, 0..1, 0..1, view_usage)
+        };
+        match res {
+            Err(ImageViewCreationError::UsageNotSubsetOfImage) => (),
+            _ => panic!(),
+        };
+
+        let res = unsafe {
+            UnsafeImageView::raw_with_usage(&image, ViewType::Dim2d, 0..1, 0..1, ImageUsage::none())
+        };
+        match res {
+            Err(ImageViewCreationError::UsageNotSubsetOfImage) => (),
+            _ => panic!(),
+        };
+    }
 }
@@ -11,6 +11,7 @@ use std::hash::Hash;
 use std::hash::Hasher;
 
 use buffer::BufferAccess;
+use device::DeviceOwned;
 use format::ClearValue;
 use format::Format;
 use format::PossibleCompressedFormatDesc;
@@ -26,6 +27,7 @@ use image::Dimensions;
 use image::ImageDimensions;
 use image::ImageLayout;
 use sampler::Sampler;
+use sampler::SamplerReductionMode;
 use sync::AccessError;
 
 use SafeDeref;
@@ -484,7 +486,18 @@ pub unsafe trait ImageViewAccess {
     /// This method should check whether the sampler's configuration can be used with the format
     /// of the view.
     // TODO: return a Result and propagate it when binding to a descriptor set
-    fn can_be_sampled(&self, _sampler: &Sampler) -> bool {
+    fn can_be_sampled(&self, sampler: &Sampler) -> bool {
+        if sampler.reduction_mode() != SamplerReductionMode::WeightedAverage {
+            let physical_device = sampler.device().physical_device();
+            let features = self
+                .format()
+                .properties(physical_device)
+                .optimal_tiling_features;
+            if !features.sampled_image_filter_minmax {
+                return false;
+            }
+        }
+
         true /* FIXME */
     }
 
@@ -0,0 +1,207 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::fmt;
+
+use image::sys::ImageViewCreationError;
+use image::sys::UnsafeImageView;
+use image::Dimensions;
+use image::ImageAccess;
+use image::ImageLayout;
+use image::ImageUsage;
+use image::ImageViewAccess;
+use sampler::Sampler;
+
+/// View of an image that only has a subset of the usage of the image.
+///
+/// For example, this can be used to create a storage-only view of an image that is also a color
+/// attachment, when the format of the image supports the attachment usage but not the storage
+/// usage. The view covers the same mipmap levels and array layers as the image it is created
+/// from.
+///
+/// Requires the `khr_maintenance2` extension or Vulkan 1.1.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::image::ImageUsage;
+/// use vulkano::image::UsageView;
+/// # use std::sync::Arc;
+/// # use vulkano::image::StorageImage;
+/// # use vulkano::format::R8G8B8A8Unorm;
+/// # let image: Arc<StorageImage<R8G8B8A8Unorm>> = return;
+///
+/// let usage = ImageUsage {
+///     storage: true,
+///     ..ImageUsage::none()
+/// };
+/// let view = UsageView::new(image.clone(), usage).unwrap();
+/// ```
+pub struct UsageView<I> {
+    image: I,
+    view: UnsafeImageView,
+    usage: ImageUsage,
+}
+
+impl<I> UsageView<I>
+where
+    I: ImageViewAccess,
+{
+    /// Builds a view of `image` that only has the given usage.
+    ///
+    /// Returns an error if `usage` is empty or isn't a subset of the usage of the image, or if
+    /// neither the `khr_maintenance2` extension nor Vulkan 1.1 are available on the device.
+    pub fn new(image: I, usage: ImageUsage) -> Result<UsageView<I>, ImageViewCreationError> {
+        let view = {
+            let inner = image.parent().inner();
+            let first_level = inner.first_mipmap_level as u32;
+            let first_layer = inner.first_layer as u32;
+
+            unsafe {
+                UnsafeImageView::raw_with_usage(
+                    inner.image,
+                    image.dimensions().to_view_type(),
+                    first_level..first_level + inner.num_mipmap_levels as u32,
+                    first_layer..first_layer + inner.num_layers as u32,
+                    usage,
+                )?
+            }
+        };
+
+        Ok(UsageView { image, view, usage })
+    }
+
+    /// Returns the image this view was created from.
+    #[inline]
+    pub fn image(&self) -> &I {
+        &self.image
+    }
+
+    /// Returns the usage of the view.
+    #[inline]
+    pub fn usage(&self) -> ImageUsage {
+        self.usage
+    }
+}
+
+unsafe impl<I> ImageViewAccess for UsageView<I>
+where
+    I: ImageViewAccess,
+{
+    #[inline]
+    fn parent(&self) -> &dyn ImageAccess {
+        self.image.parent()
+    }
+
+    #[inline]
+    fn dimensions(&self) -> Dimensions {
+        self.image.dimensions()
+    }
+
+    #[inline]
+    fn inner(&self) -> &UnsafeImageView {
+        &self.view
+    }
+
+    #[inline]
+    fn descriptor_set_storage_image_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_storage_image_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_combined_image_sampler_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_combined_image_sampler_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_sampled_image_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_sampled_image_layout()
+    }
+
+    #[inline]
+    fn descriptor_set_input_attachment_layout(&self) -> ImageLayout {
+        self.image.descriptor_set_input_attachment_layout()
+    }
+
+    #[inline]
+    fn identity_swizzle(&self) -> bool {
+        self.image.identity_swizzle()
+    }
+
+    #[inline]
+    fn can_be_sampled(&self, sampler: &Sampler) -> bool {
+        self.image.can_be_sampled(sampler)
+    }
+}
+
+impl<I> fmt::Debug for UsageView<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("UsageView")
+            .field("view", &self.view)
+            .field("usage", &self.usage)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::R8G8B8A8Unorm;
+    use image::Dimensions;
+    use image::ImageUsage;
+    use image::ImageViewAccess;
+    use image::ImageViewCreationError;
+    use image::StorageImage;
+    use image::UsageView;
+
+    #[test]
+    fn usage_subset() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = StorageImage::with_usage(
+            device.clone(),
+            Dimensions::Dim2d {
+                width: 32,
+                height: 32,
+            },
+            R8G8B8A8Unorm,
+            ImageUsage {
+                sampled: true,
+                storage: true,
+                ..ImageUsage::none()
+            },
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let storage = ImageUsage {
+            storage: true,
+            ..ImageUsage::none()
+        };
+        match UsageView::new(image.clone(), storage) {
+            Ok(view) => {
+                assert!(view.inner().usage_storage());
+                assert!(!view.inner().usage_sampled());
+            }
+            Err(ImageViewCreationError::Maintenance2ExtensionNotEnabled) => {
+                assert!(!device.loaded_extensions().khr_maintenance2);
+                return;
+            }
+            Err(_) => panic!(),
+        }
+
+        let color_attachment = ImageUsage {
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        match UsageView::new(image, color_attachment) {
+            Err(ImageViewCreationError::UsageNotSubsetOfImage) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -160,6 +160,8 @@ instance_extensions! {
     ext_swapchain_colorspace => b"VK_EXT_swapchain_colorspace",
     khr_get_physical_device_properties2 => b"VK_KHR_get_physical_device_properties2",
     khr_get_surface_capabilities2 => b"VK_KHR_get_surface_capabilities2",
+    khr_device_group_creation => b"VK_KHR_device_group_creation",
+    khr_external_semaphore_capabilities => b"VK_KHR_external_semaphore_capabilities",
 }
 
 /// This helper type can only be instantiated inside this module.
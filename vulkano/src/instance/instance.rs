@@ -15,6 +15,7 @@ use std::ffi::CString;
 use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::iter;
 use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
@@ -34,7 +35,11 @@ use OomError;
 use VulkanObject;
 
 use features::{Features, FeaturesFfi};
+use format::Format;
+use image::ImageTiling;
 use instance::{InstanceExtensions, RawInstanceExtensions};
+use swapchain::CapabilitiesError;
+use swapchain::Surface;
 use version::Version;
 
 /// An instance of a Vulkan context. This is the main object that should be created by an
@@ -100,6 +105,8 @@ pub struct Instance {
     extensions: RawInstanceExtensions,
     layers: SmallVec<[CString; 16]>,
     function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader + Send + Sync>>>,
+    // Version of Vulkan that the application requested when creating the instance.
+    api_version: Version,
 }
 
 // TODO: fix the underlying cause instead
@@ -209,6 +216,22 @@ impl Instance {
             None
         };
 
+        // Without an `ApplicationInfo`, the implementation assumes that the application uses
+        // Vulkan 1.0.
+        let api_version = if app_infos.is_some() {
+            Version {
+                major: 1,
+                minor: 1,
+                patch: 0,
+            }
+        } else {
+            Version {
+                major: 1,
+                minor: 0,
+                patch: 0,
+            }
+        };
+
         // Building the `vk::ApplicationInfo` if required.
         let app_infos = if let Some(app_infos) = app_infos {
             Some(vk::ApplicationInfo {
@@ -236,12 +259,7 @@ impl Instance {
                     .engine_version
                     .map(|v| v.into_vulkan_version())
                     .unwrap_or(0),
-                apiVersion: Version {
-                    major: 1,
-                    minor: 1,
-                    patch: 0,
-                }
-                .into_vulkan_version(), // TODO:
+                apiVersion: api_version.into_vulkan_version(), // TODO:
             })
         } else {
             None
@@ -321,6 +339,7 @@ impl Instance {
             extensions: extensions,
             layers: layers,
             function_pointers: function_pointers,
+            api_version,
         }))
     }
 
@@ -385,9 +404,29 @@ impl Instance {
             let mut extended_properties = PhysicalDeviceExtendedProperties::empty();
 
             let properties: vk::PhysicalDeviceProperties = unsafe {
+                let mut descriptor_indexing_properties: vk::PhysicalDeviceDescriptorIndexingPropertiesEXT =
+                    mem::zeroed();
+                descriptor_indexing_properties.sType =
+                    vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_PROPERTIES_EXT;
+
+                let mut maintenance4_properties = vk::PhysicalDeviceMaintenance4PropertiesKHR {
+                    sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MAINTENANCE_4_PROPERTIES_KHR,
+                    pNext: &mut descriptor_indexing_properties as *mut _ as *mut _,
+                    maxBufferSize: 0,
+                };
+
+                let mut filter_minmax_properties =
+                    vk::PhysicalDeviceSamplerFilterMinmaxPropertiesEXT {
+                        sType:
+                            vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SAMPLER_FILTER_MINMAX_PROPERTIES_EXT,
+                        pNext: &mut maintenance4_properties as *mut _ as *mut _,
+                        filterMinmaxSingleComponentFormats: vk::FALSE,
+                        filterMinmaxImageComponentMapping: vk::FALSE,
+                    };
+
                 let mut subgroup_properties = vk::PhysicalDeviceSubgroupProperties {
                     sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES,
-                    pNext: ptr::null_mut(),
+                    pNext: &mut filter_minmax_properties as *mut _ as *const _,
                     subgroupSize: 0,
                     supportedStages: 0,
                     supportedOperations: 0,
@@ -402,8 +441,46 @@ impl Instance {
 
                 vk.GetPhysicalDeviceProperties2KHR(device, &mut output);
 
+                // Min/max reduction is part of Vulkan 1.2. The structure is left untouched by
+                // older drivers, which can't be told apart from a `false` value.
+                let version_1_2 = Version {
+                    major: 1,
+                    minor: 2,
+                    patch: 0,
+                };
+                let filter_minmax_supported =
+                    Version::from_vulkan_version(output.properties.apiVersion) >= version_1_2
+                        || device_supports_extension(vk, device, b"VK_EXT_sampler_filter_minmax");
+
                 extended_properties = PhysicalDeviceExtendedProperties {
                     subgroup_size: Some(subgroup_properties.subgroupSize),
+                    filter_minmax_single_component_formats: if filter_minmax_supported {
+                        Some(filter_minmax_properties.filterMinmaxSingleComponentFormats != 0)
+                    } else {
+                        None
+                    },
+                    filter_minmax_image_component_mapping: if filter_minmax_supported {
+                        Some(filter_minmax_properties.filterMinmaxImageComponentMapping != 0)
+                    } else {
+                        None
+                    },
+                    // Left untouched by drivers that don't support `VK_KHR_maintenance4`.
+                    max_buffer_size: if maintenance4_properties.maxBufferSize != 0 {
+                        Some(maintenance4_properties.maxBufferSize)
+                    } else {
+                        None
+                    },
+                    // Left untouched by drivers that don't support `VK_EXT_descriptor_indexing`.
+                    update_after_bind_limits: if descriptor_indexing_properties
+                        .maxUpdateAfterBindDescriptorsInAllPools
+                        != 0
+                    {
+                        Some(UpdateAfterBindLimits::from_vulkan(
+                            &descriptor_indexing_properties,
+                        ))
+                    } else {
+                        None
+                    },
 
                     ..extended_properties
                 };
@@ -496,6 +573,14 @@ impl Instance {
         InstanceExtensions::from(&self.extensions)
     }
 
+    /// Returns the version of Vulkan that was requested when creating the instance. This is the
+    /// highest version whose core functionalities can be used, whatever the version supported
+    /// by the physical devices.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
     #[inline]
     pub fn raw_loaded_extensions(&self) -> &RawInstanceExtensions {
         &self.extensions
@@ -509,6 +594,33 @@ impl Instance {
     }
 }
 
+// Returns true if the physical device supports the given device extension. Errors are treated as
+// the extension not being supported.
+unsafe fn device_supports_extension(
+    vk: &vk::InstancePointers,
+    device: vk::PhysicalDevice,
+    name: &[u8],
+) -> bool {
+    let mut num = 0;
+    if vk.EnumerateDeviceExtensionProperties(device, ptr::null(), &mut num, ptr::null_mut())
+        != vk::SUCCESS
+    {
+        return false;
+    }
+
+    let mut properties: Vec<vk::ExtensionProperties> = Vec::with_capacity(num as usize);
+    if vk.EnumerateDeviceExtensionProperties(device, ptr::null(), &mut num, properties.as_mut_ptr())
+        != vk::SUCCESS
+    {
+        return false;
+    }
+    properties.set_len(num as usize);
+
+    properties
+        .iter()
+        .any(|p| CStr::from_ptr(p.extensionName.as_ptr()).to_bytes() == name)
+}
+
 impl fmt::Debug for Instance {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -742,12 +854,20 @@ struct PhysicalDeviceInfos {
 /// TODO: Only a small subset of available properties(https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceProperties2.html) is implemented at this moment.
 pub struct PhysicalDeviceExtendedProperties {
     subgroup_size: Option<u32>,
+    filter_minmax_single_component_formats: Option<bool>,
+    filter_minmax_image_component_mapping: Option<bool>,
+    max_buffer_size: Option<u64>,
+    update_after_bind_limits: Option<UpdateAfterBindLimits>,
 }
 
 impl PhysicalDeviceExtendedProperties {
     fn empty() -> Self {
         Self {
             subgroup_size: None,
+            filter_minmax_single_component_formats: None,
+            filter_minmax_image_component_mapping: None,
+            max_buffer_size: None,
+            update_after_bind_limits: None,
         }
     }
 
@@ -758,6 +878,103 @@ impl PhysicalDeviceExtendedProperties {
     pub fn subgroup_size(&self) -> &Option<u32> {
         &self.subgroup_size
     }
+
+    /// Whether a minimum set of single-component formats support min/max reduction samplers.
+    ///
+    /// `None` if the `ext_sampler_filter_minmax` device extension isn't supported and the device
+    /// doesn't support Vulkan 1.2.
+    /// See https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkPhysicalDeviceSamplerFilterMinmaxProperties.html for details
+    #[inline]
+    pub fn filter_minmax_single_component_formats(&self) -> &Option<bool> {
+        &self.filter_minmax_single_component_formats
+    }
+
+    /// Whether min/max reduction samplers support non-identity component mappings and
+    /// multi-component formats.
+    ///
+    /// `None` if the `ext_sampler_filter_minmax` device extension isn't supported and the device
+    /// doesn't support Vulkan 1.2.
+    #[inline]
+    pub fn filter_minmax_image_component_mapping(&self) -> &Option<bool> {
+        &self.filter_minmax_image_component_mapping
+    }
+
+    /// The maximum size in bytes of a buffer that can be created.
+    ///
+    /// `None` if the `khr_maintenance4` device extension isn't supported.
+    #[inline]
+    pub fn max_buffer_size(&self) -> &Option<u64> {
+        &self.max_buffer_size
+    }
+
+    /// The limits that apply to descriptor set layouts containing update-after-bind bindings,
+    /// instead of the regular limits.
+    ///
+    /// `None` if the `ext_descriptor_indexing` device extension isn't supported.
+    #[inline]
+    pub fn update_after_bind_limits(&self) -> &Option<UpdateAfterBindLimits> {
+        &self.update_after_bind_limits
+    }
+}
+
+/// Limits on the number of descriptors of pipeline layouts that contain descriptor set layouts
+/// with update-after-bind bindings.
+///
+/// The `max_per_stage_*` and `max_descriptor_set_*` limits count the descriptors of all the sets
+/// of the pipeline layout, including the ones that aren't update-after-bind. They replace the
+/// limits of the same name of `Limits`, which only count the descriptors of the sets that aren't
+/// update-after-bind.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UpdateAfterBindLimits {
+    /// Maximum number of descriptors in all the update-after-bind pools created on the device.
+    pub max_update_after_bind_descriptors_in_all_pools: u32,
+    pub max_per_stage_descriptor_samplers: u32,
+    pub max_per_stage_descriptor_uniform_buffers: u32,
+    pub max_per_stage_descriptor_storage_buffers: u32,
+    pub max_per_stage_descriptor_sampled_images: u32,
+    pub max_per_stage_descriptor_storage_images: u32,
+    pub max_per_stage_descriptor_input_attachments: u32,
+    pub max_per_stage_resources: u32,
+    pub max_descriptor_set_samplers: u32,
+    pub max_descriptor_set_uniform_buffers: u32,
+    pub max_descriptor_set_uniform_buffers_dynamic: u32,
+    pub max_descriptor_set_storage_buffers: u32,
+    pub max_descriptor_set_storage_buffers_dynamic: u32,
+    pub max_descriptor_set_sampled_images: u32,
+    pub max_descriptor_set_storage_images: u32,
+    pub max_descriptor_set_input_attachments: u32,
+}
+
+impl UpdateAfterBindLimits {
+    fn from_vulkan(props: &vk::PhysicalDeviceDescriptorIndexingPropertiesEXT) -> Self {
+        UpdateAfterBindLimits {
+            max_update_after_bind_descriptors_in_all_pools: props
+                .maxUpdateAfterBindDescriptorsInAllPools,
+            max_per_stage_descriptor_samplers: props.maxPerStageDescriptorUpdateAfterBindSamplers,
+            max_per_stage_descriptor_uniform_buffers: props
+                .maxPerStageDescriptorUpdateAfterBindUniformBuffers,
+            max_per_stage_descriptor_storage_buffers: props
+                .maxPerStageDescriptorUpdateAfterBindStorageBuffers,
+            max_per_stage_descriptor_sampled_images: props
+                .maxPerStageDescriptorUpdateAfterBindSampledImages,
+            max_per_stage_descriptor_storage_images: props
+                .maxPerStageDescriptorUpdateAfterBindStorageImages,
+            max_per_stage_descriptor_input_attachments: props
+                .maxPerStageDescriptorUpdateAfterBindInputAttachments,
+            max_per_stage_resources: props.maxPerStageUpdateAfterBindResources,
+            max_descriptor_set_samplers: props.maxDescriptorSetUpdateAfterBindSamplers,
+            max_descriptor_set_uniform_buffers: props.maxDescriptorSetUpdateAfterBindUniformBuffers,
+            max_descriptor_set_uniform_buffers_dynamic: props
+                .maxDescriptorSetUpdateAfterBindUniformBuffersDynamic,
+            max_descriptor_set_storage_buffers: props.maxDescriptorSetUpdateAfterBindStorageBuffers,
+            max_descriptor_set_storage_buffers_dynamic: props
+                .maxDescriptorSetUpdateAfterBindStorageBuffersDynamic,
+            max_descriptor_set_sampled_images: props.maxDescriptorSetUpdateAfterBindSampledImages,
+            max_descriptor_set_storage_images: props.maxDescriptorSetUpdateAfterBindStorageImages,
+            max_descriptor_set_input_attachments: props
+                .maxDescriptorSetUpdateAfterBindInputAttachments,
+        }
+    }
 }
 
 /// Represents one of the available devices on this machine.
@@ -916,6 +1133,22 @@ impl<'a> PhysicalDevice<'a> {
         &self.infos().available_features
     }
 
+    /// Returns true if images of the given format and tiling can be sampled with a linear
+    /// filter or a linear mipmap mode.
+    ///
+    /// Linear filtering is only guaranteed for some formats. For example many implementations
+    /// don't support it for 32-bit floating point formats, and sampling them with a linear
+    /// sampler is invalid.
+    #[inline]
+    pub fn is_format_linear_filterable(&self, format: Format, tiling: ImageTiling) -> bool {
+        let properties = format.properties(*self);
+        match tiling {
+            ImageTiling::Optimal => properties.optimal_tiling_features,
+            ImageTiling::Linear => properties.linear_tiling_features,
+        }
+        .sampled_image_filter_linear
+    }
+
     /// Builds an iterator that enumerates all the queue families on this physical device.
     #[inline]
     pub fn queue_families(&self) -> QueueFamiliesIter<'a> {
@@ -938,6 +1171,45 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Returns a queue family that can present images to the given surface, or `None` if there
+    /// is none.
+    ///
+    /// Queue families that also support graphics operations are preferred, so that the same
+    /// queue can be used for drawing and presenting. The results of the queries are cached in
+    /// the surface, see `Surface::is_supported`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the physical device and the surface don't belong to the same instance.
+    ///
+    pub fn find_present_queue_family<W>(
+        &self,
+        surface: &Surface<W>,
+    ) -> Result<Option<QueueFamily<'a>>, CapabilitiesError> {
+        assert_eq!(
+            &**self.instance as *const _,
+            &**surface.instance() as *const _,
+            "Instance mismatch in PhysicalDevice::find_present_queue_family"
+        );
+
+        let mut found = None;
+        for family in self.queue_families() {
+            if !surface.is_supported(family)? {
+                continue;
+            }
+
+            if family.supports_graphics() {
+                return Ok(Some(family));
+            }
+
+            if found.is_none() {
+                found = Some(family);
+            }
+        }
+
+        Ok(found)
+    }
+
     /// Builds an iterator that enumerates all the memory types on this physical device.
     #[inline]
     pub fn memory_types(&self) -> MemoryTypesIter<'a> {
@@ -991,6 +1263,19 @@ impl<'a> PhysicalDevice<'a> {
         Limits::from_vk_limits(&self.infos().properties.limits)
     }
 
+    /// Returns the maximum anisotropy that samplers created on this device can use.
+    ///
+    /// This is the `max_sampler_anisotropy` limit, or 1.0 if the `sampler_anisotropy` feature
+    /// isn't supported.
+    #[inline]
+    pub fn max_sampler_anisotropy(&self) -> f32 {
+        if self.supported_features().sampler_anisotropy {
+            self.limits().max_sampler_anisotropy()
+        } else {
+            1.0
+        }
+    }
+
     /// Returns an opaque number representing the version of the driver of this device.
     ///
     /// The meaning of this number is implementation-specific. It can be used in bug reports, for
@@ -1079,6 +1364,137 @@ impl<'a> Iterator for PhysicalDevicesIter<'a> {
 
 impl<'a> ExactSizeIterator for PhysicalDevicesIter<'a> {}
 
+/// Group of physical devices that can be used together to create a single logical device.
+///
+/// Physical devices in the same group are usually multiple GPUs of the same model linked
+/// together. Creating a device from a group with `Device::with_device_group` makes it possible to
+/// allocate memory on some of the physical devices only, and to choose which physical devices
+/// execute each command with device masks.
+///
+/// # Example
+///
+/// ```no_run
+/// # use vulkano::instance::Instance;
+/// # use vulkano::instance::InstanceExtensions;
+/// use vulkano::instance::PhysicalDeviceGroup;
+///
+/// # let instance = Instance::new(None, &InstanceExtensions::none(), None).unwrap();
+/// for group in PhysicalDeviceGroup::enumerate(&instance).unwrap() {
+///     println!("Group of {} devices", group.physical_device_count());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceGroup<'a> {
+    instance: &'a Arc<Instance>,
+    devices: SmallVec<[usize; 4]>,
+    subset_allocation: bool,
+}
+
+impl<'a> PhysicalDeviceGroup<'a> {
+    /// Enumerates the groups of physical devices available.
+    ///
+    /// If the `khr_device_group_creation` extension isn't enabled on the instance, each physical
+    /// device is returned in a group of its own.
+    pub fn enumerate(
+        instance: &'a Arc<Instance>,
+    ) -> Result<Vec<PhysicalDeviceGroup<'a>>, OomError> {
+        if !instance.loaded_extensions().khr_device_group_creation {
+            return Ok(PhysicalDevice::enumerate(instance)
+                .map(|physical_device| PhysicalDeviceGroup {
+                    instance,
+                    devices: iter::once(physical_device.index()).collect(),
+                    subset_allocation: false,
+                })
+                .collect());
+        }
+
+        let properties = unsafe {
+            let vk = instance.pointers();
+
+            let mut num = 0;
+            check_errors(vk.EnumeratePhysicalDeviceGroupsKHR(
+                instance.internal_object(),
+                &mut num,
+                ptr::null_mut(),
+            ))?;
+
+            let mut properties: Vec<vk::PhysicalDeviceGroupPropertiesKHR> = (0..num)
+                .map(|_| vk::PhysicalDeviceGroupPropertiesKHR {
+                    sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_GROUP_PROPERTIES_KHR,
+                    pNext: ptr::null_mut(),
+                    physicalDeviceCount: 0,
+                    physicalDevices: [0; vk::MAX_DEVICE_GROUP_SIZE_KHR],
+                    subsetAllocation: vk::FALSE,
+                })
+                .collect();
+            check_errors(vk.EnumeratePhysicalDeviceGroupsKHR(
+                instance.internal_object(),
+                &mut num,
+                properties.as_mut_ptr(),
+            ))?;
+            properties.set_len(num as usize);
+            properties
+        };
+
+        Ok(properties
+            .into_iter()
+            .map(|group| {
+                let devices = group.physicalDevices[..group.physicalDeviceCount as usize]
+                    .iter()
+                    .map(|&handle| {
+                        instance
+                            .physical_devices
+                            .iter()
+                            .position(|infos| infos.device == handle)
+                            .expect("Physical device group contains an unknown physical device")
+                    })
+                    .collect();
+
+                PhysicalDeviceGroup {
+                    instance,
+                    devices,
+                    subset_allocation: group.subsetAllocation != 0,
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the instance this group belongs to.
+    #[inline]
+    pub fn instance(&self) -> &'a Arc<Instance> {
+        &self.instance
+    }
+
+    /// Returns the number of physical devices in the group.
+    #[inline]
+    pub fn physical_device_count(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Returns the physical devices of the group.
+    ///
+    /// The index of a physical device in this list is the index of its bit in device masks.
+    #[inline]
+    pub fn physical_devices(&self) -> Vec<PhysicalDevice<'a>> {
+        self.devices
+            .iter()
+            .map(|&device| PhysicalDevice {
+                instance: self.instance,
+                device,
+            })
+            .collect()
+    }
+
+    /// Returns true if memory can be allocated on a subset of the physical devices of the group.
+    ///
+    /// If false, the device mask passed when allocating memory must contain all the physical
+    /// devices of the group.
+    #[inline]
+    pub fn supports_subset_allocation(&self) -> bool {
+        self.subset_allocation
+    }
+}
+
 /// Type of a physical device.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u32)]
@@ -1177,6 +1593,13 @@ impl<'a> QueueFamily<'a> {
         (self.flags() & vk::QUEUE_SPARSE_BINDING_BIT) != 0
     }
 
+    /// Returns true if queues of this family can be created as protected-capable queues, which
+    /// can execute protected command buffers.
+    #[inline]
+    pub fn supports_protected(&self) -> bool {
+        (self.flags() & vk::QUEUE_PROTECTED_BIT) != 0
+    }
+
     /// Internal utility function that returns the flags of this queue family.
     #[inline]
     fn flags(&self) -> u32 {
@@ -1300,6 +1723,16 @@ impl<'a> MemoryType<'a> {
         (self.flags() & vk::MEMORY_PROPERTY_LAZILY_ALLOCATED_BIT) != 0
     }
 
+    /// Returns true if memory of this memory type is protected.
+    ///
+    /// Protected memory can only be accessed by the device, and only by protected command
+    /// buffers. It is used by resources created with the protected flag. Memory of this type is
+    /// never host-visible.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        (self.flags() & vk::MEMORY_PROPERTY_PROTECTED_BIT) != 0
+    }
+
     /// Internal utility function that returns the flags of this queue family.
     #[inline]
     fn flags(&self) -> u32 {
@@ -116,10 +116,12 @@ pub use self::instance::MemoryHeapsIter;
 pub use self::instance::MemoryType;
 pub use self::instance::MemoryTypesIter;
 pub use self::instance::PhysicalDevice;
+pub use self::instance::PhysicalDeviceGroup;
 pub use self::instance::PhysicalDeviceType;
 pub use self::instance::PhysicalDevicesIter;
 pub use self::instance::QueueFamiliesIter;
 pub use self::instance::QueueFamily;
+pub use self::instance::UpdateAfterBindLimits;
 pub use self::layers::layers_list;
 pub use self::layers::LayerProperties;
 pub use self::layers::LayersIterator;
@@ -83,6 +83,57 @@ impl DeviceMemory {
         memory_type: MemoryType,
         size: usize,
         resource: DedicatedAlloc,
+    ) -> Result<DeviceMemory, DeviceMemoryAllocError> {
+        DeviceMemory::alloc_impl(device, memory_type, size, resource, None)
+    }
+
+    /// Same as `alloc`, but only allocates the memory on the physical devices whose bit is set in
+    /// `device_mask`.
+    ///
+    /// Bit `n` of the mask corresponds to the `n`th physical device of the group the device was
+    /// created with. Returns an error if the `khr_device_group` extension isn't enabled on the
+    /// device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `size` is 0.
+    /// - Panics if `memory_type` doesn't belong to the same physical device as `device`.
+    /// - Panics if `device_mask` is 0 or contains bits for physical devices that the device
+    ///   doesn't span.
+    ///
+    #[inline]
+    pub fn alloc_with_device_mask(
+        device: Arc<Device>,
+        memory_type: MemoryType,
+        size: usize,
+        device_mask: u32,
+    ) -> Result<DeviceMemory, DeviceMemoryAllocError> {
+        if !device.loaded_extensions().khr_device_group {
+            return Err(DeviceMemoryAllocError::DeviceGroupExtensionNotEnabled);
+        }
+
+        assert_ne!(device_mask, 0);
+        assert_eq!(
+            (device_mask as u64) >> device.physical_device_count(),
+            0,
+            "device mask contains physical devices outside of the device group"
+        );
+
+        DeviceMemory::alloc_impl(
+            device,
+            memory_type,
+            size,
+            DedicatedAlloc::None,
+            Some(device_mask),
+        )
+    }
+
+    fn alloc_impl(
+        device: Arc<Device>,
+        memory_type: MemoryType,
+        size: usize,
+        resource: DedicatedAlloc,
+        device_mask: Option<u32>,
     ) -> Result<DeviceMemory, DeviceMemoryAllocError> {
         assert!(size >= 1);
         assert_eq!(
@@ -128,12 +179,24 @@ impl DeviceMemory {
                 None
             };
 
+            let dedicated_alloc_ptr = dedicated_alloc_info
+                .as_ref()
+                .map(|i| i as *const vk::MemoryDedicatedAllocateInfoKHR)
+                .unwrap_or(ptr::null()) as *const c_void;
+
+            let flags_info = device_mask.map(|device_mask| vk::MemoryAllocateFlagsInfoKHR {
+                sType: vk::STRUCTURE_TYPE_MEMORY_ALLOCATE_FLAGS_INFO_KHR,
+                pNext: dedicated_alloc_ptr,
+                flags: vk::MEMORY_ALLOCATE_DEVICE_MASK_BIT_KHR,
+                deviceMask: device_mask,
+            });
+
             let infos = vk::MemoryAllocateInfo {
                 sType: vk::STRUCTURE_TYPE_MEMORY_ALLOCATE_INFO,
-                pNext: dedicated_alloc_info
+                pNext: flags_info
                     .as_ref()
-                    .map(|i| i as *const vk::MemoryDedicatedAllocateInfoKHR)
-                    .unwrap_or(ptr::null()) as *const _,
+                    .map(|i| i as *const vk::MemoryAllocateFlagsInfoKHR as *const c_void)
+                    .unwrap_or(dedicated_alloc_ptr),
                 allocationSize: size as u64,
                 memoryTypeIndex: memory_type.id(),
             };
@@ -483,6 +546,8 @@ pub enum DeviceMemoryAllocError {
     TooManyObjects,
     /// Memory map failed.
     MemoryMapFailed,
+    /// A device mask was specified but the `khr_device_group` extension isn't enabled.
+    DeviceGroupExtensionNotEnabled,
 }
 
 impl error::Error for DeviceMemoryAllocError {
@@ -507,6 +572,10 @@ impl fmt::Display for DeviceMemoryAllocError {
                     "the maximum number of allocations has been exceeded"
                 }
                 DeviceMemoryAllocError::MemoryMapFailed => "memory map failed",
+                DeviceMemoryAllocError::DeviceGroupExtensionNotEnabled => {
+                    "a device mask was specified but the `khr_device_group` extension isn't \
+                     enabled"
+                }
             }
         )
     }
@@ -546,6 +615,16 @@ mod tests {
         let _ = DeviceMemory::alloc(device.clone(), mem_ty, 256).unwrap();
     }
 
+    #[test]
+    fn device_mask_without_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+        match DeviceMemory::alloc_with_device_mask(device.clone(), mem_ty, 256, 1) {
+            Err(DeviceMemoryAllocError::DeviceGroupExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn zero_size() {
         let (device, _) = gfx_dev_and_queue!();
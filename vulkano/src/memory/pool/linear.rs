@@ -0,0 +1,358 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use device::Device;
+use device::DeviceOwned;
+use instance::Instance;
+use instance::MemoryType;
+use memory::pool::MemoryPoolAlloc;
+use memory::DeviceMemory;
+use memory::DeviceMemoryAllocError;
+use memory::MappedDeviceMemory;
+use sync::Fence;
+use OomError;
+use VulkanObject;
+
+/// Host-visible memory block from which allocations are made by bumping an offset.
+///
+/// This is meant for transient data that only lives for the duration of a frame, such as
+/// per-frame uniforms or upload scratch space. Allocating is a simple increment, and all the
+/// allocations are freed at once when the frame is over.
+///
+/// Once you have submitted the work that uses the allocations of the current frame, call
+/// `end_frame` with a fence that is signaled when that work is finished. The next call to `alloc`
+/// resets the whole block if the fence is signaled and if all the previous allocations have been
+/// dropped, or returns `LinearAllocError::FrameInFlight` otherwise. You are therefore encouraged
+/// to create one linear allocator per frame in flight.
+///
+/// Contrary to `StdMemoryPool`, the block never grows. An allocation that doesn't fit in the
+/// remaining space returns `LinearAllocError::OutOfSpace`.
+#[derive(Debug)]
+pub struct LinearAllocator {
+    device: Arc<Device>,
+    memory_type: u32,
+    memory: MappedDeviceMemory,
+    state: Mutex<LinearAllocatorState>,
+}
+
+#[derive(Debug)]
+struct LinearAllocatorState {
+    // Offset of the first free byte of the block.
+    offset: usize,
+    // Number of `LinearAllocatorAlloc` that are still alive.
+    live_allocations: usize,
+    // Fence passed to `end_frame`, if any.
+    fence: Option<Fence>,
+}
+
+impl LinearAllocator {
+    /// Allocates a new block of `size` bytes from the given memory type.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `device` and `memory_type` don't belong to the same physical device.
+    /// - Panics if the memory type is not host-visible.
+    /// - Panics if `size` is 0.
+    ///
+    pub fn new(
+        device: Arc<Device>,
+        memory_type: MemoryType,
+        size: usize,
+    ) -> Result<Arc<LinearAllocator>, DeviceMemoryAllocError> {
+        assert_eq!(
+            &**device.physical_device().instance() as *const Instance,
+            &**memory_type.physical_device().instance() as *const Instance
+        );
+        assert_eq!(
+            device.physical_device().index(),
+            memory_type.physical_device().index()
+        );
+        assert!(memory_type.is_host_visible());
+        assert!(size != 0);
+
+        let memory = DeviceMemory::alloc_and_map(device.clone(), memory_type, size)?;
+
+        Ok(Arc::new(LinearAllocator {
+            device,
+            memory_type: memory_type.id(),
+            memory,
+            state: Mutex::new(LinearAllocatorState {
+                offset: 0,
+                live_allocations: 0,
+                fence: None,
+            }),
+        }))
+    }
+
+    /// Allocates `size` bytes aligned to `alignment` from the block.
+    ///
+    /// If `end_frame` has been called, the block is first reset if the fence is signaled.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `size` is 0.
+    /// - Panics if `alignment` is 0.
+    ///
+    pub fn alloc(
+        me: &Arc<Self>,
+        size: usize,
+        alignment: usize,
+    ) -> Result<LinearAllocatorAlloc, LinearAllocError> {
+        assert!(size != 0);
+        assert!(alignment != 0);
+
+        let mut state = me.state.lock().unwrap();
+
+        if let Some(ref fence) = state.fence {
+            if state.live_allocations != 0 || !fence.ready()? {
+                return Err(LinearAllocError::FrameInFlight);
+            }
+        }
+
+        if state.fence.take().is_some() {
+            state.offset = 0;
+        }
+
+        let offset = (state.offset + alignment - 1) / alignment * alignment;
+        let available = me.size().saturating_sub(offset);
+        if size > available {
+            return Err(LinearAllocError::OutOfSpace {
+                requested: size,
+                available,
+            });
+        }
+
+        state.offset = offset + size;
+        state.live_allocations += 1;
+
+        Ok(LinearAllocatorAlloc {
+            allocator: me.clone(),
+            offset,
+            size,
+        })
+    }
+
+    /// Marks the end of the current frame.
+    ///
+    /// `fence` must be signaled once the device has finished using all the allocations that were
+    /// made so far, for example by being passed to `then_signal_fence` on the future of the last
+    /// submission of the frame.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `fence` doesn't belong to the same device as the allocator.
+    ///
+    pub fn end_frame(&self, fence: Fence) {
+        assert_eq!(
+            self.device.internal_object(),
+            fence.device().internal_object()
+        );
+
+        let mut state = self.state.lock().unwrap();
+        state.fence = Some(fence);
+    }
+
+    /// Returns the size of the block in bytes.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.memory.as_ref().size()
+    }
+
+    /// Returns the number of bytes that are still available in the block.
+    ///
+    /// Doesn't take into account the fact that the block may be reset by the next call to
+    /// `alloc`.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.size() - self.state.lock().unwrap().offset
+    }
+
+    /// Returns the memory type this allocator operates on.
+    #[inline]
+    pub fn memory_type(&self) -> MemoryType<'_> {
+        self.device
+            .physical_device()
+            .memory_type_by_id(self.memory_type)
+            .unwrap()
+    }
+}
+
+unsafe impl DeviceOwned for LinearAllocator {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+/// Allocation made from a `LinearAllocator`.
+///
+/// The memory isn't reused until this object is dropped and the frame fence is signaled.
+#[derive(Debug)]
+pub struct LinearAllocatorAlloc {
+    allocator: Arc<LinearAllocator>,
+    offset: usize,
+    size: usize,
+}
+
+impl LinearAllocatorAlloc {
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+unsafe impl MemoryPoolAlloc for LinearAllocatorAlloc {
+    #[inline]
+    fn mapped_memory(&self) -> Option<&MappedDeviceMemory> {
+        Some(&self.allocator.memory)
+    }
+
+    #[inline]
+    fn memory(&self) -> &DeviceMemory {
+        self.allocator.memory.as_ref()
+    }
+
+    #[inline]
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Drop for LinearAllocatorAlloc {
+    #[inline]
+    fn drop(&mut self) {
+        let mut state = self.allocator.state.lock().unwrap();
+        state.live_allocations -= 1;
+    }
+}
+
+/// Error that can happen when allocating from a `LinearAllocator`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinearAllocError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The allocation doesn't fit in the remaining space of the block.
+    OutOfSpace {
+        /// Number of bytes that were requested.
+        requested: usize,
+        /// Number of bytes that are available after alignment.
+        available: usize,
+    },
+
+    /// The fence passed to `end_frame` isn't signaled yet, or some allocations of the previous
+    /// frame are still alive.
+    FrameInFlight,
+}
+
+impl error::Error for LinearAllocError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            LinearAllocError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LinearAllocError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                LinearAllocError::OomError(_) => "not enough memory available",
+                LinearAllocError::OutOfSpace { .. } => {
+                    "the allocation doesn't fit in the remaining space of the block"
+                }
+                LinearAllocError::FrameInFlight => {
+                    "the previous frame is still in use and the block can't be reset"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for LinearAllocError {
+    #[inline]
+    fn from(err: OomError) -> LinearAllocError {
+        LinearAllocError::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use memory::pool::LinearAllocError;
+    use memory::pool::LinearAllocator;
+    use memory::pool::MemoryPoolAlloc;
+    use sync::Fence;
+
+    #[test]
+    fn bump_and_reset() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device
+            .physical_device()
+            .memory_types()
+            .filter(|m| m.is_host_visible())
+            .next()
+            .unwrap();
+
+        let allocator = LinearAllocator::new(device.clone(), mem_ty, 256).unwrap();
+
+        let a = LinearAllocator::alloc(&allocator, 10, 1).unwrap();
+        let b = LinearAllocator::alloc(&allocator, 16, 64).unwrap();
+        assert_eq!(a.offset(), 0);
+        assert_eq!(b.offset(), 64);
+        assert_eq!(allocator.remaining(), 256 - 80);
+
+        match LinearAllocator::alloc(&allocator, 256, 1) {
+            Err(LinearAllocError::OutOfSpace {
+                requested: 256,
+                available: 176,
+            }) => (),
+            _ => panic!(),
+        }
+
+        allocator.end_frame(Fence::alloc_signaled(device.clone()).unwrap());
+        match LinearAllocator::alloc(&allocator, 16, 1) {
+            Err(LinearAllocError::FrameInFlight) => (),
+            _ => panic!(),
+        }
+
+        drop(a);
+        drop(b);
+        let c = LinearAllocator::alloc(&allocator, 16, 1).unwrap();
+        assert_eq!(c.offset(), 0);
+    }
+
+    #[test]
+    fn fence_not_signaled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device
+            .physical_device()
+            .memory_types()
+            .filter(|m| m.is_host_visible())
+            .next()
+            .unwrap();
+
+        let allocator = LinearAllocator::new(device.clone(), mem_ty, 256).unwrap();
+        allocator.end_frame(Fence::alloc(device.clone()).unwrap());
+
+        match LinearAllocator::alloc(&allocator, 16, 1) {
+            Err(LinearAllocError::FrameInFlight) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -17,12 +17,16 @@ use memory::MemoryRequirements;
 
 pub use self::host_visible::StdHostVisibleMemoryTypePool;
 pub use self::host_visible::StdHostVisibleMemoryTypePoolAlloc;
+pub use self::linear::LinearAllocError;
+pub use self::linear::LinearAllocator;
+pub use self::linear::LinearAllocatorAlloc;
 pub use self::non_host_visible::StdNonHostVisibleMemoryTypePool;
 pub use self::non_host_visible::StdNonHostVisibleMemoryTypePoolAlloc;
 pub use self::pool::StdMemoryPool;
 pub use self::pool::StdMemoryPoolAlloc;
 
 mod host_visible;
+mod linear;
 mod non_host_visible;
 mod pool;
 
@@ -256,6 +260,18 @@ where
     }
 }
 
+impl<A> PotentialDedicatedAllocation<A> {
+    /// Returns true if this is a dedicated allocation, and false if it was allocated from a pool.
+    #[inline]
+    pub fn is_dedicated(&self) -> bool {
+        match *self {
+            PotentialDedicatedAllocation::Generic(_) => false,
+            PotentialDedicatedAllocation::Dedicated(_) => true,
+            PotentialDedicatedAllocation::DedicatedMapped(_) => true,
+        }
+    }
+}
+
 impl<A> From<A> for PotentialDedicatedAllocation<A> {
     #[inline]
     fn from(alloc: A) -> PotentialDedicatedAllocation<A> {
@@ -28,6 +28,9 @@ use vk;
 /// documentation of the `blend` module for more info.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Blend {
+    /// The logic operation to apply, if any. Requires the `logic_op` feature.
+    ///
+    /// If `Some`, blending must be disabled for all the attachments.
     pub logic_op: Option<LogicOp>,
 
     pub attachments: AttachmentsBlend,
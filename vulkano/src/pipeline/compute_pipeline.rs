@@ -25,7 +25,15 @@ use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
 use descriptor::pipeline_layout::PipelineLayoutNotSupersetError;
 use descriptor::pipeline_layout::PipelineLayoutSuperset;
 use descriptor::pipeline_layout::PipelineLayoutSys;
+use pipeline::executable;
+use pipeline::executable::PipelineExecutableCapture;
+use pipeline::executable::PipelineExecutableProperties;
+use pipeline::executable::PipelineExecutablePropertiesError;
+use pipeline::feedback::CreationFeedbackStorage;
+use pipeline::feedback::PipelineCreationFeedback;
 use pipeline::shader::EntryPointAbstract;
+use pipeline::shader::EntryPointError;
+use pipeline::shader::ExecutionModel;
 use pipeline::shader::SpecializationConstants;
 
 use check_errors;
@@ -52,6 +60,9 @@ pub struct ComputePipeline<Pl> {
 struct Inner {
     pipeline: vk::Pipeline,
     device: Arc<Device>,
+    capture: PipelineExecutableCapture,
+    local_size: Option<[u32; 3]>,
+    creation_feedback: Option<PipelineCreationFeedback>,
 }
 
 impl ComputePipeline<()> {
@@ -104,6 +115,73 @@ impl<Pl> ComputePipeline<Pl> {
         }
     }
 
+    /// Same as `with_pipeline_layout`, but also captures information about the executables of
+    /// the pipeline so that it can be retrieved with `executable_properties()`.
+    ///
+    /// An error will be returned if `capture` requests something and the
+    /// `pipeline_executable_info` feature isn't enabled on the device.
+    pub fn with_executable_capture<Cs>(
+        device: Arc<Device>,
+        shader: &Cs,
+        specialization: &Cs::SpecializationConstants,
+        pipeline_layout: Pl,
+        capture: PipelineExecutableCapture,
+    ) -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
+    where
+        Cs::PipelineLayout: Clone,
+        Cs: EntryPointAbstract,
+        Pl: PipelineLayoutAbstract,
+    {
+        if !capture.is_none() && !device.enabled_features().pipeline_executable_info {
+            return Err(ComputePipelineCreationError::PipelineExecutableInfoFeatureNotEnabled);
+        }
+
+        unsafe {
+            PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout, shader.layout())?;
+            ComputePipeline::new_impl(
+                device,
+                shader,
+                specialization,
+                pipeline_layout,
+                capture,
+                false,
+            )
+        }
+    }
+
+    /// Same as `with_pipeline_layout`, but also captures feedback about the creation of the
+    /// pipeline so that it can be retrieved with `creation_feedback()`.
+    ///
+    /// An error will be returned if the `ext_pipeline_creation_feedback` extension isn't enabled
+    /// on the device.
+    pub fn with_creation_feedback<Cs>(
+        device: Arc<Device>,
+        shader: &Cs,
+        specialization: &Cs::SpecializationConstants,
+        pipeline_layout: Pl,
+    ) -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
+    where
+        Cs::PipelineLayout: Clone,
+        Cs: EntryPointAbstract,
+        Pl: PipelineLayoutAbstract,
+    {
+        if !device.loaded_extensions().ext_pipeline_creation_feedback {
+            return Err(ComputePipelineCreationError::PipelineCreationFeedbackExtensionNotEnabled);
+        }
+
+        unsafe {
+            PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout, shader.layout())?;
+            ComputePipeline::new_impl(
+                device,
+                shader,
+                specialization,
+                pipeline_layout,
+                PipelineExecutableCapture::none(),
+                true,
+            )
+        }
+    }
+
     /// Same as `with_pipeline_layout`, but doesn't check whether the pipeline layout is a
     /// superset of what the shader expects.
     pub unsafe fn with_unchecked_pipeline_layout<Cs>(
@@ -117,8 +195,50 @@ impl<Pl> ComputePipeline<Pl> {
         Cs: EntryPointAbstract,
         Pl: PipelineLayoutAbstract,
     {
+        ComputePipeline::new_impl(
+            device,
+            shader,
+            specialization,
+            pipeline_layout,
+            PipelineExecutableCapture::none(),
+            false,
+        )
+    }
+
+    unsafe fn new_impl<Cs>(
+        device: Arc<Device>,
+        shader: &Cs,
+        specialization: &Cs::SpecializationConstants,
+        pipeline_layout: Pl,
+        capture: PipelineExecutableCapture,
+        creation_feedback: bool,
+    ) -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
+    where
+        Cs::PipelineLayout: Clone,
+        Cs: EntryPointAbstract,
+        Pl: PipelineLayoutAbstract,
+    {
+        match shader
+            .module()
+            .check_entry_point(shader.name(), ExecutionModel::GLCompute)
+        {
+            Ok(()) => (),
+            Err(EntryPointError::NotFound) => {
+                return Err(ComputePipelineCreationError::EntryPointNotFound)
+            }
+            Err(EntryPointError::WrongExecutionModel) => {
+                return Err(ComputePipelineCreationError::WrongEntryPointExecutionModel)
+            }
+        }
+
         let vk = device.pointers();
 
+        let mut feedback_storage = if creation_feedback {
+            Some(CreationFeedbackStorage::new(1))
+        } else {
+            None
+        };
+
         let pipeline = {
             let spec_descriptors = Cs::SpecializationConstants::descriptors();
             let specialization = vk::SpecializationInfo {
@@ -142,10 +262,15 @@ impl<Pl> ComputePipeline<Pl> {
                 },
             };
 
+            let feedback_infos = feedback_storage.as_mut().map(|f| f.create_info());
+
             let infos = vk::ComputePipelineCreateInfo {
                 sType: vk::STRUCTURE_TYPE_COMPUTE_PIPELINE_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0,
+                pNext: feedback_infos
+                    .as_ref()
+                    .map(|f| f as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
+                flags: capture.into_vulkan_bits(),
                 stage: stage,
                 layout: PipelineLayoutAbstract::sys(&pipeline_layout).internal_object(),
                 basePipelineHandle: 0,
@@ -168,6 +293,10 @@ impl<Pl> ComputePipeline<Pl> {
             inner: Inner {
                 device: device.clone(),
                 pipeline: pipeline,
+                capture,
+                local_size: shader.local_size(),
+                creation_feedback: feedback_storage
+                    .map(|f| f.into_feedback(Some(vk::SHADER_STAGE_COMPUTE_BIT))),
             },
             pipeline_layout: pipeline_layout,
         })
@@ -193,12 +322,46 @@ impl<Pl> ComputePipeline<Pl> {
     pub fn layout(&self) -> &Pl {
         &self.pipeline_layout
     }
+
+    /// Returns the size of the local workgroups of the compute shader, if it is known.
+    #[inline]
+    pub fn local_size(&self) -> Option<[u32; 3]> {
+        self.inner.local_size
+    }
+
+    /// Returns the properties of the executables the pipeline was compiled into.
+    ///
+    /// The pipeline must have been created with `with_executable_capture`.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutablePropertiesError> {
+        executable::executable_properties(
+            &self.inner.device,
+            self.inner.pipeline,
+            self.inner.capture,
+        )
+    }
+
+    /// Returns the feedback about the creation of the pipeline.
+    ///
+    /// Returns `None` if the pipeline wasn't created with `with_creation_feedback`.
+    #[inline]
+    pub fn creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.inner.creation_feedback.as_ref()
+    }
 }
 
 /// Trait implemented on all compute pipelines.
 pub unsafe trait ComputePipelineAbstract: PipelineLayoutAbstract {
     /// Returns an opaque object that represents the inside of the compute pipeline.
     fn inner(&self) -> ComputePipelineSys;
+
+    /// Returns the size of the local workgroups of the compute shader, if it is known.
+    #[inline]
+    fn local_size(&self) -> Option<[u32; 3]> {
+        None
+    }
 }
 
 unsafe impl<Pl> ComputePipelineAbstract for ComputePipeline<Pl>
@@ -209,6 +372,11 @@ where
     fn inner(&self) -> ComputePipelineSys {
         ComputePipelineSys(self.inner.pipeline, PhantomData)
     }
+
+    #[inline]
+    fn local_size(&self) -> Option<[u32; 3]> {
+        self.inner.local_size
+    }
 }
 
 unsafe impl<T> ComputePipelineAbstract for T
@@ -220,6 +388,11 @@ where
     fn inner(&self) -> ComputePipelineSys {
         (**self).inner()
     }
+
+    #[inline]
+    fn local_size(&self) -> Option<[u32; 3]> {
+        (**self).local_size()
+    }
 }
 
 /// Opaque object that represents the inside of the compute pipeline. Can be made into a trait
@@ -247,6 +420,11 @@ where
         self.layout().sys()
     }
 
+    #[inline]
+    fn set_layouts(&self) -> &[Arc<UnsafeDescriptorSetLayout>] {
+        self.layout().set_layouts()
+    }
+
     #[inline]
     fn descriptor_set_layout(&self, index: usize) -> Option<&Arc<UnsafeDescriptorSetLayout>> {
         self.layout().descriptor_set_layout(index)
@@ -321,6 +499,17 @@ pub enum ComputePipelineCreationError {
     PipelineLayoutCreationError(PipelineLayoutCreationError),
     /// The pipeline layout is not compatible with what the shader expects.
     IncompatiblePipelineLayout(PipelineLayoutNotSupersetError),
+    /// Capturing executable information was requested, but the `pipeline_executable_info`
+    /// feature isn't enabled.
+    PipelineExecutableInfoFeatureNotEnabled,
+    /// Capturing creation feedback was requested, but the `ext_pipeline_creation_feedback`
+    /// extension isn't enabled.
+    PipelineCreationFeedbackExtensionNotEnabled,
+    /// The shader module doesn't contain an entry point with the requested name.
+    EntryPointNotFound,
+    /// The shader module contains an entry point with the requested name, but it isn't a
+    /// compute shader.
+    WrongEntryPointExecutionModel,
 }
 
 impl error::Error for ComputePipelineCreationError {
@@ -330,6 +519,10 @@ impl error::Error for ComputePipelineCreationError {
             ComputePipelineCreationError::OomError(ref err) => Some(err),
             ComputePipelineCreationError::PipelineLayoutCreationError(ref err) => Some(err),
             ComputePipelineCreationError::IncompatiblePipelineLayout(ref err) => Some(err),
+            ComputePipelineCreationError::PipelineExecutableInfoFeatureNotEnabled => None,
+            ComputePipelineCreationError::PipelineCreationFeedbackExtensionNotEnabled => None,
+            ComputePipelineCreationError::EntryPointNotFound => None,
+            ComputePipelineCreationError::WrongEntryPointExecutionModel => None,
         }
     }
 }
@@ -348,6 +541,20 @@ impl fmt::Display for ComputePipelineCreationError {
                 ComputePipelineCreationError::IncompatiblePipelineLayout(_) => {
                     "the pipeline layout is not compatible with what the shader expects"
                 }
+                ComputePipelineCreationError::PipelineExecutableInfoFeatureNotEnabled => {
+                    "capturing executable information requires the `pipeline_executable_info` \
+                     feature"
+                }
+                ComputePipelineCreationError::PipelineCreationFeedbackExtensionNotEnabled => {
+                    "capturing creation feedback requires the `ext_pipeline_creation_feedback` \
+                     extension"
+                }
+                ComputePipelineCreationError::EntryPointNotFound => {
+                    "the shader module doesn't contain an entry point with the requested name"
+                }
+                ComputePipelineCreationError::WrongEntryPointExecutionModel => {
+                    "the entry point isn't a compute shader"
+                }
             }
         )
     }
@@ -528,6 +735,145 @@ mod tests {
 
         let data_buffer =
             CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
+        assert_eq!(pipeline.set_layouts().len(), 1);
+        let layout = pipeline.layout().descriptor_set_layout(0).unwrap();
+        let set = PersistentDescriptorSet::start(layout.clone())
+            .add_buffer(data_buffer.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut cbb =
+            AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+                .unwrap();
+        cbb.dispatch([1, 1, 1], pipeline.clone(), set, ()).unwrap();
+        let cb = cbb.build().unwrap();
+
+        let future = now(device.clone())
+            .then_execute(queue.clone(), cb)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        let data_buffer_content = data_buffer.read().unwrap();
+        assert_eq!(*data_buffer_content, 0x12345678);
+    }
+
+    #[test]
+    fn dynamic_buffer() {
+        // Same as `spec_constants`, but the output buffer is a dynamic storage buffer. The
+        // automatic command buffer builder binds it with an offset of 0.
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let module = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+            layout(set = 0, binding = 0) buffer Output {
+                int write;
+            } write;
+
+            void main() {
+                write.write = VALUE;
+            }
+            */
+            const MODULE: [u8; 480] = [
+                3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 14, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0,
+                0, 11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0,
+                0, 0, 0, 14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 5, 0, 0, 0, 4, 0, 0, 0,
+                109, 97, 105, 110, 0, 0, 0, 0, 16, 0, 6, 0, 4, 0, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 1,
+                0, 0, 0, 1, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1, 0, 0, 5, 0, 4, 0, 4, 0, 0, 0,
+                109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 4, 0, 7, 0, 0, 0, 79, 117, 116, 112, 117, 116,
+                0, 0, 6, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 119, 114, 105, 116, 101, 0, 0, 0, 5, 0,
+                4, 0, 9, 0, 0, 0, 119, 114, 105, 116, 101, 0, 0, 0, 5, 0, 4, 0, 11, 0, 0, 0, 86,
+                65, 76, 85, 69, 0, 0, 0, 72, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 35, 0, 0, 0, 0, 0, 0,
+                0, 71, 0, 3, 0, 7, 0, 0, 0, 3, 0, 0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 34, 0, 0, 0, 0, 0,
+                0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 33, 0, 0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 11, 0, 0, 0,
+                1, 0, 0, 0, 83, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0,
+                0, 21, 0, 4, 0, 6, 0, 0, 0, 32, 0, 0, 0, 1, 0, 0, 0, 30, 0, 3, 0, 7, 0, 0, 0, 6, 0,
+                0, 0, 32, 0, 4, 0, 8, 0, 0, 0, 2, 0, 0, 0, 7, 0, 0, 0, 59, 0, 4, 0, 8, 0, 0, 0, 9,
+                0, 0, 0, 2, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 50, 0, 4, 0,
+                6, 0, 0, 0, 11, 0, 0, 0, 239, 190, 173, 222, 32, 0, 4, 0, 12, 0, 0, 0, 2, 0, 0, 0,
+                6, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2,
+                0, 5, 0, 0, 0, 65, 0, 5, 0, 12, 0, 0, 0, 13, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 62,
+                0, 3, 0, 13, 0, 0, 0, 11, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
+            ];
+            ShaderModule::new(device.clone(), &MODULE).unwrap()
+        };
+
+        let shader = unsafe {
+            #[derive(Debug, Copy, Clone)]
+            struct Layout;
+            unsafe impl PipelineLayoutDesc for Layout {
+                fn num_sets(&self) -> usize {
+                    1
+                }
+                fn num_bindings_in_set(&self, set: usize) -> Option<usize> {
+                    match set {
+                        0 => Some(1),
+                        _ => None,
+                    }
+                }
+                fn descriptor(&self, set: usize, binding: usize) -> Option<DescriptorDesc> {
+                    match (set, binding) {
+                        (0, 0) => Some(DescriptorDesc {
+                            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                dynamic: Some(true),
+                                storage: true,
+                            }),
+                            array_count: 1,
+                            stages: ShaderStages {
+                                compute: true,
+                                ..ShaderStages::none()
+                            },
+                            readonly: true,
+                        }),
+                        _ => None,
+                    }
+                }
+                fn num_push_constants_ranges(&self) -> usize {
+                    0
+                }
+                fn push_constants_range(&self, num: usize) -> Option<PipelineLayoutDescPcRange> {
+                    None
+                }
+            }
+
+            static NAME: [u8; 5] = [109, 97, 105, 110, 0]; // "main"
+            module.compute_entry_point(CStr::from_ptr(NAME.as_ptr() as *const _), Layout)
+        };
+
+        #[derive(Debug, Copy, Clone)]
+        #[allow(non_snake_case)]
+        #[repr(C)]
+        struct SpecConsts {
+            VALUE: i32,
+        }
+        unsafe impl SpecializationConstants for SpecConsts {
+            fn descriptors() -> &'static [SpecializationMapEntry] {
+                static DESCRIPTORS: [SpecializationMapEntry; 1] = [SpecializationMapEntry {
+                    constant_id: 83,
+                    offset: 0,
+                    size: 4,
+                }];
+                &DESCRIPTORS
+            }
+        }
+
+        let pipeline = Arc::new(
+            ComputePipeline::new(device.clone(), &shader, &SpecConsts { VALUE: 0x12345678 })
+                .unwrap(),
+        );
+
+        let data_buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
+        assert_eq!(pipeline.set_layouts().len(), 1);
         let layout = pipeline.layout().descriptor_set_layout(0).unwrap();
         let set = PersistentDescriptorSet::start(layout.clone())
             .add_buffer(data_buffer.clone())
@@ -0,0 +1,480 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Querying the properties of the executables a pipeline was compiled into.
+//!
+//! When a pipeline is created, the Vulkan implementation compiles its shaders into one or more
+//! *executables*. With the `khr_pipeline_executable_properties` extension and the
+//! `pipeline_executable_info` feature, the application can retrieve statistics about these
+//! executables (such as the number of registers or instructions) and their internal
+//! representations (such as the disassembly or an intermediate representation). This is meant
+//! for profiling and tooling.
+//!
+//! To use this, the pipeline must be created with a `PipelineExecutableCapture` that requests the
+//! information, for example with `GraphicsPipelineBuilder::capture_executable_info` or
+//! `ComputePipeline::with_executable_capture`. Then call `executable_properties()` on the
+//! pipeline.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use vulkano::pipeline::ComputePipeline;
+//! # let pipeline: ComputePipeline<()> = return;
+//! for executable in pipeline.executable_properties().unwrap() {
+//!     println!("{}: {}", executable.name, executable.description);
+//!     for statistic in &executable.statistics {
+//!         println!("    {} = {:?}", statistic.name, statistic.value);
+//!     }
+//! }
+//! ```
+
+use std::error;
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::c_char;
+use std::ptr;
+
+use check_errors;
+use descriptor::descriptor::ShaderStages;
+use device::Device;
+use vk;
+use Error;
+use OomError;
+use VulkanObject;
+
+/// Which information about the executables of a pipeline must be captured when the pipeline is
+/// created.
+///
+/// Capturing information may slow down the creation of the pipeline, therefore you should only
+/// enable it when profiling.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PipelineExecutableCapture {
+    /// Capture the statistics of each executable.
+    pub statistics: bool,
+    /// Capture the internal representations of each executable.
+    pub internal_representations: bool,
+}
+
+impl PipelineExecutableCapture {
+    /// Builds a `PipelineExecutableCapture` with all values set to false.
+    #[inline]
+    pub fn none() -> PipelineExecutableCapture {
+        PipelineExecutableCapture {
+            statistics: false,
+            internal_representations: false,
+        }
+    }
+
+    /// Builds a `PipelineExecutableCapture` with all values set to true.
+    #[inline]
+    pub fn all() -> PipelineExecutableCapture {
+        PipelineExecutableCapture {
+            statistics: true,
+            internal_representations: true,
+        }
+    }
+
+    /// Returns true if nothing is captured.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        !self.statistics && !self.internal_representations
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::PipelineCreateFlags {
+        let mut result = 0;
+        if self.statistics {
+            result |= vk::PIPELINE_CREATE_CAPTURE_STATISTICS_BIT_KHR;
+        }
+        if self.internal_representations {
+            result |= vk::PIPELINE_CREATE_CAPTURE_INTERNAL_REPRESENTATIONS_BIT_KHR;
+        }
+        result
+    }
+}
+
+/// Properties of one of the executables of a pipeline.
+#[derive(Debug, Clone)]
+pub struct PipelineExecutableProperties {
+    /// The shader stages that were compiled into this executable.
+    pub stages: ShaderStages,
+    /// Short name of the executable, chosen by the implementation.
+    pub name: String,
+    /// Human-readable description of the executable.
+    pub description: String,
+    /// Subgroup size the executable was compiled with, or 0 if not applicable.
+    pub subgroup_size: u32,
+    /// Statistics of the executable. Empty if the statistics weren't captured.
+    pub statistics: Vec<PipelineExecutableStatistic>,
+    /// Internal representations of the executable. Empty if they weren't captured.
+    pub internal_representations: Vec<PipelineExecutableInternalRepresentation>,
+}
+
+/// A statistic about an executable, such as the number of registers it uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineExecutableStatistic {
+    /// Short name of the statistic.
+    pub name: String,
+    /// Human-readable description of the statistic.
+    pub description: String,
+    /// Value of the statistic.
+    pub value: PipelineExecutableStatisticValue,
+}
+
+/// Value of a `PipelineExecutableStatistic`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PipelineExecutableStatisticValue {
+    Bool(bool),
+    Int(i64),
+    Uint(u64),
+    Float(f64),
+}
+
+/// An internal representation of an executable, such as its disassembly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineExecutableInternalRepresentation {
+    /// Short name of the representation.
+    pub name: String,
+    /// Human-readable description of the representation.
+    pub description: String,
+    /// True if `data` is text. Text data is null-terminated.
+    pub is_text: bool,
+    /// The content of the representation.
+    pub data: Vec<u8>,
+}
+
+impl PipelineExecutableInternalRepresentation {
+    /// Returns the content as a string, if it is text.
+    #[inline]
+    pub fn text(&self) -> Option<String> {
+        if !self.is_text {
+            return None;
+        }
+
+        let end = self
+            .data
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(self.data.len());
+        Some(String::from_utf8_lossy(&self.data[..end]).into_owned())
+    }
+}
+
+/// Queries the properties of the executables of `pipeline`.
+///
+/// `capture` must be the value the pipeline was created with.
+pub(crate) fn executable_properties(
+    device: &Device,
+    pipeline: vk::Pipeline,
+    capture: PipelineExecutableCapture,
+) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutablePropertiesError> {
+    if !device
+        .loaded_extensions()
+        .khr_pipeline_executable_properties
+    {
+        return Err(PipelineExecutablePropertiesError::ExtensionNotEnabled);
+    }
+
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(PipelineExecutablePropertiesError::FeatureNotEnabled);
+    }
+
+    if capture.is_none() {
+        return Err(PipelineExecutablePropertiesError::NotCaptured);
+    }
+
+    unsafe {
+        let vk = device.pointers();
+
+        let pipeline_info = vk::PipelineInfoKHR {
+            sType: vk::STRUCTURE_TYPE_PIPELINE_INFO_KHR,
+            pNext: ptr::null(),
+            pipeline,
+        };
+
+        let mut num = 0;
+        check_errors(vk.GetPipelineExecutablePropertiesKHR(
+            device.internal_object(),
+            &pipeline_info,
+            &mut num,
+            ptr::null_mut(),
+        ))?;
+
+        let mut properties: Vec<vk::PipelineExecutablePropertiesKHR> = (0..num)
+            .map(|_| vk::PipelineExecutablePropertiesKHR {
+                sType: vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_PROPERTIES_KHR,
+                pNext: ptr::null_mut(),
+                stages: 0,
+                name: [0; vk::MAX_DESCRIPTION_SIZE as usize],
+                description: [0; vk::MAX_DESCRIPTION_SIZE as usize],
+                subgroupSize: 0,
+            })
+            .collect();
+        check_errors(vk.GetPipelineExecutablePropertiesKHR(
+            device.internal_object(),
+            &pipeline_info,
+            &mut num,
+            properties.as_mut_ptr(),
+        ))?;
+        properties.truncate(num as usize);
+
+        properties
+            .into_iter()
+            .enumerate()
+            .map(|(index, properties)| {
+                let executable_info = vk::PipelineExecutableInfoKHR {
+                    sType: vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_INFO_KHR,
+                    pNext: ptr::null(),
+                    pipeline,
+                    executableIndex: index as u32,
+                };
+
+                let statistics = if capture.statistics {
+                    statistics(device, &executable_info)?
+                } else {
+                    Vec::new()
+                };
+
+                let internal_representations = if capture.internal_representations {
+                    internal_representations(device, &executable_info)?
+                } else {
+                    Vec::new()
+                };
+
+                Ok(PipelineExecutableProperties {
+                    stages: ShaderStages::from_vulkan_bits(properties.stages),
+                    name: string_from_array(&properties.name),
+                    description: string_from_array(&properties.description),
+                    subgroup_size: properties.subgroupSize,
+                    statistics,
+                    internal_representations,
+                })
+            })
+            .collect()
+    }
+}
+
+unsafe fn statistics(
+    device: &Device,
+    executable_info: &vk::PipelineExecutableInfoKHR,
+) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutablePropertiesError> {
+    let vk = device.pointers();
+
+    let mut num = 0;
+    check_errors(vk.GetPipelineExecutableStatisticsKHR(
+        device.internal_object(),
+        executable_info,
+        &mut num,
+        ptr::null_mut(),
+    ))?;
+
+    let mut statistics: Vec<vk::PipelineExecutableStatisticKHR> = (0..num)
+        .map(|_| vk::PipelineExecutableStatisticKHR {
+            sType: vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_STATISTIC_KHR,
+            pNext: ptr::null_mut(),
+            name: [0; vk::MAX_DESCRIPTION_SIZE as usize],
+            description: [0; vk::MAX_DESCRIPTION_SIZE as usize],
+            format: 0,
+            value: vk::PipelineExecutableStatisticValueKHR { u64: 0 },
+        })
+        .collect();
+    check_errors(vk.GetPipelineExecutableStatisticsKHR(
+        device.internal_object(),
+        executable_info,
+        &mut num,
+        statistics.as_mut_ptr(),
+    ))?;
+    statistics.truncate(num as usize);
+
+    Ok(statistics
+        .into_iter()
+        .map(|statistic| PipelineExecutableStatistic {
+            name: string_from_array(&statistic.name),
+            description: string_from_array(&statistic.description),
+            value: match statistic.format {
+                vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_BOOL32_KHR => {
+                    PipelineExecutableStatisticValue::Bool(statistic.value.b32 != 0)
+                }
+                vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_INT64_KHR => {
+                    PipelineExecutableStatisticValue::Int(statistic.value.i64)
+                }
+                vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_UINT64_KHR => {
+                    PipelineExecutableStatisticValue::Uint(statistic.value.u64)
+                }
+                vk::PIPELINE_EXECUTABLE_STATISTIC_FORMAT_FLOAT64_KHR => {
+                    PipelineExecutableStatisticValue::Float(statistic.value.f64)
+                }
+                _ => panic!("unexpected statistic format: {}", statistic.format),
+            },
+        })
+        .collect())
+}
+
+unsafe fn internal_representations(
+    device: &Device,
+    executable_info: &vk::PipelineExecutableInfoKHR,
+) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutablePropertiesError> {
+    let vk = device.pointers();
+
+    let empty = || vk::PipelineExecutableInternalRepresentationKHR {
+        sType: vk::STRUCTURE_TYPE_PIPELINE_EXECUTABLE_INTERNAL_REPRESENTATION_KHR,
+        pNext: ptr::null_mut(),
+        name: [0; vk::MAX_DESCRIPTION_SIZE as usize],
+        description: [0; vk::MAX_DESCRIPTION_SIZE as usize],
+        isText: vk::FALSE,
+        dataSize: 0,
+        pData: ptr::null_mut(),
+    };
+
+    // The first call returns the number of representations, the second one their sizes, and the
+    // third one their content.
+    let mut num = 0;
+    check_errors(vk.GetPipelineExecutableInternalRepresentationsKHR(
+        device.internal_object(),
+        executable_info,
+        &mut num,
+        ptr::null_mut(),
+    ))?;
+
+    let mut representations: Vec<_> = (0..num).map(|_| empty()).collect();
+    check_errors(vk.GetPipelineExecutableInternalRepresentationsKHR(
+        device.internal_object(),
+        executable_info,
+        &mut num,
+        representations.as_mut_ptr(),
+    ))?;
+    representations.truncate(num as usize);
+
+    let mut data: Vec<Vec<u8>> = representations
+        .iter()
+        .map(|representation| vec![0; representation.dataSize])
+        .collect();
+    for (representation, data) in representations.iter_mut().zip(data.iter_mut()) {
+        representation.pData = data.as_mut_ptr() as *mut _;
+    }
+    check_errors(vk.GetPipelineExecutableInternalRepresentationsKHR(
+        device.internal_object(),
+        executable_info,
+        &mut num,
+        representations.as_mut_ptr(),
+    ))?;
+
+    Ok(representations
+        .into_iter()
+        .zip(data.into_iter())
+        .map(
+            |(representation, mut data)| PipelineExecutableInternalRepresentation {
+                name: string_from_array(&representation.name),
+                description: string_from_array(&representation.description),
+                is_text: representation.isText != 0,
+                data: {
+                    data.truncate(representation.dataSize);
+                    data
+                },
+            },
+        )
+        .collect())
+}
+
+fn string_from_array(array: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(array.as_ptr()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Error that can happen when querying the properties of the executables of a pipeline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PipelineExecutablePropertiesError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The `khr_pipeline_executable_properties` extension isn't enabled on the device.
+    ExtensionNotEnabled,
+    /// The `pipeline_executable_info` feature isn't enabled on the device.
+    FeatureNotEnabled,
+    /// The pipeline wasn't created with a `PipelineExecutableCapture` that captures anything.
+    NotCaptured,
+}
+
+impl error::Error for PipelineExecutablePropertiesError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            PipelineExecutablePropertiesError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PipelineExecutablePropertiesError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                PipelineExecutablePropertiesError::OomError(_) => "not enough memory available",
+                PipelineExecutablePropertiesError::ExtensionNotEnabled => {
+                    "the `khr_pipeline_executable_properties` extension isn't enabled"
+                }
+                PipelineExecutablePropertiesError::FeatureNotEnabled => {
+                    "the `pipeline_executable_info` feature isn't enabled"
+                }
+                PipelineExecutablePropertiesError::NotCaptured => {
+                    "the pipeline wasn't created with the capture of its executable properties"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for PipelineExecutablePropertiesError {
+    #[inline]
+    fn from(err: OomError) -> PipelineExecutablePropertiesError {
+        PipelineExecutablePropertiesError::OomError(err)
+    }
+}
+
+impl From<Error> for PipelineExecutablePropertiesError {
+    #[inline]
+    fn from(err: Error) -> PipelineExecutablePropertiesError {
+        match err {
+            err @ Error::OutOfHostMemory => {
+                PipelineExecutablePropertiesError::OomError(OomError::from(err))
+            }
+            err @ Error::OutOfDeviceMemory => {
+                PipelineExecutablePropertiesError::OomError(OomError::from(err))
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pipeline::executable::PipelineExecutableCapture;
+    use pipeline::executable::PipelineExecutableInternalRepresentation;
+
+    #[test]
+    fn capture_flags() {
+        assert_eq!(PipelineExecutableCapture::none().into_vulkan_bits(), 0);
+        assert!(PipelineExecutableCapture::none().is_none());
+        assert!(!PipelineExecutableCapture::all().is_none());
+    }
+
+    #[test]
+    fn internal_representation_text() {
+        let representation = PipelineExecutableInternalRepresentation {
+            name: "disassembly".to_owned(),
+            description: String::new(),
+            is_text: true,
+            data: b"mov r0, r1\0".to_vec(),
+        };
+        assert_eq!(representation.text().unwrap(), "mov r0, r1");
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Feedback about the creation of a pipeline.
+//!
+//! With the `ext_pipeline_creation_feedback` extension, the Vulkan implementation can report how
+//! long it took to create a pipeline and each of its shader stages, and whether the pipeline was
+//! found in the pipeline cache. This is useful to find out which pipelines cause stutter when they
+//! are created, and whether the pipeline cache is effective.
+//!
+//! To use this, enable the extension on the device and create the pipeline with
+//! `GraphicsPipelineBuilder::capture_creation_feedback` or
+//! `ComputePipeline::with_creation_feedback`. Then call `creation_feedback()` on the pipeline.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use vulkano::pipeline::ComputePipeline;
+//! # let pipeline: ComputePipeline<()> = return;
+//! if let Some(feedback) = pipeline.creation_feedback() {
+//!     println!(
+//!         "created in {:?}, cache hit: {}",
+//!         feedback.pipeline.duration, feedback.pipeline.application_pipeline_cache_hit
+//!     );
+//! }
+//! ```
+
+use std::ptr;
+use std::time::Duration;
+
+use descriptor::descriptor::ShaderStages;
+use vk;
+
+/// Feedback about the creation of a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineCreationFeedback {
+    /// Feedback about the pipeline as a whole.
+    pub pipeline: CreationFeedback,
+    /// Feedback about each shader stage of the pipeline, in the order in which the stages were
+    /// passed to the implementation.
+    pub stages: Vec<(ShaderStages, CreationFeedback)>,
+}
+
+/// Feedback about the creation of a pipeline or of one of its shader stages.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CreationFeedback {
+    /// True if the implementation provided the feedback. If false, the other members are
+    /// meaningless.
+    pub valid: bool,
+    /// True if the pipeline or stage was found in the pipeline cache passed at creation, and
+    /// didn't have to be compiled.
+    pub application_pipeline_cache_hit: bool,
+    /// True if the base pipeline passed at creation was used to speed up the creation.
+    pub base_pipeline_acceleration: bool,
+    /// Time spent creating the pipeline or stage.
+    pub duration: Duration,
+}
+
+impl CreationFeedback {
+    #[inline]
+    fn from_vulkan(feedback: &vk::PipelineCreationFeedback) -> CreationFeedback {
+        CreationFeedback {
+            valid: (feedback.flags & vk::PIPELINE_CREATION_FEEDBACK_VALID_BIT) != 0,
+            application_pipeline_cache_hit: (feedback.flags
+                & vk::PIPELINE_CREATION_FEEDBACK_APPLICATION_PIPELINE_CACHE_HIT_BIT)
+                != 0,
+            base_pipeline_acceleration: (feedback.flags
+                & vk::PIPELINE_CREATION_FEEDBACK_BASE_PIPELINE_ACCELERATION_BIT)
+                != 0,
+            duration: Duration::from_nanos(feedback.duration),
+        }
+    }
+}
+
+// Storage that the implementation writes the feedback to during the creation of a pipeline.
+pub(crate) struct CreationFeedbackStorage {
+    pipeline: vk::PipelineCreationFeedback,
+    stages: Vec<vk::PipelineCreationFeedback>,
+}
+
+impl CreationFeedbackStorage {
+    #[inline]
+    pub(crate) fn new(num_stages: usize) -> CreationFeedbackStorage {
+        let empty = vk::PipelineCreationFeedback {
+            flags: 0,
+            duration: 0,
+        };
+
+        CreationFeedbackStorage {
+            pipeline: empty,
+            stages: vec![empty; num_stages],
+        }
+    }
+
+    // Returns the struct to chain to the pipeline create info. It points to `self`, which must
+    // therefore not be moved until the pipeline has been created.
+    #[inline]
+    pub(crate) fn create_info(&mut self) -> vk::PipelineCreationFeedbackCreateInfo {
+        vk::PipelineCreationFeedbackCreateInfo {
+            sType: vk::STRUCTURE_TYPE_PIPELINE_CREATION_FEEDBACK_CREATE_INFO,
+            pNext: ptr::null(),
+            pPipelineCreationFeedback: &mut self.pipeline,
+            pipelineStageCreationFeedbackCount: self.stages.len() as u32,
+            pPipelineStageCreationFeedbacks: self.stages.as_mut_ptr(),
+        }
+    }
+
+    // Turns the written feedback into a `PipelineCreationFeedback`. `stages` are the stages that
+    // were passed to the implementation, in the same order.
+    #[inline]
+    pub(crate) fn into_feedback<I>(self, stages: I) -> PipelineCreationFeedback
+    where
+        I: IntoIterator<Item = vk::ShaderStageFlags>,
+    {
+        PipelineCreationFeedback {
+            pipeline: CreationFeedback::from_vulkan(&self.pipeline),
+            stages: stages
+                .into_iter()
+                .zip(self.stages.iter())
+                .map(|(stage, feedback)| {
+                    (
+                        ShaderStages::from_vulkan_bits(stage),
+                        CreationFeedback::from_vulkan(feedback),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::ShaderStages;
+    use pipeline::feedback::CreationFeedbackStorage;
+    use std::time::Duration;
+    use vk;
+
+    #[test]
+    fn into_feedback() {
+        let mut storage = CreationFeedbackStorage::new(1);
+        storage.pipeline.flags = vk::PIPELINE_CREATION_FEEDBACK_VALID_BIT
+            | vk::PIPELINE_CREATION_FEEDBACK_APPLICATION_PIPELINE_CACHE_HIT_BIT;
+        storage.pipeline.duration = 1500;
+        storage.stages[0].flags = vk::PIPELINE_CREATION_FEEDBACK_VALID_BIT;
+
+        let feedback = storage.into_feedback(Some(vk::SHADER_STAGE_COMPUTE_BIT));
+        assert!(feedback.pipeline.valid);
+        assert!(feedback.pipeline.application_pipeline_cache_hit);
+        assert!(!feedback.pipeline.base_pipeline_acceleration);
+        assert_eq!(feedback.pipeline.duration, Duration::from_nanos(1500));
+
+        assert_eq!(feedback.stages.len(), 1);
+        assert_eq!(feedback.stages[0].0, ShaderStages::compute());
+        assert!(feedback.stages[0].1.valid);
+    }
+}
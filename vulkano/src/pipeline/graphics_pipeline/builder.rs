@@ -12,12 +12,14 @@
 #![allow(deprecated)]
 
 use smallvec::SmallVec;
+use std::cmp;
 use std::mem;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
 use std::u32;
 
+use descriptor::descriptor::ShaderStages;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use device::Device;
 use framebuffer::RenderPassAbstract;
@@ -29,6 +31,8 @@ use pipeline::blend::LogicOp;
 use pipeline::depth_stencil::Compare;
 use pipeline::depth_stencil::DepthBounds;
 use pipeline::depth_stencil::DepthStencil;
+use pipeline::executable::PipelineExecutableCapture;
+use pipeline::feedback::CreationFeedbackStorage;
 use pipeline::graphics_pipeline::GraphicsPipeline;
 use pipeline::graphics_pipeline::GraphicsPipelineCreationError;
 use pipeline::graphics_pipeline::Inner as GraphicsPipelineInner;
@@ -39,6 +43,9 @@ use pipeline::raster::FrontFace;
 use pipeline::raster::PolygonMode;
 use pipeline::raster::Rasterization;
 use pipeline::shader::EmptyEntryPointDummy;
+use pipeline::shader::EntryPointAbstract;
+use pipeline::shader::EntryPointError;
+use pipeline::shader::ExecutionModel;
 use pipeline::shader::GraphicsEntryPointAbstract;
 use pipeline::shader::GraphicsShaderType;
 use pipeline::shader::ShaderInterfaceDefMatch;
@@ -76,6 +83,32 @@ pub struct GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss,
     depth_stencil: DepthStencil,
     blend: Blend,
     render_pass: Option<Subpass<Rp>>,
+    executable_capture: PipelineExecutableCapture,
+    creation_feedback: bool,
+    extended_dynamic: ExtendedDynamicStates,
+}
+
+// States of `VK_EXT_extended_dynamic_state` that are set when recording a command buffer.
+#[derive(Copy, Clone, Default)]
+struct ExtendedDynamicStates {
+    vertex_input_binding_stride: bool,
+    cull_mode: bool,
+    front_face: bool,
+    primitive_topology: bool,
+    depth_test_enable: bool,
+    depth_write_enable: bool,
+}
+
+impl ExtendedDynamicStates {
+    #[inline]
+    fn is_none(&self) -> bool {
+        !(self.vertex_input_binding_stride
+            || self.cull_mode
+            || self.front_face
+            || self.primitive_topology
+            || self.depth_test_enable
+            || self.depth_write_enable)
+    }
 }
 
 // Additional parameters if tessellation is used.
@@ -125,6 +158,9 @@ impl
                 depth_stencil: DepthStencil::disabled(),
                 blend: Blend::pass_through(),
                 render_pass: None,
+                executable_capture: PipelineExecutableCapture::none(),
+                creation_feedback: false,
+                extended_dynamic: ExtendedDynamicStates::default(),
             }
         }
     }
@@ -515,6 +551,10 @@ where
                 GraphicsShaderType::Vertex => {}
                 _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
             };
+            check_entry_point(
+                &self.vertex_shader.as_ref().unwrap().0,
+                ExecutionModel::Vertex,
+            )?;
 
             stages.push(vk::PipelineShaderStageCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
@@ -536,6 +576,10 @@ where
                 GraphicsShaderType::Fragment => {}
                 _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
             };
+            check_entry_point(
+                &self.fragment_shader.as_ref().unwrap().0,
+                ExecutionModel::Fragment,
+            )?;
 
             stages.push(vk::PipelineShaderStageCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
@@ -558,6 +602,8 @@ where
                     return Err(GraphicsPipelineCreationError::GeometryShaderFeatureNotEnabled);
                 }
 
+                check_entry_point(&gs.0, ExecutionModel::Geometry)?;
+
                 stages.push(vk::PipelineShaderStageCreateInfo {
                     sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
                     pNext: ptr::null(),
@@ -587,6 +633,15 @@ where
                     _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                 };
 
+                check_entry_point(
+                    &tess.tessellation_control_shader.0,
+                    ExecutionModel::TessellationControl,
+                )?;
+                check_entry_point(
+                    &tess.tessellation_evaluation_shader.0,
+                    ExecutionModel::TessellationEvaluation,
+                )?;
+
                 stages.push(vk::PipelineShaderStageCreateInfo {
                     sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
                     pNext: ptr::null(),
@@ -622,7 +677,7 @@ where
         };
 
         // Vertex bindings.
-        let (binding_descriptions, attribute_descriptions) = {
+        let (binding_descriptions, attribute_descriptions, vertex_attribute_extents) = {
             let (buffers_iter, attribs_iter) = self
                 .vertex_input
                 .definition(self.vertex_shader.as_ref().unwrap().0.input())?;
@@ -656,6 +711,7 @@ where
             }
 
             let mut attribute_descriptions = SmallVec::<[_; 8]>::new();
+            let mut vertex_attribute_extents = SmallVec::<[u32; 8]>::new();
             for (loc, binding, info) in attribs_iter {
                 // TODO: check attribute format support
 
@@ -688,9 +744,20 @@ where
                     format: info.format as u32,
                     offset: info.offset as u32,
                 });
+
+                let extent = (info.offset + info.format.size().unwrap_or(0)) as u32;
+                if vertex_attribute_extents.len() <= binding as usize {
+                    vertex_attribute_extents.resize(binding as usize + 1, 0);
+                }
+                let binding_extent = &mut vertex_attribute_extents[binding as usize];
+                *binding_extent = cmp::max(*binding_extent, extent);
             }
 
-            (binding_descriptions, attribute_descriptions)
+            (
+                binding_descriptions,
+                attribute_descriptions,
+                vertex_attribute_extents,
+            )
         };
 
         if binding_descriptions.len()
@@ -1129,6 +1196,9 @@ where
                 if !device.enabled_features().logic_op {
                     return Err(GraphicsPipelineCreationError::LogicOpFeatureNotEnabled);
                 }
+                if blend_atch.iter().any(|atch| atch.blendEnable != vk::FALSE) {
+                    return Err(GraphicsPipelineCreationError::LogicOpWithBlending);
+                }
                 vk::TRUE
             } else {
                 vk::FALSE
@@ -1144,6 +1214,29 @@ where
             },
         };
 
+        if !self.extended_dynamic.is_none() && !device.enabled_features().extended_dynamic_state {
+            return Err(GraphicsPipelineCreationError::ExtendedDynamicStateFeatureNotEnabled);
+        }
+
+        if self.extended_dynamic.vertex_input_binding_stride {
+            dynamic_states.push(vk::DYNAMIC_STATE_VERTEX_INPUT_BINDING_STRIDE_EXT);
+        }
+        if self.extended_dynamic.cull_mode {
+            dynamic_states.push(vk::DYNAMIC_STATE_CULL_MODE_EXT);
+        }
+        if self.extended_dynamic.front_face {
+            dynamic_states.push(vk::DYNAMIC_STATE_FRONT_FACE_EXT);
+        }
+        if self.extended_dynamic.primitive_topology {
+            dynamic_states.push(vk::DYNAMIC_STATE_PRIMITIVE_TOPOLOGY_EXT);
+        }
+        if self.extended_dynamic.depth_test_enable {
+            dynamic_states.push(vk::DYNAMIC_STATE_DEPTH_TEST_ENABLE_EXT);
+        }
+        if self.extended_dynamic.depth_write_enable {
+            dynamic_states.push(vk::DYNAMIC_STATE_DEPTH_WRITE_ENABLE_EXT);
+        }
+
         let dynamic_states = if !dynamic_states.is_empty() {
             Some(vk::PipelineDynamicStateCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_DYNAMIC_STATE_CREATE_INFO,
@@ -1156,11 +1249,31 @@ where
             None
         };
 
+        if !self.executable_capture.is_none() && !device.enabled_features().pipeline_executable_info
+        {
+            return Err(GraphicsPipelineCreationError::PipelineExecutableInfoFeatureNotEnabled);
+        }
+
+        if self.creation_feedback && !device.loaded_extensions().ext_pipeline_creation_feedback {
+            return Err(GraphicsPipelineCreationError::PipelineCreationFeedbackExtensionNotEnabled);
+        }
+
+        let mut feedback_storage = if self.creation_feedback {
+            Some(CreationFeedbackStorage::new(stages.len()))
+        } else {
+            None
+        };
+
         let pipeline = unsafe {
+            let feedback_infos = feedback_storage.as_mut().map(|f| f.create_info());
+
             let infos = vk::GraphicsPipelineCreateInfo {
                 sType: vk::STRUCTURE_TYPE_GRAPHICS_PIPELINE_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0, // TODO: some flags are available but none are critical
+                pNext: feedback_infos
+                    .as_ref()
+                    .map(|f| f as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
+                flags: self.executable_capture.into_vulkan_bits(),
                 stageCount: stages.len() as u32,
                 pStages: stages.as_ptr(),
                 pVertexInputState: &vertex_input_state,
@@ -1212,12 +1325,21 @@ where
 
         let (render_pass, render_pass_subpass) = self.render_pass.take().unwrap().into();
 
+        let shader_stages =
+            ShaderStages::from_vulkan_bits(stages.iter().fold(0, |bits, stage| bits | stage.stage));
+        let unused_descriptor_stages = pipeline_layout.unused_descriptor_stages(shader_stages);
+
         Ok(GraphicsPipeline {
             inner: GraphicsPipelineInner {
                 device: device.clone(),
                 pipeline: pipeline,
+                capture: self.executable_capture,
             },
+            creation_feedback: feedback_storage
+                .map(|f| f.into_feedback(stages.iter().map(|stage| stage.stage))),
             layout: pipeline_layout,
+            shader_stages,
+            unused_descriptor_stages,
 
             vertex_definition: self.vertex_input,
 
@@ -1233,6 +1355,16 @@ where
             dynamic_stencil_write_mask: self.depth_stencil.stencil_back.write_mask.is_none(),
             dynamic_stencil_reference: self.depth_stencil.stencil_back.reference.is_none(),
             dynamic_blend_constants: self.blend.blend_constants.is_none(),
+            dynamic_vertex_input_binding_stride: self.extended_dynamic.vertex_input_binding_stride,
+            dynamic_cull_mode: self.extended_dynamic.cull_mode,
+            dynamic_front_face: self.extended_dynamic.front_face,
+            dynamic_primitive_topology: self.extended_dynamic.primitive_topology,
+            dynamic_depth_test_enable: self.extended_dynamic.depth_test_enable,
+            dynamic_depth_write_enable: self.extended_dynamic.depth_write_enable,
+
+            topology: self.input_assembly_topology,
+
+            vertex_attribute_extents,
 
             num_viewports: self.viewport.as_ref().unwrap().num_viewports(),
         })
@@ -1266,6 +1398,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            executable_capture: self.executable_capture,
+            creation_feedback: self.creation_feedback,
+            extended_dynamic: self.extended_dynamic,
         }
     }
 
@@ -1319,6 +1454,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            executable_capture: self.executable_capture,
+            creation_feedback: self.creation_feedback,
+            extended_dynamic: self.extended_dynamic,
         }
     }
 
@@ -1477,6 +1615,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            executable_capture: self.executable_capture,
+            creation_feedback: self.creation_feedback,
+            extended_dynamic: self.extended_dynamic,
         }
     }
 
@@ -1513,6 +1654,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            executable_capture: self.executable_capture,
+            creation_feedback: self.creation_feedback,
+            extended_dynamic: self.extended_dynamic,
         }
     }
 
@@ -1795,6 +1939,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            executable_capture: self.executable_capture,
+            creation_feedback: self.creation_feedback,
+            extended_dynamic: self.extended_dynamic,
         }
     }
 
@@ -1861,9 +2008,23 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self.blend_collective(AttachmentBlend::alpha_blending())
     }
 
+    /// Enables a logic operation between the output of the fragment shader and the content of
+    /// the attachments. The `logic_op` feature must be enabled on the device.
+    ///
+    /// The logic operation replaces blending, therefore this function also disables blending for
+    /// all the attachments. The write masks are kept. Enabling blending again afterwards results
+    /// in an error when building the pipeline.
     #[inline]
     pub fn blend_logic_op(mut self, logic_op: LogicOp) -> Self {
         self.blend.logic_op = Some(logic_op);
+        match self.blend.attachments {
+            AttachmentsBlend::Collective(ref mut blend) => blend.enabled = false,
+            AttachmentsBlend::Individual(ref mut blend) => {
+                for blend in blend.iter_mut() {
+                    blend.enabled = false;
+                }
+            }
+        }
         self
     }
 
@@ -1892,6 +2053,96 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
+    /// Sets the strides of the vertex buffers to be dynamic. The strides of the vertex
+    /// definition are then ignored, and must be passed to `bind_vertex_buffers_with_stride` when
+    /// recording a command buffer.
+    ///
+    /// This allows using the same pipeline with vertex buffers of different layouts.
+    ///
+    /// Requires the `extended_dynamic_state` feature.
+    #[inline]
+    pub fn vertex_input_binding_stride_dynamic(mut self) -> Self {
+        self.extended_dynamic.vertex_input_binding_stride = true;
+        self
+    }
+
+    /// Sets the cull mode to be dynamic. The cull mode of the rasterization state is then
+    /// ignored, and must be set with `set_cull_mode` before drawing with this pipeline.
+    ///
+    /// Requires the `extended_dynamic_state` feature.
+    #[inline]
+    pub fn cull_mode_dynamic(mut self) -> Self {
+        self.extended_dynamic.cull_mode = true;
+        self
+    }
+
+    /// Sets the front face to be dynamic. The front face of the rasterization state is then
+    /// ignored, and must be set with `set_front_face` before drawing with this pipeline.
+    ///
+    /// Requires the `extended_dynamic_state` feature.
+    #[inline]
+    pub fn front_face_dynamic(mut self) -> Self {
+        self.extended_dynamic.front_face = true;
+        self
+    }
+
+    /// Sets the primitive topology to be dynamic. It must be set with `set_primitive_topology`
+    /// before drawing with this pipeline.
+    ///
+    /// The topology of the input assembly state still determines the topology class (points,
+    /// lines, triangles or patches) of the pipeline, and the topology that is set when drawing
+    /// must belong to the same class.
+    ///
+    /// Requires the `extended_dynamic_state` feature.
+    #[inline]
+    pub fn primitive_topology_dynamic(mut self) -> Self {
+        self.extended_dynamic.primitive_topology = true;
+        self
+    }
+
+    /// Sets whether the depth test is enabled to be dynamic. It must be set with
+    /// `set_depth_test_enable` before drawing with this pipeline. The other parameters of the
+    /// depth test still come from the depth-stencil state.
+    ///
+    /// Requires the `extended_dynamic_state` feature.
+    #[inline]
+    pub fn depth_test_enable_dynamic(mut self) -> Self {
+        self.extended_dynamic.depth_test_enable = true;
+        self
+    }
+
+    /// Sets whether the depth writes are enabled to be dynamic. It must be set with
+    /// `set_depth_write_enable` before drawing with this pipeline.
+    ///
+    /// Requires the `extended_dynamic_state` feature.
+    #[inline]
+    pub fn depth_write_enable_dynamic(mut self) -> Self {
+        self.extended_dynamic.depth_write_enable = true;
+        self
+    }
+
+    /// Sets which information about the executables of the pipeline is captured when it is
+    /// built. The information can then be retrieved with `executable_properties()`.
+    ///
+    /// Capturing anything requires the `pipeline_executable_info` feature. The default value is
+    /// `PipelineExecutableCapture::none()`.
+    #[inline]
+    pub fn capture_executable_info(mut self, capture: PipelineExecutableCapture) -> Self {
+        self.executable_capture = capture;
+        self
+    }
+
+    /// Sets whether feedback about the creation of the pipeline is captured when it is built.
+    /// The feedback can then be retrieved with `creation_feedback()`.
+    ///
+    /// Capturing the feedback requires the `ext_pipeline_creation_feedback` extension. The
+    /// default value is `false`.
+    #[inline]
+    pub fn capture_creation_feedback(mut self, capture: bool) -> Self {
+        self.creation_feedback = capture;
+        self
+    }
+
     /// Sets the render pass subpass to use.
     #[inline]
     pub fn render_pass<Rp2>(
@@ -1912,6 +2163,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: Some(subpass),
+            executable_capture: self.executable_capture,
+            creation_feedback: self.creation_feedback,
+            extended_dynamic: self.extended_dynamic,
         }
     }
 }
@@ -1957,6 +2211,26 @@ where
             depth_stencil: self.depth_stencil.clone(),
             blend: self.blend.clone(),
             render_pass: self.render_pass.clone(),
+            executable_capture: self.executable_capture,
+            creation_feedback: self.creation_feedback,
+            extended_dynamic: self.extended_dynamic,
+        }
+    }
+}
+
+// Checks that the module of `shader` contains its entry point with the execution model `model`.
+fn check_entry_point<S>(
+    shader: &S,
+    model: ExecutionModel,
+) -> Result<(), GraphicsPipelineCreationError>
+where
+    S: EntryPointAbstract,
+{
+    match shader.module().check_entry_point(shader.name(), model) {
+        Ok(()) => Ok(()),
+        Err(EntryPointError::NotFound) => Err(GraphicsPipelineCreationError::EntryPointNotFound),
+        Err(EntryPointError::WrongExecutionModel) => {
+            Err(GraphicsPipelineCreationError::WrongEntryPointExecutionModel)
         }
     }
 }
@@ -157,6 +157,22 @@ pub enum GraphicsPipelineCreationError {
     /// The `logic_op` feature must be enabled in order to use logic operations.
     LogicOpFeatureNotEnabled,
 
+    /// A logic operation is enabled, but blending is enabled for one of the attachments. Blending
+    /// is ignored when a logic operation is used, therefore it must be disabled.
+    LogicOpWithBlending,
+
+    /// The `pipeline_executable_info` feature must be enabled in order to capture information
+    /// about the executables of the pipeline.
+    PipelineExecutableInfoFeatureNotEnabled,
+
+    /// The `ext_pipeline_creation_feedback` extension must be enabled in order to capture
+    /// feedback about the creation of the pipeline.
+    PipelineCreationFeedbackExtensionNotEnabled,
+
+    /// The `extended_dynamic_state` feature must be enabled in order to make the states of
+    /// `VK_EXT_extended_dynamic_state` dynamic.
+    ExtendedDynamicStateFeatureNotEnabled,
+
     /// The depth test requires a depth attachment but render pass has no depth attachment, or
     /// depth writing is enabled and the depth attachment is read-only.
     NoDepthAttachment,
@@ -177,6 +193,13 @@ pub enum GraphicsPipelineCreationError {
     /// For example you passed a vertex shader as the fragment shader.
     WrongShaderType,
 
+    /// The shader module of a stage doesn't contain an entry point with the requested name.
+    EntryPointNotFound,
+
+    /// The shader module of a stage contains an entry point with the requested name, but it is
+    /// meant for a different stage.
+    WrongEntryPointExecutionModel,
+
     /// The `sample_rate_shading` feature must be enabled in order to use sample shading.
     SampleRateShadingFeatureNotEnabled,
 
@@ -321,6 +344,21 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 GraphicsPipelineCreationError::LogicOpFeatureNotEnabled => {
                     "the `logic_op` feature must be enabled in order to use logic operations"
                 }
+                GraphicsPipelineCreationError::LogicOpWithBlending => {
+                    "a logic operation is enabled but blending is enabled for one of the attachments"
+                }
+                GraphicsPipelineCreationError::ExtendedDynamicStateFeatureNotEnabled => {
+                    "the `extended_dynamic_state` feature must be enabled in order to make the \
+                     states of `VK_EXT_extended_dynamic_state` dynamic"
+                }
+                GraphicsPipelineCreationError::PipelineExecutableInfoFeatureNotEnabled => {
+                    "the `pipeline_executable_info` feature must be enabled in order to capture \
+                     information about the executables of the pipeline"
+                }
+                GraphicsPipelineCreationError::PipelineCreationFeedbackExtensionNotEnabled => {
+                    "the `ext_pipeline_creation_feedback` extension must be enabled in order to \
+                     capture feedback about the creation of the pipeline"
+                }
                 GraphicsPipelineCreationError::NoDepthAttachment => {
                     "the depth attachment of the render pass does not match the depth test"
                 }
@@ -337,6 +375,13 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 GraphicsPipelineCreationError::WrongShaderType => {
                     "the wrong type of shader has been passed"
                 }
+                GraphicsPipelineCreationError::EntryPointNotFound => {
+                    "the shader module of a stage doesn't contain an entry point with the \
+                 requested name"
+                }
+                GraphicsPipelineCreationError::WrongEntryPointExecutionModel => {
+                    "the entry point of a stage is meant for a different stage"
+                }
                 GraphicsPipelineCreationError::SampleRateShadingFeatureNotEnabled => {
                     "the `sample_rate_shading` feature must be enabled in order to use sample shading"
                 }
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use smallvec::SmallVec;
 use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
@@ -17,10 +18,12 @@ use std::u32;
 
 use buffer::BufferAccess;
 use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::ShaderStages;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
 use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
 use descriptor::pipeline_layout::PipelineLayoutSys;
+use descriptor::pipeline_layout::UnusedDescriptorStages;
 use descriptor::PipelineLayoutAbstract;
 use device::Device;
 use device::DeviceOwned;
@@ -33,6 +36,12 @@ use framebuffer::RenderPassDesc;
 use framebuffer::RenderPassDescClearValues;
 use framebuffer::RenderPassSys;
 use framebuffer::Subpass;
+use pipeline::executable;
+use pipeline::executable::PipelineExecutableCapture;
+use pipeline::executable::PipelineExecutableProperties;
+use pipeline::executable::PipelineExecutablePropertiesError;
+use pipeline::feedback::PipelineCreationFeedback;
+use pipeline::input_assembly::PrimitiveTopology;
 use pipeline::shader::EmptyEntryPointDummy;
 use pipeline::vertex::BufferlessDefinition;
 use pipeline::vertex::IncompatibleVertexDefinitionError;
@@ -57,6 +66,12 @@ mod creation_error;
 pub struct GraphicsPipeline<VertexDefinition, Layout, RenderP> {
     inner: Inner,
     layout: Layout,
+    creation_feedback: Option<PipelineCreationFeedback>,
+
+    // Stages of the shaders of the pipeline.
+    shader_stages: ShaderStages,
+    // Descriptors of the layout that are declared for stages the pipeline doesn't have.
+    unused_descriptor_stages: Vec<UnusedDescriptorStages>,
 
     render_pass: RenderP,
     render_pass_subpass: u32,
@@ -72,6 +87,20 @@ pub struct GraphicsPipeline<VertexDefinition, Layout, RenderP> {
     dynamic_stencil_write_mask: bool,
     dynamic_stencil_reference: bool,
     dynamic_blend_constants: bool,
+    dynamic_vertex_input_binding_stride: bool,
+    dynamic_cull_mode: bool,
+    dynamic_front_face: bool,
+    dynamic_primitive_topology: bool,
+    dynamic_depth_test_enable: bool,
+    dynamic_depth_write_enable: bool,
+
+    // Topology of the input assembly state. Only its topology class is relevant if the topology
+    // is dynamic.
+    topology: PrimitiveTopology,
+
+    // For each vertex buffer binding, the number of bytes read by the vertex attributes of the
+    // binding.
+    vertex_attribute_extents: SmallVec<[u32; 8]>,
 
     num_viewports: u32,
 }
@@ -80,6 +109,7 @@ pub struct GraphicsPipeline<VertexDefinition, Layout, RenderP> {
 struct Inner {
     pipeline: vk::Pipeline,
     device: Arc<Device>,
+    capture: PipelineExecutableCapture,
 }
 
 impl GraphicsPipeline<(), (), ()> {
@@ -115,6 +145,48 @@ impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
     pub fn device(&self) -> &Arc<Device> {
         &self.inner.device
     }
+
+    /// Returns the properties of the executables the pipeline was compiled into.
+    ///
+    /// The pipeline must have been built with `capture_executable_info`.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutablePropertiesError> {
+        executable::executable_properties(
+            &self.inner.device,
+            self.inner.pipeline,
+            self.inner.capture,
+        )
+    }
+
+    /// Returns the feedback about the creation of the pipeline.
+    ///
+    /// Returns `None` if the pipeline wasn't built with `capture_creation_feedback`.
+    #[inline]
+    pub fn creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.creation_feedback.as_ref()
+    }
+
+    /// Returns the stages of the shaders that the pipeline was built with.
+    ///
+    /// This can be passed to `PipelineLayoutDesc::unused_descriptor_stages` to find the
+    /// descriptors of the layout that are declared for stages the pipeline doesn't have.
+    #[inline]
+    pub fn shader_stages(&self) -> ShaderStages {
+        self.shader_stages
+    }
+
+    /// Returns the descriptors of the pipeline layout that are declared for stages the pipeline
+    /// doesn't have.
+    ///
+    /// This is computed with `PipelineLayoutDesc::unused_descriptor_stages` when the pipeline is
+    /// built. These descriptors aren't an error, so they don't prevent building the pipeline, but
+    /// removing the unused stages from the layout is usually better.
+    #[inline]
+    pub fn unused_descriptor_stages(&self) -> &[UnusedDescriptorStages] {
+        &self.unused_descriptor_stages
+    }
 }
 
 impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp>
@@ -193,6 +265,59 @@ impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
     pub fn has_dynamic_stencil_reference(&self) -> bool {
         self.dynamic_stencil_reference
     }
+
+    /// Returns true if the strides of the vertex buffers used by this pipeline are dynamic.
+    #[inline]
+    pub fn has_dynamic_vertex_input_binding_stride(&self) -> bool {
+        self.dynamic_vertex_input_binding_stride
+    }
+
+    /// Returns true if the cull mode used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_cull_mode(&self) -> bool {
+        self.dynamic_cull_mode
+    }
+
+    /// Returns true if the front face used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_front_face(&self) -> bool {
+        self.dynamic_front_face
+    }
+
+    /// Returns true if the primitive topology used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_primitive_topology(&self) -> bool {
+        self.dynamic_primitive_topology
+    }
+
+    /// Returns true if the enabling of the depth test used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_depth_test_enable(&self) -> bool {
+        self.dynamic_depth_test_enable
+    }
+
+    /// Returns true if the enabling of the depth writes used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_depth_write_enable(&self) -> bool {
+        self.dynamic_depth_write_enable
+    }
+
+    /// Returns the primitive topology of the input assembly state of this pipeline.
+    #[inline]
+    pub fn primitive_topology(&self) -> PrimitiveTopology {
+        self.topology
+    }
+
+    /// Returns the minimum non-zero stride of the vertex buffer binding `binding`, which is the
+    /// number of bytes read by the vertex attributes of the binding. Returns 0 if no attribute
+    /// reads from the binding.
+    #[inline]
+    pub fn min_vertex_input_binding_stride(&self, binding: u32) -> u32 {
+        self.vertex_attribute_extents
+            .get(binding as usize)
+            .cloned()
+            .unwrap_or(0)
+    }
 }
 
 unsafe impl<Mv, L, Rp> PipelineLayoutAbstract for GraphicsPipeline<Mv, L, Rp>
@@ -204,6 +329,11 @@ where
         self.layout.sys()
     }
 
+    #[inline]
+    fn set_layouts(&self) -> &[Arc<UnsafeDescriptorSetLayout>] {
+        self.layout.set_layouts()
+    }
+
     #[inline]
     fn descriptor_set_layout(&self, index: usize) -> Option<&Arc<UnsafeDescriptorSetLayout>> {
         self.layout.descriptor_set_layout(index)
@@ -380,6 +510,32 @@ pub unsafe trait GraphicsPipelineAbstract:
 
     /// Returns true if the stencil references used by this pipeline are dynamic.
     fn has_dynamic_stencil_reference(&self) -> bool;
+
+    /// Returns true if the strides of the vertex buffers used by this pipeline are dynamic.
+    fn has_dynamic_vertex_input_binding_stride(&self) -> bool;
+
+    /// Returns true if the cull mode used by this pipeline is dynamic.
+    fn has_dynamic_cull_mode(&self) -> bool;
+
+    /// Returns true if the front face used by this pipeline is dynamic.
+    fn has_dynamic_front_face(&self) -> bool;
+
+    /// Returns true if the primitive topology used by this pipeline is dynamic.
+    fn has_dynamic_primitive_topology(&self) -> bool;
+
+    /// Returns true if the enabling of the depth test used by this pipeline is dynamic.
+    fn has_dynamic_depth_test_enable(&self) -> bool;
+
+    /// Returns true if the enabling of the depth writes used by this pipeline is dynamic.
+    fn has_dynamic_depth_write_enable(&self) -> bool;
+
+    /// Returns the primitive topology of the input assembly state of this pipeline.
+    fn primitive_topology(&self) -> PrimitiveTopology;
+
+    /// Returns the minimum non-zero stride of the vertex buffer binding `binding`, which is the
+    /// number of bytes read by the vertex attributes of the binding. Returns 0 if no attribute
+    /// reads from the binding.
+    fn min_vertex_input_binding_stride(&self, binding: u32) -> u32;
 }
 
 unsafe impl<Mv, L, Rp> GraphicsPipelineAbstract for GraphicsPipeline<Mv, L, Rp>
@@ -437,6 +593,46 @@ where
     fn has_dynamic_stencil_reference(&self) -> bool {
         self.dynamic_stencil_reference
     }
+
+    #[inline]
+    fn has_dynamic_vertex_input_binding_stride(&self) -> bool {
+        self.dynamic_vertex_input_binding_stride
+    }
+
+    #[inline]
+    fn has_dynamic_cull_mode(&self) -> bool {
+        self.dynamic_cull_mode
+    }
+
+    #[inline]
+    fn has_dynamic_front_face(&self) -> bool {
+        self.dynamic_front_face
+    }
+
+    #[inline]
+    fn has_dynamic_primitive_topology(&self) -> bool {
+        self.dynamic_primitive_topology
+    }
+
+    #[inline]
+    fn has_dynamic_depth_test_enable(&self) -> bool {
+        self.dynamic_depth_test_enable
+    }
+
+    #[inline]
+    fn has_dynamic_depth_write_enable(&self) -> bool {
+        self.dynamic_depth_write_enable
+    }
+
+    #[inline]
+    fn primitive_topology(&self) -> PrimitiveTopology {
+        self.topology
+    }
+
+    #[inline]
+    fn min_vertex_input_binding_stride(&self, binding: u32) -> u32 {
+        GraphicsPipeline::min_vertex_input_binding_stride(self, binding)
+    }
 }
 
 unsafe impl<T> GraphicsPipelineAbstract for T
@@ -493,6 +689,46 @@ where
     fn has_dynamic_stencil_reference(&self) -> bool {
         (**self).has_dynamic_stencil_reference()
     }
+
+    #[inline]
+    fn has_dynamic_vertex_input_binding_stride(&self) -> bool {
+        (**self).has_dynamic_vertex_input_binding_stride()
+    }
+
+    #[inline]
+    fn has_dynamic_cull_mode(&self) -> bool {
+        (**self).has_dynamic_cull_mode()
+    }
+
+    #[inline]
+    fn has_dynamic_front_face(&self) -> bool {
+        (**self).has_dynamic_front_face()
+    }
+
+    #[inline]
+    fn has_dynamic_primitive_topology(&self) -> bool {
+        (**self).has_dynamic_primitive_topology()
+    }
+
+    #[inline]
+    fn has_dynamic_depth_test_enable(&self) -> bool {
+        (**self).has_dynamic_depth_test_enable()
+    }
+
+    #[inline]
+    fn has_dynamic_depth_write_enable(&self) -> bool {
+        (**self).has_dynamic_depth_write_enable()
+    }
+
+    #[inline]
+    fn primitive_topology(&self) -> PrimitiveTopology {
+        (**self).primitive_topology()
+    }
+
+    #[inline]
+    fn min_vertex_input_binding_stride(&self, binding: u32) -> u32 {
+        (**self).min_vertex_input_binding_stride(binding)
+    }
 }
 
 impl<Mv, L, Rp> PartialEq for GraphicsPipeline<Mv, L, Rp>
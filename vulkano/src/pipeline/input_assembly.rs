@@ -99,14 +99,50 @@ impl PrimitiveTopology {
             _ => false,
         }
     }
+
+    /// Returns true if this primitive topology and `other` belong to the same topology class,
+    /// which is either points, lines, triangles or patches.
+    #[inline]
+    pub fn is_same_class(&self, other: &PrimitiveTopology) -> bool {
+        self.class() == other.class()
+    }
+
+    // Returns the topology class of this primitive topology, as the number of vertices of a
+    // primitive, or 0 for patches.
+    #[inline]
+    fn class(&self) -> u32 {
+        match *self {
+            PrimitiveTopology::PointList => 1,
+            PrimitiveTopology::LineList
+            | PrimitiveTopology::LineStrip
+            | PrimitiveTopology::LineListWithAdjacency
+            | PrimitiveTopology::LineStripWithAdjacency => 2,
+            PrimitiveTopology::TriangleList
+            | PrimitiveTopology::TriangleStrip
+            | PrimitiveTopology::TriangleFan
+            | PrimitiveTopology::TriangleListWithAdjacency
+            | PrimitiveTopology::TriangleStripWithAdjacency => 3,
+            PrimitiveTopology::PatchList { .. } => 0,
+        }
+    }
 }
 
 /// Trait for types that can be used as indices by the GPU.
+///
+/// Using `u8` indices requires the `VK_EXT_index_type_uint8` extension and the
+/// `index_type_uint8` feature to be enabled on the device.
 pub unsafe trait Index {
     /// Returns the type of data.
     fn ty() -> IndexType;
 }
 
+unsafe impl Index for u8 {
+    #[inline(always)]
+    fn ty() -> IndexType {
+        IndexType::U8
+    }
+}
+
 unsafe impl Index for u16 {
     #[inline(always)]
     fn ty() -> IndexType {
@@ -126,6 +162,29 @@ unsafe impl Index for u32 {
 #[allow(missing_docs)]
 #[repr(u32)]
 pub enum IndexType {
+    U8 = vk::INDEX_TYPE_UINT8_EXT,
     U16 = vk::INDEX_TYPE_UINT16,
     U32 = vk::INDEX_TYPE_UINT32,
 }
+
+#[cfg(test)]
+mod tests {
+    use pipeline::input_assembly::PrimitiveTopology;
+
+    #[test]
+    fn same_class() {
+        let triangles = PrimitiveTopology::TriangleList;
+        assert!(triangles.is_same_class(&PrimitiveTopology::TriangleFan));
+        assert!(triangles.is_same_class(&PrimitiveTopology::TriangleStripWithAdjacency));
+        assert!(!triangles.is_same_class(&PrimitiveTopology::LineStrip));
+        assert!(!triangles.is_same_class(&PrimitiveTopology::PointList));
+
+        let patches = PrimitiveTopology::PatchList {
+            vertices_per_patch: 3,
+        };
+        assert!(patches.is_same_class(&PrimitiveTopology::PatchList {
+            vertices_per_patch: 4
+        }));
+        assert!(!patches.is_same_class(&triangles));
+    }
+}
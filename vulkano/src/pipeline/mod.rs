@@ -87,13 +87,17 @@ pub use self::graphics_pipeline::GraphicsPipelineSys;
 
 mod compute_pipeline;
 mod graphics_pipeline;
+mod reflect;
 
 pub mod blend;
 pub mod cache;
 pub mod depth_stencil;
+pub mod executable;
+pub mod feedback;
 pub mod input_assembly;
 pub mod multisample;
 pub mod raster;
+pub mod reload;
 pub mod shader;
 pub mod vertex;
 pub mod viewport;
@@ -0,0 +1,443 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Minimal reflection of the input interface of vertex shaders.
+//!
+//! Only the instructions that come before the first function are parsed, like
+//! `reflect_entry_points` in the `shader` module does for the entry points.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ffi::CString;
+
+use format::Format;
+use pipeline::shader::ShaderInterfaceDefEntry;
+
+const MAGIC_NUMBER: u32 = 0x07230203;
+
+const OP_NAME: u32 = 5;
+const OP_ENTRY_POINT: u32 = 15;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_MATRIX: u32 = 24;
+const OP_TYPE_ARRAY: u32 = 28;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_CONSTANT: u32 = 43;
+const OP_FUNCTION: u32 = 54;
+const OP_VARIABLE: u32 = 59;
+const OP_DECORATE: u32 = 71;
+
+const DECORATION_BUILT_IN: u32 = 11;
+const DECORATION_LOCATION: u32 = 30;
+
+const EXECUTION_MODEL_VERTEX: u32 = 0;
+
+const STORAGE_CLASS_INPUT: u32 = 1;
+
+#[derive(Debug, Copy, Clone)]
+enum Type {
+    Int { width: u32, signed: bool },
+    Float { width: u32 },
+    Vector { component: u32, count: u32 },
+    Matrix { column: u32, count: u32 },
+    Array { element: u32, length: u32 },
+    Pointer { pointee: u32 },
+}
+
+#[derive(Default)]
+struct Module {
+    names: HashMap<u32, String>,
+    locations: HashMap<u32, u32>,
+    built_ins: HashSet<u32>,
+    types: HashMap<u32, Type>,
+    constants: HashMap<u32, u32>,
+    // Result type and storage class of each variable.
+    variables: HashMap<u32, (u32, u32)>,
+}
+
+// Returns the name and the user-defined inputs, sorted by location, of each vertex shader entry
+// point of some SPIR-V code. Returns an empty list if the header of the code is invalid.
+pub(crate) fn reflect_vertex_inputs(spirv: &[u32]) -> Vec<(CString, Vec<ShaderInterfaceDefEntry>)> {
+    let swap = match spirv.first() {
+        Some(&MAGIC_NUMBER) => false,
+        Some(&magic) if magic.swap_bytes() == MAGIC_NUMBER => true,
+        _ => return Vec::new(),
+    };
+    let words: Cow<[u32]> = if swap {
+        Cow::Owned(spirv.iter().map(|w| w.swap_bytes()).collect())
+    } else {
+        Cow::Borrowed(spirv)
+    };
+
+    let mut module = Module::default();
+    // Name and interface of each vertex shader entry point.
+    let mut entry_points = Vec::new();
+
+    // The first five words are the header.
+    let mut index = 5;
+
+    while index < words.len() {
+        let word_count = (words[index] >> 16) as usize;
+        let opcode = words[index] & 0xffff;
+
+        if word_count == 0 || index + word_count > words.len() {
+            break;
+        }
+
+        // Everything we are interested in is declared before the first function.
+        if opcode == OP_FUNCTION {
+            break;
+        }
+
+        let operands = &words[index + 1..index + word_count];
+        match (opcode, operands.len()) {
+            (OP_NAME, len) if len >= 2 => {
+                let (name, _) = parse_string(&operands[1..]);
+                module
+                    .names
+                    .insert(operands[0], String::from_utf8_lossy(&name).into_owned());
+            }
+            (OP_ENTRY_POINT, len) if len >= 3 && operands[0] == EXECUTION_MODEL_VERTEX => {
+                let (name, name_words) = parse_string(&operands[2..]);
+                let interface = operands[2 + name_words..].to_vec();
+                entry_points.push((name, interface));
+            }
+            (OP_TYPE_INT, 3) => {
+                module.types.insert(
+                    operands[0],
+                    Type::Int {
+                        width: operands[1],
+                        signed: operands[2] != 0,
+                    },
+                );
+            }
+            (OP_TYPE_FLOAT, len) if len >= 2 => {
+                module
+                    .types
+                    .insert(operands[0], Type::Float { width: operands[1] });
+            }
+            (OP_TYPE_VECTOR, 3) => {
+                module.types.insert(
+                    operands[0],
+                    Type::Vector {
+                        component: operands[1],
+                        count: operands[2],
+                    },
+                );
+            }
+            (OP_TYPE_MATRIX, 3) => {
+                module.types.insert(
+                    operands[0],
+                    Type::Matrix {
+                        column: operands[1],
+                        count: operands[2],
+                    },
+                );
+            }
+            (OP_TYPE_ARRAY, 3) => {
+                module.types.insert(
+                    operands[0],
+                    Type::Array {
+                        element: operands[1],
+                        length: operands[2],
+                    },
+                );
+            }
+            (OP_TYPE_POINTER, 3) => {
+                module.types.insert(
+                    operands[0],
+                    Type::Pointer {
+                        pointee: operands[2],
+                    },
+                );
+            }
+            (OP_CONSTANT, len) if len >= 3 => {
+                module.constants.insert(operands[1], operands[2]);
+            }
+            (OP_VARIABLE, len) if len >= 3 => {
+                module
+                    .variables
+                    .insert(operands[1], (operands[0], operands[2]));
+            }
+            (OP_DECORATE, len) if len >= 2 => match operands[1] {
+                DECORATION_LOCATION if len >= 3 => {
+                    module.locations.insert(operands[0], operands[2]);
+                }
+                DECORATION_BUILT_IN => {
+                    module.built_ins.insert(operands[0]);
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+
+        index += word_count;
+    }
+
+    entry_points
+        .into_iter()
+        .map(|(name, interface)| {
+            (
+                CString::new(name).unwrap(),
+                module.input_interface(&interface),
+            )
+        })
+        .collect()
+}
+
+// Parses a nul-terminated UTF-8 string packed in little-endian words. Returns the bytes of the
+// string and the number of words it occupies.
+fn parse_string(words: &[u32]) -> (Vec<u8>, usize) {
+    let mut bytes = Vec::new();
+
+    for (num, word) in words.iter().enumerate() {
+        for &byte in &word.to_le_bytes() {
+            if byte == 0 {
+                return (bytes, num + 1);
+            }
+            bytes.push(byte);
+        }
+    }
+
+    (bytes, words.len())
+}
+
+impl Module {
+    // Returns the user-defined input variables among `interface`, sorted by location. Variables
+    // whose type isn't supported are skipped.
+    fn input_interface(&self, interface: &[u32]) -> Vec<ShaderInterfaceDefEntry> {
+        let mut elements: Vec<_> = interface
+            .iter()
+            .filter_map(|&id| {
+                let (ty, storage_class) = *self.variables.get(&id)?;
+                if storage_class != STORAGE_CLASS_INPUT || self.built_ins.contains(&id) {
+                    return None;
+                }
+
+                let location = *self.locations.get(&id)?;
+                let (format, num_locations) = self.format(ty)?;
+
+                Some(ShaderInterfaceDefEntry {
+                    location: location..location + num_locations,
+                    format,
+                    name: self.names.get(&id).map(|name| Cow::Owned(name.clone())),
+                })
+            })
+            .collect();
+
+        elements.sort_by_key(|element| element.location.start);
+        elements
+    }
+
+    // Returns the format of each location of a type, and the number of locations it occupies.
+    // This follows what `vulkano-shaders` does.
+    fn format(&self, ty: u32) -> Option<(Format, u32)> {
+        match *self.types.get(&ty)? {
+            Type::Int { width, signed } => {
+                let format = match (width, signed) {
+                    (8, true) => Format::R8Sint,
+                    (8, false) => Format::R8Uint,
+                    (16, true) => Format::R16Sint,
+                    (16, false) => Format::R16Uint,
+                    (32, true) => Format::R32Sint,
+                    (32, false) => Format::R32Uint,
+                    (64, true) => Format::R64Sint,
+                    (64, false) => Format::R64Uint,
+                    _ => return None,
+                };
+                Some((format, 1))
+            }
+            Type::Float { width } => {
+                let format = match width {
+                    32 => Format::R32Sfloat,
+                    64 => Format::R64Sfloat,
+                    _ => return None,
+                };
+                Some((format, 1))
+            }
+            Type::Vector { component, count } => {
+                let format = match (self.format(component)?, count) {
+                    ((format, 1), 1) => format,
+                    ((Format::R32Sint, 1), 2) => Format::R32G32Sint,
+                    ((Format::R32Sint, 1), 3) => Format::R32G32B32Sint,
+                    ((Format::R32Sint, 1), 4) => Format::R32G32B32A32Sint,
+                    ((Format::R32Uint, 1), 2) => Format::R32G32Uint,
+                    ((Format::R32Uint, 1), 3) => Format::R32G32B32Uint,
+                    ((Format::R32Uint, 1), 4) => Format::R32G32B32A32Uint,
+                    ((Format::R32Sfloat, 1), 2) => Format::R32G32Sfloat,
+                    ((Format::R32Sfloat, 1), 3) => Format::R32G32B32Sfloat,
+                    ((Format::R32Sfloat, 1), 4) => Format::R32G32B32A32Sfloat,
+                    _ => return None,
+                };
+                Some((format, 1))
+            }
+            Type::Matrix { column, count } => {
+                let (format, num_locations) = self.format(column)?;
+                Some((format, num_locations * count))
+            }
+            Type::Array { element, length } => {
+                let (format, num_locations) = self.format(element)?;
+                let length = *self.constants.get(&length)?;
+                Some((format, num_locations * length))
+            }
+            Type::Pointer { pointee } => self.format(pointee),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use pipeline::reflect::reflect_vertex_inputs;
+    use std::ffi::CString;
+
+    // Builds the first word of an instruction.
+    fn op(word_count: u32, opcode: u32) -> u32 {
+        (word_count << 16) | opcode
+    }
+
+    #[test]
+    fn non_vertex_entry_points() {
+        let spirv = [
+            // Header.
+            0x07230203,
+            0x00010000,
+            0,
+            10,
+            0,
+            // OpEntryPoint Fragment %2 "frag" %3
+            op(6, 15),
+            4,
+            2,
+            u32::from_le_bytes(*b"frag"),
+            0,
+            3,
+        ];
+
+        assert!(reflect_vertex_inputs(&spirv).is_empty());
+        assert!(reflect_vertex_inputs(&[]).is_empty());
+    }
+
+    #[test]
+    fn vertex_input() {
+        let spirv = [
+            // Header.
+            0x07230203,
+            0x00010000,
+            0,
+            20,
+            0,
+            // OpEntryPoint Vertex %1 "main" %10 %11 %12
+            op(8, 15),
+            0,
+            1,
+            u32::from_le_bytes(*b"main"),
+            0,
+            10,
+            11,
+            12,
+            // OpName %10 "position"
+            op(5, 5),
+            10,
+            u32::from_le_bytes(*b"posi"),
+            u32::from_le_bytes(*b"tion"),
+            0,
+            // OpName %11 "transform"
+            op(5, 5),
+            11,
+            u32::from_le_bytes(*b"tran"),
+            u32::from_le_bytes(*b"sfor"),
+            u32::from_le_bytes(*b"m\0\0\0"),
+            // OpDecorate %10 Location 0
+            op(4, 71),
+            10,
+            30,
+            0,
+            // OpDecorate %11 Location 1
+            op(4, 71),
+            11,
+            30,
+            1,
+            // OpDecorate %12 BuiltIn VertexIndex
+            op(4, 71),
+            12,
+            11,
+            42,
+            // %2 = OpTypeFloat 32
+            op(3, 22),
+            2,
+            32,
+            // %3 = OpTypeVector %2 2
+            op(4, 23),
+            3,
+            2,
+            2,
+            // %4 = OpTypeVector %2 4
+            op(4, 23),
+            4,
+            2,
+            4,
+            // %5 = OpTypeMatrix %4 4
+            op(4, 24),
+            5,
+            4,
+            4,
+            // %6 = OpTypePointer Input %3
+            op(4, 32),
+            6,
+            1,
+            3,
+            // %7 = OpTypePointer Input %5
+            op(4, 32),
+            7,
+            1,
+            5,
+            // %8 = OpTypeInt 32 1
+            op(4, 21),
+            8,
+            32,
+            1,
+            // %9 = OpTypePointer Input %8
+            op(4, 32),
+            9,
+            1,
+            8,
+            // %11 = OpVariable %7 Input
+            op(4, 59),
+            7,
+            11,
+            1,
+            // %10 = OpVariable %6 Input
+            op(4, 59),
+            6,
+            10,
+            1,
+            // %12 = OpVariable %9 Input
+            op(4, 59),
+            9,
+            12,
+            1,
+        ];
+
+        let entry_points = reflect_vertex_inputs(&spirv);
+        assert_eq!(entry_points.len(), 1);
+        assert_eq!(entry_points[0].0, CString::new("main").unwrap());
+
+        let input = &entry_points[0].1;
+        assert_eq!(input.len(), 2);
+        assert_eq!(input[0].location, 0..1);
+        assert_eq!(input[0].format, Format::R32G32Sfloat);
+        assert_eq!(input[0].name.as_ref().unwrap(), "position");
+        assert_eq!(input[1].location, 1..5);
+        assert_eq!(input[1].format, Format::R32G32B32A32Sfloat);
+        assert_eq!(input[1].name.as_ref().unwrap(), "transform");
+    }
+}
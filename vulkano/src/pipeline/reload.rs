@@ -0,0 +1,159 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Rebuilding pipelines when their shaders change.
+//!
+//! This is meant to be used during development: when the SPIR-V of a shader is modified on disk,
+//! the application can reload it and rebuild all the pipelines that use it without restarting.
+//!
+//! A `ReloadablePipeline` holds the shader modules of a pipeline and a closure that builds the
+//! pipeline from these modules. Each call to `reload` replaces one of the modules and calls the
+//! closure again. Command buffers keep an `Arc` to the pipelines they use, therefore the previous
+//! pipeline stays alive until all the command buffers that use it are destroyed.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::ffi::CStr;
+//! use std::sync::Arc;
+//! use vulkano::descriptor::pipeline_layout::EmptyPipelineDesc;
+//! use vulkano::pipeline::ComputePipeline;
+//! use vulkano::pipeline::ComputePipelineAbstract;
+//! use vulkano::pipeline::ComputePipelineCreationError;
+//! use vulkano::pipeline::reload::ReloadablePipeline;
+//! use vulkano::pipeline::shader::ShaderModule;
+//! # use vulkano::device::Device;
+//! # let device: Arc<Device> = return;
+//! # let module: Arc<ShaderModule> = return;
+//! # let new_spirv: Vec<u8> = return;
+//!
+//! let pipeline = ReloadablePipeline::new(vec![module], move |modules| {
+//!     // The shader has a `main` entry point and doesn't use any descriptor.
+//!     let name = CStr::from_bytes_with_nul(b"main\0").unwrap();
+//!     let entry_point = unsafe {
+//!         modules[0].compute_entry_point::<(), _>(name, EmptyPipelineDesc)
+//!     };
+//!     let pipeline = ComputePipeline::new(device.clone(), &entry_point, &())?;
+//!     Ok::<_, ComputePipelineCreationError>(
+//!         Arc::new(pipeline) as Arc<dyn ComputePipelineAbstract + Send + Sync>
+//!     )
+//! }).unwrap();
+//!
+//! // Later, when the shader has been recompiled.
+//! if let Err(err) = unsafe { pipeline.reload(0, &new_spirv) } {
+//!     // The previous pipeline is kept.
+//!     println!("Failed to reload the shader: {}", err);
+//! }
+//!
+//! // Always fetch the pipeline when recording a command buffer.
+//! let current = pipeline.pipeline();
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use pipeline::shader::ShaderModule;
+use OomError;
+
+/// Pipeline that can be rebuilt when its shader modules are reloaded.
+///
+/// See the documentation of the `reload` module.
+pub struct ReloadablePipeline<P: ?Sized, E> {
+    build: Box<dyn Fn(&[Arc<ShaderModule>]) -> Result<Arc<P>, E> + Send + Sync>,
+    state: Mutex<ReloadableState<P>>,
+}
+
+struct ReloadableState<P: ?Sized> {
+    modules: Vec<Arc<ShaderModule>>,
+    pipeline: Arc<P>,
+}
+
+impl<P: ?Sized, E> ReloadablePipeline<P, E> {
+    /// Builds the pipeline for the first time by calling `build` with `modules`.
+    ///
+    /// `build` is called again with the new list of modules every time one of them is reloaded.
+    pub fn new<F>(modules: Vec<Arc<ShaderModule>>, build: F) -> Result<ReloadablePipeline<P, E>, E>
+    where
+        F: Fn(&[Arc<ShaderModule>]) -> Result<Arc<P>, E> + Send + Sync + 'static,
+    {
+        let pipeline = build(&modules)?;
+
+        Ok(ReloadablePipeline {
+            build: Box::new(build),
+            state: Mutex::new(ReloadableState { modules, pipeline }),
+        })
+    }
+
+    /// Returns the latest version of the pipeline.
+    ///
+    /// You should call this function every time you record a command buffer instead of keeping
+    /// the returned pipeline around, otherwise reloads won't be taken into account.
+    #[inline]
+    pub fn pipeline(&self) -> Arc<P> {
+        self.state.lock().unwrap().pipeline.clone()
+    }
+
+    /// Returns the shader modules the latest version of the pipeline was built with.
+    #[inline]
+    pub fn modules(&self) -> Vec<Arc<ShaderModule>> {
+        self.state.lock().unwrap().modules.clone()
+    }
+
+    /// Rebuilds the pipeline from the current modules.
+    ///
+    /// This can be used to rebuild the pipeline after something else than a shader changed, for
+    /// example a specialization constant captured by the closure. If building fails, the
+    /// previous pipeline is kept.
+    pub fn rebuild(&self) -> Result<Arc<P>, E> {
+        let mut state = self.state.lock().unwrap();
+        let pipeline = (self.build)(&state.modules)?;
+        state.pipeline = pipeline.clone();
+        Ok(pipeline)
+    }
+
+    /// Replaces the module at index `module` with a module built from `spirv`, then rebuilds
+    /// the pipeline.
+    ///
+    /// If building the module or the pipeline fails, the previous module and pipeline are kept.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `module` is out of range.
+    ///
+    /// # Safety
+    ///
+    /// Same as `ShaderModule::new`. In addition, the entry points used by the closure must still
+    /// match the interface described by the closure.
+    ///
+    pub unsafe fn reload(&self, module: usize, spirv: &[u8]) -> Result<Arc<P>, E>
+    where
+        E: From<OomError>,
+    {
+        let mut state = self.state.lock().unwrap();
+        assert!(module < state.modules.len());
+
+        let mut modules = state.modules.clone();
+        modules[module] = modules[module].reload_from_spirv(spirv)?;
+
+        let pipeline = (self.build)(&modules)?;
+        state.modules = modules;
+        state.pipeline = pipeline.clone();
+        Ok(pipeline)
+    }
+}
+
+impl<P: ?Sized, E> fmt::Debug for ReloadablePipeline<P, E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let state = self.state.lock().unwrap();
+        fmt.debug_struct("ReloadablePipeline")
+            .field("modules", &state.modules)
+            .finish()
+    }
+}
@@ -13,13 +13,16 @@
 //! code and can contain one or more entry points. Note that for the moment the official
 //! GLSL-to-SPIR-V compiler does not support multiple entry points.
 //!
-//! The vulkano library does not provide any functionality that checks and introspects the SPIR-V
-//! code, therefore the whole shader-related API is unsafe. You are encouraged to use the
+//! Apart from listing the entry points of a module with `ShaderModule::entry_points` and the
+//! inputs of its vertex shaders with `ShaderModule::vertex_input`, the vulkano library does not
+//! provide any functionality that checks and introspects the SPIR-V code, therefore the whole
+//! shader-related API is unsafe. You are encouraged to use the
 //! `vulkano-shaders` crate that will generate Rust code that wraps around vulkano's shaders API.
 
 use std::borrow::Cow;
 use std::error;
 use std::ffi::CStr;
+use std::ffi::CString;
 use std::fmt;
 use std::iter;
 use std::iter::Empty as EmptyIter;
@@ -28,12 +31,20 @@ use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::Range;
 use std::ptr;
+use std::slice;
 use std::sync::Arc;
+use std::vec::IntoIter as VecIntoIter;
 
 use descriptor::pipeline_layout::EmptyPipelineDesc;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
 use format::Format;
 use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::reflect::reflect_vertex_inputs;
+use pipeline::vertex::AttributeInfo;
+use pipeline::vertex::IncompatibleVertexDefinitionError;
+use pipeline::vertex::SingleBufferDefinition;
+use pipeline::vertex::Vertex;
+use pipeline::vertex::VertexDefinition;
 
 use check_errors;
 use device::Device;
@@ -51,6 +62,10 @@ pub struct ShaderModule {
     module: vk::ShaderModule,
     // Pointer to the device.
     device: Arc<Device>,
+    // The entry points declared in the SPIR-V code.
+    entry_points: Vec<(CString, ExecutionModel)>,
+    // The name and the input interface of the vertex shader entry points.
+    vertex_inputs: Vec<(CString, Vec<ShaderInterfaceDefEntry>)>,
 }
 
 impl ShaderModule {
@@ -64,7 +79,15 @@ impl ShaderModule {
     ///
     pub unsafe fn new(device: Arc<Device>, spirv: &[u8]) -> Result<Arc<ShaderModule>, OomError> {
         debug_assert!((spirv.len() % 4) == 0);
-        Self::from_ptr(device, spirv.as_ptr() as *const _, spirv.len())
+        let words: Vec<u32> = spirv
+            .chunks(4)
+            .map(|chunk| {
+                let mut bytes = [0; 4];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+                u32::from_ne_bytes(bytes)
+            })
+            .collect();
+        Self::from_words(device, &words)
     }
 
     /// Builds a new shader module from SPIR-V 32-bit words.
@@ -79,7 +102,27 @@ impl ShaderModule {
         device: Arc<Device>,
         spirv: &[u32],
     ) -> Result<Arc<ShaderModule>, OomError> {
-        Self::from_ptr(device, spirv.as_ptr(), spirv.len() * mem::size_of::<u32>())
+        Self::from_ptr(
+            device,
+            spirv.as_ptr(),
+            spirv.len() * mem::size_of::<u32>(),
+            reflect_entry_points(spirv),
+            reflect_vertex_inputs(spirv),
+        )
+    }
+
+    /// Builds a new shader module on the same device as this one, from updated SPIR-V bytes.
+    ///
+    /// This module is left untouched and stays valid for as long as it is alive. This is
+    /// typically used to reload a shader during development, see the `reload` module.
+    ///
+    /// # Safety
+    ///
+    /// Same as `new`.
+    ///
+    #[inline]
+    pub unsafe fn reload_from_spirv(&self, spirv: &[u8]) -> Result<Arc<ShaderModule>, OomError> {
+        ShaderModule::new(self.device.clone(), spirv)
     }
 
     /// Builds a new shader module from SPIR-V.
@@ -94,6 +137,8 @@ impl ShaderModule {
         device: Arc<Device>,
         spirv: *const u32,
         spirv_len: usize,
+        entry_points: Vec<(CString, ExecutionModel)>,
+        vertex_inputs: Vec<(CString, Vec<ShaderInterfaceDefEntry>)>,
     ) -> Result<Arc<ShaderModule>, OomError> {
         let module = {
             let infos = vk::ShaderModuleCreateInfo {
@@ -118,9 +163,77 @@ impl ShaderModule {
         Ok(Arc::new(ShaderModule {
             module: module,
             device: device,
+            entry_points: entry_points,
+            vertex_inputs: vertex_inputs,
         }))
     }
 
+    /// Returns the entry points declared in the SPIR-V code of this module, with their name and
+    /// execution model.
+    ///
+    /// The same name can be used by several entry points, as long as their execution models are
+    /// different.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use vulkano::pipeline::shader::ExecutionModel;
+    /// # let module: std::sync::Arc<vulkano::pipeline::shader::ShaderModule> = return;
+    ///
+    /// let fragment_entry_point = module
+    ///     .entry_points()
+    ///     .find(|&(_, model)| model == ExecutionModel::Fragment)
+    ///     .map(|(name, _)| name);
+    /// ```
+    #[inline]
+    pub fn entry_points(&self) -> EntryPoints<'_> {
+        EntryPoints {
+            inner: self.entry_points.iter(),
+        }
+    }
+
+    /// Returns the input interface of the vertex shader entry point named `name`, by reflecting
+    /// the SPIR-V code of the module. Returns `None` if there is no such entry point.
+    ///
+    /// The returned interface can be passed to `graphics_entry_point`, and it can be checked
+    /// against a `Vertex` type with `ReflectedShaderInterface::vertex_attributes`.
+    ///
+    /// Inputs whose type isn't supported by the reflection are left out of the interface.
+    #[inline]
+    pub fn vertex_input(&self, name: &CStr) -> Option<ReflectedShaderInterface> {
+        self.vertex_inputs
+            .iter()
+            .find(|&&(ref entry_name, _)| entry_name.as_c_str() == name)
+            .map(|&(_, ref elements)| ReflectedShaderInterface {
+                elements: elements.clone(),
+            })
+    }
+
+    /// Checks that the module contains an entry point named `name` with the execution model
+    /// `model`.
+    pub(crate) fn check_entry_point(
+        &self,
+        name: &CStr,
+        model: ExecutionModel,
+    ) -> Result<(), EntryPointError> {
+        let mut found_name = false;
+
+        for (entry_name, entry_model) in self.entry_points() {
+            if entry_name == name {
+                if entry_model == model {
+                    return Ok(());
+                }
+                found_name = true;
+            }
+        }
+
+        if found_name {
+            Err(EntryPointError::WrongExecutionModel)
+        } else {
+            Err(EntryPointError::NotFound)
+        }
+    }
+
     /// Gets access to an entry point contained in this module.
     ///
     /// This is purely a *logical* operation. It returns a struct that *represents* the entry
@@ -129,7 +242,8 @@ impl ShaderModule {
     /// # Safety
     ///
     /// - The user must check that the entry point exists in the module, as this is not checked
-    ///   by Vulkan.
+    ///   by Vulkan. Building a pipeline with an entry point that doesn't exist or that has the
+    ///   wrong execution model returns an error.
     /// - The input, output and layout must correctly describe the input, output and layout used
     ///   by this stage.
     ///
@@ -160,7 +274,8 @@ impl ShaderModule {
     /// # Safety
     ///
     /// - The user must check that the entry point exists in the module, as this is not checked
-    ///   by Vulkan.
+    ///   by Vulkan. Building a pipeline with an entry point that doesn't exist or that has the
+    ///   wrong execution model returns an error.
     /// - The layout must correctly describe the layout used by this stage.
     ///
     #[inline]
@@ -173,11 +288,166 @@ impl ShaderModule {
             module: self,
             name: name,
             layout: layout,
+            local_size: None,
             marker: PhantomData,
         }
     }
 }
 
+/// Iterator to the entry points of a `ShaderModule`.
+///
+/// Can be obtained by calling `entry_points()` on the shader module.
+#[derive(Debug, Clone)]
+pub struct EntryPoints<'a> {
+    inner: slice::Iter<'a, (CString, ExecutionModel)>,
+}
+
+impl<'a> Iterator for EntryPoints<'a> {
+    type Item = (&'a CStr, ExecutionModel);
+
+    #[inline]
+    fn next(&mut self) -> Option<(&'a CStr, ExecutionModel)> {
+        self.inner
+            .next()
+            .map(|&(ref name, model)| (name.as_c_str(), model))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for EntryPoints<'a> {}
+
+/// Execution model of an entry point, in other words the stage of the pipeline that it is meant
+/// for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ExecutionModel {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    GLCompute,
+    Kernel,
+    TaskNV,
+    MeshNV,
+    RayGeneration,
+    Intersection,
+    AnyHit,
+    ClosestHit,
+    Miss,
+    Callable,
+}
+
+impl ExecutionModel {
+    #[inline]
+    fn from_spirv(value: u32) -> Option<ExecutionModel> {
+        Some(match value {
+            0 => ExecutionModel::Vertex,
+            1 => ExecutionModel::TessellationControl,
+            2 => ExecutionModel::TessellationEvaluation,
+            3 => ExecutionModel::Geometry,
+            4 => ExecutionModel::Fragment,
+            5 => ExecutionModel::GLCompute,
+            6 => ExecutionModel::Kernel,
+            5267 => ExecutionModel::TaskNV,
+            5268 => ExecutionModel::MeshNV,
+            5313 => ExecutionModel::RayGeneration,
+            5314 => ExecutionModel::Intersection,
+            5315 => ExecutionModel::AnyHit,
+            5316 => ExecutionModel::ClosestHit,
+            5317 => ExecutionModel::Miss,
+            5318 => ExecutionModel::Callable,
+            _ => return None,
+        })
+    }
+}
+
+impl From<GraphicsShaderType> for ExecutionModel {
+    #[inline]
+    fn from(ty: GraphicsShaderType) -> ExecutionModel {
+        match ty {
+            GraphicsShaderType::Vertex => ExecutionModel::Vertex,
+            GraphicsShaderType::TessellationControl => ExecutionModel::TessellationControl,
+            GraphicsShaderType::TessellationEvaluation => ExecutionModel::TessellationEvaluation,
+            GraphicsShaderType::Geometry(_) => ExecutionModel::Geometry,
+            GraphicsShaderType::Fragment => ExecutionModel::Fragment,
+        }
+    }
+}
+
+// Error when checking that a shader module contains an entry point.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum EntryPointError {
+    // There is no entry point with this name.
+    NotFound,
+    // There are entry points with this name, but none of them has the requested execution model.
+    WrongExecutionModel,
+}
+
+// Extracts the `OpEntryPoint` instructions of some SPIR-V code. Returns an empty list if the
+// header of the code is invalid.
+fn reflect_entry_points(spirv: &[u32]) -> Vec<(CString, ExecutionModel)> {
+    const MAGIC_NUMBER: u32 = 0x07230203;
+    const OP_ENTRY_POINT: u32 = 15;
+    const OP_FUNCTION: u32 = 54;
+
+    let swap = match spirv.first() {
+        Some(&MAGIC_NUMBER) => false,
+        Some(&magic) if magic.swap_bytes() == MAGIC_NUMBER => true,
+        _ => return Vec::new(),
+    };
+    let word = |index: usize| {
+        if swap {
+            spirv[index].swap_bytes()
+        } else {
+            spirv[index]
+        }
+    };
+
+    let mut entry_points = Vec::new();
+    // The first five words are the header.
+    let mut index = 5;
+
+    while index < spirv.len() {
+        let word_count = (word(index) >> 16) as usize;
+        let opcode = word(index) & 0xffff;
+
+        if word_count == 0 || index + word_count > spirv.len() {
+            break;
+        }
+
+        // All the entry points are declared before the first function.
+        if opcode == OP_FUNCTION {
+            break;
+        }
+
+        if opcode == OP_ENTRY_POINT && word_count >= 4 {
+            // The name is a nul-terminated UTF-8 string packed in little-endian words, after
+            // the execution model and the id of the function.
+            let mut name = Vec::new();
+            'name: for i in (index + 3)..(index + word_count) {
+                for &byte in &word(i).to_le_bytes() {
+                    if byte == 0 {
+                        break 'name;
+                    }
+                    name.push(byte);
+                }
+            }
+
+            if let Some(model) = ExecutionModel::from_spirv(word(index + 1)) {
+                entry_points.push((CString::new(name).unwrap(), model));
+            }
+        }
+
+        index += word_count;
+    }
+
+    entry_points
+}
+
 unsafe impl VulkanObject for ShaderModule {
     type Object = vk::ShaderModule;
 
@@ -342,6 +612,13 @@ pub unsafe trait EntryPointAbstract {
 
     /// Returns the pipeline layout used by the shader stage.
     fn layout(&self) -> &Self::PipelineLayout;
+
+    /// Returns the size of the local workgroups of the shader, if it is a compute shader whose
+    /// `LocalSize` execution mode is known.
+    #[inline]
+    fn local_size(&self) -> Option<[u32; 3]> {
+        None
+    }
 }
 
 /// Represents the entry point of a compute shader in a shader module.
@@ -352,9 +629,23 @@ pub struct ComputeEntryPoint<'a, S, L> {
     module: &'a ShaderModule,
     name: &'a CStr,
     layout: L,
+    local_size: Option<[u32; 3]>,
     marker: PhantomData<S>,
 }
 
+impl<'a, S, L> ComputeEntryPoint<'a, S, L> {
+    /// Sets the size of the local workgroups of the shader, as declared by its `LocalSize`
+    /// execution mode.
+    ///
+    /// This is called automatically by the code generated by `vulkano-shaders`. It is used by
+    /// `AutoCommandBufferBuilder::dispatch_for` to compute the number of workgroups.
+    #[inline]
+    pub fn with_local_size(mut self, local_size: [u32; 3]) -> ComputeEntryPoint<'a, S, L> {
+        self.local_size = Some(local_size);
+        self
+    }
+}
+
 unsafe impl<'a, S, L> EntryPointAbstract for ComputeEntryPoint<'a, S, L>
 where
     L: PipelineLayoutDesc,
@@ -377,6 +668,11 @@ where
     fn layout(&self) -> &L {
         &self.layout
     }
+
+    #[inline]
+    fn local_size(&self) -> Option<[u32; 3]> {
+        self.local_size
+    }
 }
 
 /// A dummy that implements `GraphicsEntryPointAbstract` and `EntryPointAbstract`.
@@ -456,6 +752,102 @@ pub struct ShaderInterfaceDefEntry {
     pub name: Option<Cow<'static, str>>,
 }
 
+/// Interface of a shader stage obtained by reflecting its SPIR-V code.
+///
+/// Can be obtained by calling `vertex_input()` on a shader module.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate vulkano;
+/// # fn main() {
+/// use std::ffi::CStr;
+/// # let module: std::sync::Arc<vulkano::pipeline::shader::ShaderModule> = return;
+///
+/// #[derive(Default, Copy, Clone)]
+/// struct MyVertex {
+///     position: [f32; 2],
+/// }
+/// impl_vertex!(MyVertex, position);
+///
+/// let name = CStr::from_bytes_with_nul(b"main\0").unwrap();
+/// let input = module.vertex_input(name).unwrap();
+///
+/// // Returns an error if `MyVertex` doesn't match the inputs of the shader.
+/// let attributes = input.vertex_attributes::<MyVertex>(0).unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReflectedShaderInterface {
+    elements: Vec<ShaderInterfaceDefEntry>,
+}
+
+impl ReflectedShaderInterface {
+    /// Checks that the vertex type `V` provides all the inputs of the interface with matching
+    /// formats, and returns the vertex attributes to read them from binding `binding`, as
+    /// `(location, binding, infos)`.
+    ///
+    /// The inputs are matched with the members of `V` by name, therefore the SPIR-V code must
+    /// contain the names of its inputs. Returns `UnnamedAttribute` if the names were stripped.
+    pub fn vertex_attributes<V>(
+        &self,
+        binding: u32,
+    ) -> Result<Vec<(u32, u32, AttributeInfo)>, IncompatibleVertexDefinitionError>
+    where
+        V: Vertex,
+    {
+        if let Some(element) = self.elements.iter().find(|element| element.name.is_none()) {
+            return Err(IncompatibleVertexDefinitionError::UnnamedAttribute {
+                location: element.location.start,
+            });
+        }
+
+        let (_, attributes) = SingleBufferDefinition::<V>::new().definition(self)?;
+        Ok(attributes
+            .map(|(location, _, infos)| (location, binding, infos))
+            .collect())
+    }
+
+    /// Returns the vertex attributes to read the inputs of the interface from binding `binding`,
+    /// if the vertices are laid out tightly in the order of the locations, as
+    /// `(location, binding, infos)`. Also returns the size of a vertex.
+    ///
+    /// This can be used to build the vertex input state when there is no corresponding `Vertex`
+    /// type.
+    pub fn packed_vertex_attributes(
+        &self,
+        binding: u32,
+    ) -> (usize, Vec<(u32, u32, AttributeInfo)>) {
+        let mut offset = 0;
+        let mut attributes = Vec::new();
+
+        for element in &self.elements {
+            for location in element.location.clone() {
+                attributes.push((
+                    location,
+                    binding,
+                    AttributeInfo {
+                        offset,
+                        format: element.format,
+                    },
+                ));
+                offset += element.format.size().unwrap();
+            }
+        }
+
+        (offset, attributes)
+    }
+}
+
+unsafe impl ShaderInterfaceDef for ReflectedShaderInterface {
+    type Iter = VecIntoIter<ShaderInterfaceDefEntry>;
+
+    #[inline]
+    fn elements(&self) -> VecIntoIter<ShaderInterfaceDefEntry> {
+        self.elements.clone().into_iter()
+    }
+}
+
 /// Description of an empty shader interface.
 #[derive(Debug, Copy, Clone)]
 pub struct EmptyShaderInterfaceDef;
@@ -671,3 +1063,124 @@ pub struct SpecializationMapEntry {
     /// Size of the data in bytes. Must match the size of the constant (`4` for booleans).
     pub size: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use pipeline::shader::reflect_entry_points;
+    use pipeline::shader::ExecutionModel;
+    use pipeline::shader::ReflectedShaderInterface;
+    use pipeline::shader::ShaderInterfaceDefEntry;
+    use pipeline::vertex::IncompatibleVertexDefinitionError;
+    use std::borrow::Cow;
+    use std::ffi::CString;
+
+    #[test]
+    fn reflect() {
+        let spirv = [
+            // Header.
+            0x07230203,
+            0x00010000,
+            0,
+            10,
+            0,
+            // OpEntryPoint GLCompute %1 "main"
+            (5 << 16) | 15,
+            5,
+            1,
+            u32::from_le_bytes(*b"main"),
+            0,
+            // OpEntryPoint Fragment %2 "frag" %3
+            (6 << 16) | 15,
+            4,
+            2,
+            u32::from_le_bytes(*b"frag"),
+            0,
+            3,
+            // OpFunction
+            (5 << 16) | 54,
+            4,
+            1,
+            0,
+            5,
+        ];
+
+        let entry_points = reflect_entry_points(&spirv);
+        assert_eq!(
+            entry_points,
+            vec![
+                (CString::new("main").unwrap(), ExecutionModel::GLCompute),
+                (CString::new("frag").unwrap(), ExecutionModel::Fragment),
+            ]
+        );
+
+        let swapped: Vec<u32> = spirv.iter().map(|w| w.swap_bytes()).collect();
+        assert_eq!(reflect_entry_points(&swapped), entry_points);
+    }
+
+    #[test]
+    fn reflect_invalid_header() {
+        assert!(reflect_entry_points(&[0, 1, 2, 3, 4]).is_empty());
+        assert!(reflect_entry_points(&[]).is_empty());
+    }
+
+    #[test]
+    fn reflected_vertex_attributes() {
+        #[derive(Default, Copy, Clone)]
+        struct Good {
+            color: [f32; 4],
+            position: [f32; 2],
+        }
+        ::impl_vertex!(Good, color, position);
+
+        #[derive(Default, Copy, Clone)]
+        struct Bad {
+            position: [f32; 3],
+        }
+        ::impl_vertex!(Bad, position);
+
+        let interface = ReflectedShaderInterface {
+            elements: vec![
+                ShaderInterfaceDefEntry {
+                    location: 0..1,
+                    format: Format::R32G32Sfloat,
+                    name: Some(Cow::Borrowed("position")),
+                },
+                ShaderInterfaceDefEntry {
+                    location: 1..2,
+                    format: Format::R32G32B32A32Sfloat,
+                    name: Some(Cow::Borrowed("color")),
+                },
+            ],
+        };
+
+        let attributes = interface.vertex_attributes::<Good>(2).unwrap();
+        assert_eq!(attributes.len(), 2);
+        assert_eq!((attributes[0].0, attributes[0].1), (0, 2));
+        assert_eq!(attributes[0].2.offset, 16);
+        assert_eq!((attributes[1].0, attributes[1].1), (1, 2));
+        assert_eq!(attributes[1].2.offset, 0);
+
+        match interface.vertex_attributes::<Bad>(0) {
+            Err(IncompatibleVertexDefinitionError::FormatMismatch { .. }) => (),
+            _ => panic!(),
+        }
+
+        let (stride, packed) = interface.packed_vertex_attributes(0);
+        assert_eq!(stride, 24);
+        assert_eq!(packed[1].2.offset, 8);
+
+        // The names of the inputs were stripped from the SPIR-V code.
+        let stripped = ReflectedShaderInterface {
+            elements: vec![ShaderInterfaceDefEntry {
+                location: 0..1,
+                format: Format::R32G32Sfloat,
+                name: None,
+            }],
+        };
+        match stripped.vertex_attributes::<Good>(0) {
+            Err(IncompatibleVertexDefinitionError::UnnamedAttribute { location: 0 }) => (),
+            _ => panic!(),
+        }
+    }
+}
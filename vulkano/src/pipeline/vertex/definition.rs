@@ -89,6 +89,13 @@ pub enum IncompatibleVertexDefinitionError {
         /// The format in the vertex definition.
         definition: (VertexMemberTy, usize),
     },
+
+    /// An attribute of the vertex shader has no name, therefore it can't be matched with the
+    /// vertex source.
+    UnnamedAttribute {
+        /// First location of the attribute.
+        location: u32,
+    },
 }
 
 impl error::Error for IncompatibleVertexDefinitionError {}
@@ -105,6 +112,9 @@ impl fmt::Display for IncompatibleVertexDefinitionError {
                 IncompatibleVertexDefinitionError::FormatMismatch { .. } => {
                     "the format of an attribute does not match"
                 }
+                IncompatibleVertexDefinitionError::UnnamedAttribute { .. } => {
+                    "an attribute of the vertex shader has no name"
+                }
             }
         )
     }
@@ -0,0 +1,183 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use buffer::BufferUsage;
+use buffer::CpuAccessibleBuffer;
+use buffer::TypedBufferAccess;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::DrawIndexedError;
+use command_buffer::DynamicState;
+use descriptor::descriptor_set::DescriptorSetsCollection;
+use device::Device;
+use memory::DeviceMemoryAllocError;
+use pipeline::input_assembly::Index;
+use pipeline::vertex::Vertex;
+use pipeline::vertex::VertexSource;
+use pipeline::GraphicsPipelineAbstract;
+
+/// A vertex buffer and an index buffer that are drawn together.
+///
+/// `V` is the type of the vertices and `I` the type of the indices. `draw` binds both buffers
+/// and draws all the indices of the index buffer.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::pipeline::vertex::Mesh;
+/// # use std::sync::Arc;
+/// # use vulkano::command_buffer::AutoCommandBufferBuilder;
+/// # use vulkano::command_buffer::DynamicState;
+/// # use vulkano::device::Device;
+/// # use vulkano::descriptor::PipelineLayoutAbstract;
+/// # use vulkano::framebuffer::RenderPassAbstract;
+/// # use vulkano::pipeline::GraphicsPipeline;
+/// # use vulkano::pipeline::vertex::SingleBufferDefinition;
+/// # #[derive(Default, Copy, Clone)]
+/// # struct Vertex { position: [f32; 2] }
+/// # vulkano::impl_vertex!(Vertex, position);
+/// # let device: Arc<Device> = return;
+/// # let pipeline: Arc<GraphicsPipeline<SingleBufferDefinition<Vertex>,
+/// #     Box<dyn PipelineLayoutAbstract + Send + Sync>,
+/// #     Arc<dyn RenderPassAbstract + Send + Sync>>> = return;
+/// # let mut builder: AutoCommandBufferBuilder = return;
+///
+/// let vertices = vec![Vertex { position: [0.0, 0.0] }; 4];
+/// let mesh = Mesh::from_iter(device, vertices.into_iter(), vec![0u16, 1, 2, 2, 1, 3].into_iter())
+///     .unwrap();
+///
+/// mesh.draw(&mut builder, pipeline, &DynamicState::none(), (), ())
+///     .unwrap();
+/// ```
+pub struct Mesh<V, I, Vb, Ib> {
+    vertex_buffer: Vb,
+    index_buffer: Ib,
+    marker: PhantomData<(V, I)>,
+}
+
+impl<V, I> Mesh<V, I, Arc<CpuAccessibleBuffer<[V]>>, Arc<CpuAccessibleBuffer<[I]>>>
+where
+    V: Vertex,
+    I: Index + Send + Sync + 'static,
+{
+    /// Builds a mesh by uploading the vertices and the indices to new CPU-accessible buffers.
+    pub fn from_iter<Vi, Ii>(
+        device: Arc<Device>,
+        vertices: Vi,
+        indices: Ii,
+    ) -> Result<Self, DeviceMemoryAllocError>
+    where
+        Vi: ExactSizeIterator<Item = V>,
+        Ii: ExactSizeIterator<Item = I>,
+    {
+        let vertex_buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::vertex_buffer(),
+            false,
+            vertices,
+        )?;
+        let index_buffer =
+            CpuAccessibleBuffer::from_iter(device, BufferUsage::index_buffer(), false, indices)?;
+        Ok(Mesh::new(vertex_buffer, index_buffer))
+    }
+}
+
+impl<V, I, Vb, Ib> Mesh<V, I, Vb, Ib>
+where
+    V: Vertex,
+    I: Index + 'static,
+    Vb: TypedBufferAccess<Content = [V]> + Send + Sync + Clone + 'static,
+    Ib: TypedBufferAccess<Content = [I]> + Send + Sync + Clone + 'static,
+{
+    /// Builds a mesh from existing buffers.
+    #[inline]
+    pub fn new(vertex_buffer: Vb, index_buffer: Ib) -> Mesh<V, I, Vb, Ib> {
+        Mesh {
+            vertex_buffer,
+            index_buffer,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the vertex buffer.
+    #[inline]
+    pub fn vertex_buffer(&self) -> &Vb {
+        &self.vertex_buffer
+    }
+
+    /// Returns the index buffer.
+    #[inline]
+    pub fn index_buffer(&self) -> &Ib {
+        &self.index_buffer
+    }
+
+    /// Returns the number of vertices.
+    #[inline]
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_buffer.len() as u32
+    }
+
+    /// Returns the number of indices, which is the number of vertices that `draw` processes.
+    #[inline]
+    pub fn index_count(&self) -> u32 {
+        self.index_buffer.len() as u32
+    }
+
+    /// Adds a command to `builder` that binds the buffers of the mesh and draws all of its
+    /// indices.
+    ///
+    /// This is a shortcut for `AutoCommandBufferBuilder::draw_indexed`.
+    #[inline]
+    pub fn draw<'a, P, Gp, S, Pc>(
+        &self,
+        builder: &'a mut AutoCommandBufferBuilder<P>,
+        pipeline: Gp,
+        dynamic: &DynamicState,
+        sets: S,
+        constants: Pc,
+    ) -> Result<&'a mut AutoCommandBufferBuilder<P>, DrawIndexedError>
+    where
+        Gp: GraphicsPipelineAbstract + VertexSource<Vb> + Send + Sync + 'static + Clone,
+        S: DescriptorSetsCollection,
+    {
+        builder.draw_indexed(
+            pipeline,
+            dynamic,
+            self.vertex_buffer.clone(),
+            self.index_buffer.clone(),
+            sets,
+            constants,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pipeline::vertex::Mesh;
+
+    #[derive(Default, Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+    ::impl_vertex!(Vertex, position);
+
+    #[test]
+    fn counts() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vertices = vec![Vertex::default(); 4];
+        let indices = vec![0u32, 1, 2, 2, 1, 3];
+        let mesh = Mesh::from_iter(device, vertices.into_iter(), indices.into_iter()).unwrap();
+
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.index_count(), 6);
+    }
+}
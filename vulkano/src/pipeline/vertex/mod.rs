@@ -72,6 +72,7 @@ pub use self::definition::VertexDefinition;
 pub use self::definition::VertexSource;
 pub use self::impl_vertex::VertexMember;
 pub use self::instance_buffer::SingleInstanceBufferDefinition;
+pub use self::mesh::Mesh;
 pub use self::one_one::OneVertexOneInstanceDefinition;
 pub use self::single::SingleBufferDefinition;
 pub use self::two::TwoBuffersDefinition;
@@ -83,6 +84,7 @@ mod bufferless;
 mod definition;
 mod impl_vertex;
 mod instance_buffer;
+mod mesh;
 mod one_one;
 mod single;
 mod two;
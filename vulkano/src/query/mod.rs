@@ -28,9 +28,15 @@ use Error;
 use OomError;
 use VulkanObject;
 
+pub use self::readback::QueryResults;
+pub use self::readback::QueryResultsRing;
+
+mod readback;
+
 pub struct UnsafeQueryPool {
     pool: vk::QueryPool,
     device: Arc<Device>,
+    ty: QueryType,
     num_slots: u32,
 }
 
@@ -49,6 +55,19 @@ impl UnsafeQueryPool {
                     return Err(QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled);
                 }
 
+                if (flags.geometry_shader_invocations || flags.geometry_shader_primitives)
+                    && !device.enabled_features().geometry_shader
+                {
+                    return Err(QueryPoolCreationError::GeometryShaderFeatureNotEnabled);
+                }
+
+                if (flags.tessellation_control_shader_patches
+                    || flags.tessellation_evaluation_shader_invocations)
+                    && !device.enabled_features().tessellation_shader
+                {
+                    return Err(QueryPoolCreationError::TessellationShaderFeatureNotEnabled);
+                }
+
                 (vk::QUERY_TYPE_PIPELINE_STATISTICS, flags.into())
             }
         };
@@ -77,10 +96,17 @@ impl UnsafeQueryPool {
         Ok(UnsafeQueryPool {
             pool: pool,
             device: device,
+            ty,
             num_slots: num_slots,
         })
     }
 
+    /// Returns the type of the queries of that query pool.
+    #[inline]
+    pub fn ty(&self) -> QueryType {
+        self.ty
+    }
+
     /// Returns the number of slots of that query pool.
     #[inline]
     pub fn num_slots(&self) -> u32 {
@@ -104,7 +130,7 @@ impl UnsafeQueryPool {
     pub fn queries_range(&self, first_index: u32, count: u32) -> Option<UnsafeQueriesRange> {
         assert!(count >= 1);
 
-        if first_index + count < self.num_slots() {
+        if first_index + count <= self.num_slots() {
             Some(UnsafeQueriesRange {
                 pool: self,
                 first: first_index,
@@ -181,6 +207,56 @@ pub enum QueryType {
     Timestamp,
 }
 
+impl QueryType {
+    /// Returns the number of values that a query of this type produces.
+    ///
+    /// This doesn't include the availability value that is written after the results when
+    /// `with_availability` is set in the `QueryResultFlags`.
+    #[inline]
+    pub fn result_size(&self) -> usize {
+        match *self {
+            QueryType::Occlusion => 1,
+            QueryType::PipelineStatistics(flags) => flags.count(),
+            QueryType::Timestamp => 1,
+        }
+    }
+}
+
+/// How the results of queries are retrieved. Results are always written as 64-bit values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct QueryResultFlags {
+    /// Wait until the results of all the queries are available. If false, the queries whose
+    /// results aren't available yet are skipped.
+    ///
+    /// The queries must eventually be written by a command that was submitted, otherwise the
+    /// wait never ends. When copying the results with a command buffer, waiting on queries that
+    /// are never issued can result in a `VK_ERROR_DEVICE_LOST` error.
+    pub wait: bool,
+    /// Write a value after the results of each query, which is non-zero if the results are
+    /// available and zero otherwise.
+    pub with_availability: bool,
+    /// Write the partial results of queries that aren't available yet. Can't be used with
+    /// timestamp queries.
+    pub partial: bool,
+}
+
+impl QueryResultFlags {
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::QueryResultFlags {
+        let mut result = vk::QUERY_RESULT_64_BIT;
+        if self.wait {
+            result |= vk::QUERY_RESULT_WAIT_BIT;
+        }
+        if self.with_availability {
+            result |= vk::QUERY_RESULT_WITH_AVAILABILITY_BIT;
+        }
+        if self.partial {
+            result |= vk::QUERY_RESULT_PARTIAL_BIT;
+        }
+        result
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct QueryPipelineStatisticFlags {
     pub input_assembly_vertices: bool,
@@ -213,6 +289,102 @@ impl QueryPipelineStatisticFlags {
             compute_shader_invocations: false,
         }
     }
+
+    /// Builds a `QueryPipelineStatisticFlags` with the geometry shader statistics. Requires the
+    /// `geometry_shader` feature.
+    #[inline]
+    pub fn geometry_shader() -> QueryPipelineStatisticFlags {
+        QueryPipelineStatisticFlags {
+            geometry_shader_invocations: true,
+            geometry_shader_primitives: true,
+            ..QueryPipelineStatisticFlags::none()
+        }
+    }
+
+    /// Builds a `QueryPipelineStatisticFlags` with the tessellation shaders statistics. Requires
+    /// the `tessellation_shader` feature.
+    #[inline]
+    pub fn tessellation_shader() -> QueryPipelineStatisticFlags {
+        QueryPipelineStatisticFlags {
+            tessellation_control_shader_patches: true,
+            tessellation_evaluation_shader_invocations: true,
+            ..QueryPipelineStatisticFlags::none()
+        }
+    }
+
+    /// Returns the number of statistics that are enabled, which is the number of values written
+    /// for each query.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.list().iter().filter(|&&enabled| enabled).count()
+    }
+
+    /// Decodes the values written for a single query with these flags.
+    ///
+    /// Vulkan writes one value per enabled statistic, in the order of the fields of
+    /// `QueryPipelineStatisticFlags`. Statistics that aren't enabled are `None` in the result.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the length of `values` isn't equal to `count()`.
+    ///
+    pub fn decode(&self, values: &[u64]) -> PipelineStatistics {
+        assert_eq!(values.len(), self.count());
+
+        let mut values = values.iter().cloned();
+        let mut next = |enabled: bool| if enabled { values.next() } else { None };
+
+        PipelineStatistics {
+            input_assembly_vertices: next(self.input_assembly_vertices),
+            input_assembly_primitives: next(self.input_assembly_primitives),
+            vertex_shader_invocations: next(self.vertex_shader_invocations),
+            geometry_shader_invocations: next(self.geometry_shader_invocations),
+            geometry_shader_primitives: next(self.geometry_shader_primitives),
+            clipping_invocations: next(self.clipping_invocations),
+            clipping_primitives: next(self.clipping_primitives),
+            fragment_shader_invocations: next(self.fragment_shader_invocations),
+            tessellation_control_shader_patches: next(self.tessellation_control_shader_patches),
+            tessellation_evaluation_shader_invocations: next(
+                self.tessellation_evaluation_shader_invocations,
+            ),
+            compute_shader_invocations: next(self.compute_shader_invocations),
+        }
+    }
+
+    #[inline]
+    fn list(&self) -> [bool; 11] {
+        [
+            self.input_assembly_vertices,
+            self.input_assembly_primitives,
+            self.vertex_shader_invocations,
+            self.geometry_shader_invocations,
+            self.geometry_shader_primitives,
+            self.clipping_invocations,
+            self.clipping_primitives,
+            self.fragment_shader_invocations,
+            self.tessellation_control_shader_patches,
+            self.tessellation_evaluation_shader_invocations,
+            self.compute_shader_invocations,
+        ]
+    }
+}
+
+/// Result of a pipeline statistics query, as decoded by `QueryPipelineStatisticFlags::decode`.
+///
+/// Each field is `None` if the corresponding statistic wasn't requested when creating the pool.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PipelineStatistics {
+    pub input_assembly_vertices: Option<u64>,
+    pub input_assembly_primitives: Option<u64>,
+    pub vertex_shader_invocations: Option<u64>,
+    pub geometry_shader_invocations: Option<u64>,
+    pub geometry_shader_primitives: Option<u64>,
+    pub clipping_invocations: Option<u64>,
+    pub clipping_primitives: Option<u64>,
+    pub fragment_shader_invocations: Option<u64>,
+    pub tessellation_control_shader_patches: Option<u64>,
+    pub tessellation_evaluation_shader_invocations: Option<u64>,
+    pub compute_shader_invocations: Option<u64>,
 }
 
 impl Into<vk::QueryPipelineStatisticFlags> for QueryPipelineStatisticFlags {
@@ -272,6 +444,12 @@ pub enum QueryPoolCreationError {
     OomError(OomError),
     /// A pipeline statistics pool was requested but the corresponding feature wasn't enabled.
     PipelineStatisticsQueryFeatureNotEnabled,
+    /// Geometry shader statistics were requested but the `geometry_shader` feature wasn't
+    /// enabled.
+    GeometryShaderFeatureNotEnabled,
+    /// Tessellation shader statistics were requested but the `tessellation_shader` feature
+    /// wasn't enabled.
+    TessellationShaderFeatureNotEnabled,
 }
 
 impl error::Error for QueryPoolCreationError {
@@ -296,6 +474,14 @@ impl fmt::Display for QueryPoolCreationError {
                     "a pipeline statistics pool was requested but the corresponding feature \
                  wasn't enabled"
                 }
+                QueryPoolCreationError::GeometryShaderFeatureNotEnabled => {
+                    "geometry shader statistics were requested but the `geometry_shader` feature \
+                 wasn't enabled"
+                }
+                QueryPoolCreationError::TessellationShaderFeatureNotEnabled => {
+                    "tessellation shader statistics were requested but the `tessellation_shader` \
+                 feature wasn't enabled"
+                }
             }
         )
     }
@@ -330,9 +516,7 @@ impl OcclusionQueriesPool {
             inner: match UnsafeQueryPool::new(device, QueryType::Occlusion, num_slots) {
                 Ok(q) => q,
                 Err(QueryPoolCreationError::OomError(err)) => return Err(err),
-                Err(QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled) => {
-                    unreachable!()
-                }
+                Err(_) => unreachable!(),
             },
         })
     }
@@ -365,6 +549,7 @@ unsafe impl DeviceOwned for OcclusionQueriesPool {
 #[cfg(test)]
 mod tests {
     use query::OcclusionQueriesPool;
+    use query::PipelineStatistics;
     use query::QueryPipelineStatisticFlags;
     use query::QueryPoolCreationError;
     use query::QueryType;
@@ -386,4 +571,22 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn decode_statistics() {
+        let flags = QueryPipelineStatisticFlags {
+            vertex_shader_invocations: true,
+            ..QueryPipelineStatisticFlags::tessellation_shader()
+        };
+        assert_eq!(flags.count(), 3);
+        assert_eq!(
+            flags.decode(&[5, 6, 7]),
+            PipelineStatistics {
+                vertex_shader_invocations: Some(5),
+                tessellation_control_shader_patches: Some(6),
+                tessellation_evaluation_shader_invocations: Some(7),
+                ..PipelineStatistics::default()
+            }
+        );
+    }
 }
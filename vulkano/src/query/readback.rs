@@ -0,0 +1,247 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::iter;
+use std::sync::Arc;
+
+use buffer::cpu_access::ReadLock;
+use buffer::cpu_access::ReadLockError;
+use buffer::BufferUsage;
+use buffer::CpuAccessibleBuffer;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::CopyQueryPoolResultsError;
+use device::DeviceOwned;
+use memory::DeviceMemoryAllocError;
+use query::QueryResultFlags;
+use query::UnsafeQueryPool;
+
+/// Ring of host-visible buffers that the results of a query pool are copied to, in order to read
+/// them from the CPU without blocking.
+///
+/// Each frame, call `copy_results` at the end of the command buffer that uses the queries. This
+/// records a copy of the results of all the queries of the pool, with their availability, to the
+/// next buffer of the ring. Then call `read_oldest` to read the results that were copied
+/// `num_buffers` frames ago, which are usually available by then.
+///
+/// Contrary to waiting for the results of the queries, reading never blocks. If the GPU is still
+/// writing the buffer, `read_oldest` returns an error. If a query hadn't completed when the copy
+/// was executed, its results are reported as unavailable. The ring never resets the queries,
+/// which is up to the user.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use vulkano::query::QueryResultsRing;
+/// use vulkano::query::QueryType;
+/// use vulkano::query::UnsafeQueryPool;
+/// # use vulkano::command_buffer::AutoCommandBufferBuilder;
+/// # let device: Arc<vulkano::device::Device> = return;
+/// # let mut builder: AutoCommandBufferBuilder = return;
+///
+/// let pool = Arc::new(UnsafeQueryPool::new(device.clone(), QueryType::Timestamp, 2).unwrap());
+/// let mut ring = QueryResultsRing::new(pool.clone(), 3).unwrap();
+///
+/// // ... write the timestamps in `builder` ...
+/// ring.copy_results(&mut builder).unwrap();
+/// // ... build and submit the command buffer ...
+///
+/// if let Ok(results) = ring.read_oldest() {
+///     if let (Some(start), Some(end)) = (results.get(0), results.get(1)) {
+///         println!("frame took {} ticks", end[0] - start[0]);
+///     }
+/// }
+/// ```
+pub struct QueryResultsRing {
+    pool: Arc<UnsafeQueryPool>,
+
+    // The buffers of the ring, zero-initialized so that the queries of buffers that were never
+    // written are reported as unavailable.
+    buffers: Vec<Arc<CpuAccessibleBuffer<[u64]>>>,
+
+    // Number of values written for each query, including the availability.
+    values_per_query: usize,
+
+    // Index of the buffer that the next copy writes to. This is also the buffer with the oldest
+    // results.
+    next: usize,
+}
+
+impl QueryResultsRing {
+    /// Builds a ring of `num_buffers` buffers for the results of `pool`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `num_buffers` is 0.
+    ///
+    pub fn new(
+        pool: Arc<UnsafeQueryPool>,
+        num_buffers: usize,
+    ) -> Result<QueryResultsRing, DeviceMemoryAllocError> {
+        assert!(num_buffers != 0);
+
+        let values_per_query = pool.ty().result_size() + 1;
+        let len = values_per_query * pool.num_slots() as usize;
+
+        let buffers = (0..num_buffers)
+            .map(|_| {
+                CpuAccessibleBuffer::from_iter(
+                    pool.device().clone(),
+                    BufferUsage::transfer_destination(),
+                    true,
+                    iter::repeat(0).take(len),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(QueryResultsRing {
+            pool,
+            buffers,
+            values_per_query,
+            next: 0,
+        })
+    }
+
+    /// Returns the query pool whose results are copied.
+    #[inline]
+    pub fn pool(&self) -> &Arc<UnsafeQueryPool> {
+        &self.pool
+    }
+
+    /// Returns the number of buffers of the ring.
+    #[inline]
+    pub fn num_buffers(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Adds a command to `builder` that copies the results of all the queries of the pool to the
+    /// next buffer of the ring, then moves to the following buffer.
+    ///
+    /// The copy doesn't wait for the queries to complete. It also never resets the queries, so
+    /// they must be reset before they are written again, for example with
+    /// `UnsafeCommandBufferBuilder::reset_query_pool`. Otherwise the results of a previous frame
+    /// can be reported as available if the new results aren't written yet when the copy is
+    /// executed.
+    pub fn copy_results<P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<P>,
+    ) -> Result<(), CopyQueryPoolResultsError> {
+        let flags = QueryResultFlags {
+            with_availability: true,
+            ..QueryResultFlags::default()
+        };
+
+        builder.copy_query_pool_results(
+            self.pool.clone(),
+            0,
+            self.pool.num_slots(),
+            self.buffers[self.next].clone(),
+            flags,
+        )?;
+
+        self.next = (self.next + 1) % self.buffers.len();
+        Ok(())
+    }
+
+    /// Reads the oldest results of the ring, which are overwritten by the next call to
+    /// `copy_results`.
+    ///
+    /// Returns an error if the GPU is still writing to the buffer.
+    #[inline]
+    pub fn read_oldest(&self) -> Result<QueryResults<'_>, ReadLockError> {
+        Ok(QueryResults {
+            data: self.buffers[self.next].read()?,
+            values_per_query: self.values_per_query,
+        })
+    }
+}
+
+/// Results of queries copied with their availability, returned by `QueryResultsRing`.
+pub struct QueryResults<'a> {
+    data: ReadLock<'a, [u64]>,
+    values_per_query: usize,
+}
+
+impl<'a> QueryResults<'a> {
+    /// Returns the number of queries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len() / self.values_per_query
+    }
+
+    /// Returns the results of the query at the given index, or `None` if they weren't available
+    /// when they were copied.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `index` is out of range.
+    ///
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&[u64]> {
+        available_results(&self.data, self.values_per_query, index)
+    }
+
+    /// Returns an iterator to the results of all the queries. See `get`.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Option<&[u64]>> {
+        (0..self.len()).map(move |index| self.get(index))
+    }
+}
+
+// Returns the results of the query at `index` in `data`, which contains `values_per_query` values
+// for each query with the availability last, or `None` if the availability is zero.
+#[inline]
+fn available_results(data: &[u64], values_per_query: usize, index: usize) -> Option<&[u64]> {
+    let start = index * values_per_query;
+    let (values, availability) =
+        data[start..start + values_per_query].split_at(values_per_query - 1);
+
+    if availability[0] != 0 {
+        Some(values)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::available_results;
+    use query::QueryResultsRing;
+    use query::QueryType;
+    use query::UnsafeQueryPool;
+    use std::sync::Arc;
+
+    #[test]
+    fn availability() {
+        let data = [5, 1, 6, 0, 7, 2];
+        assert_eq!(available_results(&data, 2, 0), Some(&[5][..]));
+        assert_eq!(available_results(&data, 2, 1), None);
+        assert_eq!(available_results(&data, 2, 2), Some(&[7][..]));
+
+        let data = [5, 6, 1];
+        assert_eq!(available_results(&data, 3, 0), Some(&[5, 6][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn availability_out_of_range() {
+        available_results(&[5, 1], 2, 1);
+    }
+
+    #[test]
+    fn initially_unavailable() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = Arc::new(UnsafeQueryPool::new(device, QueryType::Timestamp, 4).unwrap());
+        let ring = QueryResultsRing::new(pool, 2).unwrap();
+
+        let results = ring.read_oldest().unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|r| r.is_none()));
+    }
+}
@@ -62,6 +62,7 @@
 //!
 // FIXME: restrictions aren't checked yet
 
+use std::collections::HashMap;
 use std::error;
 use std::fmt;
 use std::mem::MaybeUninit;
@@ -71,6 +72,9 @@ use std::sync::Arc;
 use check_errors;
 use device::Device;
 use device::DeviceOwned;
+use format::Format;
+use format::FormatTy;
+use image::ImageTiling;
 use vk;
 use Error;
 use OomError;
@@ -84,6 +88,7 @@ pub struct Sampler {
     device: Arc<Device>,
     compare_mode: bool,
     unnormalized: bool,
+    reduction_mode: SamplerReductionMode,
     usable_with_float_formats: bool,
     usable_with_int_formats: bool,
     usable_with_swizzling: bool,
@@ -117,6 +122,40 @@ impl Sampler {
         .unwrap()
     }
 
+    /// Same as `simple_repeat_linear`, but falls back to nearest sampling if optimally tiled
+    /// images of the given format can't be filtered linearly.
+    ///
+    /// Use this function when the format of the sampled images isn't known in advance, for
+    /// example for textures loaded from files.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if out of memory or the maximum number of samplers has exceeded.
+    ///
+    pub fn simple_repeat_linear_for_format(device: Arc<Device>, format: Format) -> Arc<Sampler> {
+        if device
+            .physical_device()
+            .is_format_linear_filterable(format, ImageTiling::Optimal)
+        {
+            return Sampler::simple_repeat_linear(device);
+        }
+
+        Sampler::new(
+            device,
+            Filter::Nearest,
+            Filter::Nearest,
+            MipmapMode::Nearest,
+            SamplerAddressMode::Repeat,
+            SamplerAddressMode::Repeat,
+            SamplerAddressMode::Repeat,
+            0.0,
+            1.0,
+            0.0,
+            1_000.0,
+        )
+        .unwrap()
+    }
+
     /// Shortcut for creating a sampler with linear sampling, that only uses the main level of
     /// images, and with the repeat mode for borders.
     ///
@@ -158,7 +197,9 @@ impl Sampler {
     ///
     /// `max_anisotropy` must be greater than or equal to 1.0. If greater than 1.0, the
     /// implementation will use anisotropic filtering. Using a value greater than 1.0 requires
-    /// the `sampler_anisotropy` feature to be enabled when creating the device.
+    /// the `sampler_anisotropy` feature to be enabled when creating the device. The value is
+    /// clamped to what the device supports, as with `clamp_anisotropy`. Use a `SamplerDesc` with
+    /// `strict_anisotropy` to get an error instead.
     ///
     /// `min_lod` and `max_lod` are respectively the minimum and maximum mipmap level to use.
     /// `max_lod` must always be greater than or equal to `min_lod`.
@@ -196,6 +237,9 @@ impl Sampler {
             min_lod,
             max_lod,
             None,
+            SamplerReductionMode::WeightedAverage,
+            None,
+            false,
         )
     }
 
@@ -243,9 +287,146 @@ impl Sampler {
             min_lod,
             max_lod,
             Some(compare),
+            SamplerReductionMode::WeightedAverage,
+            None,
+            false,
+        )
+    }
+
+    /// Creates a new `Sampler` with the given behavior.
+    ///
+    /// Contrary to `new`, the texels that are read by the filter are combined according to
+    /// `reduction_mode` instead of always being averaged. For example a `Min` sampler with a
+    /// `Linear` filter returns the smallest of the texels it reads, which is useful to build
+    /// hierarchical depth buffers.
+    ///
+    /// Using a reduction mode other than `WeightedAverage` requires the
+    /// `VK_EXT_sampler_filter_minmax` extension to be enabled on the device. Such a sampler can
+    /// only be used with images whose format supports the `sampled_image_filter_minmax` format
+    /// feature. If `filter_minmax_single_component_formats` is true in the extended properties
+    /// of the physical device, this is guaranteed for the common single-component formats, such
+    /// as `R32Sfloat` and `D32Sfloat`.
+    ///
+    /// # Panic
+    ///
+    /// Same panic reasons as `new`.
+    ///
+    #[inline(always)]
+    pub fn with_reduction_mode(
+        device: Arc<Device>,
+        mag_filter: Filter,
+        min_filter: Filter,
+        mipmap_mode: MipmapMode,
+        address_u: SamplerAddressMode,
+        address_v: SamplerAddressMode,
+        address_w: SamplerAddressMode,
+        mip_lod_bias: f32,
+        max_anisotropy: f32,
+        min_lod: f32,
+        max_lod: f32,
+        reduction_mode: SamplerReductionMode,
+    ) -> Result<Arc<Sampler>, SamplerCreationError> {
+        Sampler::new_impl(
+            device,
+            mag_filter,
+            min_filter,
+            mipmap_mode,
+            address_u,
+            address_v,
+            address_w,
+            mip_lod_bias,
+            max_anisotropy,
+            min_lod,
+            max_lod,
+            None,
+            reduction_mode,
+            None,
+            false,
+        )
+    }
+
+    /// Creates a new `Sampler` with the given behavior.
+    ///
+    /// Contrary to `new`, the address modes that are `ClampToCustomBorder` use `border_color`
+    /// instead of one of the predefined border colors. Passing `ClampToBorder` in this case is an
+    /// error.
+    ///
+    /// `format` is the format of the image views that the sampler will be used with. The
+    /// component type of the format must match the type of `border_color`. Passing `None`
+    /// requires the `custom_border_color_without_format` feature to be enabled.
+    ///
+    /// Using a custom border color requires the `VK_EXT_custom_border_color` extension and the
+    /// `custom_border_colors` feature to be enabled on the device.
+    ///
+    /// # Panic
+    ///
+    /// Same panic reasons as `new`.
+    ///
+    #[inline(always)]
+    pub fn with_custom_border_color(
+        device: Arc<Device>,
+        mag_filter: Filter,
+        min_filter: Filter,
+        mipmap_mode: MipmapMode,
+        address_u: SamplerAddressMode,
+        address_v: SamplerAddressMode,
+        address_w: SamplerAddressMode,
+        mip_lod_bias: f32,
+        max_anisotropy: f32,
+        min_lod: f32,
+        max_lod: f32,
+        border_color: CustomBorderColor,
+        format: Option<Format>,
+    ) -> Result<Arc<Sampler>, SamplerCreationError> {
+        Sampler::new_impl(
+            device,
+            mag_filter,
+            min_filter,
+            mipmap_mode,
+            address_u,
+            address_v,
+            address_w,
+            mip_lod_bias,
+            max_anisotropy,
+            min_lod,
+            max_lod,
+            None,
+            SamplerReductionMode::WeightedAverage,
+            Some((border_color, format)),
+            false,
         )
     }
 
+    /// Returns `max_anisotropy` clamped to the anisotropy that samplers created on `device` can
+    /// use.
+    ///
+    /// The result is 1.0, which disables anisotropic filtering, if the `sampler_anisotropy`
+    /// feature isn't enabled on the device. Otherwise it is at most the `max_sampler_anisotropy`
+    /// limit. This allows requesting, for example, 16x anisotropy on every device, even on
+    /// devices that only support 8x.
+    ///
+    /// The constructors of `Sampler` already clamp the anisotropy they are given, so this is
+    /// mostly useful to know the anisotropy that a sampler will use.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use vulkano::sampler::Sampler;
+    /// # let device: std::sync::Arc<vulkano::device::Device> = return;
+    ///
+    /// let anisotropy = Sampler::clamp_anisotropy(&device, 16.0);
+    /// println!("16x anisotropy is clamped to {}x", anisotropy);
+    /// ```
+    #[inline]
+    pub fn clamp_anisotropy(device: &Device, max_anisotropy: f32) -> f32 {
+        if !device.enabled_features().sampler_anisotropy {
+            return 1.0;
+        }
+
+        let limit = device.physical_device().limits().max_sampler_anisotropy();
+        max_anisotropy.max(1.0).min(limit)
+    }
+
     fn new_impl(
         device: Arc<Device>,
         mag_filter: Filter,
@@ -259,11 +440,19 @@ impl Sampler {
         min_lod: f32,
         max_lod: f32,
         compare: Option<Compare>,
+        reduction_mode: SamplerReductionMode,
+        custom_border_color: Option<(CustomBorderColor, Option<Format>)>,
+        strict_anisotropy: bool,
     ) -> Result<Arc<Sampler>, SamplerCreationError> {
         assert!(max_anisotropy >= 1.0);
         assert!(min_lod <= max_lod);
 
-        // Check max anisotropy.
+        // Check max anisotropy, or clamp it to what the device supports.
+        let max_anisotropy = if !strict_anisotropy {
+            Sampler::clamp_anisotropy(&device, max_anisotropy)
+        } else {
+            max_anisotropy
+        };
         if max_anisotropy > 1.0 {
             if !device.enabled_features().sampler_anisotropy {
                 return Err(SamplerCreationError::SamplerAnisotropyFeatureNotEnabled);
@@ -299,6 +488,13 @@ impl Sampler {
             }
         }
 
+        // Check sampler_filter_minmax extension support
+        if reduction_mode != SamplerReductionMode::WeightedAverage {
+            if !device.loaded_extensions().ext_sampler_filter_minmax {
+                return Err(SamplerCreationError::SamplerFilterMinmaxExtensionNotEnabled);
+            }
+        }
+
         // Handling border color.
         let border_color = address_u.border_color();
         let border_color = match (border_color, address_v.border_color()) {
@@ -318,11 +514,75 @@ impl Sampler {
             (b, None) => b,
         };
 
+        // Check custom border color.
+        if let Some((color, format)) = custom_border_color {
+            if !device.loaded_extensions().ext_custom_border_color {
+                return Err(SamplerCreationError::CustomBorderColorExtensionNotEnabled);
+            }
+
+            if !device.enabled_features().custom_border_colors {
+                return Err(SamplerCreationError::CustomBorderColorsFeatureNotEnabled);
+            }
+
+            if border_color.is_some() {
+                return Err(SamplerCreationError::CustomBorderColorWithPredefinedBorderColor);
+            }
+
+            match format {
+                Some(format) => {
+                    if !color.compatible_with_format(format) {
+                        return Err(SamplerCreationError::CustomBorderColorFormatMismatch);
+                    }
+                }
+                None => {
+                    if !device.enabled_features().custom_border_color_without_format {
+                        return Err(
+                            SamplerCreationError::CustomBorderColorWithoutFormatFeatureNotEnabled,
+                        );
+                    }
+                }
+            }
+        } else if [address_u, address_v, address_w]
+            .iter()
+            .any(|&mode| mode == SamplerAddressMode::ClampToCustomBorder)
+        {
+            return Err(SamplerCreationError::CustomBorderColorMissing);
+        }
+
         let vk = device.pointers();
         let sampler = unsafe {
+            let reduction_mode_infos = if reduction_mode != SamplerReductionMode::WeightedAverage {
+                Some(vk::SamplerReductionModeCreateInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_SAMPLER_REDUCTION_MODE_CREATE_INFO_EXT,
+                    pNext: ptr::null(),
+                    reductionMode: reduction_mode as u32,
+                })
+            } else {
+                None
+            };
+
+            let custom_border_color_infos = custom_border_color.map(|(color, format)| {
+                vk::SamplerCustomBorderColorCreateInfoEXT {
+                    sType: vk::STRUCTURE_TYPE_SAMPLER_CUSTOM_BORDER_COLOR_CREATE_INFO_EXT,
+                    pNext: reduction_mode_infos
+                        .as_ref()
+                        .map(|i| i as *const _ as *const _)
+                        .unwrap_or(ptr::null()),
+                    customBorderColor: color.to_vk(),
+                    format: format.map(|f| f as u32).unwrap_or(vk::FORMAT_UNDEFINED),
+                }
+            });
+
             let infos = vk::SamplerCreateInfo {
                 sType: vk::STRUCTURE_TYPE_SAMPLER_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: if let Some(ref infos) = custom_border_color_infos {
+                    infos as *const _ as *const _
+                } else {
+                    reduction_mode_infos
+                        .as_ref()
+                        .map(|i| i as *const _ as *const _)
+                        .unwrap_or(ptr::null())
+                },
                 flags: 0, // reserved
                 magFilter: mag_filter as u32,
                 minFilter: min_filter as u32,
@@ -345,7 +605,11 @@ impl Sampler {
                 compareOp: compare.map(|c| c as u32).unwrap_or(0),
                 minLod: min_lod,
                 maxLod: max_lod,
-                borderColor: border_color.map(|b| b as u32).unwrap_or(0),
+                borderColor: match custom_border_color {
+                    Some((CustomBorderColor::Float(_), _)) => vk::BORDER_COLOR_FLOAT_CUSTOM_EXT,
+                    Some((CustomBorderColor::Int(_), _)) => vk::BORDER_COLOR_INT_CUSTOM_EXT,
+                    None => border_color.map(|b| b as u32).unwrap_or(0),
+                },
                 unnormalizedCoordinates: vk::FALSE,
             };
 
@@ -359,11 +623,30 @@ impl Sampler {
             output.assume_init()
         };
 
+        if let Some((color, _)) = custom_border_color {
+            let is_float = match color {
+                CustomBorderColor::Float(_) => true,
+                CustomBorderColor::Int(_) => false,
+            };
+
+            return Ok(Arc::new(Sampler {
+                sampler: sampler,
+                device: device.clone(),
+                compare_mode: compare.is_some(),
+                unnormalized: false,
+                reduction_mode,
+                usable_with_float_formats: is_float,
+                usable_with_int_formats: compare.is_none() && !is_float,
+                usable_with_swizzling: true,
+            }));
+        }
+
         Ok(Arc::new(Sampler {
             sampler: sampler,
             device: device.clone(),
             compare_mode: compare.is_some(),
             unnormalized: false,
+            reduction_mode,
             usable_with_float_formats: match border_color {
                 Some(BorderColor::FloatTransparentBlack) => true,
                 Some(BorderColor::FloatOpaqueBlack) => true,
@@ -455,6 +738,7 @@ impl Sampler {
             device: device.clone(),
             compare_mode: false,
             unnormalized: true,
+            reduction_mode: SamplerReductionMode::WeightedAverage,
             usable_with_float_formats: match border_color {
                 Some(BorderColor::FloatTransparentBlack) => true,
                 Some(BorderColor::FloatOpaqueBlack) => true,
@@ -477,6 +761,12 @@ impl Sampler {
         }))
     }
 
+    /// Returns the reduction mode of the sampler.
+    #[inline]
+    pub fn reduction_mode(&self) -> SamplerReductionMode {
+        self.reduction_mode
+    }
+
     /// Returns true if the sampler is a compare-mode sampler.
     #[inline]
     pub fn compare_mode(&self) -> bool {
@@ -546,6 +836,194 @@ impl Drop for Sampler {
     }
 }
 
+/// Description of a sampler, used to create the samplers of a `SamplerTable`.
+///
+/// The fields have the same meaning as the parameters of `Sampler::new`. The default value is the
+/// configuration of `Sampler::simple_repeat_linear`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SamplerDesc {
+    pub mag_filter: Filter,
+    pub min_filter: Filter,
+    pub mipmap_mode: MipmapMode,
+    pub address_u: SamplerAddressMode,
+    pub address_v: SamplerAddressMode,
+    pub address_w: SamplerAddressMode,
+    pub mip_lod_bias: f32,
+    pub max_anisotropy: f32,
+    pub min_lod: f32,
+    pub max_lod: f32,
+    /// If `Some`, the sampler compares depth values, as with `Sampler::compare`.
+    pub compare: Option<Compare>,
+    /// See `Sampler::with_reduction_mode`.
+    pub reduction_mode: SamplerReductionMode,
+    /// The custom border color and the format of the image views, as with
+    /// `Sampler::with_custom_border_color`.
+    pub custom_border_color: Option<(CustomBorderColor, Option<Format>)>,
+    /// If true, a `max_anisotropy` that the device doesn't support is an error instead of being
+    /// clamped to what the device supports.
+    pub strict_anisotropy: bool,
+}
+
+impl SamplerDesc {
+    /// Creates a sampler from this description.
+    ///
+    /// # Panic
+    ///
+    /// Same panic reasons as `Sampler::new`.
+    ///
+    #[inline]
+    pub fn build(&self, device: Arc<Device>) -> Result<Arc<Sampler>, SamplerCreationError> {
+        Sampler::new_impl(
+            device,
+            self.mag_filter,
+            self.min_filter,
+            self.mipmap_mode,
+            self.address_u,
+            self.address_v,
+            self.address_w,
+            self.mip_lod_bias,
+            self.max_anisotropy,
+            self.min_lod,
+            self.max_lod,
+            self.compare,
+            self.reduction_mode,
+            self.custom_border_color,
+            self.strict_anisotropy,
+        )
+    }
+}
+
+impl Default for SamplerDesc {
+    #[inline]
+    fn default() -> SamplerDesc {
+        SamplerDesc {
+            mag_filter: Filter::Linear,
+            min_filter: Filter::Linear,
+            mipmap_mode: MipmapMode::Linear,
+            address_u: SamplerAddressMode::Repeat,
+            address_v: SamplerAddressMode::Repeat,
+            address_w: SamplerAddressMode::Repeat,
+            mip_lod_bias: 0.0,
+            max_anisotropy: 1.0,
+            min_lod: 0.0,
+            max_lod: 1_000.0,
+            compare: None,
+            reduction_mode: SamplerReductionMode::WeightedAverage,
+            custom_border_color: None,
+            strict_anisotropy: false,
+        }
+    }
+}
+
+/// A set of named samplers, created all at once from a table of descriptions.
+///
+/// This is useful to create the samplers that an application uses during its whole lifetime at
+/// startup, and look them up by name afterwards. Entries with identical descriptions share the
+/// same `Sampler` object, so that the table doesn't create more samplers than necessary.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::sampler::Compare;
+/// use vulkano::sampler::Filter;
+/// use vulkano::sampler::SamplerAddressMode;
+/// use vulkano::sampler::SamplerDesc;
+/// use vulkano::sampler::SamplerTable;
+///
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// let clamp = SamplerAddressMode::ClampToEdge;
+///
+/// let samplers = SamplerTable::new(
+///     device.clone(),
+///     vec![
+///         ("linear_repeat", SamplerDesc::default()),
+///         (
+///             "nearest_clamp",
+///             SamplerDesc {
+///                 mag_filter: Filter::Nearest,
+///                 min_filter: Filter::Nearest,
+///                 address_u: clamp,
+///                 address_v: clamp,
+///                 address_w: clamp,
+///                 ..SamplerDesc::default()
+///             },
+///         ),
+///         (
+///             "shadow",
+///             SamplerDesc {
+///                 address_u: clamp,
+///                 address_v: clamp,
+///                 address_w: clamp,
+///                 compare: Some(Compare::LessOrEqual),
+///                 ..SamplerDesc::default()
+///             },
+///         ),
+///     ],
+/// )
+/// .unwrap();
+///
+/// let shadow_sampler = samplers.get("shadow").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SamplerTable {
+    samplers: HashMap<String, Arc<Sampler>>,
+}
+
+impl SamplerTable {
+    /// Creates a sampler for each entry of `descs`, identified by the name of the entry.
+    ///
+    /// Returns the error of the first sampler that couldn't be created, if any.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if two entries have the same name.
+    /// - Same panic reasons as `Sampler::new` for each description.
+    ///
+    pub fn new<'a, I>(device: Arc<Device>, descs: I) -> Result<SamplerTable, SamplerCreationError>
+    where
+        I: IntoIterator<Item = (&'a str, SamplerDesc)>,
+    {
+        // Samplers that were already created, to reuse them for identical descriptions. Since
+        // descriptions contain floats, they can't be hashed and we do a linear search instead.
+        let mut created: Vec<(SamplerDesc, Arc<Sampler>)> = Vec::new();
+        let mut samplers = HashMap::new();
+
+        for (name, desc) in descs {
+            let sampler = match created.iter().find(|&&(ref d, _)| *d == desc) {
+                Some(&(_, ref sampler)) => sampler.clone(),
+                None => {
+                    let sampler = desc.build(device.clone())?;
+                    created.push((desc, sampler.clone()));
+                    sampler
+                }
+            };
+
+            let previous = samplers.insert(name.to_owned(), sampler);
+            assert!(
+                previous.is_none(),
+                "duplicate sampler name in table: {}",
+                name
+            );
+        }
+
+        Ok(SamplerTable { samplers })
+    }
+
+    /// Returns the sampler with the given name, if any.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Arc<Sampler>> {
+        self.samplers.get(name)
+    }
+
+    /// Returns an iterator to the names and samplers of the table, in no particular order.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &Arc<Sampler>)> {
+        self.samplers
+            .iter()
+            .map(|(name, sampler)| (name.as_str(), sampler))
+    }
+}
+
 /// Describes how the color of each pixel should be determined.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u32)]
@@ -571,6 +1049,21 @@ pub enum MipmapMode {
     Linear = vk::SAMPLER_MIPMAP_MODE_LINEAR,
 }
 
+/// Describes how the texels read by the filter are combined into a single value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum SamplerReductionMode {
+    /// The texels are averaged according to the weights computed by the filter. This is the
+    /// regular behavior of samplers.
+    WeightedAverage = vk::SAMPLER_REDUCTION_MODE_WEIGHTED_AVERAGE_EXT,
+
+    /// The component-wise minimum of the texels with a non-zero weight is returned.
+    Min = vk::SAMPLER_REDUCTION_MODE_MIN_EXT,
+
+    /// The component-wise maximum of the texels with a non-zero weight is returned.
+    Max = vk::SAMPLER_REDUCTION_MODE_MAX_EXT,
+}
+
 /// How the sampler should behave when it needs to access a pixel that is out of range of the
 /// texture.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -594,6 +1087,10 @@ pub enum SamplerAddressMode {
     /// color.
     ClampToBorder(BorderColor),
 
+    /// Same as `ClampToBorder`, except that the border has the color passed to
+    /// `Sampler::with_custom_border_color`.
+    ClampToCustomBorder,
+
     /// Similar to `MirroredRepeat`, except that coordinates are clamped to the range
     /// `[-1.0, 1.0]`.
     MirrorClampToEdge,
@@ -607,6 +1104,7 @@ impl SamplerAddressMode {
             SamplerAddressMode::MirroredRepeat => vk::SAMPLER_ADDRESS_MODE_MIRRORED_REPEAT,
             SamplerAddressMode::ClampToEdge => vk::SAMPLER_ADDRESS_MODE_CLAMP_TO_EDGE,
             SamplerAddressMode::ClampToBorder(_) => vk::SAMPLER_ADDRESS_MODE_CLAMP_TO_BORDER,
+            SamplerAddressMode::ClampToCustomBorder => vk::SAMPLER_ADDRESS_MODE_CLAMP_TO_BORDER,
             SamplerAddressMode::MirrorClampToEdge => vk::SAMPLER_ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE,
         }
     }
@@ -687,6 +1185,45 @@ pub enum BorderColor {
     IntOpaqueWhite = vk::BORDER_COLOR_INT_OPAQUE_WHITE,
 }
 
+/// A border color of your choice, used by the address modes that are `ClampToCustomBorder`.
+///
+/// See `Sampler::with_custom_border_color`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CustomBorderColor {
+    /// A floating-point color. Can only be used with floating-point, normalized and depth
+    /// images.
+    Float([f32; 4]),
+
+    /// An integer color. Can only be used with integer and stencil images.
+    Int([i32; 4]),
+}
+
+impl CustomBorderColor {
+    /// Returns true if the color can be used with image views of the given format.
+    #[inline]
+    pub fn compatible_with_format(&self, format: Format) -> bool {
+        match (*self, format.ty()) {
+            (CustomBorderColor::Float(_), FormatTy::Float) => true,
+            (CustomBorderColor::Float(_), FormatTy::Depth) => true,
+            (CustomBorderColor::Float(_), FormatTy::DepthStencil) => true,
+            (CustomBorderColor::Float(_), FormatTy::Compressed) => true,
+            (CustomBorderColor::Int(_), FormatTy::Uint) => true,
+            (CustomBorderColor::Int(_), FormatTy::Sint) => true,
+            (CustomBorderColor::Int(_), FormatTy::Stencil) => true,
+            (CustomBorderColor::Int(_), FormatTy::DepthStencil) => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    fn to_vk(self) -> vk::ClearColorValue {
+        match self {
+            CustomBorderColor::Float(float32) => vk::ClearColorValue { float32 },
+            CustomBorderColor::Int(int32) => vk::ClearColorValue { int32 },
+        }
+    }
+}
+
 /// Error that can happen when creating an instance.
 #[derive(Clone, Debug, PartialEq)]
 pub enum SamplerCreationError {
@@ -720,6 +1257,32 @@ pub enum SamplerCreationError {
     /// Using `MirrorClampToEdge` requires enabling the `VK_KHR_sampler_mirror_clamp_to_edge`
     /// extension when creating the device.
     SamplerMirrorClampToEdgeExtensionNotEnabled,
+
+    /// Using a reduction mode other than `WeightedAverage` requires enabling the
+    /// `VK_EXT_sampler_filter_minmax` extension when creating the device.
+    SamplerFilterMinmaxExtensionNotEnabled,
+
+    /// Using a custom border color requires enabling the `VK_EXT_custom_border_color` extension
+    /// when creating the device.
+    CustomBorderColorExtensionNotEnabled,
+
+    /// Using a custom border color requires enabling the `custom_border_colors` feature when
+    /// creating the device.
+    CustomBorderColorsFeatureNotEnabled,
+
+    /// Using a custom border color without a format requires enabling the
+    /// `custom_border_color_without_format` feature when creating the device.
+    CustomBorderColorWithoutFormatFeatureNotEnabled,
+
+    /// The type of the custom border color doesn't match the component type of the format.
+    CustomBorderColorFormatMismatch,
+
+    /// A custom border color can't be combined with `ClampToBorder` and a predefined border
+    /// color.
+    CustomBorderColorWithPredefinedBorderColor,
+
+    /// `ClampToCustomBorder` was used without passing a custom border color.
+    CustomBorderColorMissing,
 }
 
 impl error::Error for SamplerCreationError {
@@ -750,6 +1313,27 @@ impl fmt::Display for SamplerCreationError {
                 SamplerCreationError::SamplerMirrorClampToEdgeExtensionNotEnabled => {
                     "the device extension `VK_KHR_sampler_mirror_clamp_to_edge` is not enabled"
                 }
+                SamplerCreationError::SamplerFilterMinmaxExtensionNotEnabled => {
+                    "the device extension `VK_EXT_sampler_filter_minmax` is not enabled"
+                }
+                SamplerCreationError::CustomBorderColorExtensionNotEnabled => {
+                    "the device extension `VK_EXT_custom_border_color` is not enabled"
+                }
+                SamplerCreationError::CustomBorderColorsFeatureNotEnabled => {
+                    "the `custom_border_colors` feature is not enabled"
+                }
+                SamplerCreationError::CustomBorderColorWithoutFormatFeatureNotEnabled => {
+                    "the `custom_border_color_without_format` feature is not enabled"
+                }
+                SamplerCreationError::CustomBorderColorFormatMismatch => {
+                    "the type of the custom border color doesn't match the format"
+                }
+                SamplerCreationError::CustomBorderColorWithPredefinedBorderColor => {
+                    "a custom border color can't be combined with a predefined border color"
+                }
+                SamplerCreationError::CustomBorderColorMissing => {
+                    "`ClampToCustomBorder` was used without a custom border color"
+                }
             }
         )
     }
@@ -776,7 +1360,90 @@ impl From<Error> for SamplerCreationError {
 
 #[cfg(test)]
 mod tests {
+    use format;
+    use image;
     use sampler;
+    use std::sync::Arc;
+
+    #[test]
+    fn linear_for_format() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // Linear filtering of `R8G8B8A8Unorm` with optimal tiling is required by the specs.
+        assert!(device.physical_device().is_format_linear_filterable(
+            format::Format::R8G8B8A8Unorm,
+            image::ImageTiling::Optimal
+        ));
+
+        let s = sampler::Sampler::simple_repeat_linear_for_format(
+            device,
+            format::Format::R32G32B32A32Sfloat,
+        );
+        assert!(s.usable_with_float_formats());
+    }
+
+    #[test]
+    fn table_deduplicates() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let nearest = sampler::SamplerDesc {
+            mag_filter: sampler::Filter::Nearest,
+            min_filter: sampler::Filter::Nearest,
+            ..sampler::SamplerDesc::default()
+        };
+
+        let table = sampler::SamplerTable::new(
+            device,
+            vec![
+                ("a", sampler::SamplerDesc::default()),
+                ("b", nearest),
+                ("c", sampler::SamplerDesc::default()),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(table.iter().len(), 3);
+        assert!(Arc::ptr_eq(
+            table.get("a").unwrap(),
+            table.get("c").unwrap()
+        ));
+        assert!(!Arc::ptr_eq(
+            table.get("a").unwrap(),
+            table.get("b").unwrap()
+        ));
+        assert!(table.get("d").is_none());
+    }
+
+    #[test]
+    fn table_duplicate_name() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!("duplicate sampler name in table: a", {
+            let _ = sampler::SamplerTable::new(
+                device,
+                vec![
+                    ("a", sampler::SamplerDesc::default()),
+                    ("a", sampler::SamplerDesc::default()),
+                ],
+            );
+        });
+    }
+
+    #[test]
+    fn table_error() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = sampler::SamplerDesc {
+            max_anisotropy: 2.0,
+            strict_anisotropy: true,
+            ..sampler::SamplerDesc::default()
+        };
+
+        match sampler::SamplerTable::new(device, Some(("aniso", desc))) {
+            Err(sampler::SamplerCreationError::SamplerAnisotropyFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
 
     #[test]
     fn create_regular() {
@@ -922,21 +1589,13 @@ mod tests {
     fn anisotropy_feature() {
         let (device, queue) = gfx_dev_and_queue!();
 
-        let r = sampler::Sampler::new(
-            device,
-            sampler::Filter::Linear,
-            sampler::Filter::Linear,
-            sampler::MipmapMode::Nearest,
-            sampler::SamplerAddressMode::Repeat,
-            sampler::SamplerAddressMode::Repeat,
-            sampler::SamplerAddressMode::Repeat,
-            1.0,
-            2.0,
-            0.0,
-            2.0,
-        );
+        let desc = sampler::SamplerDesc {
+            max_anisotropy: 2.0,
+            strict_anisotropy: true,
+            ..sampler::SamplerDesc::default()
+        };
 
-        match r {
+        match desc.build(device) {
             Err(sampler::SamplerCreationError::SamplerAnisotropyFeatureNotEnabled) => (),
             _ => panic!(),
         }
@@ -946,7 +1605,23 @@ mod tests {
     fn anisotropy_limit() {
         let (device, queue) = gfx_dev_and_queue!(sampler_anisotropy);
 
-        let r = sampler::Sampler::new(
+        let desc = sampler::SamplerDesc {
+            max_anisotropy: 100000000.0,
+            strict_anisotropy: true,
+            ..sampler::SamplerDesc::default()
+        };
+
+        match desc.build(device) {
+            Err(sampler::SamplerCreationError::AnisotropyLimitExceeded { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn anisotropy_clamped() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let _ = sampler::Sampler::new(
             device,
             sampler::Filter::Linear,
             sampler::Filter::Linear,
@@ -958,12 +1633,22 @@ mod tests {
             100000000.0,
             0.0,
             2.0,
-        );
+        )
+        .unwrap();
+    }
 
-        match r {
-            Err(sampler::SamplerCreationError::AnisotropyLimitExceeded { .. }) => (),
-            _ => panic!(),
-        }
+    #[test]
+    fn clamp_anisotropy() {
+        let (device, _) = gfx_dev_and_queue!();
+        assert_eq!(sampler::Sampler::clamp_anisotropy(&device, 16.0), 1.0);
+
+        let (device, _) = gfx_dev_and_queue!(sampler_anisotropy);
+        let clamped = sampler::Sampler::clamp_anisotropy(&device, 100000000.0);
+        assert_eq!(
+            clamped,
+            device.physical_device().limits().max_sampler_anisotropy()
+        );
+        assert!(clamped <= device.physical_device().max_sampler_anisotropy());
     }
 
     #[test]
@@ -1013,4 +1698,86 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn sampler_filter_minmax_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let r = sampler::Sampler::with_reduction_mode(
+            device,
+            sampler::Filter::Linear,
+            sampler::Filter::Linear,
+            sampler::MipmapMode::Nearest,
+            sampler::SamplerAddressMode::ClampToEdge,
+            sampler::SamplerAddressMode::ClampToEdge,
+            sampler::SamplerAddressMode::ClampToEdge,
+            0.0,
+            1.0,
+            0.0,
+            2.0,
+            sampler::SamplerReductionMode::Min,
+        );
+
+        match r {
+            Err(sampler::SamplerCreationError::SamplerFilterMinmaxExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn custom_border_color_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let r = sampler::Sampler::with_custom_border_color(
+            device,
+            sampler::Filter::Linear,
+            sampler::Filter::Linear,
+            sampler::MipmapMode::Nearest,
+            sampler::SamplerAddressMode::ClampToCustomBorder,
+            sampler::SamplerAddressMode::ClampToCustomBorder,
+            sampler::SamplerAddressMode::ClampToCustomBorder,
+            0.0,
+            1.0,
+            0.0,
+            2.0,
+            sampler::CustomBorderColor::Float([0.5, 0.5, 0.5, 1.0]),
+            Some(format::Format::R8G8B8A8Unorm),
+        );
+
+        match r {
+            Err(sampler::SamplerCreationError::CustomBorderColorExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn custom_border_color_missing() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let r = sampler::Sampler::new(
+            device,
+            sampler::Filter::Linear,
+            sampler::Filter::Linear,
+            sampler::MipmapMode::Nearest,
+            sampler::SamplerAddressMode::ClampToCustomBorder,
+            sampler::SamplerAddressMode::Repeat,
+            sampler::SamplerAddressMode::Repeat,
+            0.0,
+            1.0,
+            0.0,
+            2.0,
+        );
+
+        match r {
+            Err(sampler::SamplerCreationError::CustomBorderColorMissing) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn custom_border_color_format() {
+        let color = sampler::CustomBorderColor::Int([1, 2, 3, 4]);
+        assert!(color.compatible_with_format(format::Format::R8G8B8A8Uint));
+        assert!(!color.compatible_with_format(format::Format::R8G8B8A8Unorm));
+    }
 }
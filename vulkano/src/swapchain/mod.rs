@@ -332,6 +332,7 @@ pub use self::swapchain::FullscreenExclusive;
 pub use self::swapchain::FullscreenExclusiveError;
 pub use self::swapchain::PresentFuture;
 pub use self::swapchain::Swapchain;
+pub use self::swapchain::SwapchainAcquireFences;
 pub use self::swapchain::SwapchainAcquireFuture;
 pub use self::swapchain::SwapchainCreationError;
 
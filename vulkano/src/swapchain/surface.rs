@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::collections::HashMap;
 use std::error;
 use std::fmt;
 use std::mem::MaybeUninit;
@@ -14,6 +15,7 @@ use std::os::raw::c_ulong;
 use std::ptr;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use format::Format;
 use image::ImageUsage;
@@ -43,6 +45,10 @@ pub struct Surface<W> {
     // If true, a swapchain has been associated to this surface, and that any new swapchain
     // creation should be forbidden.
     has_swapchain: AtomicBool,
+
+    // Cache of the results of `is_supported`, indexed by physical device index and queue family
+    // id.
+    supported_queue_families: Mutex<HashMap<(usize, u32), bool>>,
 }
 
 impl<W> Surface<W> {
@@ -60,6 +66,7 @@ impl<W> Surface<W> {
             instance: instance,
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }
     }
 
@@ -128,6 +135,7 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
@@ -178,6 +186,7 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
@@ -227,6 +236,7 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
@@ -276,6 +286,7 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
@@ -325,6 +336,7 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
@@ -370,6 +382,7 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
@@ -416,6 +429,7 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
@@ -462,6 +476,7 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
@@ -507,13 +522,22 @@ impl<W> Surface<W> {
             instance: instance.clone(),
             surface: surface,
             has_swapchain: AtomicBool::new(false),
+            supported_queue_families: Mutex::new(HashMap::new()),
         }))
     }
 
     /// Returns true if the given queue family can draw on this surface.
+    ///
+    /// The result is cached, so that calling this function again for the same queue family
+    /// doesn't query the implementation.
     // FIXME: vulkano doesn't check this for the moment!
     pub fn is_supported(&self, queue: QueueFamily) -> Result<bool, CapabilitiesError> {
-        unsafe {
+        let key = (queue.physical_device().index(), queue.id());
+        if let Some(&supported) = self.supported_queue_families.lock().unwrap().get(&key) {
+            return Ok(supported);
+        }
+
+        let supported = unsafe {
             let vk = self.instance.pointers();
 
             let mut output = MaybeUninit::uninit();
@@ -523,8 +547,14 @@ impl<W> Surface<W> {
                 self.surface,
                 output.as_mut_ptr(),
             ))?;
-            Ok(output.assume_init() != 0)
-        }
+            output.assume_init() != 0
+        };
+
+        self.supported_queue_families
+            .lock()
+            .unwrap()
+            .insert(key, supported);
+        Ok(supported)
     }
 
     /// Retrieves the capabilities of a surface when used by a certain device.
@@ -50,6 +50,7 @@ use sync::AccessCheckError;
 use sync::AccessError;
 use sync::AccessFlagBits;
 use sync::Fence;
+use sync::FenceWaitError;
 use sync::FlushError;
 use sync::GpuFuture;
 use sync::PipelineStages;
@@ -113,26 +114,8 @@ pub fn acquire_next_image<W>(
     let semaphore = Semaphore::from_pool(swapchain.device.clone())?;
     let fence = Fence::from_pool(swapchain.device.clone())?;
 
-    let AcquiredImage { id, suboptimal } = {
-        // Check that this is not an old swapchain. From specs:
-        // > swapchain must not have been replaced by being passed as the
-        // > VkSwapchainCreateInfoKHR::oldSwapchain value to vkCreateSwapchainKHR
-        let stale = swapchain.stale.lock().unwrap();
-        if *stale {
-            return Err(AcquireError::OutOfDate);
-        }
-
-        let acquire_result =
-            unsafe { acquire_next_image_raw(&swapchain, timeout, Some(&semaphore), Some(&fence)) };
-
-        if let &Err(AcquireError::FullscreenExclusiveLost) = &acquire_result {
-            swapchain
-                .fullscreen_exclusive_held
-                .store(false, Ordering::SeqCst);
-        }
-
-        acquire_result?
-    };
+    let AcquiredImage { id, suboptimal } =
+        acquire_next_image_checked(&swapchain, timeout, Some(&semaphore), Some(&fence))?;
 
     Ok((
         id,
@@ -147,6 +130,207 @@ pub fn acquire_next_image<W>(
     ))
 }
 
+// Same as `acquire_next_image_raw`, but checks that the swapchain isn't stale and keeps track of
+// the fullscreen exclusivity.
+fn acquire_next_image_checked<W>(
+    swapchain: &Swapchain<W>,
+    timeout: Option<Duration>,
+    semaphore: Option<&Semaphore>,
+    fence: Option<&Fence>,
+) -> Result<AcquiredImage, AcquireError> {
+    // Check that this is not an old swapchain. From specs:
+    // > swapchain must not have been replaced by being passed as the
+    // > VkSwapchainCreateInfoKHR::oldSwapchain value to vkCreateSwapchainKHR
+    let stale = swapchain.stale.lock().unwrap();
+    if *stale {
+        return Err(AcquireError::OutOfDate);
+    }
+
+    let acquire_result = unsafe { acquire_next_image_raw(swapchain, timeout, semaphore, fence) };
+
+    if let &Err(AcquireError::FullscreenExclusiveLost) = &acquire_result {
+        swapchain
+            .fullscreen_exclusive_held
+            .store(false, Ordering::SeqCst);
+    }
+
+    acquire_result
+}
+
+/// Acquires the images of a swapchain with fences instead of semaphores.
+///
+/// When the CPU needs to know when an image has been acquired, `acquire_next_image` can't be
+/// used and the application must signal a fence. This fence then has to be waited upon and
+/// reset before being used again, and an image must not be acquired again before its previous
+/// acquisition has completed. `SwapchainAcquireFences` owns one fence per image of the swapchain
+/// (plus one) and takes care of this lifecycle.
+///
+/// If `acquire_next_image` returns `AcquireError::OutOfDate`, or if the acquisition was
+/// suboptimal, the swapchain should be recreated and passed to `replace_swapchain`.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::swapchain::AcquireError;
+/// use vulkano::swapchain::SwapchainAcquireFences;
+/// # use std::sync::Arc;
+/// # use vulkano::swapchain::Swapchain;
+/// # let swapchain: Arc<Swapchain<()>> = return;
+///
+/// let mut fences = SwapchainAcquireFences::new(swapchain).unwrap();
+///
+/// match fences.acquire_next_image(None) {
+///     Ok((image, suboptimal, fence)) => {
+///         fence.wait(None).unwrap();
+///         // The image can now be drawn on.
+///         if suboptimal {
+///             // Recreate the swapchain after presenting.
+///         }
+///     }
+///     Err(AcquireError::OutOfDate) => {
+///         // Recreate the swapchain and call `replace_swapchain`.
+///     }
+///     Err(err) => panic!("{:?}", err),
+/// }
+/// ```
+pub struct SwapchainAcquireFences<W> {
+    swapchain: Arc<Swapchain<W>>,
+    fences: Vec<Fence>,
+    // True if the fence with the same index was passed to an acquisition and hasn't been reset
+    // since.
+    pending: Vec<bool>,
+    // For each image, the index of the fence of its last acquisition.
+    image_fences: Vec<Option<usize>>,
+    next_fence: usize,
+}
+
+impl<W> SwapchainAcquireFences<W> {
+    /// Builds the fences for the given swapchain.
+    pub fn new(swapchain: Arc<Swapchain<W>>) -> Result<SwapchainAcquireFences<W>, OomError> {
+        let mut fences = SwapchainAcquireFences {
+            swapchain,
+            fences: Vec::new(),
+            pending: Vec::new(),
+            image_fences: Vec::new(),
+            next_fence: 0,
+        };
+        fences.resize()?;
+        Ok(fences)
+    }
+
+    /// Returns the swapchain whose images are acquired.
+    #[inline]
+    pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
+        &self.swapchain
+    }
+
+    /// Tries to acquire an image of the swapchain.
+    ///
+    /// Returns the index of the image, whether the acquisition was suboptimal, and the fence
+    /// that will be signaled when the image has been acquired. The image must not be accessed
+    /// before this fence is signaled. Since the fence is reset by `SwapchainAcquireFences`, it
+    /// must not be reset by the caller.
+    ///
+    /// Before reusing a fence, this function blocks until the acquisition that previously used
+    /// it has completed. The `timeout` applies to this wait as well as to the acquisition.
+    pub fn acquire_next_image(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<(usize, bool, &Fence), AcquireError> {
+        let fence_index = self.next_fence;
+        self.wait_and_reset(fence_index, timeout)?;
+
+        let AcquiredImage { id, suboptimal } = acquire_next_image_checked(
+            &self.swapchain,
+            timeout,
+            None,
+            Some(&self.fences[fence_index]),
+        )?;
+        self.pending[fence_index] = true;
+        self.next_fence = (fence_index + 1) % self.fences.len();
+
+        // Make sure that the previous acquisition of the same image has completed.
+        if let Some(previous) = self.image_fences[id].replace(fence_index) {
+            if previous != fence_index {
+                self.wait_and_reset(previous, timeout)?;
+            }
+        }
+
+        Ok((id, suboptimal, &self.fences[fence_index]))
+    }
+
+    /// Returns the fence of the last acquisition of the given image, or `None` if the image
+    /// hasn't been acquired yet or if its fence has been reset since.
+    #[inline]
+    pub fn image_fence(&self, image: usize) -> Option<&Fence> {
+        match self.image_fences.get(image) {
+            Some(&Some(index)) if self.pending[index] => Some(&self.fences[index]),
+            _ => None,
+        }
+    }
+
+    /// Waits for all the pending acquisitions, then replaces the swapchain.
+    ///
+    /// This should be called after recreating the swapchain, for example after
+    /// `acquire_next_image` returned `AcquireError::OutOfDate`.
+    pub fn replace_swapchain(&mut self, swapchain: Arc<Swapchain<W>>) -> Result<(), AcquireError> {
+        for index in 0..self.fences.len() {
+            self.wait_and_reset(index, None)?;
+        }
+
+        self.swapchain = swapchain;
+        self.resize()?;
+        Ok(())
+    }
+
+    // Waits on the fence with the given index if it is pending, then resets it.
+    fn wait_and_reset(
+        &mut self,
+        index: usize,
+        timeout: Option<Duration>,
+    ) -> Result<(), AcquireError> {
+        if !self.pending[index] {
+            return Ok(());
+        }
+
+        self.fences[index].wait(timeout)?;
+        self.fences[index].reset()?;
+        self.pending[index] = false;
+        for image_fence in self.image_fences.iter_mut() {
+            if *image_fence == Some(index) {
+                *image_fence = None;
+            }
+        }
+        Ok(())
+    }
+
+    // Adjusts the number of fences to the number of images of the swapchain. All the fences must
+    // have been reset.
+    fn resize(&mut self) -> Result<(), OomError> {
+        let num_images = self.swapchain.num_images() as usize;
+
+        while self.fences.len() < num_images + 1 {
+            self.fences
+                .push(Fence::alloc(self.swapchain.device.clone())?);
+        }
+        self.fences.truncate(num_images + 1);
+        self.pending = vec![false; num_images + 1];
+        self.image_fences = vec![None; num_images];
+        self.next_fence = 0;
+        Ok(())
+    }
+}
+
+impl<W> fmt::Debug for SwapchainAcquireFences<W> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("SwapchainAcquireFences")
+            .field("swapchain", &self.swapchain)
+            .field("fences", &self.fences.len())
+            .finish()
+    }
+}
+
 /// Presents an image on the screen.
 ///
 /// The parameter is the same index as what `acquire_next_image` returned. The image must
@@ -412,6 +596,36 @@ impl<W> Swapchain<W> {
         )
     }
 
+    /// Recreates the swapchain with a different present mode, keeping the format, the number of
+    /// images and the other parameters unchanged.
+    ///
+    /// This can be used to toggle vsync at runtime, for example by switching between
+    /// `PresentMode::Fifo` and `PresentMode::Mailbox` or `PresentMode::Immediate`. Returns
+    /// `SwapchainCreationError::UnsupportedPresentMode` if the surface doesn't support `mode`.
+    /// You can check in advance with the `present_modes` field of the surface's capabilities.
+    pub fn recreate_with_present_mode(
+        &self,
+        present_mode: PresentMode,
+    ) -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError> {
+        Swapchain::new_inner(
+            self.device.clone(),
+            self.surface.clone(),
+            self.num_images,
+            self.format,
+            self.color_space,
+            None,
+            self.layers,
+            self.usage,
+            self.sharing.clone(),
+            self.transform,
+            self.alpha,
+            present_mode,
+            self.fullscreen_exclusive,
+            self.clipped,
+            Some(self),
+        )
+    }
+
     fn new_inner(
         device: Arc<Device>,
         surface: Arc<Surface<W>>,
@@ -754,6 +968,14 @@ impl<W> Swapchain<W> {
         self.layers
     }
 
+    /// Returns the usage of the images of the swapchain.
+    ///
+    /// See the documentation of `Swapchain::new`.
+    #[inline]
+    pub fn usage(&self) -> ImageUsage {
+        self.usage
+    }
+
     /// Returns the transform that was passed when creating the swapchain.
     ///
     /// See the documentation of `Swapchain::new`.
@@ -1327,6 +1549,17 @@ impl From<OomError> for AcquireError {
     }
 }
 
+impl From<FenceWaitError> for AcquireError {
+    #[inline]
+    fn from(err: FenceWaitError) -> AcquireError {
+        match err {
+            FenceWaitError::OomError(err) => AcquireError::OomError(err),
+            FenceWaitError::Timeout => AcquireError::Timeout,
+            FenceWaitError::DeviceLostError => AcquireError::DeviceLost,
+        }
+    }
+}
+
 impl From<Error> for AcquireError {
     #[inline]
     fn from(err: Error) -> AcquireError {
@@ -1624,3 +1857,148 @@ pub unsafe fn acquire_next_image_raw<W>(
 
     Ok(AcquiredImage { id, suboptimal })
 }
+
+#[cfg(test)]
+mod tests {
+    use device::Device;
+    use device::DeviceExtensions;
+    use features::Features;
+    use image::ImageLayout;
+    use image::ImageUsage;
+    use image::ImageViewAccess;
+    use image::SampledSwapchainImage;
+    use image::SampledSwapchainImageError;
+    use image::SwapchainImage;
+    use instance::Instance;
+    use instance::InstanceExtensions;
+    use instance::PhysicalDevice;
+    use std::sync::Arc;
+    use swapchain::display::Display;
+    use swapchain::display::DisplayPlane;
+    use swapchain::Capabilities;
+    use swapchain::ColorSpace;
+    use swapchain::FullscreenExclusive;
+    use swapchain::PresentMode;
+    use swapchain::Surface;
+    use swapchain::Swapchain;
+    use swapchain::SwapchainCreationError;
+    use sync::SharingMode;
+
+    // Creates a swapchain with the given usage on the first display, or returns `None` if there
+    // is no display or if the usage isn't supported. Presenting requires a display, which isn't
+    // available on most test machines.
+    fn display_swapchain(
+        usage: ImageUsage,
+    ) -> Option<(
+        Arc<Swapchain<()>>,
+        Vec<Arc<SwapchainImage<()>>>,
+        Capabilities,
+    )> {
+        let extensions = InstanceExtensions {
+            khr_surface: true,
+            khr_display: true,
+            ..InstanceExtensions::none()
+        };
+        let instance = Instance::new(None, &extensions, None).ok()?;
+        let physical = PhysicalDevice::enumerate(&instance).next()?;
+
+        let display = Display::enumerate(physical).next()?;
+        let mode = display.display_modes().next()?;
+        let plane = DisplayPlane::enumerate(physical).find(|p| p.supports(&display))?;
+        let surface = Surface::<()>::from_display_mode(&mode, &plane).unwrap();
+
+        let queue_family = physical
+            .queue_families()
+            .find(|q| q.supports_graphics() && surface.is_supported(*q).unwrap_or(false))?;
+        let device_extensions = DeviceExtensions {
+            khr_swapchain: true,
+            ..DeviceExtensions::none()
+        };
+        let (device, _) = Device::new(
+            physical,
+            &Features::none(),
+            &device_extensions,
+            Some((queue_family, 0.5)),
+        )
+        .unwrap();
+
+        let caps = surface.capabilities(physical).unwrap();
+        let (format, _) = caps.supported_formats[0];
+        let (swapchain, images) = Swapchain::new(
+            device,
+            surface,
+            caps.min_image_count,
+            format,
+            caps.current_extent.unwrap_or(caps.min_image_extent),
+            1,
+            usage,
+            SharingMode::Exclusive,
+            caps.current_transform,
+            caps.supported_composite_alpha.iter().next().unwrap(),
+            PresentMode::Fifo,
+            FullscreenExclusive::Default,
+            true,
+            ColorSpace::SrgbNonLinear,
+        )
+        .ok()?;
+
+        Some((swapchain, images, caps))
+    }
+
+    #[test]
+    fn recreate_with_present_mode() {
+        let (swapchain, _, caps) = match display_swapchain(ImageUsage::color_attachment()) {
+            Some(s) => s,
+            None => return,
+        };
+
+        let unsupported = [
+            PresentMode::Immediate,
+            PresentMode::Mailbox,
+            PresentMode::Relaxed,
+        ]
+        .iter()
+        .cloned()
+        .find(|&m| !caps.present_modes.supports(m));
+        if let Some(unsupported) = unsupported {
+            match swapchain.recreate_with_present_mode(unsupported) {
+                Err(SwapchainCreationError::UnsupportedPresentMode) => (),
+                _ => panic!(),
+            }
+        }
+
+        let (recreated, images) = swapchain
+            .recreate_with_present_mode(PresentMode::Fifo)
+            .unwrap();
+        assert_eq!(recreated.present_mode(), PresentMode::Fifo);
+        assert_eq!(recreated.format(), swapchain.format());
+        assert_eq!(recreated.num_images(), swapchain.num_images());
+        assert_eq!(images.len() as u32, recreated.num_images());
+    }
+
+    #[test]
+    fn sampled_swapchain_image() {
+        let (_, images, _) = match display_swapchain(ImageUsage::color_attachment()) {
+            Some(s) => s,
+            None => return,
+        };
+        match SampledSwapchainImage::new(images[0].clone()) {
+            Err(SampledSwapchainImageError::MissingSampledUsage) => (),
+            _ => panic!(),
+        }
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::color_attachment()
+        };
+        let (_, images, _) = match display_swapchain(usage) {
+            Some(s) => s,
+            None => return,
+        };
+        let view = SampledSwapchainImage::new(images[0].clone()).unwrap();
+        assert_eq!(
+            view.descriptor_set_sampled_image_layout(),
+            ImageLayout::ShaderReadOnlyOptimal
+        );
+    }
+}
@@ -10,10 +10,13 @@
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use check_errors;
 use device::Device;
 use device::DeviceOwned;
+use device::Queue;
+use sync::PipelineStages;
 use vk;
 use OomError;
 use Success;
@@ -32,6 +35,28 @@ pub struct Event {
     // The device.
     device: Arc<Device>,
     must_put_in_pool: bool,
+    // State of the event after the commands that were submitted so far.
+    state: Mutex<EventState>,
+}
+
+// State of an event, as known from the host and from the command buffers that were submitted.
+#[derive(Debug)]
+struct EventState {
+    // Family and index of the queue of the last command buffer that set or reset the event.
+    queue: Option<(u32, u32)>,
+    // Stages passed to the last `set_event` command, or the host stage if the event was last set
+    // from the host. `None` if the event is reset.
+    stages: Option<PipelineStages>,
+}
+
+impl EventState {
+    #[inline]
+    fn reset() -> EventState {
+        EventState {
+            queue: None,
+            stages: None,
+        }
+    }
 }
 
 impl Event {
@@ -54,6 +79,7 @@ impl Event {
                     event: raw_event,
                     device: device,
                     must_put_in_pool: true,
+                    state: Mutex::new(EventState::reset()),
                 })
             }
             None => {
@@ -93,6 +119,7 @@ impl Event {
             device: device,
             event: event,
             must_put_in_pool: must_put_in_pool,
+            state: Mutex::new(EventState::reset()),
         })
     }
 
@@ -117,6 +144,13 @@ impl Event {
         unsafe {
             let vk = self.device.pointers();
             check_errors(vk.SetEvent(self.device.internal_object(), self.event))?;
+            *self.state.get_mut().unwrap() = EventState {
+                queue: None,
+                stages: Some(PipelineStages {
+                    host: true,
+                    ..PipelineStages::none()
+                }),
+            };
             Ok(())
         }
     }
@@ -140,10 +174,50 @@ impl Event {
         unsafe {
             let vk = self.device.pointers();
             check_errors(vk.ResetEvent(self.device.internal_object(), self.event))?;
+            *self.state.get_mut().unwrap() = EventState::reset();
             Ok(())
         }
     }
 
+    /// Returns the queue family of the last command buffer that set or reset the event, if it
+    /// has been set or reset by a submitted command buffer since it was last set or reset from
+    /// the host.
+    ///
+    /// An event that is set by a command buffer can only be waited on by command buffers that
+    /// are submitted to the same queue, and therefore belong to the same queue family.
+    #[inline]
+    pub fn queue_family_id(&self) -> Option<u32> {
+        self.state.lock().unwrap().queue.map(|(family, _)| family)
+    }
+
+    // Returns the stages passed to the last `set_event` command that was submitted, or the host
+    // stage if the event was last set from the host. Returns `None` if the event is reset.
+    #[inline]
+    pub(crate) fn set_stages(&self) -> Option<PipelineStages> {
+        self.state.lock().unwrap().stages
+    }
+
+    // Same as `set_stages`, but also returns `None` if the event was last set by a command buffer
+    // submitted to another queue than `queue`.
+    #[inline]
+    pub(crate) fn set_stages_on_queue(&self, queue: &Queue) -> Option<PipelineStages> {
+        let state = self.state.lock().unwrap();
+        match state.queue {
+            Some(current) if current != (queue.family().id(), queue.id_within_family()) => None,
+            _ => state.stages,
+        }
+    }
+
+    // Records that a command buffer that sets the event with `stages`, or resets it if `None`, is
+    // being submitted to `queue`.
+    #[inline]
+    pub(crate) fn submit_set(&self, queue: &Queue, stages: Option<PipelineStages>) {
+        *self.state.lock().unwrap() = EventState {
+            queue: Some((queue.family().id(), queue.id_within_family())),
+            stages,
+        };
+    }
+
     /// Changes the `Event` to the unsignaled state.
     ///
     /// # Panic
@@ -192,6 +266,7 @@ impl Drop for Event {
 #[cfg(test)]
 mod tests {
     use sync::Event;
+    use sync::PipelineStages;
     use VulkanObject;
 
     #[test]
@@ -223,6 +298,27 @@ mod tests {
         assert!(!event.signaled().unwrap());
     }
 
+    #[test]
+    fn set_stages() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut event = Event::alloc(device).unwrap();
+        assert_eq!(event.set_stages(), None);
+
+        event.set();
+        assert_eq!(
+            event.set_stages(),
+            Some(PipelineStages {
+                host: true,
+                ..PipelineStages::none()
+            })
+        );
+        assert_eq!(event.queue_family_id(), None);
+
+        event.reset();
+        assert_eq!(event.set_stages(), None);
+    }
+
     #[test]
     fn event_pool() {
         let (device, _) = gfx_dev_and_queue!();
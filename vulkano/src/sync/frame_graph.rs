@@ -0,0 +1,826 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Scheduling of the passes of a frame based on the resources they read and write.
+//!
+//! A *frame graph* is a description of the work done during a frame, split into *passes*. Each
+//! pass is submitted to a queue and declares which resources (buffers or images) it reads and
+//! writes, at which pipeline stages and with which access types. From this description the
+//! `FrameGraph` computes a `FrameSchedule`, which contains for each pass:
+//!
+//! - The value that the pass must signal on the timeline semaphore of its queue.
+//! - The timeline semaphore values of other queues that the pass must wait for.
+//! - The pipeline barriers that must be recorded at the start of the pass in order to synchronize
+//!   with previous passes on the same queue, including image layout transitions.
+//!
+//! Passes are scheduled in the order in which they are declared, and each queue is expected to
+//! own one `TimelineSemaphore`. The values of the schedule are relative to the value of the
+//! timeline semaphores at the start of the frame, see `ScheduledPass::add_to_submission`.
+//!
+//! Resources are identified by a `ResourceId` rather than by an actual buffer or image, so that
+//! the same graph can be compiled once and reused for multiple frames. It is the responsibility
+//! of the user to record the barriers of the schedule with the actual resources, for example with
+//! an `UnsafeCommandBufferBuilderPipelineBarrier`.
+//!
+//! > **Note**: The schedule doesn't perform queue family ownership transfers. Resources that are
+//! > used by queues of different families must be created with concurrent sharing.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::image::ImageLayout;
+//! use vulkano::sync::AccessFlagBits;
+//! use vulkano::sync::PipelineStages;
+//! use vulkano::sync::frame_graph::FrameGraph;
+//!
+//! let mut graph = FrameGraph::new();
+//! let particles = graph.add_buffer();
+//! let color = graph.add_image(ImageLayout::Undefined);
+//!
+//! // The particles are simulated on the compute queue (queue 1)...
+//! let simulate = graph.add_pass(1)
+//!     .write_buffer(particles,
+//!                   PipelineStages { compute_shader: true, .. PipelineStages::none() },
+//!                   AccessFlagBits { shader_write: true, .. AccessFlagBits::none() })
+//!     .build();
+//!
+//! // ... then drawn on the graphics queue (queue 0).
+//! let draw = graph.add_pass(0)
+//!     .read_buffer(particles,
+//!                  PipelineStages { vertex_input: true, .. PipelineStages::none() },
+//!                  AccessFlagBits { vertex_attribute_read: true, .. AccessFlagBits::none() })
+//!     .write_image(color,
+//!                  PipelineStages { color_attachment_output: true, .. PipelineStages::none() },
+//!                  AccessFlagBits { color_attachment_write: true, .. AccessFlagBits::none() },
+//!                  ImageLayout::ColorAttachmentOptimal)
+//!     .build();
+//!
+//! let schedule = graph.compile().unwrap();
+//! let draw = schedule.pass(draw);
+//! assert_eq!(draw.waits.len(), 1);
+//! assert_eq!(draw.waits[0].queue, 1);
+//! ```
+
+use std::error;
+use std::fmt;
+
+use command_buffer::submit::SubmitCommandBufferBuilder;
+use image::ImageLayout;
+use sync::AccessFlagBits;
+use sync::PipelineStages;
+use sync::TimelineSemaphore;
+
+/// Identifier of a resource declared in a `FrameGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ResourceId(usize);
+
+/// Identifier of a pass declared in a `FrameGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PassId(usize);
+
+/// Description of the passes of a frame and of the resources they use.
+#[derive(Debug, Clone, Default)]
+pub struct FrameGraph {
+    // For each resource, `None` if it's a buffer or the initial layout if it's an image.
+    resources: Vec<Option<ImageLayout>>,
+    passes: Vec<Pass>,
+}
+
+#[derive(Debug, Clone)]
+struct Pass {
+    queue: u32,
+    accesses: Vec<Access>,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Access {
+    resource: ResourceId,
+    stages: PipelineStages,
+    access: AccessFlagBits,
+    write: bool,
+    // Only relevant for images.
+    layout: ImageLayout,
+}
+
+impl FrameGraph {
+    /// Builds a new empty `FrameGraph`.
+    #[inline]
+    pub fn new() -> FrameGraph {
+        FrameGraph::default()
+    }
+
+    /// Declares a new buffer resource.
+    #[inline]
+    pub fn add_buffer(&mut self) -> ResourceId {
+        self.resources.push(None);
+        ResourceId(self.resources.len() - 1)
+    }
+
+    /// Declares a new image resource, whose layout at the start of the frame is `initial_layout`.
+    ///
+    /// Passing `ImageLayout::Undefined` means that the content of the image at the start of the
+    /// frame doesn't need to be preserved.
+    #[inline]
+    pub fn add_image(&mut self, initial_layout: ImageLayout) -> ResourceId {
+        self.resources.push(Some(initial_layout));
+        ResourceId(self.resources.len() - 1)
+    }
+
+    /// Starts declaring a new pass that will be submitted to the queue whose index is `queue`.
+    ///
+    /// The index of the queue is chosen by the user, and is used to know which passes execute on
+    /// the same queue and which timeline semaphore to wait for.
+    #[inline]
+    pub fn add_pass(&mut self, queue: u32) -> PassBuilder<'_> {
+        PassBuilder {
+            graph: self,
+            pass: Pass {
+                queue,
+                accesses: Vec::new(),
+            },
+        }
+    }
+
+    /// Computes the order of the submissions and the synchronization between them.
+    pub fn compile(&self) -> Result<FrameSchedule, FrameGraphError> {
+        // Last write of each resource, and list of the reads that happened since then.
+        let mut states: Vec<ResourceState> = self
+            .resources
+            .iter()
+            .map(|&layout| ResourceState {
+                last_write: None,
+                reads: Vec::new(),
+                layout: layout.unwrap_or(ImageLayout::Undefined),
+            })
+            .collect();
+
+        // Number of passes scheduled so far on each queue.
+        let mut signal_counts: Vec<(u32, u64)> = Vec::new();
+        // For each queue, the values of each other queue that it has already waited for, and the
+        // stages that were blocked by each wait.
+        let mut known_values: Vec<(u32, u32, u64, PipelineStages)> = Vec::new();
+
+        let mut passes: Vec<ScheduledPass> = Vec::with_capacity(self.passes.len());
+
+        for (pass_index, pass) in self.passes.iter().enumerate() {
+            let pass_id = PassId(pass_index);
+
+            let signal_value = match signal_counts.iter_mut().find(|c| c.0 == pass.queue) {
+                Some(count) => {
+                    count.1 += 1;
+                    count.1
+                }
+                None => {
+                    signal_counts.push((pass.queue, 1));
+                    1
+                }
+            };
+
+            let mut waits: Vec<TimelineWait> = Vec::new();
+            let mut barriers = Vec::new();
+
+            for (num, access) in pass.accesses.iter().enumerate() {
+                let state = match states.get_mut(access.resource.0) {
+                    Some(s) => s,
+                    None => {
+                        return Err(FrameGraphError::UnknownResource {
+                            pass: pass_id,
+                            resource: access.resource,
+                        })
+                    }
+                };
+
+                let is_image = self.resources[access.resource.0].is_some();
+
+                if pass.accesses[..num]
+                    .iter()
+                    .any(|a| a.resource == access.resource)
+                {
+                    return Err(FrameGraphError::ResourceAccessedTwice {
+                        pass: pass_id,
+                        resource: access.resource,
+                    });
+                }
+
+                if is_image
+                    && (access.layout == ImageLayout::Undefined
+                        || access.layout == ImageLayout::Preinitialized)
+                {
+                    return Err(FrameGraphError::InvalidLayout {
+                        pass: pass_id,
+                        resource: access.resource,
+                    });
+                }
+
+                let layout_transition = is_image && state.layout != access.layout;
+
+                // Previous accesses that this access must be ordered after.
+                let mut dependencies: Vec<(PassId, PipelineStages, AccessFlagBits)> = Vec::new();
+                if let Some(last_write) = state.last_write {
+                    dependencies.push(last_write);
+                }
+                if access.write || layout_transition {
+                    for &(read_pass, read_stages) in &state.reads {
+                        dependencies.push((read_pass, read_stages, AccessFlagBits::none()));
+                    }
+                }
+
+                let mut barrier = ResourceBarrier {
+                    resource: access.resource,
+                    source_stages: PipelineStages::none(),
+                    source_access: AccessFlagBits::none(),
+                    destination_stages: access.stages,
+                    destination_access: access.access,
+                    layout_transition: if layout_transition {
+                        Some((state.layout, access.layout))
+                    } else {
+                        None
+                    },
+                };
+
+                for &(dep_pass, dep_stages, dep_access) in &dependencies {
+                    let dep_queue = self.passes[dep_pass.0].queue;
+                    if dep_queue == pass.queue {
+                        barrier.source_stages |= dep_stages;
+                        barrier.source_access |= dep_access;
+                        continue;
+                    }
+
+                    // The semaphore wait makes the writes of the other queue available and
+                    // visible to the waiting stages. A layout transition, if any, must then happen
+                    // after the wait.
+                    let dep_value = passes[dep_pass.0].signal_value;
+                    if layout_transition {
+                        barrier.source_stages |= access.stages;
+                    }
+
+                    // A previous wait only covers this access if it blocked all of its stages.
+                    let known_stages = known_values
+                        .iter()
+                        .filter(|k| k.0 == pass.queue && k.1 == dep_queue && k.2 >= dep_value)
+                        .fold(PipelineStages::none(), |stages, k| stages | k.3);
+                    if known_stages | access.stages == known_stages {
+                        continue;
+                    }
+
+                    match waits.iter_mut().find(|w| w.queue == dep_queue) {
+                        Some(wait) => {
+                            wait.value = wait.value.max(dep_value);
+                            wait.stages |= access.stages;
+                        }
+                        None => waits.push(TimelineWait {
+                            queue: dep_queue,
+                            value: dep_value,
+                            stages: access.stages,
+                        }),
+                    }
+                }
+
+                if layout_transition && barrier.source_stages == PipelineStages::none() {
+                    // First use of the image in the frame.
+                    barrier.source_stages = PipelineStages {
+                        top_of_pipe: true,
+                        ..PipelineStages::none()
+                    };
+                }
+
+                if barrier.source_stages != PipelineStages::none() {
+                    barriers.push(barrier);
+                }
+
+                // Updating the state of the resource.
+                if access.write || layout_transition {
+                    let written = if access.write {
+                        access.access
+                    } else {
+                        AccessFlagBits::none()
+                    };
+                    state.last_write = Some((pass_id, access.stages, written));
+                    state.reads.clear();
+                }
+                if !access.write {
+                    state.reads.push((pass_id, access.stages));
+                }
+                state.layout = access.layout;
+            }
+
+            for wait in &waits {
+                known_values.push((pass.queue, wait.queue, wait.value, wait.stages));
+            }
+
+            passes.push(ScheduledPass {
+                pass: pass_id,
+                queue: pass.queue,
+                signal_value,
+                waits,
+                barriers,
+            });
+        }
+
+        Ok(FrameSchedule {
+            passes,
+            signal_counts,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ResourceState {
+    last_write: Option<(PassId, PipelineStages, AccessFlagBits)>,
+    reads: Vec<(PassId, PipelineStages)>,
+    layout: ImageLayout,
+}
+
+/// Builder for a pass of a `FrameGraph`. Returned by `FrameGraph::add_pass`.
+pub struct PassBuilder<'g> {
+    graph: &'g mut FrameGraph,
+    pass: Pass,
+}
+
+impl<'g> PassBuilder<'g> {
+    /// Declares that the pass reads from a buffer.
+    #[inline]
+    pub fn read_buffer(
+        self,
+        resource: ResourceId,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+    ) -> PassBuilder<'g> {
+        self.access(resource, stages, access, false, ImageLayout::Undefined)
+    }
+
+    /// Declares that the pass writes to a buffer. Use this function as well if the pass both
+    /// reads and writes the buffer.
+    #[inline]
+    pub fn write_buffer(
+        self,
+        resource: ResourceId,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+    ) -> PassBuilder<'g> {
+        self.access(resource, stages, access, true, ImageLayout::Undefined)
+    }
+
+    /// Declares that the pass reads from an image, which must be in `layout`.
+    #[inline]
+    pub fn read_image(
+        self,
+        resource: ResourceId,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+        layout: ImageLayout,
+    ) -> PassBuilder<'g> {
+        self.access(resource, stages, access, false, layout)
+    }
+
+    /// Declares that the pass writes to an image, which must be in `layout`. Use this function as
+    /// well if the pass both reads and writes the image.
+    #[inline]
+    pub fn write_image(
+        self,
+        resource: ResourceId,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+        layout: ImageLayout,
+    ) -> PassBuilder<'g> {
+        self.access(resource, stages, access, true, layout)
+    }
+
+    /// Adds the pass to the graph.
+    #[inline]
+    pub fn build(self) -> PassId {
+        self.graph.passes.push(self.pass);
+        PassId(self.graph.passes.len() - 1)
+    }
+
+    #[inline]
+    fn access(
+        mut self,
+        resource: ResourceId,
+        stages: PipelineStages,
+        access: AccessFlagBits,
+        write: bool,
+        layout: ImageLayout,
+    ) -> PassBuilder<'g> {
+        self.pass.accesses.push(Access {
+            resource,
+            stages,
+            access,
+            write,
+            layout,
+        });
+        self
+    }
+}
+
+/// Result of compiling a `FrameGraph`.
+#[derive(Debug, Clone)]
+pub struct FrameSchedule {
+    passes: Vec<ScheduledPass>,
+    signal_counts: Vec<(u32, u64)>,
+}
+
+impl FrameSchedule {
+    /// Returns the passes in the order in which they must be submitted.
+    #[inline]
+    pub fn passes(&self) -> &[ScheduledPass] {
+        &self.passes
+    }
+
+    /// Returns the scheduling information of a pass.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the pass doesn't belong to the graph that produced this schedule.
+    ///
+    #[inline]
+    pub fn pass(&self, pass: PassId) -> &ScheduledPass {
+        &self.passes[pass.0]
+    }
+
+    /// Returns the number of passes submitted to the given queue, which is also the amount by
+    /// which the timeline semaphore of that queue is incremented over the frame.
+    #[inline]
+    pub fn signal_count(&self, queue: u32) -> u64 {
+        self.signal_counts
+            .iter()
+            .find(|c| c.0 == queue)
+            .map(|c| c.1)
+            .unwrap_or(0)
+    }
+}
+
+/// Scheduling information of a single pass.
+#[derive(Debug, Clone)]
+pub struct ScheduledPass {
+    /// The pass this information is about.
+    pub pass: PassId,
+    /// Index of the queue the pass must be submitted to.
+    pub queue: u32,
+    /// Value that the pass must signal on the timeline semaphore of its queue, relative to the
+    /// value of that semaphore at the start of the frame.
+    pub signal_value: u64,
+    /// Timeline semaphore values of other queues that must be waited for before the pass starts.
+    pub waits: Vec<TimelineWait>,
+    /// Pipeline barriers that must be recorded at the start of the pass.
+    pub barriers: Vec<ResourceBarrier>,
+}
+
+impl ScheduledPass {
+    /// Adds the semaphore waits and the semaphore signal of this pass to a submission.
+    ///
+    /// `semaphores` must contain the timeline semaphore of each queue, indexed by queue index,
+    /// and `base_values` the value of each of these semaphores at the start of the frame.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `semaphores` or `base_values` don't contain an entry for one of the queues
+    ///   used by this pass.
+    ///
+    /// # Safety
+    ///
+    /// Same as `SubmitCommandBufferBuilder::add_wait_timeline_semaphore` and
+    /// `SubmitCommandBufferBuilder::add_signal_timeline_semaphore`.
+    ///
+    pub unsafe fn add_to_submission<'a>(
+        &self,
+        builder: &mut SubmitCommandBufferBuilder<'a>,
+        semaphores: &'a [TimelineSemaphore],
+        base_values: &[u64],
+    ) {
+        for wait in &self.waits {
+            builder.add_wait_timeline_semaphore(
+                &semaphores[wait.queue as usize],
+                base_values[wait.queue as usize] + wait.value,
+                wait.stages,
+            );
+        }
+
+        builder.add_signal_timeline_semaphore(
+            &semaphores[self.queue as usize],
+            base_values[self.queue as usize] + self.signal_value,
+        );
+    }
+}
+
+/// Wait operation on the timeline semaphore of another queue.
+#[derive(Debug, Copy, Clone)]
+pub struct TimelineWait {
+    /// Index of the queue whose semaphore must be waited for.
+    pub queue: u32,
+    /// Value to wait for, relative to the value of the semaphore at the start of the frame.
+    pub value: u64,
+    /// Stages of the pass that must wait.
+    pub stages: PipelineStages,
+}
+
+/// Pipeline barrier on a resource that must be recorded at the start of a pass.
+#[derive(Debug, Copy, Clone)]
+pub struct ResourceBarrier {
+    /// The resource concerned by the barrier.
+    pub resource: ResourceId,
+    /// Stages of the previous passes that must be finished.
+    pub source_stages: PipelineStages,
+    /// Memory accesses of the previous passes that must be made available.
+    pub source_access: AccessFlagBits,
+    /// Stages of this pass that must wait.
+    pub destination_stages: PipelineStages,
+    /// Memory accesses of this pass that must see the writes of the previous passes.
+    pub destination_access: AccessFlagBits,
+    /// If the resource is an image whose layout must change, contains the old and new layouts.
+    pub layout_transition: Option<(ImageLayout, ImageLayout)>,
+}
+
+/// Error that can happen when compiling a `FrameGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FrameGraphError {
+    /// A pass uses a resource that wasn't declared in this graph.
+    UnknownResource {
+        /// The pass that uses the resource.
+        pass: PassId,
+        /// The unknown resource.
+        resource: ResourceId,
+    },
+
+    /// A pass declares multiple accesses to the same resource. Accesses must be merged instead.
+    ResourceAccessedTwice {
+        /// The pass that uses the resource.
+        pass: PassId,
+        /// The resource.
+        resource: ResourceId,
+    },
+
+    /// A pass uses an image in the `Undefined` or `Preinitialized` layout.
+    InvalidLayout {
+        /// The pass that uses the image.
+        pass: PassId,
+        /// The image.
+        resource: ResourceId,
+    },
+}
+
+impl error::Error for FrameGraphError {}
+
+impl fmt::Display for FrameGraphError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                FrameGraphError::UnknownResource { .. } => {
+                    "a pass uses a resource that wasn't declared in this graph"
+                }
+                FrameGraphError::ResourceAccessedTwice { .. } => {
+                    "a pass declares multiple accesses to the same resource"
+                }
+                FrameGraphError::InvalidLayout { .. } => {
+                    "a pass uses an image in the `Undefined` or `Preinitialized` layout"
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::ImageLayout;
+    use sync::frame_graph::FrameGraph;
+    use sync::frame_graph::FrameGraphError;
+    use sync::AccessFlagBits;
+    use sync::PipelineStages;
+
+    fn compute() -> PipelineStages {
+        PipelineStages {
+            compute_shader: true,
+            ..PipelineStages::none()
+        }
+    }
+
+    fn fragment() -> PipelineStages {
+        PipelineStages {
+            fragment_shader: true,
+            ..PipelineStages::none()
+        }
+    }
+
+    fn shader_read() -> AccessFlagBits {
+        AccessFlagBits {
+            shader_read: true,
+            ..AccessFlagBits::none()
+        }
+    }
+
+    fn shader_write() -> AccessFlagBits {
+        AccessFlagBits {
+            shader_write: true,
+            ..AccessFlagBits::none()
+        }
+    }
+
+    #[test]
+    fn same_queue_barrier() {
+        let mut graph = FrameGraph::new();
+        let buf = graph.add_buffer();
+        let a = graph
+            .add_pass(0)
+            .write_buffer(buf, compute(), shader_write())
+            .build();
+        let b = graph
+            .add_pass(0)
+            .read_buffer(buf, fragment(), shader_read())
+            .build();
+
+        let schedule = graph.compile().unwrap();
+        assert!(schedule.pass(a).barriers.is_empty());
+
+        let b = schedule.pass(b);
+        assert!(b.waits.is_empty());
+        assert_eq!(b.signal_value, 2);
+        assert_eq!(b.barriers.len(), 1);
+        assert_eq!(b.barriers[0].source_stages, compute());
+        assert!(b.barriers[0].source_access.shader_write);
+        assert_eq!(b.barriers[0].destination_stages, fragment());
+        assert!(b.barriers[0].layout_transition.is_none());
+        assert_eq!(schedule.signal_count(0), 2);
+    }
+
+    #[test]
+    fn cross_queue_wait() {
+        let mut graph = FrameGraph::new();
+        let buf = graph.add_buffer();
+        graph
+            .add_pass(1)
+            .write_buffer(buf, compute(), shader_write())
+            .build();
+        let b = graph
+            .add_pass(0)
+            .read_buffer(buf, fragment(), shader_read())
+            .build();
+
+        let schedule = graph.compile().unwrap();
+        let b = schedule.pass(b);
+        assert!(b.barriers.is_empty());
+        assert_eq!(b.waits.len(), 1);
+        assert_eq!(b.waits[0].queue, 1);
+        assert_eq!(b.waits[0].value, 1);
+        assert_eq!(b.waits[0].stages, fragment());
+    }
+
+    #[test]
+    fn redundant_wait_skipped() {
+        let mut graph = FrameGraph::new();
+        let buf1 = graph.add_buffer();
+        let buf2 = graph.add_buffer();
+        graph
+            .add_pass(1)
+            .write_buffer(buf1, compute(), shader_write())
+            .write_buffer(buf2, compute(), shader_write())
+            .build();
+        let b = graph
+            .add_pass(0)
+            .read_buffer(buf1, fragment(), shader_read())
+            .build();
+        let c = graph
+            .add_pass(0)
+            .read_buffer(buf2, fragment(), shader_read())
+            .build();
+
+        let schedule = graph.compile().unwrap();
+        assert_eq!(schedule.pass(b).waits.len(), 1);
+        assert!(schedule.pass(c).waits.is_empty());
+    }
+
+    #[test]
+    fn wait_widened_for_other_stages() {
+        let vertex = PipelineStages {
+            vertex_shader: true,
+            ..PipelineStages::none()
+        };
+
+        let mut graph = FrameGraph::new();
+        let buf1 = graph.add_buffer();
+        let buf2 = graph.add_buffer();
+        graph
+            .add_pass(1)
+            .write_buffer(buf1, compute(), shader_write())
+            .write_buffer(buf2, compute(), shader_write())
+            .build();
+        let b = graph
+            .add_pass(0)
+            .read_buffer(buf1, fragment(), shader_read())
+            .build();
+        let c = graph
+            .add_pass(0)
+            .read_buffer(buf2, vertex, shader_read())
+            .build();
+        let d = graph
+            .add_pass(0)
+            .read_buffer(buf2, fragment() | vertex, shader_read())
+            .build();
+
+        let schedule = graph.compile().unwrap();
+        assert_eq!(schedule.pass(b).waits[0].stages, fragment());
+
+        let c = schedule.pass(c);
+        assert_eq!(c.waits.len(), 1);
+        assert_eq!(c.waits[0].queue, 1);
+        assert_eq!(c.waits[0].value, 1);
+        assert_eq!(c.waits[0].stages, vertex);
+
+        // Both stages have already been waited for by `b` and `c`.
+        assert!(schedule.pass(d).waits.is_empty());
+    }
+
+    #[test]
+    fn write_after_read() {
+        let mut graph = FrameGraph::new();
+        let buf = graph.add_buffer();
+        graph
+            .add_pass(0)
+            .read_buffer(buf, fragment(), shader_read())
+            .build();
+        let b = graph
+            .add_pass(0)
+            .write_buffer(buf, compute(), shader_write())
+            .build();
+
+        let schedule = graph.compile().unwrap();
+        let b = schedule.pass(b);
+        assert_eq!(b.barriers.len(), 1);
+        assert_eq!(b.barriers[0].source_stages, fragment());
+        assert!(!b.barriers[0].source_access.shader_read);
+    }
+
+    #[test]
+    fn image_layout_transitions() {
+        let mut graph = FrameGraph::new();
+        let img = graph.add_image(ImageLayout::Undefined);
+        let a = graph
+            .add_pass(0)
+            .write_image(img, compute(), shader_write(), ImageLayout::General)
+            .build();
+        let b = graph
+            .add_pass(0)
+            .read_image(
+                img,
+                fragment(),
+                shader_read(),
+                ImageLayout::ShaderReadOnlyOptimal,
+            )
+            .build();
+
+        let schedule = graph.compile().unwrap();
+        let a = schedule.pass(a);
+        assert_eq!(a.barriers.len(), 1);
+        assert!(a.barriers[0].source_stages.top_of_pipe);
+        assert_eq!(
+            a.barriers[0].layout_transition,
+            Some((ImageLayout::Undefined, ImageLayout::General))
+        );
+
+        let b = schedule.pass(b);
+        assert_eq!(b.barriers.len(), 1);
+        assert_eq!(
+            b.barriers[0].layout_transition,
+            Some((ImageLayout::General, ImageLayout::ShaderReadOnlyOptimal))
+        );
+    }
+
+    #[test]
+    fn resource_accessed_twice() {
+        let mut graph = FrameGraph::new();
+        let buf = graph.add_buffer();
+        graph
+            .add_pass(0)
+            .read_buffer(buf, fragment(), shader_read())
+            .write_buffer(buf, compute(), shader_write())
+            .build();
+
+        match graph.compile() {
+            Err(FrameGraphError::ResourceAccessedTwice { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_layout() {
+        let mut graph = FrameGraph::new();
+        let img = graph.add_image(ImageLayout::Undefined);
+        graph
+            .add_pass(0)
+            .read_image(img, fragment(), shader_read(), ImageLayout::Undefined)
+            .build();
+
+        match graph.compile() {
+            Err(FrameGraphError::InvalidLayout { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use sync::FenceSignalFuture;
+use sync::FlushError;
+use sync::GpuFuture;
+
+/// Set of futures that are each followed by a fence, so that the CPU can wait for all of them at
+/// once.
+///
+/// A single fence can only be signaled by a submission to a single queue. When the work of a
+/// frame is spread over multiple queues, push the last future of each queue in a `FenceSet` and
+/// call `wait_all` to wait until all the GPU work of the frame is done.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::sync::FenceSet;
+/// # use vulkano::sync::GpuFuture;
+/// # let graphics_future: Box<dyn GpuFuture + Send + Sync> = return;
+/// # let compute_future: Box<dyn GpuFuture + Send + Sync> = return;
+///
+/// let mut fences = FenceSet::new();
+/// fences.push(graphics_future).unwrap();
+/// fences.push(compute_future).unwrap();
+/// fences.wait_all(None).unwrap();
+/// ```
+pub struct FenceSet {
+    futures: Vec<FenceSignalFuture<Box<dyn GpuFuture + Send + Sync>>>,
+}
+
+impl FenceSet {
+    /// Builds an empty `FenceSet`.
+    #[inline]
+    pub fn new() -> FenceSet {
+        FenceSet {
+            futures: Vec::new(),
+        }
+    }
+
+    /// Signals a fence after `future` and flushes it, then adds it to the set.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `future` isn't tied to a queue, for example if it is a `NowFuture`.
+    ///
+    pub fn push<F>(&mut self, future: F) -> Result<(), FlushError>
+    where
+        F: GpuFuture + Send + Sync + 'static,
+    {
+        let future =
+            (Box::new(future) as Box<dyn GpuFuture + Send + Sync>).then_signal_fence_and_flush()?;
+        self.futures.push(future);
+        Ok(())
+    }
+
+    /// Returns the number of futures in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.futures.len()
+    }
+
+    /// Returns true if the set is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.futures.is_empty()
+    }
+
+    /// Waits until all the fences of the set are signaled, or at least until the timeout
+    /// duration has elapsed.
+    ///
+    /// The timeout applies to the whole set and not to each fence individually. Once this
+    /// function returns `Ok`, the set is empty and can be reused for the next batch.
+    pub fn wait_all(&mut self, timeout: Option<Duration>) -> Result<(), FlushError> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        for future in self.futures.iter() {
+            let remaining = deadline.map(|deadline| {
+                let now = Instant::now();
+                if deadline > now {
+                    deadline - now
+                } else {
+                    Duration::new(0, 0)
+                }
+            });
+
+            future.wait(remaining)?;
+        }
+
+        self.futures.clear();
+        Ok(())
+    }
+}
+
+impl Default for FenceSet {
+    #[inline]
+    fn default() -> FenceSet {
+        FenceSet::new()
+    }
+}
@@ -32,11 +32,13 @@ use sync::FenceWaitError;
 use sync::PipelineStages;
 use OomError;
 
+pub use self::fence_set::FenceSet;
 pub use self::fence_signal::{FenceSignalFuture, FenceSignalFutureBehavior};
 pub use self::join::JoinFuture;
 pub use self::now::{now, NowFuture};
 pub use self::semaphore_signal::SemaphoreSignalFuture;
 
+mod fence_set;
 mod fence_signal;
 mod join;
 mod now;
@@ -468,6 +470,12 @@ pub enum FlushError {
 
     /// The flush operation needed to block, but the timeout has elapsed.
     Timeout,
+
+    /// A protected command buffer was submitted to a queue that isn't protected-capable.
+    QueueNotProtected,
+
+    /// Protected and unprotected command buffers were submitted together.
+    ProtectedCommandBufferMismatch,
 }
 
 impl error::Error for FlushError {
@@ -500,6 +508,13 @@ impl fmt::Display for FlushError {
                     "the flush operation needed to block, but the timeout has \
                                     elapsed"
                 }
+                FlushError::QueueNotProtected => {
+                    "a protected command buffer was submitted to a queue that isn't \
+                     protected-capable"
+                }
+                FlushError::ProtectedCommandBufferMismatch => {
+                    "protected and unprotected command buffers were submitted together"
+                }
             }
         )
     }
@@ -531,6 +546,10 @@ impl From<SubmitCommandBufferError> for FlushError {
         match err {
             SubmitCommandBufferError::OomError(err) => FlushError::OomError(err),
             SubmitCommandBufferError::DeviceLost => FlushError::DeviceLost,
+            SubmitCommandBufferError::QueueNotProtected => FlushError::QueueNotProtected,
+            SubmitCommandBufferError::ProtectedCommandBufferMismatch => {
+                FlushError::ProtectedCommandBufferMismatch
+            }
         }
     }
 }
@@ -112,21 +112,30 @@ pub use self::fence::FenceWaitError;
 pub use self::future::now;
 pub use self::future::AccessCheckError;
 pub use self::future::AccessError;
+pub use self::future::FenceSet;
 pub use self::future::FenceSignalFuture;
 pub use self::future::FlushError;
 pub use self::future::GpuFuture;
 pub use self::future::JoinFuture;
 pub use self::future::NowFuture;
 pub use self::future::SemaphoreSignalFuture;
+pub(crate) use self::pipeline::access2_to_access1;
 pub use self::pipeline::AccessFlagBits;
 pub use self::pipeline::PipelineStages;
+pub use self::semaphore::ExternalSemaphoreHandleType;
+pub use self::semaphore::ExternalSemaphoreHandleTypes;
 pub use self::semaphore::Semaphore;
+pub use self::semaphore::SemaphoreError;
+pub use self::timeline_semaphore::TimelineSemaphore;
+pub use self::timeline_semaphore::TimelineSemaphoreError;
 
 mod event;
 mod fence;
+pub mod frame_graph;
 mod future;
 mod pipeline;
 mod semaphore;
+mod timeline_semaphore;
 
 /// Declares in which queue(s) a resource can be used.
 ///
@@ -114,10 +114,10 @@ macro_rules! access_flags {
             }
 
             #[inline]
-            pub(crate) fn into_vulkan_bits(self) -> vk::AccessFlagBits {
+            pub(crate) fn into_vulkan_bits2(self) -> vk::AccessFlags2 {
                 let mut result = 0;
                 $(
-                    if self.$elem { result |= $val }
+                    if self.$elem { result |= $val as vk::AccessFlags2 }
                 )+
                 result
             }
@@ -165,9 +165,19 @@ access_flags! {
     host_write => vk::ACCESS_HOST_WRITE_BIT,
     memory_read => vk::ACCESS_MEMORY_READ_BIT,
     memory_write => vk::ACCESS_MEMORY_WRITE_BIT,
+    shader_sampled_read => vk::ACCESS_2_SHADER_SAMPLED_READ_BIT,
+    shader_storage_read => vk::ACCESS_2_SHADER_STORAGE_READ_BIT,
+    shader_storage_write => vk::ACCESS_2_SHADER_STORAGE_WRITE_BIT,
 }
 
 impl AccessFlagBits {
+    // Returns the synchronization1 flags. The flags that only exist with synchronization2 are
+    // replaced with the flags that include them.
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::AccessFlagBits {
+        access2_to_access1(self.into_vulkan_bits2())
+    }
+
     /// Returns true if the access flags can be used with the given pipeline stages.
     ///
     /// Corresponds to `Table 4. Supported access types` in section `6.1.3. Access Types` of the
@@ -188,7 +198,12 @@ impl AccessFlagBits {
             return false;
         }
 
-        if (self.uniform_read || self.shader_read || self.shader_write)
+        if (self.uniform_read
+            || self.shader_read
+            || self.shader_write
+            || self.shader_sampled_read
+            || self.shader_storage_read
+            || self.shader_storage_write)
             && !stages.vertex_shader
             && !stages.tessellation_control_shader
             && !stages.tessellation_evaluation_shader
@@ -230,3 +245,44 @@ impl AccessFlagBits {
         true
     }
 }
+
+// Converts synchronization2 access flags to synchronization1 access flags. The shader sampled
+// and storage reads are included in the shader read access, and the shader storage write is
+// included in the shader write access.
+#[inline]
+pub(crate) fn access2_to_access1(bits: vk::AccessFlags2) -> vk::AccessFlags {
+    let mut result = bits & 0xffff_ffff;
+    if bits & (vk::ACCESS_2_SHADER_SAMPLED_READ_BIT | vk::ACCESS_2_SHADER_STORAGE_READ_BIT) != 0 {
+        result |= vk::ACCESS_SHADER_READ_BIT as vk::AccessFlags2;
+    }
+    if bits & vk::ACCESS_2_SHADER_STORAGE_WRITE_BIT != 0 {
+        result |= vk::ACCESS_SHADER_WRITE_BIT as vk::AccessFlags2;
+    }
+    result as vk::AccessFlags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access2_fallback() {
+        let access = AccessFlagBits {
+            shader_sampled_read: true,
+            shader_storage_write: true,
+            transfer_read: true,
+            ..AccessFlagBits::none()
+        };
+
+        assert_eq!(
+            access.into_vulkan_bits(),
+            vk::ACCESS_SHADER_READ_BIT | vk::ACCESS_SHADER_WRITE_BIT | vk::ACCESS_TRANSFER_READ_BIT
+        );
+        assert_eq!(
+            access.into_vulkan_bits2(),
+            vk::ACCESS_2_SHADER_SAMPLED_READ_BIT
+                | vk::ACCESS_2_SHADER_STORAGE_WRITE_BIT
+                | vk::ACCESS_TRANSFER_READ_BIT as vk::AccessFlags2
+        );
+    }
+}
@@ -7,14 +7,26 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::error;
+use std::fmt;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
 
+#[cfg(unix)]
+use std::fs::File;
+#[cfg(windows)]
+use std::os::raw::c_void;
+#[cfg(unix)]
+use std::os::unix::io::FromRawFd;
+#[cfg(unix)]
+use std::os::unix::io::IntoRawFd;
+
 use check_errors;
 use device::Device;
 use device::DeviceOwned;
 use vk;
+use Error;
 use OomError;
 use SafeDeref;
 use VulkanObject;
@@ -31,6 +43,7 @@ where
     semaphore: vk::Semaphore,
     device: D,
     must_put_in_pool: bool,
+    export_handle_types: ExternalSemaphoreHandleTypes,
 }
 
 impl<D> Semaphore<D>
@@ -50,6 +63,7 @@ where
                 device: device,
                 semaphore: raw_sem,
                 must_put_in_pool: true,
+                export_handle_types: ExternalSemaphoreHandleTypes::none(),
             }),
             None => {
                 // Pool is empty, alloc new semaphore
@@ -88,8 +102,459 @@ where
             device: device,
             semaphore: semaphore,
             must_put_in_pool: must_put_in_pool,
+            export_handle_types: ExternalSemaphoreHandleTypes::none(),
         })
     }
+
+    /// Builds a new semaphore that can be exported to the given handle types.
+    ///
+    /// The `khr_external_semaphore` extension must be enabled on the device, as well as
+    /// `khr_external_semaphore_fd` for the file descriptor handle types and
+    /// `khr_external_semaphore_win32` for the Windows handle types.
+    pub fn alloc_exportable(
+        device: D,
+        handle_types: ExternalSemaphoreHandleTypes,
+    ) -> Result<Semaphore<D>, SemaphoreError> {
+        check_external_extensions(&device, handle_types)?;
+
+        let semaphore = unsafe {
+            let export_infos = vk::ExportSemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_EXPORT_SEMAPHORE_CREATE_INFO,
+                pNext: ptr::null(),
+                handleTypes: handle_types.into(),
+            };
+
+            let infos = vk::SemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO,
+                pNext: &export_infos as *const _ as *const _,
+                flags: 0, // reserved
+            };
+
+            let vk = device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateSemaphore(
+                device.internal_object(),
+                &infos,
+                ptr::null(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(Semaphore {
+            device: device,
+            semaphore: semaphore,
+            must_put_in_pool: false,
+            export_handle_types: handle_types,
+        })
+    }
+
+    /// Builds a new semaphore whose payload is imported from a POSIX file descriptor.
+    ///
+    /// On success, the implementation takes ownership of the file descriptor and closes it when
+    /// it no longer needs it. On error, the file descriptor is closed.
+    ///
+    /// The import is permanent, except for `SyncFd` handles which can only be imported
+    /// temporarily: the semaphore returns to its own payload after the next wait operation.
+    ///
+    /// The `khr_external_semaphore` and `khr_external_semaphore_fd` extensions must be enabled on
+    /// the device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` is not `OpaqueFd` or `SyncFd`.
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must have been exported from a semaphore with the same handle type, by a device
+    ///   that has the same device UUID and driver UUID.
+    ///
+    #[cfg(unix)]
+    pub unsafe fn from_fd(
+        device: D,
+        handle_type: ExternalSemaphoreHandleType,
+        fd: File,
+    ) -> Result<Semaphore<D>, SemaphoreError> {
+        assert!(handle_type.is_fd());
+        check_external_extensions(&device, handle_type.into())?;
+
+        let semaphore = Semaphore::alloc_impl(device, false)?;
+
+        let fd = fd.into_raw_fd();
+        let infos = vk::ImportSemaphoreFdInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_SEMAPHORE_FD_INFO_KHR,
+            pNext: ptr::null(),
+            semaphore: semaphore.semaphore,
+            flags: if handle_type == ExternalSemaphoreHandleType::SyncFd {
+                vk::SEMAPHORE_IMPORT_TEMPORARY_BIT
+            } else {
+                0
+            },
+            handleType: handle_type as u32,
+            fd,
+        };
+
+        let vk = semaphore.device.pointers();
+        if let Err(err) =
+            check_errors(vk.ImportSemaphoreFdKHR(semaphore.device.internal_object(), &infos))
+        {
+            // Ownership of the file descriptor is only transferred on success.
+            drop(File::from_raw_fd(fd));
+            return Err(err.into());
+        }
+
+        Ok(semaphore)
+    }
+
+    /// Exports the payload of the semaphore to a new `OpaqueFd` POSIX file descriptor.
+    ///
+    /// The caller owns the returned file descriptor, and dropping the `File` closes it. Each call
+    /// returns a new file descriptor that refers to the same payload as the semaphore.
+    #[cfg(unix)]
+    #[inline]
+    pub fn export_fd(&self) -> Result<File, SemaphoreError> {
+        unsafe { self.export_fd_impl(ExternalSemaphoreHandleType::OpaqueFd) }
+    }
+
+    /// Exports the payload of the semaphore to a new `SyncFd` POSIX file descriptor.
+    ///
+    /// The caller owns the returned file descriptor, and dropping the `File` closes it. Contrary
+    /// to `export_fd`, the file descriptor is a snapshot of the pending signal operation, and
+    /// exporting it unsignals the semaphore like a wait operation would.
+    ///
+    /// # Safety
+    ///
+    /// - The semaphore must be signaled, or have a pending signal operation submitted.
+    ///
+    #[cfg(unix)]
+    #[inline]
+    pub unsafe fn export_sync_fd(&self) -> Result<File, SemaphoreError> {
+        self.export_fd_impl(ExternalSemaphoreHandleType::SyncFd)
+    }
+
+    #[cfg(unix)]
+    unsafe fn export_fd_impl(
+        &self,
+        handle_type: ExternalSemaphoreHandleType,
+    ) -> Result<File, SemaphoreError> {
+        if !self.export_handle_types.contains(handle_type) {
+            return Err(SemaphoreError::HandleTypeNotExportable { handle_type });
+        }
+
+        let infos = vk::SemaphoreGetFdInfoKHR {
+            sType: vk::STRUCTURE_TYPE_SEMAPHORE_GET_FD_INFO_KHR,
+            pNext: ptr::null(),
+            semaphore: self.semaphore,
+            handleType: handle_type as u32,
+        };
+
+        let vk = self.device.pointers();
+        let mut output = MaybeUninit::uninit();
+        check_errors(vk.GetSemaphoreFdKHR(
+            self.device.internal_object(),
+            &infos,
+            output.as_mut_ptr(),
+        ))?;
+        Ok(File::from_raw_fd(output.assume_init()))
+    }
+
+    /// Builds a new semaphore whose payload is imported from a Windows handle.
+    ///
+    /// Contrary to file descriptors, importing a Windows handle doesn't transfer its ownership.
+    /// The caller must still close `OpaqueWin32` and `D3D12Fence` handles with `CloseHandle`
+    /// once they are no longer needed, while `OpaqueWin32Kmt` handles are not reference-counted
+    /// and must not be closed.
+    ///
+    /// The `khr_external_semaphore` and `khr_external_semaphore_win32` extensions must be
+    /// enabled on the device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` is `OpaqueFd` or `SyncFd`.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must have been exported from a semaphore with the same handle type, by a device
+    ///   that has the same device UUID and driver UUID.
+    ///
+    #[cfg(windows)]
+    pub unsafe fn from_win32_handle(
+        device: D,
+        handle_type: ExternalSemaphoreHandleType,
+        handle: *mut c_void,
+    ) -> Result<Semaphore<D>, SemaphoreError> {
+        assert!(!handle_type.is_fd());
+        check_external_extensions(&device, handle_type.into())?;
+
+        let semaphore = Semaphore::alloc_impl(device, false)?;
+
+        let infos = vk::ImportSemaphoreWin32HandleInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_SEMAPHORE_WIN32_HANDLE_INFO_KHR,
+            pNext: ptr::null(),
+            semaphore: semaphore.semaphore,
+            flags: 0,
+            handleType: handle_type as u32,
+            handle,
+            name: ptr::null(),
+        };
+
+        let vk = semaphore.device.pointers();
+        check_errors(vk.ImportSemaphoreWin32HandleKHR(semaphore.device.internal_object(), &infos))?;
+
+        Ok(semaphore)
+    }
+
+    /// Exports the payload of the semaphore to a Windows handle.
+    ///
+    /// The caller owns the returned `OpaqueWin32` and `D3D12Fence` handles and must close them
+    /// with `CloseHandle`. `OpaqueWin32Kmt` handles are not reference-counted and must not be
+    /// closed; they stay valid as long as the payload of the semaphore exists.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` is `OpaqueFd` or `SyncFd`.
+    ///
+    #[cfg(windows)]
+    pub fn export_win32_handle(
+        &self,
+        handle_type: ExternalSemaphoreHandleType,
+    ) -> Result<*mut c_void, SemaphoreError> {
+        assert!(!handle_type.is_fd());
+
+        if !self.export_handle_types.contains(handle_type) {
+            return Err(SemaphoreError::HandleTypeNotExportable { handle_type });
+        }
+
+        unsafe {
+            let infos = vk::SemaphoreGetWin32HandleInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_GET_WIN32_HANDLE_INFO_KHR,
+                pNext: ptr::null(),
+                semaphore: self.semaphore,
+                handleType: handle_type as u32,
+            };
+
+            let vk = self.device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.GetSemaphoreWin32HandleKHR(
+                self.device.internal_object(),
+                &infos,
+                output.as_mut_ptr(),
+            ))?;
+            Ok(output.assume_init())
+        }
+    }
+
+    /// Returns the handle types that the semaphore can be exported to.
+    #[inline]
+    pub fn export_handle_types(&self) -> ExternalSemaphoreHandleTypes {
+        self.export_handle_types
+    }
+}
+
+// Checks that the extensions required by the given external handle types are enabled.
+fn check_external_extensions(
+    device: &Device,
+    handle_types: ExternalSemaphoreHandleTypes,
+) -> Result<(), SemaphoreError> {
+    let extensions = device.loaded_extensions();
+
+    if !extensions.khr_external_semaphore {
+        return Err(SemaphoreError::MissingExtension {
+            name: "VK_KHR_external_semaphore",
+        });
+    }
+
+    if (handle_types.opaque_fd || handle_types.sync_fd) && !extensions.khr_external_semaphore_fd {
+        return Err(SemaphoreError::MissingExtension {
+            name: "VK_KHR_external_semaphore_fd",
+        });
+    }
+
+    if (handle_types.opaque_win32 || handle_types.opaque_win32_kmt || handle_types.d3d12_fence)
+        && !extensions.khr_external_semaphore_win32
+    {
+        return Err(SemaphoreError::MissingExtension {
+            name: "VK_KHR_external_semaphore_win32",
+        });
+    }
+
+    Ok(())
+}
+
+/// Type of an external handle that the payload of a semaphore can be exported to or imported
+/// from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ExternalSemaphoreHandleType {
+    /// A POSIX file descriptor that refers to the payload of the semaphore.
+    OpaqueFd = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT,
+    /// A reference-counted Windows NT handle that refers to the payload of the semaphore.
+    OpaqueWin32 = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT,
+    /// A global share handle that refers to the payload of the semaphore. It is not
+    /// reference-counted.
+    OpaqueWin32Kmt = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT,
+    /// A Windows NT handle to a Direct3D 12 fence.
+    D3D12Fence = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT,
+    /// A POSIX file descriptor to a Linux sync file or Android fence.
+    SyncFd = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT,
+}
+
+impl ExternalSemaphoreHandleType {
+    /// Returns true if the handle is a POSIX file descriptor.
+    #[inline]
+    pub fn is_fd(&self) -> bool {
+        match *self {
+            ExternalSemaphoreHandleType::OpaqueFd | ExternalSemaphoreHandleType::SyncFd => true,
+            _ => false,
+        }
+    }
+}
+
+/// Set of external handle types that a semaphore can be exported to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ExternalSemaphoreHandleTypes {
+    pub opaque_fd: bool,
+    pub opaque_win32: bool,
+    pub opaque_win32_kmt: bool,
+    pub d3d12_fence: bool,
+    pub sync_fd: bool,
+}
+
+impl ExternalSemaphoreHandleTypes {
+    /// Builds an `ExternalSemaphoreHandleTypes` with all values set to false.
+    #[inline]
+    pub fn none() -> ExternalSemaphoreHandleTypes {
+        ExternalSemaphoreHandleTypes::default()
+    }
+
+    /// Builds an `ExternalSemaphoreHandleTypes` with `opaque_fd` set to true, which is the handle
+    /// type to use to share a semaphore with another process on POSIX systems.
+    #[inline]
+    pub fn posix() -> ExternalSemaphoreHandleTypes {
+        ExternalSemaphoreHandleTypes {
+            opaque_fd: true,
+            ..ExternalSemaphoreHandleTypes::none()
+        }
+    }
+
+    /// Returns true if `handle_type` is part of the set.
+    #[inline]
+    pub fn contains(&self, handle_type: ExternalSemaphoreHandleType) -> bool {
+        let bits: vk::ExternalSemaphoreHandleTypeFlags = (*self).into();
+        (bits & handle_type as u32) != 0
+    }
+}
+
+impl From<ExternalSemaphoreHandleType> for ExternalSemaphoreHandleTypes {
+    #[inline]
+    fn from(handle_type: ExternalSemaphoreHandleType) -> ExternalSemaphoreHandleTypes {
+        let mut result = ExternalSemaphoreHandleTypes::none();
+        match handle_type {
+            ExternalSemaphoreHandleType::OpaqueFd => result.opaque_fd = true,
+            ExternalSemaphoreHandleType::OpaqueWin32 => result.opaque_win32 = true,
+            ExternalSemaphoreHandleType::OpaqueWin32Kmt => result.opaque_win32_kmt = true,
+            ExternalSemaphoreHandleType::D3D12Fence => result.d3d12_fence = true,
+            ExternalSemaphoreHandleType::SyncFd => result.sync_fd = true,
+        }
+        result
+    }
+}
+
+impl From<ExternalSemaphoreHandleTypes> for vk::ExternalSemaphoreHandleTypeFlags {
+    #[inline]
+    fn from(val: ExternalSemaphoreHandleTypes) -> Self {
+        let mut result = 0;
+        if val.opaque_fd {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT;
+        }
+        if val.opaque_win32 {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT;
+        }
+        if val.opaque_win32_kmt {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT;
+        }
+        if val.d3d12_fence {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT;
+        }
+        if val.sync_fd {
+            result |= vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT;
+        }
+        result
+    }
+}
+
+/// Error that can be returned when creating, importing or exporting a semaphore.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SemaphoreError {
+    /// Not enough memory available.
+    OomError(OomError),
+
+    /// An extension required for this handle type is missing.
+    MissingExtension {
+        /// Name of the missing extension.
+        name: &'static str,
+    },
+
+    /// The semaphore wasn't created with the handle type in its exportable handle types.
+    HandleTypeNotExportable {
+        /// The requested handle type.
+        handle_type: ExternalSemaphoreHandleType,
+    },
+
+    /// The imported handle isn't valid for this handle type.
+    InvalidExternalHandle,
+}
+
+impl error::Error for SemaphoreError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            SemaphoreError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SemaphoreError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                SemaphoreError::OomError(_) => "not enough memory available",
+                SemaphoreError::MissingExtension { .. } => {
+                    "an extension required for this handle type is missing"
+                }
+                SemaphoreError::HandleTypeNotExportable { .. } => {
+                    "the semaphore can't be exported to this handle type"
+                }
+                SemaphoreError::InvalidExternalHandle => {
+                    "the imported handle isn't valid for this handle type"
+                }
+            }
+        )
+    }
+}
+
+impl From<OomError> for SemaphoreError {
+    #[inline]
+    fn from(err: OomError) -> SemaphoreError {
+        SemaphoreError::OomError(err)
+    }
+}
+
+impl From<Error> for SemaphoreError {
+    #[inline]
+    fn from(err: Error) -> SemaphoreError {
+        match err {
+            e @ Error::OutOfHostMemory | e @ Error::OutOfDeviceMemory => {
+                SemaphoreError::OomError(e.into())
+            }
+            Error::InvalidExternalHandle => SemaphoreError::InvalidExternalHandle,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
 }
 
 unsafe impl DeviceOwned for Semaphore {
@@ -133,7 +598,10 @@ where
 
 #[cfg(test)]
 mod tests {
+    use sync::ExternalSemaphoreHandleType;
+    use sync::ExternalSemaphoreHandleTypes;
     use sync::Semaphore;
+    use sync::SemaphoreError;
     use VulkanObject;
 
     #[test]
@@ -158,4 +626,27 @@ mod tests {
         assert_eq!(device.semaphore_pool().lock().unwrap().len(), 0);
         assert_eq!(sem2.internal_object(), sem1_internal_obj);
     }
+
+    #[test]
+    fn exportable_missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match Semaphore::alloc_exportable(device, ExternalSemaphoreHandleTypes::posix()) {
+            Err(SemaphoreError::MissingExtension {
+                name: "VK_KHR_external_semaphore",
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn handle_types_contains() {
+        let types = ExternalSemaphoreHandleTypes::posix();
+        assert!(types.contains(ExternalSemaphoreHandleType::OpaqueFd));
+        assert!(!types.contains(ExternalSemaphoreHandleType::SyncFd));
+        assert_eq!(
+            ExternalSemaphoreHandleTypes::from(ExternalSemaphoreHandleType::OpaqueFd),
+            types
+        );
+    }
 }
@@ -0,0 +1,368 @@
+// Copyright (c) 2020 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::error;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use check_errors;
+use device::Device;
+use device::DeviceOwned;
+use instance::Version;
+use vk;
+use Error;
+use OomError;
+use Success;
+use VulkanObject;
+
+/// A semaphore whose state is a monotonically increasing 64-bit counter.
+///
+/// Contrary to a regular `Semaphore`, a timeline semaphore can be waited upon and signaled from
+/// both the GPU and the CPU, and any number of submissions can wait for the counter to reach a
+/// given value. This makes it possible to express all the dependencies between the submissions of
+/// a frame with a single semaphore per queue.
+///
+/// Requires the `timeline_semaphore` feature to be enabled on the device, which is provided by
+/// the `khr_timeline_semaphore` extension or by Vulkan 1.2.
+#[derive(Debug)]
+pub struct TimelineSemaphore {
+    semaphore: vk::Semaphore,
+    device: Arc<Device>,
+}
+
+impl TimelineSemaphore {
+    /// Builds a new timeline semaphore whose counter starts at `initial_value`.
+    pub fn new(
+        device: Arc<Device>,
+        initial_value: u64,
+    ) -> Result<TimelineSemaphore, TimelineSemaphoreError> {
+        if !device.enabled_features().timeline_semaphore {
+            return Err(TimelineSemaphoreError::FeatureNotEnabled);
+        }
+
+        let version_1_2 = Version {
+            major: 1,
+            minor: 2,
+            patch: 0,
+        };
+        if !device.loaded_extensions().khr_timeline_semaphore && device.api_version() < version_1_2
+        {
+            return Err(TimelineSemaphoreError::ExtensionNotLoaded);
+        }
+
+        let semaphore = unsafe {
+            let type_infos = vk::SemaphoreTypeCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_TYPE_CREATE_INFO,
+                pNext: ptr::null(),
+                semaphoreType: vk::SEMAPHORE_TYPE_TIMELINE,
+                initialValue: initial_value,
+            };
+
+            let infos = vk::SemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO,
+                pNext: &type_infos as *const _ as *const _,
+                flags: 0, // reserved
+            };
+
+            let vk = device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateSemaphore(
+                device.internal_object(),
+                &infos,
+                ptr::null(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(TimelineSemaphore { semaphore, device })
+    }
+
+    /// Returns the current value of the counter.
+    pub fn value(&self) -> Result<u64, TimelineSemaphoreError> {
+        unsafe {
+            let vk = self.device.pointers();
+            let mut output = MaybeUninit::uninit();
+            if self.device.loaded_extensions().khr_timeline_semaphore {
+                check_errors(vk.GetSemaphoreCounterValueKHR(
+                    self.device.internal_object(),
+                    self.semaphore,
+                    output.as_mut_ptr(),
+                ))?;
+            } else {
+                check_errors(vk.GetSemaphoreCounterValue(
+                    self.device.internal_object(),
+                    self.semaphore,
+                    output.as_mut_ptr(),
+                ))?;
+            }
+            Ok(output.assume_init())
+        }
+    }
+
+    /// Sets the counter to `value` from the CPU.
+    ///
+    /// # Safety
+    ///
+    /// - `value` must be greater than the current value of the counter, and greater than the
+    ///   value of any pending signal operation on this semaphore.
+    /// - The difference between `value` and the current value or the value of any pending wait
+    ///   or signal operation must not exceed `maxTimelineSemaphoreValueDifference`.
+    ///
+    pub unsafe fn signal(&self, value: u64) -> Result<(), TimelineSemaphoreError> {
+        let infos = vk::SemaphoreSignalInfo {
+            sType: vk::STRUCTURE_TYPE_SEMAPHORE_SIGNAL_INFO,
+            pNext: ptr::null(),
+            semaphore: self.semaphore,
+            value,
+        };
+
+        let vk = self.device.pointers();
+        if self.device.loaded_extensions().khr_timeline_semaphore {
+            check_errors(vk.SignalSemaphoreKHR(self.device.internal_object(), &infos))?;
+        } else {
+            check_errors(vk.SignalSemaphore(self.device.internal_object(), &infos))?;
+        }
+        Ok(())
+    }
+
+    /// Waits until the counter reaches at least `value`, or at least until the timeout duration
+    /// has elapsed.
+    ///
+    /// Returns `Err(TimelineSemaphoreError::Timeout)` if the timeout was reached instead. If you
+    /// pass a duration of 0, then the function will return without blocking.
+    pub fn wait(
+        &self,
+        value: u64,
+        timeout: Option<Duration>,
+    ) -> Result<(), TimelineSemaphoreError> {
+        TimelineSemaphore::wait_many(&[(self, value)], true, timeout)
+    }
+
+    /// Waits until the counters of several semaphores reach at least their associated value, or
+    /// at least until the timeout duration has elapsed.
+    ///
+    /// If `wait_all` is true, waits until all the counters have reached their value. Otherwise,
+    /// waits until at least one of them has.
+    ///
+    /// Returns `Err(TimelineSemaphoreError::Timeout)` if the timeout was reached instead. If you
+    /// pass a duration of 0, then the function will return without blocking. Returns
+    /// immediately if `semaphores` is empty.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the semaphores don't all belong to the same device.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use vulkano::sync::TimelineSemaphore;
+    /// # let graphics: TimelineSemaphore = return;
+    /// # let compute: TimelineSemaphore = return;
+    ///
+    /// // Wait until both timelines have finished frame 12.
+    /// TimelineSemaphore::wait_many(&[(&graphics, 12), (&compute, 12)], true, None).unwrap();
+    /// ```
+    pub fn wait_many(
+        semaphores: &[(&TimelineSemaphore, u64)],
+        wait_all: bool,
+        timeout: Option<Duration>,
+    ) -> Result<(), TimelineSemaphoreError> {
+        let device = match semaphores.first() {
+            Some(&(semaphore, _)) => &semaphore.device,
+            None => return Ok(()),
+        };
+
+        let timeout_ns = if let Some(timeout) = timeout {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        } else {
+            u64::max_value()
+        };
+
+        let (handles, values): (SmallVec<[_; 8]>, SmallVec<[_; 8]>) = semaphores
+            .iter()
+            .map(|&(semaphore, value)| {
+                assert_eq!(semaphore.device.internal_object(), device.internal_object());
+                (semaphore.semaphore, value)
+            })
+            .unzip();
+
+        let infos = vk::SemaphoreWaitInfo {
+            sType: vk::STRUCTURE_TYPE_SEMAPHORE_WAIT_INFO,
+            pNext: ptr::null(),
+            flags: if wait_all {
+                0
+            } else {
+                vk::SEMAPHORE_WAIT_ANY_BIT
+            },
+            semaphoreCount: handles.len() as u32,
+            pSemaphores: handles.as_ptr(),
+            pValues: values.as_ptr(),
+        };
+
+        let r = unsafe {
+            let vk = device.pointers();
+            if device.loaded_extensions().khr_timeline_semaphore {
+                check_errors(vk.WaitSemaphoresKHR(device.internal_object(), &infos, timeout_ns))?
+            } else {
+                check_errors(vk.WaitSemaphores(device.internal_object(), &infos, timeout_ns))?
+            }
+        };
+
+        match r {
+            Success::Success => Ok(()),
+            Success::Timeout => Err(TimelineSemaphoreError::Timeout),
+            _ => unreachable!(),
+        }
+    }
+}
+
+unsafe impl DeviceOwned for TimelineSemaphore {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for TimelineSemaphore {
+    type Object = vk::Semaphore;
+
+    const TYPE: vk::ObjectType = vk::OBJECT_TYPE_SEMAPHORE;
+
+    #[inline]
+    fn internal_object(&self) -> vk::Semaphore {
+        self.semaphore
+    }
+}
+
+impl Drop for TimelineSemaphore {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroySemaphore(self.device.internal_object(), self.semaphore, ptr::null());
+        }
+    }
+}
+
+/// Error that can be returned when creating, signaling or waiting on a timeline semaphore.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimelineSemaphoreError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The `timeline_semaphore` feature must be enabled on the device.
+    FeatureNotEnabled,
+
+    /// The `khr_timeline_semaphore` extension must be loaded if the device doesn't support
+    /// Vulkan 1.2.
+    ExtensionNotLoaded,
+
+    /// The specified timeout wasn't long enough.
+    Timeout,
+
+    /// The device has been lost.
+    DeviceLost,
+}
+
+impl error::Error for TimelineSemaphoreError {
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            TimelineSemaphoreError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimelineSemaphoreError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                TimelineSemaphoreError::OomError(_) => "no memory available",
+                TimelineSemaphoreError::FeatureNotEnabled => {
+                    "the `timeline_semaphore` feature is not enabled on the device"
+                }
+                TimelineSemaphoreError::ExtensionNotLoaded => {
+                    "the `khr_timeline_semaphore` extension is not loaded and the device doesn't \
+                     support Vulkan 1.2"
+                }
+                TimelineSemaphoreError::Timeout => "the timeout has been reached",
+                TimelineSemaphoreError::DeviceLost => "the device was lost",
+            }
+        )
+    }
+}
+
+impl From<Error> for TimelineSemaphoreError {
+    #[inline]
+    fn from(err: Error) -> TimelineSemaphoreError {
+        match err {
+            e @ Error::OutOfHostMemory | e @ Error::OutOfDeviceMemory => {
+                TimelineSemaphoreError::OomError(e.into())
+            }
+            Error::DeviceLost => TimelineSemaphoreError::DeviceLost,
+            _ => panic!("Unexpected error value: {}", err as i32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use sync::TimelineSemaphore;
+    use sync::TimelineSemaphoreError;
+
+    #[test]
+    fn feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match TimelineSemaphore::new(device, 0) {
+            Err(TimelineSemaphoreError::FeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wait_many_empty() {
+        TimelineSemaphore::wait_many(&[], true, Some(Duration::from_secs(0))).unwrap();
+        TimelineSemaphore::wait_many(&[], false, None).unwrap();
+    }
+
+    #[test]
+    fn wait_many() {
+        let (device, _) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let a = TimelineSemaphore::new(device.clone(), 5).unwrap();
+        let b = TimelineSemaphore::new(device, 0).unwrap();
+        let zero = Some(Duration::from_secs(0));
+
+        // `a` has reached its value, but `b` hasn't.
+        TimelineSemaphore::wait_many(&[(&a, 5), (&b, 1)], false, zero).unwrap();
+        match TimelineSemaphore::wait_many(&[(&a, 5), (&b, 1)], true, zero) {
+            Err(TimelineSemaphoreError::Timeout) => (),
+            _ => panic!(),
+        }
+
+        unsafe {
+            b.signal(1).unwrap();
+        }
+        TimelineSemaphore::wait_many(&[(&a, 5), (&b, 1)], true, zero).unwrap();
+    }
+}